@@ -79,13 +79,15 @@ mod client {
           <T::ResponseBody as Body>::Error: Into<StdError> + Send,
     {
 
-        pub async fn open_stream(&mut self, id: PeerId, remote_address: &IpAddr) -> Result<(Downstream, Upstream), error::OpenStream> {
+        pub async fn open_stream(&mut self, id: PeerId, remote_address: &IpAddr, version: &str) -> Result<(Downstream, Upstream), error::OpenStream> {
             let (tx, rx) = mpsc::channel(1024);
 
             let response = {
                 let mut request = tonic::Request::new(ReceiverStream::new(rx));
                 request.metadata_mut().insert("id", MetadataValue::from_str(&id.to_string()).unwrap());
                 request.metadata_mut().insert("remote-host", MetadataValue::from_str(&remote_address.to_string()).unwrap());
+                request.metadata_mut().insert("version", MetadataValue::from_str(version)
+                    .map_err(|cause| error::OpenStream { message: format!("Invalid version '{version}': {cause}") })?);
 
                 self.inner
                     .open(request)
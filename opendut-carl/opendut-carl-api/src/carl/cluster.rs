@@ -76,6 +76,53 @@ pub struct ListClusterConfigurationsError {
     pub message: String,
 }
 
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum GetClusterStateError {
+    #[error("A cluster with id <{cluster_id}> could not be found!")]
+    ClusterNotFound {
+        cluster_id: ClusterId
+    },
+    #[error("An internal error occurred determining the state of the cluster with id <{cluster_id}>:\n  {cause}")]
+    Internal {
+        cluster_id: ClusterId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum DiagnoseClusterError {
+    #[error("A cluster with id <{cluster_id}> could not be found!")]
+    ClusterNotFound {
+        cluster_id: ClusterId
+    },
+    #[error("Cluster <{cluster_id}> is not deployed, so its network cannot be diagnosed.")]
+    ClusterNotDeployed {
+        cluster_id: ClusterId
+    },
+    #[error("The network of cluster <{cluster_id}> cannot be diagnosed with the given options:\n  {cause}")]
+    IllegalOptions {
+        cluster_id: ClusterId,
+        cause: String
+    },
+    #[error("An internal error occurred while diagnosing the network of the cluster with id <{cluster_id}>:\n  {cause}")]
+    Internal {
+        cluster_id: ClusterId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ReconcileVpnError {
+    #[error("The VPN is disabled, so there is nothing to reconcile.")]
+    VpnDisabled,
+    #[error("The configured VPN keeps no state of its own, so there is nothing to reconcile.")]
+    NotSupported,
+    #[error("An internal error occurred while reconciling the VPN:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum StoreClusterDeploymentError {
     IllegalClusterState {
@@ -176,6 +223,8 @@ mod client {
     use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
 
     use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+    use opendut_types::cluster::diagnostics::{ClusterNetworkDiagnostics, NetworkDiagnosticsOptions};
+    use opendut_types::vpn::reconciliation::VpnReconciliationReport;
 
     use crate::carl::{ClientError, extract};
     use crate::proto::services::cluster_manager;
@@ -317,6 +366,70 @@ mod client {
             }
         }
 
+        pub async fn get_cluster_state(&mut self, cluster_id: ClusterId) -> Result<ClusterState, ClientError<GetClusterStateError>> {
+
+            let request = tonic::Request::new(cluster_manager::GetClusterStateRequest {
+                cluster_id: Some(cluster_id.into()),
+            });
+
+            let response = self.inner.get_cluster_state(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                cluster_manager::get_cluster_state_response::Reply::Failure(failure) => {
+                    let error = GetClusterStateError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                cluster_manager::get_cluster_state_response::Reply::Success(success) => {
+                    let state = extract!(success.state)?;
+                    Ok(state)
+                }
+            }
+        }
+
+        pub async fn diagnose_cluster(&mut self, cluster_id: ClusterId, options: NetworkDiagnosticsOptions) -> Result<ClusterNetworkDiagnostics, ClientError<DiagnoseClusterError>> {
+
+            let request = tonic::Request::new(cluster_manager::DiagnoseClusterRequest {
+                cluster_id: Some(cluster_id.into()),
+                options: Some(options.into()),
+            });
+
+            let response = self.inner.diagnose_cluster(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                cluster_manager::diagnose_cluster_response::Reply::Failure(failure) => {
+                    let error = DiagnoseClusterError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                cluster_manager::diagnose_cluster_response::Reply::Success(success) => {
+                    let diagnostics = extract!(success.diagnostics)?;
+                    Ok(diagnostics)
+                }
+            }
+        }
+
+        pub async fn reconcile_vpn(&mut self, dry_run: bool) -> Result<VpnReconciliationReport, ClientError<ReconcileVpnError>> {
+
+            let request = tonic::Request::new(cluster_manager::ReconcileVpnRequest {
+                dry_run,
+            });
+
+            let response = self.inner.reconcile_vpn(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                cluster_manager::reconcile_vpn_response::Reply::Failure(failure) => {
+                    let error = ReconcileVpnError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                cluster_manager::reconcile_vpn_response::Reply::Success(success) => {
+                    let report = extract!(success.report)?;
+                    Ok(report)
+                }
+            }
+        }
+
         pub async fn store_cluster_deployment(&mut self, deployment: ClusterDeployment) -> Result<ClusterId, ClientError<StoreClusterDeploymentError>> {
 
             let request = tonic::Request::new(cluster_manager::StoreClusterDeploymentRequest {
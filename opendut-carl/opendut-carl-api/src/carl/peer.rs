@@ -3,6 +3,8 @@ use std::fmt::Formatter;
 #[cfg(any(feature = "client", feature = "wasm-client"))]
 pub use client::*;
 use opendut_types::peer::{PeerId, PeerName};
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::executor::log::ExecutorRunId;
 use opendut_types::peer::state::PeerState;
 use opendut_types::ShortName;
 use opendut_types::topology::DeviceId;
@@ -101,6 +103,99 @@ pub enum GetPeerStateError {
     }
 }
 
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum GetPeerHistoryError {
+    #[error("A peer with id <{peer_id}> could not be found!")]
+    PeerNotFound {
+        peer_id: PeerId
+    },
+    #[error("An internal error occurred retrieving the connection history of a peer with id <{peer_id}>:\n  {cause}")]
+    Internal {
+        peer_id: PeerId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum GetExecutorLogsError {
+    #[error("No logs have been received for executor <{executor_id}>!")]
+    ExecutorNotFound {
+        executor_id: ExecutorId
+    },
+    #[error("No logs have been received for run <{run_id}> of executor <{executor_id}>!")]
+    RunNotFound {
+        executor_id: ExecutorId,
+        run_id: ExecutorRunId,
+    },
+    #[error("An internal error occurred retrieving the logs of executor <{executor_id}>:\n  {cause}")]
+    Internal {
+        executor_id: ExecutorId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ListTestRunsError {
+    #[error("An internal error occurred computing the list of test runs:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum GetTestRunError {
+    #[error("Test run <{run_id}> could not be found!")]
+    TestRunNotFound {
+        run_id: ExecutorRunId
+    },
+    #[error("An internal error occurred retrieving test run <{run_id}>:\n  {cause}")]
+    Internal {
+        run_id: ExecutorRunId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum StartExecutorError {
+    #[error("Executor <{executor_id}> could not be found!")]
+    ExecutorNotFound {
+        executor_id: ExecutorId
+    },
+    #[error("Executor <{executor_id}> cannot be started, because its peer <{peer_id}> is not connected!")]
+    PeerNotConnected {
+        executor_id: ExecutorId,
+        peer_id: PeerId,
+    },
+    #[error("Executor <{executor_id}> cannot be started, because its run <{run_id}> is still in progress! Restart the executor to stop this run.")]
+    RunInProgress {
+        executor_id: ExecutorId,
+        run_id: ExecutorRunId,
+    },
+    #[error("An internal error occurred starting executor <{executor_id}>:\n  {cause}")]
+    Internal {
+        executor_id: ExecutorId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum StopExecutorError {
+    #[error("Executor <{executor_id}> could not be found!")]
+    ExecutorNotFound {
+        executor_id: ExecutorId
+    },
+    #[error("Executor <{executor_id}> cannot be stopped, because its peer <{peer_id}> is not connected!")]
+    PeerNotConnected {
+        executor_id: ExecutorId,
+        peer_id: PeerId,
+    },
+    #[error("An internal error occurred stopping executor <{executor_id}>:\n  {cause}")]
+    Internal {
+        executor_id: ExecutorId,
+        cause: String
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum ListDevicesError {
     #[error("An internal error occurred computing the list of devices:\n  {cause}")]
@@ -122,13 +217,18 @@ mod client {
     use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
     use tracing::error;
     use opendut_types::cleo::CleoSetup;
+    use opendut_types::cluster::ClusterId;
 
     use opendut_types::peer::{PeerDescriptor, PeerId, PeerSetup};
+    use opendut_types::peer::executor::ExecutorId;
+    use opendut_types::peer::executor::log::{ExecutorRunId, ExecutorRunLog};
+    use opendut_types::peer::executor::test_run::{ExecutorRunOverrides, TestRun};
+    use opendut_types::peer::history::{PeerConnectionSession, PeerEnrollment, PeerHistory};
     use opendut_types::peer::state::PeerState;
     use opendut_types::topology::DeviceDescriptor;
 
     use crate::carl::{ClientError, extract};
-    use crate::carl::peer::{DeletePeerDescriptorError, GetPeerDescriptorError, GetPeerStateError, ListDevicesError, ListPeerDescriptorsError, StorePeerDescriptorError};
+    use crate::carl::peer::{DeletePeerDescriptorError, GetExecutorLogsError, GetPeerDescriptorError, GetPeerHistoryError, GetPeerStateError, GetTestRunError, ListDevicesError, ListPeerDescriptorsError, ListTestRunsError, StartExecutorError, StopExecutorError, StorePeerDescriptorError};
     use crate::proto::services::peer_manager;
     use crate::proto::services::peer_manager::peer_manager_client::PeerManagerClient;
 
@@ -276,12 +376,157 @@ mod client {
                 }
             }
         }
-        
-        pub async fn create_peer_setup(&mut self, peer_id: PeerId, user_id: String) -> Result<PeerSetup, CreateSetupError> {
+
+
+        pub async fn get_peer_history(&mut self, peer_id: PeerId) -> Result<PeerHistory, ClientError<GetPeerHistoryError>> {
+
+            let request = tonic::Request::new(peer_manager::GetPeerHistoryRequest {
+                peer_id: Some(peer_id.into()),
+            });
+
+            let response = self.inner.get_peer_history(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::get_peer_history_response::Reply::Failure(failure) => {
+                    let error = GetPeerHistoryError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::get_peer_history_response::Reply::Success(success) => {
+                    let sessions = success.sessions.into_iter()
+                        .map(PeerConnectionSession::try_from)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let enrollments = success.enrollments.into_iter()
+                        .map(PeerEnrollment::try_from)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(PeerHistory { sessions, enrollments })
+                }
+            }
+        }
+
+        /// Retrieves the output of a run of an executor, starting at the given sequence number.
+        /// If no run is specified, the latest run is returned.
+        pub async fn get_executor_logs(&mut self, executor_id: ExecutorId, run_id: Option<ExecutorRunId>, from_sequence_number: u64) -> Result<ExecutorRunLog, ClientError<GetExecutorLogsError>> {
+
+            let request = tonic::Request::new(peer_manager::GetExecutorLogsRequest {
+                executor_id: Some(executor_id.into()),
+                run_id: run_id.map(Into::into),
+                from_sequence_number,
+            });
+
+            let response = self.inner.get_executor_logs(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::get_executor_logs_response::Reply::Failure(failure) => {
+                    let error = GetExecutorLogsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::get_executor_logs_response::Reply::Success(success) => {
+                    let log: ExecutorRunLog = extract!(success.log)?;
+                    Ok(log)
+                }
+            }
+        }
+
+        /// Lists the test runs, optionally only those of the given cluster and/or executor.
+        pub async fn list_test_runs(&mut self, cluster_id: Option<ClusterId>, executor_id: Option<ExecutorId>) -> Result<Vec<TestRun>, ClientError<ListTestRunsError>> {
+
+            let request = tonic::Request::new(peer_manager::ListTestRunsRequest {
+                cluster_id: cluster_id.map(Into::into),
+                executor_id: executor_id.map(Into::into),
+            });
+
+            let response = self.inner.list_test_runs(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::list_test_runs_response::Reply::Failure(failure) => {
+                    let error = ListTestRunsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::list_test_runs_response::Reply::Success(success) => {
+                    Ok(success.test_runs.into_iter()
+                        .map(TestRun::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        pub async fn get_test_run(&mut self, run_id: ExecutorRunId) -> Result<TestRun, ClientError<GetTestRunError>> {
+
+            let request = tonic::Request::new(peer_manager::GetTestRunRequest {
+                run_id: Some(run_id.into()),
+            });
+
+            let response = self.inner.get_test_run(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::get_test_run_response::Reply::Failure(failure) => {
+                    let error = GetTestRunError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::get_test_run_response::Reply::Success(success) => {
+                    let test_run: TestRun = extract!(success.test_run)?;
+                    Ok(test_run)
+                }
+            }
+        }
+
+        /// Starts a new run of the executor on its peer and returns it.
+        /// With `restart`, a run which is still in progress is stopped first. Otherwise, starting fails in this case.
+        pub async fn start_executor(&mut self, executor_id: ExecutorId, overrides: ExecutorRunOverrides, restart: bool) -> Result<TestRun, ClientError<StartExecutorError>> {
+
+            let request = tonic::Request::new(peer_manager::StartExecutorRequest {
+                executor_id: Some(executor_id.into()),
+                overrides: Some(overrides.into()),
+                restart,
+            });
+
+            let response = self.inner.start_executor(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::start_executor_response::Reply::Failure(failure) => {
+                    let error = StartExecutorError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::start_executor_response::Reply::Success(success) => {
+                    let test_run: TestRun = extract!(success.test_run)?;
+                    Ok(test_run)
+                }
+            }
+        }
+
+        pub async fn stop_executor(&mut self, executor_id: ExecutorId) -> Result<(), ClientError<StopExecutorError>> {
+
+            let request = tonic::Request::new(peer_manager::StopExecutorRequest {
+                executor_id: Some(executor_id.into()),
+            });
+
+            let response = self.inner.stop_executor(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::stop_executor_response::Reply::Failure(failure) => {
+                    let error = StopExecutorError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::stop_executor_response::Reply::Success(_) => {
+                    Ok(())
+                }
+            }
+        }
+
+        /// Creates a PeerSetup for a peer. When re-enrolling, the VPN and OIDC credentials of previous PeerSetups are revoked.
+        pub async fn create_peer_setup(&mut self, peer_id: PeerId, user_id: String, reenroll: bool) -> Result<PeerSetup, CreateSetupError> {
             let request = tonic::Request::new(
                 peer_manager::GeneratePeerSetupRequest {
                     peer: Some(peer_id.into()),
                     user_id,
+                    reenroll,
                 }
             );
 
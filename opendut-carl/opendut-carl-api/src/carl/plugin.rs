@@ -0,0 +1,252 @@
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+use opendut_types::peer::PeerId;
+use opendut_types::plugin::PluginId;
+
+/// Maximum size of the messages of the plugin registry, which limits the size of the WebAssembly components.
+pub const MAX_PLUGIN_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum StorePluginError {
+    #[error("Plugins cannot be uploaded, because no plugin signing key is configured in CARL!")]
+    SigningDisabled,
+    #[error("Plugin '{name}' in version '{version}' already exists as <{other_id}>! Upload the plugin with a new version instead.")]
+    AlreadyExists {
+        name: String,
+        version: String,
+        other_id: PluginId,
+    },
+    #[error("Plugin '{name}' in version '{version}' is not valid:\n  {cause}")]
+    IllegalPlugin {
+        name: String,
+        version: String,
+        cause: String,
+    },
+    #[error("Plugin '{name}' in version '{version}' could not be stored, due to internal errors:\n  {cause}")]
+    Internal {
+        name: String,
+        version: String,
+        cause: String,
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum DeletePluginError {
+    #[error("Plugin <{plugin_id}> could not be found!")]
+    PluginNotFound {
+        plugin_id: PluginId,
+    },
+    #[error("Plugin <{plugin_id}> could not be deleted, due to internal errors:\n  {cause}")]
+    Internal {
+        plugin_id: PluginId,
+        cause: String,
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ListPluginsError {
+    #[error("An internal error occurred computing the list of plugins:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum DownloadPluginError {
+    #[error("Plugin <{plugin_id}> could not be found!")]
+    PluginNotFound {
+        plugin_id: PluginId,
+    },
+    #[error("An internal error occurred retrieving plugin <{plugin_id}>:\n  {cause}")]
+    Internal {
+        plugin_id: PluginId,
+        cause: String,
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum AssignPluginsError {
+    #[error("Plugins cannot be assigned to peer <{peer_id}>, because the peer could not be found!")]
+    PeerNotFound {
+        peer_id: PeerId,
+    },
+    #[error("Plugin <{plugin_id}> cannot be assigned to peer <{peer_id}>, because the plugin could not be found!")]
+    PluginNotFound {
+        peer_id: PeerId,
+        plugin_id: PluginId,
+    },
+    #[error("Plugins could not be assigned to peer <{peer_id}>, due to internal errors:\n  {cause}")]
+    Internal {
+        peer_id: PeerId,
+        cause: String,
+    }
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use opendut_types::peer::PeerId;
+    use opendut_types::plugin::{PluginArtifact, PluginDescriptor, PluginId, PluginKind};
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::plugin::{AssignPluginsError, DeletePluginError, DownloadPluginError, ListPluginsError, MAX_PLUGIN_MESSAGE_SIZE, StorePluginError};
+    use crate::proto::services::plugin_registry;
+    use crate::proto::services::plugin_registry::plugin_registry_client::PluginRegistryClient;
+
+    #[derive(Clone, Debug)]
+    pub struct PluginRegistry<T> {
+        inner: PluginRegistryClient<T>,
+    }
+
+    impl<T> PluginRegistry<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: PluginRegistryClient<T>) -> PluginRegistry<T> {
+            PluginRegistry {
+                inner: inner
+                    .max_decoding_message_size(MAX_PLUGIN_MESSAGE_SIZE)
+                    .max_encoding_message_size(MAX_PLUGIN_MESSAGE_SIZE)
+            }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> PluginRegistry<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = PluginRegistryClient::new(InterceptedService::new(inner, interceptor));
+            PluginRegistry::new(inner_client)
+        }
+
+        /// Uploads a plugin, which CARL signs and returns the descriptor of.
+        pub async fn store_plugin(&mut self, name: String, version: String, kind: PluginKind, binary: Vec<u8>, manifest: Option<String>) -> Result<PluginDescriptor, ClientError<StorePluginError>> {
+
+            let request = tonic::Request::new(plugin_registry::StorePluginRequest {
+                name,
+                version,
+                kind: Some(kind.into()),
+                binary,
+                manifest,
+            });
+
+            let response = self.inner.store_plugin(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                plugin_registry::store_plugin_response::Reply::Failure(failure) => {
+                    let error = StorePluginError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                plugin_registry::store_plugin_response::Reply::Success(success) => {
+                    let plugin: PluginDescriptor = extract!(success.plugin)?;
+                    Ok(plugin)
+                }
+            }
+        }
+
+        pub async fn delete_plugin(&mut self, plugin_id: PluginId) -> Result<PluginDescriptor, ClientError<DeletePluginError>> {
+
+            let request = tonic::Request::new(plugin_registry::DeletePluginRequest {
+                plugin_id: Some(plugin_id.into()),
+            });
+
+            let response = self.inner.delete_plugin(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                plugin_registry::delete_plugin_response::Reply::Failure(failure) => {
+                    let error = DeletePluginError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                plugin_registry::delete_plugin_response::Reply::Success(success) => {
+                    let plugin: PluginDescriptor = extract!(success.plugin)?;
+                    Ok(plugin)
+                }
+            }
+        }
+
+        /// Lists all plugins or, if a peer is given, the plugins assigned to this peer in the order in which they are run.
+        pub async fn list_plugins(&mut self, peer_id: Option<PeerId>) -> Result<Vec<PluginDescriptor>, ClientError<ListPluginsError>> {
+
+            let request = tonic::Request::new(plugin_registry::ListPluginsRequest {
+                peer_id: peer_id.map(Into::into),
+            });
+
+            let response = self.inner.list_plugins(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                plugin_registry::list_plugins_response::Reply::Failure(failure) => {
+                    let error = ListPluginsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                plugin_registry::list_plugins_response::Reply::Success(success) => {
+                    Ok(success.plugins.into_iter()
+                        .map(PluginDescriptor::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        pub async fn download_plugin(&mut self, plugin_id: PluginId) -> Result<(PluginDescriptor, PluginArtifact), ClientError<DownloadPluginError>> {
+
+            let request = tonic::Request::new(plugin_registry::DownloadPluginRequest {
+                plugin_id: Some(plugin_id.into()),
+            });
+
+            let response = self.inner.download_plugin(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                plugin_registry::download_plugin_response::Reply::Failure(failure) => {
+                    let error = DownloadPluginError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                plugin_registry::download_plugin_response::Reply::Success(success) => {
+                    let plugin: PluginDescriptor = extract!(success.plugin)?;
+                    let artifact = PluginArtifact { id: plugin.id, binary: success.binary };
+                    Ok((plugin, artifact))
+                }
+            }
+        }
+
+        /// Replaces the plugins assigned to the peer. The plugins are run in the given order.
+        pub async fn assign_plugins(&mut self, peer_id: PeerId, plugin_ids: Vec<PluginId>) -> Result<(), ClientError<AssignPluginsError>> {
+
+            let request = tonic::Request::new(plugin_registry::AssignPluginsRequest {
+                peer_id: Some(peer_id.into()),
+                plugin_ids: plugin_ids.into_iter().map(Into::into).collect(),
+            });
+
+            let response = self.inner.assign_plugins(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                plugin_registry::assign_plugins_response::Reply::Failure(failure) => {
+                    let error = AssignPluginsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                plugin_registry::assign_plugins_response::Reply::Success(_) => {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
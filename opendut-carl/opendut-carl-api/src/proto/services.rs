@@ -4,7 +4,7 @@ pub mod cluster_manager {
     use opendut_types::proto;
     use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
 
-    use crate::carl::cluster::{CreateClusterConfigurationError, DeleteClusterConfigurationError, DeleteClusterDeploymentError, StoreClusterDeploymentError};
+    use crate::carl::cluster::{CreateClusterConfigurationError, DeleteClusterConfigurationError, DeleteClusterDeploymentError, DiagnoseClusterError, GetClusterStateError, ReconcileVpnError, StoreClusterDeploymentError};
 
     tonic::include_proto!("opendut.carl.services.cluster_manager");
 
@@ -381,6 +381,203 @@ pub mod cluster_manager {
         }
     }
 
+    impl From<GetClusterStateError> for GetClusterStateFailure {
+        fn from(error: GetClusterStateError) -> Self {
+            let proto_error = match error {
+                GetClusterStateError::ClusterNotFound { cluster_id } => {
+                    get_cluster_state_failure::Error::ClusterNotFound(GetClusterStateFailureClusterNotFound {
+                        cluster_id: Some(cluster_id.into()),
+                    })
+                }
+                GetClusterStateError::Internal { cluster_id, cause } => {
+                    get_cluster_state_failure::Error::Internal(GetClusterStateFailureInternal {
+                        cluster_id: Some(cluster_id.into()),
+                        cause
+                    })
+                }
+            };
+            GetClusterStateFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<GetClusterStateFailureClusterNotFound> for GetClusterStateError {
+        type Error = ConversionError;
+        fn try_from(failure: GetClusterStateFailureClusterNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetClusterStateFailureClusterNotFound, GetClusterStateError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(GetClusterStateError::ClusterNotFound { cluster_id })
+        }
+    }
+
+    impl TryFrom<GetClusterStateFailureInternal> for GetClusterStateError {
+        type Error = ConversionError;
+        fn try_from(failure: GetClusterStateFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetClusterStateFailureInternal, GetClusterStateError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(GetClusterStateError::Internal { cluster_id, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<GetClusterStateFailure> for GetClusterStateError {
+        type Error = ConversionError;
+        fn try_from(failure: GetClusterStateFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetClusterStateFailure, GetClusterStateError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                get_cluster_state_failure::Error::ClusterNotFound(error) => {
+                    error.try_into()?
+                }
+                get_cluster_state_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<DiagnoseClusterError> for DiagnoseClusterFailure {
+        fn from(error: DiagnoseClusterError) -> Self {
+            let proto_error = match error {
+                DiagnoseClusterError::ClusterNotFound { cluster_id } => {
+                    diagnose_cluster_failure::Error::ClusterNotFound(DiagnoseClusterFailureClusterNotFound {
+                        cluster_id: Some(cluster_id.into()),
+                    })
+                }
+                DiagnoseClusterError::ClusterNotDeployed { cluster_id } => {
+                    diagnose_cluster_failure::Error::ClusterNotDeployed(DiagnoseClusterFailureClusterNotDeployed {
+                        cluster_id: Some(cluster_id.into()),
+                    })
+                }
+                DiagnoseClusterError::IllegalOptions { cluster_id, cause } => {
+                    diagnose_cluster_failure::Error::IllegalOptions(DiagnoseClusterFailureIllegalOptions {
+                        cluster_id: Some(cluster_id.into()),
+                        cause
+                    })
+                }
+                DiagnoseClusterError::Internal { cluster_id, cause } => {
+                    diagnose_cluster_failure::Error::Internal(DiagnoseClusterFailureInternal {
+                        cluster_id: Some(cluster_id.into()),
+                        cause
+                    })
+                }
+            };
+            DiagnoseClusterFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DiagnoseClusterFailureClusterNotFound> for DiagnoseClusterError {
+        type Error = ConversionError;
+        fn try_from(failure: DiagnoseClusterFailureClusterNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DiagnoseClusterFailureClusterNotFound, DiagnoseClusterError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(DiagnoseClusterError::ClusterNotFound { cluster_id })
+        }
+    }
+
+    impl TryFrom<DiagnoseClusterFailureClusterNotDeployed> for DiagnoseClusterError {
+        type Error = ConversionError;
+        fn try_from(failure: DiagnoseClusterFailureClusterNotDeployed) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DiagnoseClusterFailureClusterNotDeployed, DiagnoseClusterError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(DiagnoseClusterError::ClusterNotDeployed { cluster_id })
+        }
+    }
+
+    impl TryFrom<DiagnoseClusterFailureIllegalOptions> for DiagnoseClusterError {
+        type Error = ConversionError;
+        fn try_from(failure: DiagnoseClusterFailureIllegalOptions) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DiagnoseClusterFailureIllegalOptions, DiagnoseClusterError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(DiagnoseClusterError::IllegalOptions { cluster_id, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<DiagnoseClusterFailureInternal> for DiagnoseClusterError {
+        type Error = ConversionError;
+        fn try_from(failure: DiagnoseClusterFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DiagnoseClusterFailureInternal, DiagnoseClusterError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(DiagnoseClusterError::Internal { cluster_id, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<DiagnoseClusterFailure> for DiagnoseClusterError {
+        type Error = ConversionError;
+        fn try_from(failure: DiagnoseClusterFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DiagnoseClusterFailure, DiagnoseClusterError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                diagnose_cluster_failure::Error::ClusterNotFound(error) => {
+                    error.try_into()?
+                }
+                diagnose_cluster_failure::Error::ClusterNotDeployed(error) => {
+                    error.try_into()?
+                }
+                diagnose_cluster_failure::Error::IllegalOptions(error) => {
+                    error.try_into()?
+                }
+                diagnose_cluster_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<ReconcileVpnError> for ReconcileVpnFailure {
+        fn from(error: ReconcileVpnError) -> Self {
+            let proto_error = match error {
+                ReconcileVpnError::VpnDisabled => {
+                    reconcile_vpn_failure::Error::VpnDisabled(ReconcileVpnFailureVpnDisabled {})
+                }
+                ReconcileVpnError::NotSupported => {
+                    reconcile_vpn_failure::Error::NotSupported(ReconcileVpnFailureNotSupported {})
+                }
+                ReconcileVpnError::Internal { cause } => {
+                    reconcile_vpn_failure::Error::Internal(ReconcileVpnFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ReconcileVpnFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ReconcileVpnFailure> for ReconcileVpnError {
+        type Error = ConversionError;
+        fn try_from(failure: ReconcileVpnFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ReconcileVpnFailure, ReconcileVpnError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                reconcile_vpn_failure::Error::VpnDisabled(_) => ReconcileVpnError::VpnDisabled,
+                reconcile_vpn_failure::Error::NotSupported(_) => ReconcileVpnError::NotSupported,
+                reconcile_vpn_failure::Error::Internal(ReconcileVpnFailureInternal { cause }) => ReconcileVpnError::Internal { cause },
+            };
+            Ok(error)
+        }
+    }
+
 }
 
 pub mod metadata_provider {
@@ -395,7 +592,10 @@ pub mod peer_manager {
     use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
     use opendut_types::topology::DeviceId;
 
-    use crate::carl::peer::{StorePeerDescriptorError, DeletePeerDescriptorError, GetPeerDescriptorError, ListPeerDescriptorsError, GetPeerStateError};
+    use opendut_types::peer::executor::ExecutorId;
+    use opendut_types::peer::executor::log::ExecutorRunId;
+
+    use crate::carl::peer::{StorePeerDescriptorError, DeletePeerDescriptorError, GetPeerDescriptorError, ListPeerDescriptorsError, GetPeerStateError, GetPeerHistoryError, GetExecutorLogsError, ListTestRunsError, GetTestRunError, StartExecutorError, StopExecutorError};
 
     tonic::include_proto!("opendut.carl.services.peer_manager");
 
@@ -797,8 +997,687 @@ pub mod peer_manager {
             Ok(error)
         }
     }
+
+    impl From<GetPeerHistoryError> for GetPeerHistoryFailure {
+        fn from(error: GetPeerHistoryError) -> Self {
+            let proto_error = match error {
+                GetPeerHistoryError::PeerNotFound { peer_id } => {
+                    get_peer_history_failure::Error::PeerNotFound(GetPeerHistoryFailurePeerNotFound {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                GetPeerHistoryError::Internal { peer_id, cause } => {
+                    get_peer_history_failure::Error::Internal(GetPeerHistoryFailureInternal {
+                        peer_id: Some(peer_id.into()),
+                        cause
+                    })
+                }
+            };
+            GetPeerHistoryFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<GetPeerHistoryFailurePeerNotFound> for GetPeerHistoryError {
+        type Error = ConversionError;
+        fn try_from(failure: GetPeerHistoryFailurePeerNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetPeerHistoryFailurePeerNotFound, GetPeerHistoryError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            Ok(GetPeerHistoryError::PeerNotFound { peer_id })
+        }
+    }
+
+    impl TryFrom<GetPeerHistoryFailureInternal> for GetPeerHistoryError {
+        type Error = ConversionError;
+        fn try_from(failure: GetPeerHistoryFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetPeerHistoryFailureInternal, GetPeerHistoryError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            Ok(GetPeerHistoryError::Internal { peer_id, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<GetPeerHistoryFailure> for GetPeerHistoryError {
+        type Error = ConversionError;
+        fn try_from(failure: GetPeerHistoryFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetPeerHistoryFailure, GetPeerHistoryError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                get_peer_history_failure::Error::PeerNotFound(error) => {
+                    error.try_into()?
+                }
+                get_peer_history_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<GetExecutorLogsError> for GetExecutorLogsFailure {
+        fn from(error: GetExecutorLogsError) -> Self {
+            let proto_error = match error {
+                GetExecutorLogsError::ExecutorNotFound { executor_id } => {
+                    get_executor_logs_failure::Error::ExecutorNotFound(GetExecutorLogsFailureExecutorNotFound {
+                        executor_id: Some(executor_id.into()),
+                    })
+                }
+                GetExecutorLogsError::RunNotFound { executor_id, run_id } => {
+                    get_executor_logs_failure::Error::RunNotFound(GetExecutorLogsFailureRunNotFound {
+                        executor_id: Some(executor_id.into()),
+                        run_id: Some(run_id.into()),
+                    })
+                }
+                GetExecutorLogsError::Internal { executor_id, cause } => {
+                    get_executor_logs_failure::Error::Internal(GetExecutorLogsFailureInternal {
+                        executor_id: Some(executor_id.into()),
+                        cause
+                    })
+                }
+            };
+            GetExecutorLogsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<GetExecutorLogsFailureExecutorNotFound> for GetExecutorLogsError {
+        type Error = ConversionError;
+        fn try_from(failure: GetExecutorLogsFailureExecutorNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetExecutorLogsFailureExecutorNotFound, GetExecutorLogsError>;
+            let executor_id: ExecutorId = failure.executor_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("executor_id"))?
+                .try_into()?;
+            Ok(GetExecutorLogsError::ExecutorNotFound { executor_id })
+        }
+    }
+
+    impl TryFrom<GetExecutorLogsFailureRunNotFound> for GetExecutorLogsError {
+        type Error = ConversionError;
+        fn try_from(failure: GetExecutorLogsFailureRunNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetExecutorLogsFailureRunNotFound, GetExecutorLogsError>;
+            let executor_id: ExecutorId = failure.executor_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("executor_id"))?
+                .try_into()?;
+            let run_id: ExecutorRunId = failure.run_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("run_id"))?
+                .try_into()?;
+            Ok(GetExecutorLogsError::RunNotFound { executor_id, run_id })
+        }
+    }
+
+    impl TryFrom<GetExecutorLogsFailureInternal> for GetExecutorLogsError {
+        type Error = ConversionError;
+        fn try_from(failure: GetExecutorLogsFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetExecutorLogsFailureInternal, GetExecutorLogsError>;
+            let executor_id: ExecutorId = failure.executor_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("executor_id"))?
+                .try_into()?;
+            Ok(GetExecutorLogsError::Internal { executor_id, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<GetExecutorLogsFailure> for GetExecutorLogsError {
+        type Error = ConversionError;
+        fn try_from(failure: GetExecutorLogsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetExecutorLogsFailure, GetExecutorLogsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                get_executor_logs_failure::Error::ExecutorNotFound(error) => {
+                    error.try_into()?
+                }
+                get_executor_logs_failure::Error::RunNotFound(error) => {
+                    error.try_into()?
+                }
+                get_executor_logs_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<ListTestRunsError> for ListTestRunsFailure {
+        fn from(error: ListTestRunsError) -> Self {
+            let proto_error = match error {
+                ListTestRunsError::Internal { cause } => {
+                    list_test_runs_failure::Error::Internal(ListTestRunsFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListTestRunsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListTestRunsFailure> for ListTestRunsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListTestRunsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListTestRunsFailure, ListTestRunsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_test_runs_failure::Error::Internal(error) => {
+                    ListTestRunsError::Internal { cause: error.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<GetTestRunError> for GetTestRunFailure {
+        fn from(error: GetTestRunError) -> Self {
+            let proto_error = match error {
+                GetTestRunError::TestRunNotFound { run_id } => {
+                    get_test_run_failure::Error::TestRunNotFound(GetTestRunFailureTestRunNotFound {
+                        run_id: Some(run_id.into()),
+                    })
+                }
+                GetTestRunError::Internal { run_id, cause } => {
+                    get_test_run_failure::Error::Internal(GetTestRunFailureInternal {
+                        run_id: Some(run_id.into()),
+                        cause
+                    })
+                }
+            };
+            GetTestRunFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<GetTestRunFailureTestRunNotFound> for GetTestRunError {
+        type Error = ConversionError;
+        fn try_from(failure: GetTestRunFailureTestRunNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetTestRunFailureTestRunNotFound, GetTestRunError>;
+            let run_id: ExecutorRunId = failure.run_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("run_id"))?
+                .try_into()?;
+            Ok(GetTestRunError::TestRunNotFound { run_id })
+        }
+    }
+
+    impl TryFrom<GetTestRunFailureInternal> for GetTestRunError {
+        type Error = ConversionError;
+        fn try_from(failure: GetTestRunFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetTestRunFailureInternal, GetTestRunError>;
+            let run_id: ExecutorRunId = failure.run_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("run_id"))?
+                .try_into()?;
+            Ok(GetTestRunError::Internal { run_id, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<GetTestRunFailure> for GetTestRunError {
+        type Error = ConversionError;
+        fn try_from(failure: GetTestRunFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<GetTestRunFailure, GetTestRunError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                get_test_run_failure::Error::TestRunNotFound(error) => {
+                    error.try_into()?
+                }
+                get_test_run_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<StartExecutorError> for StartExecutorFailure {
+        fn from(error: StartExecutorError) -> Self {
+            let proto_error = match error {
+                StartExecutorError::ExecutorNotFound { executor_id } => {
+                    start_executor_failure::Error::ExecutorNotFound(StartExecutorFailureExecutorNotFound {
+                        executor_id: Some(executor_id.into()),
+                    })
+                }
+                StartExecutorError::PeerNotConnected { executor_id, peer_id } => {
+                    start_executor_failure::Error::PeerNotConnected(StartExecutorFailurePeerNotConnected {
+                        executor_id: Some(executor_id.into()),
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                StartExecutorError::RunInProgress { executor_id, run_id } => {
+                    start_executor_failure::Error::RunInProgress(StartExecutorFailureRunInProgress {
+                        executor_id: Some(executor_id.into()),
+                        run_id: Some(run_id.into()),
+                    })
+                }
+                StartExecutorError::Internal { executor_id, cause } => {
+                    start_executor_failure::Error::Internal(StartExecutorFailureInternal {
+                        executor_id: Some(executor_id.into()),
+                        cause,
+                    })
+                }
+            };
+            StartExecutorFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<StartExecutorFailureExecutorNotFound> for StartExecutorError {
+        type Error = ConversionError;
+        fn try_from(failure: StartExecutorFailureExecutorNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StartExecutorFailureExecutorNotFound, StartExecutorError>;
+            let executor_id: ExecutorId = failure.executor_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("executor_id"))?
+                .try_into()?;
+            Ok(StartExecutorError::ExecutorNotFound { executor_id })
+        }
+    }
+
+    impl TryFrom<StartExecutorFailurePeerNotConnected> for StartExecutorError {
+        type Error = ConversionError;
+        fn try_from(failure: StartExecutorFailurePeerNotConnected) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StartExecutorFailurePeerNotConnected, StartExecutorError>;
+            let executor_id: ExecutorId = failure.executor_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("executor_id"))?
+                .try_into()?;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            Ok(StartExecutorError::PeerNotConnected { executor_id, peer_id })
+        }
+    }
+
+    impl TryFrom<StartExecutorFailureRunInProgress> for StartExecutorError {
+        type Error = ConversionError;
+        fn try_from(failure: StartExecutorFailureRunInProgress) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StartExecutorFailureRunInProgress, StartExecutorError>;
+            let executor_id: ExecutorId = failure.executor_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("executor_id"))?
+                .try_into()?;
+            let run_id: ExecutorRunId = failure.run_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("run_id"))?
+                .try_into()?;
+            Ok(StartExecutorError::RunInProgress { executor_id, run_id })
+        }
+    }
+
+    impl TryFrom<StartExecutorFailureInternal> for StartExecutorError {
+        type Error = ConversionError;
+        fn try_from(failure: StartExecutorFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StartExecutorFailureInternal, StartExecutorError>;
+            let executor_id: ExecutorId = failure.executor_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("executor_id"))?
+                .try_into()?;
+            Ok(StartExecutorError::Internal { executor_id, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<StartExecutorFailure> for StartExecutorError {
+        type Error = ConversionError;
+        fn try_from(failure: StartExecutorFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StartExecutorFailure, StartExecutorError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                start_executor_failure::Error::ExecutorNotFound(error) => {
+                    error.try_into()?
+                }
+                start_executor_failure::Error::PeerNotConnected(error) => {
+                    error.try_into()?
+                }
+                start_executor_failure::Error::RunInProgress(error) => {
+                    error.try_into()?
+                }
+                start_executor_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<StopExecutorError> for StopExecutorFailure {
+        fn from(error: StopExecutorError) -> Self {
+            let proto_error = match error {
+                StopExecutorError::ExecutorNotFound { executor_id } => {
+                    stop_executor_failure::Error::ExecutorNotFound(StopExecutorFailureExecutorNotFound {
+                        executor_id: Some(executor_id.into()),
+                    })
+                }
+                StopExecutorError::PeerNotConnected { executor_id, peer_id } => {
+                    stop_executor_failure::Error::PeerNotConnected(StopExecutorFailurePeerNotConnected {
+                        executor_id: Some(executor_id.into()),
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                StopExecutorError::Internal { executor_id, cause } => {
+                    stop_executor_failure::Error::Internal(StopExecutorFailureInternal {
+                        executor_id: Some(executor_id.into()),
+                        cause,
+                    })
+                }
+            };
+            StopExecutorFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<StopExecutorFailureExecutorNotFound> for StopExecutorError {
+        type Error = ConversionError;
+        fn try_from(failure: StopExecutorFailureExecutorNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StopExecutorFailureExecutorNotFound, StopExecutorError>;
+            let executor_id: ExecutorId = failure.executor_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("executor_id"))?
+                .try_into()?;
+            Ok(StopExecutorError::ExecutorNotFound { executor_id })
+        }
+    }
+
+    impl TryFrom<StopExecutorFailurePeerNotConnected> for StopExecutorError {
+        type Error = ConversionError;
+        fn try_from(failure: StopExecutorFailurePeerNotConnected) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StopExecutorFailurePeerNotConnected, StopExecutorError>;
+            let executor_id: ExecutorId = failure.executor_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("executor_id"))?
+                .try_into()?;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            Ok(StopExecutorError::PeerNotConnected { executor_id, peer_id })
+        }
+    }
+
+    impl TryFrom<StopExecutorFailureInternal> for StopExecutorError {
+        type Error = ConversionError;
+        fn try_from(failure: StopExecutorFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StopExecutorFailureInternal, StopExecutorError>;
+            let executor_id: ExecutorId = failure.executor_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("executor_id"))?
+                .try_into()?;
+            Ok(StopExecutorError::Internal { executor_id, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<StopExecutorFailure> for StopExecutorError {
+        type Error = ConversionError;
+        fn try_from(failure: StopExecutorFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StopExecutorFailure, StopExecutorError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                stop_executor_failure::Error::ExecutorNotFound(error) => {
+                    error.try_into()?
+                }
+                stop_executor_failure::Error::PeerNotConnected(error) => {
+                    error.try_into()?
+                }
+                stop_executor_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
 }
 
 pub mod peer_messaging_broker {
     tonic::include_proto!("opendut.carl.services.peer_messaging_broker");
 }
+
+pub mod plugin_registry {
+    use opendut_types::peer::PeerId;
+    use opendut_types::plugin::PluginId;
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+
+    use crate::carl::plugin::{AssignPluginsError, DeletePluginError, DownloadPluginError, ListPluginsError, StorePluginError};
+
+    tonic::include_proto!("opendut.carl.services.plugin_registry");
+
+    impl From<StorePluginError> for StorePluginFailure {
+        fn from(error: StorePluginError) -> Self {
+            let proto_error = match error {
+                StorePluginError::SigningDisabled => {
+                    store_plugin_failure::Error::SigningDisabled(StorePluginFailureSigningDisabled {})
+                }
+                StorePluginError::AlreadyExists { name, version, other_id } => {
+                    store_plugin_failure::Error::AlreadyExists(StorePluginFailureAlreadyExists {
+                        name,
+                        version,
+                        other_id: Some(other_id.into()),
+                    })
+                }
+                StorePluginError::IllegalPlugin { name, version, cause } => {
+                    store_plugin_failure::Error::IllegalPlugin(StorePluginFailureIllegalPlugin {
+                        name,
+                        version,
+                        cause
+                    })
+                }
+                StorePluginError::Internal { name, version, cause } => {
+                    store_plugin_failure::Error::Internal(StorePluginFailureInternal {
+                        name,
+                        version,
+                        cause
+                    })
+                }
+            };
+            StorePluginFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<StorePluginFailure> for StorePluginError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePluginFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePluginFailure, StorePluginError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                store_plugin_failure::Error::SigningDisabled(_) => {
+                    StorePluginError::SigningDisabled
+                }
+                store_plugin_failure::Error::AlreadyExists(StorePluginFailureAlreadyExists { name, version, other_id }) => {
+                    type ErrorBuilder = ConversionErrorBuilder<StorePluginFailureAlreadyExists, StorePluginError>;
+                    let other_id: PluginId = other_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("other_id"))?
+                        .try_into()?;
+                    StorePluginError::AlreadyExists { name, version, other_id }
+                }
+                store_plugin_failure::Error::IllegalPlugin(StorePluginFailureIllegalPlugin { name, version, cause }) => {
+                    StorePluginError::IllegalPlugin { name, version, cause }
+                }
+                store_plugin_failure::Error::Internal(StorePluginFailureInternal { name, version, cause }) => {
+                    StorePluginError::Internal { name, version, cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<DeletePluginError> for DeletePluginFailure {
+        fn from(error: DeletePluginError) -> Self {
+            let proto_error = match error {
+                DeletePluginError::PluginNotFound { plugin_id } => {
+                    delete_plugin_failure::Error::PluginNotFound(DeletePluginFailurePluginNotFound {
+                        plugin_id: Some(plugin_id.into())
+                    })
+                }
+                DeletePluginError::Internal { plugin_id, cause } => {
+                    delete_plugin_failure::Error::Internal(DeletePluginFailureInternal {
+                        plugin_id: Some(plugin_id.into()),
+                        cause
+                    })
+                }
+            };
+            DeletePluginFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeletePluginFailure> for DeletePluginError {
+        type Error = ConversionError;
+        fn try_from(failure: DeletePluginFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeletePluginFailure, DeletePluginError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_plugin_failure::Error::PluginNotFound(DeletePluginFailurePluginNotFound { plugin_id }) => {
+                    let plugin_id: PluginId = plugin_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("plugin_id"))?
+                        .try_into()?;
+                    DeletePluginError::PluginNotFound { plugin_id }
+                }
+                delete_plugin_failure::Error::Internal(DeletePluginFailureInternal { plugin_id, cause }) => {
+                    let plugin_id: PluginId = plugin_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("plugin_id"))?
+                        .try_into()?;
+                    DeletePluginError::Internal { plugin_id, cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<ListPluginsError> for ListPluginsFailure {
+        fn from(error: ListPluginsError) -> Self {
+            let proto_error = match error {
+                ListPluginsError::Internal { cause } => {
+                    list_plugins_failure::Error::Internal(ListPluginsFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListPluginsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListPluginsFailure> for ListPluginsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListPluginsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListPluginsFailure, ListPluginsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_plugins_failure::Error::Internal(ListPluginsFailureInternal { cause }) => {
+                    ListPluginsError::Internal { cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<DownloadPluginError> for DownloadPluginFailure {
+        fn from(error: DownloadPluginError) -> Self {
+            let proto_error = match error {
+                DownloadPluginError::PluginNotFound { plugin_id } => {
+                    download_plugin_failure::Error::PluginNotFound(DownloadPluginFailurePluginNotFound {
+                        plugin_id: Some(plugin_id.into())
+                    })
+                }
+                DownloadPluginError::Internal { plugin_id, cause } => {
+                    download_plugin_failure::Error::Internal(DownloadPluginFailureInternal {
+                        plugin_id: Some(plugin_id.into()),
+                        cause
+                    })
+                }
+            };
+            DownloadPluginFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DownloadPluginFailure> for DownloadPluginError {
+        type Error = ConversionError;
+        fn try_from(failure: DownloadPluginFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DownloadPluginFailure, DownloadPluginError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                download_plugin_failure::Error::PluginNotFound(DownloadPluginFailurePluginNotFound { plugin_id }) => {
+                    let plugin_id: PluginId = plugin_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("plugin_id"))?
+                        .try_into()?;
+                    DownloadPluginError::PluginNotFound { plugin_id }
+                }
+                download_plugin_failure::Error::Internal(DownloadPluginFailureInternal { plugin_id, cause }) => {
+                    let plugin_id: PluginId = plugin_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("plugin_id"))?
+                        .try_into()?;
+                    DownloadPluginError::Internal { plugin_id, cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<AssignPluginsError> for AssignPluginsFailure {
+        fn from(error: AssignPluginsError) -> Self {
+            let proto_error = match error {
+                AssignPluginsError::PeerNotFound { peer_id } => {
+                    assign_plugins_failure::Error::PeerNotFound(AssignPluginsFailurePeerNotFound {
+                        peer_id: Some(peer_id.into())
+                    })
+                }
+                AssignPluginsError::PluginNotFound { peer_id, plugin_id } => {
+                    assign_plugins_failure::Error::PluginNotFound(AssignPluginsFailurePluginNotFound {
+                        peer_id: Some(peer_id.into()),
+                        plugin_id: Some(plugin_id.into()),
+                    })
+                }
+                AssignPluginsError::Internal { peer_id, cause } => {
+                    assign_plugins_failure::Error::Internal(AssignPluginsFailureInternal {
+                        peer_id: Some(peer_id.into()),
+                        cause
+                    })
+                }
+            };
+            AssignPluginsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<AssignPluginsFailure> for AssignPluginsError {
+        type Error = ConversionError;
+        fn try_from(failure: AssignPluginsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<AssignPluginsFailure, AssignPluginsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                assign_plugins_failure::Error::PeerNotFound(AssignPluginsFailurePeerNotFound { peer_id }) => {
+                    let peer_id: PeerId = peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    AssignPluginsError::PeerNotFound { peer_id }
+                }
+                assign_plugins_failure::Error::PluginNotFound(AssignPluginsFailurePluginNotFound { peer_id, plugin_id }) => {
+                    let peer_id: PeerId = peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    let plugin_id: PluginId = plugin_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("plugin_id"))?
+                        .try_into()?;
+                    AssignPluginsError::PluginNotFound { peer_id, plugin_id }
+                }
+                assign_plugins_failure::Error::Internal(AssignPluginsFailureInternal { peer_id, cause }) => {
+                    let peer_id: PeerId = peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    AssignPluginsError::Internal { peer_id, cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::cluster::DiagnoseClusterError;
+use opendut_types::cluster::diagnostics::{ClusterNetworkDiagnostics, NetworkDiagnosticsOptions, PeerLinkDiagnostics};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::peer::configuration::OldPeerConfiguration;
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Additional time granted to a peer for reporting its network diagnostics, e.g. to account for network delays.
+const REPORT_TIMEOUT_MARGIN: Duration = Duration::from_secs(10);
+
+pub struct DiagnoseClusterParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub cluster_id: ClusterId,
+    pub options: NetworkDiagnosticsOptions,
+}
+
+/// Asks each peer of a deployed cluster to test its network connection to every other peer.
+/// The peers are asked one after another, so that the throughput measurements do not compete for bandwidth.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn diagnose_cluster(params: DiagnoseClusterParams) -> Result<ClusterNetworkDiagnostics, DiagnoseClusterError> {
+
+    async fn inner(params: DiagnoseClusterParams) -> Result<ClusterNetworkDiagnostics, DiagnoseClusterError> {
+        let DiagnoseClusterParams { resources_manager, peer_messaging_broker, cluster_id, options } = params;
+
+        debug!("Diagnosing network of cluster <{cluster_id}>.");
+
+        options.validate()
+            .map_err(|cause| DiagnoseClusterError::IllegalOptions { cluster_id, cause: cause.to_string() })?;
+
+        let cluster_assignment = resources_manager.resources(|resources| {
+            let configuration = resources.get::<ClusterConfiguration>(cluster_id)?;
+            let deployment = resources.get::<ClusterDeployment>(cluster_id)?;
+
+            let cluster_assignment = match &configuration {
+                Some(configuration) => resources.get::<OldPeerConfiguration>(configuration.leader)?
+                    .and_then(|old_peer_configuration| old_peer_configuration.cluster_assignment)
+                    .filter(|cluster_assignment| cluster_assignment.id == cluster_id),
+                None => None,
+            };
+            Ok((configuration, deployment, cluster_assignment))
+        }).await
+            .map_err(|cause| DiagnoseClusterError::Internal { cluster_id, cause: cause.to_string() })
+            .and_then(|(configuration, deployment, cluster_assignment)| {
+                if configuration.is_none() {
+                    return Err(DiagnoseClusterError::ClusterNotFound { cluster_id });
+                }
+                match (deployment, cluster_assignment) {
+                    (Some(_), Some(cluster_assignment)) => Ok(cluster_assignment),
+                    _ => Err(DiagnoseClusterError::ClusterNotDeployed { cluster_id }),
+                }
+            })?;
+
+        let mut links = Vec::new();
+
+        for source in &cluster_assignment.assignments {
+            let targets = cluster_assignment.assignments.iter()
+                .filter(|target| target.peer_id != source.peer_id)
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let timeout = options.max_duration_per_target()
+                .saturating_mul(u32::try_from(targets.len()).unwrap_or(u32::MAX))
+                .saturating_add(REPORT_TIMEOUT_MARGIN);
+
+            let result = peer_messaging_broker.request_network_diagnostics(
+                source.peer_id,
+                Clone::clone(&options),
+                Clone::clone(&targets),
+                timeout,
+            ).await;
+
+            match result {
+                Ok(reported_links) => links.extend(reported_links),
+                Err(cause) => {
+                    warn!("Failed to diagnose network of peer <{}> in cluster <{cluster_id}>:\n  {cause}", source.peer_id);
+                    links.extend(targets.into_iter().map(|target| PeerLinkDiagnostics {
+                        source: source.peer_id,
+                        target: target.peer_id,
+                        latency: None,
+                        throughput: None,
+                        path_mtu: None,
+                        errors: vec![cause.to_string()],
+                    }));
+                }
+            }
+        }
+
+        debug!("Diagnosed network of cluster <{cluster_id}>.");
+
+        Ok(ClusterNetworkDiagnostics { cluster_id, links })
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::resources::manager::ResourcesManager;
+    use googletest::prelude::*;
+    use opendut_types::cluster::{ClusterAssignment, ClusterName, PeerClusterAssignment};
+    use opendut_types::peer::PeerId;
+    use opendut_types::util::Port;
+    use rstest::rstest;
+    use std::collections::HashSet;
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    fn peer_messaging_broker(resources_manager: &ResourcesManagerRef) -> PeerMessagingBrokerRef {
+        PeerMessagingBroker::new(Arc::clone(resources_manager), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(10),
+            unique_remote_hosts: false,
+        })
+    }
+
+    #[tokio::test]
+    async fn should_fail_for_unknown_cluster() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let cluster_id = ClusterId::random();
+
+        let result = diagnose_cluster(DiagnoseClusterParams {
+            peer_messaging_broker: peer_messaging_broker(&resources_manager),
+            resources_manager,
+            cluster_id,
+            options: NetworkDiagnosticsOptions::default(),
+        }).await;
+
+        assert_that!(result, err(eq(&DiagnoseClusterError::ClusterNotFound { cluster_id })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_options_exceeding_the_limits() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let cluster_id = ClusterId::random();
+
+        let options = NetworkDiagnosticsOptions { ping_count: u32::MAX, ..NetworkDiagnosticsOptions::default() };
+        let cause = options.validate().unwrap_err().to_string();
+
+        let result = diagnose_cluster(DiagnoseClusterParams {
+            peer_messaging_broker: peer_messaging_broker(&resources_manager),
+            resources_manager,
+            cluster_id,
+            options,
+        }).await;
+
+        assert_that!(result, err(eq(&DiagnoseClusterError::IllegalOptions { cluster_id, cause })));
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_fail_for_undeployed_cluster(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer = fixture.peer_a_descriptor;
+
+        let cluster = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("cluster")?,
+            leader: peer.id,
+            devices: HashSet::from_iter(peer.topology.devices.iter().map(|device| device.id)),
+        };
+        resources_manager.insert(cluster.id, Clone::clone(&cluster)).await?;
+
+        let result = diagnose_cluster(DiagnoseClusterParams {
+            peer_messaging_broker: peer_messaging_broker(&resources_manager),
+            resources_manager,
+            cluster_id: cluster.id,
+            options: NetworkDiagnosticsOptions::default(),
+        }).await;
+
+        assert_that!(result, err(eq(&DiagnoseClusterError::ClusterNotDeployed { cluster_id: cluster.id })));
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_report_an_error_for_each_link_of_a_disconnected_peer(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer = fixture.peer_a_descriptor;
+        let other_peer_id = PeerId::random();
+
+        let cluster = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("cluster")?,
+            leader: peer.id,
+            devices: HashSet::from_iter(peer.topology.devices.iter().map(|device| device.id)),
+        };
+        resources_manager.insert(cluster.id, Clone::clone(&cluster)).await?;
+        resources_manager.insert(cluster.id, ClusterDeployment { id: cluster.id }).await?;
+
+        let assignment = |peer_id: PeerId, vpn_address: &str, can_server_port: u16| -> anyhow::Result<PeerClusterAssignment> {
+            Ok(PeerClusterAssignment { peer_id, vpn_address: IpAddr::from_str(vpn_address)?, can_server_port: Port(can_server_port), device_interfaces: vec![] })
+        };
+        resources_manager.insert(peer.id, OldPeerConfiguration {
+            cluster_assignment: Some(ClusterAssignment {
+                id: cluster.id,
+                leader: peer.id,
+                assignments: vec![
+                    assignment(peer.id, "10.0.0.1", 10001)?,
+                    assignment(other_peer_id, "10.0.0.2", 10002)?,
+                ],
+            }),
+        }).await?;
+
+        let result = diagnose_cluster(DiagnoseClusterParams {
+            peer_messaging_broker: peer_messaging_broker(&resources_manager),
+            resources_manager,
+            cluster_id: cluster.id,
+            options: NetworkDiagnosticsOptions::default(),
+        }).await?;
+
+        assert_that!(result.cluster_id, eq(cluster.id));
+        assert_that!(result.links, unordered_elements_are![
+            matches_pattern!(PeerLinkDiagnostics { source: eq(&peer.id), target: eq(&other_peer_id), latency: none(), errors: len(eq(1)) }),
+            matches_pattern!(PeerLinkDiagnostics { source: eq(&other_peer_id), target: eq(&peer.id), latency: none(), errors: len(eq(1)) }),
+        ]);
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use crate::actions;
+use crate::actions::{DetermineClusterPeerStatesError, DetermineClusterPeerStatesParams, DetermineClusterPeersError};
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::cluster::GetClusterStateError;
+use opendut_types::cluster::state::{ClusterState, DeployedClusterState};
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::peer::state::{PeerBlockedState, PeerState, PeerUpState};
+use std::ops::Not;
+use std::sync::Arc;
+use tracing::{debug, error};
+
+pub struct GetClusterStateParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub cluster_id: ClusterId,
+}
+
+/// Derives the state of a cluster from its deployment and the states of its peers.
+/// A deployed cluster is healthy, when all of its peers are members of the cluster.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn get_cluster_state(params: GetClusterStateParams) -> Result<ClusterState, GetClusterStateError> {
+
+    async fn inner(params: GetClusterStateParams) -> Result<ClusterState, GetClusterStateError> {
+        let GetClusterStateParams { resources_manager, cluster_id } = params;
+
+        debug!("Determining state of cluster <{cluster_id}>.");
+
+        let (configuration, deployment) = resources_manager.resources(|resources| {
+            let configuration = resources.get::<ClusterConfiguration>(cluster_id)?;
+            let deployment = resources.get::<ClusterDeployment>(cluster_id)?;
+            Ok((configuration, deployment))
+        }).await
+            .map_err(|cause| GetClusterStateError::Internal { cluster_id, cause: cause.to_string() })?;
+
+        if configuration.is_none() {
+            return Err(GetClusterStateError::ClusterNotFound { cluster_id });
+        }
+        if deployment.is_none() {
+            return Ok(ClusterState::Undeployed);
+        }
+
+        let cluster_peer_states = actions::determine_cluster_peer_states(DetermineClusterPeerStatesParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_id,
+        }).await
+            .map_err(|error| match error {
+                DetermineClusterPeerStatesError::DetermineClusterPeers { source: DetermineClusterPeersError::ClusterNotFound(_), .. } => {
+                    GetClusterStateError::ClusterNotFound { cluster_id }
+                }
+                error => GetClusterStateError::Internal { cluster_id, cause: error.to_string() },
+            })?;
+
+        let peer_states = cluster_peer_states.peer_states.values().collect::<Vec<_>>();
+
+        let is_deploying = peer_states.iter().any(|state| matches!(state, PeerState::Up { inner: PeerUpState::Blocked(PeerBlockedState::Deploying), .. }));
+        let all_members = peer_states.is_empty().not() && peer_states.iter().all(|state| matches!(state, PeerState::Up { inner: PeerUpState::Blocked(PeerBlockedState::Member), .. }));
+
+        let state = if is_deploying {
+            ClusterState::Deploying
+        } else if all_members {
+            ClusterState::Deployed(DeployedClusterState::Healthy)
+        } else {
+            ClusterState::Deployed(DeployedClusterState::Unhealthy)
+        };
+
+        debug!("Cluster <{cluster_id}> is in state '{state:?}'.");
+
+        Ok(state)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+    use googletest::prelude::*;
+    use opendut_types::cluster::ClusterName;
+    use rstest::rstest;
+    use std::collections::HashSet;
+    use std::net::IpAddr;
+    use std::str::FromStr;
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_determine_the_cluster_state_in_memory(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        should_determine_the_cluster_state(resources_manager, fixture).await
+    }
+
+    #[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_determine_the_cluster_state_in_database(fixture: Fixture) -> anyhow::Result<()> {
+        let db = crate::persistence::database::testing::spawn_and_connect_resources_manager().await?;
+        should_determine_the_cluster_state(db.resources_manager, fixture).await
+    }
+
+    async fn should_determine_the_cluster_state(resources_manager: ResourcesManagerRef, fixture: Fixture) -> anyhow::Result<()> {
+        let peer = fixture.peer_a_descriptor;
+        resources_manager.insert(peer.id, Clone::clone(&peer)).await?;
+
+        let cluster = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("cluster")?,
+            leader: peer.id,
+            devices: HashSet::from_iter(peer.topology.devices.iter().map(|device| device.id)),
+        };
+        resources_manager.insert(cluster.id, Clone::clone(&cluster)).await?;
+
+        let params = || GetClusterStateParams { resources_manager: Arc::clone(&resources_manager), cluster_id: cluster.id };
+
+        assert_that!(get_cluster_state(params()).await, ok(eq(&ClusterState::Undeployed)));
+
+        resources_manager.insert(cluster.id, ClusterDeployment { id: cluster.id }).await?;
+        assert_that!(get_cluster_state(params()).await, ok(eq(&ClusterState::Deployed(DeployedClusterState::Unhealthy))));
+
+        let remote_host = IpAddr::from_str("127.0.0.1")?;
+        resources_manager.insert(peer.id, PeerState::Up { inner: PeerUpState::Blocked(PeerBlockedState::Deploying), remote_host }).await?;
+        assert_that!(get_cluster_state(params()).await, ok(eq(&ClusterState::Deploying)));
+
+        resources_manager.insert(peer.id, PeerState::Up { inner: PeerUpState::Blocked(PeerBlockedState::Member), remote_host }).await?;
+        assert_that!(get_cluster_state(params()).await, ok(eq(&ClusterState::Deployed(DeployedClusterState::Healthy))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_consider_a_deployed_cluster_without_peers_healthy() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let cluster = ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("cluster")?,
+            leader: opendut_types::peer::PeerId::random(),
+            devices: HashSet::new(),
+        };
+        resources_manager.insert(cluster.id, Clone::clone(&cluster)).await?;
+        resources_manager.insert(cluster.id, ClusterDeployment { id: cluster.id }).await?;
+
+        let result = get_cluster_state(GetClusterStateParams { resources_manager, cluster_id: cluster.id }).await;
+
+        assert_that!(result, ok(eq(&ClusterState::Deployed(DeployedClusterState::Unhealthy))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_for_unknown_cluster() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let cluster_id = ClusterId::random();
+
+        let result = get_cluster_state(GetClusterStateParams { resources_manager, cluster_id }).await;
+
+        assert_that!(result, err(eq(&GetClusterStateError::ClusterNotFound { cluster_id })));
+        Ok(())
+    }
+}
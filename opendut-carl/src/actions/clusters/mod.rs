@@ -1,6 +1,8 @@
 pub mod create_cluster_configuration;
 pub mod delete_cluster_configuration;
 pub mod delete_cluster_deployment;
+pub mod diagnose_cluster;
 pub mod determine_cluster_peers;
 pub mod determine_cluster_peer_states;
+pub mod get_cluster_state;
 pub mod store_cluster_deployment;
@@ -5,6 +5,8 @@ pub use clusters::store_cluster_deployment::*;
 pub use clusters::determine_cluster_peers::*;
 pub use clusters::determine_cluster_peer_states::*;
 pub use clusters::delete_cluster_deployment::*;
+pub use clusters::get_cluster_state::*;
+pub use clusters::diagnose_cluster::*;
 
 mod peers;
 pub use peers::store_peer_descriptor::*;
@@ -13,6 +15,22 @@ pub use peers::generate_cleo_setup::*;
 pub use peers::delete_peer_descriptor::*;
 pub use peers::list_peer_descriptors::*;
 pub use peers::get_peer_state::*;
+pub use peers::get_peer_history::*;
 pub use peers::list_devices::*;
 pub use peers::assign_cluster::*;
 pub use peers::unassign_cluster::*;
+pub use peers::append_executor_logs::*;
+pub use peers::get_executor_logs::*;
+pub use peers::test_runs::*;
+pub use peers::start_executor::*;
+pub use peers::stop_executor::*;
+
+mod plugins;
+pub use plugins::store_plugin::*;
+pub use plugins::delete_plugin::*;
+pub use plugins::list_plugins::*;
+pub use plugins::download_plugin::*;
+pub use plugins::assign_plugins::*;
+
+mod vpn;
+pub use vpn::reconcile_vpn::*;
@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use crate::persistence::error::PersistenceError;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_types::peer::executor::log::{ExecutorLogChunk, ExecutorLogChunkId, ExecutorLogLine, ExecutorRunId, ExecutorRunLogSummary};
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::PeerId;
+use tracing::{error, trace, warn};
+
+/// Maximum number of lines retained per run of an executor, to limit the storage used by excessively chatty executors.
+pub const MAX_LOG_LINES_PER_RUN: u64 = 100_000;
+
+pub struct AppendExecutorLogsParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_id: PeerId,
+    pub executor_id: ExecutorId,
+    pub run_id: ExecutorRunId,
+    pub lines: Vec<ExecutorLogLine>,
+}
+
+/// Appends lines of output, which a peer reported for a run of one of its executors.
+/// Only the chunks the new lines belong to are loaded and stored, so appending does not depend on the size of the log.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn append_executor_logs(params: AppendExecutorLogsParams) -> Result<(), PersistenceError> {
+
+    async fn inner(params: AppendExecutorLogsParams) -> Result<(), PersistenceError> {
+        let AppendExecutorLogsParams { resources_manager, peer_id, executor_id, run_id, mut lines } = params;
+
+        trace!("Appending {} lines to log of run <{run_id}> of executor <{executor_id}>.", lines.len());
+
+        resources_manager.resources_mut(|resources| {
+            let mut summary = resources.get::<ExecutorRunLogSummary>(run_id)?
+                .unwrap_or_else(|| ExecutorRunLogSummary::new(run_id, executor_id, peer_id));
+
+            if summary.peer_id != peer_id {
+                warn!("Peer <{peer_id}> reported logs of run <{run_id}>, which belongs to peer <{}>. Ignoring.", summary.peer_id);
+                return Ok(());
+            }
+
+            // Lines are only ever appended, so lines before the expected sequence number have been received before,
+            // e.g. because EDGAR sent them again after a lost connection.
+            lines.retain(|line| line.sequence_number >= summary.next_sequence_number);
+            lines.sort_by_key(|line| line.sequence_number);
+            lines.dedup_by_key(|line| line.sequence_number);
+
+            let remaining_capacity = MAX_LOG_LINES_PER_RUN.saturating_sub(summary.line_count);
+            if lines.len() as u64 > remaining_capacity {
+                if remaining_capacity > 0 {
+                    warn!("Log of run <{run_id}> of executor <{executor_id}> reached the maximum of {MAX_LOG_LINES_PER_RUN} lines. Further lines are discarded.");
+                }
+                lines.truncate(remaining_capacity as usize);
+            }
+
+            let Some(last_line) = lines.last() else {
+                return Ok(());
+            };
+
+            summary.started_at = summary.started_at.or(lines.first().map(|line| line.timestamp));
+            summary.next_sequence_number = last_line.sequence_number.saturating_add(1);
+            summary.line_count += lines.len() as u64;
+            resources.insert(run_id, summary)?;
+
+            let mut lines_by_chunk: BTreeMap<u64, Vec<ExecutorLogLine>> = BTreeMap::new();
+            for line in lines {
+                let chunk_id = ExecutorLogChunkId::containing(run_id, line.sequence_number);
+                lines_by_chunk.entry(chunk_id.index).or_default().push(line);
+            }
+
+            for (index, lines) in lines_by_chunk {
+                let chunk_id = ExecutorLogChunkId { run_id, index };
+                let mut chunk = resources.get::<ExecutorLogChunk>(chunk_id)?
+                    .unwrap_or_else(|| ExecutorLogChunk::new(chunk_id));
+                chunk.insert(lines);
+                resources.insert(chunk_id, chunk)?;
+            }
+            Ok(())
+        }).await?
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::manager::ResourcesManager;
+    use googletest::prelude::*;
+    use opendut_types::peer::executor::log::ExecutorLogStream;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+
+    fn line(sequence_number: u64) -> ExecutorLogLine {
+        ExecutorLogLine {
+            sequence_number,
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(sequence_number),
+            stream: ExecutorLogStream::Stdout,
+            text: format!("line {sequence_number}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_append_lines_to_the_log_of_a_run() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_id = PeerId::random();
+        let executor_id = ExecutorId::random();
+        let run_id = ExecutorRunId::random();
+
+        for lines in [vec![line(0), line(1)], vec![line(1), line(2)]] {
+            append_executor_logs(AppendExecutorLogsParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer_id,
+                executor_id,
+                run_id,
+                lines,
+            }).await?;
+        }
+
+        let summary = resources_manager.get::<ExecutorRunLogSummary>(run_id).await?;
+        assert_that!(summary, some(eq(&ExecutorRunLogSummary {
+            id: run_id,
+            executor_id,
+            peer_id,
+            started_at: Some(line(0).timestamp),
+            next_sequence_number: 3,
+            line_count: 3,
+        })));
+
+        let chunk = resources_manager.get::<ExecutorLogChunk>(ExecutorLogChunkId { run_id, index: 0 }).await?;
+        assert_that!(chunk.map(|chunk| chunk.lines), some(eq(&vec![line(0), line(1), line(2)])));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_split_lines_into_chunks() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let run_id = ExecutorRunId::random();
+        let boundary = ExecutorLogChunkId::LINES_PER_CHUNK;
+
+        append_executor_logs(AppendExecutorLogsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: PeerId::random(),
+            executor_id: ExecutorId::random(),
+            run_id,
+            lines: vec![line(boundary - 1), line(boundary)],
+        }).await?;
+
+        let first_chunk = resources_manager.get::<ExecutorLogChunk>(ExecutorLogChunkId { run_id, index: 0 }).await?;
+        assert_that!(first_chunk.map(|chunk| chunk.lines), some(eq(&vec![line(boundary - 1)])));
+
+        let second_chunk = resources_manager.get::<ExecutorLogChunk>(ExecutorLogChunkId { run_id, index: 1 }).await?;
+        assert_that!(second_chunk.map(|chunk| chunk.lines), some(eq(&vec![line(boundary)])));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_ignore_lines_reported_by_another_peer() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_id = PeerId::random();
+        let executor_id = ExecutorId::random();
+        let run_id = ExecutorRunId::random();
+
+        for (peer_id, lines) in [(peer_id, vec![line(0)]), (PeerId::random(), vec![line(1)])] {
+            append_executor_logs(AppendExecutorLogsParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer_id,
+                executor_id,
+                run_id,
+                lines,
+            }).await?;
+        }
+
+        let summary = resources_manager.get::<ExecutorRunLogSummary>(run_id).await?;
+        assert_that!(summary.map(|summary| (summary.peer_id, summary.line_count)), some(eq((peer_id, 1))));
+
+        let chunk = resources_manager.get::<ExecutorLogChunk>(ExecutorLogChunkId { run_id, index: 0 }).await?;
+        assert_that!(chunk.map(|chunk| chunk.lines), some(eq(&vec![line(0)])));
+        Ok(())
+    }
+}
@@ -9,12 +9,15 @@ use opendut_types::peer::state::{PeerBlockedState, PeerState, PeerUpState};
 use opendut_types::peer::{PeerDescriptor, PeerId};
 use opendut_types::peer::ethernet::EthernetBridge;
 use opendut_types::util::net::NetworkInterfaceName;
+use opendut_types::vpn::wireguard::WireGuardPeer;
 
 pub struct AssignClusterParams {
     pub resources_manager: ResourcesManagerRef,
     pub peer_messaging_broker: PeerMessagingBrokerRef,
     pub peer_id: PeerId,
     pub cluster_assignment: ClusterAssignment,
+    /// Tunnels to the other peers of the cluster, if the VPN is configured by EDGAR itself.
+    pub wireguard_peers: Vec<WireGuardPeer>,
     pub options: AssignClusterOptions,
 }
 
@@ -65,10 +68,22 @@ pub async fn assign_cluster(params: AssignClusterParams) -> Result<(), AssignClu
                 peer_configuration.insert(bridge, ParameterTarget::Present); //TODO not always Present
             }
 
+            let previous_wireguard_peers = std::mem::take(&mut peer_configuration.wireguard_peers);
+            for wireguard_peer in params.wireguard_peers {
+                peer_configuration.insert(wireguard_peer, ParameterTarget::Present);
+            }
+            for previous_wireguard_peer in previous_wireguard_peers { //tunnels to peers of a previous cluster are removed
+                let still_present = peer_configuration.wireguard_peers.iter()
+                    .any(|wireguard_peer| wireguard_peer.id == previous_wireguard_peer.id);
+
+                if previous_wireguard_peer.target == ParameterTarget::Present && !still_present {
+                    peer_configuration.insert(previous_wireguard_peer.value, ParameterTarget::Absent);
+                }
+            }
+
             peer_configuration
         };
 
-
         let peer_state = resources.get::<PeerState>(peer_id)
             .map_err(|source| AssignClusterError::Persistence { peer_id, source })?
             .ok_or(AssignClusterError::PeerNotFound(peer_id))?;
@@ -118,6 +133,7 @@ mod tests {
     use opendut_types::peer::{PeerLocation, PeerName, PeerNetworkDescriptor};
     use opendut_types::peer::executor::ExecutorDescriptors;
     use opendut_types::topology::Topology;
+    use opendut_types::vpn::wireguard::{WireGuardPublicKey, KEY_LENGTH};
 
     #[rstest]
     #[tokio::test]
@@ -142,7 +158,7 @@ mod tests {
             resources.insert(peer_id, Clone::clone(&peer_configuration))
         }).await??;
 
-        let (_, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?).await?;
+        let (_, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?, None).await?;
         let received = receiver.recv().await.unwrap()
             .message.unwrap();
         assert_that!(
@@ -159,6 +175,11 @@ mod tests {
             leader: PeerId::random(),
             assignments: vec![],
         };
+        let wireguard_peer = WireGuardPeer {
+            public_key: WireGuardPublicKey::from_bytes([1; KEY_LENGTH]),
+            allowed_ip: IpAddr::from_str("100.64.0.2")?,
+            endpoint: None,
+        };
 
 
         assign_cluster(AssignClusterParams {
@@ -166,6 +187,7 @@ mod tests {
             peer_messaging_broker: Arc::clone(&peer_messaging_broker),
             peer_id,
             cluster_assignment: Clone::clone(&cluster_assignment),
+            wireguard_peers: vec![Clone::clone(&wireguard_peer)],
             options: AssignClusterOptions {
                 bridge_name_default: NetworkInterfaceName::try_from("br-opendut").unwrap(),
             }
@@ -183,8 +205,10 @@ mod tests {
         let mut peer_configuration = PeerConfiguration {
             executors: vec![],
             ethernet_bridges: vec![],
+            wireguard_peers: vec![],
         };
         peer_configuration.insert(EthernetBridge { name: NetworkInterfaceName::try_from("br-opendut-1")? }, ParameterTarget::Present);
+        peer_configuration.insert(wireguard_peer, ParameterTarget::Present);
 
         let received = receiver.recv().await.unwrap()
             .message.unwrap();
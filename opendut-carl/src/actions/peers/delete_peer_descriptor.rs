@@ -4,6 +4,7 @@ use crate::vpn::Vpn;
 use opendut_auth::registration::client::RegistrationClientRef;
 use opendut_carl_api::carl::peer::DeletePeerDescriptorError;
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::plugin::PeerPluginAssignment;
 use tracing::{debug, error, info, warn};
 
 pub struct DeletePeerDescriptorParams {
@@ -29,6 +30,9 @@ pub async fn delete_peer_descriptor(params: DeletePeerDescriptorParams) -> Resul
                 .map_err(|cause| DeletePeerDescriptorError::Internal { peer_id, peer_name: None, cause: cause.to_string() })?
                 .ok_or_else(|| DeletePeerDescriptorError::PeerNotFound { peer_id })?;
 
+            resources.remove::<PeerPluginAssignment>(peer_id)
+                .map_err(|cause| DeletePeerDescriptorError::Internal { peer_id, peer_name: Some(Clone::clone(&peer_descriptor.name)), cause: cause.to_string() })?;
+
             Ok(peer_descriptor)
         }).await
         .map_err(|cause| DeletePeerDescriptorError::Internal { peer_id, peer_name: None, cause: cause.to_string() })??;
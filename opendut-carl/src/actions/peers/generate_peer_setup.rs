@@ -1,12 +1,16 @@
 use crate::persistence::error::PersistenceError;
+use crate::plugin::PluginSigning;
 use crate::resources::manager::ResourcesManagerRef;
 use crate::vpn::Vpn;
 use opendut_auth::registration::client::RegistrationClientRef;
 use opendut_auth::registration::resources::UserId;
+use opendut_types::peer::history::{PeerEnrollment, PeerEnrollmentId};
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerName, PeerSetup};
 use opendut_types::util::net::{AuthConfig, Certificate};
 use opendut_types::vpn::VpnPeerConfiguration;
 use pem::Pem;
+use std::ops::Not;
+use std::time::SystemTime;
 use tracing::{debug, error, info, warn};
 use url::Url;
 
@@ -17,7 +21,10 @@ pub struct GeneratePeerSetupParams {
     pub ca: Pem,
     pub vpn: Vpn,
     pub oidc_registration_client: Option<RegistrationClientRef>,
+    pub plugin_signing: PluginSigning,
     pub user_id: UserId,
+    /// Revoke the VPN and OIDC credentials of previous PeerSetups, e.g. when the host of the peer is replaced.
+    pub reenroll: bool,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -49,6 +56,24 @@ pub async fn generate_peer_setup(params: GeneratePeerSetupParams) -> Result<Peer
 
         let peer_name = peer_descriptor.name;
 
+        if params.reenroll {
+            info!("Re-enrolling peer '{peer_name}' <{peer_id}>. Revoking the credentials of previous PeerSetups.");
+
+            if let Vpn::Enabled { vpn_client } = &params.vpn {
+                debug!("Deleting VPN peer <{peer_id}> to revoke its previous setup keys.");
+                vpn_client.delete_peer(peer_id).await
+                    .map_err(|cause| GeneratePeerSetupError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() })?;
+                vpn_client.create_peer(peer_id).await
+                    .map_err(|cause| GeneratePeerSetupError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() })?;
+            }
+
+            if let Some(registration_client) = &params.oidc_registration_client {
+                debug!("Deleting previous OIDC clients of peer '{peer_name}' <{peer_id}>.");
+                registration_client.delete_client_by_resource_id(peer_id.into()).await
+                    .map_err(|cause| GeneratePeerSetupError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() })?;
+            }
+        }
+
         let vpn_config = if let Vpn::Enabled { vpn_client } = &params.vpn {
             debug!("Retrieving VPN configuration for peer <{peer_id}>.");
             let vpn_config = vpn_client.generate_vpn_peer_configuration(params.peer).await
@@ -61,6 +86,9 @@ pub async fn generate_peer_setup(params: GeneratePeerSetupParams) -> Result<Peer
             VpnPeerConfiguration::Disabled
         };
 
+        let issued_by = Some(Clone::clone(&params.user_id.value))
+            .filter(|user_id| user_id.is_empty().not());
+
         let auth_config = match params.oidc_registration_client {
             None => {
                 AuthConfig::Disabled
@@ -77,15 +105,109 @@ pub async fn generate_peer_setup(params: GeneratePeerSetupParams) -> Result<Peer
             }
         };
 
+        let enrollment = PeerEnrollment {
+            id: PeerEnrollmentId::random(),
+            peer_id,
+            reenrollment: params.reenroll,
+            issued_by,
+            issued_at: SystemTime::now(),
+        };
+        params.resources_manager.insert(enrollment.id, enrollment).await
+            .map_err(|source| GeneratePeerSetupError::Persistance { peer_id, source })?;
+
         Ok(PeerSetup {
             id: peer_id,
             carl: params.carl_url,
             ca: Certificate(params.ca),
             auth_config,
             vpn: vpn_config,
+            plugin_verifying_key: params.plugin_signing.verifying_key(),
         })
     }
 
     inner(params).await
         .inspect_err(|err| error!("{err}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use config::Config;
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::resources::manager::ResourcesManager;
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_record_an_enrollment_when_generating_a_peer_setup(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        resources_manager.insert(fixture.peer_a_id, Clone::clone(&fixture.peer_a_descriptor)).await?;
+
+        for reenroll in [false, true] {
+            let peer_setup = generate_peer_setup(GeneratePeerSetupParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer: fixture.peer_a_id,
+                carl_url: Url::parse("https://example.com:1234")?,
+                ca: Pem::from_str(include_str!("../../../../resources/development/tls/insecure-development-ca.pem"))?,
+                vpn: Vpn::Disabled,
+                oidc_registration_client: None,
+                plugin_signing: PluginSigning::Disabled,
+                user_id: UserId { value: String::from("testUser") },
+                reenroll,
+            }).await?;
+            assert_that!(peer_setup.id, eq(fixture.peer_a_id));
+        }
+
+        let mut enrollments = resources_manager.list::<PeerEnrollment>().await?;
+        enrollments.sort_by_key(|enrollment| enrollment.issued_at);
+
+        assert_that!(enrollments, elements_are![
+            matches_pattern!(PeerEnrollment { peer_id: eq(&fixture.peer_a_id), reenrollment: eq(&false), issued_by: some(eq("testUser")) }),
+            matches_pattern!(PeerEnrollment { peer_id: eq(&fixture.peer_a_id), reenrollment: eq(&true), issued_by: some(eq("testUser")) }),
+        ]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_rotate_the_wireguard_key_when_reenrolling(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        resources_manager.insert(fixture.peer_a_id, Clone::clone(&fixture.peer_a_descriptor)).await?;
+
+        let settings = Config::builder()
+            .set_override("vpn.enabled", true)?
+            .set_override("vpn.kind", "wireguard")?
+            .set_override("vpn.wireguard.key.secret", "secret")?
+            .set_override("vpn.wireguard.network.address", "100.64.0.0")?
+            .set_override("vpn.wireguard.network.prefix.length", 10)?
+            .set_override("vpn.wireguard.listen.port", 51820)?
+            .build()?;
+        let vpn = crate::vpn::create(&settings, Arc::clone(&resources_manager))?;
+
+        let mut private_keys = Vec::new();
+        for reenroll in [false, false, true] {
+            let peer_setup = generate_peer_setup(GeneratePeerSetupParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer: fixture.peer_a_id,
+                carl_url: Url::parse("https://example.com:1234")?,
+                ca: Pem::from_str(include_str!("../../../../resources/development/tls/insecure-development-ca.pem"))?,
+                vpn: Clone::clone(&vpn),
+                oidc_registration_client: None,
+                plugin_signing: PluginSigning::Disabled,
+                user_id: UserId { value: String::from("testUser") },
+                reenroll,
+            }).await?;
+            let VpnPeerConfiguration::WireGuard { private_key, .. } = peer_setup.vpn else { panic!("Expected WireGuard configuration.") };
+            private_keys.push(private_key);
+        }
+
+        assert_that!(private_keys[1], eq(&private_keys[0]));
+        assert_that!(private_keys[2], not(eq(&private_keys[0])));
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use crate::resources::manager::ResourcesManagerRef;
+use opendut_carl_api::carl::peer::GetExecutorLogsError;
+use opendut_types::peer::executor::log::{ExecutorLogChunk, ExecutorLogChunkId, ExecutorRunId, ExecutorRunLog, ExecutorRunLogSummary};
+use opendut_types::peer::executor::ExecutorId;
+use tracing::{debug, error};
+
+/// Maximum number of chunks returned with one query. Clients fetch further lines by querying again, starting after the last returned line.
+pub const MAX_CHUNKS_PER_PAGE: u64 = 10;
+
+pub struct GetExecutorLogsParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub executor_id: ExecutorId,
+    /// Run of the executor to return the logs of. The latest run, if not set.
+    pub run_id: Option<ExecutorRunId>,
+    /// Only lines with a sequence number greater than or equal to this are returned, to allow following the logs.
+    pub from_sequence_number: u64,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn get_executor_logs(params: GetExecutorLogsParams) -> Result<ExecutorRunLog, GetExecutorLogsError> {
+
+    async fn inner(params: GetExecutorLogsParams) -> Result<ExecutorRunLog, GetExecutorLogsError> {
+        let GetExecutorLogsParams { resources_manager, executor_id, run_id, from_sequence_number } = params;
+
+        debug!("Querying logs of executor <{executor_id}>.");
+
+        let summary = match run_id {
+            Some(run_id) => {
+                let summary = resources_manager.get::<ExecutorRunLogSummary>(run_id).await
+                    .map_err(|cause| GetExecutorLogsError::Internal { executor_id, cause: cause.to_string() })?
+                    .filter(|summary| summary.executor_id == executor_id);
+
+                summary.ok_or(GetExecutorLogsError::RunNotFound { executor_id, run_id })?
+            }
+            None => {
+                let summary = resources_manager.list::<ExecutorRunLogSummary>().await
+                    .map_err(|cause| GetExecutorLogsError::Internal { executor_id, cause: cause.to_string() })?
+                    .into_iter()
+                    .filter(|summary| summary.executor_id == executor_id)
+                    .max_by_key(|summary| summary.started_at);
+
+                summary.ok_or(GetExecutorLogsError::ExecutorNotFound { executor_id })?
+            }
+        };
+
+        let mut log = ExecutorRunLog::new(summary.id, summary.executor_id, summary.peer_id);
+
+        let first_chunk_id = ExecutorLogChunkId::containing(summary.id, from_sequence_number);
+        let chunk_ids = (first_chunk_id.index..first_chunk_id.index.saturating_add(MAX_CHUNKS_PER_PAGE))
+            .map(|index| ExecutorLogChunkId { run_id: summary.id, index })
+            .take_while(|chunk_id| chunk_id.first_sequence_number() < summary.next_sequence_number);
+
+        for chunk_id in chunk_ids {
+            let chunk = resources_manager.get::<ExecutorLogChunk>(chunk_id).await
+                .map_err(|cause| GetExecutorLogsError::Internal { executor_id, cause: cause.to_string() })?;
+
+            if let Some(chunk) = chunk {
+                log.append(chunk.lines.into_iter()
+                    .filter(|line| line.sequence_number >= from_sequence_number));
+            }
+        }
+
+        debug!("Successfully queried {} lines of run <{}> of executor <{executor_id}>.", log.lines.len(), log.id);
+
+        Ok(log)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::peers::append_executor_logs::{append_executor_logs, AppendExecutorLogsParams};
+    use crate::resources::manager::ResourcesManager;
+    use googletest::prelude::*;
+    use opendut_types::peer::executor::log::{ExecutorLogLine, ExecutorLogStream};
+    use opendut_types::peer::PeerId;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+
+    async fn run_log(resources_manager: &ResourcesManagerRef, executor_id: ExecutorId, started_at_secs: u64, line_count: u64) -> anyhow::Result<ExecutorRunLog> {
+        let mut log = ExecutorRunLog::new(ExecutorRunId::random(), executor_id, PeerId::random());
+        log.append((0..line_count).map(|sequence_number| ExecutorLogLine {
+            sequence_number,
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(started_at_secs + sequence_number),
+            stream: ExecutorLogStream::Stdout,
+            text: format!("line {sequence_number}"),
+        }));
+
+        append_executor_logs(AppendExecutorLogsParams {
+            resources_manager: Arc::clone(resources_manager),
+            peer_id: log.peer_id,
+            executor_id,
+            run_id: log.id,
+            lines: log.lines.clone(),
+        }).await?;
+
+        Ok(log)
+    }
+
+    #[tokio::test]
+    async fn should_return_the_latest_run_from_the_given_sequence_number() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let executor_id = ExecutorId::random();
+
+        let _older_run = run_log(&resources_manager, executor_id, 0, 3).await?;
+        let latest_run = run_log(&resources_manager, executor_id, 100, 3).await?;
+        let _other_executor_run = run_log(&resources_manager, ExecutorId::random(), 200, 3).await?;
+
+        let result = get_executor_logs(GetExecutorLogsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            executor_id,
+            run_id: None,
+            from_sequence_number: 1,
+        }).await?;
+
+        assert_that!(result.id, eq(latest_run.id));
+        assert_that!(result.lines, eq(&latest_run.lines[1..]));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_long_logs_in_pages() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let executor_id = ExecutorId::random();
+        let page_size = MAX_CHUNKS_PER_PAGE * ExecutorLogChunkId::LINES_PER_CHUNK;
+
+        let run = run_log(&resources_manager, executor_id, 0, page_size + 5).await?;
+
+        let mut lines = Vec::new();
+        let mut from_sequence_number = 0;
+        loop {
+            let page = get_executor_logs(GetExecutorLogsParams {
+                resources_manager: Arc::clone(&resources_manager),
+                executor_id,
+                run_id: Some(run.id),
+                from_sequence_number,
+            }).await?;
+
+            if page.lines.is_empty() {
+                break;
+            }
+            assert_that!(page.lines.len() as u64, le(page_size));
+
+            from_sequence_number = page.next_sequence_number();
+            lines.extend(page.lines);
+        }
+
+        assert_that!(lines, eq(&run.lines));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_for_unknown_run() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let executor_id = ExecutorId::random();
+        let run_id = ExecutorRunId::random();
+
+        let result = get_executor_logs(GetExecutorLogsParams {
+            resources_manager,
+            executor_id,
+            run_id: Some(run_id),
+            from_sequence_number: 0,
+        }).await;
+
+        assert_that!(result, err(eq(&GetExecutorLogsError::RunNotFound { executor_id, run_id })));
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::peer::GetPeerHistoryError;
+use opendut_types::peer::history::{PeerConnectionSessions, PeerEnrollment, PeerHistory};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use tracing::{debug, error, info};
+
+pub struct GetPeerHistoryParams {
+    pub peer: PeerId,
+    pub resources_manager: ResourcesManagerRef,
+}
+
+/// Returns the connection sessions and enrollments of a peer, ordered from oldest to newest.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn get_peer_history(params: GetPeerHistoryParams) -> Result<PeerHistory, GetPeerHistoryError> {
+
+    async fn inner(params: GetPeerHistoryParams) -> Result<PeerHistory, GetPeerHistoryError> {
+
+        let peer_id = params.peer;
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying connection history of peer with peer_id <{}>.", peer_id);
+
+        let history = resources_manager.resources(|resources| {
+            let sessions = resources.get::<PeerConnectionSessions>(peer_id)?
+                .map(|sessions| sessions.sessions)
+                .unwrap_or_default();
+
+            let enrollments = resources.list::<PeerEnrollment>()?
+                .into_iter()
+                .filter(|enrollment| enrollment.peer_id == peer_id)
+                .collect::<Vec<_>>();
+
+            let peer_descriptor = resources.get::<PeerDescriptor>(peer_id)?;
+
+            Ok((sessions, enrollments, peer_descriptor))
+        }).await
+        .map_err(|cause| GetPeerHistoryError::Internal { peer_id, cause: cause.to_string() })
+        .and_then(|(mut sessions, mut enrollments, peer_descriptor)| {
+            if sessions.is_empty() && enrollments.is_empty() && peer_descriptor.is_none() {
+                Err(GetPeerHistoryError::PeerNotFound { peer_id })
+            } else {
+                sessions.sort_by_key(|session| session.connected_at);
+                enrollments.sort_by_key(|enrollment| enrollment.issued_at);
+                Ok(PeerHistory { sessions, enrollments })
+            }
+        })?;
+
+        info!("Successfully queried connection history of peer with peer_id <{}>.", peer_id);
+
+        Ok(history)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::actions;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::actions::{get_peer_history, GetPeerHistoryParams, StorePeerDescriptorParams};
+    use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+    use googletest::prelude::*;
+    use opendut_carl_api::carl::peer::GetPeerHistoryError;
+    use opendut_types::peer::history::{PeerConnectionSession, PeerConnectionSessionId, PeerConnectionSessions, PeerEnrollment, PeerEnrollmentId};
+    use opendut_types::peer::PeerId;
+    use rstest::rstest;
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_get_peer_history_in_memory(fixture: Fixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        should_get_peer_history(resources_manager, fixture).await
+    }
+
+    #[test_with::no_env(SKIP_DATABASE_CONTAINER_TESTS)]
+    #[rstest]
+    #[tokio::test]
+    async fn should_get_peer_history_in_database(fixture: Fixture) -> anyhow::Result<()> {
+        let db = crate::persistence::database::testing::spawn_and_connect_resources_manager().await?;
+        should_get_peer_history(db.resources_manager, fixture).await
+    }
+
+    async fn should_get_peer_history(resources_manager: ResourcesManagerRef, fixture: Fixture) -> anyhow::Result<()> {
+        actions::store_peer_descriptor(StorePeerDescriptorParams {
+            resources_manager: Arc::clone(&resources_manager),
+            vpn: fixture.vpn,
+            peer_descriptor: fixture.peer_a_descriptor,
+        }).await?;
+
+        let connected_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_725_000_000);
+        let older_session = PeerConnectionSession {
+            id: PeerConnectionSessionId::random(),
+            peer_id: fixture.peer_a_id,
+            remote_host: IpAddr::from_str("1.2.3.4")?,
+            edgar_version: Some(String::from("0.3.0")),
+            connected_at,
+            disconnected_at: Some(connected_at + Duration::from_secs(60)),
+        };
+        let newer_session = PeerConnectionSession {
+            id: PeerConnectionSessionId::random(),
+            peer_id: fixture.peer_a_id,
+            remote_host: IpAddr::from_str("1.2.3.5")?,
+            edgar_version: None,
+            connected_at: connected_at + Duration::from_secs(120),
+            disconnected_at: None,
+        };
+        let other_peer_id = PeerId::random();
+        let other_peer_session = PeerConnectionSession {
+            id: PeerConnectionSessionId::random(),
+            peer_id: other_peer_id,
+            ..Clone::clone(&older_session)
+        };
+        resources_manager.insert(fixture.peer_a_id, PeerConnectionSessions {
+            peer_id: fixture.peer_a_id,
+            sessions: vec![Clone::clone(&newer_session), Clone::clone(&older_session)],
+        }).await?;
+        resources_manager.insert(other_peer_id, PeerConnectionSessions {
+            peer_id: other_peer_id,
+            sessions: vec![other_peer_session],
+        }).await?;
+
+        let enrollment = PeerEnrollment {
+            id: PeerEnrollmentId::random(),
+            peer_id: fixture.peer_a_id,
+            reenrollment: true,
+            issued_by: Some(String::from("testUser")),
+            issued_at: connected_at + Duration::from_secs(90),
+        };
+        resources_manager.insert(enrollment.id, Clone::clone(&enrollment)).await?;
+
+        let history = get_peer_history(GetPeerHistoryParams {
+            peer: fixture.peer_a_id,
+            resources_manager: Clone::clone(&resources_manager),
+        }).await?;
+
+        assert_that!(history.sessions, elements_are![eq(&older_session), eq(&newer_session)]);
+        assert_that!(history.enrollments, elements_are![eq(&enrollment)]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_throw_error_if_peer_not_found() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let not_existing_peer_id = PeerId::random();
+
+        let result = get_peer_history(GetPeerHistoryParams {
+            peer: not_existing_peer_id,
+            resources_manager,
+        }).await;
+
+        assert_that!(result, err(eq(&GetPeerHistoryError::PeerNotFound { peer_id: not_existing_peer_id })));
+        Ok(())
+    }
+}
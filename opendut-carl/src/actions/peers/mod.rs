@@ -1,15 +1,21 @@
+pub mod append_executor_logs;
 pub mod assign_cluster;
 pub mod delete_peer_descriptor;
 pub mod generate_cleo_setup;
 pub mod generate_peer_setup;
+pub mod get_executor_logs;
+pub mod get_peer_history;
 pub mod get_peer_state;
 pub mod list_devices;
 pub mod list_peer_descriptors;
+pub mod start_executor;
+pub mod stop_executor;
 pub mod store_peer_descriptor;
+pub mod test_runs;
 pub mod unassign_cluster;
 
 #[cfg(test)]
-mod testing {
+pub(crate) mod testing {
     use rstest::*;
 
     use opendut_types::peer::executor::ExecutorDescriptors;
@@ -0,0 +1,259 @@
+use crate::actions::abort_unfinished_test_runs;
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::persistence::error::PersistenceResult;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::peer::StartExecutorError;
+use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, StartExecutorRun};
+use opendut_types::peer::configuration::OldPeerConfiguration;
+use opendut_types::peer::executor::log::ExecutorRunId;
+use opendut_types::peer::executor::test_run::{ExecutorRunOverrides, TestRun, TestRunStatus, TestRunUpdate};
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::state::PeerState;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use std::time::SystemTime;
+use tracing::{debug, error, info};
+
+pub struct StartExecutorParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub executor_id: ExecutorId,
+    pub overrides: ExecutorRunOverrides,
+    /// Stop a run of the executor, which is still in progress, instead of failing.
+    pub restart: bool,
+    pub triggered_by: Option<String>,
+}
+
+/// Creates a new run of an executor and instructs its peer to start it.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn start_executor(params: StartExecutorParams) -> Result<TestRun, StartExecutorError> {
+
+    async fn inner(params: StartExecutorParams) -> Result<TestRun, StartExecutorError> {
+        let StartExecutorParams { resources_manager, peer_messaging_broker, executor_id, overrides, restart, triggered_by } = params;
+
+        debug!("Starting executor <{executor_id}>.");
+
+        let test_run = resources_manager.resources_mut(|resources| {
+            let internal_error = |cause: crate::persistence::error::PersistenceError| StartExecutorError::Internal { executor_id, cause: cause.to_string() };
+
+            let peer_id = find_peer_of_executor(resources, executor_id)
+                .map_err(internal_error)?
+                .ok_or(StartExecutorError::ExecutorNotFound { executor_id })?;
+
+            let peer_state = resources.get::<PeerState>(peer_id)
+                .map_err(internal_error)?;
+            if !matches!(peer_state, Some(PeerState::Up { .. })) {
+                return Err(StartExecutorError::PeerNotConnected { executor_id, peer_id });
+            }
+
+            let run_in_progress = resources.list::<TestRun>()
+                .map_err(internal_error)?
+                .into_iter()
+                .find(|test_run| test_run.executor_id == executor_id && !test_run.status.is_finished());
+            if let Some(run_in_progress) = run_in_progress {
+                if restart {
+                    debug!("Restarting executor <{executor_id}>, which stops its run <{}>.", run_in_progress.id);
+                    abort_unfinished_test_runs(resources, |test_run| test_run.executor_id == executor_id)
+                        .map_err(internal_error)?;
+                } else {
+                    return Err(StartExecutorError::RunInProgress { executor_id, run_id: run_in_progress.id });
+                }
+            }
+
+            let cluster_id = resources.get::<OldPeerConfiguration>(peer_id)
+                .map_err(internal_error)?
+                .and_then(|configuration| configuration.cluster_assignment)
+                .map(|cluster_assignment| cluster_assignment.id);
+
+            let test_run = TestRun::new(ExecutorRunId::random(), executor_id, peer_id, cluster_id, triggered_by);
+            resources.insert(test_run.id, Clone::clone(&test_run))
+                .map_err(internal_error)?;
+
+            Ok(test_run)
+        }).await
+        .map_err(|cause| StartExecutorError::Internal { executor_id, cause: cause.to_string() })??;
+
+        let send_result = peer_messaging_broker.send_to_peer(
+            test_run.peer_id,
+            downstream::Message::StartExecutorRun(StartExecutorRun {
+                executor_id: Some(executor_id.into()),
+                run_id: Some(test_run.id.into()),
+                overrides: Some(overrides.into()),
+            }),
+        ).await;
+
+        if let Err(cause) = send_result {
+            let mut failed_test_run = Clone::clone(&test_run);
+            failed_test_run.apply(TestRunUpdate {
+                run_id: test_run.id,
+                executor_id,
+                status: TestRunStatus::Failed,
+                timestamp: SystemTime::now(),
+                exit_code: None,
+                results_location: None,
+            });
+            let _ignore_result = resources_manager.insert(failed_test_run.id, failed_test_run).await
+                .inspect_err(|cause| error!("Failed to mark run <{}> of executor <{executor_id}> as failed:\n  {cause}", test_run.id));
+
+            return Err(StartExecutorError::Internal { executor_id, cause: format!("Sending run <{}> to peer <{}> failed: {cause}", test_run.id, test_run.peer_id) });
+        }
+
+        info!("Started run <{}> of executor <{executor_id}> on peer <{}>.", test_run.id, test_run.peer_id);
+
+        Ok(test_run)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+/// Determines the peer, which the executor is configured on.
+pub(super) fn find_peer_of_executor(resources: &impl ResourcesStorageApi, executor_id: ExecutorId) -> PersistenceResult<Option<PeerId>> {
+    let peer_id = resources.list::<PeerDescriptor>()?
+        .into_iter()
+        .find(|peer| peer.executors.executors.iter().any(|executor| executor.id == executor_id))
+        .map(|peer| peer.id);
+    Ok(peer_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::resources::manager::ResourcesManager;
+    use googletest::prelude::*;
+    use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorKind};
+    use opendut_types::peer::executor::executable::ExecutablePath;
+    use rstest::rstest;
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    struct Setup {
+        resources_manager: ResourcesManagerRef,
+        peer_messaging_broker: PeerMessagingBrokerRef,
+        executor_id: ExecutorId,
+    }
+
+    async fn setup(fixture: Fixture) -> anyhow::Result<Setup> {
+        let settings = crate::settings::load_defaults()?;
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        );
+
+        let executor_id = ExecutorId::random();
+        let mut peer_descriptor = fixture.peer_a_descriptor;
+        peer_descriptor.executors.executors.push(ExecutorDescriptor {
+            id: executor_id,
+            kind: ExecutorKind::Executable {
+                path: ExecutablePath::try_from("/opt/tests/run.sh")?,
+                args: vec![],
+                envs: vec![],
+                working_dir: None,
+                timeout: None,
+            },
+            results_url: None,
+        });
+        resources_manager.insert(fixture.peer_a_id, peer_descriptor).await?;
+
+        Ok(Setup { resources_manager, peer_messaging_broker, executor_id })
+    }
+
+    fn params(setup: &Setup, restart: bool) -> StartExecutorParams {
+        StartExecutorParams {
+            resources_manager: Arc::clone(&setup.resources_manager),
+            peer_messaging_broker: Arc::clone(&setup.peer_messaging_broker),
+            executor_id: setup.executor_id,
+            overrides: ExecutorRunOverrides::default(),
+            restart,
+            triggered_by: Some(String::from("tester")),
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_send_a_new_run_to_the_peer(fixture: Fixture) -> anyhow::Result<()> {
+        let peer_id = fixture.peer_a_id;
+        let setup = setup(fixture).await?;
+        let (_, mut receiver) = setup.peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?, None).await?;
+        let _initial_configuration = receiver.recv().await;
+
+        let test_run = start_executor(params(&setup, false)).await?;
+
+        assert_that!(test_run.status, eq(TestRunStatus::Pending));
+        assert_that!(test_run.triggered_by, some(eq("tester")));
+        assert_that!(setup.resources_manager.get::<TestRun>(test_run.id).await?.as_ref(), some(eq(&test_run)));
+
+        let received = receiver.recv().await.unwrap()
+            .message.unwrap();
+        let downstream::Message::StartExecutorRun(StartExecutorRun { executor_id, run_id, .. }) = received else { panic!("Expected StartExecutorRun, but received: {received:?}") };
+        assert_that!(ExecutorId::try_from(executor_id.unwrap())?, eq(setup.executor_id));
+        assert_that!(ExecutorRunId::try_from(run_id.unwrap())?, eq(test_run.id));
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_only_start_an_executor_with_a_run_in_progress_when_restarting(fixture: Fixture) -> anyhow::Result<()> {
+        let peer_id = fixture.peer_a_id;
+        let setup = setup(fixture).await?;
+        let (_, _receiver) = setup.peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?, None).await?;
+
+        let first_run = start_executor(params(&setup, false)).await?;
+
+        let result = start_executor(params(&setup, false)).await;
+        assert_that!(result, err(eq(&StartExecutorError::RunInProgress { executor_id: setup.executor_id, run_id: first_run.id })));
+
+        let second_run = start_executor(params(&setup, true)).await?;
+        assert_that!(second_run.id, not(eq(first_run.id)));
+
+        let first_run = setup.resources_manager.get::<TestRun>(first_run.id).await?.unwrap();
+        assert_that!(first_run.status, eq(TestRunStatus::Aborted));
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_abort_a_running_run_when_restarting(fixture: Fixture) -> anyhow::Result<()> {
+        let peer_id = fixture.peer_a_id;
+        let setup = setup(fixture).await?;
+        let (_, _receiver) = setup.peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?, None).await?;
+
+        let first_run = start_executor(params(&setup, false)).await?;
+        let mut running_run = Clone::clone(&first_run);
+        running_run.apply(TestRunUpdate {
+            run_id: first_run.id,
+            executor_id: setup.executor_id,
+            status: TestRunStatus::Running,
+            timestamp: SystemTime::now(),
+            exit_code: None,
+            results_location: None,
+        });
+        setup.resources_manager.insert(running_run.id, running_run).await?;
+
+        let second_run = start_executor(params(&setup, true)).await?;
+
+        let first_run = setup.resources_manager.get::<TestRun>(first_run.id).await?.unwrap();
+        assert_that!(first_run.status, eq(TestRunStatus::Aborted));
+        assert_that!(first_run.finished_at, some(anything()));
+
+        let result = start_executor(params(&setup, false)).await;
+        assert_that!(result, err(eq(&StartExecutorError::RunInProgress { executor_id: setup.executor_id, run_id: second_run.id })));
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_fail_to_start_an_executor_of_a_disconnected_peer(fixture: Fixture) -> anyhow::Result<()> {
+        let peer_id = fixture.peer_a_id;
+        let setup = setup(fixture).await?;
+
+        let result = start_executor(params(&setup, false)).await;
+
+        assert_that!(result, err(eq(&StartExecutorError::PeerNotConnected { executor_id: setup.executor_id, peer_id })));
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use crate::actions::abort_pending_test_runs;
+use crate::actions::peers::start_executor::find_peer_of_executor;
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::peer::StopExecutorError;
+use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, StopExecutorRun};
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::state::PeerState;
+use tracing::{debug, error, info};
+
+pub struct StopExecutorParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub executor_id: ExecutorId,
+}
+
+/// Instructs the peer of an executor to stop its run, which is in progress.
+/// The peer then reports the run as aborted.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn stop_executor(params: StopExecutorParams) -> Result<(), StopExecutorError> {
+
+    async fn inner(params: StopExecutorParams) -> Result<(), StopExecutorError> {
+        let StopExecutorParams { resources_manager, peer_messaging_broker, executor_id } = params;
+
+        debug!("Stopping executor <{executor_id}>.");
+
+        let peer_id = resources_manager.resources_mut(|resources| {
+            let internal_error = |cause: crate::persistence::error::PersistenceError| StopExecutorError::Internal { executor_id, cause: cause.to_string() };
+
+            let peer_id = find_peer_of_executor(resources, executor_id)
+                .map_err(internal_error)?
+                .ok_or(StopExecutorError::ExecutorNotFound { executor_id })?;
+
+            let peer_state = resources.get::<PeerState>(peer_id)
+                .map_err(internal_error)?;
+            if !matches!(peer_state, Some(PeerState::Up { .. })) {
+                return Err(StopExecutorError::PeerNotConnected { executor_id, peer_id });
+            }
+
+            abort_pending_test_runs(resources, |test_run| test_run.executor_id == executor_id)
+                .map_err(internal_error)?;
+
+            Ok(peer_id)
+        }).await
+        .map_err(|cause| StopExecutorError::Internal { executor_id, cause: cause.to_string() })??;
+
+        peer_messaging_broker.send_to_peer(
+            peer_id,
+            downstream::Message::StopExecutorRun(StopExecutorRun {
+                executor_id: Some(executor_id.into()),
+            }),
+        ).await
+        .map_err(|cause| StopExecutorError::Internal { executor_id, cause: format!("Sending stop request to peer <{peer_id}> failed: {cause}") })?;
+
+        info!("Requested peer <{peer_id}> to stop executor <{executor_id}>.");
+
+        Ok(())
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
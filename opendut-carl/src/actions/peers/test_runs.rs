@@ -0,0 +1,189 @@
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use opendut_carl_api::carl::peer::{GetTestRunError, ListTestRunsError};
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::executor::log::ExecutorRunId;
+use opendut_types::peer::executor::test_run::{TestRun, TestRunStatus, TestRunUpdate};
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::PeerId;
+use std::ops::Not;
+use tracing::{debug, error, warn};
+
+/// Marks the matching runs as aborted, which have not been started yet.
+/// Runs which have been started are reported as aborted by the peer itself, when it stops the executors.
+pub fn abort_pending_test_runs(resources: &mut impl ResourcesStorageApi, matches: impl Fn(&TestRun) -> bool) -> PersistenceResult<()> {
+    abort_test_runs(resources, |test_run| test_run.status == TestRunStatus::Pending && matches(test_run))
+}
+
+/// Marks the matching runs as aborted, which have not finished yet, including runs which have been started.
+/// Used when the peer will not report the end of these runs, e.g. because it disconnected or the runs are replaced by a restart.
+pub fn abort_unfinished_test_runs(resources: &mut impl ResourcesStorageApi, matches: impl Fn(&TestRun) -> bool) -> PersistenceResult<()> {
+    abort_test_runs(resources, |test_run| test_run.status.is_finished().not() && matches(test_run))
+}
+
+fn abort_test_runs(resources: &mut impl ResourcesStorageApi, matches: impl Fn(&TestRun) -> bool) -> PersistenceResult<()> {
+    let test_runs = resources.list::<TestRun>()?
+        .into_iter()
+        .filter(|test_run| matches(test_run));
+
+    for test_run in test_runs {
+        debug!("Aborting test run <{}> of executor <{}> with status '{}'.", test_run.id, test_run.executor_id, test_run.status);
+        let test_run = TestRun {
+            status: TestRunStatus::Aborted,
+            finished_at: Some(std::time::SystemTime::now()),
+            ..test_run
+        };
+        resources.insert(test_run.id, test_run)?;
+    }
+    Ok(())
+}
+
+pub struct UpdateTestRunParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_id: PeerId,
+    pub update: TestRunUpdate,
+}
+
+/// Applies the progress of a run, as reported by a peer.
+/// If CARL did not create the run, e.g. because it was restarted in the meantime, the run is created without a cluster.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn update_test_run(params: UpdateTestRunParams) -> Result<(), PersistenceError> {
+
+    async fn inner(params: UpdateTestRunParams) -> Result<(), PersistenceError> {
+        let UpdateTestRunParams { resources_manager, peer_id, update } = params;
+        let run_id = update.run_id;
+
+        debug!("Updating test run <{run_id}> of executor <{}> to status '{}'.", update.executor_id, update.status);
+
+        resources_manager.resources_mut(|resources| {
+            let mut test_run = resources.get::<TestRun>(run_id)?
+                .unwrap_or_else(|| {
+                    warn!("Peer <{peer_id}> reported test run <{run_id}>, which is not known. Creating it.");
+                    TestRun::new(run_id, update.executor_id, peer_id, None, None)
+                });
+
+            if test_run.peer_id != peer_id {
+                warn!("Peer <{peer_id}> reported test run <{run_id}>, which belongs to peer <{}>. Ignoring.", test_run.peer_id);
+                return Ok(());
+            }
+
+            test_run.apply(update);
+            resources.insert(run_id, test_run)
+        }).await?
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ListTestRunsParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub cluster_id: Option<ClusterId>,
+    pub executor_id: Option<ExecutorId>,
+}
+
+/// Returns the matching test runs, ordered from newest to oldest.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_test_runs(params: ListTestRunsParams) -> Result<Vec<TestRun>, ListTestRunsError> {
+
+    async fn inner(params: ListTestRunsParams) -> Result<Vec<TestRun>, ListTestRunsError> {
+        let ListTestRunsParams { resources_manager, cluster_id, executor_id } = params;
+
+        let mut test_runs = resources_manager.list::<TestRun>().await
+            .map_err(|cause| ListTestRunsError::Internal { cause: cause.to_string() })?
+            .into_iter()
+            .filter(|test_run| cluster_id.is_none() || test_run.cluster_id == cluster_id)
+            .filter(|test_run| executor_id.is_none() || Some(test_run.executor_id) == executor_id)
+            .collect::<Vec<_>>();
+
+        test_runs.sort_by_key(|test_run| std::cmp::Reverse(test_run.created_at));
+
+        Ok(test_runs)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct GetTestRunParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub run_id: ExecutorRunId,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn get_test_run(params: GetTestRunParams) -> Result<TestRun, GetTestRunError> {
+
+    async fn inner(params: GetTestRunParams) -> Result<TestRun, GetTestRunError> {
+        let GetTestRunParams { resources_manager, run_id } = params;
+
+        resources_manager.get::<TestRun>(run_id).await
+            .map_err(|cause| GetTestRunError::Internal { run_id, cause: cause.to_string() })?
+            .ok_or(GetTestRunError::TestRunNotFound { run_id })
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::manager::ResourcesManager;
+    use googletest::prelude::*;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+
+    #[tokio::test]
+    async fn should_apply_updates_to_a_created_test_run() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let peer_id = PeerId::random();
+        let test_run = TestRun::new(ExecutorRunId::random(), ExecutorId::random(), peer_id, Some(ClusterId::random()), Some(String::from("tester")));
+        resources_manager.insert(test_run.id, Clone::clone(&test_run)).await?;
+
+        update_test_run(UpdateTestRunParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id,
+            update: TestRunUpdate {
+                run_id: test_run.id,
+                executor_id: test_run.executor_id,
+                status: TestRunStatus::Succeeded,
+                timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(10),
+                exit_code: Some(0),
+                results_location: None,
+            },
+        }).await?;
+
+        let result = get_test_run(GetTestRunParams { resources_manager, run_id: test_run.id }).await?;
+        assert_that!(result.status, eq(TestRunStatus::Succeeded));
+        assert_that!(result.exit_code, some(eq(0)));
+        assert_that!(result.triggered_by, some(eq("tester")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_list_test_runs_of_an_executor_from_newest_to_oldest() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        let executor_id = ExecutorId::random();
+
+        let test_run = |executor_id: ExecutorId, created_at_secs: u64| TestRun {
+            created_at: SystemTime::UNIX_EPOCH + Duration::from_secs(created_at_secs),
+            ..TestRun::new(ExecutorRunId::random(), executor_id, PeerId::random(), None, None)
+        };
+        let older = test_run(executor_id, 10);
+        let newer = test_run(executor_id, 20);
+        let other = test_run(ExecutorId::random(), 30);
+        for test_run in [&older, &newer, &other] {
+            resources_manager.insert(test_run.id, Clone::clone(test_run)).await?;
+        }
+
+        let result = list_test_runs(ListTestRunsParams {
+            resources_manager,
+            cluster_id: None,
+            executor_id: Some(executor_id),
+        }).await?;
+
+        assert_that!(result, elements_are![eq(&newer), eq(&older)]);
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::actions::abort_pending_test_runs;
 use crate::persistence::error::PersistenceError;
 use crate::resources::manager::ResourcesManagerRef;
 use opendut_types::peer::state::{PeerState, PeerUpState};
@@ -38,6 +39,9 @@ pub async fn unassign_cluster(params: UnassignClusterParams) -> Result<(), Unass
             }
         }
 
+        abort_pending_test_runs(resources, |test_run| test_run.peer_id == peer_id)
+            .map_err(|source| UnassignClusterError::Persistence { peer_id, source })?;
+
         Ok(())
     }).await
     .map_err(|source| UnassignClusterError::Persistence { peer_id, source })??;
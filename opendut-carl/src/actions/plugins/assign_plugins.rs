@@ -0,0 +1,127 @@
+use tracing::{debug, error, info};
+
+use opendut_carl_api::carl::plugin::AssignPluginsError;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::plugin::{PeerPluginAssignment, PluginDescriptor, PluginId};
+
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct AssignPluginsParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_id: PeerId,
+    /// Replaces the plugins previously assigned to the peer. EDGAR runs the plugins in this order.
+    pub plugin_ids: Vec<PluginId>,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn assign_plugins(params: AssignPluginsParams) -> Result<(), AssignPluginsError> {
+
+    async fn inner(params: AssignPluginsParams) -> Result<(), AssignPluginsError> {
+
+        let AssignPluginsParams { resources_manager, peer_id, plugin_ids } = params;
+
+        debug!("Assigning {} plugin(s) to peer <{peer_id}>.", plugin_ids.len());
+
+        resources_manager.resources_mut(|resources| {
+            resources.get::<PeerDescriptor>(peer_id)
+                .map_err(|cause| AssignPluginsError::Internal { peer_id, cause: cause.to_string() })?
+                .ok_or(AssignPluginsError::PeerNotFound { peer_id })?;
+
+            for plugin_id in &plugin_ids {
+                resources.get::<PluginDescriptor>(*plugin_id)
+                    .map_err(|cause| AssignPluginsError::Internal { peer_id, cause: cause.to_string() })?
+                    .ok_or(AssignPluginsError::PluginNotFound { peer_id, plugin_id: *plugin_id })?;
+            }
+
+            let mut plugins: Vec<PluginId> = Vec::with_capacity(plugin_ids.len());
+            for plugin_id in plugin_ids {
+                if !plugins.contains(&plugin_id) {
+                    plugins.push(plugin_id);
+                }
+            }
+
+            resources.insert(peer_id, PeerPluginAssignment { peer_id, plugins })
+                .map_err(|cause| AssignPluginsError::Internal { peer_id, cause: cause.to_string() })
+        }).await
+        .map_err(|cause| AssignPluginsError::Internal { peer_id, cause: cause.to_string() })??;
+
+        info!("Successfully assigned plugins to peer <{peer_id}>.");
+
+        Ok(())
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::actions::plugins::testing::{plugin_fixture, store_test_plugin, PluginFixture};
+    use crate::actions::{list_plugins, ListPluginsParams};
+    use crate::resources::manager::ResourcesManager;
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_list_the_assigned_plugins_in_order(fixture: Fixture, plugin_fixture: PluginFixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        resources_manager.insert(fixture.peer_a_id, Clone::clone(&fixture.peer_a_descriptor)).await?;
+
+        let plugin_a = store_test_plugin(&resources_manager, &plugin_fixture, "plugin-a", "1.0.0").await?;
+        let plugin_b = store_test_plugin(&resources_manager, &plugin_fixture, "plugin-b", "1.0.0").await?;
+        store_test_plugin(&resources_manager, &plugin_fixture, "plugin-c", "1.0.0").await?;
+
+        assign_plugins(AssignPluginsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: fixture.peer_a_id,
+            plugin_ids: vec![plugin_b.id, plugin_a.id, plugin_b.id],
+        }).await?;
+
+        let assigned = list_plugins(ListPluginsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: Some(fixture.peer_a_id),
+        }).await?;
+        assert_that!(assigned, elements_are![eq(&plugin_b), eq(&plugin_a)]);
+
+        let all = list_plugins(ListPluginsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: None,
+        }).await?;
+        assert_that!(all, len(eq(3)));
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_assign_unknown_plugins_or_to_unknown_peers(fixture: Fixture, plugin_fixture: PluginFixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let plugin = store_test_plugin(&resources_manager, &plugin_fixture, "plugin-a", "1.0.0").await?;
+
+        let result = assign_plugins(AssignPluginsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: fixture.peer_a_id,
+            plugin_ids: vec![plugin.id],
+        }).await;
+        assert_that!(result, err(eq(&AssignPluginsError::PeerNotFound { peer_id: fixture.peer_a_id })));
+
+        resources_manager.insert(fixture.peer_a_id, Clone::clone(&fixture.peer_a_descriptor)).await?;
+
+        let unknown_plugin_id = PluginId::random();
+        let result = assign_plugins(AssignPluginsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: fixture.peer_a_id,
+            plugin_ids: vec![plugin.id, unknown_plugin_id],
+        }).await;
+        assert_that!(result, err(eq(&AssignPluginsError::PluginNotFound { peer_id: fixture.peer_a_id, plugin_id: unknown_plugin_id })));
+        assert_that!(resources_manager.get::<PeerPluginAssignment>(fixture.peer_a_id).await?, none());
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use tracing::{debug, error, info};
+
+use opendut_carl_api::carl::plugin::DeletePluginError;
+use opendut_types::plugin::{PeerPluginAssignment, PluginArtifact, PluginDescriptor, PluginId};
+
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct DeletePluginParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub plugin_id: PluginId,
+}
+
+/// Deletes a plugin from the plugin registry and removes it from the plugins assigned to peers.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_plugin(params: DeletePluginParams) -> Result<PluginDescriptor, DeletePluginError> {
+
+    async fn inner(params: DeletePluginParams) -> Result<PluginDescriptor, DeletePluginError> {
+
+        let plugin_id = params.plugin_id;
+
+        debug!("Deleting plugin <{plugin_id}>.");
+
+        let plugin = params.resources_manager.resources_mut(|resources| {
+            let assignments = resources.list::<PeerPluginAssignment>()
+                .map_err(|cause| DeletePluginError::Internal { plugin_id, cause: cause.to_string() })?;
+
+            for mut assignment in assignments {
+                if assignment.plugins.contains(&plugin_id) {
+                    assignment.plugins.retain(|assigned| *assigned != plugin_id);
+                    resources.insert(assignment.peer_id, assignment)
+                        .map_err(|cause| DeletePluginError::Internal { plugin_id, cause: cause.to_string() })?;
+                }
+            }
+
+            resources.remove::<PluginArtifact>(plugin_id)
+                .map_err(|cause| DeletePluginError::Internal { plugin_id, cause: cause.to_string() })?;
+
+            resources.remove::<PluginDescriptor>(plugin_id)
+                .map_err(|cause| DeletePluginError::Internal { plugin_id, cause: cause.to_string() })?
+                .ok_or(DeletePluginError::PluginNotFound { plugin_id })
+        }).await
+        .map_err(|cause| DeletePluginError::Internal { plugin_id, cause: cause.to_string() })??;
+
+        info!("Successfully deleted plugin '{}' in version '{}' <{plugin_id}>.", plugin.name, plugin.version);
+
+        Ok(plugin)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::actions::plugins::testing::{plugin_fixture, store_test_plugin, PluginFixture};
+    use crate::actions::{assign_plugins, AssignPluginsParams};
+    use crate::resources::manager::ResourcesManager;
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_remove_a_deleted_plugin_from_the_assigned_plugins(fixture: Fixture, plugin_fixture: PluginFixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        resources_manager.insert(fixture.peer_a_id, Clone::clone(&fixture.peer_a_descriptor)).await?;
+
+        let plugin_a = store_test_plugin(&resources_manager, &plugin_fixture, "plugin-a", "1.0.0").await?;
+        let plugin_b = store_test_plugin(&resources_manager, &plugin_fixture, "plugin-b", "1.0.0").await?;
+
+        assign_plugins(AssignPluginsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: fixture.peer_a_id,
+            plugin_ids: vec![plugin_a.id, plugin_b.id],
+        }).await?;
+
+        let deleted = delete_plugin(DeletePluginParams {
+            resources_manager: Arc::clone(&resources_manager),
+            plugin_id: plugin_a.id,
+        }).await?;
+
+        assert_that!(deleted, eq(&plugin_a));
+        assert_that!(resources_manager.get::<PluginArtifact>(plugin_a.id).await?, none());
+        assert_that!(
+            resources_manager.get::<PeerPluginAssignment>(fixture.peer_a_id).await?.map(|assignment| assignment.plugins),
+            some(elements_are![eq(&plugin_b.id)])
+        );
+
+        let result = delete_plugin(DeletePluginParams {
+            resources_manager: Arc::clone(&resources_manager),
+            plugin_id: plugin_a.id,
+        }).await;
+        assert_that!(result, err(eq(&DeletePluginError::PluginNotFound { plugin_id: plugin_a.id })));
+        Ok(())
+    }
+}
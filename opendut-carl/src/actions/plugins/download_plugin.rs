@@ -0,0 +1,39 @@
+use tracing::{debug, error};
+
+use opendut_carl_api::carl::plugin::DownloadPluginError;
+use opendut_types::plugin::{PluginArtifact, PluginDescriptor, PluginId};
+
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct DownloadPluginParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub plugin_id: PluginId,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn download_plugin(params: DownloadPluginParams) -> Result<(PluginDescriptor, PluginArtifact), DownloadPluginError> {
+
+    async fn inner(params: DownloadPluginParams) -> Result<(PluginDescriptor, PluginArtifact), DownloadPluginError> {
+
+        let plugin_id = params.plugin_id;
+
+        debug!("Retrieving plugin <{plugin_id}>.");
+
+        let (descriptor, artifact) = params.resources_manager.resources(|resources| {
+            let descriptor = resources.get::<PluginDescriptor>(plugin_id)?;
+            let artifact = resources.get::<PluginArtifact>(plugin_id)?;
+            Ok((descriptor, artifact))
+        }).await
+        .map_err(|cause| DownloadPluginError::Internal { plugin_id, cause: cause.to_string() })?;
+
+        match (descriptor, artifact) {
+            (Some(descriptor), Some(artifact)) => Ok((descriptor, artifact)),
+            (Some(_), None) => Err(DownloadPluginError::Internal { plugin_id, cause: String::from("The WebAssembly component of the plugin is missing.") }),
+            (None, _) => Err(DownloadPluginError::PluginNotFound { plugin_id }),
+        }
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
@@ -0,0 +1,52 @@
+use tracing::{debug, error};
+
+use opendut_carl_api::carl::plugin::ListPluginsError;
+use opendut_types::peer::PeerId;
+use opendut_types::plugin::{PeerPluginAssignment, PluginDescriptor};
+
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct ListPluginsParams {
+    pub resources_manager: ResourcesManagerRef,
+    /// Only list the plugins assigned to this peer, in the order in which they are run.
+    pub peer_id: Option<PeerId>,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_plugins(params: ListPluginsParams) -> Result<Vec<PluginDescriptor>, ListPluginsError> {
+
+    async fn inner(params: ListPluginsParams) -> Result<Vec<PluginDescriptor>, ListPluginsError> {
+
+        debug!("Querying plugins.");
+
+        let plugins = params.resources_manager.resources(|resources| {
+            match params.peer_id {
+                None => {
+                    let mut plugins = resources.list::<PluginDescriptor>()?;
+                    plugins.sort_by(|a, b| a.name.cmp(&b.name).then(a.uploaded_at.cmp(&b.uploaded_at)));
+                    Ok(plugins)
+                }
+                Some(peer_id) => {
+                    let assigned = resources.get::<PeerPluginAssignment>(peer_id)?
+                        .map(|assignment| assignment.plugins)
+                        .unwrap_or_default();
+
+                    let mut plugins = Vec::with_capacity(assigned.len());
+                    for plugin_id in assigned {
+                        if let Some(plugin) = resources.get::<PluginDescriptor>(plugin_id)? {
+                            plugins.push(plugin);
+                        }
+                    }
+                    Ok(plugins)
+                }
+            }
+        }).await
+        .map_err(|cause| ListPluginsError::Internal { cause: cause.to_string() })?;
+
+        Ok(plugins)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
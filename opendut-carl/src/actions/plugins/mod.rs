@@ -0,0 +1,44 @@
+pub mod assign_plugins;
+pub mod delete_plugin;
+pub mod download_plugin;
+pub mod list_plugins;
+pub mod store_plugin;
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use std::sync::Arc;
+
+    use ed25519_dalek::SigningKey;
+    use rstest::*;
+
+    use opendut_carl_api::carl::plugin::StorePluginError;
+    use opendut_types::plugin::{PluginDescriptor, PluginKind};
+
+    use crate::actions::{store_plugin, StorePluginParams};
+    use crate::plugin::PluginSigning;
+    use crate::resources::manager::ResourcesManagerRef;
+
+    pub struct PluginFixture {
+        pub plugin_signing: PluginSigning,
+    }
+
+    #[fixture]
+    pub fn plugin_fixture() -> PluginFixture {
+        PluginFixture {
+            plugin_signing: PluginSigning::Enabled { signing_key: Arc::new(SigningKey::from_bytes(&[7; 32])) },
+        }
+    }
+
+    pub async fn store_test_plugin(resources_manager: &ResourcesManagerRef, fixture: &PluginFixture, name: &str, version: &str) -> Result<PluginDescriptor, StorePluginError> {
+        store_plugin(StorePluginParams {
+            resources_manager: Arc::clone(resources_manager),
+            plugin_signing: Clone::clone(&fixture.plugin_signing),
+            name: name.to_owned(),
+            version: version.to_owned(),
+            kind: PluginKind::Setup,
+            binary: format!("{name}-{version}").into_bytes(),
+            manifest: None,
+            uploaded_by: Some(String::from("testUser")),
+        }).await
+    }
+}
@@ -0,0 +1,172 @@
+use std::time::SystemTime;
+
+use sha2::{Digest, Sha256};
+use tracing::{debug, error, info};
+
+use opendut_carl_api::carl::plugin::StorePluginError;
+use opendut_types::plugin::{PluginArtifact, PluginDescriptor, PluginId, PluginKind};
+
+use crate::plugin::PluginSigning;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+
+pub struct StorePluginParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub plugin_signing: PluginSigning,
+    pub name: String,
+    pub version: String,
+    pub kind: PluginKind,
+    pub binary: Vec<u8>,
+    pub manifest: Option<String>,
+    pub uploaded_by: Option<String>,
+}
+
+/// Stores a new plugin in the plugin registry and signs it, so EDGAR can verify it after downloading.
+/// A plugin is never replaced. Changes to a plugin need to be uploaded with a new version.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn store_plugin(params: StorePluginParams) -> Result<PluginDescriptor, StorePluginError> {
+
+    async fn inner(params: StorePluginParams) -> Result<PluginDescriptor, StorePluginError> {
+
+        let StorePluginParams { resources_manager, plugin_signing, name, version, kind, binary, manifest, uploaded_by } = params;
+
+        debug!("Storing plugin '{name}' in version '{version}'.");
+
+        if let PluginSigning::Disabled = plugin_signing {
+            return Err(StorePluginError::SigningDisabled);
+        }
+
+        validate(&name, &version, &binary)
+            .map_err(|cause| StorePluginError::IllegalPlugin { name: Clone::clone(&name), version: Clone::clone(&version), cause })?;
+
+        let mut descriptor = PluginDescriptor {
+            id: PluginId::random(),
+            name: Clone::clone(&name),
+            version: Clone::clone(&version),
+            kind,
+            checksum: Sha256::digest(&binary).to_vec(),
+            manifest,
+            signature: Vec::new(),
+            uploaded_by,
+            uploaded_at: SystemTime::now(),
+        };
+        descriptor.signature = plugin_signing.sign(&descriptor)
+            .ok_or(StorePluginError::SigningDisabled)?;
+
+        let plugin_id = descriptor.id;
+
+        resources_manager.resources_mut(|resources| {
+            let existing = resources.list::<PluginDescriptor>()
+                .map_err(|cause| StorePluginError::Internal { name: Clone::clone(&name), version: Clone::clone(&version), cause: cause.to_string() })?
+                .into_iter()
+                .find(|other| other.name == name && other.version == version);
+
+            if let Some(existing) = existing {
+                return Err(StorePluginError::AlreadyExists { name: Clone::clone(&name), version: Clone::clone(&version), other_id: existing.id });
+            }
+
+            resources.insert(plugin_id, Clone::clone(&descriptor))
+                .map_err(|cause| StorePluginError::Internal { name: Clone::clone(&name), version: Clone::clone(&version), cause: cause.to_string() })?;
+            resources.insert(plugin_id, PluginArtifact { id: plugin_id, binary })
+                .map_err(|cause| StorePluginError::Internal { name: Clone::clone(&name), version: Clone::clone(&version), cause: cause.to_string() })?;
+            Ok(())
+        }).await
+        .map_err(|cause| StorePluginError::Internal { name: Clone::clone(&name), version: Clone::clone(&version), cause: cause.to_string() })??;
+
+        info!("Successfully stored plugin '{name}' in version '{version}' as <{plugin_id}>.");
+
+        Ok(descriptor)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+/// The name is used as file name on the peers, so only characters are allowed, which are safe in file names.
+fn validate(name: &str, version: &str, binary: &[u8]) -> Result<(), String> {
+    let is_allowed = |character: char| character.is_ascii_alphanumeric() || matches!(character, '-' | '_' | '.');
+
+    if name.is_empty() || name.starts_with('.') || !name.chars().all(is_allowed) {
+        return Err(String::from("The name must not be empty, must not start with a dot and may only contain ASCII letters, digits, '-', '_' and '.'."));
+    }
+    if version.is_empty() || !version.chars().all(is_allowed) {
+        return Err(String::from("The version must not be empty and may only contain ASCII letters, digits, '-', '_' and '.'."));
+    }
+    if binary.is_empty() {
+        return Err(String::from("The WebAssembly component must not be empty."));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ed25519_dalek::{Signature, VerifyingKey};
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::actions::plugins::testing::{plugin_fixture, store_test_plugin, PluginFixture};
+    use crate::resources::manager::ResourcesManager;
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_store_a_signed_plugin(plugin_fixture: PluginFixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let plugin = store_test_plugin(&resources_manager, &plugin_fixture, "can-setup", "1.0.0").await?;
+
+        assert_that!(plugin.checksum, eq(&Sha256::digest(b"can-setup-1.0.0").to_vec()));
+        assert_that!(plugin.uploaded_by, some(eq("testUser")));
+
+        let verifying_key = VerifyingKey::from_bytes(&plugin_fixture.plugin_signing.verifying_key().unwrap().0)?;
+        let signature = Signature::from_slice(&plugin.signature)?;
+        assert_that!(verifying_key.verify_strict(&plugin.signed_content(), &signature), ok(anything()));
+
+        assert_that!(resources_manager.get::<PluginDescriptor>(plugin.id).await?, some(eq(&plugin)));
+        let artifact = resources_manager.get::<PluginArtifact>(plugin.id).await?;
+        assert_that!(artifact.map(|artifact| artifact.binary), some(eq(&b"can-setup-1.0.0".to_vec())));
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_store_a_plugin_with_an_existing_name_and_version(plugin_fixture: PluginFixture) -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let existing = store_test_plugin(&resources_manager, &plugin_fixture, "can-setup", "1.0.0").await?;
+
+        let result = store_test_plugin(&resources_manager, &plugin_fixture, "can-setup", "1.0.0").await;
+        assert_that!(result, err(eq(&StorePluginError::AlreadyExists { name: String::from("can-setup"), version: String::from("1.0.0"), other_id: existing.id })));
+
+        store_test_plugin(&resources_manager, &plugin_fixture, "can-setup", "1.1.0").await?;
+        assert_that!(resources_manager.list::<PluginDescriptor>().await?, len(eq(2)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_store_a_plugin_without_signing_key_or_with_illegal_name() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new_in_memory();
+
+        let params = |plugin_signing: PluginSigning, name: &str| StorePluginParams {
+            resources_manager: Arc::clone(&resources_manager),
+            plugin_signing,
+            name: name.to_owned(),
+            version: String::from("1.0.0"),
+            kind: PluginKind::Service,
+            binary: vec![1, 2, 3],
+            manifest: None,
+            uploaded_by: None,
+        };
+
+        let result = store_plugin(params(PluginSigning::Disabled, "can-setup")).await;
+        assert_that!(result, err(eq(&StorePluginError::SigningDisabled)));
+
+        let result = store_plugin(params(plugin_fixture().plugin_signing, "../can-setup")).await;
+        assert!(matches!(result, Err(StorePluginError::IllegalPlugin { .. })));
+
+        assert_that!(resources_manager.list::<PluginDescriptor>().await?, empty());
+        Ok(())
+    }
+}
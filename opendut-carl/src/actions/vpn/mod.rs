@@ -0,0 +1 @@
+pub mod reconcile_vpn;
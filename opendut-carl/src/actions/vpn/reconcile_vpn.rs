@@ -0,0 +1,246 @@
+use std::collections::HashSet;
+
+use opendut_carl_api::carl::cluster::ReconcileVpnError;
+use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::peer::configuration::OldPeerConfiguration;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::vpn::reconciliation::{VpnDrift, VpnDriftEntry, VpnDriftResolution, VpnReconciliationReport};
+use opendut_vpn::VpnManagementClient;
+use tracing::{debug, error, info, warn};
+
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::storage::ResourcesStorageApi;
+use crate::vpn::Vpn;
+
+pub struct ReconcileVpnParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub vpn: Vpn,
+    pub dry_run: bool,
+}
+
+/// Compares the deployed clusters and the peers in CARL with the resources in the VPN service.
+/// Orphaned resources are removed from the VPN service, unless it is a dry run.
+/// Missing resources are only reported, as they are recreated when deploying the cluster again or when generating a new PeerSetup.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn reconcile_vpn(params: ReconcileVpnParams) -> Result<VpnReconciliationReport, ReconcileVpnError> {
+
+    async fn inner(params: ReconcileVpnParams) -> Result<VpnReconciliationReport, ReconcileVpnError> {
+        let ReconcileVpnParams { resources_manager, vpn, dry_run } = params;
+
+        let Vpn::Enabled { vpn_client } = vpn else {
+            return Err(ReconcileVpnError::VpnDisabled);
+        };
+
+        debug!("Reconciling resources of the VPN service with CARL.");
+
+        //query the VPN service before CARL, so that resources which were just created in the VPN service, but are not yet stored in CARL, are not mistaken for orphans
+        let vpn_resources = vpn_client.list_resources().await
+            .map_err(|cause| ReconcileVpnError::Internal { cause: cause.to_string() })?
+            .ok_or(ReconcileVpnError::NotSupported)?;
+
+        let (peers, deployed_clusters, assigned_clusters) = resources_manager.resources(|resources| {
+            let peers = resources.list::<PeerDescriptor>()?
+                .into_iter()
+                .map(|peer| peer.id)
+                .collect::<HashSet<PeerId>>();
+
+            let deployed_clusters = resources.list::<ClusterDeployment>()?
+                .into_iter()
+                .map(|deployment| deployment.id)
+                .collect::<HashSet<ClusterId>>();
+
+            //a cluster is only created in the VPN service, when it is assigned to its peers, so deployments waiting for their peers are not missing
+            let mut assigned_clusters = HashSet::new();
+            for cluster_id in &deployed_clusters {
+                if let Some(configuration) = resources.get::<ClusterConfiguration>(*cluster_id)? {
+                    let is_assigned = resources.get::<OldPeerConfiguration>(configuration.leader)?
+                        .and_then(|old_peer_configuration| old_peer_configuration.cluster_assignment)
+                        .is_some_and(|cluster_assignment| cluster_assignment.id == *cluster_id);
+                    if is_assigned {
+                        assigned_clusters.insert(*cluster_id);
+                    }
+                }
+            }
+            Ok((peers, deployed_clusters, assigned_clusters))
+        }).await
+            .map_err(|cause| ReconcileVpnError::Internal { cause: cause.to_string() })?;
+
+        let vpn_clusters = vpn_resources.clusters.into_iter().collect::<HashSet<_>>();
+        let vpn_peers = vpn_resources.peers.into_iter().collect::<HashSet<_>>();
+
+        let drifts = vpn_clusters.difference(&deployed_clusters).map(|&cluster_id| VpnDrift::OrphanedCluster { cluster_id })
+            .chain(assigned_clusters.difference(&vpn_clusters).map(|&cluster_id| VpnDrift::MissingCluster { cluster_id }))
+            .chain(vpn_peers.difference(&peers).map(|&peer_id| VpnDrift::OrphanedPeer { peer_id }))
+            .chain(peers.difference(&vpn_peers).map(|&peer_id| VpnDrift::MissingPeer { peer_id }))
+            .collect::<Vec<_>>();
+
+        let mut entries = Vec::with_capacity(drifts.len());
+        for drift in drifts {
+            let resolution = if dry_run {
+                VpnDriftResolution::Reported
+            } else {
+                resolve_drift(vpn_client.as_ref(), &drift).await
+            };
+            entries.push(VpnDriftEntry { drift, resolution });
+        }
+
+        if entries.is_empty() {
+            debug!("Resources of the VPN service are in sync with CARL.");
+        } else {
+            info!("Found {} difference(s) between the resources of the VPN service and CARL.", entries.len());
+        }
+
+        Ok(VpnReconciliationReport {
+            dry_run,
+            drifts: entries,
+        })
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+async fn resolve_drift(vpn_client: &(dyn VpnManagementClient + Send + Sync), drift: &VpnDrift) -> VpnDriftResolution {
+    let result = match drift {
+        VpnDrift::OrphanedCluster { cluster_id } => vpn_client.delete_cluster(*cluster_id).await
+            .map_err(|cause| cause.to_string()),
+        VpnDrift::OrphanedPeer { peer_id } => vpn_client.delete_peer(*peer_id).await
+            .map_err(|cause| cause.to_string()),
+        VpnDrift::MissingCluster { .. } | VpnDrift::MissingPeer { .. } => {
+            return VpnDriftResolution::Reported;
+        }
+    };
+
+    match result {
+        Ok(()) => {
+            info!("Resolved drift by removing the orphaned resource from the VPN service: {drift}");
+            VpnDriftResolution::Removed
+        }
+        Err(cause) => {
+            warn!("Failed to remove the orphaned resource from the VPN service: {drift}\n  {cause}");
+            VpnDriftResolution::RemovalFailed { cause }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use opendut_types::cluster::ClusterName;
+    use opendut_types::vpn::VpnPeerConfiguration;
+    use opendut_vpn::{ClusterTraffic, CreateClusterError, CreatePeerError, CreateVpnPeerConfigurationError, DeleteClusterError, DeletePeerError, ListResourcesError, VpnResources};
+
+    use super::*;
+    use crate::actions::peers::testing::{fixture, Fixture};
+    use crate::resources::manager::ResourcesManager;
+
+    #[derive(Default)]
+    struct FakeVpnClient {
+        resources: VpnResources,
+        deleted_clusters: Mutex<Vec<ClusterId>>,
+        deleted_peers: Mutex<Vec<PeerId>>,
+    }
+
+    #[tonic::async_trait]
+    impl VpnManagementClient for FakeVpnClient {
+        async fn create_cluster(&self, _: ClusterId, _: &[PeerId], _: &ClusterTraffic) -> std::result::Result<(), CreateClusterError> { Ok(()) }
+        async fn delete_cluster(&self, cluster_id: ClusterId) -> std::result::Result<(), DeleteClusterError> {
+            self.deleted_clusters.lock().unwrap().push(cluster_id);
+            Ok(())
+        }
+        async fn create_peer(&self, _: PeerId) -> std::result::Result<(), CreatePeerError> { Ok(()) }
+        async fn delete_peer(&self, peer_id: PeerId) -> std::result::Result<(), DeletePeerError> {
+            self.deleted_peers.lock().unwrap().push(peer_id);
+            Ok(())
+        }
+        async fn generate_vpn_peer_configuration(&self, _: PeerId) -> std::result::Result<VpnPeerConfiguration, CreateVpnPeerConfigurationError> {
+            Ok(VpnPeerConfiguration::Disabled)
+        }
+        async fn list_resources(&self) -> std::result::Result<Option<VpnResources>, ListResourcesError> {
+            Ok(Some(Clone::clone(&self.resources)))
+        }
+    }
+
+    async fn setup(fixture: &Fixture, vpn_resources: VpnResources) -> anyhow::Result<(ResourcesManagerRef, Arc<FakeVpnClient>, ClusterId)> {
+        let resources_manager = ResourcesManager::new_in_memory();
+        resources_manager.insert(fixture.peer_a_id, Clone::clone(&fixture.peer_a_descriptor)).await?;
+
+        let deployed_cluster_id = ClusterId::random();
+        resources_manager.insert(deployed_cluster_id, ClusterConfiguration {
+            id: deployed_cluster_id,
+            name: ClusterName::try_from("DeployedCluster")?,
+            leader: fixture.peer_a_id,
+            devices: HashSet::from([fixture.peer_a_device_1]),
+        }).await?;
+        resources_manager.insert(deployed_cluster_id, ClusterDeployment { id: deployed_cluster_id }).await?;
+
+        let vpn_client = Arc::new(FakeVpnClient { resources: vpn_resources, ..Default::default() });
+        Ok((resources_manager, vpn_client, deployed_cluster_id))
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_report_orphans_without_removing_them_in_a_dry_run(fixture: Fixture) -> anyhow::Result<()> {
+        let orphaned_cluster_id = ClusterId::random();
+        let orphaned_peer_id = PeerId::random();
+
+        let (resources_manager, vpn_client, deployed_cluster_id) = setup(&fixture, VpnResources {
+            clusters: vec![orphaned_cluster_id],
+            peers: vec![fixture.peer_a_id, orphaned_peer_id],
+        }).await?;
+
+        let report = reconcile_vpn(ReconcileVpnParams {
+            resources_manager,
+            vpn: Vpn::Enabled { vpn_client: Clone::clone(&vpn_client) as _ },
+            dry_run: true,
+        }).await?;
+
+        assert_that!(report.drifts, unordered_elements_are![
+            eq(&VpnDriftEntry { drift: VpnDrift::OrphanedCluster { cluster_id: orphaned_cluster_id }, resolution: VpnDriftResolution::Reported }),
+            eq(&VpnDriftEntry { drift: VpnDrift::OrphanedPeer { peer_id: orphaned_peer_id }, resolution: VpnDriftResolution::Reported }),
+        ]);
+        assert_that!(report.drifts.iter().any(|entry| entry.drift == VpnDrift::MissingCluster { cluster_id: deployed_cluster_id }), eq(false)); //not yet assigned to its peers
+        assert_that!(vpn_client.deleted_clusters.lock().unwrap().as_slice(), empty());
+        assert_that!(vpn_client.deleted_peers.lock().unwrap().as_slice(), empty());
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_remove_orphans_and_report_missing_resources(fixture: Fixture) -> anyhow::Result<()> {
+        let orphaned_cluster_id = ClusterId::random();
+
+        let (resources_manager, vpn_client, _) = setup(&fixture, VpnResources {
+            clusters: vec![orphaned_cluster_id],
+            peers: vec![],
+        }).await?;
+
+        let report = reconcile_vpn(ReconcileVpnParams {
+            resources_manager,
+            vpn: Vpn::Enabled { vpn_client: Clone::clone(&vpn_client) as _ },
+            dry_run: false,
+        }).await?;
+
+        assert_that!(report.drifts, unordered_elements_are![
+            eq(&VpnDriftEntry { drift: VpnDrift::OrphanedCluster { cluster_id: orphaned_cluster_id }, resolution: VpnDriftResolution::Removed }),
+            eq(&VpnDriftEntry { drift: VpnDrift::MissingPeer { peer_id: fixture.peer_a_id }, resolution: VpnDriftResolution::Reported }),
+        ]);
+        assert_that!(vpn_client.deleted_clusters.lock().unwrap().as_slice(), elements_are![eq(&orphaned_cluster_id)]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_the_vpn_is_disabled() {
+        let result = reconcile_vpn(ReconcileVpnParams {
+            resources_manager: ResourcesManager::new_in_memory(),
+            vpn: Vpn::Disabled,
+            dry_run: true,
+        }).await;
+
+        assert_that!(result, err(eq(&ReconcileVpnError::VpnDisabled)));
+    }
+}
@@ -12,8 +12,9 @@ use opendut_types::cluster::{ClusterAssignment, ClusterConfiguration, ClusterDep
 use opendut_types::peer::state::{PeerState, PeerUpState};
 use opendut_types::peer::{PeerDescriptor, PeerId};
 use opendut_types::topology::{DeviceDescriptor, DeviceId};
-use opendut_types::util::net::{NetworkInterfaceDescriptor, NetworkInterfaceName};
+use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
 use opendut_types::util::Port;
+use opendut_vpn::ClusterTraffic;
 
 use crate::actions;
 use crate::actions::{AssignClusterOptions, AssignClusterParams, DeleteClusterDeploymentParams, DetermineClusterPeerStatesParams, GetPeerStateParams, ListPeerDescriptorsParams, StoreClusterConfigurationParams};
@@ -256,20 +257,6 @@ impl ClusterManager {
 
         let member_ids = member_interface_mapping.keys().cloned().collect::<Vec<_>>();
 
-        if let Vpn::Enabled { vpn_client } = &self.vpn {
-            vpn_client.create_cluster(cluster_id, &member_ids).await
-                .map_err(|cause| {
-                    let message = format!("Failure while creating cluster <{cluster_id}> in VPN service.");
-                    error!("{}\n  {cause}", message);
-                    DeployClusterError::Internal { cluster_id, cause: message }
-                })?;
-
-            let peers_string = member_ids.iter().map(|peer| peer.to_string()).collect::<Vec<_>>().join(",");
-            debug!("Created group for cluster <{cluster_id}> in VPN service, using peers: {peers_string}");
-        } else {
-            debug!("VPN disabled. Not creating VPN group.")
-        }
-
         let n_peers = u16::try_from(member_interface_mapping.len())
             .map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })?;
         if self.options.can_server_port_range_start + n_peers >= self.options.can_server_port_range_end {
@@ -295,6 +282,27 @@ impl ClusterManager {
             .collect::<Vec<_>>();
         self.can_server_port_counter += n_peers;
 
+        let cluster_traffic = ClusterTraffic {
+            ethernet: member_interface_mapping.values()
+                .flatten()
+                .any(|interface| matches!(interface.configuration, NetworkInterfaceConfiguration::Ethernet)),
+            can_server_ports: can_server_ports.iter().map(|port| port.0).collect(),
+        };
+
+        if let Vpn::Enabled { vpn_client } = &self.vpn {
+            vpn_client.create_cluster(cluster_id, &member_ids, &cluster_traffic).await
+                .map_err(|cause| {
+                    let message = format!("Failure while creating cluster <{cluster_id}> in VPN service.");
+                    error!("{}\n  {cause}", message);
+                    DeployClusterError::Internal { cluster_id, cause: message }
+                })?;
+
+            let peers_string = member_ids.iter().map(|peer| peer.to_string()).collect::<Vec<_>>().join(",");
+            debug!("Created group for cluster <{cluster_id}> in VPN service, using peers: {peers_string}");
+        } else {
+            debug!("VPN disabled. Not creating VPN group.")
+        }
+
         let member_assignments: Vec<Result<PeerClusterAssignment, DeployClusterError>> = {
             let assignment_futures = std::iter::zip(member_interface_mapping, can_server_ports)
                 .map(|((peer_id, device_interfaces), can_server_port)| {
@@ -334,7 +342,19 @@ impl ClusterManager {
             bridge_name_default: self.options.bridge_name_default.clone(),
         };
 
-        for member_id in member_ids {
+        for &member_id in &member_ids {
+            let wireguard_peers = match &self.vpn {
+                Vpn::Enabled { vpn_client } => {
+                    vpn_client.generate_wireguard_peers(cluster_id, member_id, &member_ids).await
+                        .map_err(|cause| {
+                            let message = format!("Failure while generating VPN tunnels of peer <{member_id}> for cluster <{cluster_id}>.");
+                            error!("{}\n  {cause}", message);
+                            DeployClusterError::Internal { cluster_id, cause: message }
+                        })?
+                }
+                Vpn::Disabled => Vec::new(),
+            };
+
             actions::assign_cluster(AssignClusterParams {
                 resources_manager: Arc::clone(&self.resources_manager),
                 peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
@@ -344,6 +364,7 @@ impl ClusterManager {
                     leader: cluster_config.leader,
                     assignments: member_assignments.clone(),
                 },
+                wireguard_peers,
                 options: assign_cluster_options.clone(),
             }).await
             .map_err(|cause| {
@@ -555,7 +576,7 @@ mod test {
         }
 
         async fn peer_open(peer_id: PeerId, peer_remote_host: IpAddr, peer_messaging_broker: PeerMessagingBrokerRef) -> anyhow::Result<mpsc::Receiver<Downstream>> {
-            let (_peer_tx, mut peer_rx) = peer_messaging_broker.open(peer_id, peer_remote_host).await?;
+            let (_peer_tx, mut peer_rx) = peer_messaging_broker.open(peer_id, peer_remote_host, None).await?;
             receive_peer_configuration_message(&mut peer_rx).await; //initial peer configuration after connect
             Ok(peer_rx)
         }
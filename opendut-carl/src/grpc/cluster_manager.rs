@@ -6,24 +6,31 @@ use tracing::trace;
 use opendut_carl_api::proto::services::cluster_manager::*;
 use opendut_carl_api::proto::services::cluster_manager::cluster_manager_server::{ClusterManager as ClusterManagerService, ClusterManagerServer};
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::cluster::diagnostics::NetworkDiagnosticsOptions;
 
 use crate::actions;
-use crate::actions::{CreateClusterConfigurationParams, DeleteClusterConfigurationParams};
+use crate::actions::{CreateClusterConfigurationParams, DeleteClusterConfigurationParams, DiagnoseClusterParams, GetClusterStateParams, ReconcileVpnParams};
 use crate::cluster::manager::ClusterManagerRef;
 use crate::grpc::extract;
+use crate::peer::broker::PeerMessagingBrokerRef;
 use crate::resources::manager::ResourcesManagerRef;
+use crate::vpn::Vpn;
 
 pub struct ClusterManagerFacade {
     cluster_manager: ClusterManagerRef,
     resources_manager: ResourcesManagerRef,
+    peer_messaging_broker: PeerMessagingBrokerRef,
+    vpn: Vpn,
 }
 
 impl ClusterManagerFacade {
 
-    pub fn new(cluster_manager: ClusterManagerRef, resources_manager: ResourcesManagerRef) -> Self {
+    pub fn new(cluster_manager: ClusterManagerRef, resources_manager: ResourcesManagerRef, peer_messaging_broker: PeerMessagingBrokerRef, vpn: Vpn) -> Self {
         Self {
             cluster_manager,
-            resources_manager
+            resources_manager,
+            peer_messaging_broker,
+            vpn,
         }
     }
 
@@ -141,6 +148,71 @@ impl ClusterManagerService for ClusterManagerFacade {
         }))
     }
 
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn get_cluster_state(&self, request: Request<GetClusterStateRequest>) -> Result<Response<GetClusterStateResponse>, Status> {
+
+        let request = request.into_inner();
+        let cluster_id: ClusterId = extract!(request.cluster_id)?;
+
+        trace!("Received request to get state of cluster <{cluster_id}>.");
+
+        let result = actions::get_cluster_state(GetClusterStateParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            cluster_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(GetClusterStateResponse {
+                    reply: Some(get_cluster_state_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(state) => {
+                Ok(Response::new(GetClusterStateResponse {
+                    reply: Some(get_cluster_state_response::Reply::Success(
+                        GetClusterStateSuccess {
+                            state: Some(state.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn diagnose_cluster(&self, request: Request<DiagnoseClusterRequest>) -> Result<Response<DiagnoseClusterResponse>, Status> {
+
+        let request = request.into_inner();
+        let cluster_id: ClusterId = extract!(request.cluster_id)?;
+        let options: NetworkDiagnosticsOptions = extract!(request.options)?;
+
+        trace!("Received request to diagnose network of cluster <{cluster_id}> with options: {options:?}");
+
+        let result = actions::diagnose_cluster(DiagnoseClusterParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+            cluster_id,
+            options,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DiagnoseClusterResponse {
+                    reply: Some(diagnose_cluster_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(diagnostics) => {
+                Ok(Response::new(DiagnoseClusterResponse {
+                    reply: Some(diagnose_cluster_response::Reply::Success(
+                        DiagnoseClusterSuccess {
+                            diagnostics: Some(diagnostics.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
     #[tracing::instrument(skip_all, level="trace")]
     async fn store_cluster_deployment(&self, request: Request<StoreClusterDeploymentRequest>) -> Result<Response<StoreClusterDeploymentResponse>, Status> {
 
@@ -210,4 +282,39 @@ impl ClusterManagerService for ClusterManagerFacade {
             ))
         }))
     }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn reconcile_vpn(&self, request: Request<ReconcileVpnRequest>) -> Result<Response<ReconcileVpnResponse>, Status> {
+
+        let dry_run = request.into_inner().dry_run;
+
+        trace!("Received request to reconcile the VPN (dry run: {dry_run}).");
+
+        let result = {
+            let _cluster_manager = self.cluster_manager.lock().await; //prevent clusters from being deployed in the VPN meanwhile
+
+            actions::reconcile_vpn(ReconcileVpnParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                vpn: Clone::clone(&self.vpn),
+                dry_run,
+            }).await
+        };
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ReconcileVpnResponse {
+                    reply: Some(reconcile_vpn_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(report) => {
+                Ok(Response::new(ReconcileVpnResponse {
+                    reply: Some(reconcile_vpn_response::Reply::Success(
+                        ReconcileVpnSuccess {
+                            report: Some(report.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
 }
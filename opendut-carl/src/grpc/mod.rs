@@ -4,11 +4,13 @@ pub use cluster_manager::ClusterManagerFacade;
 pub use metadata_provider::MetadataProviderFacade;
 pub use peer_manager::PeerManagerFacade;
 pub use peer_messaging_broker::PeerMessagingBrokerFacade;
+pub use plugin_registry::PluginRegistryFacade;
 
 mod cluster_manager;
 mod peer_manager;
 mod peer_messaging_broker;
 mod metadata_provider;
+mod plugin_registry;
 
 pub trait ExtractOrInvalidArgument<A, B>
 where
@@ -11,38 +11,51 @@ use opendut_carl_api::carl::peer::{GetPeerDescriptorError, GetPeerStateError};
 use opendut_carl_api::proto::services::peer_manager;
 use opendut_carl_api::proto::services::peer_manager::*;
 use opendut_carl_api::proto::services::peer_manager::peer_manager_server::{PeerManager as PeerManagerService, PeerManagerServer};
+use opendut_types::cluster::ClusterId;
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::executor::log::ExecutorRunId;
+use opendut_types::peer::executor::test_run::ExecutorRunOverrides;
 use opendut_types::cleo::{CleoId};
 
 use crate::actions;
-use crate::actions::{DeletePeerDescriptorParams, GenerateCleoSetupParams, GeneratePeerSetupParams, GetPeerStateParams, ListDevicesParams, ListPeerDescriptorsParams, StorePeerDescriptorParams};
+use crate::auth::CurrentUser;
+use crate::actions::{DeletePeerDescriptorParams, GenerateCleoSetupParams, GeneratePeerSetupParams, GetExecutorLogsParams, GetPeerHistoryParams, GetPeerStateParams, GetTestRunParams, ListDevicesParams, ListPeerDescriptorsParams, ListTestRunsParams, StartExecutorParams, StopExecutorParams, StorePeerDescriptorParams};
 use crate::grpc::extract;
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::plugin::PluginSigning;
 use crate::resources::manager::ResourcesManagerRef;
 use crate::vpn::Vpn;
 
 pub struct PeerManagerFacade {
     resources_manager: ResourcesManagerRef,
+    peer_messaging_broker: PeerMessagingBrokerRef,
     vpn: Vpn,
     carl_url: Url,
     ca: Pem,
     oidc_registration_client: Option<RegistrationClientRef>,
+    plugin_signing: PluginSigning,
 }
 
 impl PeerManagerFacade {
 
     pub fn new(
         resources_manager: ResourcesManagerRef,
+        peer_messaging_broker: PeerMessagingBrokerRef,
         vpn: Vpn,
         carl_url: Url,
         ca: Pem,
         oidc_registration_client: Option<RegistrationClientRef>,
+        plugin_signing: PluginSigning,
     ) -> Self {
         PeerManagerFacade {
             resources_manager,
+            peer_messaging_broker,
             vpn,
             carl_url,
             ca,
             oidc_registration_client,
+            plugin_signing,
         }
     }
 
@@ -222,6 +235,232 @@ impl PeerManagerService for PeerManagerFacade {
         }
     }
 
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn get_peer_history(&self, request: Request<GetPeerHistoryRequest>) -> Result<Response<GetPeerHistoryResponse>, Status> {
+
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+
+        trace!("Received request to get connection history for peer <{peer_id}>.");
+
+        let result =
+            actions::get_peer_history(GetPeerHistoryParams {
+                peer: peer_id,
+                resources_manager: Arc::clone(&self.resources_manager),
+            }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(GetPeerHistoryResponse {
+                    reply: Some(get_peer_history_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(history) => {
+                Ok(Response::new(GetPeerHistoryResponse {
+                    reply: Some(get_peer_history_response::Reply::Success(
+                        GetPeerHistorySuccess {
+                            sessions: history.sessions.into_iter()
+                                .map(From::from)
+                                .collect(),
+                            enrollments: history.enrollments.into_iter()
+                                .map(From::from)
+                                .collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn get_executor_logs(&self, request: Request<GetExecutorLogsRequest>) -> Result<Response<GetExecutorLogsResponse>, Status> {
+
+        let request = request.into_inner();
+        let executor_id: ExecutorId = extract!(request.executor_id)?;
+        let run_id = request.run_id
+            .map(|run_id| ExecutorRunId::try_from(run_id)
+                .map_err(|cause| Status::invalid_argument(format!("Field 'request.run_id' is not valid: {cause}")))
+            )
+            .transpose()?;
+
+        trace!("Received request to get logs of executor <{executor_id}>.");
+
+        let result =
+            actions::get_executor_logs(GetExecutorLogsParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                executor_id,
+                run_id,
+                from_sequence_number: request.from_sequence_number,
+            }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(GetExecutorLogsResponse {
+                    reply: Some(get_executor_logs_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(log) => {
+                Ok(Response::new(GetExecutorLogsResponse {
+                    reply: Some(get_executor_logs_response::Reply::Success(
+                        GetExecutorLogsSuccess {
+                            log: Some(log.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_test_runs(&self, request: Request<ListTestRunsRequest>) -> Result<Response<ListTestRunsResponse>, Status> {
+
+        let request = request.into_inner();
+        let cluster_id = request.cluster_id
+            .map(|cluster_id| ClusterId::try_from(cluster_id)
+                .map_err(|cause| Status::invalid_argument(format!("Field 'request.cluster_id' is not valid: {cause}")))
+            )
+            .transpose()?;
+        let executor_id = request.executor_id
+            .map(|executor_id| ExecutorId::try_from(executor_id)
+                .map_err(|cause| Status::invalid_argument(format!("Field 'request.executor_id' is not valid: {cause}")))
+            )
+            .transpose()?;
+
+        trace!("Received request to list test runs.");
+
+        let result =
+            actions::list_test_runs(ListTestRunsParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                cluster_id,
+                executor_id,
+            }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListTestRunsResponse {
+                    reply: Some(list_test_runs_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(test_runs) => {
+                Ok(Response::new(ListTestRunsResponse {
+                    reply: Some(list_test_runs_response::Reply::Success(
+                        ListTestRunsSuccess {
+                            test_runs: test_runs.into_iter()
+                                .map(From::from)
+                                .collect()
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn get_test_run(&self, request: Request<GetTestRunRequest>) -> Result<Response<GetTestRunResponse>, Status> {
+
+        let request = request.into_inner();
+        let run_id: ExecutorRunId = extract!(request.run_id)?;
+
+        trace!("Received request to get test run <{run_id}>.");
+
+        let result =
+            actions::get_test_run(GetTestRunParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                run_id,
+            }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(GetTestRunResponse {
+                    reply: Some(get_test_run_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(test_run) => {
+                Ok(Response::new(GetTestRunResponse {
+                    reply: Some(get_test_run_response::Reply::Success(
+                        GetTestRunSuccess {
+                            test_run: Some(test_run.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn start_executor(&self, request: Request<StartExecutorRequest>) -> Result<Response<StartExecutorResponse>, Status> {
+
+        let triggered_by = request.extensions().get::<CurrentUser>()
+            .map(|current_user| Clone::clone(&current_user.name));
+
+        let request = request.into_inner();
+        let executor_id: ExecutorId = extract!(request.executor_id)?;
+        let overrides = request.overrides
+            .map(|overrides| ExecutorRunOverrides::try_from(overrides)
+                .map_err(|cause| Status::invalid_argument(format!("Field 'request.overrides' is not valid: {cause}")))
+            )
+            .transpose()?
+            .unwrap_or_default();
+
+        trace!("Received request to start executor <{executor_id}>.");
+
+        let result =
+            actions::start_executor(StartExecutorParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+                executor_id,
+                overrides,
+                restart: request.restart,
+                triggered_by,
+            }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(StartExecutorResponse {
+                    reply: Some(start_executor_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(test_run) => {
+                Ok(Response::new(StartExecutorResponse {
+                    reply: Some(start_executor_response::Reply::Success(
+                        StartExecutorSuccess {
+                            test_run: Some(test_run.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn stop_executor(&self, request: Request<StopExecutorRequest>) -> Result<Response<StopExecutorResponse>, Status> {
+
+        let request = request.into_inner();
+        let executor_id: ExecutorId = extract!(request.executor_id)?;
+
+        trace!("Received request to stop executor <{executor_id}>.");
+
+        let result =
+            actions::stop_executor(StopExecutorParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+                executor_id,
+            }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(StopExecutorResponse {
+                    reply: Some(stop_executor_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(()) => {
+                Ok(Response::new(StopExecutorResponse {
+                    reply: Some(stop_executor_response::Reply::Success(StopExecutorSuccess {}))
+                }))
+            }
+        }
+    }
+
     #[tracing::instrument(skip_all, level="trace")]
     async fn list_devices(&self, _: Request<ListDevicesRequest>) -> Result<Response<ListDevicesResponse>, Status> {
 
@@ -253,7 +492,9 @@ impl PeerManagerService for PeerManagerFacade {
             ca: Clone::clone(&self.ca),
             vpn: Clone::clone(&self.vpn),
             oidc_registration_client: self.oidc_registration_client.clone(),
+            plugin_signing: Clone::clone(&self.plugin_signing),
             user_id,
+            reenroll: request.reenroll,
         }).await.map_err(|cause| Status::internal(format!("Peer setup could not be created: {}", cause)))?;
 
         let response = peer_manager::generate_peer_setup_response::Reply::Success(peer_manager::GeneratePeerSetupSuccess {
@@ -309,6 +550,7 @@ mod tests {
     use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
     use opendut_auth_tests::registration_client;
 
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
     use crate::resources::manager::ResourcesManager;
     use crate::vpn::Vpn;
 
@@ -328,12 +570,19 @@ mod tests {
     async fn test_successful_create_delete(#[future] registration_client: RegistrationClientRef) -> Result<()> {
 
         let resources_manager = ResourcesManager::new_in_memory();
+        let settings = crate::settings::load_defaults()?;
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        );
         let testee = PeerManagerFacade::new(
             Arc::clone(&resources_manager),
+            peer_messaging_broker,
             Vpn::Disabled,
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
             Some(registration_client.await),
+            PluginSigning::Disabled,
         );
 
         let peer_id = PeerId::random();
@@ -429,12 +678,19 @@ mod tests {
     async fn register_fails_when_no_id_specified(#[future] registration_client: RegistrationClientRef) -> Result<()> {
 
         let resources_manager = ResourcesManager::new_in_memory();
+        let settings = crate::settings::load_defaults()?;
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        );
         let testee = PeerManagerFacade::new(
             Arc::clone(&resources_manager),
+            peer_messaging_broker,
             Vpn::Disabled,
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
             Some(registration_client.await),
+            PluginSigning::Disabled,
         );
 
         let create_peer_reply = testee.store_peer_descriptor(Request::new(
@@ -469,12 +725,19 @@ mod tests {
     async fn unregister_fails_when_no_id_specified(#[future] registration_client: RegistrationClientRef) -> Result<()> {
 
         let resources_manager = ResourcesManager::new_in_memory();
+        let settings = crate::settings::load_defaults()?;
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        );
         let testee = PeerManagerFacade::new(
             Arc::clone(&resources_manager),
+            peer_messaging_broker,
             Vpn::Disabled,
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
             Some(registration_client.await),
+            PluginSigning::Disabled,
         );
 
         let delete_peer_reply = testee.delete_peer_descriptor(Request::new(
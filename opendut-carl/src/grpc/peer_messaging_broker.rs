@@ -17,14 +17,16 @@ use opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_bro
 use opendut_carl_api::proto::services::peer_messaging_broker::upstream;
 use opendut_types::peer::PeerId;
 use crate::peer::broker::{OpenError, PeerMessagingBrokerRef};
+use crate::vpn::Vpn;
 
 pub struct PeerMessagingBrokerFacade {
     peer_messaging_broker: PeerMessagingBrokerRef,
+    vpn: Vpn,
 }
 
 impl PeerMessagingBrokerFacade {
-    pub fn new(peer_messaging_broker: PeerMessagingBrokerRef) -> Self {
-        Self { peer_messaging_broker }
+    pub fn new(peer_messaging_broker: PeerMessagingBrokerRef, vpn: Vpn) -> Self {
+        Self { peer_messaging_broker, vpn }
     }
     pub fn into_grpc_service(self) -> CorsGrpcWeb<PeerMessagingBrokerServer<Self>> {
         tonic_web::enable(PeerMessagingBrokerServer::new(self))
@@ -52,13 +54,22 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
             })?;
 
 
-        let (tx_inbound, rx_outbound) = self.peer_messaging_broker.open(peer_id, remote_host).await
+        let edgar_version = extract_version(request.metadata());
+
+        let (tx_inbound, rx_outbound) = self.peer_messaging_broker.open(peer_id, remote_host, edgar_version).await
             .map_err(|cause| match cause {
                 OpenError::PeerAlreadyConnected { .. } => Status::aborted(cause.to_string()),
+                OpenError::RemoteHostInUse { .. } => Status::already_exists(cause.to_string()),
                 OpenError::SendApplyPeerConfiguration { .. } => Status::unavailable(cause.to_string()),
                 OpenError::Persistence { .. } => Status::internal(cause.to_string()),
             })?;
 
+        if let Vpn::Enabled { vpn_client } = &self.vpn {
+            // Peers of VPNs without endpoint discovery, like WireGuard, are expected to be reachable under the address they report.
+            // The address of the connection is not used, as it is the address of a reverse proxy in many deployments.
+            vpn_client.update_peer_endpoint(peer_id, remote_host).await;
+        }
+
         let mut inbound = request.into_inner();
         tokio::spawn(async move {
             while let Some(result) = inbound.next().await {
@@ -115,5 +126,13 @@ fn extract_remote_host(metadata: &MetadataMap) -> Result<IpAddr, UserError> {
     Ok(remote_host)
 }
 
+/// The version is optional, since older versions of EDGAR do not send it.
+fn extract_version(metadata: &MetadataMap) -> Option<String> {
+    metadata
+        .get("version")
+        .and_then(|version| version.to_str().ok())
+        .map(ToOwned::to_owned)
+}
+
 
 type UserError = String;
@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::carl::plugin::MAX_PLUGIN_MESSAGE_SIZE;
+use opendut_carl_api::proto::services::plugin_registry::*;
+use opendut_carl_api::proto::services::plugin_registry::plugin_registry_server::{PluginRegistry as PluginRegistryService, PluginRegistryServer};
+use opendut_types::peer::PeerId;
+use opendut_types::plugin::{PluginId, PluginKind};
+
+use crate::actions;
+use crate::actions::{AssignPluginsParams, DeletePluginParams, DownloadPluginParams, ListPluginsParams, StorePluginParams};
+use crate::auth::CurrentUser;
+use crate::grpc::extract;
+use crate::plugin::PluginSigning;
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct PluginRegistryFacade {
+    resources_manager: ResourcesManagerRef,
+    plugin_signing: PluginSigning,
+}
+
+impl PluginRegistryFacade {
+
+    pub fn new(resources_manager: ResourcesManagerRef, plugin_signing: PluginSigning) -> Self {
+        Self {
+            resources_manager,
+            plugin_signing,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<PluginRegistryServer<Self>> {
+        tonic_web::enable(
+            PluginRegistryServer::new(self)
+                .max_decoding_message_size(MAX_PLUGIN_MESSAGE_SIZE)
+                .max_encoding_message_size(MAX_PLUGIN_MESSAGE_SIZE)
+        )
+    }
+}
+
+#[tonic::async_trait]
+impl PluginRegistryService for PluginRegistryFacade {
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn store_plugin(&self, request: Request<StorePluginRequest>) -> Result<Response<StorePluginResponse>, Status> {
+
+        let uploaded_by = request.extensions().get::<CurrentUser>()
+            .map(|current_user| Clone::clone(&current_user.name));
+
+        let request = request.into_inner();
+        let kind: PluginKind = extract!(request.kind)?;
+
+        trace!("Received request to store plugin '{}' in version '{}'.", request.name, request.version);
+
+        let result = actions::store_plugin(StorePluginParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            plugin_signing: Clone::clone(&self.plugin_signing),
+            name: request.name,
+            version: request.version,
+            kind,
+            binary: request.binary,
+            manifest: request.manifest,
+            uploaded_by,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(StorePluginResponse {
+                    reply: Some(store_plugin_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(plugin) => {
+                Ok(Response::new(StorePluginResponse {
+                    reply: Some(store_plugin_response::Reply::Success(
+                        StorePluginSuccess {
+                            plugin: Some(plugin.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn delete_plugin(&self, request: Request<DeletePluginRequest>) -> Result<Response<DeletePluginResponse>, Status> {
+
+        let request = request.into_inner();
+        let plugin_id: PluginId = extract!(request.plugin_id)?;
+
+        trace!("Received request to delete plugin <{plugin_id}>.");
+
+        let result = actions::delete_plugin(DeletePluginParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            plugin_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeletePluginResponse {
+                    reply: Some(delete_plugin_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(plugin) => {
+                Ok(Response::new(DeletePluginResponse {
+                    reply: Some(delete_plugin_response::Reply::Success(
+                        DeletePluginSuccess {
+                            plugin: Some(plugin.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn list_plugins(&self, request: Request<ListPluginsRequest>) -> Result<Response<ListPluginsResponse>, Status> {
+
+        let request = request.into_inner();
+        let peer_id = request.peer_id
+            .map(|peer_id| PeerId::try_from(peer_id)
+                .map_err(|cause| Status::invalid_argument(format!("Field 'request.peer_id' is not valid: {cause}")))
+            )
+            .transpose()?;
+
+        trace!("Received request to list plugins.");
+
+        let result = actions::list_plugins(ListPluginsParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListPluginsResponse {
+                    reply: Some(list_plugins_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(plugins) => {
+                Ok(Response::new(ListPluginsResponse {
+                    reply: Some(list_plugins_response::Reply::Success(
+                        ListPluginsSuccess {
+                            plugins: plugins.into_iter().map(Into::into).collect()
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn download_plugin(&self, request: Request<DownloadPluginRequest>) -> Result<Response<DownloadPluginResponse>, Status> {
+
+        let request = request.into_inner();
+        let plugin_id: PluginId = extract!(request.plugin_id)?;
+
+        trace!("Received request to download plugin <{plugin_id}>.");
+
+        let result = actions::download_plugin(DownloadPluginParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            plugin_id,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DownloadPluginResponse {
+                    reply: Some(download_plugin_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok((plugin, artifact)) => {
+                Ok(Response::new(DownloadPluginResponse {
+                    reply: Some(download_plugin_response::Reply::Success(
+                        DownloadPluginSuccess {
+                            plugin: Some(plugin.into()),
+                            binary: artifact.binary,
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip_all, level="trace")]
+    async fn assign_plugins(&self, request: Request<AssignPluginsRequest>) -> Result<Response<AssignPluginsResponse>, Status> {
+
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+        let plugin_ids = request.plugin_ids.into_iter()
+            .map(PluginId::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'request.plugin_ids' is not valid: {cause}")))?;
+
+        trace!("Received request to assign plugins to peer <{peer_id}>.");
+
+        let result = actions::assign_plugins(AssignPluginsParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_id,
+            plugin_ids,
+        }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(AssignPluginsResponse {
+                    reply: Some(assign_plugins_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(()) => {
+                Ok(Response::new(AssignPluginsResponse {
+                    reply: Some(assign_plugins_response::Reply::Success(AssignPluginsSuccess {}))
+                }))
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use axum::response::IntoResponse;
+use http::{header, StatusCode};
+use tracing::error;
+use opendut_util::telemetry::metrics;
+
+/// Exposes the meters of CARL in the Prometheus text format, if enabled via `opentelemetry.metrics.prometheus.enabled`.
+pub async fn prometheus_metrics() -> impl IntoResponse {
+    let Some(registry) = metrics::prometheus_registry() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    match metrics::encode_prometheus_metrics(registry) {
+        Ok(body) => {
+            let headers = [(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)];
+            (headers, body).into_response()
+        }
+        Err(cause) => {
+            error!("Failed to encode metrics for Prometheus: {cause}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
@@ -31,14 +31,16 @@ use util::in_memory_cache::CustomInMemoryCache;
 use crate::auth::grpc_auth_layer::GrpcAuthenticationLayer;
 use crate::auth::json_web_key::JwkCacheValue;
 use crate::cluster::manager::{ClusterManager, ClusterManagerOptions, ClusterManagerRef};
-use crate::grpc::{ClusterManagerFacade, MetadataProviderFacade, PeerManagerFacade, PeerMessagingBrokerFacade};
+use crate::grpc::{ClusterManagerFacade, MetadataProviderFacade, PeerManagerFacade, PeerMessagingBrokerFacade, PluginRegistryFacade};
 use crate::http::router;
 use crate::http::state::{CarlInstallDirectory, HttpState, LeaConfig, LeaIdentityProviderConfig};
 use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions, PeerMessagingBrokerRef};
+use crate::plugin::PluginSigning;
 use crate::provisioning::cleo_script::CleoScript;
 use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
 use crate::resources::storage::PersistenceOptions;
 use crate::vpn::Vpn;
+use crate::vpn::reconciler::VpnReconcilerOptions;
 
 pub mod grpc;
 pub mod util;
@@ -49,6 +51,7 @@ mod cluster;
 mod metrics;
 pub mod persistence;
 mod peer;
+mod plugin;
 mod resources;
 pub mod settings;
 mod vpn;
@@ -112,8 +115,8 @@ pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
     let ca_certificate = Pem::from_config_path("network.tls.ca", &settings.config).await?;
     let oidc_registration_client = RegistrationClient::from_settings(&settings.config).await.expect("Failed to load oidc registration client!");
 
-    let vpn = vpn::create(&settings.config)
-        .context("Error while parsing VPN configuration.")?;
+    let plugin_signing = PluginSigning::load(&settings.config)
+        .context("Error while loading plugin signing key.")?;
 
     let resources_manager = {
         let resources_storage_options = PersistenceOptions::load(&settings.config)?;
@@ -122,6 +125,9 @@ pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
             .context("Creating ResourcesManager failed")?
     };
 
+    let vpn = vpn::create(&settings.config, Arc::clone(&resources_manager))
+        .context("Error while parsing VPN configuration.")?;
+
     metrics::initialize_metrics_collection(Arc::clone(&resources_manager));
 
     let peer_messaging_broker = PeerMessagingBroker::new(
@@ -135,6 +141,13 @@ pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
         ClusterManagerOptions::load(&settings.config)?,
     ).await;
 
+    vpn::reconciler::spawn(
+        Arc::clone(&resources_manager),
+        Arc::clone(&cluster_manager),
+        Clone::clone(&vpn),
+        VpnReconcilerOptions::load(&settings.config)?,
+    );
+
     let grpc_auth_layer = match oidc_registration_client.clone() {
         None => GrpcAuthenticationLayer::AuthDisabled,
         Some(oidc_client_ref) => {
@@ -156,6 +169,7 @@ pub async fn create(settings: LoadedConfig) -> anyhow::Result<()> {
         cluster_manager,
         peer_messaging_broker,
         vpn,
+        plugin_signing,
         carl_url,
         settings.config,
         ca_certificate,
@@ -176,6 +190,7 @@ fn spawn_server(
     cluster_manager: ClusterManagerRef,
     peer_messaging_broker: PeerMessagingBrokerRef,
     vpn: Vpn,
+    plugin_signing: PluginSigning,
     carl_url: ResourceHomeUrl,
     settings: config::Config,
     ca: Pem,
@@ -184,17 +199,20 @@ fn spawn_server(
 ) -> BoxFuture<'static, anyhow::Result<()>> {
     let oidc_enabled = settings.get_bool("network.oidc.enabled").unwrap_or(false);
 
-    let cluster_manager_facade = ClusterManagerFacade::new(Arc::clone(&cluster_manager), Arc::clone(&resources_manager));
+    let cluster_manager_facade = ClusterManagerFacade::new(Arc::clone(&cluster_manager), Arc::clone(&resources_manager), Arc::clone(&peer_messaging_broker), Clone::clone(&vpn));
     let metadata_provider_facade = MetadataProviderFacade::new();
 
     let peer_manager_facade = PeerManagerFacade::new(
         Arc::clone(&resources_manager),
-        vpn,
+        Arc::clone(&peer_messaging_broker),
+        Clone::clone(&vpn),
         Clone::clone(&carl_url.value()),
         ca.clone(),
         oidc_registration_client,
+        Clone::clone(&plugin_signing),
     );
-    let peer_messaging_broker_facade = PeerMessagingBrokerFacade::new(Arc::clone(&peer_messaging_broker));
+    let peer_messaging_broker_facade = PeerMessagingBrokerFacade::new(Arc::clone(&peer_messaging_broker), vpn);
+    let plugin_registry_facade = PluginRegistryFacade::new(Arc::clone(&resources_manager), plugin_signing);
 
     let grpc = Server::builder()
         .layer(async_interceptor(move |request| {
@@ -205,6 +223,7 @@ fn spawn_server(
         .add_service(metadata_provider_facade.into_grpc_service())
         .add_service(peer_manager_facade.into_grpc_service())
         .add_service(peer_messaging_broker_facade.into_grpc_service())
+        .add_service(plugin_registry_facade.into_grpc_service())
         .into_service()
         .map_response(|response| response.map(axum::body::boxed))
         .boxed_clone();
@@ -268,6 +287,7 @@ fn spawn_server(
                 .route("/api/cleo/:architecture/download", get(router::cleo::download_cleo))
                 .route("/api/edgar/:architecture/download", get(router::edgar::download_edgar))
                 .route("/api/lea/config", get(router::lea_config))
+                .route("/metrics", get(router::metrics::prometheus_metrics))
                 .nest_service(
                     "/",
                     ServeDir::new(&lea_dir)
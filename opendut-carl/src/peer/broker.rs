@@ -1,36 +1,51 @@
 use std::collections::HashMap;
 use std::net::IpAddr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use opentelemetry::propagation::TextMapPropagator;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use tokio::sync::mpsc::error::SendError;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
 use tracing::{debug, error, info, warn, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use opendut_carl_api::proto::services::peer_messaging_broker::upstream;
 use opendut_carl_api::proto::services::peer_messaging_broker::Pong;
-use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, ApplyPeerConfiguration, Downstream, TracingContext};
+use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, ApplyPeerConfiguration, Downstream, ExecutorLogs, NetworkDiagnosticsReport, RunNetworkDiagnostics, TracingContext};
+use opendut_types::cluster::diagnostics::{NetworkDiagnosticsId, NetworkDiagnosticsOptions, PeerLinkDiagnostics};
+use opendut_types::cluster::PeerClusterAssignment;
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::executor::log::{ExecutorLogLine, ExecutorRunId, ExecutorRunLog};
+use opendut_types::peer::executor::test_run::TestRunUpdate;
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::history::{PeerConnectionSession, PeerConnectionSessionId, PeerConnectionSessions};
 use opendut_types::peer::state::{PeerState, PeerUpState};
 use opendut_types::peer::PeerId;
+use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
 
+use crate::actions;
+use crate::actions::{abort_unfinished_test_runs, AppendExecutorLogsParams, UpdateTestRunParams};
 use crate::persistence::error::PersistenceError;
 use crate::resources::manager::ResourcesManagerRef;
 use crate::resources::storage::ResourcesStorageApi;
 
+/// Number of connection sessions retained per peer. Older sessions are deleted when a peer connects.
+pub const MAX_CONNECTION_SESSIONS_PER_PEER: usize = 100;
+
 pub type PeerMessagingBrokerRef = Arc<PeerMessagingBroker>;
+type PendingNetworkDiagnosticsRef = Arc<Mutex<HashMap<NetworkDiagnosticsId, oneshot::Sender<Vec<PeerLinkDiagnostics>>>>>;
 
 
 pub struct PeerMessagingBroker {
     resources_manager: ResourcesManagerRef,
     peers: Arc<RwLock<HashMap<PeerId, PeerMessagingRef>>>,
+    pending_network_diagnostics: PendingNetworkDiagnosticsRef,
     options: PeerMessagingBrokerOptions,
 }
 struct PeerMessagingRef {
     downstream: mpsc::Sender<Downstream>,
+    session_id: PeerConnectionSessionId,
 }
 
 impl PeerMessagingBroker {
@@ -38,6 +53,7 @@ impl PeerMessagingBroker {
         Arc::new(Self {
             resources_manager,
             peers: Default::default(),
+            pending_network_diagnostics: Default::default(),
             options,
         })
     }
@@ -67,26 +83,56 @@ impl PeerMessagingBroker {
         Ok(())
     }
 
+    /// Asks the peer to test its network connection to the given targets and waits for its report.
+    #[tracing::instrument(skip(self, targets), level="trace")]
+    pub async fn request_network_diagnostics(
+        &self,
+        peer_id: PeerId,
+        options: NetworkDiagnosticsOptions,
+        targets: Vec<PeerClusterAssignment>,
+        timeout: Duration,
+    ) -> Result<Vec<PeerLinkDiagnostics>, RequestNetworkDiagnosticsError> {
+        let id = NetworkDiagnosticsId::random();
+
+        let (tx_report, rx_report) = oneshot::channel();
+        self.pending_network_diagnostics.lock().await.insert(id, tx_report);
+
+        let result = async {
+            self.send_to_peer(peer_id, downstream::Message::RunNetworkDiagnostics(
+                RunNetworkDiagnostics {
+                    id: Some(id.into()),
+                    options: Some(options.into()),
+                    targets: targets.into_iter().map(Into::into).collect(),
+                }
+            )).await
+            .map_err(|source| RequestNetworkDiagnosticsError::Send { peer_id, source })?;
+
+            match tokio::time::timeout(timeout, rx_report).await {
+                Ok(Ok(links)) => Ok(links),
+                Ok(Err(_)) => Err(RequestNetworkDiagnosticsError::Disconnected { peer_id }),
+                Err(_) => Err(RequestNetworkDiagnosticsError::Timeout { peer_id, timeout }),
+            }
+        }.await;
+
+        self.pending_network_diagnostics.lock().await.remove(&id);
+        result
+    }
+
     pub async fn open(
         &self,
         peer_id: PeerId,
         remote_host: IpAddr,
+        edgar_version: Option<String>,
     ) -> Result<(mpsc::Sender<upstream::Message>, mpsc::Receiver<Downstream>), OpenError> {
 
         let (tx_inbound, mut rx_inbound) = mpsc::channel::<upstream::Message>(1024);
         let (tx_outbound, rx_outbound) = mpsc::channel::<Downstream>(1024);
 
-        let peer_messaging_ref = PeerMessagingRef {
-            downstream: Clone::clone(&tx_outbound),
-        };
-
-        self.peers.write().await.insert(peer_id, peer_messaging_ref);
-
         fn new_peer_up_state(remote_host: IpAddr) -> PeerState {
             PeerState::Up { inner: PeerUpState::Available, remote_host }
         }
 
-        self.resources_manager.resources_mut(|resources| {
+        let session_id = self.resources_manager.resources_mut(|resources| {
             let maybe_peer_state = resources.get::<PeerState>(peer_id)
                 .map_err(|source| OpenError::Persistence { peer_id, source })?;
 
@@ -106,13 +152,58 @@ impl PeerMessagingBroker {
                     }
                 }
             }
+            .and_then(|new_peer_state| {
+                if self.options.unique_remote_hosts {
+                    let remote_host_in_use = resources.list::<PeerState>()
+                        .map_err(|source| OpenError::Persistence { peer_id, source })?
+                        .into_iter()
+                        .any(|peer_state| matches!(peer_state, PeerState::Up { remote_host: other_remote_host, .. } if other_remote_host == remote_host));
+
+                    if remote_host_in_use {
+                        error!("Peer <{peer_id}> opened stream with address {remote_host}, which is already used by another connected peer.");
+                        return Err(OpenError::RemoteHostInUse { peer_id, remote_host });
+                    }
+                }
+                Ok(new_peer_state)
+            })
             .and_then(|new_peer_state| {
                 resources.insert(peer_id, new_peer_state)
                     .map_err(|source| OpenError::Persistence { peer_id, source })
-            })
+            })?;
+
+            //runs from before the peer connected will not be reported anymore, e.g. because EDGAR was restarted
+            abort_unfinished_test_runs(resources, |test_run| test_run.peer_id == peer_id)
+                .map_err(|source| OpenError::Persistence { peer_id, source })?;
+
+            let mut sessions = resources.get::<PeerConnectionSessions>(peer_id)
+                .map_err(|source| OpenError::Persistence { peer_id, source })?
+                .unwrap_or_else(|| PeerConnectionSessions::new(peer_id));
+
+            let session = PeerConnectionSession {
+                id: PeerConnectionSessionId::random(),
+                peer_id,
+                remote_host,
+                edgar_version,
+                connected_at: SystemTime::now(),
+                disconnected_at: None,
+            };
+            let session_id = session.id;
+            open_connection_session(&mut sessions, session);
+
+            resources.insert(peer_id, sessions)
+                .map_err(|source| OpenError::Persistence { peer_id, source })?;
+
+            Ok(session_id)
         }).await
         .map_err(|source| OpenError::Persistence { peer_id, source })??;
 
+        let peer_messaging_ref = PeerMessagingRef {
+            downstream: Clone::clone(&tx_outbound),
+            session_id,
+        };
+
+        self.peers.write().await.insert(peer_id, peer_messaging_ref);
+
         let old_peer_configuration = self.resources_manager.get::<OldPeerConfiguration>(peer_id).await
             .map_err(|source| OpenError::Persistence { peer_id, source })?
             .unwrap_or_default(); //PeerConfiguration is not persisted across restarts
@@ -135,13 +226,14 @@ impl PeerMessagingBroker {
         {
             let peers = Arc::clone(&self.peers);
             let resources_manager = Arc::clone(&self.resources_manager);
+            let pending_network_diagnostics = Arc::clone(&self.pending_network_diagnostics);
 
             tokio::spawn(async move {
                 loop {
                     let received = tokio::time::timeout(timeout_duration, rx_inbound.recv()).await;
 
                     match received {
-                        Ok(Some(message)) => handle_stream_message(message, peer_id, &tx_outbound).await,
+                        Ok(Some(message)) => handle_stream_message(message, peer_id, &tx_outbound, &pending_network_diagnostics, &resources_manager).await,
                         Ok(None) => {
                             info!("Peer <{peer_id}> disconnected!");
                             break;
@@ -169,23 +261,68 @@ impl PeerMessagingBroker {
         resources_manager: ResourcesManagerRef,
         peers: Arc<RwLock<HashMap<PeerId, PeerMessagingRef>>>
     ) -> Result<(), RemovePeerError> {
-        debug!("Setting state of peer <{peer_id}> to Down.");
-        resources_manager.insert(peer_id, PeerState::Down).await
-            .map_err(|source| RemovePeerError::Persistence { peer_id, source })?;
+        debug!("Setting state of peer <{peer_id}> to Down and aborting its unfinished test runs.");
+        resources_manager.resources_mut(|resources| {
+            resources.insert(peer_id, PeerState::Down)?;
+            abort_unfinished_test_runs(resources, |test_run| test_run.peer_id == peer_id)
+        }).await
+        .map_err(|source| RemovePeerError::Persistence { peer_id, source })?
+        .map_err(|source| RemovePeerError::Persistence { peer_id, source })?;
 
         debug!("Removing peer <{peer_id}> from list of peers connected to message broker.");
         let mut peers = peers.write().await;
         match peers.remove(&peer_id) {
-            Some(_) => Ok(()),
+            Some(peer_messaging_ref) => {
+                close_connection_session(peer_id, peer_messaging_ref.session_id, resources_manager).await
+                    .map_err(|source| RemovePeerError::Persistence { peer_id, source })
+            }
             None => Err(RemovePeerError::PeerNotFound(peer_id)),
         }
     }
 }
 
+async fn close_connection_session(peer_id: PeerId, session_id: PeerConnectionSessionId, resources_manager: ResourcesManagerRef) -> Result<(), PersistenceError> {
+    resources_manager.resources_mut(|resources| {
+        let mut sessions = resources.get::<PeerConnectionSessions>(peer_id)?
+            .unwrap_or_else(|| PeerConnectionSessions::new(peer_id));
+
+        let Some(session) = sessions.sessions.iter_mut().find(|session| session.id == session_id) else {
+            warn!("Connection session <{session_id}> of peer <{peer_id}> could not be found when closing it.");
+            return Ok(());
+        };
+        session.disconnected_at = Some(SystemTime::now());
+
+        resources.insert(peer_id, sessions)
+    }).await?
+}
+
+/// Adds a new session to the sessions of a peer and deletes the oldest sessions beyond [MAX_CONNECTION_SESSIONS_PER_PEER].
+///
+/// Sessions of this peer which were never closed, e.g. because CARL was stopped while the peer was connected, are closed beforehand.
+/// Since the actual time of disconnection is unknown, they are closed with the time of the session start.
+fn open_connection_session(sessions: &mut PeerConnectionSessions, session: PeerConnectionSession) {
+    let peer_id = sessions.peer_id;
+
+    for stale_session in sessions.sessions.iter_mut().filter(|session| session.is_connected()) {
+        debug!("Closing stale connection session <{}> of peer <{peer_id}>.", stale_session.id);
+        stale_session.disconnected_at = Some(stale_session.connected_at);
+    }
+
+    sessions.sessions.push(session);
+
+    let excess_sessions = sessions.sessions.len().saturating_sub(MAX_CONNECTION_SESSIONS_PER_PEER);
+    if excess_sessions > 0 {
+        debug!("Deleting the {excess_sessions} oldest connection sessions of peer <{peer_id}>.");
+        sessions.sessions.drain(..excess_sessions);
+    }
+}
+
 async fn handle_stream_message(
     message: upstream::Message,
     peer_id: PeerId,
     tx_outbound: &mpsc::Sender<Downstream>,
+    pending_network_diagnostics: &PendingNetworkDiagnosticsRef,
+    resources_manager: &ResourcesManagerRef,
 ) {
     match message {
         upstream::Message::Ping(_) => {
@@ -195,9 +332,92 @@ async fn handle_stream_message(
                 tx_outbound.send(Downstream{message:Some(message), context}).await
                     .inspect_err(|cause| warn!("Failed to send ping to peer <{peer_id}>:\n  {cause}"));
         },
+        upstream::Message::NetworkDiagnosticsReport(report) => {
+            let _ignore_result = handle_network_diagnostics_report(report, pending_network_diagnostics).await
+                .inspect_err(|cause| warn!("Failed to handle network diagnostics report of peer <{peer_id}>:\n  {cause}"));
+        }
+        upstream::Message::ExecutorLogs(logs) => {
+            let _ignore_result = handle_executor_logs(logs, peer_id, resources_manager).await
+                .inspect_err(|cause| warn!("Failed to handle executor logs of peer <{peer_id}>:\n  {cause}"));
+        }
+        upstream::Message::TestRunUpdate(update) => {
+            let _ignore_result = handle_test_run_update(update, peer_id, resources_manager).await
+                .inspect_err(|cause| warn!("Failed to handle test run update of peer <{peer_id}>:\n  {cause}"));
+        }
     }
 }
 
+async fn handle_test_run_update(
+    update: opendut_types::proto::peer::executor::TestRunUpdate,
+    peer_id: PeerId,
+    resources_manager: &ResourcesManagerRef,
+) -> Result<(), Error> {
+    let update = TestRunUpdate::try_from(update)
+        .map_err(|cause| Error::Other { message: cause.to_string() })?;
+
+    actions::update_test_run(UpdateTestRunParams {
+        resources_manager: Arc::clone(resources_manager),
+        peer_id,
+        update,
+    }).await
+    .map_err(|cause| Error::Other { message: cause.to_string() })
+}
+
+async fn handle_executor_logs(
+    logs: ExecutorLogs,
+    peer_id: PeerId,
+    resources_manager: &ResourcesManagerRef,
+) -> Result<(), Error> {
+    type ErrorBuilder = ConversionErrorBuilder<ExecutorLogs, ExecutorRunLog>;
+
+    let executor_id: ExecutorId = logs.executor_id
+        .ok_or(ErrorBuilder::field_not_set("executor_id"))
+        .and_then(TryInto::try_into)
+        .map_err(|cause| Error::Other { message: cause.to_string() })?;
+
+    let run_id: ExecutorRunId = logs.run_id
+        .ok_or(ErrorBuilder::field_not_set("run_id"))
+        .and_then(TryInto::try_into)
+        .map_err(|cause| Error::Other { message: cause.to_string() })?;
+
+    let lines = logs.lines.into_iter()
+        .map(TryInto::try_into)
+        .collect::<Result<Vec<ExecutorLogLine>, ConversionError>>()
+        .map_err(|cause| Error::Other { message: cause.to_string() })?;
+
+    actions::append_executor_logs(AppendExecutorLogsParams {
+        resources_manager: Arc::clone(resources_manager),
+        peer_id,
+        executor_id,
+        run_id,
+        lines,
+    }).await
+    .map_err(|cause| Error::Other { message: cause.to_string() })
+}
+
+async fn handle_network_diagnostics_report(
+    report: NetworkDiagnosticsReport,
+    pending_network_diagnostics: &PendingNetworkDiagnosticsRef,
+) -> Result<(), ConversionError> {
+    type ErrorBuilder = ConversionErrorBuilder<NetworkDiagnosticsReport, Vec<PeerLinkDiagnostics>>;
+
+    let id: NetworkDiagnosticsId = report.id
+        .ok_or(ErrorBuilder::field_not_set("id"))?
+        .try_into()?;
+
+    let links = report.links.into_iter()
+        .map(TryInto::try_into)
+        .collect::<Result<Vec<PeerLinkDiagnostics>, _>>()?;
+
+    match pending_network_diagnostics.lock().await.remove(&id) {
+        Some(tx_report) => {
+            let _ignore_result = tx_report.send(links); //requester may have timed out in the meantime
+        }
+        None => warn!("Received network diagnostics report <{id}>, which was not requested or has timed out."),
+    }
+    Ok(())
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("DownstreamSend Error: {0}")]
@@ -208,6 +428,16 @@ pub enum Error {
     Other { message: String },
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum RequestNetworkDiagnosticsError {
+    #[error("Error while sending network diagnostics request to peer <{peer_id}>.")]
+    Send { peer_id: PeerId, #[source] source: Error },
+    #[error("Peer <{peer_id}> did not report the results of its network diagnostics within {} ms.", timeout.as_millis())]
+    Timeout { peer_id: PeerId, timeout: Duration },
+    #[error("Peer <{peer_id}> disconnected before reporting the results of its network diagnostics.")]
+    Disconnected { peer_id: PeerId },
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum OpenError {
     #[error(
@@ -217,6 +447,13 @@ pub enum OpenError {
     )]
     PeerAlreadyConnected { peer_id: PeerId },
 
+    #[error(
+        "Peer <{peer_id}> opened stream with address {remote_host}, which is already used by another connected peer. \
+        Configure a unique address for the peer via 'vpn.direct.address' in EDGAR. \
+        Rejecting connection."
+    )]
+    RemoteHostInUse { peer_id: PeerId, remote_host: IpAddr },
+
     #[error("Error while sending peer configuration to peer:\n  {cause}")]
     SendApplyPeerConfiguration { peer_id: PeerId, cause: String },
 
@@ -235,6 +472,9 @@ pub enum RemovePeerError {
 #[derive(Clone)]
 pub struct PeerMessagingBrokerOptions {
     pub peer_disconnect_timeout: Duration,
+    /// Rejects peers, which report the address of another connected peer.
+    /// Enabled for the direct network, where EDGAR determines its address itself.
+    pub unique_remote_hosts: bool,
 }
 impl PeerMessagingBrokerOptions {
     pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
@@ -242,8 +482,12 @@ impl PeerMessagingBrokerOptions {
             config.get::<u64>("peer.disconnect.timeout.ms")?
         );
 
+        let unique_remote_hosts = config.get::<bool>("vpn.enabled")?
+            && config.get::<String>("vpn.kind")? == "direct";
+
         Ok(PeerMessagingBrokerOptions {
             peer_disconnect_timeout,
+            unique_remote_hosts,
         })
     }
 }
@@ -258,6 +502,7 @@ mod tests {
     use tokio::sync::mpsc::Receiver;
 
     use opendut_carl_api::proto::services::peer_messaging_broker::Ping;
+    use opendut_types::peer::executor::test_run::{TestRun, TestRunStatus};
 
     use crate::resources::manager::ResourcesManager;
     use crate::resources::storage::ResourcesStorageApi;
@@ -269,12 +514,13 @@ mod tests {
 
         let options = PeerMessagingBrokerOptions {
             peer_disconnect_timeout: Duration::from_millis(200),
+            unique_remote_hosts: false,
         };
         let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options.clone());
 
         let remote_host = IpAddr::from_str("1.2.3.4")?;
 
-        let (sender, mut receiver) = testee.open(peer_id, remote_host).await?;
+        let (sender, mut receiver) = testee.open(peer_id, remote_host, None).await?;
 
         { //assert state contains peer connected and up
             let peers = testee.peers.read().await;
@@ -335,6 +581,15 @@ mod tests {
                     panic!("PeerState should be 'Down' after timeout.");
                 }
             }
+
+            let sessions = resources_manager.get::<PeerConnectionSessions>(peer_id).await?
+                .expect("Connection sessions of peer <{peer_id}> should exist.")
+                .sessions;
+            assert_that!(sessions.len(), eq(1));
+            let session = sessions.first().unwrap();
+            assert_that!(session.peer_id, eq(peer_id));
+            assert_that!(session.remote_host, eq(remote_host));
+            assert!(session.disconnected_at.is_some(), "Connection session should be closed after timeout.");
         }
 
         Ok(())
@@ -346,15 +601,16 @@ mod tests {
 
         let options = PeerMessagingBrokerOptions {
             peer_disconnect_timeout: Duration::from_millis(200),
+            unique_remote_hosts: false,
         };
         let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options.clone());
 
         let remote_host = IpAddr::from_str("1.2.3.4")?;
 
-        let result = testee.open(peer_id, remote_host).await;
+        let result = testee.open(peer_id, remote_host, None).await;
         assert!(result.is_ok());
 
-        let result = testee.open(peer_id, remote_host).await;
+        let result = testee.open(peer_id, remote_host, None).await;
         assert_that!(
             result.unwrap_err(),
             matches_pattern!(OpenError::PeerAlreadyConnected { peer_id: eq(&peer_id) })
@@ -363,6 +619,230 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_return_the_network_diagnostics_reported_by_the_peer() -> anyhow::Result<()> {
+        let Fixture { resources_manager, peer_id } = fixture().await?;
+
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(10),
+            unique_remote_hosts: false,
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options);
+
+        let (sender, mut receiver) = testee.open(peer_id, IpAddr::from_str("1.2.3.4")?, None).await?;
+        let _initial_apply_peer_configuration = receiver.recv().await.unwrap();
+
+        let target = PeerClusterAssignment {
+            peer_id: PeerId::random(),
+            vpn_address: IpAddr::from_str("10.0.0.2")?,
+            can_server_port: opendut_types::util::Port(10001),
+            device_interfaces: vec![],
+        };
+        let link = PeerLinkDiagnostics {
+            source: peer_id,
+            target: target.peer_id,
+            latency: None,
+            throughput: None,
+            path_mtu: Some(1420),
+            errors: vec![],
+        };
+
+        let request = {
+            let testee = Arc::clone(&testee);
+            let target = Clone::clone(&target);
+            tokio::spawn(async move {
+                testee.request_network_diagnostics(peer_id, NetworkDiagnosticsOptions::default(), vec![target], Duration::from_secs(5)).await
+            })
+        };
+
+        let received = receiver.recv().await.unwrap().message.unwrap();
+        let downstream::Message::RunNetworkDiagnostics(run_network_diagnostics) = received else {
+            panic!("Expected RunNetworkDiagnostics, but received: {received:?}");
+        };
+        let expected_target: opendut_types::proto::cluster::PeerClusterAssignment = Clone::clone(&target).into();
+        assert_that!(run_network_diagnostics.targets, elements_are![eq(&expected_target)]);
+
+        sender.send(upstream::Message::NetworkDiagnosticsReport(NetworkDiagnosticsReport {
+            id: run_network_diagnostics.id,
+            links: vec![Clone::clone(&link).into()],
+        })).await?;
+
+        let result = request.await??;
+        assert_that!(result, elements_are![eq(&link)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_time_out_when_the_peer_does_not_report_network_diagnostics() -> anyhow::Result<()> {
+        let Fixture { resources_manager, peer_id } = fixture().await?;
+
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(10),
+            unique_remote_hosts: false,
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options);
+
+        let (_sender, _receiver) = testee.open(peer_id, IpAddr::from_str("1.2.3.4")?, None).await?;
+
+        let result = testee.request_network_diagnostics(peer_id, NetworkDiagnosticsOptions::default(), vec![], Duration::from_millis(100)).await;
+
+        assert_that!(result, err(matches_pattern!(RequestNetworkDiagnosticsError::Timeout { .. })));
+        assert!(testee.pending_network_diagnostics.lock().await.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_close_stale_sessions_and_retain_only_the_newest_sessions() -> anyhow::Result<()> {
+        let peer_id = PeerId::random();
+        let session = |seconds: u64| PeerConnectionSession {
+            id: PeerConnectionSessionId::random(),
+            peer_id,
+            remote_host: IpAddr::from_str("1.2.3.4").unwrap(),
+            edgar_version: None,
+            connected_at: SystemTime::UNIX_EPOCH + Duration::from_secs(seconds),
+            disconnected_at: None,
+        };
+        let mut sessions = PeerConnectionSessions::new(peer_id);
+
+        for seconds in 0..=(MAX_CONNECTION_SESSIONS_PER_PEER as u64) {
+            open_connection_session(&mut sessions, session(seconds));
+        }
+
+        assert_that!(sessions.sessions.len(), eq(MAX_CONNECTION_SESSIONS_PER_PEER));
+        let oldest = sessions.sessions.first().unwrap();
+        assert_that!(oldest.connected_at, eq(SystemTime::UNIX_EPOCH + Duration::from_secs(1)));
+        assert_that!(oldest.disconnected_at, some(eq(oldest.connected_at)));
+
+        let connected_sessions = sessions.sessions.iter().filter(|session| session.is_connected()).count();
+        assert_that!(connected_sessions, eq(1));
+        assert_that!(sessions.sessions.last().unwrap().is_connected(), eq(true));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_close_the_connection_session_of_a_peer() -> anyhow::Result<()> {
+        let Fixture { resources_manager, peer_id } = fixture().await?;
+
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(10),
+            unique_remote_hosts: false,
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options);
+
+        let (_sender, _receiver) = testee.open(peer_id, IpAddr::from_str("1.2.3.4")?, None).await?;
+        testee.remove_peer(peer_id).await?;
+        let (_sender, _receiver) = testee.open(peer_id, IpAddr::from_str("1.2.3.4")?, None).await?;
+
+        let sessions = resources_manager.get::<PeerConnectionSessions>(peer_id).await?
+            .map(|sessions| sessions.sessions)
+            .unwrap_or_default();
+        assert_that!(sessions.iter().map(PeerConnectionSession::is_connected).collect::<Vec<_>>(), elements_are![eq(&false), eq(&true)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_a_peer_with_the_address_of_another_connected_peer_if_remote_hosts_must_be_unique() -> anyhow::Result<()> {
+        let Fixture { resources_manager, peer_id } = fixture().await?;
+        let other_peer_id = PeerId::random();
+        let remote_host = IpAddr::from_str("192.168.1.23")?;
+
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(10),
+            unique_remote_hosts: true,
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options);
+
+        let (_sender, _receiver) = testee.open(peer_id, remote_host, None).await?;
+
+        let result = testee.open(other_peer_id, remote_host, None).await;
+        assert!(matches!(result, Err(OpenError::RemoteHostInUse { peer_id, .. }) if peer_id == other_peer_id));
+        assert_that!(resources_manager.get::<PeerState>(other_peer_id).await?, none());
+
+        let (_other_sender, _other_receiver) = testee.open(other_peer_id, IpAddr::from_str("192.168.1.24")?, None).await?;
+
+        testee.remove_peer(peer_id).await?;
+        let (_sender, _receiver) = testee.open(PeerId::random(), remote_host, None).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_abort_the_unfinished_test_runs_of_a_disconnected_peer() -> anyhow::Result<()> {
+        let Fixture { resources_manager, peer_id } = fixture().await?;
+
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(10),
+            unique_remote_hosts: false,
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options);
+
+        let (_sender, _receiver) = testee.open(peer_id, IpAddr::from_str("1.2.3.4")?, None).await?;
+        let test_runs = insert_test_runs(&resources_manager, peer_id).await?;
+
+        testee.remove_peer(peer_id).await?;
+
+        assert_that!(test_run_statuses(&resources_manager, &test_runs).await?, elements_are![
+            eq(&TestRunStatus::Aborted),
+            eq(&TestRunStatus::Aborted),
+            eq(&TestRunStatus::Succeeded),
+            eq(&TestRunStatus::Running),
+        ]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_abort_the_unfinished_test_runs_of_a_peer_when_it_connects_again() -> anyhow::Result<()> {
+        let Fixture { resources_manager, peer_id } = fixture().await?;
+        let test_runs = insert_test_runs(&resources_manager, peer_id).await?; //e.g. left behind by CARL stopping while the peer was connected
+
+        let options = PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(10),
+            unique_remote_hosts: false,
+        };
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options);
+
+        let (_sender, _receiver) = testee.open(peer_id, IpAddr::from_str("1.2.3.4")?, None).await?;
+
+        assert_that!(test_run_statuses(&resources_manager, &test_runs).await?, elements_are![
+            eq(&TestRunStatus::Aborted),
+            eq(&TestRunStatus::Aborted),
+            eq(&TestRunStatus::Succeeded),
+            eq(&TestRunStatus::Running),
+        ]);
+        Ok(())
+    }
+
+    /// Inserts a pending, a running and a succeeded run of the peer, as well as a running run of another peer.
+    async fn insert_test_runs(resources_manager: &ResourcesManagerRef, peer_id: PeerId) -> anyhow::Result<Vec<TestRun>> {
+        let test_run = |peer_id: PeerId, status: TestRunStatus| TestRun {
+            status,
+            ..TestRun::new(ExecutorRunId::random(), ExecutorId::random(), peer_id, None, None)
+        };
+        let test_runs = vec![
+            test_run(peer_id, TestRunStatus::Pending),
+            test_run(peer_id, TestRunStatus::Running),
+            test_run(peer_id, TestRunStatus::Succeeded),
+            test_run(PeerId::random(), TestRunStatus::Running),
+        ];
+        for test_run in &test_runs {
+            resources_manager.insert(test_run.id, Clone::clone(test_run)).await?;
+        }
+        Ok(test_runs)
+    }
+
+    async fn test_run_statuses(resources_manager: &ResourcesManagerRef, test_runs: &[TestRun]) -> anyhow::Result<Vec<TestRunStatus>> {
+        let mut statuses = Vec::new();
+        for test_run in test_runs {
+            let test_run = resources_manager.get::<TestRun>(test_run.id).await?
+                .expect("Test run should exist.");
+            statuses.push(test_run.status);
+        }
+        Ok(statuses)
+    }
+
     async fn do_ping(sender: &mpsc::Sender<upstream::Message>, receiver: &mut Receiver<Downstream>) {
         sender.send(upstream::Message::Ping(Ping {})).await
             .unwrap();
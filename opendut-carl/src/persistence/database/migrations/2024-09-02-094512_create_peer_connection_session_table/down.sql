@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS peer_connection_session_peer_id_index;
+DROP TABLE IF EXISTS peer_connection_session;
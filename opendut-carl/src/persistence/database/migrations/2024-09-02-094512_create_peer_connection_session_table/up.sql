@@ -0,0 +1,9 @@
+CREATE TABLE peer_connection_session (
+    session_id uuid PRIMARY KEY,
+    peer_id uuid NOT NULL, -- no foreign key, to retain the history of peers which connected without or before having a descriptor
+    remote_host text NOT NULL,
+    edgar_version text NULL,
+    connected_at timestamp NOT NULL,
+    disconnected_at timestamp NULL
+);
+CREATE INDEX peer_connection_session_peer_id_index ON peer_connection_session(peer_id);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS executor_log_line;
+DROP INDEX IF EXISTS executor_run_log_executor_id_index;
+DROP TABLE IF EXISTS executor_run_log;
@@ -0,0 +1,18 @@
+CREATE TABLE executor_run_log (
+    run_id uuid PRIMARY KEY,
+    executor_id uuid NOT NULL, -- no foreign key, to retain the logs of executors which have been removed from their peer
+    peer_id uuid NOT NULL,
+    started_at timestamp,
+    next_sequence_number bigint NOT NULL DEFAULT 0,
+    line_count bigint NOT NULL DEFAULT 0
+);
+CREATE INDEX executor_run_log_executor_id_index ON executor_run_log(executor_id);
+
+CREATE TABLE executor_log_line (
+    run_id uuid NOT NULL REFERENCES executor_run_log(run_id) ON DELETE CASCADE,
+    sequence_number bigint NOT NULL,
+    timestamp timestamp NOT NULL,
+    stream text NOT NULL,
+    text text NOT NULL,
+    PRIMARY KEY (run_id, sequence_number)
+);
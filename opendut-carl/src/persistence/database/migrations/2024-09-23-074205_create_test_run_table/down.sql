@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS test_run_cluster_id_index;
+DROP INDEX IF EXISTS test_run_executor_id_index;
+DROP TABLE IF EXISTS test_run;
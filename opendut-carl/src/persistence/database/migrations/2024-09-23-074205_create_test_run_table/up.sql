@@ -0,0 +1,15 @@
+CREATE TABLE test_run (
+    run_id uuid PRIMARY KEY,
+    executor_id uuid NOT NULL, -- no foreign key, to retain the runs of executors which have been removed from their peer
+    peer_id uuid NOT NULL,
+    cluster_id uuid NULL, -- no foreign key, to retain the runs of clusters which have been deleted
+    triggered_by text NULL,
+    created_at timestamp NOT NULL,
+    started_at timestamp NULL,
+    finished_at timestamp NULL,
+    status text NOT NULL,
+    exit_code integer NULL,
+    results_location text NULL
+);
+CREATE INDEX test_run_executor_id_index ON test_run(executor_id);
+CREATE INDEX test_run_cluster_id_index ON test_run(cluster_id);
@@ -0,0 +1,8 @@
+CREATE TABLE executor_kind_executable (
+    executor_id uuid PRIMARY KEY REFERENCES executor_descriptor(executor_id) ON DELETE CASCADE,
+    path text NOT NULL,
+    args text[] NOT NULL,
+    envs jsonb[] NOT NULL,
+    working_dir text NULL,
+    timeout_seconds bigint NULL
+);
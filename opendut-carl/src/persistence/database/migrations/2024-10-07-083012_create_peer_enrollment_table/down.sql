@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS peer_enrollment_peer_id_index;
+DROP TABLE IF EXISTS peer_enrollment;
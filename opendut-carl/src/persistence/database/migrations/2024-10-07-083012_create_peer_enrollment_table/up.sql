@@ -0,0 +1,8 @@
+CREATE TABLE peer_enrollment (
+    enrollment_id uuid PRIMARY KEY,
+    peer_id uuid NOT NULL, -- no foreign key, to retain the enrollments of peers which have been deleted
+    reenrollment boolean NOT NULL,
+    issued_by text NULL,
+    issued_at timestamp NOT NULL
+);
+CREATE INDEX peer_enrollment_peer_id_index ON peer_enrollment(peer_id);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS peer_plugin_assignment;
+DROP TABLE IF EXISTS plugin_artifact;
+DROP TABLE IF EXISTS plugin_descriptor;
@@ -0,0 +1,24 @@
+CREATE TABLE plugin_descriptor (
+    plugin_id uuid PRIMARY KEY,
+    name text NOT NULL,
+    version text NOT NULL,
+    kind text NOT NULL,
+    checksum bytea NOT NULL,
+    manifest text NULL,
+    signature bytea NOT NULL,
+    uploaded_by text NULL,
+    uploaded_at timestamp NOT NULL,
+    UNIQUE (name, version)
+);
+
+CREATE TABLE plugin_artifact (
+    plugin_id uuid PRIMARY KEY REFERENCES plugin_descriptor(plugin_id) ON DELETE CASCADE,
+    binary_data bytea NOT NULL
+);
+
+CREATE TABLE peer_plugin_assignment (
+    peer_id uuid NOT NULL REFERENCES peer_descriptor(peer_id) ON DELETE CASCADE,
+    plugin_id uuid NOT NULL REFERENCES plugin_descriptor(plugin_id) ON DELETE CASCADE,
+    position integer NOT NULL,
+    PRIMARY KEY (peer_id, plugin_id)
+);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS wireguard_peer_allocation;
@@ -0,0 +1,5 @@
+CREATE TABLE wireguard_peer_allocation (
+    peer_id uuid PRIMARY KEY, -- no foreign key, as the address is allocated before the peer descriptor is stored
+    address text NOT NULL UNIQUE,
+    key_nonce uuid NOT NULL
+);
@@ -56,6 +56,38 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    executor_kind_executable (executor_id) {
+        executor_id -> Uuid,
+        path -> Text,
+        args -> Array<Nullable<Text>>,
+        envs -> Array<Nullable<Jsonb>>,
+        working_dir -> Nullable<Text>,
+        timeout_seconds -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    executor_log_line (run_id, sequence_number) {
+        run_id -> Uuid,
+        sequence_number -> Int8,
+        timestamp -> Timestamp,
+        stream -> Text,
+        text -> Text,
+    }
+}
+
+diesel::table! {
+    executor_run_log (run_id) {
+        run_id -> Uuid,
+        executor_id -> Uuid,
+        peer_id -> Uuid,
+        started_at -> Nullable<Timestamp>,
+        next_sequence_number -> Int8,
+        line_count -> Int8,
+    }
+}
+
 diesel::table! {
     network_interface_descriptor (network_interface_id) {
         network_interface_id -> Uuid,
@@ -76,6 +108,27 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    peer_connection_session (session_id) {
+        session_id -> Uuid,
+        peer_id -> Uuid,
+        remote_host -> Text,
+        edgar_version -> Nullable<Text>,
+        connected_at -> Timestamp,
+        disconnected_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    peer_enrollment (enrollment_id) {
+        enrollment_id -> Uuid,
+        peer_id -> Uuid,
+        reenrollment -> Bool,
+        issued_by -> Nullable<Text>,
+        issued_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     peer_descriptor (peer_id) {
         peer_id -> Uuid,
@@ -85,6 +138,59 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    peer_plugin_assignment (peer_id, plugin_id) {
+        peer_id -> Uuid,
+        plugin_id -> Uuid,
+        position -> Int4,
+    }
+}
+
+diesel::table! {
+    plugin_artifact (plugin_id) {
+        plugin_id -> Uuid,
+        binary_data -> Bytea,
+    }
+}
+
+diesel::table! {
+    plugin_descriptor (plugin_id) {
+        plugin_id -> Uuid,
+        name -> Text,
+        version -> Text,
+        kind -> Text,
+        checksum -> Bytea,
+        manifest -> Nullable<Text>,
+        signature -> Bytea,
+        uploaded_by -> Nullable<Text>,
+        uploaded_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    test_run (run_id) {
+        run_id -> Uuid,
+        executor_id -> Uuid,
+        peer_id -> Uuid,
+        cluster_id -> Nullable<Uuid>,
+        triggered_by -> Nullable<Text>,
+        created_at -> Timestamp,
+        started_at -> Nullable<Timestamp>,
+        finished_at -> Nullable<Timestamp>,
+        status -> Text,
+        exit_code -> Nullable<Int4>,
+        results_location -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    wireguard_peer_allocation (peer_id) {
+        peer_id -> Uuid,
+        address -> Text,
+        key_nonce -> Uuid,
+    }
+}
+
 diesel::joinable!(cluster_configuration -> peer_descriptor (leader_id));
 diesel::joinable!(cluster_device -> cluster_configuration (cluster_id));
 diesel::joinable!(cluster_device -> device_descriptor (device_id));
@@ -92,8 +198,13 @@ diesel::joinable!(device_descriptor -> network_interface_descriptor (network_int
 diesel::joinable!(device_tag -> device_descriptor (device_id));
 diesel::joinable!(executor_descriptor -> peer_descriptor (peer_id));
 diesel::joinable!(executor_kind_container -> executor_descriptor (executor_id));
+diesel::joinable!(executor_kind_executable -> executor_descriptor (executor_id));
+diesel::joinable!(executor_log_line -> executor_run_log (run_id));
 diesel::joinable!(network_interface_descriptor -> peer_descriptor (peer_id));
 diesel::joinable!(network_interface_kind_can -> network_interface_descriptor (network_interface_id));
+diesel::joinable!(peer_plugin_assignment -> peer_descriptor (peer_id));
+diesel::joinable!(peer_plugin_assignment -> plugin_descriptor (plugin_id));
+diesel::joinable!(plugin_artifact -> plugin_descriptor (plugin_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     cluster_configuration,
@@ -102,7 +213,17 @@ diesel::allow_tables_to_appear_in_same_query!(
     device_tag,
     executor_descriptor,
     executor_kind_container,
+    executor_kind_executable,
+    executor_log_line,
+    executor_run_log,
     network_interface_descriptor,
     network_interface_kind_can,
+    peer_connection_session,
     peer_descriptor,
+    peer_enrollment,
+    peer_plugin_assignment,
+    plugin_artifact,
+    plugin_descriptor,
+    test_run,
+    wireguard_peer_allocation,
 );
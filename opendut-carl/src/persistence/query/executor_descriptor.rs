@@ -1,5 +1,6 @@
 use diesel::{Connection, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
 use opendut_types::peer::executor::container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume};
+use opendut_types::peer::executor::executable::{ExecutablePath, ExecutableTimeout, ExecutableWorkingDirectory};
 use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorId, ExecutorKind, ResultsUrl};
 use opendut_types::peer::PeerId;
 use tracing::warn;
@@ -41,14 +42,37 @@ pub(in crate::persistence) struct PersistableExecutorKindContainer {
     args: NullRemovingTextArray,
 }
 
+#[derive(diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::Identifiable, diesel::Associations, diesel::AsChangeset, Debug, PartialEq)]
+#[diesel(table_name = schema::executor_kind_executable)]
+#[diesel(primary_key(executor_id))]
+#[diesel(belongs_to(PersistableExecutorDescriptor, foreign_key = executor_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub(in crate::persistence) struct PersistableExecutorKindExecutable {
+    pub executor_id: Uuid,
+    path: String,
+    args: NullRemovingTextArray,
+    envs: Vec<Option<PersistableEnvironmentVariable>>,
+    working_dir: Option<String>,
+    timeout_seconds: Option<i64>,
+}
+
 pub fn insert_into_database(executor: ExecutorDescriptor, peer_id: PeerId, connection: &mut PgConnection) -> PersistenceResult<()> {
     let ExecutorDescriptor { id, kind, results_url } = executor;
 
     let executor_id = id.uuid;
 
-    let (kind, executor_kind_container) = match kind {
-        ExecutorKind::Executable => {
-            (PersistableExecutorKind::Executable, None)
+    let (kind, executor_kind_container, executor_kind_executable) = match kind {
+        ExecutorKind::Executable { path, args, envs, working_dir, timeout } => {
+
+            let executor_kind_executable = PersistableExecutorKindExecutable {
+                executor_id,
+                path: path.into(),
+                args: args.into_iter().map(|arg| arg.value().to_owned()).collect(),
+                envs: envs.into_iter().map(|env| Some(env.into())).collect(),
+                working_dir: working_dir.map(String::from),
+                timeout_seconds: timeout.map(|timeout| u64::from(timeout) as i64),
+            };
+            (PersistableExecutorKind::Executable, None, Some(executor_kind_executable))
         }
         ExecutorKind::Container { engine, name, image, volumes, devices, envs, ports, command, args } => {
 
@@ -80,7 +104,7 @@ pub fn insert_into_database(executor: ExecutorDescriptor, peer_id: PeerId, conne
                 command,
                 args,
             };
-            (PersistableExecutorKind::Container, Some(executor_kind_container))
+            (PersistableExecutorKind::Container, Some(executor_kind_container), None)
         }
     };
 
@@ -93,13 +117,14 @@ pub fn insert_into_database(executor: ExecutorDescriptor, peer_id: PeerId, conne
         peer_id: peer_id.uuid,
     };
 
-    insert_persistable(executor_descriptor, executor_kind_container, executor.id, connection)
+    insert_persistable(executor_descriptor, executor_kind_container, executor_kind_executable, executor.id, connection)
 }
 
 
 fn insert_persistable(
     executor_descriptor: PersistableExecutorDescriptor,
     maybe_executor_kind_container: Option<PersistableExecutorKindContainer>,
+    maybe_executor_kind_executable: Option<PersistableExecutorKindExecutable>,
     executor_id: ExecutorId,
     connection: &mut PgConnection
 ) -> PersistenceResult<()> {
@@ -124,6 +149,16 @@ fn insert_persistable(
                 .map_err(|cause| PersistenceError::insert::<PersistableExecutorKindContainer>(executor_id.uuid, cause))
         }).transpose()?;
 
+        maybe_executor_kind_executable.map(|executor_kind_executable| {
+            diesel::insert_into(schema::executor_kind_executable::table)
+                .values(&executor_kind_executable)
+                .on_conflict(schema::executor_kind_executable::executor_id)
+                .do_update()
+                .set(&executor_kind_executable)
+                .execute(connection)
+                .map_err(|cause| PersistenceError::insert::<PersistableExecutorKindExecutable>(executor_id.uuid, cause))
+        }).transpose()?;
+
         Ok(())
     })?;
 
@@ -137,12 +172,12 @@ pub fn list_filtered_by_peer(
 ) -> PersistenceResult<Vec<ExecutorDescriptor>> {
     let persistables = list_filtered_by_peer_id_persistable(peer_id, connection)?;
 
-    let result = persistables.into_iter().map(|(persistable_executable_descriptor, persistable_executable_kind_container)| {
+    let result = persistables.into_iter().map(|(persistable_executable_descriptor, persistable_executable_kind_container, persistable_executor_kind_executable)| {
         let PersistableExecutorDescriptor { executor_id, kind, results_url, peer_id: _ } = persistable_executable_descriptor;
 
         let id = ExecutorId::from(executor_id);
 
-        let kind = executor_kind_from_persistable(kind, persistable_executable_kind_container)?;
+        let kind = executor_kind_from_persistable(kind, persistable_executable_kind_container, persistable_executor_kind_executable)?;
 
         let results_url = results_url.map(ResultsUrl::try_from).transpose()
             .map_err(PersistenceError::list::<ExecutorDescriptor>)?;
@@ -159,12 +194,18 @@ fn list_filtered_by_peer_id_persistable(
     connection: &mut PgConnection
 ) -> PersistenceResult<Vec<(
     PersistableExecutorDescriptor,
-    Option<PersistableExecutorKindContainer>
+    Option<PersistableExecutorKindContainer>,
+    Option<PersistableExecutorKindExecutable>,
 )>> {
     schema::executor_descriptor::table
         .left_join(schema::executor_kind_container::table)
+        .left_join(schema::executor_kind_executable::table)
         .filter(schema::executor_descriptor::peer_id.eq(peer_id.uuid))
-        .select((PersistableExecutorDescriptor::as_select(), Option::<PersistableExecutorKindContainer>::as_select()))
+        .select((
+            PersistableExecutorDescriptor::as_select(),
+            Option::<PersistableExecutorKindContainer>::as_select(),
+            Option::<PersistableExecutorKindExecutable>::as_select(),
+        ))
         .get_results(connection)
         .map_err(PersistenceError::list::<ExecutorDescriptor>)
 }
@@ -172,9 +213,51 @@ fn list_filtered_by_peer_id_persistable(
 fn executor_kind_from_persistable(
     persistable_executor_kind: PersistableExecutorKind,
     persistable_executor_kind_container: Option<PersistableExecutorKindContainer>,
+    persistable_executor_kind_executable: Option<PersistableExecutorKindExecutable>,
 ) -> PersistenceResult<ExecutorKind> {
     let result = match persistable_executor_kind {
-        PersistableExecutorKind::Executable => ExecutorKind::Executable,
+        PersistableExecutorKind::Executable => {
+            let persistable_executor_kind_executable = persistable_executor_kind_executable
+                .ok_or(PersistenceError::new::<ExecutorKind>(None::<Uuid>, PersistenceOperation::List, Option::<PersistenceError>::None))?;
+
+            let PersistableExecutorKindExecutable { executor_id: _, path, args, envs, working_dir, timeout_seconds } = persistable_executor_kind_executable;
+
+            let path = ExecutablePath::try_from(path)
+                .map_err(PersistenceError::list::<ExecutorKind>)?;
+
+            let args = args.into_iter()
+                .map(ContainerCommandArgument::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(PersistenceError::list::<ExecutorKind>)?;
+
+            let envs = envs.into_iter()
+                .filter_map(|env| {
+                    if env.is_none() {
+                        warn!("Database contained a NULL value in a list of envs. Removing the NULL value and loading list without it.");
+                    }
+                    env
+                })
+                .map(ContainerEnvironmentVariable::from)
+                .collect::<Vec<_>>();
+
+            let working_dir = working_dir
+                .map(ExecutableWorkingDirectory::try_from)
+                .transpose()
+                .map_err(PersistenceError::list::<ExecutorKind>)?;
+
+            let timeout = timeout_seconds
+                .map(|timeout_seconds| ExecutableTimeout::try_from(timeout_seconds as u64))
+                .transpose()
+                .map_err(PersistenceError::list::<ExecutorKind>)?;
+
+            ExecutorKind::Executable {
+                path,
+                args,
+                envs,
+                working_dir,
+                timeout,
+            }
+        }
         PersistableExecutorKind::Container => {
             let persistable_executor_kind_container = persistable_executor_kind_container
                 .ok_or(PersistenceError::new::<ExecutorKind>(None::<Uuid>, PersistenceOperation::List, Option::<PersistenceError>::None))?;
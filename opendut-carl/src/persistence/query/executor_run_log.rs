@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use uuid::Uuid;
+
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::executor::log::{ExecutorLogChunk, ExecutorLogChunkId, ExecutorLogLine, ExecutorRunId, ExecutorRunLogSummary};
+use opendut_types::peer::PeerId;
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+use crate::persistence::query::types::executor_log_stream::PersistableExecutorLogStream;
+
+pub fn insert_summary(summary: ExecutorRunLogSummary, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let ExecutorRunLogSummary { id, executor_id, peer_id, started_at, next_sequence_number, line_count } = summary;
+
+    let persistable_run = PersistableExecutorRunLog {
+        run_id: id.0,
+        executor_id: executor_id.uuid,
+        peer_id: peer_id.uuid,
+        started_at,
+        next_sequence_number: i64::try_from(next_sequence_number)
+            .map_err(|cause| PersistenceError::insert::<ExecutorRunLogSummary>(id.0, cause))?,
+        line_count: i64::try_from(line_count)
+            .map_err(|cause| PersistenceError::insert::<ExecutorRunLogSummary>(id.0, cause))?,
+    };
+
+    diesel::insert_into(schema::executor_run_log::table)
+        .values(&persistable_run)
+        .on_conflict(schema::executor_run_log::run_id)
+        .do_update()
+        .set(&persistable_run)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<ExecutorRunLogSummary>(id.0, cause))?;
+    Ok(())
+}
+
+pub fn remove_summary(run_id: ExecutorRunId, connection: &mut PgConnection) -> PersistenceResult<Option<ExecutorRunLogSummary>> {
+    let result = list_summaries(Filter::By(run_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::executor_run_log::table
+            .filter(schema::executor_run_log::run_id.eq(run_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<ExecutorRunLogSummary>(run_id.0, cause))?;
+
+    Ok(result)
+}
+
+pub fn list_summaries(filter_by_run_id: Filter<ExecutorRunId>, connection: &mut PgConnection) -> PersistenceResult<Vec<ExecutorRunLogSummary>> {
+    let mut query = schema::executor_run_log::table.into_boxed();
+
+    if let Filter::By(run_id) = filter_by_run_id {
+        query = query.filter(schema::executor_run_log::run_id.eq(run_id.0));
+    }
+
+    let persistable_runs = query
+        .select(PersistableExecutorRunLog::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<ExecutorRunLogSummary>)?;
+
+    persistable_runs.into_iter()
+        .map(|persistable_run| {
+            let PersistableExecutorRunLog { run_id, executor_id, peer_id, started_at, next_sequence_number, line_count } = persistable_run;
+
+            let to_u64 = |value: i64| u64::try_from(value)
+                .map_err(|cause|
+                    PersistenceError::get::<ExecutorRunLogSummary>(run_id, cause)
+                        .context("Failed to convert from database values to ExecutorRunLogSummary.")
+                );
+
+            Ok(ExecutorRunLogSummary {
+                id: ExecutorRunId::from(run_id),
+                executor_id: ExecutorId::from(executor_id),
+                peer_id: PeerId::from(peer_id),
+                started_at,
+                next_sequence_number: to_u64(next_sequence_number)?,
+                line_count: to_u64(line_count)?,
+            })
+        })
+        .collect()
+}
+
+/// Inserts those lines of the chunk, which have not been stored before.
+/// The summary of the run needs to be stored beforehand.
+pub fn insert_chunk(chunk: ExecutorLogChunk, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let ExecutorLogChunk { id, lines } = chunk;
+    let run_id = id.run_id;
+
+    let persistable_lines = lines.into_iter()
+        .map(|line| {
+            let sequence_number = i64::try_from(line.sequence_number)
+                .map_err(|cause| PersistenceError::insert::<ExecutorLogChunk>(run_id.0, cause))?;
+
+            Ok(PersistableExecutorLogLine {
+                run_id: run_id.0,
+                sequence_number,
+                timestamp: line.timestamp,
+                stream: PersistableExecutorLogStream::from(line.stream),
+                text: line.text,
+            })
+        })
+        .collect::<PersistenceResult<Vec<_>>>()?;
+
+    diesel::insert_into(schema::executor_log_line::table)
+        .values(&persistable_lines)
+        .on_conflict_do_nothing()
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<ExecutorLogChunk>(run_id.0, cause))?;
+    Ok(())
+}
+
+pub fn remove_chunk(chunk_id: ExecutorLogChunkId, connection: &mut PgConnection) -> PersistenceResult<Option<ExecutorLogChunk>> {
+    let result = get_chunk(chunk_id, connection)?;
+
+    let (first_sequence_number, end_sequence_number) = sequence_number_range(chunk_id)
+        .map_err(|cause| PersistenceError::remove::<ExecutorLogChunk>(chunk_id.run_id.0, cause))?;
+
+    diesel::delete(
+        schema::executor_log_line::table
+            .filter(schema::executor_log_line::run_id.eq(chunk_id.run_id.0))
+            .filter(schema::executor_log_line::sequence_number.ge(first_sequence_number))
+            .filter(schema::executor_log_line::sequence_number.lt(end_sequence_number))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<ExecutorLogChunk>(chunk_id.run_id.0, cause))?;
+
+    Ok(result)
+}
+
+/// Returns the lines of the chunk, or `None`, if none of its lines have been stored.
+pub fn get_chunk(chunk_id: ExecutorLogChunkId, connection: &mut PgConnection) -> PersistenceResult<Option<ExecutorLogChunk>> {
+    let (first_sequence_number, end_sequence_number) = sequence_number_range(chunk_id)
+        .map_err(|cause| PersistenceError::get::<ExecutorLogChunk>(chunk_id.run_id.0, cause))?;
+
+    let persistable_lines = schema::executor_log_line::table
+        .filter(schema::executor_log_line::run_id.eq(chunk_id.run_id.0))
+        .filter(schema::executor_log_line::sequence_number.ge(first_sequence_number))
+        .filter(schema::executor_log_line::sequence_number.lt(end_sequence_number))
+        .order(schema::executor_log_line::sequence_number.asc())
+        .select(PersistableExecutorLogLine::as_select())
+        .get_results(connection)
+        .map_err(|cause| PersistenceError::get::<ExecutorLogChunk>(chunk_id.run_id.0, cause))?;
+
+    let chunk = into_chunks(persistable_lines)?.pop();
+    Ok(chunk)
+}
+
+pub fn list_chunks(connection: &mut PgConnection) -> PersistenceResult<Vec<ExecutorLogChunk>> {
+    let persistable_lines = schema::executor_log_line::table
+        .order((schema::executor_log_line::run_id.asc(), schema::executor_log_line::sequence_number.asc()))
+        .select(PersistableExecutorLogLine::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<ExecutorLogChunk>)?;
+
+    into_chunks(persistable_lines)
+}
+
+fn sequence_number_range(chunk_id: ExecutorLogChunkId) -> Result<(i64, i64), std::num::TryFromIntError> {
+    let first_sequence_number = i64::try_from(chunk_id.first_sequence_number())?;
+    let end_sequence_number = first_sequence_number.saturating_add(i64::try_from(ExecutorLogChunkId::LINES_PER_CHUNK)?);
+    Ok((first_sequence_number, end_sequence_number))
+}
+
+/// Groups lines, which are ordered by sequence number, into their chunks.
+fn into_chunks(persistable_lines: Vec<PersistableExecutorLogLine>) -> PersistenceResult<Vec<ExecutorLogChunk>> {
+    let mut chunks: BTreeMap<(Uuid, u64), ExecutorLogChunk> = BTreeMap::new();
+
+    for persistable_line in persistable_lines {
+        let PersistableExecutorLogLine { run_id, sequence_number, timestamp, stream, text } = persistable_line;
+
+        let sequence_number = u64::try_from(sequence_number)
+            .map_err(|cause|
+                PersistenceError::get::<ExecutorLogChunk>(run_id, cause)
+                    .context("Failed to convert from database values to ExecutorLogLine.")
+            )?;
+
+        let chunk_id = ExecutorLogChunkId::containing(ExecutorRunId::from(run_id), sequence_number);
+
+        chunks.entry((run_id, chunk_id.index))
+            .or_insert_with(|| ExecutorLogChunk::new(chunk_id))
+            .lines.push(ExecutorLogLine {
+                sequence_number,
+                timestamp,
+                stream: stream.into(),
+                text,
+            });
+    }
+
+    Ok(chunks.into_values().collect())
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::executor_run_log)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(treat_none_as_null = true)]
+struct PersistableExecutorRunLog {
+    pub run_id: Uuid,
+    pub executor_id: Uuid,
+    pub peer_id: Uuid,
+    pub started_at: Option<SystemTime>,
+    pub next_sequence_number: i64,
+    pub line_count: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable)]
+#[diesel(table_name = schema::executor_log_line)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistableExecutorLogLine {
+    pub run_id: Uuid,
+    pub sequence_number: i64,
+    pub timestamp: SystemTime,
+    pub stream: PersistableExecutorLogStream,
+    pub text: String,
+}
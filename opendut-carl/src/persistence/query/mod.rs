@@ -4,8 +4,16 @@ pub mod cluster_device;
 pub mod device_descriptor;
 pub mod device_tag;
 pub mod executor_descriptor;
+pub mod executor_run_log;
 pub mod network_interface_descriptor;
+pub mod peer_connection_session;
 pub mod peer_descriptor;
+pub mod peer_enrollment;
+pub mod peer_plugin_assignment;
+pub mod plugin_artifact;
+pub mod plugin_descriptor;
+pub mod test_run;
+pub mod wireguard_peer_allocation;
 
 mod types;
 
@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use diesel::{Connection, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use uuid::Uuid;
+
+use opendut_types::peer::history::{PeerConnectionSession, PeerConnectionSessionId, PeerConnectionSessions};
+use opendut_types::peer::PeerId;
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+
+/// Stores the sessions of a peer and deletes its sessions, which are no longer retained.
+pub fn insert(sessions: PeerConnectionSessions, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let PeerConnectionSessions { peer_id, sessions } = sessions;
+
+    let persistable_sessions = sessions.into_iter()
+        .map(|session| {
+            let PeerConnectionSession { id, peer_id, remote_host, edgar_version, connected_at, disconnected_at } = session;
+            PersistablePeerConnectionSession {
+                session_id: id.0,
+                peer_id: peer_id.uuid,
+                remote_host: remote_host.to_string(),
+                edgar_version,
+                connected_at,
+                disconnected_at,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    connection.transaction::<_, PersistenceError, _>(|connection| {
+        let retained_session_ids = persistable_sessions.iter()
+            .map(|session| session.session_id)
+            .collect::<Vec<_>>();
+
+        diesel::delete(
+            schema::peer_connection_session::table
+                .filter(schema::peer_connection_session::peer_id.eq(peer_id.uuid))
+                .filter(schema::peer_connection_session::session_id.ne_all(retained_session_ids))
+        )
+        .execute(connection)
+        .map_err(|cause| PersistenceError::remove::<PeerConnectionSessions>(peer_id.uuid, cause))?;
+
+        for persistable in &persistable_sessions {
+            diesel::insert_into(schema::peer_connection_session::table)
+                .values(persistable)
+                .on_conflict(schema::peer_connection_session::session_id)
+                .do_update()
+                .set(persistable)
+                .execute(connection)
+                .map_err(|cause| PersistenceError::insert::<PeerConnectionSessions>(peer_id.uuid, cause))?;
+        }
+        Ok(())
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::peer_connection_session)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistablePeerConnectionSession {
+    pub session_id: Uuid,
+    pub peer_id: Uuid,
+    pub remote_host: String,
+    pub edgar_version: Option<String>,
+    pub connected_at: SystemTime,
+    pub disconnected_at: Option<SystemTime>,
+}
+
+pub fn remove(peer_id: PeerId, connection: &mut PgConnection) -> PersistenceResult<Option<PeerConnectionSessions>> {
+    let result = list(Filter::By(peer_id), connection)?
+        .pop();
+
+    diesel::delete(
+        schema::peer_connection_session::table
+            .filter(schema::peer_connection_session::peer_id.eq(peer_id.uuid))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<PeerConnectionSessions>(peer_id.uuid, cause))?;
+
+    Ok(result)
+}
+
+/// Lists the sessions grouped by peer. Filtering by peer uses the index on the peer ID.
+pub fn list(filter_by_peer_id: Filter<PeerId>, connection: &mut PgConnection) -> PersistenceResult<Vec<PeerConnectionSessions>> {
+    let mut query = schema::peer_connection_session::table.into_boxed();
+
+    if let Filter::By(peer_id) = filter_by_peer_id {
+        query = query.filter(schema::peer_connection_session::peer_id.eq(peer_id.uuid));
+    }
+
+    let persistable_sessions = query
+        .order((schema::peer_connection_session::peer_id.asc(), schema::peer_connection_session::connected_at.asc()))
+        .select(PersistablePeerConnectionSession::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<PeerConnectionSessions>)?;
+
+    let mut sessions_by_peer: BTreeMap<Uuid, PeerConnectionSessions> = BTreeMap::new();
+
+    for persistable in persistable_sessions {
+        let PersistablePeerConnectionSession { session_id, peer_id, remote_host, edgar_version, connected_at, disconnected_at } = persistable;
+
+        let remote_host = IpAddr::from_str(&remote_host)
+            .map_err(|cause|
+                PersistenceError::get::<PeerConnectionSessions>(peer_id, cause)
+                    .context("Failed to convert from database values to PeerConnectionSession.")
+            )?;
+
+        sessions_by_peer.entry(peer_id)
+            .or_insert_with(|| PeerConnectionSessions::new(PeerId::from(peer_id)))
+            .sessions.push(PeerConnectionSession {
+                id: PeerConnectionSessionId::from(session_id),
+                peer_id: PeerId::from(peer_id),
+                remote_host,
+                edgar_version,
+                connected_at,
+                disconnected_at,
+            });
+    }
+
+    Ok(sessions_by_peer.into_values().collect())
+}
@@ -0,0 +1,88 @@
+use std::time::SystemTime;
+
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use uuid::Uuid;
+
+use opendut_types::peer::history::{PeerEnrollment, PeerEnrollmentId};
+use opendut_types::peer::PeerId;
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+
+pub fn insert(enrollment: PeerEnrollment, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let PeerEnrollment { id, peer_id, reenrollment, issued_by, issued_at } = enrollment;
+
+    let persistable = PersistablePeerEnrollment {
+        enrollment_id: id.0,
+        peer_id: peer_id.uuid,
+        reenrollment,
+        issued_by,
+        issued_at,
+    };
+
+    diesel::insert_into(schema::peer_enrollment::table)
+        .values(&persistable)
+        .on_conflict(schema::peer_enrollment::enrollment_id)
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<PeerEnrollment>(persistable.enrollment_id, cause))?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::peer_enrollment)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistablePeerEnrollment {
+    pub enrollment_id: Uuid,
+    pub peer_id: Uuid,
+    pub reenrollment: bool,
+    pub issued_by: Option<String>,
+    pub issued_at: SystemTime,
+}
+
+pub fn remove(enrollment_id: PeerEnrollmentId, connection: &mut PgConnection) -> PersistenceResult<Option<PeerEnrollment>> {
+    let result = list(Filter::By(enrollment_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::peer_enrollment::table
+            .filter(schema::peer_enrollment::enrollment_id.eq(enrollment_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<PeerEnrollment>(enrollment_id.0, cause))?;
+
+    Ok(result)
+}
+
+pub fn list(filter_by_enrollment_id: Filter<PeerEnrollmentId>, connection: &mut PgConnection) -> PersistenceResult<Vec<PeerEnrollment>> {
+    let mut query = schema::peer_enrollment::table.into_boxed();
+
+    if let Filter::By(enrollment_id) = filter_by_enrollment_id {
+        query = query.filter(schema::peer_enrollment::enrollment_id.eq(enrollment_id.0));
+    }
+
+    let persistable_enrollments = query
+        .order(schema::peer_enrollment::issued_at.asc())
+        .select(PersistablePeerEnrollment::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<PeerEnrollment>)?;
+
+    let enrollments = persistable_enrollments.into_iter()
+        .map(|persistable| {
+            let PersistablePeerEnrollment { enrollment_id, peer_id, reenrollment, issued_by, issued_at } = persistable;
+
+            PeerEnrollment {
+                id: PeerEnrollmentId::from(enrollment_id),
+                peer_id: PeerId::from(peer_id),
+                reenrollment,
+                issued_by,
+                issued_at,
+            }
+        })
+        .collect();
+
+    Ok(enrollments)
+}
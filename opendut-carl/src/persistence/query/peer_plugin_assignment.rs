@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+
+use diesel::{Connection, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use uuid::Uuid;
+
+use opendut_types::peer::PeerId;
+use opendut_types::plugin::{PeerPluginAssignment, PluginId};
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+
+/// Replaces the plugins previously assigned to the peer.
+pub fn insert(assignment: PeerPluginAssignment, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let PeerPluginAssignment { peer_id, plugins } = assignment;
+
+    let persistables = plugins.into_iter()
+        .enumerate()
+        .map(|(position, plugin_id)| {
+            let position = i32::try_from(position)
+                .map_err(|cause| PersistenceError::insert::<PeerPluginAssignment>(peer_id.uuid, cause))?;
+
+            Ok(PersistablePeerPluginAssignment {
+                peer_id: peer_id.uuid,
+                plugin_id: plugin_id.0,
+                position,
+            })
+        })
+        .collect::<PersistenceResult<Vec<_>>>()?;
+
+    connection.transaction::<_, PersistenceError, _>(|connection| {
+        delete_for_peer(peer_id, connection)?;
+
+        diesel::insert_into(schema::peer_plugin_assignment::table)
+            .values(&persistables)
+            .execute(connection)
+            .map_err(|cause| PersistenceError::insert::<PeerPluginAssignment>(peer_id.uuid, cause))?;
+        Ok(())
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable)]
+#[diesel(table_name = schema::peer_plugin_assignment)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistablePeerPluginAssignment {
+    pub peer_id: Uuid,
+    pub plugin_id: Uuid,
+    pub position: i32,
+}
+
+pub fn remove(peer_id: PeerId, connection: &mut PgConnection) -> PersistenceResult<Option<PeerPluginAssignment>> {
+    let result = list(Filter::By(peer_id), connection)?
+        .first().cloned();
+
+    delete_for_peer(peer_id, connection)?;
+
+    Ok(result)
+}
+
+fn delete_for_peer(peer_id: PeerId, connection: &mut PgConnection) -> PersistenceResult<()> {
+    diesel::delete(
+        schema::peer_plugin_assignment::table
+            .filter(schema::peer_plugin_assignment::peer_id.eq(peer_id.uuid))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<PeerPluginAssignment>(peer_id.uuid, cause))?;
+    Ok(())
+}
+
+pub fn list(filter_by_peer_id: Filter<PeerId>, connection: &mut PgConnection) -> PersistenceResult<Vec<PeerPluginAssignment>> {
+    let mut query = schema::peer_plugin_assignment::table.into_boxed();
+
+    if let Filter::By(peer_id) = filter_by_peer_id {
+        query = query.filter(schema::peer_plugin_assignment::peer_id.eq(peer_id.uuid));
+    }
+
+    let persistable_assignments = query
+        .order((schema::peer_plugin_assignment::peer_id.asc(), schema::peer_plugin_assignment::position.asc()))
+        .select(PersistablePeerPluginAssignment::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<PeerPluginAssignment>)?;
+
+    let mut plugins_by_peer: BTreeMap<Uuid, Vec<PluginId>> = BTreeMap::new();
+    for persistable in persistable_assignments {
+        plugins_by_peer.entry(persistable.peer_id).or_default()
+            .push(PluginId::from(persistable.plugin_id));
+    }
+
+    let assignments = plugins_by_peer.into_iter()
+        .map(|(peer_id, plugins)| PeerPluginAssignment {
+            peer_id: PeerId::from(peer_id),
+            plugins,
+        })
+        .collect();
+
+    Ok(assignments)
+}
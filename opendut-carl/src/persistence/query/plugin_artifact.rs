@@ -0,0 +1,71 @@
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use uuid::Uuid;
+
+use opendut_types::plugin::{PluginArtifact, PluginId};
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+
+/// Requires the [`opendut_types::plugin::PluginDescriptor`] of the plugin to have been inserted before.
+pub fn insert(artifact: PluginArtifact, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let PluginArtifact { id, binary } = artifact;
+
+    let persistable = PersistablePluginArtifact {
+        plugin_id: id.0,
+        binary_data: binary,
+    };
+
+    diesel::insert_into(schema::plugin_artifact::table)
+        .values(&persistable)
+        .on_conflict(schema::plugin_artifact::plugin_id)
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<PluginArtifact>(persistable.plugin_id, cause))?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::plugin_artifact)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistablePluginArtifact {
+    pub plugin_id: Uuid,
+    pub binary_data: Vec<u8>,
+}
+
+pub fn remove(plugin_id: PluginId, connection: &mut PgConnection) -> PersistenceResult<Option<PluginArtifact>> {
+    let result = list(Filter::By(plugin_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::plugin_artifact::table
+            .filter(schema::plugin_artifact::plugin_id.eq(plugin_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<PluginArtifact>(plugin_id.0, cause))?;
+
+    Ok(result)
+}
+
+pub fn list(filter_by_plugin_id: Filter<PluginId>, connection: &mut PgConnection) -> PersistenceResult<Vec<PluginArtifact>> {
+    let mut query = schema::plugin_artifact::table.into_boxed();
+
+    if let Filter::By(plugin_id) = filter_by_plugin_id {
+        query = query.filter(schema::plugin_artifact::plugin_id.eq(plugin_id.0));
+    }
+
+    let persistable_artifacts = query
+        .select(PersistablePluginArtifact::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<PluginArtifact>)?;
+
+    let artifacts = persistable_artifacts.into_iter()
+        .map(|persistable| PluginArtifact {
+            id: PluginId::from(persistable.plugin_id),
+            binary: persistable.binary_data,
+        })
+        .collect();
+
+    Ok(artifacts)
+}
@@ -0,0 +1,100 @@
+use std::time::SystemTime;
+
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use uuid::Uuid;
+
+use opendut_types::plugin::{PluginDescriptor, PluginId};
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+use crate::persistence::query::types::plugin_kind::PersistablePluginKind;
+
+pub fn insert(descriptor: PluginDescriptor, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let PluginDescriptor { id, name, version, kind, checksum, manifest, signature, uploaded_by, uploaded_at } = descriptor;
+
+    let persistable = PersistablePluginDescriptor {
+        plugin_id: id.0,
+        name,
+        version,
+        kind: PersistablePluginKind::from(kind),
+        checksum,
+        manifest,
+        signature,
+        uploaded_by,
+        uploaded_at,
+    };
+
+    diesel::insert_into(schema::plugin_descriptor::table)
+        .values(&persistable)
+        .on_conflict(schema::plugin_descriptor::plugin_id)
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<PluginDescriptor>(persistable.plugin_id, cause))?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::plugin_descriptor)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistablePluginDescriptor {
+    pub plugin_id: Uuid,
+    pub name: String,
+    pub version: String,
+    pub kind: PersistablePluginKind,
+    pub checksum: Vec<u8>,
+    pub manifest: Option<String>,
+    pub signature: Vec<u8>,
+    pub uploaded_by: Option<String>,
+    pub uploaded_at: SystemTime,
+}
+
+pub fn remove(plugin_id: PluginId, connection: &mut PgConnection) -> PersistenceResult<Option<PluginDescriptor>> {
+    let result = list(Filter::By(plugin_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::plugin_descriptor::table
+            .filter(schema::plugin_descriptor::plugin_id.eq(plugin_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<PluginDescriptor>(plugin_id.0, cause))?;
+
+    Ok(result)
+}
+
+pub fn list(filter_by_plugin_id: Filter<PluginId>, connection: &mut PgConnection) -> PersistenceResult<Vec<PluginDescriptor>> {
+    let mut query = schema::plugin_descriptor::table.into_boxed();
+
+    if let Filter::By(plugin_id) = filter_by_plugin_id {
+        query = query.filter(schema::plugin_descriptor::plugin_id.eq(plugin_id.0));
+    }
+
+    let persistable_descriptors = query
+        .order(schema::plugin_descriptor::uploaded_at.asc())
+        .select(PersistablePluginDescriptor::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<PluginDescriptor>)?;
+
+    let descriptors = persistable_descriptors.into_iter()
+        .map(|persistable| {
+            let PersistablePluginDescriptor { plugin_id, name, version, kind, checksum, manifest, signature, uploaded_by, uploaded_at } = persistable;
+
+            PluginDescriptor {
+                id: PluginId::from(plugin_id),
+                name,
+                version,
+                kind: kind.into(),
+                checksum,
+                manifest,
+                signature,
+                uploaded_by,
+                uploaded_at,
+            }
+        })
+        .collect();
+
+    Ok(descriptors)
+}
@@ -0,0 +1,117 @@
+use std::time::SystemTime;
+
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use url::Url;
+use uuid::Uuid;
+
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::executor::log::ExecutorRunId;
+use opendut_types::peer::executor::test_run::TestRun;
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::PeerId;
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+use crate::persistence::query::types::test_run_status::PersistableTestRunStatus;
+
+pub fn insert(test_run: TestRun, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let TestRun { id, executor_id, peer_id, cluster_id, triggered_by, created_at, started_at, finished_at, status, exit_code, results_location } = test_run;
+
+    let persistable = PersistableTestRun {
+        run_id: id.0,
+        executor_id: executor_id.uuid,
+        peer_id: peer_id.uuid,
+        cluster_id: cluster_id.map(|cluster_id| cluster_id.0),
+        triggered_by,
+        created_at,
+        started_at,
+        finished_at,
+        status: PersistableTestRunStatus::from(status),
+        exit_code,
+        results_location: results_location.map(String::from),
+    };
+
+    diesel::insert_into(schema::test_run::table)
+        .values(&persistable)
+        .on_conflict(schema::test_run::run_id)
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<TestRun>(persistable.run_id, cause))?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::test_run)]
+#[diesel(treat_none_as_null = true)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistableTestRun {
+    pub run_id: Uuid,
+    pub executor_id: Uuid,
+    pub peer_id: Uuid,
+    pub cluster_id: Option<Uuid>,
+    pub triggered_by: Option<String>,
+    pub created_at: SystemTime,
+    pub started_at: Option<SystemTime>,
+    pub finished_at: Option<SystemTime>,
+    pub status: PersistableTestRunStatus,
+    pub exit_code: Option<i32>,
+    pub results_location: Option<String>,
+}
+
+pub fn remove(run_id: ExecutorRunId, connection: &mut PgConnection) -> PersistenceResult<Option<TestRun>> {
+    let result = list(Filter::By(run_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::test_run::table
+            .filter(schema::test_run::run_id.eq(run_id.0))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<TestRun>(run_id.0, cause))?;
+
+    Ok(result)
+}
+
+pub fn list(filter_by_run_id: Filter<ExecutorRunId>, connection: &mut PgConnection) -> PersistenceResult<Vec<TestRun>> {
+    let mut query = schema::test_run::table.into_boxed();
+
+    if let Filter::By(run_id) = filter_by_run_id {
+        query = query.filter(schema::test_run::run_id.eq(run_id.0));
+    }
+
+    let persistable_test_runs = query
+        .order(schema::test_run::created_at.asc())
+        .select(PersistableTestRun::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<TestRun>)?;
+
+    persistable_test_runs.into_iter().map(|persistable| {
+        let PersistableTestRun { run_id, executor_id, peer_id, cluster_id, triggered_by, created_at, started_at, finished_at, status, exit_code, results_location } = persistable;
+
+        let results_location = results_location
+            .map(|results_location| Url::parse(&results_location))
+            .transpose()
+            .map_err(|cause| PersistenceError::get::<TestRun>(run_id, cause))?;
+
+        Ok(TestRun {
+            id: ExecutorRunId::from(run_id),
+            executor_id: ExecutorId::from(executor_id),
+            peer_id: PeerId::from(peer_id),
+            cluster_id: cluster_id.map(ClusterId::from),
+            triggered_by,
+            created_at,
+            started_at,
+            finished_at,
+            status: status.into(),
+            exit_code,
+            results_location,
+        })
+    })
+    .collect::<PersistenceResult<Vec<_>>>()
+    .map_err(|cause|
+        PersistenceError::list::<TestRun>(cause)
+            .context("Failed to convert from database values to TestRun.")
+    )
+}
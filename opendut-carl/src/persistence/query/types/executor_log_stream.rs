@@ -0,0 +1,53 @@
+use std::io::Write;
+
+use diesel::deserialize::FromSql;
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+use diesel::{AsExpression, FromSqlRow};
+use opendut_types::peer::executor::log::ExecutorLogStream;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromSqlRow, AsExpression)]
+#[diesel(sql_type = Text)]
+pub enum PersistableExecutorLogStream {
+    Stdout,
+    Stderr,
+}
+impl ToSql<Text, Pg> for PersistableExecutorLogStream {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> diesel::serialize::Result {
+        match *self {
+            PersistableExecutorLogStream::Stdout => out.write_all(STDOUT)?,
+            PersistableExecutorLogStream::Stderr => out.write_all(STDERR)?,
+        }
+        Ok(IsNull::No)
+    }
+}
+impl FromSql<Text, Pg> for PersistableExecutorLogStream {
+    fn from_sql(bytes: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        match bytes.as_bytes() {
+            STDOUT => Ok(PersistableExecutorLogStream::Stdout),
+            STDERR => Ok(PersistableExecutorLogStream::Stderr),
+            _ => Err("Unrecognized enum variant".into()),
+        }
+    }
+}
+
+const STDOUT: &[u8] = b"stdout";
+const STDERR: &[u8] = b"stderr";
+
+impl From<ExecutorLogStream> for PersistableExecutorLogStream {
+    fn from(value: ExecutorLogStream) -> Self {
+        match value {
+            ExecutorLogStream::Stdout => PersistableExecutorLogStream::Stdout,
+            ExecutorLogStream::Stderr => PersistableExecutorLogStream::Stderr,
+        }
+    }
+}
+impl From<PersistableExecutorLogStream> for ExecutorLogStream {
+    fn from(value: PersistableExecutorLogStream) -> Self {
+        match value {
+            PersistableExecutorLogStream::Stdout => ExecutorLogStream::Stdout,
+            PersistableExecutorLogStream::Stderr => ExecutorLogStream::Stderr,
+        }
+    }
+}
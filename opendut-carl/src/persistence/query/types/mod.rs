@@ -1,5 +1,8 @@
 pub mod container_engine_kind;
 pub mod environment_variable;
 pub mod executor_kind;
+pub mod executor_log_stream;
 pub mod network_interface_kind;
 pub mod null_removing_text_array;
+pub mod plugin_kind;
+pub mod test_run_status;
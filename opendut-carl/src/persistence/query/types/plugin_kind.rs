@@ -0,0 +1,53 @@
+use std::io::Write;
+
+use diesel::deserialize::FromSql;
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+use diesel::{AsExpression, FromSqlRow};
+use opendut_types::plugin::PluginKind;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromSqlRow, AsExpression)]
+#[diesel(sql_type = Text)]
+pub enum PersistablePluginKind {
+    Setup,
+    Service,
+}
+impl ToSql<Text, Pg> for PersistablePluginKind {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> diesel::serialize::Result {
+        match *self {
+            PersistablePluginKind::Setup => out.write_all(SETUP)?,
+            PersistablePluginKind::Service => out.write_all(SERVICE)?,
+        }
+        Ok(IsNull::No)
+    }
+}
+impl FromSql<Text, Pg> for PersistablePluginKind {
+    fn from_sql(bytes: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        match bytes.as_bytes() {
+            SETUP => Ok(PersistablePluginKind::Setup),
+            SERVICE => Ok(PersistablePluginKind::Service),
+            _ => Err("Unrecognized enum variant".into()),
+        }
+    }
+}
+
+const SETUP: &[u8] = b"setup";
+const SERVICE: &[u8] = b"service";
+
+impl From<PluginKind> for PersistablePluginKind {
+    fn from(value: PluginKind) -> Self {
+        match value {
+            PluginKind::Setup => PersistablePluginKind::Setup,
+            PluginKind::Service => PersistablePluginKind::Service,
+        }
+    }
+}
+impl From<PersistablePluginKind> for PluginKind {
+    fn from(value: PersistablePluginKind) -> Self {
+        match value {
+            PersistablePluginKind::Setup => PluginKind::Setup,
+            PersistablePluginKind::Service => PluginKind::Service,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::io::Write;
+
+use diesel::deserialize::FromSql;
+use diesel::pg::{Pg, PgValue};
+use diesel::serialize::{IsNull, Output, ToSql};
+use diesel::sql_types::Text;
+use diesel::{AsExpression, FromSqlRow};
+use opendut_types::peer::executor::test_run::TestRunStatus;
+
+#[derive(Clone, Debug, PartialEq, Eq, FromSqlRow, AsExpression)]
+#[diesel(sql_type = Text)]
+pub enum PersistableTestRunStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+    Aborted,
+}
+impl ToSql<Text, Pg> for PersistableTestRunStatus {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> diesel::serialize::Result {
+        match *self {
+            PersistableTestRunStatus::Pending => out.write_all(PENDING)?,
+            PersistableTestRunStatus::Running => out.write_all(RUNNING)?,
+            PersistableTestRunStatus::Succeeded => out.write_all(SUCCEEDED)?,
+            PersistableTestRunStatus::Failed => out.write_all(FAILED)?,
+            PersistableTestRunStatus::Aborted => out.write_all(ABORTED)?,
+        }
+        Ok(IsNull::No)
+    }
+}
+impl FromSql<Text, Pg> for PersistableTestRunStatus {
+    fn from_sql(bytes: PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        match bytes.as_bytes() {
+            PENDING => Ok(PersistableTestRunStatus::Pending),
+            RUNNING => Ok(PersistableTestRunStatus::Running),
+            SUCCEEDED => Ok(PersistableTestRunStatus::Succeeded),
+            FAILED => Ok(PersistableTestRunStatus::Failed),
+            ABORTED => Ok(PersistableTestRunStatus::Aborted),
+            _ => Err("Unrecognized enum variant".into()),
+        }
+    }
+}
+
+const PENDING: &[u8] = b"pending";
+const RUNNING: &[u8] = b"running";
+const SUCCEEDED: &[u8] = b"succeeded";
+const FAILED: &[u8] = b"failed";
+const ABORTED: &[u8] = b"aborted";
+
+impl From<TestRunStatus> for PersistableTestRunStatus {
+    fn from(value: TestRunStatus) -> Self {
+        match value {
+            TestRunStatus::Pending => PersistableTestRunStatus::Pending,
+            TestRunStatus::Running => PersistableTestRunStatus::Running,
+            TestRunStatus::Succeeded => PersistableTestRunStatus::Succeeded,
+            TestRunStatus::Failed => PersistableTestRunStatus::Failed,
+            TestRunStatus::Aborted => PersistableTestRunStatus::Aborted,
+        }
+    }
+}
+impl From<PersistableTestRunStatus> for TestRunStatus {
+    fn from(value: PersistableTestRunStatus) -> Self {
+        match value {
+            PersistableTestRunStatus::Pending => TestRunStatus::Pending,
+            PersistableTestRunStatus::Running => TestRunStatus::Running,
+            PersistableTestRunStatus::Succeeded => TestRunStatus::Succeeded,
+            PersistableTestRunStatus::Failed => TestRunStatus::Failed,
+            PersistableTestRunStatus::Aborted => TestRunStatus::Aborted,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::net::Ipv4Addr;
+use std::str::FromStr;
+
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper};
+use uuid::Uuid;
+
+use opendut_types::peer::PeerId;
+use opendut_types::vpn::wireguard::WireGuardPeerAllocation;
+
+use crate::persistence::database::schema;
+use crate::persistence::error::{PersistenceError, PersistenceResult};
+use crate::persistence::query::Filter;
+
+pub fn insert(allocation: WireGuardPeerAllocation, connection: &mut PgConnection) -> PersistenceResult<()> {
+    let WireGuardPeerAllocation { peer_id, address, key_nonce } = allocation;
+
+    let persistable = PersistableWireGuardPeerAllocation {
+        peer_id: peer_id.uuid,
+        address: address.to_string(),
+        key_nonce,
+    };
+
+    diesel::insert_into(schema::wireguard_peer_allocation::table)
+        .values(&persistable)
+        .on_conflict(schema::wireguard_peer_allocation::peer_id)
+        .do_update()
+        .set(&persistable)
+        .execute(connection)
+        .map_err(|cause| PersistenceError::insert::<WireGuardPeerAllocation>(persistable.peer_id, cause))?;
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, diesel::Queryable, diesel::Selectable, diesel::Insertable, diesel::AsChangeset)]
+#[diesel(table_name = schema::wireguard_peer_allocation)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+struct PersistableWireGuardPeerAllocation {
+    pub peer_id: Uuid,
+    pub address: String,
+    pub key_nonce: Uuid,
+}
+
+pub fn remove(peer_id: PeerId, connection: &mut PgConnection) -> PersistenceResult<Option<WireGuardPeerAllocation>> {
+    let result = list(Filter::By(peer_id), connection)?
+        .first().cloned();
+
+    diesel::delete(
+        schema::wireguard_peer_allocation::table
+            .filter(schema::wireguard_peer_allocation::peer_id.eq(peer_id.uuid))
+    )
+    .execute(connection)
+    .map_err(|cause| PersistenceError::remove::<WireGuardPeerAllocation>(peer_id.uuid, cause))?;
+
+    Ok(result)
+}
+
+pub fn list(filter_by_peer_id: Filter<PeerId>, connection: &mut PgConnection) -> PersistenceResult<Vec<WireGuardPeerAllocation>> {
+    let mut query = schema::wireguard_peer_allocation::table.into_boxed();
+
+    if let Filter::By(peer_id) = filter_by_peer_id {
+        query = query.filter(schema::wireguard_peer_allocation::peer_id.eq(peer_id.uuid));
+    }
+
+    let persistable_allocations = query
+        .select(PersistableWireGuardPeerAllocation::as_select())
+        .get_results(connection)
+        .map_err(PersistenceError::list::<WireGuardPeerAllocation>)?;
+
+    persistable_allocations.into_iter()
+        .map(|persistable| {
+            let PersistableWireGuardPeerAllocation { peer_id, address, key_nonce } = persistable;
+
+            let address = Ipv4Addr::from_str(&address)
+                .map_err(|cause|
+                    PersistenceError::get::<WireGuardPeerAllocation>(peer_id, cause)
+                        .context("Failed to convert from database values to WireGuardPeerAllocation.")
+                )?;
+
+            Ok(WireGuardPeerAllocation {
+                peer_id: PeerId::from(peer_id),
+                address,
+                key_nonce,
+            })
+        })
+        .collect()
+}
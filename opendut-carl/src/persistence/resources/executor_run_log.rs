@@ -0,0 +1,44 @@
+use opendut_types::peer::executor::log::{ExecutorLogChunk, ExecutorLogChunkId, ExecutorRunId, ExecutorRunLogSummary};
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, Storage};
+
+impl Persistable for ExecutorRunLogSummary {
+    fn insert(self, _run_id: ExecutorRunId, storage: &mut Storage) -> PersistenceResult<()> {
+        query::executor_run_log::insert_summary(self, &mut storage.db.connection())
+    }
+
+    fn remove(run_id: ExecutorRunId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        query::executor_run_log::remove_summary(run_id, &mut storage.db.connection())
+    }
+
+    fn get(run_id: ExecutorRunId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = query::executor_run_log::list_summaries(Filter::By(run_id), &mut storage.db.connection())?
+            .first().cloned();
+        Ok(result)
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        query::executor_run_log::list_summaries(Filter::Not, &mut storage.db.connection())
+    }
+}
+
+impl Persistable for ExecutorLogChunk {
+    fn insert(self, _chunk_id: ExecutorLogChunkId, storage: &mut Storage) -> PersistenceResult<()> {
+        query::executor_run_log::insert_chunk(self, &mut storage.db.connection())
+    }
+
+    fn remove(chunk_id: ExecutorLogChunkId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        query::executor_run_log::remove_chunk(chunk_id, &mut storage.db.connection())
+    }
+
+    fn get(chunk_id: ExecutorLogChunkId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        query::executor_run_log::get_chunk(chunk_id, &mut storage.db.connection())
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        query::executor_run_log::list_chunks(&mut storage.db.connection())
+    }
+}
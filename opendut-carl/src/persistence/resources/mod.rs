@@ -5,10 +5,18 @@ use std::fmt::Debug;
 
 pub mod cluster_configuration;
 pub mod cluster_deployment;
+pub mod executor_run_log;
 pub mod old_peer_configuration;
 pub mod peer_configuration;
+pub mod peer_connection_session;
 pub mod peer_descriptor;
+pub mod peer_enrollment;
+pub mod peer_plugin_assignment;
 pub mod peer_state;
+pub mod plugin_artifact;
+pub mod plugin_descriptor;
+pub mod test_run;
+pub mod wireguard_peer_allocation;
 
 pub trait Persistable: Send + Sync + Sized + Debug + Resource {
     fn insert(self, id: Self::Id, storage: &mut Storage) -> PersistenceResult<()>;
@@ -0,0 +1,26 @@
+use opendut_types::peer::history::{PeerEnrollment, PeerEnrollmentId};
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, Storage};
+
+impl Persistable for PeerEnrollment {
+    fn insert(self, _enrollment_id: PeerEnrollmentId, storage: &mut Storage) -> PersistenceResult<()> {
+        query::peer_enrollment::insert(self, &mut storage.db.connection())
+    }
+
+    fn remove(enrollment_id: PeerEnrollmentId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        query::peer_enrollment::remove(enrollment_id, &mut storage.db.connection())
+    }
+
+    fn get(enrollment_id: PeerEnrollmentId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = query::peer_enrollment::list(Filter::By(enrollment_id), &mut storage.db.connection())?
+            .first().cloned();
+        Ok(result)
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        query::peer_enrollment::list(Filter::Not, &mut storage.db.connection())
+    }
+}
@@ -0,0 +1,27 @@
+use opendut_types::peer::PeerId;
+use opendut_types::plugin::PeerPluginAssignment;
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, Storage};
+
+impl Persistable for PeerPluginAssignment {
+    fn insert(self, _peer_id: PeerId, storage: &mut Storage) -> PersistenceResult<()> {
+        query::peer_plugin_assignment::insert(self, &mut storage.db.connection())
+    }
+
+    fn remove(peer_id: PeerId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        query::peer_plugin_assignment::remove(peer_id, &mut storage.db.connection())
+    }
+
+    fn get(peer_id: PeerId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = query::peer_plugin_assignment::list(Filter::By(peer_id), &mut storage.db.connection())?
+            .first().cloned();
+        Ok(result)
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        query::peer_plugin_assignment::list(Filter::Not, &mut storage.db.connection())
+    }
+}
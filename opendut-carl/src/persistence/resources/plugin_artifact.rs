@@ -0,0 +1,26 @@
+use opendut_types::plugin::{PluginArtifact, PluginId};
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, Storage};
+
+impl Persistable for PluginArtifact {
+    fn insert(self, _plugin_id: PluginId, storage: &mut Storage) -> PersistenceResult<()> {
+        query::plugin_artifact::insert(self, &mut storage.db.connection())
+    }
+
+    fn remove(plugin_id: PluginId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        query::plugin_artifact::remove(plugin_id, &mut storage.db.connection())
+    }
+
+    fn get(plugin_id: PluginId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = query::plugin_artifact::list(Filter::By(plugin_id), &mut storage.db.connection())?
+            .first().cloned();
+        Ok(result)
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        query::plugin_artifact::list(Filter::Not, &mut storage.db.connection())
+    }
+}
@@ -0,0 +1,26 @@
+use opendut_types::plugin::{PluginDescriptor, PluginId};
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, Storage};
+
+impl Persistable for PluginDescriptor {
+    fn insert(self, _plugin_id: PluginId, storage: &mut Storage) -> PersistenceResult<()> {
+        query::plugin_descriptor::insert(self, &mut storage.db.connection())
+    }
+
+    fn remove(plugin_id: PluginId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        query::plugin_descriptor::remove(plugin_id, &mut storage.db.connection())
+    }
+
+    fn get(plugin_id: PluginId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = query::plugin_descriptor::list(Filter::By(plugin_id), &mut storage.db.connection())?
+            .first().cloned();
+        Ok(result)
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        query::plugin_descriptor::list(Filter::Not, &mut storage.db.connection())
+    }
+}
@@ -0,0 +1,27 @@
+use opendut_types::peer::executor::log::ExecutorRunId;
+use opendut_types::peer::executor::test_run::TestRun;
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, Storage};
+
+impl Persistable for TestRun {
+    fn insert(self, _run_id: ExecutorRunId, storage: &mut Storage) -> PersistenceResult<()> {
+        query::test_run::insert(self, &mut storage.db.connection())
+    }
+
+    fn remove(run_id: ExecutorRunId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        query::test_run::remove(run_id, &mut storage.db.connection())
+    }
+
+    fn get(run_id: ExecutorRunId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = query::test_run::list(Filter::By(run_id), &mut storage.db.connection())?
+            .first().cloned();
+        Ok(result)
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        query::test_run::list(Filter::Not, &mut storage.db.connection())
+    }
+}
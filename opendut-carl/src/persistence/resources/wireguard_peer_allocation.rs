@@ -0,0 +1,27 @@
+use opendut_types::peer::PeerId;
+use opendut_types::vpn::wireguard::WireGuardPeerAllocation;
+
+use super::Persistable;
+use crate::persistence::error::PersistenceResult;
+use crate::persistence::query::Filter;
+use crate::persistence::{query, Storage};
+
+impl Persistable for WireGuardPeerAllocation {
+    fn insert(self, _peer_id: PeerId, storage: &mut Storage) -> PersistenceResult<()> {
+        query::wireguard_peer_allocation::insert(self, &mut storage.db.connection())
+    }
+
+    fn remove(peer_id: PeerId, storage: &mut Storage) -> PersistenceResult<Option<Self>> {
+        query::wireguard_peer_allocation::remove(peer_id, &mut storage.db.connection())
+    }
+
+    fn get(peer_id: PeerId, storage: &Storage) -> PersistenceResult<Option<Self>> {
+        let result = query::wireguard_peer_allocation::list(Filter::By(peer_id), &mut storage.db.connection())?
+            .first().cloned();
+        Ok(result)
+    }
+
+    fn list(storage: &Storage) -> PersistenceResult<Vec<Self>> {
+        query::wireguard_peer_allocation::list(Filter::Not, &mut storage.db.connection())
+    }
+}
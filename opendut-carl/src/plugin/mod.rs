@@ -0,0 +1,96 @@
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use config::Config;
+use ed25519_dalek::{Signer, SigningKey};
+use tracing::info;
+
+use opendut_types::plugin::{PluginDescriptor, PluginVerifyingKey};
+
+/// Signs the plugins uploaded to the plugin registry, so EDGAR can verify them before instantiation.
+/// Uploading plugins is not possible, if no signing key is configured.
+#[derive(Clone)]
+pub enum PluginSigning {
+    Enabled { signing_key: Arc<SigningKey> },
+    Disabled,
+}
+
+impl PluginSigning {
+    pub fn load(settings: &Config) -> anyhow::Result<Self> {
+        let key_secret_key = "plugins.signing.key.secret";
+        let key_secret = settings.get_string(key_secret_key)?;
+
+        if key_secret.trim().is_empty() {
+            info!("No plugin signing key configured in '{key_secret_key}'. Uploading plugins to the plugin registry is disabled.");
+            return Ok(PluginSigning::Disabled);
+        }
+
+        let secret = BASE64_STANDARD.decode(key_secret.trim())
+            .context(format!("Invalid configuration parameter for '{key_secret_key}'. Expected Base64."))?;
+        let secret = <[u8; ed25519_dalek::SECRET_KEY_LENGTH]>::try_from(secret)
+            .map_err(|secret| anyhow!("Invalid configuration parameter for '{key_secret_key}'. Expected {} bytes, but got {}.", ed25519_dalek::SECRET_KEY_LENGTH, secret.len()))?;
+
+        let signing_key = SigningKey::from_bytes(&secret);
+        let signing = PluginSigning::Enabled { signing_key: Arc::new(signing_key) };
+        if let Some(verifying_key) = signing.verifying_key() {
+            info!("Signing plugins with key, which is verified via public key: {verifying_key}");
+        }
+        Ok(signing)
+    }
+
+    pub fn verifying_key(&self) -> Option<PluginVerifyingKey> {
+        match self {
+            PluginSigning::Enabled { signing_key } => Some(PluginVerifyingKey(signing_key.verifying_key().to_bytes())),
+            PluginSigning::Disabled => None,
+        }
+    }
+
+    pub fn sign(&self, descriptor: &PluginDescriptor) -> Option<Vec<u8>> {
+        match self {
+            PluginSigning::Enabled { signing_key } => {
+                let signature = signing_key.sign(&descriptor.signed_content());
+                Some(signature.to_bytes().to_vec())
+            }
+            PluginSigning::Disabled => None,
+        }
+    }
+}
+
+impl fmt::Debug for PluginSigning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.verifying_key() {
+            Some(verifying_key) => write!(f, "PluginSigning::Enabled {{ verifying_key: {verifying_key} }}"),
+            None => write!(f, "PluginSigning::Disabled"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_be_disabled_without_a_signing_key() -> anyhow::Result<()> {
+        let settings = crate::settings::load_defaults()?;
+
+        let signing = PluginSigning::load(&settings.config)?;
+
+        assert_that!(signing.verifying_key(), none());
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_a_signing_key_of_the_wrong_length() -> anyhow::Result<()> {
+        let settings = Config::builder()
+            .set_override("plugins.signing.key.secret", BASE64_STANDARD.encode([1, 2, 3]))?
+            .build()?;
+
+        assert_that!(PluginSigning::load(&settings), err(anything()));
+        Ok(())
+    }
+}
@@ -1,8 +1,13 @@
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::executor::log::{ExecutorLogChunk, ExecutorLogChunkId, ExecutorRunId, ExecutorRunLogSummary};
+use opendut_types::peer::executor::test_run::TestRun;
+use opendut_types::peer::history::{PeerConnectionSessions, PeerEnrollment, PeerEnrollmentId};
 use opendut_types::peer::state::PeerState;
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::plugin::{PeerPluginAssignment, PluginArtifact, PluginDescriptor, PluginId};
 use opendut_types::resources::Id;
+use opendut_types::vpn::wireguard::WireGuardPeerAllocation;
 
 use crate::resources::resource::Resource;
 
@@ -31,6 +36,31 @@ impl IntoId<PeerState> for PeerId {
         Id::from(self.uuid)
     }
 }
+impl IntoId<PeerConnectionSessions> for PeerId {
+    fn into_id(self) -> Id {
+        Id::from(self.uuid)
+    }
+}
+impl IntoId<PeerEnrollment> for PeerEnrollmentId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+impl IntoId<ExecutorRunLogSummary> for ExecutorRunId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+impl IntoId<ExecutorLogChunk> for ExecutorLogChunkId {
+    fn into_id(self) -> Id {
+        Id::from(self.uuid())
+    }
+}
+impl IntoId<TestRun> for ExecutorRunId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
 impl IntoId<OldPeerConfiguration> for PeerId {
     fn into_id(self) -> Id {
         Id::from(self.uuid)
@@ -41,3 +71,23 @@ impl IntoId<PeerConfiguration> for PeerId {
         Id::from(self.uuid)
     }
 }
+impl IntoId<PluginDescriptor> for PluginId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+impl IntoId<PluginArtifact> for PluginId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+impl IntoId<PeerPluginAssignment> for PeerId {
+    fn into_id(self) -> Id {
+        Id::from(self.uuid)
+    }
+}
+impl IntoId<WireGuardPeerAllocation> for PeerId {
+    fn into_id(self) -> Id {
+        Id::from(self.uuid)
+    }
+}
@@ -103,7 +103,16 @@ impl ResourcesManager {
             old_peer_configuration,
             peer_configuration,
             peer_descriptor,
-            peer_state
+            peer_state,
+            peer_connection_sessions,
+            peer_enrollment,
+            executor_run_log_summary,
+            executor_log_chunk,
+            test_run,
+            plugin_descriptor,
+            plugin_artifact,
+            peer_plugin_assignment,
+            wireguard_peer_allocation,
         } = relayed_subscription_events;
 
         async fn notify_for_relayed_subscription_events_on_channel<R: Resource + Subscribable + Clone>(
@@ -124,6 +133,15 @@ impl ResourcesManager {
         notify_for_relayed_subscription_events_on_channel(peer_configuration, state).await;
         notify_for_relayed_subscription_events_on_channel(peer_descriptor, state).await;
         notify_for_relayed_subscription_events_on_channel(peer_state, state).await;
+        notify_for_relayed_subscription_events_on_channel(peer_connection_sessions, state).await;
+        notify_for_relayed_subscription_events_on_channel(peer_enrollment, state).await;
+        notify_for_relayed_subscription_events_on_channel(executor_run_log_summary, state).await;
+        notify_for_relayed_subscription_events_on_channel(executor_log_chunk, state).await;
+        notify_for_relayed_subscription_events_on_channel(test_run, state).await;
+        notify_for_relayed_subscription_events_on_channel(plugin_descriptor, state).await;
+        notify_for_relayed_subscription_events_on_channel(plugin_artifact, state).await;
+        notify_for_relayed_subscription_events_on_channel(peer_plugin_assignment, state).await;
+        notify_for_relayed_subscription_events_on_channel(wireguard_peer_allocation, state).await;
     }
 }
 
@@ -3,8 +3,13 @@ use std::fmt::Debug;
 
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::executor::log::{ExecutorLogChunk, ExecutorLogChunkId, ExecutorRunId, ExecutorRunLogSummary};
+use opendut_types::peer::executor::test_run::TestRun;
+use opendut_types::peer::history::{PeerConnectionSessions, PeerEnrollment, PeerEnrollmentId};
 use opendut_types::peer::state::PeerState;
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::plugin::{PeerPluginAssignment, PluginArtifact, PluginDescriptor, PluginId};
+use opendut_types::vpn::wireguard::WireGuardPeerAllocation;
 
 use crate::resources::ids::IntoId;
 
@@ -30,3 +35,30 @@ impl Resource for PeerDescriptor {
 impl Resource for PeerState {
     type Id = PeerId;
 }
+impl Resource for PeerConnectionSessions {
+    type Id = PeerId;
+}
+impl Resource for PeerEnrollment {
+    type Id = PeerEnrollmentId;
+}
+impl Resource for ExecutorRunLogSummary {
+    type Id = ExecutorRunId;
+}
+impl Resource for ExecutorLogChunk {
+    type Id = ExecutorLogChunkId;
+}
+impl Resource for TestRun {
+    type Id = ExecutorRunId;
+}
+impl Resource for PluginDescriptor {
+    type Id = PluginId;
+}
+impl Resource for PluginArtifact {
+    type Id = PluginId;
+}
+impl Resource for PeerPluginAssignment {
+    type Id = PeerId;
+}
+impl Resource for WireGuardPeerAllocation {
+    type Id = PeerId;
+}
@@ -1,4 +1,5 @@
 use opendut_types::peer::executor::container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine};
+use opendut_types::peer::executor::executable::{ExecutablePath, ExecutableTimeout, ExecutableWorkingDirectory};
 use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorDescriptors, ExecutorId, ExecutorKind, ResultsUrl};
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
 use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, DeviceTag, Topology};
@@ -143,7 +144,17 @@ pub fn peer_descriptor() -> anyhow::Result<PeerDescriptor> {
                 },
                 ExecutorDescriptor {
                     id: ExecutorId::random(),
-                    kind: ExecutorKind::Executable,
+                    kind: ExecutorKind::Executable {
+                        path: ExecutablePath::try_from("/opt/tests/run.sh")?,
+                        args: vec![
+                            ContainerCommandArgument::try_from("--verbose")?,
+                        ],
+                        envs: vec![
+                            ContainerEnvironmentVariable::new("env-name", "env-value")?,
+                        ],
+                        working_dir: Some(ExecutableWorkingDirectory::try_from("/opt/tests")?),
+                        timeout: Some(ExecutableTimeout::try_from(600)?),
+                    },
                     results_url: Some(ResultsUrl::try_from("https://example.com/")?),
                 },
             ]
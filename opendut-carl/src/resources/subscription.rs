@@ -1,8 +1,13 @@
 use crate::resources::resource::Resource;
 use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment};
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::executor::log::{ExecutorLogChunk, ExecutorRunLogSummary};
+use opendut_types::peer::executor::test_run::TestRun;
+use opendut_types::peer::history::{PeerConnectionSessions, PeerEnrollment};
 use opendut_types::peer::state::PeerState;
 use opendut_types::peer::PeerDescriptor;
+use opendut_types::plugin::{PeerPluginAssignment, PluginArtifact, PluginDescriptor};
+use opendut_types::vpn::wireguard::WireGuardPeerAllocation;
 use tokio::sync::broadcast;
 
 
@@ -47,6 +52,15 @@ impl_subscribable!(OldPeerConfiguration, old_peer_configuration);
 impl_subscribable!(PeerConfiguration, peer_configuration);
 impl_subscribable!(PeerDescriptor, peer_descriptor);
 impl_subscribable!(PeerState, peer_state);
+impl_subscribable!(PeerConnectionSessions, peer_connection_sessions);
+impl_subscribable!(PeerEnrollment, peer_enrollment);
+impl_subscribable!(ExecutorRunLogSummary, executor_run_log_summary);
+impl_subscribable!(ExecutorLogChunk, executor_log_chunk);
+impl_subscribable!(TestRun, test_run);
+impl_subscribable!(PluginDescriptor, plugin_descriptor);
+impl_subscribable!(PluginArtifact, plugin_artifact);
+impl_subscribable!(PeerPluginAssignment, peer_plugin_assignment);
+impl_subscribable!(WireGuardPeerAllocation, wireguard_peer_allocation);
 
 
 pub type ResourceSubscriptionChannel<R> = (broadcast::Sender<SubscriptionEvent<R>>, broadcast::Receiver<SubscriptionEvent<R>>); //store both the sender and initial receiver, to prevent channel from closing
@@ -58,6 +72,15 @@ pub struct ResourceSubscriptionChannels {
     pub peer_configuration: ResourceSubscriptionChannel<PeerConfiguration>,
     pub peer_descriptor: ResourceSubscriptionChannel<PeerDescriptor>,
     pub peer_state: ResourceSubscriptionChannel<PeerState>,
+    pub peer_connection_sessions: ResourceSubscriptionChannel<PeerConnectionSessions>,
+    pub peer_enrollment: ResourceSubscriptionChannel<PeerEnrollment>,
+    pub executor_run_log_summary: ResourceSubscriptionChannel<ExecutorRunLogSummary>,
+    pub executor_log_chunk: ResourceSubscriptionChannel<ExecutorLogChunk>,
+    pub test_run: ResourceSubscriptionChannel<TestRun>,
+    pub plugin_descriptor: ResourceSubscriptionChannel<PluginDescriptor>,
+    pub plugin_artifact: ResourceSubscriptionChannel<PluginArtifact>,
+    pub peer_plugin_assignment: ResourceSubscriptionChannel<PeerPluginAssignment>,
+    pub wireguard_peer_allocation: ResourceSubscriptionChannel<WireGuardPeerAllocation>,
 }
 impl ResourceSubscriptionChannels {
     pub fn subscribe<R: Resource + Subscribable>(&mut self) -> Subscription<R> {
@@ -82,6 +105,15 @@ impl Default for ResourceSubscriptionChannels {
         let peer_configuration = broadcast::channel(capacity);
         let peer_descriptor = broadcast::channel(capacity);
         let peer_state = broadcast::channel(capacity);
+        let peer_connection_sessions = broadcast::channel(capacity);
+        let peer_enrollment = broadcast::channel(capacity);
+        let executor_run_log_summary = broadcast::channel(capacity);
+        let executor_log_chunk = broadcast::channel(capacity);
+        let test_run = broadcast::channel(capacity);
+        let plugin_descriptor = broadcast::channel(capacity);
+        let plugin_artifact = broadcast::channel(capacity);
+        let peer_plugin_assignment = broadcast::channel(capacity);
+        let wireguard_peer_allocation = broadcast::channel(capacity);
 
         Self {
             cluster_configuration,
@@ -90,6 +122,15 @@ impl Default for ResourceSubscriptionChannels {
             peer_configuration,
             peer_descriptor,
             peer_state,
+            peer_connection_sessions,
+            peer_enrollment,
+            executor_run_log_summary,
+            executor_log_chunk,
+            test_run,
+            plugin_descriptor,
+            plugin_artifact,
+            peer_plugin_assignment,
+            wireguard_peer_allocation,
         }
     }
 }
@@ -3,8 +3,10 @@ use opendut_util::settings::{LoadedConfig, LoadError};
 pub fn load_with_overrides(overrides: config::Config) -> Result<LoadedConfig, LoadError> {
     let carl_config_hide_secrets_override = config::Config::builder()
         .set_override("vpn.netbird.auth.secret", "redacted")?
+        .set_override("vpn.wireguard.key.secret", "redacted")?
         .set_override("network.oidc.client.secret", "redacted")?
         .set_override("persistence.database.password", "redacted")?
+        .set_override("plugins.signing.key.secret", "redacted")?
         .build()?;
 
     opendut_util::settings::load_config("carl", include_str!("../carl.toml"), config::FileFormat::Toml, overrides, carl_config_hide_secrets_override)
@@ -0,0 +1,87 @@
+use tracing::debug;
+
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_types::util::net::IpNetwork;
+use opendut_types::vpn::VpnPeerConfiguration;
+use opendut_vpn::{ClusterTraffic, CreateClusterError, CreatePeerError, CreateVpnPeerConfigurationError, DeleteClusterError, DeletePeerError, VpnManagementClient};
+
+/// Used when the peers are connected via a shared network, e.g. in an air-gapped lab.
+/// There is no VPN software to manage, so CARL only tells EDGAR in which network to look for its address.
+/// The address reported by EDGAR is then used for the tunnels between the peers of a cluster.
+pub struct DirectNetworkClient {
+    pub network: Option<IpNetwork>,
+}
+
+#[tonic::async_trait]
+impl VpnManagementClient for DirectNetworkClient {
+
+    async fn create_cluster(&self, cluster_id: ClusterId, _peers: &[PeerId], _traffic: &ClusterTraffic) -> Result<(), CreateClusterError> {
+        debug!("Peers are connected via a direct network. Nothing to create for cluster <{cluster_id}>.");
+        Ok(())
+    }
+
+    async fn delete_cluster(&self, cluster_id: ClusterId) -> Result<(), DeleteClusterError> {
+        debug!("Peers are connected via a direct network. Nothing to delete for cluster <{cluster_id}>.");
+        Ok(())
+    }
+
+    async fn create_peer(&self, peer_id: PeerId) -> Result<(), CreatePeerError> {
+        debug!("Peers are connected via a direct network. Nothing to create for peer <{peer_id}>.");
+        Ok(())
+    }
+
+    async fn delete_peer(&self, peer_id: PeerId) -> Result<(), DeletePeerError> {
+        debug!("Peers are connected via a direct network. Nothing to delete for peer <{peer_id}>.");
+        Ok(())
+    }
+
+    async fn generate_vpn_peer_configuration(&self, _peer_id: PeerId) -> Result<VpnPeerConfiguration, CreateVpnPeerConfigurationError> {
+        Ok(VpnPeerConfiguration::Direct {
+            network: self.network,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_provide_the_configured_network_to_the_peers() -> anyhow::Result<()> {
+        let network = IpNetwork::from_str("192.168.56.0/24")?;
+        let testee = DirectNetworkClient { network: Some(network) };
+
+        let configuration = testee.generate_vpn_peer_configuration(PeerId::random()).await.unwrap();
+
+        assert_that!(configuration, eq(&VpnPeerConfiguration::Direct { network: Some(network) }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_provide_no_network_to_the_peers_if_none_is_configured() -> anyhow::Result<()> {
+        let testee = DirectNetworkClient { network: None };
+
+        let configuration = testee.generate_vpn_peer_configuration(PeerId::random()).await.unwrap();
+
+        assert_that!(configuration, eq(&VpnPeerConfiguration::Direct { network: None }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_succeed_without_managing_any_resources() -> anyhow::Result<()> {
+        let testee = DirectNetworkClient { network: None };
+        let cluster_id = ClusterId::random();
+        let peers = [PeerId::random(), PeerId::random()];
+
+        testee.create_peer(peers[0]).await.unwrap();
+        testee.create_cluster(cluster_id, &peers, &ClusterTraffic::default()).await.unwrap();
+        testee.delete_cluster(cluster_id).await.unwrap();
+        testee.delete_peer(peers[0]).await.unwrap();
+        Ok(())
+    }
+}
@@ -1,4 +1,6 @@
+use std::net::Ipv4Addr;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -7,8 +9,16 @@ use config::Config;
 use tracing::debug;
 use url::Url;
 
+use opendut_types::util::net::IpNetwork;
 use opendut_vpn::VpnManagementClient;
-use opendut_vpn_netbird::{NetbirdManagementClient, NetbirdManagementClientConfiguration, NetbirdToken};
+use opendut_vpn_netbird::{NetbirdManagementClient, NetbirdManagementClientConfiguration, NetbirdPolicyMode, NetbirdToken};
+use opendut_vpn_wireguard::{WireGuardManagementClient, WireGuardManagementClientConfiguration};
+
+use crate::resources::manager::ResourcesManagerRef;
+
+mod direct;
+pub mod reconciler;
+mod wireguard;
 
 #[derive(Clone)]
 pub enum Vpn {
@@ -16,7 +26,7 @@ pub enum Vpn {
     Disabled,
 }
 
-pub fn create(settings: &Config) -> anyhow::Result<Vpn> {
+pub fn create(settings: &Config, resources_manager: ResourcesManagerRef) -> anyhow::Result<Vpn> {
 
     let vpn = settings.get::<bool>("vpn.enabled")?;
 
@@ -55,6 +65,14 @@ pub fn create(settings: &Config) -> anyhow::Result<Vpn> {
 
                 let setup_key_expiration_ms = settings.get::<Option<u64>>("vpn.netbird.setup.key.expiration.ms")?
                     .ok_or_else(|| anyhow!("No configuration found for: vpn.netbird.setup.key.expiration.ms"))?;
+
+                let policy_mode = if settings.get::<bool>("vpn.netbird.policy.restricted")? {
+                    let throughput_port = settings.get::<u16>("vpn.netbird.policy.metrics.throughput.port")?;
+                    let allow_all_protocols_for_ethernet = settings.get::<bool>("vpn.netbird.policy.ethernet.allow.all.protocols")?;
+                    NetbirdPolicyMode::Restricted { throughput_port, allow_all_protocols_for_ethernet }
+                } else {
+                    NetbirdPolicyMode::AllowAll
+                };
                 
                 debug!("Try to parse VPN configuration.");
                 let vpn_client = NetbirdManagementClient::create(
@@ -65,12 +83,48 @@ pub fn create(settings: &Config) -> anyhow::Result<Vpn> {
                         timeout: Duration::from_millis(timeout_ms),
                         retries,
                         setup_key_expiration: Duration::from_millis(setup_key_expiration_ms),
+                        policy_mode,
                     }
                 )?;
                 Ok(Vpn::Enabled { vpn_client: Arc::new(vpn_client) })
             }
+            "wireguard" => {
+                let key_secret = settings.get::<String>("vpn.wireguard.key.secret")?;
+                if key_secret.trim().is_empty() {
+                    bail!("No configuration found for: vpn.wireguard.key.secret");
+                }
+
+                let network_address = settings.get::<Ipv4Addr>("vpn.wireguard.network.address")?;
+                let network_prefix_length = settings.get::<u8>("vpn.wireguard.network.prefix.length")?;
+                let listen_port = settings.get::<u16>("vpn.wireguard.listen.port")?;
+
+                let vpn_client = WireGuardManagementClient::create(
+                    WireGuardManagementClientConfiguration {
+                        key_secret,
+                        network_address,
+                        network_prefix_length,
+                        listen_port,
+                    },
+                    Arc::new(wireguard::ResourcesWireGuardPeerStore { resources_manager }),
+                )?;
+                Ok(Vpn::Enabled { vpn_client: Arc::new(vpn_client) })
+            }
+            "direct" => {
+                let vpn_direct_network_key = "vpn.direct.network";
+                let network = settings.get::<String>(vpn_direct_network_key)?;
+                let network = if network.trim().is_empty() {
+                    None
+                } else {
+                    let network = IpNetwork::from_str(&network)
+                        .map_err(|cause| anyhow!("Invalid configuration parameter for '{vpn_direct_network_key}': {cause}"))?;
+                    Some(network)
+                };
+
+                let vpn_client = direct::DirectNetworkClient { network };
+                Ok(Vpn::Enabled { vpn_client: Arc::new(vpn_client) })
+            }
             "" => unknown_enum_variant(settings, vpn_kind_key),
-            other => Err(anyhow!("Invalid configuration parameter '{other}' for key '{vpn_kind_key}', allowed value is 'netbird'.")),
+            other => Err(anyhow!("Invalid configuration parameter '{other}' for key '{vpn_kind_key}', allowed values are 'netbird', 'wireguard' and 'direct'.")),
         }
     } else {
         Ok(Vpn::Disabled)
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use config::Config;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, warn};
+
+use opendut_carl_api::carl::cluster::ReconcileVpnError;
+use opendut_types::vpn::reconciliation::VpnDriftResolution;
+
+use crate::actions;
+use crate::actions::ReconcileVpnParams;
+use crate::cluster::manager::ClusterManagerRef;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::vpn::Vpn;
+
+pub struct VpnReconcilerOptions {
+    /// Interval between reconciliations. Periodic reconciliation is disabled, if not set.
+    pub interval: Option<Duration>,
+    /// Whether orphaned resources are removed from the VPN service, instead of only being reported.
+    pub cleanup: bool,
+}
+
+impl VpnReconcilerOptions {
+    pub fn load(config: &Config) -> anyhow::Result<Self> {
+        let interval_ms = config.get::<u64>("vpn.reconciliation.interval.ms")?;
+        let cleanup = config.get::<bool>("vpn.reconciliation.cleanup")?;
+
+        Ok(Self {
+            interval: (interval_ms > 0).then(|| Duration::from_millis(interval_ms)),
+            cleanup,
+        })
+    }
+}
+
+/// Periodically compares the resources of the VPN service with CARL, to detect resources which were left behind,
+/// e.g. because CARL crashed while deploying a cluster.
+pub fn spawn(resources_manager: ResourcesManagerRef, cluster_manager: ClusterManagerRef, vpn: Vpn, options: VpnReconcilerOptions) {
+    let Some(interval) = options.interval else {
+        debug!("Periodic reconciliation of the VPN is disabled.");
+        return;
+    };
+    if let Vpn::Disabled = vpn {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        ticker.tick().await; //the first tick completes immediately, but the VPN is only reconciled after one interval, when CARL has settled after starting
+
+        loop {
+            ticker.tick().await;
+
+            let result = {
+                let _cluster_manager = cluster_manager.lock().await; //prevent clusters from being deployed in the VPN meanwhile
+
+                actions::reconcile_vpn(ReconcileVpnParams {
+                    resources_manager: Arc::clone(&resources_manager),
+                    vpn: Clone::clone(&vpn),
+                    dry_run: !options.cleanup,
+                }).await
+            };
+
+            match result {
+                Ok(report) => {
+                    for entry in report.drifts {
+                        match entry.resolution {
+                            VpnDriftResolution::Reported => warn!("Detected drift between VPN service and CARL: {}", entry.drift),
+                            VpnDriftResolution::Removed => info!("Removed orphaned resource from VPN service: {}", entry.drift),
+                            VpnDriftResolution::RemovalFailed { cause } => warn!("Failed to remove orphaned resource from VPN service: {}\n  {cause}", entry.drift),
+                        }
+                    }
+                }
+                Err(ReconcileVpnError::NotSupported) => {
+                    debug!("The configured VPN keeps no state of its own. Stopping periodic reconciliation of the VPN.");
+                    break;
+                }
+                Err(_) => {} //already logged by the action, retried in the next interval
+            }
+        }
+    });
+}
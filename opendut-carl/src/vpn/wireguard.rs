@@ -0,0 +1,29 @@
+use opendut_types::peer::PeerId;
+use opendut_types::vpn::wireguard::WireGuardPeerAllocation;
+use opendut_vpn_wireguard::{StoreError, WireGuardPeerStore};
+
+use crate::resources::manager::ResourcesManagerRef;
+
+/// Keeps the VPN addresses allocated by the WireGuard client as resources of CARL,
+/// so they survive a restart and are not handed out twice.
+pub struct ResourcesWireGuardPeerStore {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+#[tonic::async_trait]
+impl WireGuardPeerStore for ResourcesWireGuardPeerStore {
+    async fn list(&self) -> Result<Vec<WireGuardPeerAllocation>, StoreError> {
+        let allocations = self.resources_manager.list::<WireGuardPeerAllocation>().await?;
+        Ok(allocations)
+    }
+
+    async fn insert(&self, allocation: WireGuardPeerAllocation) -> Result<(), StoreError> {
+        self.resources_manager.insert(allocation.peer_id, allocation).await?;
+        Ok(())
+    }
+
+    async fn remove(&self, peer_id: PeerId) -> Result<(), StoreError> {
+        self.resources_manager.remove::<WireGuardPeerAllocation>(peer_id).await?;
+        Ok(())
+    }
+}
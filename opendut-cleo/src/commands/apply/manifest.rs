@@ -0,0 +1,398 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
+use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
+use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorDescriptors, ExecutorId, ExecutorKind, ResultsUrl};
+use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, DeviceTag, Topology};
+use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
+
+use crate::commands::network_interface::CanParameters;
+
+/// Declarative description of a set of peers and cluster configurations.
+///
+/// The fields only contain primitive values, which get validated when converting them into the respective opendut-types.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Manifest {
+    #[serde(default)]
+    pub peers: Vec<PeerManifest>,
+    #[serde(default)]
+    pub clusters: Vec<ClusterManifest>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct PeerManifest {
+    pub id: PeerId,
+    pub name: String,
+    pub location: Option<String>,
+    pub bridge_name: Option<String>,
+    #[serde(default)]
+    pub network_interfaces: Vec<NetworkInterfaceManifest>,
+    #[serde(default)]
+    pub devices: Vec<DeviceManifest>,
+    #[serde(default)]
+    pub executors: Vec<ExecutorManifest>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NetworkInterfaceManifest {
+    pub id: Option<NetworkInterfaceId>,
+    pub name: String,
+    #[serde(flatten)]
+    pub configuration: NetworkInterfaceConfigurationManifest,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum NetworkInterfaceConfigurationManifest {
+    Ethernet,
+    Can(CanParameters),
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct DeviceManifest {
+    pub id: Option<DeviceId>,
+    pub name: String,
+    pub description: Option<String>,
+    ///Name of the network interface of the peer, which the device is connected to
+    pub interface: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExecutorManifest {
+    pub id: Option<ExecutorId>,
+    #[serde(flatten)]
+    pub kind: ExecutorKind,
+    pub results_url: Option<ResultsUrl>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ClusterManifest {
+    pub id: ClusterId,
+    pub name: String,
+    pub leader: PeerId,
+    ///Names of the devices, which are part of the cluster
+    pub devices: Vec<String>,
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> crate::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|cause| format!("Failed to open file '{}': {}", path.display(), cause))?;
+        Self::parse(&content)
+            .map_err(|cause| format!("Failed to parse '{}' as manifest: {}", path.display(), cause))
+    }
+
+    /// Parses a YAML-formatted manifest. Since YAML is a superset of JSON, JSON-formatted manifests are accepted, too.
+    pub fn parse(content: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(content)
+    }
+
+    /// Converts the declared peers into descriptors.
+    ///
+    /// IDs of network interfaces, devices and executors may be omitted in the manifest.
+    /// In that case, the ID of the matching element of the already existing peer is kept, so that applying the same manifest again does not cause any changes.
+    pub fn peer_descriptors(&self, current_peers: &[PeerDescriptor]) -> crate::Result<Vec<PeerDescriptor>> {
+        let mut peer_ids = HashSet::new();
+        let mut descriptors = Vec::with_capacity(self.peers.len());
+
+        for peer in &self.peers {
+            if !peer_ids.insert(peer.id) {
+                Err(format!("Peer <{}> is declared multiple times in the manifest.", peer.id))?
+            }
+            let current = current_peers.iter().find(|current| current.id == peer.id);
+            let descriptor = peer.to_descriptor(current)
+                .map_err(|error| format!("Invalid declaration of peer <{}>:\n  {}", peer.id, error))?;
+            descriptors.push(descriptor);
+        }
+        Ok(descriptors)
+    }
+
+    /// Converts the declared clusters into configurations.
+    ///
+    /// Devices are referenced by name and looked up in `available_devices`, which should contain the devices of the declared peers as well as those of all other peers known to CARL.
+    pub fn cluster_configurations(&self, available_devices: &[DeviceDescriptor]) -> crate::Result<Vec<ClusterConfiguration>> {
+        let mut cluster_ids = HashSet::new();
+        let mut configurations = Vec::with_capacity(self.clusters.len());
+
+        for cluster in &self.clusters {
+            if !cluster_ids.insert(cluster.id) {
+                Err(format!("Cluster <{}> is declared multiple times in the manifest.", cluster.id))?
+            }
+            let configuration = cluster.to_configuration(available_devices)
+                .map_err(|error| format!("Invalid declaration of cluster <{}>:\n  {}", cluster.id, error))?;
+            configurations.push(configuration);
+        }
+        Ok(configurations)
+    }
+}
+
+impl PeerManifest {
+    fn to_descriptor(&self, current: Option<&PeerDescriptor>) -> crate::Result<PeerDescriptor> {
+        let name = PeerName::try_from(self.name.clone())
+            .map_err(|error| error.to_string())?;
+
+        let location = self.location.clone()
+            .map(PeerLocation::try_from)
+            .transpose()
+            .map_err(|error| error.to_string())?;
+
+        let bridge_name = self.bridge_name.clone()
+            .map(NetworkInterfaceName::try_from)
+            .transpose()
+            .map_err(|error| error.to_string())?;
+
+        let mut interfaces: Vec<NetworkInterfaceDescriptor> = Vec::with_capacity(self.network_interfaces.len());
+        for interface in &self.network_interfaces {
+            let name = NetworkInterfaceName::try_from(interface.name.clone())
+                .map_err(|error| error.to_string())?;
+            if interfaces.iter().any(|existing| existing.name == name) {
+                Err(format!("Network interface '{}' is declared multiple times.", name))?
+            }
+            let id = interface.id
+                .or_else(|| current
+                    .and_then(|current| current.network.interfaces.iter().find(|existing| existing.name == name))
+                    .map(|existing| existing.id)
+                )
+                .unwrap_or_else(NetworkInterfaceId::random);
+
+            interfaces.push(NetworkInterfaceDescriptor {
+                id,
+                name,
+                configuration: interface.configuration.to_configuration()?,
+            });
+        }
+
+        let mut devices: Vec<DeviceDescriptor> = Vec::with_capacity(self.devices.len());
+        for device in &self.devices {
+            let name = DeviceName::try_from(device.name.clone())
+                .map_err(|error| error.to_string())?;
+            if devices.iter().any(|existing| existing.name == name) {
+                Err(format!("Device '{}' is declared multiple times.", name))?
+            }
+            let interface = interfaces.iter()
+                .find(|interface| interface.name.name() == device.interface)
+                .ok_or_else(|| format!("Device '{}' references network interface '{}', which is not declared for this peer.", name, device.interface))?;
+            let id = device.id
+                .or_else(|| current
+                    .and_then(|current| current.topology.devices.iter().find(|existing| existing.name == name))
+                    .map(|existing| existing.id)
+                )
+                .unwrap_or_else(DeviceId::random);
+
+            devices.push(DeviceDescriptor {
+                id,
+                name,
+                description: device.description.clone()
+                    .map(DeviceDescription::try_from)
+                    .transpose()
+                    .map_err(|error| error.to_string())?,
+                interface: interface.id,
+                tags: device.tags.clone()
+                    .into_iter()
+                    .map(DeviceTag::try_from)
+                    .collect::<Result<_, _>>()
+                    .map_err(|error| error.to_string())?,
+            });
+        }
+
+        let executors = self.executors.iter()
+            .map(|executor| {
+                let id = executor.id
+                    .or_else(|| current
+                        .and_then(|current| current.executors.executors.iter()
+                            .find(|existing| existing.kind == executor.kind && existing.results_url == executor.results_url)
+                        )
+                        .map(|existing| existing.id)
+                    )
+                    .unwrap_or_else(ExecutorId::random);
+
+                ExecutorDescriptor {
+                    id,
+                    kind: executor.kind.clone(),
+                    results_url: executor.results_url.clone(),
+                }
+            })
+            .collect();
+
+        Ok(PeerDescriptor {
+            id: self.id,
+            name,
+            location,
+            network: PeerNetworkDescriptor {
+                interfaces,
+                bridge_name,
+            },
+            topology: Topology {
+                devices,
+            },
+            executors: ExecutorDescriptors {
+                executors,
+            },
+        })
+    }
+}
+
+impl NetworkInterfaceConfigurationManifest {
+    fn to_configuration(&self) -> crate::Result<NetworkInterfaceConfiguration> {
+        match self {
+            NetworkInterfaceConfigurationManifest::Ethernet => Ok(NetworkInterfaceConfiguration::Ethernet),
+            NetworkInterfaceConfigurationManifest::Can(parameters) => parameters.to_configuration(&NetworkInterfaceConfiguration::Ethernet),
+        }
+    }
+}
+
+impl ClusterManifest {
+    fn to_configuration(&self, available_devices: &[DeviceDescriptor]) -> crate::Result<ClusterConfiguration> {
+        let name = ClusterName::try_from(self.name.clone())
+            .map_err(|error| error.to_string())?;
+
+        let devices = self.devices.iter()
+            .map(|device_name| {
+                let matching_devices = available_devices.iter()
+                    .filter(|device| device.name.value() == device_name)
+                    .collect::<Vec<_>>();
+                match matching_devices.as_slice() {
+                    [] => Err(format!("Device '{}' not found", device_name)),
+                    [device] => Ok(device.id),
+                    _ => Err(format!("Multiple devices found for the name '{}'", device_name)),
+                }
+            })
+            .collect::<crate::Result<HashSet<_>>>()?;
+
+        if devices.len() < 2 {
+            Err("Specify at least 2 devices per cluster configuration.".to_string())?
+        }
+
+        Ok(ClusterConfiguration {
+            id: self.id,
+            name,
+            leader: self.leader,
+            devices,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use googletest::prelude::*;
+    use indoc::indoc;
+
+    use opendut_types::peer::executor::container::{ContainerImage, Engine};
+    use opendut_types::util::net::CanSamplePoint;
+
+    use super::*;
+
+    const MANIFEST: &str = indoc!(r#"
+        peers:
+          - id: 8b5c3e0a-1d2f-4a8e-9c41-7f7e6f2f2b11
+            name: bench-1
+            location: Lab
+            network-interfaces:
+              - name: eth0
+                type: ethernet
+              - name: vcan0
+                type: can
+                bitrate: 250000
+                fd: false
+            devices:
+              - name: ecu-1
+                interface: eth0
+                tags: [ powertrain ]
+              - name: ecu-2
+                interface: vcan0
+            executors:
+              - container:
+                  engine: podman
+                  image: testenv:latest
+                  volumes: []
+        clusters:
+          - id: 0b1f1dd8-54a7-4c3e-8a4e-0b8a9a53ab2c
+            name: bench-cluster
+            leader: 8b5c3e0a-1d2f-4a8e-9c41-7f7e6f2f2b11
+            devices: [ ecu-1, ecu-2 ]
+    "#);
+
+    #[test]
+    fn should_convert_manifest_into_peer_descriptors_and_cluster_configurations() -> anyhow::Result<()> {
+        let manifest = Manifest::parse(MANIFEST)?;
+
+        let peers = manifest.peer_descriptors(&[]).map_err(anyhow::Error::msg)?;
+        assert_that!(peers.len(), eq(1));
+        let peer = &peers[0];
+        assert_that!(peer.name, eq(&PeerName::try_from("bench-1")?));
+        assert_that!(peer.network.interfaces.len(), eq(2));
+        assert_that!(peer.network.interfaces[1].configuration, eq(&NetworkInterfaceConfiguration::Can {
+            bitrate: 250_000,
+            sample_point: CanSamplePoint::try_from(0.7)?,
+            fd: false,
+            data_bitrate: 250_000,
+            data_sample_point: CanSamplePoint::try_from(0.7)?,
+        }));
+        assert_that!(peer.topology.devices[1].interface, eq(peer.network.interfaces[1].id));
+        assert_that!(peer.executors.executors[0].kind, eq(&ExecutorKind::Container {
+            engine: Engine::Podman,
+            name: Default::default(),
+            image: ContainerImage::try_from("testenv:latest")?,
+            volumes: vec![],
+            devices: vec![],
+            envs: vec![],
+            ports: vec![],
+            command: Default::default(),
+            args: vec![],
+        }));
+
+        let clusters = manifest.cluster_configurations(&peer.topology.devices).map_err(anyhow::Error::msg)?;
+        assert_that!(clusters.len(), eq(1));
+        assert_that!(clusters[0].leader, eq(peer.id));
+        assert_that!(clusters[0].devices, eq(&peer.topology.devices.iter().map(|device| device.id).collect::<HashSet<_>>()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_keep_ids_of_existing_elements() -> anyhow::Result<()> {
+        let manifest = Manifest::parse(MANIFEST)?;
+
+        let current = manifest.peer_descriptors(&[]).map_err(anyhow::Error::msg)?;
+        let desired = manifest.peer_descriptors(&current).map_err(anyhow::Error::msg)?;
+
+        assert_that!(desired, eq(&current));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_devices_with_undeclared_interface() -> anyhow::Result<()> {
+        let manifest = Manifest::parse(indoc!(r#"
+            peers:
+              - id: 8b5c3e0a-1d2f-4a8e-9c41-7f7e6f2f2b11
+                name: bench-1
+                devices:
+                  - name: ecu-1
+                    interface: eth0
+        "#))?;
+
+        assert_that!(manifest.peer_descriptors(&[]), err(anything()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_unknown_fields() {
+        let result = Manifest::parse(indoc!(r#"
+            peers: []
+            deployments: []
+        "#));
+
+        assert_that!(result, err(anything()));
+    }
+}
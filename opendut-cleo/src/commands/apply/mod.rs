@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::topology::DeviceDescriptor;
+
+use crate::CreateOutputFormat;
+use crate::commands::apply::manifest::Manifest;
+use crate::commands::apply::plan::{Change, Plan};
+
+pub mod manifest;
+pub mod plan;
+
+#[derive(clap::Args)]
+pub struct ApplyManifestCli {
+    ///Path to the manifest file
+    #[arg(short, long)]
+    file: Option<PathBuf>,
+    ///Only show the changes, which would be necessary to reach the declared state, without applying them
+    #[arg(long)]
+    dry_run: bool,
+    ///Delete peers and cluster configurations, which are not declared in the manifest
+    #[arg(long)]
+    prune: bool,
+}
+
+impl ApplyManifestCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let file = self.file
+            .ok_or(String::from("Specify a manifest file via '--file' or use one of the subcommands."))?;
+
+        let manifest = Manifest::load(&file)?;
+
+        let current_peers = carl.peers.list_peer_descriptors().await
+            .map_err(|error| format!("Could not list peers.\n  {}", error))?;
+        let current_clusters = carl.cluster.list_cluster_configurations().await
+            .map_err(|error| format!("Could not list cluster configurations.\n  {}", error))?;
+
+        let desired_peers = manifest.peer_descriptors(&current_peers)?;
+
+        let available_devices = desired_peers.iter()
+            .chain(current_peers.iter().filter(|current| desired_peers.iter().all(|desired| desired.id != current.id)))
+            .flat_map(|peer| peer.topology.devices.iter().cloned())
+            .collect::<Vec<DeviceDescriptor>>();
+        let desired_clusters = manifest.cluster_configurations(&available_devices)?;
+
+        let plan = Plan::compute(&current_peers, desired_peers, &current_clusters, desired_clusters, self.prune);
+
+        match output {
+            CreateOutputFormat::Text => println!("{}", plan.render_text()),
+            CreateOutputFormat::Json => println!("{}", serde_json::to_string(&plan).unwrap()),
+            CreateOutputFormat::PrettyJson => println!("{}", serde_json::to_string_pretty(&plan).unwrap()),
+        }
+
+        if self.dry_run || !plan.has_changes() {
+            return Ok(());
+        }
+
+        apply_plan(plan, carl).await?;
+
+        if let CreateOutputFormat::Text = output {
+            println!("Successfully applied manifest '{}'.", file.display());
+        }
+        Ok(())
+    }
+}
+
+/// Applies the changes in an order, which satisfies the dependencies between the resources:
+/// Cluster configurations are removed before the peers they reference and are stored after the peers they reference.
+async fn apply_plan(plan: Plan, carl: &mut CarlClient) -> crate::Result<()> {
+    let cluster_deployments = carl.cluster.list_cluster_deployments().await
+        .map_err(|_| String::from("Failed to get list of cluster deployments!"))?;
+
+    for change in &plan.clusters {
+        let cluster_id = match change {
+            Change::Update { current, .. } | Change::Delete { current } => current.id,
+            Change::Create { .. } | Change::Unchanged { .. } => continue,
+        };
+        if cluster_deployments.iter().any(|deployment| deployment.id == cluster_id) {
+            Err(format!("Cluster <{}> can not be changed while it is deployed.", cluster_id))?
+        }
+    }
+
+    for change in &plan.clusters {
+        if let Change::Delete { current } = change {
+            carl.cluster.delete_cluster_configuration(current.id).await
+                .map_err(|error| format!("Failed to delete cluster configuration <{}>.\n  {}", current.id, error))?;
+        }
+    }
+
+    for change in plan.peers.iter() {
+        if let Change::Create { desired } | Change::Update { desired, .. } = change {
+            carl.peers.store_peer_descriptor(Clone::clone(desired)).await
+                .map_err(|error| format!("Failed to store peer <{}>.\n  {}", desired.id, error))?;
+        }
+    }
+
+    for change in plan.clusters.iter() {
+        if let Change::Create { desired } | Change::Update { desired, .. } = change {
+            carl.cluster.store_cluster_configuration(Clone::clone(desired)).await
+                .map_err(|error| format!("Failed to store cluster configuration <{}>.\n  {}", desired.id, error))?;
+        }
+    }
+
+    for change in plan.peers.iter() {
+        if let Change::Delete { current } = change {
+            carl.peers.delete_peer_descriptor(current.id).await
+                .map_err(|error| format!("Failed to delete peer <{}>.\n  {}", current.id, error))?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,415 @@
+use std::fmt::Display;
+
+use console::Style;
+use serde::Serialize;
+
+use opendut_types::cluster::ClusterConfiguration;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorKind};
+use opendut_types::topology::DeviceDescriptor;
+
+/// Change of a single resource, which is necessary to get from the state in CARL to the state declared in the manifest.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "action", rename_all = "kebab-case")]
+pub enum Change<T> {
+    Create { desired: T },
+    Update { current: T, desired: T, differences: Vec<Difference> },
+    Delete { current: T },
+    Unchanged { current: T },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "description", rename_all = "kebab-case")]
+pub enum Difference {
+    Added(String),
+    Removed(String),
+    Changed(String),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Plan {
+    pub peers: Vec<Change<PeerDescriptor>>,
+    pub clusters: Vec<Change<ClusterConfiguration>>,
+    /// Number of peers and clusters in CARL, which are not declared in the manifest and are kept, because pruning was not requested.
+    #[serde(skip)]
+    pub untouched: usize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PlanSummary {
+    pub create: usize,
+    pub update: usize,
+    pub delete: usize,
+    pub unchanged: usize,
+}
+
+impl Plan {
+    /// Compares the declared with the current resources.
+    ///
+    /// Resources, which exist in CARL but are not declared, are only deleted when `prune` is set.
+    pub fn compute(
+        current_peers: &[PeerDescriptor],
+        desired_peers: Vec<PeerDescriptor>,
+        current_clusters: &[ClusterConfiguration],
+        desired_clusters: Vec<ClusterConfiguration>,
+        prune: bool,
+    ) -> Self {
+        let (peers, untouched_peers) = compute_changes(current_peers, desired_peers, |peer| peer.id, peer_differences, prune);
+        let (clusters, untouched_clusters) = compute_changes(current_clusters, desired_clusters, |cluster| cluster.id, cluster_differences, prune);
+
+        Self {
+            peers,
+            clusters,
+            untouched: untouched_peers + untouched_clusters,
+        }
+    }
+
+    pub fn summary(&self) -> PlanSummary {
+        let mut summary = PlanSummary::default();
+        let actions = self.peers.iter().map(Change::action)
+            .chain(self.clusters.iter().map(Change::action));
+        for action in actions {
+            match action {
+                Action::Create => summary.create += 1,
+                Action::Update => summary.update += 1,
+                Action::Delete => summary.delete += 1,
+                Action::Unchanged => summary.unchanged += 1,
+            }
+        }
+        summary
+    }
+
+    pub fn has_changes(&self) -> bool {
+        let summary = self.summary();
+        summary.create + summary.update + summary.delete > 0
+    }
+
+    pub fn render_text(&self) -> String {
+        let mut lines = Vec::new();
+
+        if !self.peers.is_empty() {
+            lines.push(String::from("Peers:"));
+            for change in &self.peers {
+                render_change(change, |peer| format!("{} <{}>", peer.name, peer.id), &mut lines);
+            }
+        }
+        if !self.clusters.is_empty() {
+            lines.push(String::from("Clusters:"));
+            for change in &self.clusters {
+                render_change(change, |cluster| format!("{} <{}>", cluster.name, cluster.id), &mut lines);
+            }
+        }
+
+        let PlanSummary { create, update, delete, unchanged } = self.summary();
+        lines.push(format!("Plan: {create} to create, {update} to update, {delete} to delete, {unchanged} unchanged."));
+        if self.untouched > 0 {
+            lines.push(format!("{} resource(s) in CARL are not declared in the manifest and are left untouched. Use '--prune' to delete them.", self.untouched));
+        }
+        lines.join("\n")
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action { Create, Update, Delete, Unchanged }
+
+impl<T> Change<T> {
+    fn action(&self) -> Action {
+        match self {
+            Change::Create { .. } => Action::Create,
+            Change::Update { .. } => Action::Update,
+            Change::Delete { .. } => Action::Delete,
+            Change::Unchanged { .. } => Action::Unchanged,
+        }
+    }
+}
+
+fn compute_changes<T: Clone, Id: PartialEq>(
+    current: &[T],
+    desired: Vec<T>,
+    id: impl Fn(&T) -> Id,
+    differences: impl Fn(&T, &T) -> Vec<Difference>,
+    prune: bool,
+) -> (Vec<Change<T>>, usize) {
+    let mut changes = Vec::new();
+    let mut untouched = 0;
+
+    for current in current {
+        if desired.iter().any(|desired| id(desired) == id(current)) {
+            continue;
+        }
+        if prune {
+            changes.push(Change::Delete { current: current.clone() });
+        } else {
+            untouched += 1;
+        }
+    }
+
+    for desired in desired {
+        let change = match current.iter().find(|current| id(current) == id(&desired)) {
+            None => Change::Create { desired },
+            Some(current) => {
+                let differences = differences(current, &desired);
+                if differences.is_empty() {
+                    Change::Unchanged { current: current.clone() }
+                } else {
+                    Change::Update { current: current.clone(), desired, differences }
+                }
+            }
+        };
+        changes.push(change);
+    }
+
+    (changes, untouched)
+}
+
+fn render_change<T>(change: &Change<T>, describe: impl Fn(&T) -> String, lines: &mut Vec<String>) {
+    let green = Style::new().green();
+    let yellow = Style::new().yellow();
+    let red = Style::new().red();
+
+    match change {
+        Change::Create { desired } => lines.push(format!("  {}", green.apply_to(format!("+ {}", describe(desired))))),
+        Change::Delete { current } => lines.push(format!("  {}", red.apply_to(format!("- {}", describe(current))))),
+        Change::Unchanged { current } => lines.push(format!("    {} (unchanged)", describe(current))),
+        Change::Update { desired, differences, .. } => {
+            lines.push(format!("  {}", yellow.apply_to(format!("~ {}", describe(desired)))));
+            for difference in differences {
+                let line = match difference {
+                    Difference::Added(description) => green.apply_to(format!("+ {description}")),
+                    Difference::Removed(description) => red.apply_to(format!("- {description}")),
+                    Difference::Changed(description) => yellow.apply_to(format!("~ {description}")),
+                };
+                lines.push(format!("      {line}"));
+            }
+        }
+    }
+}
+
+fn peer_differences(current: &PeerDescriptor, desired: &PeerDescriptor) -> Vec<Difference> {
+    let mut differences = Vec::new();
+
+    if current.name != desired.name {
+        differences.push(Difference::Changed(format!("name: '{}' -> '{}'", current.name, desired.name)));
+    }
+    if current.location != desired.location {
+        differences.push(Difference::Changed(format!("location: {} -> {}", display_optional(&current.location), display_optional(&desired.location))));
+    }
+    if current.network.bridge_name != desired.network.bridge_name {
+        differences.push(Difference::Changed(format!("bridge name: {} -> {}", display_optional(&current.network.bridge_name), display_optional(&desired.network.bridge_name))));
+    }
+    differences.extend(collection_differences(
+        "network interface",
+        &current.network.interfaces,
+        &desired.network.interfaces,
+        |interface| interface.id,
+        |interface| interface.to_string(),
+    ));
+    differences.extend(collection_differences(
+        "device",
+        &current.topology.devices,
+        &desired.topology.devices,
+        |device| device.id,
+        describe_device,
+    ));
+    differences.extend(collection_differences(
+        "executor",
+        &current.executors.executors,
+        &desired.executors.executors,
+        |executor| executor.id,
+        describe_executor,
+    ));
+
+    differences
+}
+
+fn cluster_differences(current: &ClusterConfiguration, desired: &ClusterConfiguration) -> Vec<Difference> {
+    let mut differences = Vec::new();
+
+    if current.name != desired.name {
+        differences.push(Difference::Changed(format!("name: '{}' -> '{}'", current.name, desired.name)));
+    }
+    if current.leader != desired.leader {
+        differences.push(Difference::Changed(format!("leader: <{}> -> <{}>", current.leader, desired.leader)));
+    }
+    for device in desired.devices.difference(&current.devices) {
+        differences.push(Difference::Added(format!("device <{device}>")));
+    }
+    for device in current.devices.difference(&desired.devices) {
+        differences.push(Difference::Removed(format!("device <{device}>")));
+    }
+
+    differences
+}
+
+/// Compares two collections of elements by their ID, so that a different order of the elements is not considered a change.
+fn collection_differences<T: PartialEq, Id: PartialEq>(
+    kind: &str,
+    current: &[T],
+    desired: &[T],
+    id: impl Fn(&T) -> Id,
+    describe: impl Fn(&T) -> String,
+) -> Vec<Difference> {
+    let mut differences = Vec::new();
+
+    for current_element in current {
+        match desired.iter().find(|desired_element| id(desired_element) == id(current_element)) {
+            None => differences.push(Difference::Removed(format!("{kind} {}", describe(current_element)))),
+            Some(desired_element) if desired_element != current_element => {
+                let (current_description, desired_description) = (describe(current_element), describe(desired_element));
+                if current_description == desired_description {
+                    differences.push(Difference::Changed(format!("{kind} {desired_description}")));
+                } else {
+                    differences.push(Difference::Changed(format!("{kind} {current_description} -> {desired_description}")));
+                }
+            }
+            Some(_) => {}
+        }
+    }
+    for desired_element in desired {
+        if current.iter().all(|current_element| id(current_element) != id(desired_element)) {
+            differences.push(Difference::Added(format!("{kind} {}", describe(desired_element))));
+        }
+    }
+
+    differences
+}
+
+fn describe_device(device: &DeviceDescriptor) -> String {
+    let tags = device.tags.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("'{}' [description: {}, tags: [{}]]", device.name, display_optional(&device.description), tags)
+}
+
+fn describe_executor(executor: &ExecutorDescriptor) -> String {
+    match &executor.kind {
+        ExecutorKind::Executable { path, .. } => format!("<{}> (executable '{path}')", executor.id.uuid),
+        ExecutorKind::Container { engine, image, .. } => format!("<{}> ({engine} container '{image}')", executor.id.uuid),
+    }
+}
+
+fn display_optional<T: Display>(value: &Option<T>) -> String {
+    value.as_ref()
+        .map(|value| format!("'{value}'"))
+        .unwrap_or_else(|| String::from("none"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use googletest::prelude::*;
+    use rstest::{fixture, rstest};
+
+    use opendut_types::cluster::{ClusterId, ClusterName};
+    use opendut_types::peer::{PeerId, PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::topology::{DeviceId, DeviceName, Topology};
+    use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
+
+    use super::*;
+
+    #[fixture]
+    fn peer() -> PeerDescriptor {
+        let interface = NetworkInterfaceDescriptor {
+            id: NetworkInterfaceId::random(),
+            name: NetworkInterfaceName::try_from("eth0").unwrap(),
+            configuration: NetworkInterfaceConfiguration::Ethernet,
+        };
+        PeerDescriptor {
+            id: PeerId::random(),
+            name: PeerName::try_from("bench-1").unwrap(),
+            location: None,
+            network: PeerNetworkDescriptor {
+                interfaces: vec![Clone::clone(&interface)],
+                bridge_name: None,
+            },
+            topology: Topology {
+                devices: vec![
+                    DeviceDescriptor {
+                        id: DeviceId::random(),
+                        name: DeviceName::try_from("ecu-1").unwrap(),
+                        description: None,
+                        interface: interface.id,
+                        tags: vec![],
+                    },
+                    DeviceDescriptor {
+                        id: DeviceId::random(),
+                        name: DeviceName::try_from("ecu-2").unwrap(),
+                        description: None,
+                        interface: interface.id,
+                        tags: vec![],
+                    },
+                ],
+            },
+            executors: ExecutorDescriptors {
+                executors: vec![],
+            },
+        }
+    }
+
+    fn cluster(peer: &PeerDescriptor) -> ClusterConfiguration {
+        ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from("bench-cluster").unwrap(),
+            leader: peer.id,
+            devices: peer.topology.devices.iter().map(|device| device.id).collect::<HashSet<_>>(),
+        }
+    }
+
+    #[rstest]
+    fn should_plan_creation_of_new_resources(peer: PeerDescriptor) {
+        let cluster = cluster(&peer);
+
+        let plan = Plan::compute(&[], vec![Clone::clone(&peer)], &[], vec![Clone::clone(&cluster)], false);
+
+        assert_that!(plan.peers, elements_are![eq(&Change::Create { desired: peer })]);
+        assert_that!(plan.clusters, elements_are![eq(&Change::Create { desired: cluster })]);
+        assert_that!(plan.has_changes(), eq(true));
+    }
+
+    #[rstest]
+    fn should_not_plan_changes_for_identical_resources_in_different_order(peer: PeerDescriptor) {
+        let mut desired = Clone::clone(&peer);
+        desired.topology.devices.reverse();
+
+        let plan = Plan::compute(&[Clone::clone(&peer)], vec![desired], &[], vec![], false);
+
+        assert_that!(plan.peers, elements_are![eq(&Change::Unchanged { current: peer })]);
+        assert_that!(plan.has_changes(), eq(false));
+    }
+
+    #[rstest]
+    fn should_plan_update_with_differences(peer: PeerDescriptor) -> anyhow::Result<()> {
+        let mut desired = Clone::clone(&peer);
+        desired.name = PeerName::try_from("bench-2")?;
+        let removed_device = desired.topology.devices.remove(1);
+
+        let plan = Plan::compute(&[Clone::clone(&peer)], vec![Clone::clone(&desired)], &[], vec![], false);
+
+        assert_that!(plan.peers, elements_are![eq(&Change::Update {
+            current: peer,
+            desired,
+            differences: vec![
+                Difference::Changed(String::from("name: 'bench-1' -> 'bench-2'")),
+                Difference::Removed(format!("device {}", describe_device(&removed_device))),
+            ],
+        })]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn should_only_plan_deletion_of_undeclared_resources_when_pruning(peer: PeerDescriptor) {
+        let cluster = cluster(&peer);
+
+        let plan = Plan::compute(&[Clone::clone(&peer)], vec![], &[Clone::clone(&cluster)], vec![], false);
+        assert_that!(plan.has_changes(), eq(false));
+        assert_that!(plan.untouched, eq(2));
+
+        let plan = Plan::compute(&[Clone::clone(&peer)], vec![], &[Clone::clone(&cluster)], vec![], true);
+        assert_that!(plan.peers, elements_are![eq(&Change::Delete { current: peer })]);
+        assert_that!(plan.clusters, elements_are![eq(&Change::Delete { current: cluster })]);
+        assert_that!(plan.summary(), eq(PlanSummary { create: 0, update: 0, delete: 2, unchanged: 0 }));
+    }
+}
@@ -11,18 +11,18 @@ pub struct CreateDeviceCli {
     ///ID of the peer to add the device to
     #[arg(long)]
     peer_id: Uuid,
-    ///ID of the device to be added or updated
+    ///ID of the device to be added
     #[arg(long)]
     device_id: Option<Uuid>,
     ///Name of the device
     #[arg(long)]
-    name: Option<String>,
+    name: String,
     ///Description of device
     #[arg(long)]
     description: Option<String>,
     ///Interface of device
     #[arg(long)]
-    interface: Option<NetworkInterfaceName>,
+    interface: NetworkInterfaceName,
     /// Tags of device
     #[arg(long("tag"))]
     tags: Option<Vec<String>>,
@@ -39,67 +39,36 @@ impl CreateDeviceCli {
         let peer_network_interface_names = peer_descriptor.network.interfaces.iter().map(|peer_interface| {
             peer_interface.name.clone()
         }).collect::<Vec<_>>();
-        let maybe_existing_device = peer_descriptor.topology.devices.iter_mut().find(|device| device.id == device_id);
-        match maybe_existing_device {
+        if peer_descriptor.topology.devices.iter().any(|device| device.id == device_id) {
+            Err(format!("Cannot create new device because a device with the ID <{}> already exists. Use 'update device' to change it.", device_id))?
+        }
+
+        let interface = match peer_descriptor.network.interfaces.iter().find(|descriptor| descriptor.name == self.interface) {
+            Some(network_interface_descriptor) => network_interface_descriptor.clone(),
             None => {
-                //TODO provide separate `update device` command to not need this custom input handling
-                let name = self.name.ok_or(String::from("Cannot create new device because of missing device name."))?;
-                let interface_name = self.interface.ok_or(String::from("Cannot create new device because of missing interface name."))?;
+                Err(format!("Cannot create new device because interface is not one of the allowed values: {} \nAllowed interfaces are configured on the peer.",
+                            peer_network_interface_names.into_iter().map(|name| name.name()).collect::<Vec<_>>().join(", ")))?
+            },
+        };
 
-                let interface = match peer_descriptor.network.interfaces.iter().find(|descriptor| descriptor.name == interface_name) {
-                    Some(network_interface_descriptor) => network_interface_descriptor.clone(),
-                    None => {
-                        Err(format!("Cannot create new device because interface is not one of the allowed values: {} \nAllowed interfaces are configured on the peer.",
-                                    peer_network_interface_names.into_iter().map(|name| name.name()).collect::<Vec<_>>().join(", ")))?
-                    },
-                };
+        let new_device = DeviceDescriptor {
+            id: device_id,
+            name: DeviceName::try_from(self.name)
+                .map_err(|error| error.to_string())?,
+            description: self.description
+                .map(DeviceDescription::try_from)
+                .transpose()
+                .map_err(|error| error.to_string())?,
+            interface: interface.id,
+            tags: self.tags
+                .unwrap_or_default()
+                .into_iter()
+                .map(DeviceTag::try_from)
+                .collect::<Result<_, _>>()
+                .map_err(|error| error.to_string())?,
+        };
+        peer_descriptor.topology.devices.push(new_device);
 
-                let new_device = DeviceDescriptor {
-                    id: device_id,
-                    name: DeviceName::try_from(name)
-                        .map_err(|error| error.to_string())?,
-                    description: self.description
-                        .map(DeviceDescription::try_from)
-                        .transpose()
-                        .map_err(|error| error.to_string())?,
-                    interface: interface.id,
-                    tags: self.tags
-                        .unwrap_or_default()
-                        .into_iter()
-                        .map(DeviceTag::try_from)
-                        .collect::<Result<_, _>>()
-                        .map_err(|error| error.to_string())?,
-                };
-                peer_descriptor.topology.devices.push(new_device);
-            }
-            Some(device) => {
-                if let Some(name) = self.name {
-                    device.name = DeviceName::try_from(name)
-                        .map_err(|error| error.to_string())?;
-                }
-                if let Some(description) = self.description {
-                    device.description = DeviceDescription::try_from(description)
-                        .map_err(|error| error.to_string())
-                        .ok();
-                }
-                if let Some(interface_name) = self.interface {
-                    device.interface = match peer_descriptor.network.interfaces.iter().find(|descriptor| descriptor.name == interface_name) {
-                        Some(network_interface_descriptor) => network_interface_descriptor.id,
-                        None => {
-                            Err(format!("Cannot create new device because interface is not one of the allowed values: {} \nAllowed interfaces are configured on the peer.",
-                                        peer_network_interface_names.into_iter().map(|name| name.name()).collect::<Vec<_>>().join(", ")))?
-                        },
-                    };
-                }
-                if let Some(tags) = self.tags {
-                    device.tags = tags
-                        .into_iter()
-                        .map(DeviceTag::try_from)
-                        .collect::<Result<_, _>>()
-                        .map_err(|error| error.to_string())?;
-                }
-            }
-        }
         carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
             .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
         let output_format = DescribeOutputFormat::from(output);
@@ -2,6 +2,7 @@ pub mod create;
 pub mod describe;
 pub mod find;
 pub mod delete;
+pub mod update;
 pub mod list;
 
 use cli_table::{Table, WithTitle};
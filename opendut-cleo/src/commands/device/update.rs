@@ -0,0 +1,257 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::topology::{DeviceDescription, DeviceId, DeviceName, DeviceTag};
+use opendut_types::util::net::NetworkInterfaceName;
+
+use crate::{CreateOutputFormat, DescribeOutputFormat};
+
+/// Update a device
+#[derive(clap::Parser)]
+pub struct UpdateDeviceCli {
+    ///DeviceID
+    #[arg()]
+    id: Uuid,
+    ///New name of the device
+    #[arg(long)]
+    name: Option<String>,
+    ///New description of the device
+    #[arg(long)]
+    description: Option<String>,
+    ///Remove the description of the device
+    #[arg(long, conflicts_with = "description")]
+    clear_description: bool,
+    ///Name of the network interface of the peer, which the device is connected to
+    #[arg(long)]
+    interface: Option<NetworkInterfaceName>,
+    ///Tags of device, replacing the existing tags
+    #[arg(long("tag"))]
+    tags: Option<Vec<String>>,
+    ///Remove all tags of the device
+    #[arg(long, conflicts_with = "tags")]
+    clear_tags: bool,
+}
+
+impl UpdateDeviceCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let device_id = DeviceId::from(self.id);
+
+        let peers = carl.peers.list_peer_descriptors().await
+            .map_err(|error| format!("Could not list peers.\n  {}", error))?;
+
+        let mut peer_descriptor = peers.into_iter()
+            .find(|peer| peer.topology.devices.iter().any(|device| device.id == device_id))
+            .ok_or(format!("Cannot find a peer with the device <{}>.", device_id))?;
+
+        self.apply_to(device_id, &mut peer_descriptor)
+            .map_err(|error| format!("Could not update device <{}>.\n  {}", device_id, error))?;
+
+        carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
+            .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_descriptor.id, error))?;
+        let output_format = DescribeOutputFormat::from(output);
+        crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
+
+        Ok(())
+    }
+
+    fn apply_to(self, device_id: DeviceId, peer_descriptor: &mut PeerDescriptor) -> crate::Result<()> {
+        let interface_id = match self.interface {
+            None => None,
+            Some(interface_name) => {
+                let interface = peer_descriptor.network.interfaces.iter()
+                    .find(|interface| interface.name == interface_name)
+                    .ok_or_else(|| {
+                        let allowed_interfaces = peer_descriptor.network.interfaces.iter()
+                            .map(|interface| interface.name.name())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        format!("Interface '{interface_name}' is not one of the allowed values: {allowed_interfaces} \nAllowed interfaces are configured on the peer.")
+                    })?;
+                Some(interface.id)
+            }
+        };
+
+        let device = peer_descriptor.topology.devices.iter_mut()
+            .find(|device| device.id == device_id)
+            .ok_or(format!("Peer <{}> has no device <{}>.", peer_descriptor.id, device_id))?;
+
+        if let Some(name) = self.name {
+            device.name = DeviceName::try_from(name)
+                .map_err(|error| error.to_string())?;
+        }
+
+        if self.clear_description {
+            device.description = None;
+        } else if let Some(description) = self.description {
+            device.description = Some(DeviceDescription::try_from(description)
+                .map_err(|error| error.to_string())?);
+        }
+
+        if let Some(interface_id) = interface_id {
+            device.interface = interface_id;
+        }
+
+        if self.clear_tags {
+            device.tags = vec![];
+        } else if let Some(tags) = self.tags {
+            device.tags = tags
+                .into_iter()
+                .map(DeviceTag::try_from)
+                .collect::<Result<_, _>>()
+                .map_err(|error| error.to_string())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+    use googletest::prelude::*;
+    use rstest::{fixture, rstest};
+
+    use opendut_types::peer::{PeerId, PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::topology::{DeviceDescriptor, Topology};
+    use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId};
+
+    use super::*;
+
+    #[fixture]
+    fn peer_descriptor() -> PeerDescriptor {
+        let eth0 = NetworkInterfaceDescriptor {
+            id: NetworkInterfaceId::random(),
+            name: NetworkInterfaceName::try_from("eth0").unwrap(),
+            configuration: NetworkInterfaceConfiguration::Ethernet,
+        };
+        let eth1 = NetworkInterfaceDescriptor {
+            id: NetworkInterfaceId::random(),
+            name: NetworkInterfaceName::try_from("eth1").unwrap(),
+            configuration: NetworkInterfaceConfiguration::Ethernet,
+        };
+
+        PeerDescriptor {
+            id: PeerId::random(),
+            name: PeerName::try_from("bench-1").unwrap(),
+            location: None,
+            topology: Topology {
+                devices: vec![
+                    DeviceDescriptor {
+                        id: DeviceId::random(),
+                        name: DeviceName::try_from("ecu-1").unwrap(),
+                        description: Some(DeviceDescription::try_from("Engine Control Unit").unwrap()),
+                        interface: eth0.id,
+                        tags: vec![DeviceTag::try_from("engine").unwrap()],
+                    },
+                ],
+            },
+            network: PeerNetworkDescriptor {
+                interfaces: vec![eth0, eth1],
+                bridge_name: None,
+            },
+            executors: ExecutorDescriptors {
+                executors: vec![],
+            },
+        }
+    }
+
+    fn device_id(peer_descriptor: &PeerDescriptor) -> DeviceId {
+        peer_descriptor.topology.devices[0].id
+    }
+
+    #[rstest]
+    fn should_only_update_the_given_fields(mut peer_descriptor: PeerDescriptor) -> anyhow::Result<()> {
+        let device_id = device_id(&peer_descriptor);
+        let cli = UpdateDeviceCli::try_parse_from([
+            "update", &device_id.to_string(), "--name", "ecu-2",
+        ])?;
+
+        cli.apply_to(device_id, &mut peer_descriptor).map_err(anyhow::Error::msg)?;
+
+        let device = &peer_descriptor.topology.devices[0];
+        assert_that!(device.name, eq(&DeviceName::try_from("ecu-2")?));
+        assert_that!(device.description, some(eq(&DeviceDescription::try_from("Engine Control Unit")?)));
+        assert_that!(device.interface, eq(peer_descriptor.network.interfaces[0].id));
+        assert_that!(device.tags, elements_are![eq(&DeviceTag::try_from("engine")?)]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn should_move_the_device_to_another_interface_and_replace_its_tags(mut peer_descriptor: PeerDescriptor) -> anyhow::Result<()> {
+        let device_id = device_id(&peer_descriptor);
+        let cli = UpdateDeviceCli::try_parse_from([
+            "update", &device_id.to_string(), "--interface", "eth1", "--tag", "brakes", "--tag", "hil",
+        ])?;
+
+        cli.apply_to(device_id, &mut peer_descriptor).map_err(anyhow::Error::msg)?;
+
+        let device = &peer_descriptor.topology.devices[0];
+        assert_that!(device.interface, eq(peer_descriptor.network.interfaces[1].id));
+        assert_that!(device.tags, elements_are![eq(&DeviceTag::try_from("brakes")?), eq(&DeviceTag::try_from("hil")?)]);
+        Ok(())
+    }
+
+    #[rstest]
+    fn should_clear_the_description_and_tags(mut peer_descriptor: PeerDescriptor) -> anyhow::Result<()> {
+        let device_id = device_id(&peer_descriptor);
+        let cli = UpdateDeviceCli::try_parse_from([
+            "update", &device_id.to_string(), "--clear-description", "--clear-tags",
+        ])?;
+
+        cli.apply_to(device_id, &mut peer_descriptor).map_err(anyhow::Error::msg)?;
+
+        let device = &peer_descriptor.topology.devices[0];
+        assert_that!(device.description, none());
+        assert_that!(device.tags, empty());
+        Ok(())
+    }
+
+    #[rstest]
+    fn should_reject_conflicting_arguments(peer_descriptor: PeerDescriptor) {
+        let device_id = device_id(&peer_descriptor).to_string();
+
+        assert!(UpdateDeviceCli::try_parse_from(["update", &device_id, "--description", "ECU", "--clear-description"]).is_err());
+        assert!(UpdateDeviceCli::try_parse_from(["update", &device_id, "--tag", "engine", "--clear-tags"]).is_err());
+    }
+
+    #[rstest]
+    fn should_reject_an_interface_which_is_not_configured_on_the_peer(mut peer_descriptor: PeerDescriptor) -> anyhow::Result<()> {
+        let device_id = device_id(&peer_descriptor);
+        let original = Clone::clone(&peer_descriptor);
+        let cli = UpdateDeviceCli::try_parse_from([
+            "update", &device_id.to_string(), "--name", "ecu-2", "--interface", "vcan0",
+        ])?;
+
+        let result = cli.apply_to(device_id, &mut peer_descriptor);
+
+        assert_that!(result, err(contains_substring("Interface 'vcan0' is not one of the allowed values: eth0, eth1")));
+        assert_that!(peer_descriptor, eq(&original));
+        Ok(())
+    }
+
+    #[rstest]
+    fn should_reject_an_invalid_name(mut peer_descriptor: PeerDescriptor) -> anyhow::Result<()> {
+        let device_id = device_id(&peer_descriptor);
+        let cli = UpdateDeviceCli::try_parse_from([
+            "update", &device_id.to_string(), "--name", "ecu 2!",
+        ])?;
+
+        assert_that!(cli.apply_to(device_id, &mut peer_descriptor), err(anything()));
+        Ok(())
+    }
+
+    #[rstest]
+    fn should_reject_a_device_which_the_peer_does_not_have(mut peer_descriptor: PeerDescriptor) -> anyhow::Result<()> {
+        let unknown_device_id = DeviceId::random();
+        let cli = UpdateDeviceCli::try_parse_from([
+            "update", &unknown_device_id.to_string(), "--name", "ecu-2",
+        ])?;
+
+        let result = cli.apply_to(unknown_device_id, &mut peer_descriptor);
+
+        assert_that!(result, err(contains_substring(format!("has no device <{unknown_device_id}>"))));
+        Ok(())
+    }
+}
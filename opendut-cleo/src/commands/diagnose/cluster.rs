@@ -0,0 +1,145 @@
+use std::ops::Not;
+
+use cli_table::{print_stdout, Table, WithTitle};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::ClusterId;
+use opendut_types::cluster::diagnostics::{NetworkDiagnosticsOptions, PeerLinkDiagnostics};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+
+use crate::ListOutputFormat;
+use crate::parse::duration::ParseableDuration;
+
+/// Test latency, throughput and path MTU between all peers of a deployed cluster
+#[derive(clap::Parser)]
+pub struct DiagnoseClusterCli {
+    ///ClusterID
+    #[arg()]
+    id: Uuid,
+    ///Number of pings sent to each peer to measure the latency, 0 to skip
+    #[arg(long, default_value_t = 5)]
+    ping_count: u32,
+    ///Time to wait for a ping reply, e.g. 500ms or 2s
+    #[arg(long, default_value = "1s")]
+    ping_timeout: ParseableDuration,
+    ///Duration of each throughput test, 0 to skip
+    #[arg(long, default_value = "5s")]
+    throughput_duration: ParseableDuration,
+    ///Bandwidth at which data is sent during the throughput tests, in kilobit per second
+    #[arg(long, default_value_t = 100_000)]
+    target_bandwidth: u64,
+    ///Skip the discovery of the path MTU
+    #[arg(long)]
+    skip_path_mtu: bool,
+}
+
+#[derive(Table, Debug)]
+struct PeerLinkTable {
+    #[table(title = "Source")]
+    source: String,
+    #[table(title = "Target")]
+    target: String,
+    #[table(title = "RTT min/avg/max [ms]")]
+    round_trip_time: String,
+    #[table(title = "Ping Loss [%]")]
+    ping_loss: String,
+    #[table(title = "TCP Send [Mbit/s]")]
+    tcp_send: String,
+    #[table(title = "TCP Receive [Mbit/s]")]
+    tcp_receive: String,
+    #[table(title = "UDP Send [Mbit/s]")]
+    udp_send: String,
+    #[table(title = "UDP Jitter [ms]")]
+    udp_jitter: String,
+    #[table(title = "UDP Loss [%]")]
+    udp_loss: String,
+    #[table(title = "Path MTU")]
+    path_mtu: String,
+    #[table(title = "Errors")]
+    errors: String,
+}
+
+impl DiagnoseClusterCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let cluster_id = ClusterId::from(self.id);
+
+        let options = NetworkDiagnosticsOptions {
+            ping_count: self.ping_count,
+            ping_timeout: self.ping_timeout.0,
+            throughput_duration: self.throughput_duration.0,
+            target_bandwidth_kbit_per_second: self.target_bandwidth,
+            path_mtu_discovery: self.skip_path_mtu.not(),
+        };
+
+        let diagnostics = carl.cluster.diagnose_cluster(cluster_id, options).await
+            .map_err(|error| format!("Could not diagnose network of cluster <{cluster_id}>.\n  {error}"))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let peers = carl.peers.list_peer_descriptors().await
+                    .map_err(|error| format!("Could not list peers.\n  {error}"))?;
+
+                let table = diagnostics.links.iter()
+                    .map(|link| to_table_row(link, &peers))
+                    .collect::<Vec<_>>();
+
+                print_stdout(table.with_title())
+                    .expect("Network diagnostics should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&diagnostics).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&diagnostics).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn to_table_row(link: &PeerLinkDiagnostics, peers: &[PeerDescriptor]) -> PeerLinkTable {
+    let peer_name = |peer_id: PeerId| {
+        peers.iter()
+            .find(|peer| peer.id == peer_id)
+            .map(|peer| peer.name.to_string())
+            .unwrap_or_else(|| peer_id.to_string())
+    };
+    let milliseconds = |duration: std::time::Duration| format!("{:.2}", duration.as_secs_f64() * 1000.0);
+    let not_measured = || String::from("-");
+
+    let (round_trip_time, ping_loss) = match &link.latency {
+        Some(latency) => (
+            format!("{}/{}/{}", milliseconds(latency.min), milliseconds(latency.avg), milliseconds(latency.max)),
+            format!("{:.1}", latency.packet_loss_percent()),
+        ),
+        None => (not_measured(), not_measured()),
+    };
+
+    let (tcp_send, tcp_receive, udp_send, udp_jitter, udp_loss) = match &link.throughput {
+        Some(throughput) => (
+            format!("{:.1}", throughput.tcp_send_megabits_per_second),
+            format!("{:.1}", throughput.tcp_receive_megabits_per_second),
+            format!("{:.1}", throughput.udp_send_megabits_per_second),
+            milliseconds(throughput.udp_jitter),
+            format!("{:.1}", throughput.udp_packet_loss_percent),
+        ),
+        None => (not_measured(), not_measured(), not_measured(), not_measured(), not_measured()),
+    };
+
+    PeerLinkTable {
+        source: peer_name(link.source),
+        target: peer_name(link.target),
+        round_trip_time,
+        ping_loss,
+        tcp_send,
+        tcp_receive,
+        udp_send,
+        udp_jitter,
+        udp_loss,
+        path_mtu: link.path_mtu.map(|path_mtu| path_mtu.to_string()).unwrap_or_else(not_measured),
+        errors: link.errors.join("\n"),
+    }
+}
@@ -3,6 +3,7 @@ use uuid::Uuid;
 
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::PeerId;
+use opendut_types::peer::executor::executable::{ExecutablePath, ExecutableTimeout, ExecutableWorkingDirectory};
 use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ExecutorKind, ResultsUrl};
 
 use crate::{CreateOutputFormat, DescribeOutputFormat, EngineVariants};
@@ -90,6 +91,73 @@ impl CreateContainerExecutorCli {
         let peer_id = PeerId::from(self.peer_id);
         
 
+        let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
+            .map_err(|_| format!("Failed to get peer with ID <{}>.", peer_id))?;
+
+        peer_descriptor.executors.executors.push(executor_descriptor);
+
+        carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
+            .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
+        let output_format = DescribeOutputFormat::from(output);
+        crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
+
+        Ok(())
+    }
+}
+
+/// Create an executable executor, which runs a program natively on the peer
+#[derive(clap::Parser)]
+pub struct CreateExecutableExecutorCli {
+    ///ID of the peer to add the executable executor to
+    #[arg(long)]
+    peer_id: Uuid,
+    ///Path of the executable. Relative paths are looked up in the PATH of EDGAR.
+    #[arg(long)]
+    path: ExecutablePath,
+    ///Arguments
+    #[arg(short, long, num_args = 1..)]
+    args: Option<Vec<ContainerCommandArgument>>,
+    ///Environment variables in the form NAME=VALUE
+    #[arg(long, num_args = 1..)]
+    envs: Option<Vec<String>>,
+    ///Working directory
+    #[arg(long)]
+    working_dir: Option<ExecutableWorkingDirectory>,
+    ///Timeout in seconds, after which the executable is killed
+    #[arg(long)]
+    timeout: Option<ExecutableTimeout>,
+    ///URL to which results will be uploaded
+    #[arg(short, long)]
+    results_url: Option<ResultsUrl>,
+}
+
+impl CreateExecutableExecutorCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+
+        let mut environment_variables = vec![];
+
+        for env in self.envs.unwrap_or_default() {
+            let (name, value) = env.split_once('=')
+                .ok_or(format!("Environment variable '{env}' is not of the form NAME=VALUE."))?;
+            let env = ContainerEnvironmentVariable::new(name, value)
+                .map_err(|cause| cause.to_string())?;
+            environment_variables.push(env)
+        };
+
+        let executor_descriptor = ExecutorDescriptor {
+            id: ExecutorId::random(),
+            kind: ExecutorKind::Executable {
+                path: self.path,
+                args: self.args.unwrap_or_default(),
+                envs: environment_variables,
+                working_dir: self.working_dir,
+                timeout: self.timeout,
+            },
+            results_url: self.results_url,
+        };
+
+        let peer_id = PeerId::from(self.peer_id);
+
         let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
             .map_err(|_| format!("Failed to get peer with ID <{}>.", peer_id))?;
 
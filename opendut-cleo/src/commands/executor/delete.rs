@@ -31,7 +31,7 @@ impl DeleteContainerExecutorCli {
 
         for container_image in container_images {
             peer.executors.executors.retain(|executor| match &executor.kind {
-                ExecutorKind::Executable => true,
+                ExecutorKind::Executable { .. } => true,
                 ExecutorKind::Container { image, .. } => {
                     image != &container_image
                 }
@@ -49,7 +49,7 @@ fn filter_connected_peers(
 ) -> Vec<ContainerExecutorTable> {
     let mut executor_table = vec![];
     for executor in &peer.executors.executors {
-        let ExecutorDescriptor { id: _, kind, results_url } = executor;
+        let ExecutorDescriptor { id, kind, results_url } = executor;
         
         if let ExecutorKind::Container {
             engine,
@@ -69,6 +69,7 @@ fn filter_connected_peers(
             let envs = envs.iter().map(|env|
                 format!("{}={}", env.name(), env.value())).collect::<Vec<_>>();
             executor_table.push(ContainerExecutorTable {
+                id: id.uuid,
                 engine: Clone::clone(engine),
                 name: name.into(),
                 image: Clone::clone(image),
@@ -87,6 +88,8 @@ fn filter_connected_peers(
 
 #[derive(Table, Debug, Serialize)]
 struct ContainerExecutorTable {
+    #[table(title = "ExecutorID")]
+    id: Uuid,
     #[table(title = "Engine")]
     engine: Engine,
     #[table(title = "Name")]
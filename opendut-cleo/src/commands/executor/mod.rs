@@ -1,4 +1,8 @@
 pub mod create;
 pub mod delete;
+pub mod update;
 pub mod list;
-pub mod apply;
\ No newline at end of file
+pub mod apply;
+pub mod start;
+pub mod stop;
+pub mod restart;
\ No newline at end of file
@@ -0,0 +1,17 @@
+use opendut_carl_api::carl::CarlClient;
+
+use crate::commands::executor::start::StartExecutorArgs;
+use crate::CreateOutputFormat;
+
+/// Stop the current run of an executor, if any, and start a new one
+#[derive(clap::Parser)]
+pub struct RestartExecutorCli {
+    #[command(flatten)]
+    args: StartExecutorArgs,
+}
+
+impl RestartExecutorCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        self.args.execute(carl, output, true).await
+    }
+}
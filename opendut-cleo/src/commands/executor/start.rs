@@ -0,0 +1,69 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::executor::container::{ContainerCommandArgument, ContainerEnvironmentVariable};
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::executor::test_run::{ExecutorRunOverrides, TestRun};
+
+use crate::CreateOutputFormat;
+
+/// Start a run of an executor
+#[derive(clap::Parser)]
+pub struct StartExecutorCli {
+    #[command(flatten)]
+    args: StartExecutorArgs,
+}
+
+#[derive(clap::Args)]
+pub struct StartExecutorArgs {
+    ///ID of the executor
+    #[arg()]
+    id: Uuid,
+    ///Environment variables for this run, in the form NAME=VALUE. Replace configured variables with the same name.
+    #[arg(long = "env")]
+    envs: Vec<String>,
+    ///Arguments for this run. Replace all configured arguments.
+    #[arg(long = "arg", allow_hyphen_values = true)]
+    args: Vec<ContainerCommandArgument>,
+}
+
+impl StartExecutorCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        self.args.execute(carl, output, false).await
+    }
+}
+
+impl StartExecutorArgs {
+    pub(super) async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat, restart: bool) -> crate::Result<()> {
+        let executor_id = ExecutorId::from(self.id);
+
+        let envs = self.envs.into_iter()
+            .map(|env| {
+                let (name, value) = env.split_once('=')
+                    .ok_or_else(|| format!("Environment variable '{env}' is not in the form NAME=VALUE."))?;
+                ContainerEnvironmentVariable::new(name, value)
+                    .map_err(|cause| cause.to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let overrides = ExecutorRunOverrides {
+            envs,
+            args: self.args,
+        };
+
+        let test_run = carl.peers.start_executor(executor_id, overrides, restart).await
+            .map_err(|error| format!("Failed to start executor <{executor_id}>.\n  {error}"))?;
+
+        render_started_run(test_run, output);
+        Ok(())
+    }
+}
+
+fn render_started_run(test_run: TestRun, output: CreateOutputFormat) {
+    let text = match output {
+        CreateOutputFormat::Text => format!("Started run <{}> of executor <{}>.", test_run.id, test_run.executor_id),
+        CreateOutputFormat::Json => serde_json::to_string(&test_run).unwrap(),
+        CreateOutputFormat::PrettyJson => serde_json::to_string_pretty(&test_run).unwrap(),
+    };
+    println!("{text}");
+}
@@ -0,0 +1,24 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::executor::ExecutorId;
+
+/// Stop the current run of an executor
+#[derive(clap::Parser)]
+pub struct StopExecutorCli {
+    ///ID of the executor
+    #[arg()]
+    id: Uuid,
+}
+
+impl StopExecutorCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let executor_id = ExecutorId::from(self.id);
+
+        carl.peers.stop_executor(executor_id).await
+            .map_err(|error| format!("Failed to stop executor <{executor_id}>.\n  {error}"))?;
+
+        println!("Stopped executor <{executor_id}>.");
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ExecutorId, ExecutorKind, ResultsUrl};
+use opendut_types::peer::executor::executable::{ExecutablePath, ExecutableTimeout, ExecutableWorkingDirectory};
+
+use crate::{CreateOutputFormat, DescribeOutputFormat, EngineVariants};
+
+/// Update an executor of a peer
+#[derive(clap::Parser)]
+pub struct UpdateExecutorCli {
+    ///ID of the peer the executor belongs to
+    #[arg(long)]
+    peer_id: Uuid,
+    ///ExecutorID
+    #[arg(long)]
+    id: Uuid,
+    ///Engine
+    #[arg(short, long)]
+    engine: Option<EngineVariants>,
+    ///Container name
+    #[arg(short, long)]
+    name: Option<ContainerName>,
+    ///Container image
+    #[arg(short, long)]
+    image: Option<ContainerImage>,
+    ///Container volumes, replacing the existing volumes
+    #[arg(short, long, num_args = 1..)]
+    volumes: Option<Vec<ContainerVolume>>,
+    ///Container devices, replacing the existing devices
+    #[arg(long, num_args = 1..)]
+    devices: Option<Vec<ContainerDevice>>,
+    ///Environment variables in the form NAME=VALUE, replacing the existing envs
+    #[arg(long, num_args = 1..)]
+    envs: Option<Vec<String>>,
+    ///Container ports, replacing the existing ports
+    #[arg(short, long, num_args = 1..)]
+    ports: Option<Vec<ContainerPortSpec>>,
+    ///Container command
+    #[arg(short, long)]
+    command: Option<ContainerCommand>,
+    ///Arguments, replacing the existing arguments
+    #[arg(short, long, num_args = 1..)]
+    args: Option<Vec<ContainerCommandArgument>>,
+    ///Path of the executable
+    #[arg(long)]
+    path: Option<ExecutablePath>,
+    ///Working directory of the executable
+    #[arg(long)]
+    working_dir: Option<ExecutableWorkingDirectory>,
+    ///Timeout of the executable in seconds
+    #[arg(long)]
+    timeout: Option<ExecutableTimeout>,
+    ///URL to which results will be uploaded
+    #[arg(short, long)]
+    results_url: Option<ResultsUrl>,
+    ///Do not upload results
+    #[arg(long, conflicts_with = "results_url")]
+    clear_results_url: bool,
+}
+
+impl UpdateExecutorCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let peer_id = PeerId::from(self.peer_id);
+
+        let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
+            .map_err(|_| format!("Failed to get peer with ID <{}>.", peer_id))?;
+
+        let executor_id = ExecutorId::from(self.id);
+        self.apply_to(&mut peer_descriptor)
+            .map_err(|error| format!("Could not update executor <{}>.\n  {}", executor_id.uuid, error))?;
+
+        carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
+            .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
+        let output_format = DescribeOutputFormat::from(output);
+        crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
+
+        Ok(())
+    }
+
+    fn apply_to(self, peer_descriptor: &mut PeerDescriptor) -> crate::Result<()> {
+        let executor_id = ExecutorId::from(self.id);
+
+        let executor = peer_descriptor.executors.executors.iter_mut()
+            .find(|executor| executor.id == executor_id)
+            .ok_or(format!("Peer <{}> has no executor <{}>.", peer_descriptor.id, executor_id.uuid))?;
+
+        match &mut executor.kind {
+            ExecutorKind::Executable { path, args, envs, working_dir, timeout } => {
+                let has_container_parameters = self.engine.is_some() || self.name.is_some() || self.image.is_some()
+                    || self.volumes.is_some() || self.devices.is_some() || self.ports.is_some()
+                    || self.command.is_some();
+                if has_container_parameters {
+                    Err(String::from("Container parameters can only be specified for container executors."))?
+                }
+                if let Some(new_path) = self.path {
+                    *path = new_path;
+                }
+                if let Some(new_envs) = self.envs {
+                    *envs = parse_environment_variables(new_envs)?;
+                }
+                if let Some(new_args) = self.args {
+                    *args = new_args;
+                }
+                if let Some(new_working_dir) = self.working_dir {
+                    *working_dir = Some(new_working_dir);
+                }
+                if let Some(new_timeout) = self.timeout {
+                    *timeout = Some(new_timeout);
+                }
+            }
+            ExecutorKind::Container { engine, name, image, volumes, devices, envs, ports, command, args } => {
+                let has_executable_parameters = self.path.is_some() || self.working_dir.is_some() || self.timeout.is_some();
+                if has_executable_parameters {
+                    Err(String::from("Executable parameters can only be specified for executable executors."))?
+                }
+                if let Some(new_engine) = self.engine {
+                    *engine = match new_engine {
+                        EngineVariants::Docker => { Engine::Docker }
+                        EngineVariants::Podman => { Engine::Podman }
+                    };
+                }
+                if let Some(new_name) = self.name {
+                    *name = new_name;
+                }
+                if let Some(new_image) = self.image {
+                    *image = new_image;
+                }
+                if let Some(new_volumes) = self.volumes {
+                    *volumes = new_volumes;
+                }
+                if let Some(new_devices) = self.devices {
+                    *devices = new_devices;
+                }
+                if let Some(new_envs) = self.envs {
+                    *envs = parse_environment_variables(new_envs)?;
+                }
+                if let Some(new_ports) = self.ports {
+                    *ports = new_ports;
+                }
+                if let Some(new_command) = self.command {
+                    *command = new_command;
+                }
+                if let Some(new_args) = self.args {
+                    *args = new_args;
+                }
+            }
+        }
+
+        if self.clear_results_url {
+            executor.results_url = None;
+        } else if let Some(results_url) = self.results_url {
+            executor.results_url = Some(results_url);
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_environment_variables(envs: Vec<String>) -> crate::Result<Vec<ContainerEnvironmentVariable>> {
+    envs.into_iter()
+        .map(|env| {
+            let (env_name, env_value) = env.split_once('=')
+                .ok_or(format!("Environment variable '{env}' is not of the form NAME=VALUE."))?;
+            ContainerEnvironmentVariable::new(env_name, env_value)
+                .map_err(|cause| cause.to_string())
+        })
+        .collect()
+}
@@ -8,15 +8,27 @@ pub struct GenerateSetupStringCli {
     ///PeerID
     #[arg()]
     id: Uuid,
+    ///Revoke the VPN and OIDC credentials of previous setup strings, e.g. when replacing the host of the peer
+    #[arg(long)]
+    reenroll: bool,
 }
 
 impl GenerateSetupStringCli {
-    //TODO: what happens if peer with the ID is already set up?
     pub async fn execute(self, carl: &mut CarlClient, cleo_oidc_client_id: String,) -> crate::Result<()> {
         let peer_id = PeerId::from(self.id);
+
+        if !self.reenroll {
+            let was_set_up_before = carl.peers.get_peer_history(peer_id).await
+                .map(|history| !history.enrollments.is_empty())
+                .unwrap_or(false);
+            if was_set_up_before {
+                eprintln!("A setup string was generated for peer <{peer_id}> before. If its host is replaced, use '--reenroll' to revoke the credentials of previous setup strings.");
+            }
+        }
+
         let created_setup = carl
             .peers
-            .create_peer_setup(peer_id, cleo_oidc_client_id)
+            .create_peer_setup(peer_id, cleo_oidc_client_id, self.reenroll)
             .await
             .map_err(|error| format!("Could not create setup string.\n  {}", error))?;
 
@@ -24,6 +36,9 @@ impl GenerateSetupStringCli {
             Ok(setup_string) => {
                 println!("{}", setup_string);
                 eprintln!("Setup-Strings may only be used to set up one host. For setting up multiple hosts, you should create a peer for each host.");
+                if self.reenroll {
+                    eprintln!("Previous setup strings of this peer have been revoked. Apply this setup string via 'opendut-edgar setup managed --reenroll <SETUP-STRING>'.");
+                }
             }
             Err(_) => {
                 println!("Could not configure setup string...")
@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::executor::log::{ExecutorLogLine, ExecutorLogStream, ExecutorRunId};
+
+use crate::LogsOutputFormat;
+
+/// Interval in which new lines are requested from CARL, when following the logs.
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Show the output of a run of an executor
+#[derive(clap::Parser)]
+pub struct ExecutorLogsCli {
+    ///ExecutorID
+    #[arg()]
+    id: Uuid,
+    ///ID of the run to show the output of. Defaults to the latest run.
+    #[arg(long)]
+    run: Option<Uuid>,
+    ///Keep printing new lines as they are received by CARL
+    #[arg(short, long)]
+    follow: bool,
+    ///Prefix each line with the time it was output by the executor
+    #[arg(short, long)]
+    timestamps: bool,
+}
+
+impl ExecutorLogsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: LogsOutputFormat) -> crate::Result<()> {
+        let executor_id = ExecutorId::from(self.id);
+        let mut run_id = self.run.map(ExecutorRunId::from);
+        let mut from_sequence_number = 0;
+
+        loop {
+            let log = carl.peers.get_executor_logs(executor_id, run_id, from_sequence_number).await
+                .map_err(|error| format!("Could not retrieve logs of executor <{executor_id}>.\n  {error}"))?;
+
+            //stay with the same run when following, even if a newer run is started in the meantime
+            run_id = Some(log.id);
+            if let Some(last_line) = log.lines.last() {
+                from_sequence_number = last_line.sequence_number + 1;
+            }
+
+            for line in &log.lines {
+                print_line(line, &output, self.timestamps);
+            }
+
+            //CARL returns long logs in pages, so continue right away, as long as lines are returned
+            if log.lines.is_empty() {
+                if !self.follow {
+                    break;
+                }
+                tokio::time::sleep(FOLLOW_INTERVAL).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn print_line(line: &ExecutorLogLine, output: &LogsOutputFormat, timestamps: bool) {
+    match output {
+        LogsOutputFormat::Text => {
+            let text = if timestamps {
+                let timestamp = DateTime::<Utc>::from(line.timestamp).to_rfc3339_opts(SecondsFormat::Millis, true);
+                format!("{timestamp} {}", line.text)
+            } else {
+                Clone::clone(&line.text)
+            };
+            match line.stream {
+                ExecutorLogStream::Stdout => println!("{text}"),
+                ExecutorLogStream::Stderr => eprintln!("{text}"),
+            }
+        }
+        LogsOutputFormat::Json => {
+            let json = serde_json::to_string(line).unwrap();
+            println!("{}", json);
+        }
+    }
+}
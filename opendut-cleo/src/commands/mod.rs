@@ -1,10 +1,17 @@
+pub mod apply;
 pub mod cluster_configuration;
 pub mod cluster_deployment;
 pub mod device;
+pub mod diagnose;
+pub mod logs;
 pub mod peer;
 pub mod network_interface;
+pub mod plugin;
 pub mod executor;
+pub mod reconcile;
+pub mod test_run;
 pub mod decode_setup_string;
 pub mod generate_setup_string;
 pub mod completions;
 pub mod setup;
+pub mod wait;
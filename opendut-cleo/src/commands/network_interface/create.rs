@@ -2,9 +2,10 @@ use uuid::Uuid;
 
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::PeerId;
-use opendut_types::util::net::{CanSamplePoint, NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
+use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
 
 use crate::{CreateOutputFormat, DescribeOutputFormat, NetworkInterfaceType};
+use crate::commands::network_interface::CanParameters;
 
 /// Create a network interface
 #[derive(clap::Parser)]
@@ -18,6 +19,8 @@ pub struct CreateNetworkInterfaceCli {
     ///Name of the network interface
     #[arg(long("name"))]
     interface_name: String,
+    #[command(flatten)]
+    can_parameters: CanParameters,
 }
 impl CreateNetworkInterfaceCli {
     pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
@@ -33,16 +36,15 @@ impl CreateNetworkInterfaceCli {
 
         let interface_name = NetworkInterfaceName::try_from(self.interface_name).map_err(|error| error.to_string())?;
 
-        // TODO: Properly implement CAN parameter configuration
         let interface_configuration = match self.interface_type {
-            NetworkInterfaceType::Ethernet => NetworkInterfaceConfiguration::Ethernet,
-            NetworkInterfaceType::Can => NetworkInterfaceConfiguration::Can {
-                bitrate: 500000,
-                sample_point: CanSamplePoint::try_from(0.7).unwrap(),
-                fd: true,
-                data_bitrate: 2000000,
-                data_sample_point: CanSamplePoint::try_from(0.7).unwrap(),
-            },
+            NetworkInterfaceType::Ethernet => {
+                if self.can_parameters.is_specified() {
+                    Err(String::from("CAN parameters can only be specified for network interfaces of type CAN."))?
+                }
+                NetworkInterfaceConfiguration::Ethernet
+            }
+            NetworkInterfaceType::Can => self.can_parameters.to_configuration(&NetworkInterfaceConfiguration::Ethernet)
+                .map_err(|error| format!("Could not create network interface '{}'.\n  {}", interface_name, error))?,
         };
 
         if peer_interface_names.contains(&interface_name) {
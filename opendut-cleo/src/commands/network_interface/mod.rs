@@ -1,2 +1,215 @@
 pub mod create;
 pub mod delete;
+pub mod update;
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use opendut_types::util::net::{CanSamplePoint, NetworkInterfaceConfiguration};
+
+#[derive(clap::Args, Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct CanParameters {
+    ///Preset for the CAN parameters, which can be refined with the individual parameters
+    #[arg(long, value_enum)]
+    #[serde(rename = "preset")]
+    can_preset: Option<CanPreset>,
+    ///CAN bitrate in bit/s
+    #[arg(long)]
+    bitrate: Option<u32>,
+    ///CAN sample point, between 0.0 and 0.999
+    #[arg(long)]
+    sample_point: Option<f32>,
+    ///Whether CAN FD is enabled
+    #[arg(long)]
+    fd: Option<bool>,
+    ///CAN FD data bitrate in bit/s
+    #[arg(long)]
+    data_bitrate: Option<u32>,
+    ///CAN FD data sample point, between 0.0 and 0.999
+    #[arg(long)]
+    data_sample_point: Option<f32>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+pub enum CanPreset {
+    ///Classical CAN with 125 kbit/s
+    #[value(name = "125k")]
+    #[serde(rename = "125k")]
+    Classic125k,
+    ///Classical CAN with 250 kbit/s
+    #[value(name = "250k")]
+    #[serde(rename = "250k")]
+    Classic250k,
+    ///Classical CAN with 500 kbit/s
+    #[value(name = "500k")]
+    #[serde(rename = "500k")]
+    Classic500k,
+    ///Classical CAN with 1 Mbit/s
+    #[value(name = "1m")]
+    #[serde(rename = "1m")]
+    Classic1m,
+    ///CAN FD with 500 kbit/s nominal and 2 Mbit/s data bitrate
+    #[default]
+    #[value(name = "500k-2m-fd")]
+    #[serde(rename = "500k-2m-fd")]
+    Fd500k2m,
+    ///CAN FD with 1 Mbit/s nominal and 5 Mbit/s data bitrate
+    #[value(name = "1m-5m-fd")]
+    #[serde(rename = "1m-5m-fd")]
+    Fd1m5m,
+}
+
+impl CanPreset {
+    pub fn configuration(self) -> NetworkInterfaceConfiguration {
+        let (bitrate, sample_point_times_1000, fd, data_bitrate, data_sample_point_times_1000): (u32, u32, bool, u32, u32) = match self {
+            CanPreset::Classic125k => (125_000, 875, false, 125_000, 875),
+            CanPreset::Classic250k => (250_000, 875, false, 250_000, 875),
+            CanPreset::Classic500k => (500_000, 875, false, 500_000, 875),
+            CanPreset::Classic1m => (1_000_000, 750, false, 1_000_000, 750),
+            CanPreset::Fd500k2m => (500_000, 700, true, 2_000_000, 700),
+            CanPreset::Fd1m5m => (1_000_000, 800, true, 5_000_000, 750),
+        };
+        NetworkInterfaceConfiguration::Can {
+            bitrate,
+            sample_point: CanSamplePoint::try_from(sample_point_times_1000).expect("Sample point of preset should be valid."),
+            fd,
+            data_bitrate,
+            data_sample_point: CanSamplePoint::try_from(data_sample_point_times_1000).expect("Sample point of preset should be valid."),
+        }
+    }
+}
+
+impl CanParameters {
+    pub fn is_specified(&self) -> bool {
+        self.can_preset.is_some()
+            || self.bitrate.is_some()
+            || self.sample_point.is_some()
+            || self.fd.is_some()
+            || self.data_bitrate.is_some()
+            || self.data_sample_point.is_some()
+    }
+
+    /// Builds a CAN configuration from the specified parameters.
+    /// Parameters, which are neither specified directly nor via a preset, are taken from `base`, if it is a CAN configuration, or else from the default preset.
+    pub fn to_configuration(&self, base: &NetworkInterfaceConfiguration) -> crate::Result<NetworkInterfaceConfiguration> {
+        let base = match (self.can_preset, base) {
+            (Some(preset), _) => preset.configuration(),
+            (None, NetworkInterfaceConfiguration::Can { .. }) => Clone::clone(base),
+            (None, NetworkInterfaceConfiguration::Ethernet) => CanPreset::default().configuration(),
+        };
+        let NetworkInterfaceConfiguration::Can { bitrate, sample_point, fd, data_bitrate, data_sample_point } = base else {
+            return Err(format!("Cannot derive CAN parameters from a configuration of type {base}."));
+        };
+
+        let bitrate = self.bitrate.unwrap_or(bitrate);
+        let sample_point = match self.sample_point {
+            Some(sample_point) => CanSamplePoint::try_from(sample_point).map_err(|error| error.to_string())?,
+            None => sample_point,
+        };
+        let fd = self.fd.unwrap_or(fd);
+
+        if bitrate == 0 {
+            Err(String::from("The CAN bitrate must be greater than 0."))?
+        }
+
+        if fd {
+            let data_bitrate = self.data_bitrate.unwrap_or(data_bitrate);
+            let data_sample_point = match self.data_sample_point {
+                Some(data_sample_point) => CanSamplePoint::try_from(data_sample_point).map_err(|error| error.to_string())?,
+                None => data_sample_point,
+            };
+            if data_bitrate < bitrate {
+                Err(format!("The CAN FD data bitrate ({data_bitrate}) must not be lower than the bitrate ({bitrate})."))?
+            }
+            Ok(NetworkInterfaceConfiguration::Can { bitrate, sample_point, fd, data_bitrate, data_sample_point })
+        } else {
+            if self.data_bitrate.is_some() || self.data_sample_point.is_some() {
+                Err(String::from("The data bitrate and data sample point can only be specified for CAN FD."))?
+            }
+            Ok(NetworkInterfaceConfiguration::Can {
+                bitrate,
+                sample_point: Clone::clone(&sample_point),
+                fd,
+                data_bitrate: bitrate,
+                data_sample_point: sample_point,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_use_default_preset_without_parameters() -> anyhow::Result<()> {
+        let configuration = CanParameters::default()
+            .to_configuration(&NetworkInterfaceConfiguration::Ethernet)
+            .map_err(anyhow::Error::msg)?;
+
+        assert_that!(configuration, eq(&NetworkInterfaceConfiguration::Can {
+            bitrate: 500_000,
+            sample_point: CanSamplePoint::try_from(0.7)?,
+            fd: true,
+            data_bitrate: 2_000_000,
+            data_sample_point: CanSamplePoint::try_from(0.7)?,
+        }));
+        Ok(())
+    }
+
+    #[test]
+    fn should_refine_preset_with_individual_parameters() -> anyhow::Result<()> {
+        let parameters = CanParameters {
+            can_preset: Some(CanPreset::Classic250k),
+            sample_point: Some(0.8),
+            ..Default::default()
+        };
+
+        let configuration = parameters.to_configuration(&NetworkInterfaceConfiguration::Ethernet)
+            .map_err(anyhow::Error::msg)?;
+
+        assert_that!(configuration, eq(&NetworkInterfaceConfiguration::Can {
+            bitrate: 250_000,
+            sample_point: CanSamplePoint::try_from(0.8)?,
+            fd: false,
+            data_bitrate: 250_000,
+            data_sample_point: CanSamplePoint::try_from(0.8)?,
+        }));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_data_parameters_for_classical_can() {
+        let parameters = CanParameters {
+            can_preset: Some(CanPreset::Classic500k),
+            data_bitrate: Some(2_000_000),
+            ..Default::default()
+        };
+
+        assert_that!(parameters.to_configuration(&NetworkInterfaceConfiguration::Ethernet), err(anything()));
+    }
+
+    #[test]
+    fn should_reject_data_bitrate_lower_than_bitrate() {
+        let parameters = CanParameters {
+            bitrate: Some(1_000_000),
+            data_bitrate: Some(500_000),
+            ..Default::default()
+        };
+
+        assert_that!(parameters.to_configuration(&NetworkInterfaceConfiguration::Ethernet), err(anything()));
+    }
+
+    #[test]
+    fn should_reject_invalid_sample_point() {
+        let parameters = CanParameters {
+            sample_point: Some(1.5),
+            ..Default::default()
+        };
+
+        assert_that!(parameters.to_configuration(&NetworkInterfaceConfiguration::Ethernet), err(anything()));
+    }
+}
@@ -0,0 +1,170 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceName};
+
+use crate::{CreateOutputFormat, DescribeOutputFormat, NetworkInterfaceType};
+use crate::commands::network_interface::CanParameters;
+
+/// Update a network interface of a peer
+#[derive(clap::Parser)]
+pub struct UpdateNetworkInterfaceCli {
+    ///ID of the peer the network interface belongs to
+    #[arg(long)]
+    peer_id: Uuid,
+    ///Current name of the network interface
+    #[arg(long("name"))]
+    interface_name: NetworkInterfaceName,
+    ///New name of the network interface
+    #[arg(long)]
+    new_name: Option<NetworkInterfaceName>,
+    ///New type of the network interface
+    #[arg(long("type"))]
+    interface_type: Option<NetworkInterfaceType>,
+    #[command(flatten)]
+    can_parameters: CanParameters,
+}
+
+impl UpdateNetworkInterfaceCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let peer_id = PeerId::from(self.peer_id);
+
+        let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
+            .map_err(|_| format!("Failed to get peer with ID <{}>.", peer_id))?;
+
+        let interface_name = Clone::clone(&self.interface_name);
+        self.apply_to(&mut peer_descriptor)
+            .map_err(|error| format!("Could not update network interface '{}'.\n  {}", interface_name, error))?;
+
+        carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
+            .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
+        let output_format = DescribeOutputFormat::from(output);
+        crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
+
+        Ok(())
+    }
+
+    fn apply_to(self, peer_descriptor: &mut PeerDescriptor) -> crate::Result<()> {
+        if let Some(new_name) = &self.new_name {
+            if peer_descriptor.network.interfaces.iter().any(|interface| &interface.name == new_name) {
+                Err(format!("Peer <{}> already has a network interface with name '{}'.", peer_descriptor.id, new_name))?
+            }
+        }
+
+        let interface = peer_descriptor.network.interfaces.iter_mut()
+            .find(|interface| interface.name == self.interface_name)
+            .ok_or(format!("Peer <{}> has no network interface with name '{}'.", peer_descriptor.id, self.interface_name))?;
+
+        let interface_type = match (&self.interface_type, &interface.configuration) {
+            (Some(interface_type), _) => interface_type.clone(),
+            (None, NetworkInterfaceConfiguration::Ethernet) => NetworkInterfaceType::Ethernet,
+            (None, NetworkInterfaceConfiguration::Can { .. }) => NetworkInterfaceType::Can,
+        };
+
+        interface.configuration = match interface_type {
+            NetworkInterfaceType::Ethernet => {
+                if self.can_parameters.is_specified() {
+                    Err(String::from("CAN parameters can only be specified for network interfaces of type CAN."))?
+                }
+                NetworkInterfaceConfiguration::Ethernet
+            }
+            NetworkInterfaceType::Can => self.can_parameters.to_configuration(&interface.configuration)?,
+        };
+
+        if let Some(new_name) = self.new_name {
+            interface.name = new_name;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+    use googletest::prelude::*;
+    use rstest::{fixture, rstest};
+
+    use opendut_types::peer::{PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::util::net::{CanSamplePoint, NetworkInterfaceDescriptor, NetworkInterfaceId};
+
+    use super::*;
+
+    #[fixture]
+    fn peer_descriptor() -> PeerDescriptor {
+        PeerDescriptor {
+            id: PeerId::random(),
+            name: PeerName::try_from("bench-1").unwrap(),
+            location: None,
+            network: PeerNetworkDescriptor {
+                interfaces: vec![
+                    NetworkInterfaceDescriptor {
+                        id: NetworkInterfaceId::random(),
+                        name: NetworkInterfaceName::try_from("eth0").unwrap(),
+                        configuration: NetworkInterfaceConfiguration::Ethernet,
+                    },
+                    NetworkInterfaceDescriptor {
+                        id: NetworkInterfaceId::random(),
+                        name: NetworkInterfaceName::try_from("vcan0").unwrap(),
+                        configuration: NetworkInterfaceConfiguration::Can {
+                            bitrate: 500000,
+                            sample_point: CanSamplePoint::try_from(0.7).unwrap(),
+                            fd: true,
+                            data_bitrate: 2000000,
+                            data_sample_point: CanSamplePoint::try_from(0.7).unwrap(),
+                        },
+                    },
+                ],
+                bridge_name: None,
+            },
+            topology: Default::default(),
+            executors: ExecutorDescriptors {
+                executors: vec![],
+            },
+        }
+    }
+
+    #[rstest]
+    fn should_update_single_can_parameter(mut peer_descriptor: PeerDescriptor) -> anyhow::Result<()> {
+        let cli = UpdateNetworkInterfaceCli::try_parse_from([
+            "update", "--peer-id", &peer_descriptor.id.to_string(), "--name", "vcan0", "--bitrate", "250000",
+        ])?;
+
+        cli.apply_to(&mut peer_descriptor).map_err(anyhow::Error::msg)?;
+
+        assert_that!(peer_descriptor.network.interfaces[1].configuration, eq(&NetworkInterfaceConfiguration::Can {
+            bitrate: 250000,
+            sample_point: CanSamplePoint::try_from(0.7)?,
+            fd: true,
+            data_bitrate: 2000000,
+            data_sample_point: CanSamplePoint::try_from(0.7)?,
+        }));
+        Ok(())
+    }
+
+    #[rstest]
+    fn should_rename_interface(mut peer_descriptor: PeerDescriptor) -> anyhow::Result<()> {
+        let interface_id = peer_descriptor.network.interfaces[0].id;
+        let cli = UpdateNetworkInterfaceCli::try_parse_from([
+            "update", "--peer-id", &peer_descriptor.id.to_string(), "--name", "eth0", "--new-name", "eth1",
+        ])?;
+
+        cli.apply_to(&mut peer_descriptor).map_err(anyhow::Error::msg)?;
+
+        assert_that!(peer_descriptor.network.interfaces[0].id, eq(interface_id));
+        assert_that!(peer_descriptor.network.interfaces[0].name, eq(&NetworkInterfaceName::try_from("eth1")?));
+        Ok(())
+    }
+
+    #[rstest]
+    fn should_reject_can_parameters_for_ethernet_interface(mut peer_descriptor: PeerDescriptor) -> anyhow::Result<()> {
+        let cli = UpdateNetworkInterfaceCli::try_parse_from([
+            "update", "--peer-id", &peer_descriptor.id.to_string(), "--name", "eth0", "--fd", "false",
+        ])?;
+
+        assert_that!(cli.apply_to(&mut peer_descriptor), err(anything()));
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ use uuid::Uuid;
 
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor};
 use crate::DescribeOutputFormat;
 
 /// Describe a peer
@@ -35,6 +36,17 @@ pub fn render_peer_descriptor(peer_descriptor: PeerDescriptor, output: DescribeO
         .map(|device| device.name.value())
         .collect::<Vec<_>>()
         .join(", ");
+    let network_interfaces = if peer_descriptor.network.interfaces.is_empty() {
+        String::from(" none")
+    } else {
+        peer_descriptor
+            .network
+            .interfaces
+            .iter()
+            .map(render_network_interface)
+            .collect::<Vec<_>>()
+            .join("")
+    };
     let text = match output {
         DescribeOutputFormat::Text => {
             format!(
@@ -42,10 +54,11 @@ pub fn render_peer_descriptor(peer_descriptor: PeerDescriptor, output: DescribeO
                     "
                 Peer: {}
                   Id: {}
-                  Devices: [{}]\
+                  Devices: [{}]
+                  Network Interfaces:{}\
             "
                 ),
-                peer_descriptor.name, peer_descriptor.id, peer_devices
+                peer_descriptor.name, peer_descriptor.id, peer_devices, network_interfaces
             )
         }
         DescribeOutputFormat::Json => serde_json::to_string(&peer_descriptor).unwrap(),
@@ -55,3 +68,21 @@ pub fn render_peer_descriptor(peer_descriptor: PeerDescriptor, output: DescribeO
     };
     println!("{text}");
 }
+
+fn render_network_interface(interface: &NetworkInterfaceDescriptor) -> String {
+    match &interface.configuration {
+        NetworkInterfaceConfiguration::Ethernet => {
+            format!("\n    {}: Ethernet", interface.name)
+        }
+        NetworkInterfaceConfiguration::Can { bitrate, sample_point, fd, data_bitrate, data_sample_point } => {
+            let mut text = format!("\n    {}: CAN{}", interface.name, if *fd { " FD" } else { "" });
+            text.push_str(&format!("\n      Bitrate: {bitrate} bit/s"));
+            text.push_str(&format!("\n      Sample Point: {sample_point}"));
+            if *fd {
+                text.push_str(&format!("\n      Data Bitrate: {data_bitrate} bit/s"));
+                text.push_str(&format!("\n      Data Sample Point: {data_sample_point}"));
+            }
+            text
+        }
+    }
+}
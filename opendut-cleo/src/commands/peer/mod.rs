@@ -2,6 +2,7 @@ pub mod list;
 pub mod describe;
 pub mod create;
 pub mod delete;
+pub mod update;
 
 
 
@@ -0,0 +1,136 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName};
+use opendut_types::util::net::NetworkInterfaceName;
+
+use crate::{CreateOutputFormat, DescribeOutputFormat};
+
+/// Update a peer
+#[derive(clap::Parser)]
+pub struct UpdatePeerCli {
+    ///PeerID
+    #[arg()]
+    id: Uuid,
+    ///New name of peer
+    #[arg(short, long)]
+    name: Option<String>,
+    ///New location of peer
+    #[arg(long)]
+    location: Option<String>,
+    ///Remove the location of peer
+    #[arg(long, conflicts_with = "location")]
+    clear_location: bool,
+    ///Custom bridge name;
+    /// Please note bridges with custom names are not automatically removed and need to be removed manually.
+    /// Not removing the bridge could lead to network traffic being misdirected!
+    #[arg(long)]
+    bridge_name: Option<String>,
+    ///Use the default bridge name instead of a custom one
+    #[arg(long, conflicts_with = "bridge_name")]
+    clear_bridge_name: bool,
+}
+
+impl UpdatePeerCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let peer_id = PeerId::from(self.id);
+
+        let mut peer_descriptor = carl.peers.get_peer_descriptor(peer_id).await
+            .map_err(|_| format!("Failed to get peer with ID <{}>.", peer_id))?;
+
+        self.apply_to(&mut peer_descriptor)
+            .map_err(|error| format!("Could not update peer.\n  {}", error))?;
+
+        carl.peers.store_peer_descriptor(Clone::clone(&peer_descriptor)).await
+            .map_err(|error| format!("Failed to update peer <{}>.\n  {}", peer_id, error))?;
+        let output_format = DescribeOutputFormat::from(output);
+        crate::commands::peer::describe::render_peer_descriptor(peer_descriptor, output_format);
+
+        Ok(())
+    }
+
+    fn apply_to(self, peer_descriptor: &mut PeerDescriptor) -> crate::Result<()> {
+        if let Some(name) = self.name {
+            peer_descriptor.name = PeerName::try_from(name)
+                .map_err(|error| error.to_string())?;
+        }
+
+        if self.clear_location {
+            peer_descriptor.location = None;
+        } else if let Some(location) = self.location {
+            peer_descriptor.location = Some(PeerLocation::try_from(location)
+                .map_err(|error| error.to_string())?);
+        }
+
+        if self.clear_bridge_name {
+            peer_descriptor.network.bridge_name = None;
+        } else if let Some(bridge_name) = self.bridge_name {
+            peer_descriptor.network.bridge_name = Some(NetworkInterfaceName::try_from(bridge_name)
+                .map_err(|error| error.to_string())?);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clap::Parser;
+    use googletest::prelude::*;
+    use rstest::{fixture, rstest};
+
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::peer::PeerNetworkDescriptor;
+
+    use super::*;
+
+    #[fixture]
+    fn peer_descriptor() -> PeerDescriptor {
+        PeerDescriptor {
+            id: PeerId::random(),
+            name: PeerName::try_from("bench-1").unwrap(),
+            location: Some(PeerLocation::try_from("Ulm").unwrap()),
+            network: PeerNetworkDescriptor {
+                interfaces: vec![],
+                bridge_name: None,
+            },
+            topology: Default::default(),
+            executors: ExecutorDescriptors {
+                executors: vec![],
+            },
+        }
+    }
+
+    #[rstest]
+    fn should_only_update_specified_fields(peer_descriptor: PeerDescriptor) -> anyhow::Result<()> {
+        let cli = UpdatePeerCli::try_parse_from(["update", &peer_descriptor.id.to_string(), "--name", "bench-2"])?;
+
+        let mut updated = Clone::clone(&peer_descriptor);
+        cli.apply_to(&mut updated).map_err(anyhow::Error::msg)?;
+
+        assert_that!(updated.name, eq(&PeerName::try_from("bench-2")?));
+        assert_that!(updated.location, eq(&peer_descriptor.location));
+        assert_that!(updated.network, eq(&peer_descriptor.network));
+        Ok(())
+    }
+
+    #[rstest]
+    fn should_clear_location(peer_descriptor: PeerDescriptor) -> anyhow::Result<()> {
+        let cli = UpdatePeerCli::try_parse_from(["update", &peer_descriptor.id.to_string(), "--clear-location"])?;
+
+        let mut updated = Clone::clone(&peer_descriptor);
+        cli.apply_to(&mut updated).map_err(anyhow::Error::msg)?;
+
+        assert_that!(updated.location, none());
+        Ok(())
+    }
+
+    #[rstest]
+    fn should_reject_invalid_name(peer_descriptor: PeerDescriptor) -> anyhow::Result<()> {
+        let cli = UpdatePeerCli::try_parse_from(["update", &peer_descriptor.id.to_string(), "--name", ""])?;
+
+        let mut updated = Clone::clone(&peer_descriptor);
+        assert_that!(cli.apply_to(&mut updated), err(anything()));
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::PeerId;
+use opendut_types::plugin::PluginId;
+
+use crate::CreateOutputFormat;
+
+/// Assign plugins from the plugin registry to a peer, replacing the previously assigned plugins
+#[derive(clap::Parser)]
+pub struct AssignPeerPluginsCli {
+    ///PeerID
+    #[arg()]
+    peer_id: Uuid,
+    ///PluginIDs in the order in which EDGAR runs the plugins; pass none to unassign all plugins
+    #[arg(long, num_args = 0..)]
+    plugin_ids: Vec<Uuid>,
+}
+
+impl AssignPeerPluginsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let peer_id = PeerId::from(self.peer_id);
+        let plugin_ids = self.plugin_ids.into_iter()
+            .map(PluginId::from)
+            .collect::<Vec<_>>();
+
+        carl.plugins.assign_plugins(peer_id, plugin_ids).await
+            .map_err(|error| format!("Failed to assign plugins to peer <{peer_id}>.\n  {error}"))?;
+
+        let plugins = carl.plugins.list_plugins(Some(peer_id)).await
+            .map_err(|error| format!("Failed to list plugins assigned to peer <{peer_id}>.\n  {error}"))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Assigned {} plugin(s) to peer <{peer_id}>:", plugins.len());
+                for plugin in plugins {
+                    println!("  {} {} ({}) <{}>", plugin.name, plugin.version, plugin.kind, plugin.id);
+                }
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&plugins).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&plugins).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use console::Style;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::plugin::PluginKind;
+
+use crate::{CreateOutputFormat, PluginKindType};
+
+/// Upload a plugin to the plugin registry of CARL, which signs it for distribution to peers
+#[derive(clap::Parser)]
+pub struct CreatePluginCli {
+    ///Path to the WebAssembly component of the plugin
+    #[arg(long)]
+    file: PathBuf,
+    ///Name of the plugin, defaults to the file name without extension
+    #[arg(short, long)]
+    name: Option<String>,
+    ///Version of the plugin; a name may only be uploaded once per version
+    #[arg(long)]
+    version: String,
+    ///Whether the plugin contributes tasks to `edgar setup` or is loaded by the EDGAR service
+    #[arg(long)]
+    kind: PluginKindType,
+    ///Path to the capability manifest of the plugin in TOML; no capabilities are granted without it
+    #[arg(long)]
+    manifest: Option<PathBuf>,
+}
+
+impl CreatePluginCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let binary = std::fs::read(&self.file)
+            .map_err(|error| format!("Could not read plugin from '{}'.\n  {}", self.file.display(), error))?;
+
+        let manifest = self.manifest
+            .map(|manifest| std::fs::read_to_string(&manifest)
+                .map_err(|error| format!("Could not read capability manifest from '{}'.\n  {}", manifest.display(), error))
+            )
+            .transpose()?;
+
+        let name = match self.name {
+            Some(name) => name,
+            None => self.file.file_stem()
+                .map(|name| name.to_string_lossy().into_owned())
+                .ok_or_else(|| format!("Could not determine plugin name from '{}'. Specify it with --name.", self.file.display()))?,
+        };
+
+        let kind = match self.kind {
+            PluginKindType::Setup => PluginKind::Setup,
+            PluginKindType::Service => PluginKind::Service,
+        };
+
+        let plugin = carl.plugins.store_plugin(name, self.version, kind, binary, manifest).await
+            .map_err(|error| format!("Failed to upload plugin.\n  {error}"))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                let bold = Style::new().bold();
+                println!(
+                    "Uploaded the plugin '{}' in version '{}' with the ID: <{}>",
+                    plugin.name,
+                    plugin.version,
+                    bold.apply_to(plugin.id)
+                );
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&plugin).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&plugin).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
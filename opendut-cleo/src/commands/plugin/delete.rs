@@ -0,0 +1,24 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::plugin::PluginId;
+
+/// Delete a plugin from the plugin registry and unassign it from all peers
+#[derive(clap::Parser)]
+pub struct DeletePluginCli {
+    ///PluginID
+    #[arg()]
+    id: Uuid,
+}
+
+impl DeletePluginCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = PluginId::from(self.id);
+
+        let plugin = carl.plugins.delete_plugin(id).await
+            .map_err(|error| format!("Failed to delete plugin with the id '{}'.\n  {}", id, error))?;
+        println!("Deleted plugin '{}' in version '{}' with the PluginID: {}", plugin.name, plugin.version, id);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use cli_table::{print_stdout, Table, WithTitle};
+use serde::Serialize;
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::PeerId;
+use opendut_types::plugin::{PluginDescriptor, PluginId, PluginKind};
+
+use crate::commands::test_run::list::render_time;
+use crate::ListOutputFormat;
+
+/// List the plugins in the plugin registry
+#[derive(clap::Parser)]
+pub struct ListPluginsCli {
+    ///Only list the plugins assigned to the peer with this PeerID, in the order in which they are run
+    #[arg(long)]
+    peer: Option<Uuid>,
+}
+
+#[derive(Table, Debug, Serialize)]
+struct PluginTable {
+    #[table(title = "PluginID")]
+    id: PluginId,
+    #[table(title = "Name")]
+    name: String,
+    #[table(title = "Version")]
+    version: String,
+    #[table(title = "Kind")]
+    kind: PluginKind,
+    #[table(title = "SHA-256")]
+    checksum: String,
+    #[table(title = "Uploaded By")]
+    uploaded_by: String,
+    #[table(title = "Uploaded")]
+    uploaded_at: String,
+}
+
+impl ListPluginsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let peer_id = self.peer.map(PeerId::from);
+
+        let plugins = carl.plugins.list_plugins(peer_id).await
+            .map_err(|error| format!("Could not list plugins.\n  {}", error))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let plugins_table = plugins.into_iter()
+                    .map(render_plugin)
+                    .collect::<Vec<_>>();
+                print_stdout(plugins_table.with_title())
+                    .expect("List of plugins should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&plugins).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&plugins).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn render_plugin(plugin: PluginDescriptor) -> PluginTable {
+    PluginTable {
+        id: plugin.id,
+        name: plugin.name,
+        version: plugin.version,
+        kind: plugin.kind,
+        checksum: plugin.checksum.iter().map(|byte| format!("{byte:02x}")).collect(),
+        uploaded_by: plugin.uploaded_by.unwrap_or_default(),
+        uploaded_at: render_time(plugin.uploaded_at),
+    }
+}
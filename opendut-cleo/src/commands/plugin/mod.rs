@@ -0,0 +1,4 @@
+pub mod assign;
+pub mod create;
+pub mod delete;
+pub mod list;
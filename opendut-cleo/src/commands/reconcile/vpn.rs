@@ -0,0 +1,93 @@
+use cli_table::{print_stdout, Table, WithTitle};
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::vpn::reconciliation::{VpnDrift, VpnDriftEntry, VpnDriftResolution};
+
+use crate::ListOutputFormat;
+
+/// Compare the deployed clusters and peers with the resources in the VPN service and remove orphaned resources
+#[derive(clap::Parser)]
+pub struct ReconcileVpnCli {
+    ///Only report the differences, without removing orphaned resources
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Table, Debug)]
+struct VpnDriftTable {
+    #[table(title = "Drift")]
+    drift: &'static str,
+    #[table(title = "Resource")]
+    resource: String,
+    #[table(title = "Resolution")]
+    resolution: String,
+}
+
+impl ReconcileVpnCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+
+        let report = carl.cluster.reconcile_vpn(self.dry_run).await
+            .map_err(|error| format!("Could not reconcile the VPN.\n  {error}"))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                if report.is_in_sync() {
+                    println!("The resources of the VPN service are in sync with CARL.");
+                } else {
+                    let table = report.drifts.iter()
+                        .map(to_table_row)
+                        .collect::<Vec<_>>();
+
+                    print_stdout(table.with_title())
+                        .expect("VPN drifts should be printable as table.");
+
+                    if report.dry_run && report.drifts.iter().any(|entry| entry.drift.is_orphan()) {
+                        println!("This was a dry run. Run without '--dry-run' to remove the orphaned resources.");
+                    }
+                }
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&report).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&report).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        let failed_removals = report.drifts.iter()
+            .filter(|entry| matches!(entry.resolution, VpnDriftResolution::RemovalFailed { .. }))
+            .count();
+        if failed_removals == 0 {
+            Ok(())
+        } else {
+            Err(format!("Failed to remove {failed_removals} orphaned resource(s) from the VPN service."))
+        }
+    }
+}
+
+fn to_table_row(entry: &VpnDriftEntry) -> VpnDriftTable {
+    let (drift, resource) = match entry.drift {
+        VpnDrift::OrphanedCluster { cluster_id } => ("Orphaned", format!("Cluster <{cluster_id}>")),
+        VpnDrift::MissingCluster { cluster_id } => ("Missing", format!("Cluster <{cluster_id}>")),
+        VpnDrift::OrphanedPeer { peer_id } => ("Orphaned", format!("Peer <{peer_id}>")),
+        VpnDrift::MissingPeer { peer_id } => ("Missing", format!("Peer <{peer_id}>")),
+    };
+
+    let resolution = match &entry.resolution {
+        VpnDriftResolution::Reported => match entry.drift {
+            VpnDrift::OrphanedCluster { .. } | VpnDrift::OrphanedPeer { .. } => String::from("Not removed (dry run)"),
+            VpnDrift::MissingCluster { .. } => String::from("Deploy the cluster again to recreate it"),
+            VpnDrift::MissingPeer { .. } => String::from("Generate a new PeerSetup to recreate it"),
+        },
+        VpnDriftResolution::Removed => String::from("Removed"),
+        VpnDriftResolution::RemovalFailed { cause } => format!("Removal failed: {cause}"),
+    };
+
+    VpnDriftTable {
+        drift,
+        resource,
+        resolution,
+    }
+}
@@ -0,0 +1,67 @@
+use indoc::indoc;
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::executor::log::ExecutorRunId;
+use opendut_types::peer::executor::test_run::TestRun;
+
+use crate::commands::test_run::list::render_time;
+use crate::DescribeOutputFormat;
+
+/// Describe a run of an executor
+#[derive(clap::Parser)]
+pub struct DescribeTestRunCli {
+    ///RunID
+    #[arg()]
+    id: Uuid,
+}
+
+impl DescribeTestRunCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: DescribeOutputFormat) -> crate::Result<()> {
+        let run_id = ExecutorRunId::from(self.id);
+
+        let test_run = carl.peers.get_test_run(run_id).await
+            .map_err(|error| format!("Failed to retrieve test run <{run_id}>.\n  {error}"))?;
+
+        render_test_run(test_run, output);
+        Ok(())
+    }
+}
+
+fn render_test_run(test_run: TestRun, output: DescribeOutputFormat) {
+    let optional = |value: Option<String>| value.unwrap_or_else(|| String::from("-"));
+
+    let text = match output {
+        DescribeOutputFormat::Text => {
+            format!(
+                indoc!("
+                    Test Run: {}
+                      Status: {}
+                      Executor: {}
+                      Peer: {}
+                      Cluster: {}
+                      Triggered By: {}
+                      Created: {}
+                      Started: {}
+                      Finished: {}
+                      Exit Code: {}
+                      Results: {}\
+                "),
+                test_run.id,
+                test_run.status,
+                test_run.executor_id,
+                test_run.peer_id,
+                optional(test_run.cluster_id.map(|cluster_id| cluster_id.to_string())),
+                optional(test_run.triggered_by),
+                render_time(test_run.created_at),
+                optional(test_run.started_at.map(render_time)),
+                optional(test_run.finished_at.map(render_time)),
+                optional(test_run.exit_code.map(|exit_code| exit_code.to_string())),
+                optional(test_run.results_location.map(|url| url.to_string())),
+            )
+        }
+        DescribeOutputFormat::Json => serde_json::to_string(&test_run).unwrap(),
+        DescribeOutputFormat::PrettyJson => serde_json::to_string_pretty(&test_run).unwrap(),
+    };
+    println!("{text}");
+}
@@ -0,0 +1,108 @@
+use cli_table::{print_stdout, Table, WithTitle};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::executor::log::ExecutorRunId;
+use opendut_types::peer::executor::test_run::{TestRun, TestRunStatus};
+use opendut_types::peer::executor::ExecutorId;
+
+use crate::ListOutputFormat;
+
+/// List all runs of executors, newest first
+#[derive(clap::Parser)]
+pub struct ListTestRunsCli {
+    ///Only list runs within the cluster with this ClusterID
+    #[arg(long)]
+    cluster: Option<Uuid>,
+    ///Only list runs of the executor with this ExecutorID
+    #[arg(long)]
+    executor: Option<Uuid>,
+}
+
+#[derive(Table, Debug, Serialize)]
+struct TestRunTable {
+    #[table(title = "RunID")]
+    id: ExecutorRunId,
+    #[table(title = "ExecutorID")]
+    executor_id: ExecutorId,
+    #[table(title = "Status")]
+    status: TestRunStatus,
+    #[table(title = "Exit Code")]
+    exit_code: String,
+    #[table(title = "Started")]
+    started_at: String,
+    #[table(title = "Finished")]
+    finished_at: String,
+}
+
+impl ListTestRunsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let cluster_id = self.cluster.map(ClusterId::from);
+        let executor_id = self.executor.map(ExecutorId::from);
+
+        let test_runs = carl.peers.list_test_runs(cluster_id, executor_id).await
+            .map_err(|error| format!("Could not list test runs.\n  {}", error))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let test_runs_table = test_runs.into_iter()
+                    .map(render_test_run)
+                    .collect::<Vec<_>>();
+                print_stdout(test_runs_table.with_title())
+                    .expect("List of test runs should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&test_runs).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&test_runs).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn render_test_run(test_run: TestRun) -> TestRunTable {
+    TestRunTable {
+        id: test_run.id,
+        executor_id: test_run.executor_id,
+        status: test_run.status,
+        exit_code: test_run.exit_code.map(|exit_code| exit_code.to_string()).unwrap_or_default(),
+        started_at: test_run.started_at.map(render_time).unwrap_or_default(),
+        finished_at: test_run.finished_at.map(render_time).unwrap_or_default(),
+    }
+}
+
+pub(crate) fn render_time(time: std::time::SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use opendut_types::peer::PeerId;
+
+    use super::*;
+
+    #[test]
+    fn should_leave_the_times_of_a_pending_test_run_empty() {
+        let test_run = TestRun::new(ExecutorRunId::random(), ExecutorId::random(), PeerId::random(), None, None);
+
+        assert_that!(
+            render_test_run(test_run.clone()),
+            matches_pattern!(TestRunTable {
+                id: eq(&test_run.id),
+                status: eq(&TestRunStatus::Pending),
+                exit_code: eq(""),
+                started_at: eq(""),
+                finished_at: eq(""),
+            })
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use std::process::ExitCode;
+
+use clap::ValueEnum;
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::ClusterId;
+use opendut_types::cluster::state::{ClusterState, DeployedClusterState};
+use opendut_types::ShortName;
+
+use crate::commands::wait::WaitOptions;
+
+/// Wait until a cluster reaches the specified state
+#[derive(clap::Parser)]
+pub struct WaitClusterCli {
+    ///ClusterID
+    #[arg()]
+    id: Uuid,
+    ///State to wait for
+    #[arg(long, value_enum)]
+    state: ClusterStateCondition,
+    #[command(flatten)]
+    options: WaitOptions,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClusterStateCondition {
+    ///Cluster has no deployment
+    Undeployed,
+    ///Cluster is being deployed
+    Deploying,
+    ///Cluster is deployed and all peers are members of the cluster, same as "healthy"
+    Deployed,
+    ///Cluster is deployed and all peers are members of the cluster
+    Healthy,
+    ///Cluster is deployed, but not all peers are members of the cluster
+    Unhealthy,
+}
+
+impl ClusterStateCondition {
+    fn is_satisfied_by(&self, state: &ClusterState) -> bool {
+        matches!((self, state),
+            (ClusterStateCondition::Undeployed, ClusterState::Undeployed)
+            | (ClusterStateCondition::Deploying, ClusterState::Deploying)
+            | (ClusterStateCondition::Deployed | ClusterStateCondition::Healthy, ClusterState::Deployed(DeployedClusterState::Healthy))
+            | (ClusterStateCondition::Unhealthy, ClusterState::Deployed(DeployedClusterState::Unhealthy))
+        )
+    }
+}
+
+impl WaitClusterCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<ExitCode> {
+        let cluster_id = ClusterId::from(self.id);
+        let condition = self.state;
+
+        super::wait_for_state(
+            self.options,
+            || {
+                let mut cluster = Clone::clone(&carl.cluster);
+                async move {
+                    cluster.get_cluster_state(cluster_id).await
+                        .map_err(|error| format!("Could not get state of cluster <{cluster_id}>.\n  {error}"))
+                }
+            },
+            |state| condition.is_satisfied_by(state),
+            |state| format!("Cluster <{cluster_id}> is in state '{}'.", state.short_name()),
+        ).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(ClusterStateCondition::Undeployed, ClusterState::Undeployed, true)]
+    #[case(ClusterStateCondition::Deployed, ClusterState::Deploying, false)]
+    #[case(ClusterStateCondition::Deployed, ClusterState::Deployed(DeployedClusterState::Unhealthy), false)]
+    #[case(ClusterStateCondition::Deployed, ClusterState::Deployed(DeployedClusterState::Healthy), true)]
+    #[case(ClusterStateCondition::Unhealthy, ClusterState::Deployed(DeployedClusterState::Unhealthy), true)]
+    #[case(ClusterStateCondition::Healthy, ClusterState::Deployed(DeployedClusterState::Unhealthy), false)]
+    #[case(ClusterStateCondition::Healthy, ClusterState::Deployed(DeployedClusterState::Healthy), true)]
+    fn should_check_whether_cluster_state_satisfies_condition(#[case] condition: ClusterStateCondition, #[case] state: ClusterState, #[case] expected: bool) {
+        assert_that!(condition.is_satisfied_by(&state), eq(expected));
+    }
+}
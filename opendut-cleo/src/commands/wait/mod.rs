@@ -0,0 +1,74 @@
+pub mod cluster;
+pub mod peer;
+
+use std::future::Future;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use console::Style;
+
+/// Exit code, when the awaited state was not reached within the timeout.
+pub const TIMEOUT_EXIT_CODE: u8 = 2;
+
+#[derive(clap::Args, Clone)]
+pub struct WaitOptions {
+    ///Maximum time to wait, e.g. 30s, 2m or 1h
+    #[arg(long, default_value = "60s")]
+    timeout: crate::parse::duration::ParseableDuration,
+    ///Time between two state queries, e.g. 500ms or 2s. Must be greater than zero.
+    #[arg(long, default_value = "1s")]
+    interval: crate::parse::duration::ParseableNonZeroDuration,
+}
+
+/// Repeatedly queries the state via `query_state` until `is_reached` returns true or the timeout expires.
+/// Every change of the state is printed, so the progress can be followed.
+async fn wait_for_state<State, Query, QueryFuture>(
+    options: WaitOptions,
+    mut query_state: Query,
+    is_reached: impl Fn(&State) -> bool,
+    describe: impl Fn(&State) -> String,
+) -> crate::Result<ExitCode>
+where
+    State: PartialEq,
+    Query: FnMut() -> QueryFuture,
+    QueryFuture: Future<Output=crate::Result<State>>,
+{
+    let WaitOptions { timeout, interval } = options;
+
+    let polling = async {
+        let mut last_state: Option<State> = None;
+        loop {
+            let state = query_state().await?;
+
+            if last_state.as_ref() != Some(&state) {
+                println!("{}", describe(&state));
+            }
+            if is_reached(&state) {
+                return Ok::<_, crate::Error>(());
+            }
+            last_state = Some(state);
+
+            tokio::time::sleep(interval.0).await;
+        }
+    };
+
+    match tokio::time::timeout(timeout.0, polling).await {
+        Ok(result) => {
+            result?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(_) => {
+            let red = Style::new().red();
+            eprintln!("{}", red.apply_to(format!("Timed out after {}.", format_duration(timeout.0))));
+            Ok(ExitCode::from(TIMEOUT_EXIT_CODE))
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration.subsec_millis() == 0 {
+        format!("{}s", duration.as_secs())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
@@ -0,0 +1,104 @@
+use std::process::ExitCode;
+
+use clap::ValueEnum;
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::PeerId;
+use opendut_types::peer::state::{PeerBlockedState, PeerState, PeerUpState};
+use opendut_types::ShortName;
+
+use crate::commands::wait::WaitOptions;
+
+/// Wait until a peer reaches the specified state
+#[derive(clap::Parser)]
+pub struct WaitPeerCli {
+    ///PeerID
+    #[arg()]
+    id: Uuid,
+    ///State to wait for
+    #[arg(long, value_enum)]
+    state: PeerStateCondition,
+    #[command(flatten)]
+    options: WaitOptions,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeerStateCondition {
+    ///Peer is not connected to CARL
+    Down,
+    ///Peer is connected to CARL, regardless of whether it is in use
+    Up,
+    ///Peer is connected to CARL and not in use by a cluster
+    Available,
+    ///Peer is connected to CARL and in use by a cluster
+    Blocked,
+    ///Peer is being deployed into a cluster
+    Deploying,
+    ///Peer is member of a deployed cluster
+    Member,
+    ///Peer is being undeployed from a cluster
+    Undeploying,
+}
+
+impl PeerStateCondition {
+    fn is_satisfied_by(&self, state: &PeerState) -> bool {
+        matches!((self, state),
+            (PeerStateCondition::Down, PeerState::Down)
+            | (PeerStateCondition::Up, PeerState::Up { .. })
+            | (PeerStateCondition::Available, PeerState::Up { inner: PeerUpState::Available, .. })
+            | (PeerStateCondition::Blocked, PeerState::Up { inner: PeerUpState::Blocked(_), .. })
+            | (PeerStateCondition::Deploying, PeerState::Up { inner: PeerUpState::Blocked(PeerBlockedState::Deploying), .. })
+            | (PeerStateCondition::Member, PeerState::Up { inner: PeerUpState::Blocked(PeerBlockedState::Member), .. })
+            | (PeerStateCondition::Undeploying, PeerState::Up { inner: PeerUpState::Blocked(PeerBlockedState::Undeploying), .. })
+        )
+    }
+}
+
+impl WaitPeerCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<ExitCode> {
+        let peer_id = PeerId::from(self.id);
+        let condition = self.state;
+
+        super::wait_for_state(
+            self.options,
+            || {
+                let mut peers = Clone::clone(&carl.peers);
+                async move {
+                    peers.get_peer_state(peer_id).await
+                        .map_err(|error| format!("Could not get state of peer <{peer_id}>.\n  {error}"))
+                }
+            },
+            |state| condition.is_satisfied_by(state),
+            |state| format!("Peer <{peer_id}> is in state '{}'.", state.short_name()),
+        ).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+    use std::str::FromStr;
+
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+
+    fn up(inner: PeerUpState) -> PeerState {
+        PeerState::Up { inner, remote_host: IpAddr::from_str("127.0.0.1").unwrap() }
+    }
+
+    #[rstest]
+    #[case(PeerStateCondition::Down, PeerState::Down, true)]
+    #[case(PeerStateCondition::Up, PeerState::Down, false)]
+    #[case(PeerStateCondition::Up, up(PeerUpState::Blocked(PeerBlockedState::Member)), true)]
+    #[case(PeerStateCondition::Available, up(PeerUpState::Available), true)]
+    #[case(PeerStateCondition::Available, up(PeerUpState::Blocked(PeerBlockedState::Deploying)), false)]
+    #[case(PeerStateCondition::Blocked, up(PeerUpState::Blocked(PeerBlockedState::Undeploying)), true)]
+    #[case(PeerStateCondition::Member, up(PeerUpState::Blocked(PeerBlockedState::Member)), true)]
+    #[case(PeerStateCondition::Member, up(PeerUpState::Available), false)]
+    fn should_check_whether_peer_state_satisfies_condition(#[case] condition: PeerStateCondition, #[case] state: PeerState, #[case] expected: bool) {
+        assert_that!(condition.is_satisfied_by(&state), eq(expected));
+    }
+}
@@ -39,10 +39,13 @@ enum Commands {
         #[arg(value_enum, short, long, default_value_t=ListOutputFormat::Table)]
         output: ListOutputFormat,
     },
-    ///Create openDuT resource from configuration file
+    ///Create or update openDuT resources from configuration file
+    #[command(args_conflicts_with_subcommands = true)]
     Apply {
         #[command(subcommand)]
-        resource: ApplyResource,
+        resource: Option<ApplyResource>,
+        #[command(flatten)]
+        manifest: commands::apply::ApplyManifestCli,
         ///Text, JSON or prettified JSON as output format
         #[arg(value_enum, short, long, default_value_t=CreateOutputFormat::Text)]
         output: CreateOutputFormat,
@@ -55,6 +58,14 @@ enum Commands {
         #[arg(value_enum, short, long, default_value_t=CreateOutputFormat::Text)]
         output: CreateOutputFormat,
     },
+    ///Update openDuT resource
+    Update {
+        #[command(subcommand)]
+        resource: Box<UpdateResource>,
+        ///Text, JSON or prettified JSON as output format
+        #[arg(value_enum, short, long, default_value_t=CreateOutputFormat::Text)]
+        output: CreateOutputFormat,
+    },
     GenerateSetupString(commands::generate_setup_string::GenerateSetupStringCli),
     DecodeSetupString(commands::decode_setup_string::DecodeSetupStringCli),
     ///Describe openDuT resource
@@ -81,6 +92,63 @@ enum Commands {
         #[command(subcommand)]
         resource: DeleteResource,
     },
+    ///Run diagnostics on an openDuT resource
+    Diagnose {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: DiagnoseResource,
+        ///JSON, prettified JSON or table as output format
+        #[arg(value_enum, short, long, default_value_t=ListOutputFormat::Table)]
+        output: ListOutputFormat,
+    },
+    ///Reconcile the state of CARL with an external service
+    Reconcile {
+        ///Name of the external service
+        #[command(subcommand)]
+        resource: ReconcileResource,
+        ///JSON, prettified JSON or table as output format
+        #[arg(value_enum, short, long, default_value_t=ListOutputFormat::Table)]
+        output: ListOutputFormat,
+    },
+    ///Show the output of an openDuT resource
+    Logs {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: LogsResource,
+        ///Text or JSON lines as output format
+        #[arg(value_enum, short, long, default_value_t=LogsOutputFormat::Text)]
+        output: LogsOutputFormat,
+    },
+    ///Start an openDuT resource
+    Start {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: StartResource,
+        ///Text, JSON or prettified JSON as output format
+        #[arg(value_enum, short, long, default_value_t=CreateOutputFormat::Text)]
+        output: CreateOutputFormat,
+    },
+    ///Stop an openDuT resource
+    Stop {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: StopResource,
+    },
+    ///Restart an openDuT resource
+    Restart {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: RestartResource,
+        ///Text, JSON or prettified JSON as output format
+        #[arg(value_enum, short, long, default_value_t=CreateOutputFormat::Text)]
+        output: CreateOutputFormat,
+    },
+    ///Wait until an openDuT resource reaches a state. Exits with code 2, if the timeout expires.
+    Wait {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: WaitResource,
+    },
     Config,
     /// Generates shell completion
     Completions {
@@ -97,6 +165,8 @@ enum ListResource {
     Peers(commands::peer::list::ListPeersCli),
     Devices(commands::device::list::ListDevicesCli),
     ContainerExecutor(commands::executor::list::ListContainerExecutorCli),
+    TestRuns(commands::test_run::list::ListTestRunsCli),
+    Plugins(commands::plugin::list::ListPluginsCli),
 }
 
 #[derive(clap::Args)]
@@ -120,6 +190,12 @@ pub enum NetworkInterfaceType {
     Can,
 }
 
+#[derive(ValueEnum, Clone)]
+pub enum PluginKindType {
+    Setup,
+    Service,
+}
+
 #[derive(Subcommand)]
 enum ApplyResource {
     ContainerExecutor(commands::executor::apply::ApplyContainerExecutorCli),
@@ -131,8 +207,19 @@ enum CreateResource {
     ClusterDeployment(commands::cluster_deployment::create::CreateClusterDeploymentCli),
     Peer(commands::peer::create::CreatePeerCli),
     ContainerExecutor(commands::executor::create::CreateContainerExecutorCli),
+    ExecutableExecutor(commands::executor::create::CreateExecutableExecutorCli),
     NetworkInterface(commands::network_interface::create::CreateNetworkInterfaceCli),
-    Device(commands::device::create::CreateDeviceCli)
+    Device(commands::device::create::CreateDeviceCli),
+    Plugin(commands::plugin::create::CreatePluginCli),
+}
+
+#[derive(Subcommand)]
+enum UpdateResource {
+    Peer(commands::peer::update::UpdatePeerCli),
+    Executor(Box<commands::executor::update::UpdateExecutorCli>),
+    NetworkInterface(commands::network_interface::update::UpdateNetworkInterfaceCli),
+    Device(commands::device::update::UpdateDeviceCli),
+    PeerPlugins(commands::plugin::assign::AssignPeerPluginsCli),
 }
 
 #[derive(Subcommand)]
@@ -140,6 +227,7 @@ enum DescribeResource {
     ClusterConfiguration(commands::cluster_configuration::describe::DescribeClusterConfigurationCli),
     Peer(commands::peer::describe::DescribePeerCli),
     Device(commands::device::describe::DescribeDeviceCli),
+    TestRun(commands::test_run::describe::DescribeTestRunCli),
 }
 
 #[derive(Subcommand)]
@@ -155,6 +243,43 @@ enum DeleteResource {
     ContainerExecutor(commands::executor::delete::DeleteContainerExecutorCli),
     NetworkInterface(commands::network_interface::delete::DeleteNetworkInterfaceCli),
     Device(commands::device::delete::DeleteDeviceCli),
+    Plugin(commands::plugin::delete::DeletePluginCli),
+}
+
+#[derive(Subcommand)]
+enum DiagnoseResource {
+    Cluster(commands::diagnose::cluster::DiagnoseClusterCli),
+}
+
+#[derive(Subcommand)]
+enum ReconcileResource {
+    Vpn(commands::reconcile::vpn::ReconcileVpnCli),
+}
+
+#[derive(Subcommand)]
+enum LogsResource {
+    Executor(commands::logs::executor::ExecutorLogsCli),
+}
+
+#[derive(Subcommand)]
+enum StartResource {
+    Executor(commands::executor::start::StartExecutorCli),
+}
+
+#[derive(Subcommand)]
+enum StopResource {
+    Executor(commands::executor::stop::StopExecutorCli),
+}
+
+#[derive(Subcommand)]
+enum RestartResource {
+    Executor(commands::executor::restart::RestartExecutorCli),
+}
+
+#[derive(Subcommand)]
+enum WaitResource {
+    Peer(commands::wait::peer::WaitPeerCli),
+    Cluster(commands::wait::cluster::WaitClusterCli),
 }
 
 #[derive(ValueEnum, Clone)]
@@ -188,6 +313,13 @@ impl From<CreateOutputFormat> for DescribeOutputFormat {
     }
 }
 
+#[derive(ValueEnum, Clone)]
+enum LogsOutputFormat {
+    Text,
+    ///One JSON object per line
+    Json,
+}
+
 #[derive(ValueEnum, Clone)]
 enum DecodeSetupStringOutputFormat {
     Text,
@@ -199,7 +331,7 @@ enum DecodeSetupStringOutputFormat {
 async fn main() -> ExitCode {
     let red = Style::new().red();
     match execute().await {
-        Ok(()) => ExitCode::SUCCESS,
+        Ok(exit_code) => exit_code,
         Err(error) => {
             eprintln!("{}", red.apply_to(error));
             ExitCode::FAILURE
@@ -207,7 +339,7 @@ async fn main() -> ExitCode {
     }
 }
 
-async fn execute() -> Result<()> {
+async fn execute() -> Result<ExitCode> {
     // TODO: make it actually hide secrets in the logging output
     let cleo_config_hide_secrets_override = config::Config::builder()
         .set_override("network.oidc.client.secret", "redacted")
@@ -220,11 +352,10 @@ async fn execute() -> Result<()> {
 
     let args = Args::parse();
 
-    execute_command(args.command, &settings).await?;
-    Ok(())
+    execute_command(args.command, &settings).await
 }
 
-async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<()>{
+async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<ExitCode>{
     match commands {
         Commands::Setup(implementation) => {
             implementation.execute().await?;
@@ -247,14 +378,23 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 ListResource::Devices(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                ListResource::TestRuns(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::Plugins(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
             }
         }
-        Commands::Apply { resource, output } => {
+        Commands::Apply { resource, manifest, output } => {
             let mut carl = create_carl_client(&settings.config).await;
             match resource {
-                ApplyResource::ContainerExecutor(implementation) => {
+                Some(ApplyResource::ContainerExecutor(implementation)) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                None => {
+                    manifest.execute(&mut carl, output).await?;
+                }
             }
         }
         Commands::Create { resource, output } => {
@@ -272,12 +412,38 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 CreateResource::ContainerExecutor(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                CreateResource::ExecutableExecutor(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
                 CreateResource::NetworkInterface(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
                 CreateResource::Device(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                CreateResource::Plugin(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+            }
+        }
+        Commands::Update { resource, output } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match *resource {
+                UpdateResource::Peer(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                UpdateResource::Executor(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                UpdateResource::NetworkInterface(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                UpdateResource::Device(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                UpdateResource::PeerPlugins(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
             }
         }
         Commands::GenerateSetupString(implementation) => {
@@ -300,6 +466,9 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 DescribeResource::Device (implementation)=> {
                     implementation.execute(&mut carl, output).await?
                 }
+                DescribeResource::TestRun(implementation)=> {
+                    implementation.execute(&mut carl, output).await?
+                }
             }
         }
         Commands::Delete { resource} => {
@@ -323,6 +492,9 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 DeleteResource::Device(implementation) => {
                     implementation.execute(&mut carl).await?;
                 }
+                DeleteResource::Plugin(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
             }
         }
         Commands::Find { resource, output } => {
@@ -333,6 +505,66 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 }
             }
         }
+        Commands::Diagnose { resource, output } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                DiagnoseResource::Cluster(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+            }
+        }
+        Commands::Reconcile { resource, output } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                ReconcileResource::Vpn(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+            }
+        }
+        Commands::Logs { resource, output } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                LogsResource::Executor(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+            }
+        }
+        Commands::Start { resource, output } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                StartResource::Executor(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+            }
+        }
+        Commands::Stop { resource } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                StopResource::Executor(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+            }
+        }
+        Commands::Restart { resource, output } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                RestartResource::Executor(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+            }
+        }
+        Commands::Wait { resource } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            let exit_code = match resource {
+                WaitResource::Peer(implementation) => {
+                    implementation.execute(&mut carl).await?
+                }
+                WaitResource::Cluster(implementation) => {
+                    implementation.execute(&mut carl).await?
+                }
+            };
+            return Ok(exit_code);
+        }
         Commands::Config => {
             println!("Active CLEO configuration: {:?}", settings);
         }
@@ -341,7 +573,7 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
             commands::completions::print_completions(shell, &mut cmd);
         }
     }
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
 pub async fn create_carl_client(config: &config::Config) -> CarlClient {
@@ -0,0 +1,84 @@
+use std::ops::Not;
+use std::time::Duration;
+
+use super::*;
+
+/// Parses durations like `500ms`, `30s`, `2m` or `1h`. A value without unit is interpreted as seconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseableDuration(pub Duration);
+impl FromStr for ParseableDuration {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let trimmed = value.trim();
+        let split_index = trimmed.find(|char: char| char.is_ascii_digit().not())
+            .unwrap_or(trimmed.len());
+        let (amount, unit) = trimmed.split_at(split_index);
+
+        let amount = amount.parse::<u64>()
+            .map_err(|cause| ParseError::new::<Self>(value, format!("Expected a number followed by an optional unit: {cause}")))?;
+
+        let seconds_per_unit = match unit.trim() {
+            "ms" => return Ok(Self(Duration::from_millis(amount))),
+            "" | "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            other => return Err(ParseError::new::<Self>(value, format!("Unknown unit '{other}'. Valid units are: ms, s, m, h"))),
+        };
+        let seconds = amount.checked_mul(seconds_per_unit)
+            .ok_or_else(|| ParseError::new::<Self>(value, "Duration is too long."))?;
+        Ok(Self(Duration::from_secs(seconds)))
+    }
+}
+
+/// Like [`ParseableDuration`], but rejects a duration of zero, e.g. for intervals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseableNonZeroDuration(pub Duration);
+impl FromStr for ParseableNonZeroDuration {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let ParseableDuration(duration) = ParseableDuration::from_str(value)?;
+        if duration.is_zero() {
+            return Err(ParseError::new::<Self>(value, "Duration must be greater than zero."));
+        }
+        Ok(Self(duration))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("500ms", Duration::from_millis(500))]
+    #[case("120s", Duration::from_secs(120))]
+    #[case("120", Duration::from_secs(120))]
+    #[case("2m", Duration::from_secs(120))]
+    #[case("1h", Duration::from_secs(3600))]
+    fn should_parse_duration(#[case] value: &str, #[case] expected: Duration) {
+        assert_that!(ParseableDuration::from_str(value), ok(eq(&ParseableDuration(expected))));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("s")]
+    #[case("-1s")]
+    #[case("10d")]
+    #[case("18446744073709551615h")]
+    fn should_reject_invalid_duration(#[case] value: &str) {
+        assert_that!(ParseableDuration::from_str(value), err(anything()));
+    }
+
+    #[rstest]
+    #[case("0")]
+    #[case("0ms")]
+    #[case("0h")]
+    fn should_reject_zero_for_non_zero_duration(#[case] value: &str) {
+        assert_that!(ParseableDuration::from_str(value), ok(eq(&ParseableDuration(Duration::ZERO))));
+        assert_that!(ParseableNonZeroDuration::from_str(value), err(anything()));
+    }
+}
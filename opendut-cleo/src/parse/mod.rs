@@ -1,4 +1,5 @@
 pub mod cluster;
+pub mod duration;
 
 use std::str::FromStr;
 
@@ -6,6 +6,39 @@ bindgen!({world: "setup-plugin", path: ".\\wit\\world.wit"});
 #[cfg(not(target_os = "windows"))]
 bindgen!({world: "setup-plugin", path: "./wit/world.wit"});
 
+/// Version of the WIT package `edgar:setup`, which plugins need to be built against.
+pub const API_VERSION: &str = "1.1.0";
+
 pub use self::edgar::setup::host::Host;
 pub use self::edgar::setup::host::LogLevel;
+pub use self::edgar::setup::types::HostError;
+pub use self::edgar::setup::filesystem::Host as FilesystemHost;
+pub use self::edgar::setup::network::{Host as NetworkHost, NetworkInterface};
+pub use self::edgar::setup::peer::{Host as PeerHost, PeerSetup, VpnKind};
+pub use self::edgar::setup::types::Host as TypesHost;
 pub use self::exports::edgar::setup::task::{Guest, Success, TaskFulfilled};
+
+/// Bindings for plugins, which are loaded by the EDGAR service. The imported interfaces are shared with setup plugins.
+pub mod service {
+    use wasmtime::component::bindgen;
+
+    #[cfg(target_os = "windows")]
+    bindgen!({world: "service-plugin", path: ".\\wit\\world.wit", with: {
+        "edgar:setup/types": crate::host::edgar::setup::types,
+        "edgar:setup/host": crate::host::edgar::setup::host,
+        "edgar:setup/filesystem": crate::host::edgar::setup::filesystem,
+        "edgar:setup/network": crate::host::edgar::setup::network,
+        "edgar:setup/peer": crate::host::edgar::setup::peer,
+    }});
+
+    #[cfg(not(target_os = "windows"))]
+    bindgen!({world: "service-plugin", path: "./wit/world.wit", with: {
+        "edgar:setup/types": crate::host::edgar::setup::types,
+        "edgar:setup/host": crate::host::edgar::setup::host,
+        "edgar:setup/filesystem": crate::host::edgar::setup::filesystem,
+        "edgar:setup/network": crate::host::edgar::setup::network,
+        "edgar:setup/peer": crate::host::edgar::setup::peer,
+    }});
+
+    pub use self::exports::edgar::setup::cluster_handler::{ClusterAssignment, PeerAssignment, Success};
+}
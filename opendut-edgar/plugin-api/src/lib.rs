@@ -1,6 +1,12 @@
 #[cfg(feature = "host")]
 pub mod host;
 
+#[cfg(any(feature = "plugin", feature = "service-plugin"))]
+mod macros;
+
 #[cfg(feature = "plugin")]
 pub mod plugin;
 
+#[cfg(feature = "service-plugin")]
+pub mod service_plugin;
+
@@ -0,0 +1,43 @@
+/// Defines the logging functions in the bindings of a world, which all import the same `host` interface.
+macro_rules! log_functions {
+    () => {
+        pub fn trace(message: &str){
+            edgar::setup::host::log(edgar::setup::host::LogLevel::Trace, message);
+        }
+
+        pub fn debug(message: &str){
+            edgar::setup::host::log(edgar::setup::host::LogLevel::Debug, message);
+        }
+
+        pub fn info(message: &str){
+            edgar::setup::host::log(edgar::setup::host::LogLevel::Info, message);
+        }
+
+        pub fn warn(message: &str){
+            edgar::setup::host::log(edgar::setup::host::LogLevel::Warn, message);
+        }
+
+        pub fn error(message: &str){
+            edgar::setup::host::log(edgar::setup::host::LogLevel::Error, message);
+        }
+    };
+}
+pub(crate) use log_functions;
+
+/// Adds constructors to the `success` record, which is generated separately for each world.
+macro_rules! impl_success {
+    ($success:ty) => {
+        impl $success {
+            pub fn new() -> Self {
+                Self {
+                    message: None
+                }
+            }
+
+            pub fn message(message: impl Into<String>) -> Self {
+                Self { message: Some(message.into()) }
+            }
+        }
+    };
+}
+pub(crate) use impl_success;
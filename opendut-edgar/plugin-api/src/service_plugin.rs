@@ -0,0 +1,18 @@
+pub mod bindings {
+    use wit_bindgen::generate;
+
+    generate!({world: "service-plugin", path: "wit/world.wit", pub_export_macro: true, export_macro_name: "export"});
+
+    crate::macros::log_functions!();
+}
+
+crate::macros::impl_success!(crate::service_plugin::bindings::exports::edgar::setup::cluster_handler::Success);
+
+pub use crate::service_plugin::bindings::{trace, debug, info, warn, error};
+pub use crate::service_plugin::bindings::edgar::setup::host;
+pub use crate::service_plugin::bindings::edgar::setup::filesystem;
+pub use crate::service_plugin::bindings::edgar::setup::network;
+pub use crate::service_plugin::bindings::edgar::setup::peer;
+pub use crate::service_plugin::bindings::edgar::setup::types::HostError;
+pub use crate::service_plugin::bindings::export;
+pub use crate::service_plugin::bindings::exports::edgar::setup::cluster_handler;
@@ -9,6 +9,7 @@ mkdir --parents target/distribution/test-plugins/
 
 cp target/wasm32-wasip1/release/*.wasm target/distribution/test-plugins/
 cp plugins.txt target/distribution/test-plugins/
+cp manifests/*.toml target/distribution/test-plugins/
 
 tar --directory=target/distribution/ --create --gzip --file=target/distribution/test-plugins.tar.gz  test-plugins/
 
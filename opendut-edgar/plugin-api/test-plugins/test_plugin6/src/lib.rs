@@ -0,0 +1,42 @@
+use opendut_edgar_plugin_api::plugin::{export, info, HostError};
+use opendut_edgar_plugin_api::plugin::{filesystem, network, peer};
+use opendut_edgar_plugin_api::plugin::task::{Guest, Success, TaskFulfilled};
+
+struct TestPlugin6;
+
+impl Guest for TestPlugin6 {
+    fn description() -> String {
+        String::from("Test Plugin 6 - Host API and Capabilities")
+    }
+
+    fn check_fulfilled() -> Result<TaskFulfilled, ()> {
+        Ok(TaskFulfilled::Unchecked)
+    }
+
+    fn execute() -> Result<Success, ()> {
+        let interfaces = network::list_interfaces().map_err(|_| ())?;
+        info(format!("Found network interfaces: {:?}", interfaces.iter().map(|interface| &interface.name).collect::<Vec<_>>()).as_str());
+
+        let peer_setup = peer::get_peer_setup().map_err(|_| ())?;
+        info(format!("Applying PeerSetup: {peer_setup:?}").as_str());
+
+        let os_release = filesystem::read_file("/etc/os-release").map_err(|_| ())?;
+        info(format!("Read {} bytes from /etc/os-release", os_release.len()).as_str());
+
+        let expect_denied = filesystem::read_file("/etc/shadow");
+        info(format!("Expecting to be denied: {:?}", &expect_denied).as_str());
+        if !matches!(expect_denied, Err(HostError::PermissionDenied(_))) {
+            return Err(());
+        }
+
+        let expect_denied = peer::get_edgar_config("peer.id");
+        info(format!("Expecting to be denied: {:?}", &expect_denied).as_str());
+        if !matches!(expect_denied, Err(HostError::PermissionDenied(_))) {
+            return Err(());
+        }
+
+        Ok(Success::message("Host API returned the expected results"))
+    }
+}
+
+export!(TestPlugin6 with_types_in opendut_edgar_plugin_api::plugin::bindings);
@@ -0,0 +1,16 @@
+#!/bin/sh
+set -ex
+
+cargo install cargo-component --locked
+cargo component build --release
+
+rm -r target/distribution/ || true  #ignore errors if directory doesn't exist
+mkdir --parents target/distribution/service-plugins/
+
+cp target/wasm32-wasip1/release/*.wasm target/distribution/service-plugins/
+cp plugins.txt target/distribution/service-plugins/
+cp manifests/*.toml target/distribution/service-plugins/
+
+tar --directory=target/distribution/ --create --gzip --file=target/distribution/test-service-plugins.tar.gz  service-plugins/
+
+echo "Distribution placed at: target/distribution/test-service-plugins.tar.gz"
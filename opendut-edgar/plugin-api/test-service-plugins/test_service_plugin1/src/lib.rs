@@ -0,0 +1,33 @@
+use opendut_edgar_plugin_api::service_plugin::{export, info};
+use opendut_edgar_plugin_api::service_plugin::cluster_handler::{ClusterAssignment, Guest, Success};
+use opendut_edgar_plugin_api::service_plugin::filesystem;
+
+struct TestServicePlugin1;
+
+const MARKER_FILE: &str = "/tmp/opendut-test-service-plugin1";
+
+impl Guest for TestServicePlugin1 {
+    fn description() -> String {
+        String::from("Test Service Plugin 1 - Cluster Deployment")
+    }
+
+    fn cluster_deployed(assignment: ClusterAssignment) -> Result<Success, String> {
+        info(format!("Deployed cluster <{}> with {} peer(s).", assignment.id, assignment.peers.len()).as_str());
+
+        filesystem::write_file(MARKER_FILE, assignment.id.as_bytes())
+            .map_err(|cause| format!("Failed to write marker file: {cause:?}"))?;
+
+        Ok(Success::message(format!("Wrote cluster ID to {MARKER_FILE}")))
+    }
+
+    fn cluster_undeployed(assignment: ClusterAssignment) -> Result<Success, String> {
+        info(format!("Undeployed cluster <{}>.", assignment.id).as_str());
+
+        filesystem::write_file(MARKER_FILE, &[])
+            .map_err(|cause| format!("Failed to clear marker file: {cause:?}"))?;
+
+        Ok(Success::new())
+    }
+}
+
+export!(TestServicePlugin1 with_types_in opendut_edgar_plugin_api::service_plugin::bindings);
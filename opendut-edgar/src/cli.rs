@@ -2,7 +2,7 @@ use std::collections::HashSet;
 use std::net::Ipv4Addr;
 use std::str::FromStr;
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use tracing::info;
 use url::Url;
 use uuid::Uuid;
@@ -56,6 +56,14 @@ enum SetupMode {
         // Setup String retrieved from LEA
         #[arg()]
         setup_string: String,
+
+        /// Apply a Setup String, which was generated for re-enrolling this peer, replacing the credentials of a previous setup
+        #[arg(long)]
+        reenroll: bool,
+
+        /// Format of the output. With "json", the plan of a dry run or the result of the setup is printed as JSON, e.g. for provisioning tools.
+        #[arg(value_enum, long, default_value_t=OutputFormat::Text)]
+        output: OutputFormat,
     },
     /// Setup your system for network routing without automatic management. This setup method will be removed in the future.
     Unmanaged {
@@ -79,6 +87,8 @@ enum SetupMode {
         #[arg(long)]
         bridge: Option<NetworkInterfaceName>,
     },
+    /// Remove EDGAR from your system, undoing the steps of a managed setup in reverse order. The configuration file is kept.
+    Uninstall,
 }
 
 pub async fn cli() -> anyhow::Result<()> {
@@ -101,7 +111,11 @@ pub async fn cli() -> anyhow::Result<()> {
             info!("Setup command being executed: {:?}", command);
 
             let dry_run = if dry_run { DryRun::Yes } else { DryRun::No };
-            let dry_run = force_dry_run_in_development(dry_run);
+            let output = match &setup_mode {
+                SetupMode::Managed { output, .. } => *output,
+                SetupMode::Unmanaged { .. } | SetupMode::Uninstall => OutputFormat::Text,
+            };
+            let dry_run = force_dry_run_in_development(dry_run, output);
 
             if dry_run.not() {
                 sudo::with_env(&["OPENDUT_EDGAR_"]) //Request before doing anything else, as it restarts the process when sudo is not present.
@@ -109,8 +123,8 @@ pub async fn cli() -> anyhow::Result<()> {
             }
 
             match setup_mode {
-                SetupMode::Managed { setup_string } => {
-                    setup::start::managed(dry_run, no_confirm, setup_string, mtu).await?;
+                SetupMode::Managed { setup_string, reenroll, output } => {
+                    setup::start::managed(dry_run, no_confirm, setup_string, mtu, reenroll, output).await?;
                 },
                 SetupMode::Unmanaged { management_url, setup_key, leader, bridge, device_interfaces } => {
                     let setup_key = SetupKey { uuid: setup_key };
@@ -119,6 +133,9 @@ pub async fn cli() -> anyhow::Result<()> {
                     let device_interfaces = HashSet::from_iter(device_interfaces);
                     setup::start::unmanaged(dry_run, no_confirm, management_url, setup_key, bridge, device_interfaces, leader, mtu).await?;
                 }
+                SetupMode::Uninstall => {
+                    setup::start::uninstall(dry_run, no_confirm).await?;
+                }
             };
             info!("EDGAR Setup finished!\n");
             Ok(())
@@ -151,9 +168,15 @@ impl DryRun {
     }
 }
 
-fn force_dry_run_in_development(dry_run: DryRun) -> DryRun {
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat { Text, Json }
+
+fn force_dry_run_in_development(dry_run: DryRun, output: OutputFormat) -> DryRun {
     if project::is_running_in_development() {
-        println!("{DEVELOPMENT_DRY_RUN_BANNER}");
+        match output {
+            OutputFormat::Text => println!("{DEVELOPMENT_DRY_RUN_BANNER}"),
+            OutputFormat::Json => eprintln!("{DEVELOPMENT_DRY_RUN_BANNER}"), //keep stdout parseable
+        }
         info!("{DEVELOPMENT_DRY_RUN_BANNER}");
         DryRun::Yes
     } else {
@@ -48,7 +48,7 @@ pub async fn open_stream(
     carl: &mut CarlClient,
 ) -> anyhow::Result<(broker::Downstream, broker::Upstream), broker::error::OpenStream> {
     debug!("Opening peer messaging stream...");
-    let (rx_inbound, tx_outbound) = carl.broker.open_stream(self_id, remote_address).await?;
+    let (rx_inbound, tx_outbound) = carl.broker.open_stream(self_id, remote_address, crate::app_info::CRATE_VERSION).await?;
 
     tx_outbound.send(peer_messaging_broker::Upstream {
         message: Some(peer_messaging_broker::upstream::Message::Ping(peer_messaging_broker::Ping {})),
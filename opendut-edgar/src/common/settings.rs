@@ -7,6 +7,13 @@ pub mod key {
     pub mod peer {
         pub const id: &str = "peer.id";
     }
+    pub mod plugins {
+        pub mod registry {
+            pub mod verifying {
+                pub const key: &str = "plugins.registry.verifying.key";
+            }
+        }
+    }
     pub mod vpn {
         pub const table: &str = "vpn";
 
@@ -15,6 +22,24 @@ pub mod key {
                 pub const host: &str = "vpn.disabled.remote.host";
             }
         }
+
+        pub mod direct {
+            pub const network: &str = "vpn.direct.network";
+            pub const address: &str = "vpn.direct.address";
+        }
+
+        pub mod wireguard {
+            pub mod private {
+                pub const key: &str = "vpn.wireguard.private.key";
+            }
+            pub const address: &str = "vpn.wireguard.address";
+            pub mod prefix {
+                pub const length: &str = "vpn.wireguard.prefix.length";
+            }
+            pub mod listen {
+                pub const port: &str = "vpn.wireguard.listen.port";
+            }
+        }
     }
 }
 
@@ -23,7 +48,9 @@ pub fn default_config_file_path() -> PathBuf {
 }
 
 pub fn load_with_overrides(overrides: config::Config) -> anyhow::Result<LoadedConfig> {
-    let edgar_config_hide_secrets_override = opendut_util::settings::Config::default();
+    let edgar_config_hide_secrets_override = config::Config::builder()
+        .set_override(key::vpn::wireguard::private::key, "redacted")?
+        .build()?;
 
     Ok(opendut_util::settings::load_config("edgar", include_str!("../../edgar.toml"), config::FileFormat::Toml, overrides, edgar_config_hide_secrets_override)?)
 }
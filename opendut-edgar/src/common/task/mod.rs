@@ -1,5 +1,7 @@
 use async_trait::async_trait;
+use serde::Serialize;
 
+pub mod report;
 pub mod runner;
 
 #[async_trait]
@@ -13,9 +15,22 @@ pub trait Task: Send + Sync {
 
     /// Make changes to the host system.
     async fn execute(&self) -> anyhow::Result<Success>;
+
+    /// Called before undoing the task, to check whether its changes still need to be reversed.
+    /// And called after undoing the task, to check whether the changes were reversed successfully.
+    /// Tasks, which make no lasting changes to the host system, count as undone by default.
+    async fn check_undone(&self) -> anyhow::Result<TaskFulfilled> {
+        Ok(TaskFulfilled::Yes)
+    }
+
+    /// Reverse the changes, which `execute()` made to the host system, e.g. when uninstalling EDGAR.
+    async fn undo(&self) -> anyhow::Result<Success> {
+        Ok(Success::default())
+    }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum TaskFulfilled {
     ///Task does not need to be executed, or successfully changed the host system during its execution.
     Yes,
@@ -0,0 +1,56 @@
+use serde::Serialize;
+
+use crate::common::task::TaskFulfilled;
+
+/// Machine-readable result of running tasks, e.g. for provisioning tools which drive the setup of EDGAR.
+/// In a dry run, this is the plan of which tasks would change the host system.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RunReport {
+    pub dry_run: bool,
+    /// Whether all tasks succeeded.
+    pub success: bool,
+    pub tasks: Vec<TaskReport>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TaskReport {
+    pub task: String,
+    /// State of the host system before running the task. Not set, if it could not be determined or the task was skipped.
+    pub fulfilled: Option<TaskFulfilled>,
+    /// Whether the task needs to change the host system.
+    pub would_change: bool,
+    pub outcome: TaskOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl TaskReport {
+    pub(super) fn new(task: String, fulfilled: Option<TaskFulfilled>, outcome: TaskOutcome) -> Self {
+        Self {
+            task,
+            fulfilled,
+            would_change: matches!(fulfilled, Some(TaskFulfilled::No | TaskFulfilled::Unchecked)),
+            outcome,
+            message: None,
+            error: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TaskOutcome {
+    /// The host system already was in the expected state.
+    Unchanged,
+    /// The task changed the host system.
+    Changed,
+    /// The task would have changed the host system, but it was a dry run.
+    NeedsChange,
+    Failed,
+    /// The task was not run, because a previous task failed.
+    Skipped,
+}
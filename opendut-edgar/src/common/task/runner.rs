@@ -1,93 +1,162 @@
 use std::time::Duration;
 
 use indicatif::{ProgressBar, ProgressStyle};
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, Instrument};
 
+use crate::cli::OutputFormat;
+use crate::common::task::report::{RunReport, TaskOutcome, TaskReport};
 use crate::common::task::{Success, Task, TaskFulfilled};
 
-pub async fn run(run_mode: RunMode, tasks: &[Box<dyn Task>]) -> anyhow::Result<()> {
+pub async fn run(run_mode: RunMode, output: OutputFormat, tasks: &[Box<dyn Task>]) -> anyhow::Result<RunReport> {
     if tasks.is_empty() {
         debug!("No tasks to run. Skipping.");
-        return Ok(())
+        return Ok(RunReport { dry_run: run_mode.is_dry_run(), success: true, tasks: vec![] })
     }
 
     let task_names_string = tasks.iter().map(|task| task.description()).collect::<Vec<_>>().join(", ");
     debug!("Running tasks: {task_names_string}");
 
-    run_tasks(tasks, run_mode).await;
+    let task_reports = run_tasks(tasks, run_mode, output).await;
 
-    println!();
+    if output == OutputFormat::Text {
+        println!();
+    }
     debug!("Completed running tasks: {task_names_string}");
-    Ok(())
+
+    Ok(RunReport {
+        dry_run: run_mode.is_dry_run(),
+        success: task_reports.iter().all(|report| report.outcome != TaskOutcome::Failed),
+        tasks: task_reports,
+    })
 }
 
 async fn run_tasks(
     tasks: &[Box<dyn Task>],
     run_mode: RunMode,
-) {
-    println!();
+    output: OutputFormat,
+) -> Vec<TaskReport> {
+    if output == OutputFormat::Text {
+        println!();
+    }
+
+    let tasks: Vec<&Box<dyn Task>> = if run_mode.is_uninstall() {
+        tasks.iter().rev().collect() //undo in reverse order, as later tasks may depend on earlier ones, e.g. NetBird needs to be unpacked to uninstall its service
+    } else {
+        tasks.iter().collect()
+    };
 
     let progress_style = ProgressStyle::with_template(" {spinner:.dim}  {msg}").unwrap()
         .tick_strings(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏", ""]);
+
+    let mut task_reports = Vec::with_capacity(tasks.len());
+    let mut failed = false;
     for task in tasks {
-        let spinner = ProgressBar::new_spinner();
-        spinner.enable_steady_tick(Duration::from_millis(120));
-        spinner.set_style(progress_style.clone());
-        spinner.set_message(task.description());
+        if failed {
+            task_reports.push(TaskReport::new(task.description(), None, TaskOutcome::Skipped));
+            continue;
+        }
+        let span = tracing::info_span!("task", task = %task.description());
 
-        let is_fulfilled = match task.check_fulfilled().await {
-            Ok(is_fulfilled) => is_fulfilled,
-            Err(cause) => {
-                print_outcome(task.description(), Outcome::Failed);
-                print_error("Error while determining system state:", Some(cause));
-                return;
-            }
-        };
+        let task_report = run_task(task.as_ref(), run_mode, output, &progress_style)
+            .instrument(span)
+            .await;
 
-        let outcome = match is_fulfilled {
-            TaskFulfilled::Yes => Outcome::Unchanged,
-            TaskFulfilled::No | TaskFulfilled::Unchecked => {
-                if run_mode == RunMode::SetupDryRun {
-                    Outcome::DryRun
+        failed = task_report.outcome == TaskOutcome::Failed;
+        task_reports.push(task_report);
+    }
+    task_reports
+}
+
+/// Runs a single task. If the task failed, the error has already been printed.
+async fn run_task(
+    task: &dyn Task,
+    run_mode: RunMode,
+    output: OutputFormat,
+    progress_style: &ProgressStyle,
+) -> TaskReport {
+    let spinner = ProgressBar::new_spinner();
+    spinner.enable_steady_tick(Duration::from_millis(120));
+    spinner.set_style(progress_style.clone());
+    spinner.set_message(task.description());
+
+    let is_fulfilled = match check(task, run_mode).await {
+        Ok(is_fulfilled) => is_fulfilled,
+        Err(cause) => {
+            return failed(task, None, "Error while determining system state:", Some(cause), output);
+        }
+    };
+
+    let outcome = match is_fulfilled {
+        TaskFulfilled::Yes => Outcome::Unchanged,
+        TaskFulfilled::No | TaskFulfilled::Unchecked => {
+            if run_mode.is_dry_run() {
+                Outcome::DryRun
+            } else {
+                let result = if run_mode.is_uninstall() {
+                    task.undo().await
                 } else {
-                    let result = task.execute().await;
-                    spinner.finish_and_clear();
-                    match result {
-                        Ok(success) => Outcome::Changed(success),
-                        Err(cause) => {
-                            print_outcome(task.description(), Outcome::Failed);
-                            print_error("Error while executing:", Some(cause));
-                            return;
-                        }
+                    task.execute().await
+                };
+                spinner.finish_and_clear();
+                match result {
+                    Ok(success) => Outcome::Changed(success),
+                    Err(cause) => {
+                        return failed(task, Some(is_fulfilled), "Error while executing:", Some(cause), output);
                     }
                 }
             }
-        };
-        spinner.finish_and_clear();
-
-        if let Outcome::Changed(_) = outcome {
-            match task.check_fulfilled().await {
-                Ok(fulfillment) => match fulfillment {
-                    TaskFulfilled::Yes | TaskFulfilled::Unchecked => {}, //do nothing
-                    TaskFulfilled::No => {
-                        print_outcome(task.description(), Outcome::Failed);
-                        print_error("Execution succeeded, but system state check indicated task still needing execution.", None);
-                        return;
-                    }
-                }
-                Err(cause) => {
-                    print_outcome(task.description(), Outcome::Failed);
-                    print_error("Error while determining system state after execution:", Some(cause));
-                    return;
+        }
+    };
+    spinner.finish_and_clear();
+
+    if let Outcome::Changed(_) = outcome {
+        match check(task, run_mode).await {
+            Ok(fulfillment) => match fulfillment {
+                TaskFulfilled::Yes | TaskFulfilled::Unchecked => {}, //do nothing
+                TaskFulfilled::No => {
+                    return failed(task, Some(is_fulfilled), "Execution succeeded, but system state check indicated task still needing execution.", None, output);
                 }
             }
-        };
+            Err(cause) => {
+                return failed(task, Some(is_fulfilled), "Error while determining system state after execution:", Some(cause), output);
+            }
+        }
+    };
+
+    print_outcome(task.description(), &outcome, output);
+
+    let (task_outcome, message) = match outcome {
+        Outcome::Changed(success) => (TaskOutcome::Changed, success.message),
+        Outcome::DryRun => (TaskOutcome::NeedsChange, None),
+        Outcome::Unchanged => (TaskOutcome::Unchanged, None),
+        Outcome::Failed => (TaskOutcome::Failed, None),
+    };
+    TaskReport {
+        message,
+        ..TaskReport::new(task.description(), Some(is_fulfilled), task_outcome)
+    }
+}
 
-        print_outcome(task.description(), outcome)
+fn failed(task: &dyn Task, fulfilled: Option<TaskFulfilled>, context: &str, error: Option<anyhow::Error>, output: OutputFormat) -> TaskReport {
+    print_outcome(task.description(), &Outcome::Failed, output);
+    let error = print_error(context, error);
+
+    TaskReport {
+        error: Some(error),
+        ..TaskReport::new(task.description(), fulfilled, TaskOutcome::Failed)
     }
 }
 
-fn print_error(context: impl AsRef<str>, error: Option<anyhow::Error>) {
+async fn check(task: &dyn Task, run_mode: RunMode) -> anyhow::Result<TaskFulfilled> {
+    if run_mode.is_uninstall() {
+        task.check_undone().await
+    } else {
+        task.check_fulfilled().await
+    }
+}
+
+/// Prints the error to stderr and returns the printed message.
+fn print_error(context: impl AsRef<str>, error: Option<anyhow::Error>) -> String {
     let message = {
         let mut message = String::new();
         message.push_str(context.as_ref());
@@ -106,10 +175,19 @@ fn print_error(context: impl AsRef<str>, error: Option<anyhow::Error>) {
         eprintln!("    {}", line);
     }
     error!("{message}");
+    message.trim_end().to_owned()
 }
 
 #[derive(Clone, Copy, PartialEq)]
-pub enum RunMode { Setup, SetupDryRun, Service }
+pub enum RunMode { Setup, SetupDryRun, Uninstall, UninstallDryRun, Service }
+impl RunMode {
+    fn is_dry_run(&self) -> bool {
+        matches!(self, RunMode::SetupDryRun | RunMode::UninstallDryRun)
+    }
+    fn is_uninstall(&self) -> bool {
+        matches!(self, RunMode::Uninstall | RunMode::UninstallDryRun)
+    }
+}
 
 enum Outcome {
     Changed(Success),
@@ -117,7 +195,7 @@ enum Outcome {
     Unchanged,
     Failed,
 }
-fn print_outcome(task_name: String, outcome: Outcome) {
+fn print_outcome(task_name: String, outcome: &Outcome, output: OutputFormat) {
 
     fn message(task_name: &str, outcome: &Outcome, interactive: bool) -> String {
         let tick = if interactive { " ✅ " } else { "Task succeeded: " };
@@ -148,6 +226,186 @@ fn print_outcome(task_name: String, outcome: Outcome) {
         }
     }
 
-    println!("{}", message(&task_name, &outcome, console::user_attended()));
-    info!("{}", message(&task_name, &outcome, false));
+    if output == OutputFormat::Text {
+        println!("{}", message(&task_name, outcome, console::user_attended()));
+    }
+    info!("{}", message(&task_name, outcome, false));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id};
+    use tracing::{Event, Subscriber};
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::registry::LookupSpan;
+    use tracing_subscriber::Layer;
+
+    use super::*;
+
+    struct FakeTask {
+        fulfilled: TaskFulfilled,
+        fails: bool,
+        executed: AtomicBool,
+    }
+    impl FakeTask {
+        fn new(fulfilled: TaskFulfilled, fails: bool) -> Self {
+            Self { fulfilled, fails, executed: AtomicBool::new(false) }
+        }
+    }
+
+    #[async_trait]
+    impl Task for FakeTask {
+        fn description(&self) -> String {
+            format!("Fake Task ({:?})", self.fulfilled)
+        }
+        async fn check_fulfilled(&self) -> anyhow::Result<TaskFulfilled> {
+            if self.executed.load(Ordering::SeqCst) {
+                Ok(TaskFulfilled::Yes)
+            } else {
+                Ok(self.fulfilled)
+            }
+        }
+        async fn execute(&self) -> anyhow::Result<Success> {
+            if self.fails {
+                anyhow::bail!("Execution failed.");
+            }
+            self.executed.store(true, Ordering::SeqCst);
+            Ok(Success::message("done"))
+        }
+    }
+
+    #[tokio::test]
+    async fn should_report_the_plan_in_a_dry_run() -> anyhow::Result<()> {
+        let tasks: Vec<Box<dyn Task>> = vec![
+            Box::new(FakeTask::new(TaskFulfilled::Yes, false)),
+            Box::new(FakeTask::new(TaskFulfilled::No, false)),
+        ];
+
+        let report = run(RunMode::SetupDryRun, OutputFormat::Json, &tasks).await?;
+
+        assert!(report.dry_run);
+        assert!(report.success);
+        assert_eq!(report.tasks[0].outcome, TaskOutcome::Unchanged);
+        assert!(!report.tasks[0].would_change);
+        assert_eq!(report.tasks[1].outcome, TaskOutcome::NeedsChange);
+        assert_eq!(report.tasks[1].fulfilled, Some(TaskFulfilled::No));
+        assert!(report.tasks[1].would_change);
+
+        let json = serde_json::to_value(&report)?;
+        assert_eq!(json["tasks"][1]["would-change"], true);
+        assert_eq!(json["tasks"][1]["fulfilled"], "no");
+        assert_eq!(json["tasks"][1]["outcome"], "needs-change");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_trace_each_task_in_its_own_span() -> anyhow::Result<()> {
+        let recorder = TaskSpanRecorder::default();
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+
+        let tasks: Vec<Box<dyn Task>> = vec![
+            Box::new(FakeTask::new(TaskFulfilled::Yes, false)),
+            Box::new(FakeTask::new(TaskFulfilled::No, true)),
+            Box::new(FakeTask::new(TaskFulfilled::Unchecked, false)),
+        ];
+
+        run(RunMode::Setup, OutputFormat::Json, &tasks).await?;
+
+        assert_eq!(recorder.spans(), vec![
+            String::from("Fake Task (Yes)"),
+            String::from("Fake Task (No)"),
+        ]);
+        assert_eq!(recorder.events(), vec![
+            (String::from("Fake Task (Yes)"), String::from("Task succeeded: Fake Task (Yes) (Unchanged)")),
+            (String::from("Fake Task (No)"), String::from("Task failed: Fake Task (No)")),
+        ]);
+        Ok(())
+    }
+
+    /// Records the description of each task span and the outcome messages logged within them.
+    #[derive(Clone, Default)]
+    struct TaskSpanRecorder {
+        spans: Arc<Mutex<Vec<String>>>,
+        events: Arc<Mutex<Vec<(String, String)>>>,
+    }
+    impl TaskSpanRecorder {
+        fn spans(&self) -> Vec<String> {
+            self.spans.lock().unwrap().clone()
+        }
+        fn events(&self) -> Vec<(String, String)> {
+            self.events.lock().unwrap().clone()
+        }
+    }
+    struct TaskDescription(String);
+
+    impl<S> Layer<S> for TaskSpanRecorder
+    where S: Subscriber + for<'lookup> LookupSpan<'lookup> {
+        fn on_new_span(&self, attributes: &Attributes<'_>, id: &Id, context: Context<'_, S>) {
+            if attributes.metadata().name() != "task" {
+                return;
+            }
+            let mut visitor = FieldVisitor::new("task");
+            attributes.record(&mut visitor);
+            if let (Some(description), Some(span)) = (visitor.value, context.span(id)) {
+                self.spans.lock().unwrap().push(description.clone());
+                span.extensions_mut().insert(TaskDescription(description));
+            }
+        }
+
+        fn on_event(&self, event: &Event<'_>, context: Context<'_, S>) {
+            let mut visitor = FieldVisitor::new("message");
+            event.record(&mut visitor);
+            let (Some(message), Some(span)) = (visitor.value, context.event_span(event)) else { return };
+            if !message.starts_with("Task ") {
+                return;
+            }
+            let extensions = span.extensions();
+            if let Some(TaskDescription(description)) = extensions.get::<TaskDescription>() {
+                self.events.lock().unwrap().push((description.clone(), message));
+            }
+        }
+    }
+
+    struct FieldVisitor {
+        name: &'static str,
+        value: Option<String>,
+    }
+    impl FieldVisitor {
+        fn new(name: &'static str) -> Self {
+            Self { name, value: None }
+        }
+    }
+    impl Visit for FieldVisitor {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            if field.name() == self.name {
+                self.value = Some(format!("{value:?}"));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn should_skip_the_remaining_tasks_after_a_task_failed() -> anyhow::Result<()> {
+        let tasks: Vec<Box<dyn Task>> = vec![
+            Box::new(FakeTask::new(TaskFulfilled::No, false)),
+            Box::new(FakeTask::new(TaskFulfilled::No, true)),
+            Box::new(FakeTask::new(TaskFulfilled::No, false)),
+        ];
+
+        let report = run(RunMode::Setup, OutputFormat::Json, &tasks).await?;
+
+        assert!(!report.success);
+        assert_eq!(report.tasks[0].outcome, TaskOutcome::Changed);
+        assert_eq!(report.tasks[0].message.as_deref(), Some("done"));
+        assert_eq!(report.tasks[1].outcome, TaskOutcome::Failed);
+        assert!(report.tasks[1].error.as_ref().is_some_and(|error| error.contains("Execution failed.")));
+        assert_eq!(report.tasks[2].outcome, TaskOutcome::Skipped);
+        assert_eq!(report.tasks[2].fulfilled, None);
+        Ok(())
+    }
 }
@@ -62,6 +62,7 @@ impl CanManager {
         Ok(false)
     }
 
+    #[tracing::instrument(skip(self))]
     async fn create_can_route(&self, src: &NetworkInterfaceName, dst: &NetworkInterfaceName, can_fd: bool, max_hops: u8) -> Result<(), Error> {
         let mut cmd = Command::new("cangw");
         cmd.arg("-A")
@@ -94,6 +95,7 @@ impl CanManager {
         }
     }
 
+    #[tracing::instrument(skip_all)]
     async fn remove_all_can_routes(&self) -> Result<(), Error> {
         let output = Command::new("cangw")
                     .arg("-F")
@@ -107,6 +109,7 @@ impl CanManager {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all)]
     pub async fn setup_local_routing(
         &self,
         bridge_name: &NetworkInterfaceName,
@@ -133,6 +136,7 @@ impl CanManager {
         Ok(())
     }
     
+    #[tracing::instrument(skip_all)]
     async fn create_can_bridge(&self, bridge_name: &NetworkInterfaceName) -> anyhow::Result<()> {
     
         if self.network_interface_manager.find_interface(bridge_name).await?.is_none() {
@@ -146,6 +150,7 @@ impl CanManager {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, fields(interface = %interface.name))]
     async fn update_can_interface(&self, interface: &NetworkInterfaceDescriptor) -> anyhow::Result<()> {
         if let Some(network_interface) = self.network_interface_manager.find_interface(&interface.name).await? {
             self.network_interface_manager.set_interface_down(&network_interface).await?;
@@ -165,6 +170,7 @@ impl CanManager {
         self.cannelloni_termination_token.lock().unwrap().store(true, Ordering::Relaxed);
     }
     
+    #[tracing::instrument(skip_all)]
     pub async fn setup_remote_routing_client(&self, bridge_name: &NetworkInterfaceName, leader_ip: &IpAddr, leader_port: &Port) -> Result<(), Error> {
 
         self.terminate_cannelloni_managers().await;
@@ -191,6 +197,7 @@ impl CanManager {
         Ok(())
     }
     
+    #[tracing::instrument(skip_all)]
     pub async fn setup_remote_routing_server(&self, bridge_name: &NetworkInterfaceName, remote_assignments: &Vec<PeerClusterAssignment>) -> Result<(), Error>  {
 
         self.terminate_cannelloni_managers().await;
@@ -8,4 +8,7 @@ mod can_manager;
 mod vpn;
 mod test_execution;
 mod network_metrics;
+mod prometheus_endpoint;
 mod tasks;
+#[cfg(not(target_arch = "arm"))]
+mod plugin;
@@ -33,6 +33,7 @@ pub async fn setup_interfaces(
     Ok(())
 }
 
+#[tracing::instrument(skip_all)]
 async fn remove_existing_interfaces(network_interface_manager: NetworkInterfaceManagerRef) -> Result<(), Error> {
 
     let interfaces_to_remove = network_interface_manager.list_interfaces().await?
@@ -46,6 +47,7 @@ async fn remove_existing_interfaces(network_interface_manager: NetworkInterfaceM
     Ok(())
 }
 
+#[tracing::instrument(skip_all, fields(%remote_ip))]
 async fn create_interface(
     local_ip: &Ipv4Addr,
     remote_ip: &Ipv4Addr,
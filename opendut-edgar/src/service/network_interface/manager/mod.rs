@@ -1,6 +1,6 @@
 use std::fmt::{Debug, Formatter};
 use std::io;
-use std::net::Ipv4Addr;
+use std::net::{IpAddr, Ipv4Addr};
 use std::sync::Arc;
 
 use anyhow::anyhow;
@@ -95,6 +95,27 @@ impl NetworkInterfaceManager {
         Ok(interface)
     }
 
+    pub async fn create_wireguard_interface(&self, name: &NetworkInterfaceName) -> Result<Interface, Error> {
+        self.handle
+            .link()
+            .add()
+            .wireguard(name.name())
+            .execute().await
+            .map_err(|cause| Error::WireGuardCreation { name: name.clone(), cause: cause.into() })?;
+        let interface = self.try_find_interface(name).await?;
+        Ok(interface)
+    }
+
+    pub async fn add_address(&self, interface: &Interface, address: IpAddr, prefix_length: u8) -> Result<(), Error> {
+        debug!("Add address {address}/{prefix_length} to interface {}.", interface.name);
+        self.handle
+            .address()
+            .add(interface.index, address, prefix_length)
+            .execute().await
+            .map_err(|cause| Error::AddAddress { interface: interface.clone(), address, cause: cause.into() })?;
+        Ok(())
+    }
+
     pub async fn set_interface_up(&self, interface: &Interface) -> Result<(), Error> {
         debug!("Set interface {} up.", interface.name);
         self.handle
@@ -222,6 +243,10 @@ pub enum Error {
     DeleteInterface { interface: Interface, cause: Box<rtnetlink::Error> },
     #[error("Failure while creating gretap interface '{name}': {cause}")]
     GretapCreation { name: NetworkInterfaceName, cause: Box<rtnetlink::Error> },
+    #[error("Failure while creating WireGuard interface '{name}': {cause}")]
+    WireGuardCreation { name: NetworkInterfaceName, cause: Box<rtnetlink::Error> },
+    #[error("Failure while adding address {address} to interface {interface}: {cause}")]
+    AddAddress { interface: Interface, address: IpAddr, cause: Box<rtnetlink::Error> },
     #[error("Interface with name '{name}' not found.")]
     InterfaceNotFound { name: NetworkInterfaceName },
     #[error("Failure while listing interfaces: {cause}")]
@@ -0,0 +1,575 @@
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::ops::Not;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use opentelemetry::{global, KeyValue};
+use regex::Regex;
+use serde::Deserialize;
+use tokio::process::Command;
+use tokio::time::{sleep, Instant};
+use tracing::{error, trace};
+
+use crate::fs;
+
+/// Number of bits of a classic CAN frame with 11-bit identifier, which do not carry payload,
+/// including the interframe space. Bit stuffing is not accounted for.
+const CAN_FRAME_OVERHEAD_BITS: u64 = 47;
+
+/// Name of the process, which tunnels CAN frames to the other peers of a cluster.
+const CANNELLONI_PROCESS_NAME: &str = "cannelloni";
+
+fn cangw_route_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"(?m)^cangw -A -s (\S+) -d (\S+) ((?:-X )?)-e .*# (\d+) handled (\d+) dropped \d+ deleted").unwrap()
+    })
+}
+
+/// Latest readings of the cumulative counters, which are observed whenever the metrics are exported.
+/// Interfaces, routes and tunnels, which no longer exist, are not part of the readings and are therefore no longer reported.
+#[derive(Default)]
+struct CanCounterReadings {
+    interfaces: Vec<CanInterfaceStatistics>,
+    gateway_routes: Vec<CanGatewayRouteStatistics>,
+    tunnels: Vec<CanTunnelStatistics>,
+}
+
+/// Periodically reads the statistics of all CAN interfaces, CAN gateway routes and CAN tunnels from the kernel
+/// and records them with the default meter.
+pub fn launch_can_metrics_collection(interval: Duration) {
+    let meter = global::meter(opendut_util::telemetry::DEFAULT_METER_NAME);
+
+    let frames_per_second = meter.f64_gauge("can_frames_per_second").init();
+    let bus_load_percent = meter.f64_gauge("can_bus_load_percent").init();
+    let error_frames = meter.u64_observable_counter("can_error_frames").init();
+    let bus_off_events = meter.u64_observable_counter("can_bus_off_events").init();
+    let dropped_frames = meter.u64_observable_counter("can_dropped_frames").init();
+    let gateway_handled_frames = meter.u64_observable_counter("can_gateway_handled_frames").init();
+    let gateway_dropped_frames = meter.u64_observable_counter("can_gateway_dropped_frames").init();
+    let tunnel_dropped_packets = meter.u64_observable_counter("can_tunnel_dropped_packets").init();
+
+    let readings = Arc::new(Mutex::new(CanCounterReadings::default()));
+
+    let observed_readings = Arc::clone(&readings);
+    meter.register_callback(&[
+        error_frames.as_any(),
+        bus_off_events.as_any(),
+        dropped_frames.as_any(),
+        gateway_handled_frames.as_any(),
+        gateway_dropped_frames.as_any(),
+        tunnel_dropped_packets.as_any(),
+    ], move |observer| {
+        let Ok(readings) = observed_readings.lock() else { return };
+
+        for statistics in &readings.interfaces {
+            let interface = [KeyValue::new("interface", statistics.name.clone())];
+            let rx = [KeyValue::new("interface", statistics.name.clone()), KeyValue::new("direction", "rx")];
+            let tx = [KeyValue::new("interface", statistics.name.clone()), KeyValue::new("direction", "tx")];
+
+            observer.observe_u64(&error_frames, statistics.rx.errors + statistics.tx.errors, &interface);
+            observer.observe_u64(&bus_off_events, statistics.bus_off, &interface);
+            observer.observe_u64(&dropped_frames, statistics.rx.dropped, &rx);
+            observer.observe_u64(&dropped_frames, statistics.tx.dropped, &tx);
+        }
+
+        for route in &readings.gateway_routes {
+            let attributes = [
+                KeyValue::new("source", route.source.clone()),
+                KeyValue::new("destination", route.destination.clone()),
+                KeyValue::new("can_fd", route.can_fd),
+            ];
+            observer.observe_u64(&gateway_handled_frames, route.handled, &attributes);
+            observer.observe_u64(&gateway_dropped_frames, route.dropped, &attributes);
+        }
+
+        for tunnel in &readings.tunnels {
+            let attributes = [
+                KeyValue::new("local_address", tunnel.local_address.to_string()),
+                KeyValue::new("remote_address", tunnel.remote_address.to_string()),
+            ];
+            observer.observe_u64(&tunnel_dropped_packets, tunnel.dropped, &attributes);
+        }
+    }).expect("Could not register CAN metrics collection callback");
+
+    tokio::spawn(async move {
+        let mut previous_samples: HashMap<String, CanInterfaceSample> = HashMap::new();
+
+        loop {
+            sleep(interval).await;
+
+            match read_can_interface_statistics().await {
+                Ok(interfaces) => {
+                    let rates = update_samples(&mut previous_samples, &interfaces, Instant::now());
+                    for (name, rates) in rates {
+                        let interface = [KeyValue::new("interface", name.clone())];
+                        let rx = [KeyValue::new("interface", name.clone()), KeyValue::new("direction", "rx")];
+                        let tx = [KeyValue::new("interface", name.clone()), KeyValue::new("direction", "tx")];
+
+                        frames_per_second.record(rates.rx_frames_per_second, &rx);
+                        frames_per_second.record(rates.tx_frames_per_second, &tx);
+                        if let Some(load) = rates.bus_load_percent {
+                            bus_load_percent.record(load, &interface);
+                        }
+                        trace!("Measured CAN traffic on interface '{name}': {rates:?}");
+                    }
+                    readings.lock().unwrap().interfaces = interfaces;
+                }
+                Err(cause) => error!("Failed to read statistics of CAN interfaces: {cause}"),
+            }
+
+            match read_can_gateway_statistics().await {
+                Ok(routes) => readings.lock().unwrap().gateway_routes = routes,
+                Err(cause) => error!("Failed to read statistics of CAN gateway routes: {cause}"),
+            }
+
+            match read_can_tunnel_statistics() {
+                Ok(tunnels) => readings.lock().unwrap().tunnels = tunnels,
+                Err(cause) => error!("Failed to read statistics of CAN tunnels: {cause}"),
+            }
+        }
+    });
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CanInterfaceStatistics {
+    pub name: String,
+    /// Nominal bitrate of the bus. Not known for virtual CAN interfaces.
+    pub bitrate: Option<u64>,
+    pub rx: CanDirectionStatistics,
+    pub tx: CanDirectionStatistics,
+    pub bus_off: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct CanDirectionStatistics {
+    pub bytes: u64,
+    pub packets: u64,
+    pub errors: u64,
+    pub dropped: u64,
+}
+
+struct CanInterfaceSample {
+    statistics: CanInterfaceStatistics,
+    sampled_at: Instant,
+}
+
+#[derive(Debug, PartialEq)]
+struct CanInterfaceRates {
+    rx_frames_per_second: f64,
+    tx_frames_per_second: f64,
+    bus_load_percent: Option<f64>,
+}
+
+impl CanInterfaceSample {
+    fn rates_since(&self, previous: &CanInterfaceSample) -> CanInterfaceRates {
+        let elapsed = self.sampled_at.saturating_duration_since(previous.sampled_at);
+        calculate_rates(&previous.statistics, &self.statistics, elapsed)
+    }
+}
+
+/// Replaces the previous samples with the current statistics and returns the rates of those interfaces, which were sampled before.
+/// Samples of interfaces, which no longer exist, are discarded.
+fn update_samples(previous_samples: &mut HashMap<String, CanInterfaceSample>, interfaces: &[CanInterfaceStatistics], sampled_at: Instant) -> Vec<(String, CanInterfaceRates)> {
+    let mut rates = Vec::new();
+    let mut current_samples = HashMap::with_capacity(interfaces.len());
+
+    for statistics in interfaces {
+        let sample = CanInterfaceSample { statistics: statistics.clone(), sampled_at };
+        if let Some(previous) = previous_samples.get(&statistics.name) {
+            rates.push((statistics.name.clone(), sample.rates_since(previous)));
+        }
+        current_samples.insert(statistics.name.clone(), sample);
+    }
+
+    *previous_samples = current_samples;
+    rates
+}
+
+/// Calculates the frame rates between two readings of the counters.
+/// Counters which were reset in between, e.g. because the interface was re-created, are treated as starting from zero.
+fn calculate_rates(previous: &CanInterfaceStatistics, current: &CanInterfaceStatistics, elapsed: Duration) -> CanInterfaceRates {
+    let delta = |current: u64, previous: u64| current.checked_sub(previous).unwrap_or(current);
+
+    let rx_frames = delta(current.rx.packets, previous.rx.packets);
+    let tx_frames = delta(current.tx.packets, previous.tx.packets);
+    let payload_bytes = delta(current.rx.bytes, previous.rx.bytes) + delta(current.tx.bytes, previous.tx.bytes);
+
+    let seconds = elapsed.as_secs_f64();
+    if seconds <= 0.0 {
+        return CanInterfaceRates { rx_frames_per_second: 0.0, tx_frames_per_second: 0.0, bus_load_percent: None };
+    }
+
+    let bus_load_percent = current.bitrate
+        .filter(|bitrate| *bitrate > 0)
+        .map(|bitrate| {
+            let bits = (rx_frames + tx_frames) * CAN_FRAME_OVERHEAD_BITS + payload_bytes * 8;
+            (bits as f64 / seconds) / bitrate as f64 * 100.0
+        });
+
+    CanInterfaceRates {
+        rx_frames_per_second: rx_frames as f64 / seconds,
+        tx_frames_per_second: tx_frames as f64 / seconds,
+        bus_load_percent,
+    }
+}
+
+async fn read_can_interface_statistics() -> Result<Vec<CanInterfaceStatistics>, Error> {
+    let output = Command::new("ip")
+        .args(["-details", "-statistics", "-json", "link", "show"])
+        .output()
+        .await
+        .map_err(|cause| Error::CommandLineProgramExecution { command: "ip".to_string(), cause })?;
+
+    if output.status.success().not() {
+        return Err(Error::Other { message: format!("Listing network interfaces failed: {:?}", String::from_utf8_lossy(&output.stderr).trim()) });
+    }
+
+    parse_ip_link_statistics(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[derive(Deserialize)]
+struct IpLink {
+    ifname: String,
+    #[serde(default)]
+    link_type: String,
+    linkinfo: Option<IpLinkInfo>,
+    stats64: Option<IpLinkStatistics>,
+}
+
+#[derive(Deserialize)]
+struct IpLinkInfo {
+    info_data: Option<serde_json::Value>,
+    info_xstats: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct IpLinkStatistics {
+    rx: CanDirectionStatistics,
+    tx: CanDirectionStatistics,
+}
+
+/// Parses the output of `ip -details -statistics -json link show`, keeping only CAN interfaces.
+fn parse_ip_link_statistics(output: &str) -> Result<Vec<CanInterfaceStatistics>, Error> {
+    let links: Vec<IpLink> = serde_json::from_str(output)
+        .map_err(|cause| Error::Other { message: format!("Failed to parse output of 'ip link show': {cause}") })?;
+
+    let interfaces = links.into_iter()
+        .filter(|link| link.link_type == "can")
+        .map(|link| {
+            let (bitrate, bus_off) = match &link.linkinfo {
+                Some(linkinfo) => (
+                    linkinfo.info_data.as_ref()
+                        .and_then(|info_data| info_data.pointer("/bittiming/bitrate"))
+                        .and_then(serde_json::Value::as_u64),
+                    linkinfo.info_xstats.as_ref()
+                        .and_then(|info_xstats| info_xstats.get("bus_off"))
+                        .and_then(serde_json::Value::as_u64)
+                        .unwrap_or_default(),
+                ),
+                None => (None, 0),
+            };
+            let (rx, tx) = link.stats64
+                .map(|stats| (stats.rx, stats.tx))
+                .unwrap_or_default();
+
+            CanInterfaceStatistics { name: link.ifname, bitrate, rx, tx, bus_off }
+        })
+        .collect();
+
+    Ok(interfaces)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CanGatewayRouteStatistics {
+    pub source: String,
+    pub destination: String,
+    pub can_fd: bool,
+    pub handled: u64,
+    pub dropped: u64,
+}
+
+async fn read_can_gateway_statistics() -> Result<Vec<CanGatewayRouteStatistics>, Error> {
+    let output = Command::new("cangw")
+        .arg("-L")
+        .output()
+        .await
+        .map_err(|cause| Error::CommandLineProgramExecution { command: "cangw".to_string(), cause })?;
+
+    // cangw -L returns non-zero exit code despite succeeding, so we don't check it here
+
+    Ok(parse_cangw_statistics(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parses the output of `cangw -L`, which lists each route with its frame counters in a trailing comment.
+fn parse_cangw_statistics(output: &str) -> Vec<CanGatewayRouteStatistics> {
+    cangw_route_pattern().captures_iter(output)
+        .map(|captures| captures.extract())
+        .map(|(_, [source, destination, can_fd_flag, handled, dropped])| CanGatewayRouteStatistics {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            can_fd: can_fd_flag.trim() == "-X",
+            handled: handled.parse().unwrap_or_default(),
+            dropped: dropped.parse().unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Statistics of the UDP socket of a tunnel, via which Cannelloni exchanges CAN frames with another peer.
+/// Each packet may carry multiple CAN frames.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CanTunnelStatistics {
+    pub local_address: SocketAddr,
+    /// Unspecified for the sockets of Cannelloni servers, which are not connected to a remote address.
+    pub remote_address: SocketAddr,
+    pub dropped: u64,
+}
+
+/// Reads the number of dropped packets of the UDP sockets, which are opened by Cannelloni processes.
+fn read_can_tunnel_statistics() -> Result<Vec<CanTunnelStatistics>, Error> {
+    let socket_inodes = find_socket_inodes_of_process(CANNELLONI_PROCESS_NAME)?;
+    if socket_inodes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut tunnels = Vec::new();
+    for path in ["/proc/net/udp", "/proc/net/udp6"] {
+        let content = fs::read_to_string(path)
+            .map_err(|cause| Error::Other { message: format!("Failed to read UDP sockets: {cause}") })?;
+
+        tunnels.extend(
+            parse_proc_net_udp(&content).into_iter()
+                .filter(|socket| socket_inodes.contains(&socket.inode))
+                .map(|socket| CanTunnelStatistics {
+                    local_address: socket.local_address,
+                    remote_address: socket.remote_address,
+                    dropped: socket.drops,
+                })
+        );
+    }
+    Ok(tunnels)
+}
+
+/// Determines the inodes of the sockets, which are opened by processes with the given name.
+/// Processes, which terminate while being inspected, are skipped.
+fn find_socket_inodes_of_process(process_name: &str) -> Result<HashSet<u64>, Error> {
+    let processes = fs::read_dir("/proc")
+        .map_err(|cause| Error::Other { message: format!("Failed to list processes: {cause}") })?;
+
+    let mut socket_inodes = HashSet::new();
+    for process in processes.flatten() {
+        let is_matching_process = fs::read_to_string(process.path().join("comm"))
+            .is_ok_and(|comm| comm.trim() == process_name);
+        if is_matching_process.not() {
+            continue;
+        }
+
+        let Ok(file_descriptors) = fs::read_dir(process.path().join("fd")) else { continue };
+        for file_descriptor in file_descriptors.flatten() {
+            if let Some(inode) = fs::read_link(file_descriptor.path()).ok()
+                .and_then(|target| parse_socket_inode(&target.to_string_lossy())) {
+                socket_inodes.insert(inode);
+            }
+        }
+    }
+    Ok(socket_inodes)
+}
+
+/// Parses the target of a file descriptor link of a socket, e.g. `socket:[12345]`.
+fn parse_socket_inode(link_target: &str) -> Option<u64> {
+    link_target.strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse().ok()
+}
+
+#[derive(Debug, PartialEq)]
+struct UdpSocketStatistics {
+    local_address: SocketAddr,
+    remote_address: SocketAddr,
+    inode: u64,
+    drops: u64,
+}
+
+/// Parses the content of `/proc/net/udp` or `/proc/net/udp6`, skipping lines which cannot be parsed.
+fn parse_proc_net_udp(content: &str) -> Vec<UdpSocketStatistics> {
+    content.lines()
+        .skip(1) // header
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            Some(UdpSocketStatistics {
+                local_address: parse_proc_net_address(fields.get(1)?)?,
+                remote_address: parse_proc_net_address(fields.get(2)?)?,
+                inode: fields.get(9)?.parse().ok()?,
+                drops: fields.get(12)?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Parses an address of `/proc/net/udp` or `/proc/net/udp6`, e.g. `0100007F:1F90`.
+/// The IP address is printed as hexadecimal 32-bit words in host byte order, the port in network byte order.
+fn parse_proc_net_address(value: &str) -> Option<SocketAddr> {
+    let (ip, port) = value.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let parse_word = |word: &str| u32::from_str_radix(word, 16).ok().map(u32::to_ne_bytes);
+
+    let ip = match ip.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(parse_word(ip)?)),
+        32 => {
+            let mut octets = [0u8; 16];
+            for (index, chunk) in octets.chunks_exact_mut(4).enumerate() {
+                chunk.copy_from_slice(&parse_word(ip.get(index * 8..(index + 1) * 8)?)?);
+            }
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failure while invoking command line program '{command}': {cause}")]
+    CommandLineProgramExecution { command: String, cause: std::io::Error },
+    #[error("{message}")]
+    Other { message: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_statistics_of_can_interfaces() -> anyhow::Result<()> {
+        let output = r#"[
+            {"ifindex":1,"ifname":"lo","link_type":"loopback","stats64":{"rx":{"bytes":100,"packets":1,"errors":0,"dropped":0},"tx":{"bytes":100,"packets":1,"errors":0,"dropped":0}}},
+            {"ifindex":4,"ifname":"can0","link_type":"can","linkinfo":{"info_kind":"can","info_data":{"state":"ERROR-ACTIVE","bittiming":{"bitrate":500000,"sample_point":"0.875"}},"info_xstats":{"restarts":1,"bus_error":3,"arbitration_lost":0,"error_warning":2,"error_passive":1,"bus_off":1}},"stats64":{"rx":{"bytes":800,"packets":100,"errors":3,"dropped":2,"over_errors":0},"tx":{"bytes":400,"packets":50,"errors":1,"dropped":0,"carrier_errors":0}}},
+            {"ifindex":5,"ifname":"br-opendut-can","link_type":"can","linkinfo":{"info_kind":"vcan"},"stats64":{"rx":{"bytes":16,"packets":2,"errors":0,"dropped":0},"tx":{"bytes":8,"packets":1,"errors":0,"dropped":1}}}
+        ]"#;
+
+        let result = parse_ip_link_statistics(output)?;
+
+        assert_that!(result, elements_are![
+            eq(&CanInterfaceStatistics {
+                name: String::from("can0"),
+                bitrate: Some(500000),
+                rx: CanDirectionStatistics { bytes: 800, packets: 100, errors: 3, dropped: 2 },
+                tx: CanDirectionStatistics { bytes: 400, packets: 50, errors: 1, dropped: 0 },
+                bus_off: 1,
+            }),
+            eq(&CanInterfaceStatistics {
+                name: String::from("br-opendut-can"),
+                bitrate: None,
+                rx: CanDirectionStatistics { bytes: 16, packets: 2, errors: 0, dropped: 0 },
+                tx: CanDirectionStatistics { bytes: 8, packets: 1, errors: 0, dropped: 1 },
+                bus_off: 0,
+            }),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_statistics_of_can_gateway_routes() {
+        let output = indoc!("
+            cangw -A -s br-opendut-can -d can0 -X -e -l 2 # 10 handled 1 dropped 0 deleted
+            cangw -A -s can0 -d br-opendut-can -e -l 2 # 20 handled 0 dropped 0 deleted
+        ");
+
+        let result = parse_cangw_statistics(output);
+
+        assert_that!(result, elements_are![
+            eq(&CanGatewayRouteStatistics { source: String::from("br-opendut-can"), destination: String::from("can0"), can_fd: true, handled: 10, dropped: 1 }),
+            eq(&CanGatewayRouteStatistics { source: String::from("can0"), destination: String::from("br-opendut-can"), can_fd: false, handled: 20, dropped: 0 }),
+        ]);
+    }
+
+    #[test]
+    fn should_calculate_frame_rates_and_bus_load() {
+        let previous = CanInterfaceStatistics {
+            name: String::from("can0"),
+            bitrate: Some(500_000),
+            ..Default::default()
+        };
+        let current = CanInterfaceStatistics {
+            rx: CanDirectionStatistics { bytes: 8_000, packets: 1_000, ..Default::default() },
+            tx: CanDirectionStatistics { bytes: 0, packets: 1_000, ..Default::default() },
+            ..previous.clone()
+        };
+
+        let result = calculate_rates(&previous, &current, Duration::from_secs(2));
+
+        assert_that!(result.rx_frames_per_second, eq(500.0));
+        assert_that!(result.tx_frames_per_second, eq(500.0));
+        let expected_bits_per_second = (2_000.0 * 47.0 + 8_000.0 * 8.0) / 2.0;
+        assert_that!(result.bus_load_percent, some(eq(expected_bits_per_second / 500_000.0 * 100.0)));
+    }
+
+    #[test]
+    fn should_treat_reset_counters_as_starting_from_zero() {
+        let previous = CanInterfaceStatistics {
+            name: String::from("vcan0"),
+            rx: CanDirectionStatistics { packets: 1_000, ..Default::default() },
+            ..Default::default()
+        };
+        let current = CanInterfaceStatistics {
+            rx: CanDirectionStatistics { packets: 10, ..Default::default() },
+            ..previous.clone()
+        };
+
+        let result = calculate_rates(&previous, &current, Duration::from_secs(1));
+
+        assert_that!(result.rx_frames_per_second, eq(10.0));
+        assert_that!(result.bus_load_percent, none());
+    }
+
+    #[test]
+    fn should_discard_samples_of_removed_interfaces() {
+        let can0 = CanInterfaceStatistics { name: String::from("can0"), ..Default::default() };
+        let can1 = CanInterfaceStatistics { name: String::from("can1"), ..Default::default() };
+        let mut previous_samples = HashMap::new();
+        let sampled_at = Instant::now();
+
+        let rates = update_samples(&mut previous_samples, &[can0.clone(), can1], sampled_at);
+        assert_that!(rates, empty());
+
+        let rates = update_samples(&mut previous_samples, &[can0], sampled_at + Duration::from_secs(1));
+        assert_that!(rates.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>(), elements_are![eq(&"can0")]);
+        assert_that!(previous_samples.keys().map(String::as_str).collect::<Vec<_>>(), elements_are![eq(&"can0")]);
+    }
+
+    #[test]
+    fn should_parse_socket_inodes() {
+        assert_that!(parse_socket_inode("socket:[12345]"), some(eq(12345)));
+        assert_that!(parse_socket_inode("/dev/null"), none());
+        assert_that!(parse_socket_inode("pipe:[12345]"), none());
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn should_parse_statistics_of_udp_sockets() -> anyhow::Result<()> {
+        let udp = indoc!("
+               sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+              123: 0100007F:2710 00000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 4711 2 0000000000000000 3
+              124: 0200080A:C350 0100080A:2710 01 00000000:00000000 00:00000000 00000000     0        0 4712 2 0000000000000000 0
+        ");
+        let udp6 = indoc!("
+               sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+              125: 00000000000000000000000001000000:2710 00000000000000000000000000000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 4713 2 0000000000000000 7
+        ");
+
+        assert_that!(parse_proc_net_udp(udp), elements_are![
+            eq(&UdpSocketStatistics { local_address: "127.0.0.1:10000".parse()?, remote_address: "0.0.0.0:0".parse()?, inode: 4711, drops: 3 }),
+            eq(&UdpSocketStatistics { local_address: "10.8.0.2:50000".parse()?, remote_address: "10.8.0.1:10000".parse()?, inode: 4712, drops: 0 }),
+        ]);
+        assert_that!(parse_proc_net_udp(udp6), elements_are![
+            eq(&UdpSocketStatistics { local_address: "[::1]:10000".parse()?, remote_address: "[::]:0".parse()?, inode: 4713, drops: 7 }),
+        ]);
+        Ok(())
+    }
+}
@@ -0,0 +1,230 @@
+use std::future::Future;
+use std::net::IpAddr;
+use std::ops::Not;
+use std::time::{Duration, Instant};
+
+use tracing::{debug, trace};
+
+use opendut_types::cluster::diagnostics::{LatencyDiagnostics, NetworkDiagnosticsOptions, PeerLinkDiagnostics, ThroughputDiagnostics};
+use opendut_types::cluster::PeerClusterAssignment;
+use opendut_types::peer::PeerId;
+
+use crate::service::network_metrics::throughput;
+use crate::service::network_metrics::throughput::ThroughputOptions;
+
+/// Largest MTU which is probed during path MTU discovery, as commonly used for Ethernet.
+const MAX_PROBED_MTU: u16 = 1500;
+/// Smallest MTU which every IPv4 host must support, as per RFC 791.
+const MIN_PROBED_MTU_IPV4: u16 = 576;
+/// Smallest MTU which every link of an IPv6 path must support, as per RFC 8200.
+const MIN_PROBED_MTU_IPV6: u16 = 1280;
+
+/// Runs the requested tests from this peer to each of the targets, one after another.
+pub async fn diagnose_links(
+    self_id: PeerId,
+    targets: Vec<PeerClusterAssignment>,
+    options: &NetworkDiagnosticsOptions,
+    throughput_port: u16,
+) -> Vec<PeerLinkDiagnostics> {
+    let mut links = Vec::with_capacity(targets.len());
+
+    for target in targets {
+        debug!("Diagnosing network link to peer <{}> with IP {}.", target.peer_id, target.vpn_address);
+        let mut errors = Vec::new();
+
+        let latency = if options.ping_count > 0 {
+            measure_latency(target.vpn_address, options.ping_count, options.ping_timeout).await
+                .inspect_err(|cause| errors.push(cause.clone()))
+                .ok()
+        } else {
+            None
+        };
+
+        let throughput = if options.throughput_duration.is_zero().not() {
+            let throughput_options = ThroughputOptions {
+                interval: Duration::ZERO,
+                duration: options.throughput_duration,
+                port: throughput_port,
+                target_bandwidth_kbit_per_second: options.target_bandwidth_kbit_per_second,
+                tcp_enabled: true,
+                udp_enabled: true,
+            };
+            measure_throughput(target.vpn_address, &throughput_options).await
+                .inspect_err(|cause| errors.push(cause.clone()))
+                .ok()
+        } else {
+            None
+        };
+
+        let path_mtu = if options.path_mtu_discovery {
+            discover_path_mtu(target.vpn_address, options.ping_timeout).await
+                .inspect_err(|cause| errors.push(cause.clone()))
+                .ok()
+        } else {
+            None
+        };
+
+        links.push(PeerLinkDiagnostics {
+            source: self_id,
+            target: target.peer_id,
+            latency,
+            throughput,
+            path_mtu,
+            errors,
+        });
+    }
+    links
+}
+
+async fn measure_latency(address: IpAddr, count: u32, timeout: Duration) -> Result<LatencyDiagnostics, String> {
+    let mut round_trip_times = Vec::with_capacity(count as usize);
+
+    for _ in 0..count {
+        match ping(address, 32, timeout).await {
+            Ok(round_trip_time) => round_trip_times.push(round_trip_time),
+            Err(cause) => trace!("Ping to {address} failed: {cause}"),
+        }
+    }
+
+    let received = round_trip_times.len() as u32;
+    if received == 0 {
+        return Err(format!("None of the {count} pings to {address} were answered within {} ms.", timeout.as_millis()));
+    }
+
+    Ok(LatencyDiagnostics {
+        sent: count,
+        received,
+        min: round_trip_times.iter().min().copied().unwrap_or_default(),
+        avg: round_trip_times.iter().sum::<Duration>() / received,
+        max: round_trip_times.iter().max().copied().unwrap_or_default(),
+    })
+}
+
+async fn measure_throughput(address: IpAddr, options: &ThroughputOptions) -> Result<ThroughputDiagnostics, String> {
+    let measurement = throughput::client::measure(address, options).await
+        .map_err(|cause| cause.to_string())?;
+
+    let udp = measurement.udp;
+    Ok(ThroughputDiagnostics {
+        tcp_send_megabits_per_second: measurement.tcp_send.map(|report| report.megabits_per_second()).unwrap_or_default(),
+        tcp_receive_megabits_per_second: measurement.tcp_receive.map(|report| report.megabits_per_second()).unwrap_or_default(),
+        udp_send_megabits_per_second: udp.map(|udp| udp.report.transfer.megabits_per_second()).unwrap_or_default(),
+        udp_jitter: udp.map(|udp| udp.report.jitter).unwrap_or_default(),
+        udp_packet_loss_percent: udp.map(|udp| udp.report.packet_loss_percent(udp.sent_datagrams)).unwrap_or_default(),
+    })
+}
+
+/// Determines the largest packet size which reaches the target without fragmentation,
+/// by sending pings with the "don't fragment" flag set.
+async fn discover_path_mtu(address: IpAddr, timeout: Duration) -> Result<u16, String> {
+    let header_size: u16 = match address {
+        IpAddr::V4(_) => 20 + 8, //IPv4 header + ICMP header
+        IpAddr::V6(_) => 40 + 8, //IPv6 header + ICMPv6 header
+    };
+
+    let min_probed_mtu = min_probed_mtu(address);
+
+    let path_mtu = find_largest_passing_size(min_probed_mtu, MAX_PROBED_MTU, |mtu| async move {
+        ping(address, usize::from(mtu - header_size), timeout).await.is_ok()
+    }).await;
+
+    path_mtu.ok_or_else(|| format!("Pings to {address} with a packet size of {min_probed_mtu} bytes did not succeed."))
+}
+
+fn min_probed_mtu(address: IpAddr) -> u16 {
+    match address {
+        IpAddr::V4(_) => MIN_PROBED_MTU_IPV4,
+        IpAddr::V6(_) => MIN_PROBED_MTU_IPV6,
+    }
+}
+
+/// Binary search for the largest size within the given bounds, for which the probe succeeds.
+/// Returns `None`, if the probe fails already for the lower bound.
+async fn find_largest_passing_size<Probe, ProbeFuture>(min: u16, max: u16, probe: Probe) -> Option<u16>
+where
+    Probe: Fn(u16) -> ProbeFuture,
+    ProbeFuture: Future<Output=bool>,
+{
+    if probe(max).await {
+        return Some(max);
+    }
+    if probe(min).await.not() {
+        return None;
+    }
+
+    let (mut passing, mut failing) = (min, max);
+    while failing - passing > 1 {
+        let size = passing + (failing - passing) / 2;
+        if probe(size).await {
+            passing = size;
+        } else {
+            failing = size;
+        }
+    }
+    Some(passing)
+}
+
+async fn ping(address: IpAddr, payload_size: usize, timeout: Duration) -> Result<Duration, String> {
+    tokio::task::spawn_blocking(move || {
+        let payload = vec![0u8; payload_size];
+        let options = ping_rs::PingOptions { ttl: 128, dont_fragment: true };
+
+        let start = Instant::now();
+        ping_rs::send_ping(&address, timeout, &payload, Some(&options))
+            .map(|_| start.elapsed())
+            .map_err(|cause| format!("{cause:?}"))
+    }).await
+        .map_err(|cause| cause.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_find_the_largest_passing_size() {
+        let probes = AtomicUsize::new(0);
+        let probe = |size: u16| {
+            probes.fetch_add(1, Ordering::Relaxed);
+            async move { size <= 1420 }
+        };
+
+        let result = find_largest_passing_size(MIN_PROBED_MTU_IPV4, MAX_PROBED_MTU, probe).await;
+
+        assert_that!(result, some(eq(1420)));
+        assert_that!(probes.load(Ordering::Relaxed), le(12));
+    }
+
+    #[tokio::test]
+    async fn should_return_the_upper_bound_if_it_passes() {
+        let result = find_largest_passing_size(MIN_PROBED_MTU_IPV4, MAX_PROBED_MTU, |_| async { true }).await;
+        assert_that!(result, some(eq(MAX_PROBED_MTU)));
+    }
+
+    #[tokio::test]
+    async fn should_return_none_if_the_lower_bound_fails() {
+        let result = find_largest_passing_size(MIN_PROBED_MTU_IPV4, MAX_PROBED_MTU, |_| async { false }).await;
+        assert_that!(result, none());
+    }
+
+    #[tokio::test]
+    async fn should_probe_ipv6_targets_from_the_ipv6_minimum_mtu() {
+        let address = IpAddr::from(std::net::Ipv6Addr::LOCALHOST);
+        let probed_sizes = std::sync::Mutex::new(Vec::new());
+        let probe = |size: u16| {
+            probed_sizes.lock().unwrap().push(size);
+            async move { size <= 1000 }
+        };
+
+        let result = find_largest_passing_size(min_probed_mtu(address), MAX_PROBED_MTU, probe).await;
+
+        assert_that!(min_probed_mtu(address), eq(1280));
+        assert_that!(min_probed_mtu(IpAddr::from(std::net::Ipv4Addr::LOCALHOST)), eq(576));
+        assert_that!(result, none());
+        assert_that!(probed_sizes.into_inner().unwrap(), eq(&vec![MAX_PROBED_MTU, MIN_PROBED_MTU_IPV6]));
+    }
+}
@@ -1,2 +1,4 @@
+pub mod can;
+pub mod diagnostics;
 pub mod ping;
-pub mod rperf;
+pub mod throughput;
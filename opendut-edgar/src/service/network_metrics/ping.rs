@@ -6,7 +6,7 @@ use tokio::time::sleep;
 use tracing::{error, trace};
 use opendut_types::cluster::PeerClusterAssignment;
 
-pub async fn cluster_ping(peers: Vec<PeerClusterAssignment>, ping_interval_ms: Duration) {
+pub async fn cluster_ping(peers: Vec<PeerClusterAssignment>, ping_interval_ms: Duration, timeout: Duration) {
     let meter = global::meter(opendut_util::telemetry::DEFAULT_METER_NAME);
     let rtt = meter.f64_gauge("round_trip_time").init();
 
@@ -17,7 +17,6 @@ pub async fn cluster_ping(peers: Vec<PeerClusterAssignment>, ping_interval_ms: D
         let options = ping_rs::PingOptions { ttl: 128, dont_fragment: true };
         loop {
             sleep(ping_interval_ms).await;
-            let timeout = Duration::from_secs(1); //TODO make configurable
             for peer in peers.clone() {
                 let remote_address = peer.vpn_address;
                 let result = ping_rs::send_ping(&remote_address, timeout, &data, Some(&options));
@@ -0,0 +1,161 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+use opentelemetry::{global, KeyValue};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::time::sleep;
+use tracing::{debug, error};
+use uuid::Uuid;
+
+use opendut_types::cluster::PeerClusterAssignment;
+
+use crate::service::network_metrics::throughput::{receive_tcp, send_tcp, Pacer, TestKind, TestRequest, ThroughputError, ThroughputOptions, TransferReport, UdpDatagramHeader, UdpReport, UDP_DATAGRAM_SIZE, UDP_GRACE_PERIOD};
+
+/// Additional time granted to a test beyond its duration, before it is aborted.
+const TIMEOUT_MARGIN: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Debug)]
+pub struct ThroughputMeasurement {
+    pub tcp_send: Option<TransferReport>,
+    pub tcp_receive: Option<TransferReport>,
+    pub udp: Option<UdpMeasurement>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct UdpMeasurement {
+    pub sent_datagrams: u64,
+    pub report: UdpReport,
+}
+
+pub async fn launch_throughput_measurements(peers: Vec<PeerClusterAssignment>, options: ThroughputOptions) {
+    let meter = global::meter(opendut_util::telemetry::DEFAULT_METER_NAME);
+
+    let megabits_second_send = meter.f64_gauge("megabits_second_send").init();
+    let megabits_second_receive = meter.f64_gauge("megabits_second_receive").init();
+    let udp_megabits_second_send = meter.f64_gauge("udp_megabits_second_send").init();
+    let udp_jitter_milliseconds = meter.f64_gauge("udp_jitter_milliseconds").init();
+    let udp_packet_loss_percent = meter.f64_gauge("udp_packet_loss_percent").init();
+
+    tokio::spawn(async move {
+        loop {
+            sleep(options.interval).await;
+            for peer in peers.iter() { //measure one peer after another, so the measurements don't compete for bandwidth
+                let attributes = [
+                    KeyValue::new("peer_id", peer.peer_id.to_string()),
+                    KeyValue::new("peer_ip_address", peer.vpn_address.to_string()),
+                ];
+
+                match measure(peer.vpn_address, &options).await {
+                    Ok(measurement) => {
+                        if let Some(tcp_send) = measurement.tcp_send {
+                            megabits_second_send.record(tcp_send.megabits_per_second(), &attributes);
+                        }
+                        if let Some(tcp_receive) = measurement.tcp_receive {
+                            megabits_second_receive.record(tcp_receive.megabits_per_second(), &attributes);
+                        }
+                        if let Some(udp) = measurement.udp {
+                            udp_megabits_second_send.record(udp.report.transfer.megabits_per_second(), &attributes);
+                            udp_jitter_milliseconds.record(udp.report.jitter.as_secs_f64() * 1000.0, &attributes);
+                            udp_packet_loss_percent.record(udp.report.packet_loss_percent(udp.sent_datagrams), &attributes);
+                        }
+                        debug!("Measured throughput to peer {peer_id} with IP {peer_ip}: {measurement:?}", peer_id=peer.peer_id, peer_ip=peer.vpn_address);
+                    }
+                    Err(cause) => error!("Error while measuring throughput to peer {peer_id} with IP {peer_ip}: {cause}", peer_id=peer.peer_id, peer_ip=peer.vpn_address),
+                }
+            }
+        }
+    });
+}
+
+pub async fn measure(address: IpAddr, options: &ThroughputOptions) -> Result<ThroughputMeasurement, ThroughputError> {
+    let address = SocketAddr::new(address, options.port);
+    let timeout = options.duration + UDP_GRACE_PERIOD + TIMEOUT_MARGIN;
+
+    let tcp_send = if options.tcp_enabled {
+        Some(with_timeout(timeout, measure_tcp_send(address, options)).await?)
+    } else {
+        None
+    };
+    let tcp_receive = if options.tcp_enabled {
+        Some(with_timeout(timeout, measure_tcp_receive(address, options)).await?)
+    } else {
+        None
+    };
+    let udp = if options.udp_enabled {
+        Some(with_timeout(timeout, measure_udp_send(address, options)).await?)
+    } else {
+        None
+    };
+
+    Ok(ThroughputMeasurement { tcp_send, tcp_receive, udp })
+}
+
+async fn measure_tcp_send(address: SocketAddr, options: &ThroughputOptions) -> Result<TransferReport, ThroughputError> {
+    let mut stream = connect(address, TestKind::TcpSend, options).await?;
+
+    send_tcp(&mut stream, options.duration, options.target_bandwidth_kbit_per_second).await?;
+    stream.shutdown().await?;
+
+    TransferReport::read_from(&mut stream).await
+}
+
+async fn measure_tcp_receive(address: SocketAddr, options: &ThroughputOptions) -> Result<TransferReport, ThroughputError> {
+    let mut stream = connect(address, TestKind::TcpReceive, options).await?;
+
+    receive_tcp(&mut stream).await
+}
+
+async fn measure_udp_send(address: SocketAddr, options: &ThroughputOptions) -> Result<UdpMeasurement, ThroughputError> {
+    let test_id = Uuid::new_v4().as_u64_pair().0;
+    let mut stream = connect_with_id(address, TestKind::UdpSend, options, test_id).await?;
+    stream.read_u8().await?; //wait until the server is ready to receive
+
+    let local_address = match address {
+        SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    };
+    let socket = UdpSocket::bind(SocketAddr::new(local_address, 0)).await?;
+    socket.connect(address).await?;
+
+    let mut buffer = [0u8; UDP_DATAGRAM_SIZE];
+    let pacer = Pacer::new(options.target_bandwidth_kbit_per_second);
+    let mut sent_datagrams = 0u64;
+    let mut sent_bytes = 0u64;
+
+    while pacer.elapsed() < options.duration {
+        let header = UdpDatagramHeader { test_id, sequence_number: sent_datagrams, sent_after: pacer.elapsed() };
+        header.encode_into(&mut buffer);
+        sent_bytes += socket.send(&buffer).await? as u64;
+        sent_datagrams += 1;
+        pacer.wait_for(sent_bytes).await;
+    }
+
+    sleep(UDP_GRACE_PERIOD).await;
+    stream.write_u8(1).await?; //finished sending
+    stream.flush().await?;
+
+    let report = UdpReport::read_from(&mut stream).await?;
+    Ok(UdpMeasurement { sent_datagrams, report })
+}
+
+async fn connect(address: SocketAddr, kind: TestKind, options: &ThroughputOptions) -> Result<TcpStream, ThroughputError> {
+    connect_with_id(address, kind, options, 0).await
+}
+
+async fn connect_with_id(address: SocketAddr, kind: TestKind, options: &ThroughputOptions, test_id: u64) -> Result<TcpStream, ThroughputError> {
+    let mut stream = TcpStream::connect(address).await?;
+    let request = TestRequest {
+        kind,
+        duration: options.duration,
+        bandwidth_kbit_per_second: options.target_bandwidth_kbit_per_second,
+        test_id,
+    };
+    request.write_to(&mut stream).await?;
+    Ok(stream)
+}
+
+async fn with_timeout<T>(timeout: Duration, future: impl std::future::Future<Output=Result<T, ThroughputError>>) -> Result<T, ThroughputError> {
+    tokio::time::timeout(timeout, future).await
+        .map_err(|_| ThroughputError::Timeout)?
+}
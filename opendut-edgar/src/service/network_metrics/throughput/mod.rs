@@ -0,0 +1,399 @@
+//! Measures TCP and UDP throughput, as well as UDP jitter and packet loss, between the peers of a cluster.
+//!
+//! Each EDGAR runs a [server] on a fixed port. The [client] of another peer connects via TCP and sends a [TestRequest],
+//! after which test data is transmitted for the requested duration, paced to the target bandwidth.
+//! The receiving side then reports the measured values back, so both directions can be measured from the client.
+
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::time::Instant;
+
+pub mod client;
+pub mod server;
+
+const PROTOCOL_VERSION: u8 = 1;
+
+/// Size of the chunks written to a TCP stream.
+const TCP_CHUNK_SIZE: usize = 16 * 1024;
+
+/// Size of the UDP datagrams. Small enough to not be fragmented within the VPN tunnel.
+const UDP_DATAGRAM_SIZE: usize = 1200;
+
+/// Time to wait for in-flight UDP datagrams, before the receiver is asked for its report.
+const UDP_GRACE_PERIOD: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Debug)]
+pub struct ThroughputOptions {
+    pub interval: Duration,
+    pub duration: Duration,
+    pub port: u16,
+    pub target_bandwidth_kbit_per_second: u64,
+    pub tcp_enabled: bool,
+    pub udp_enabled: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TestKind {
+    /// Client sends via TCP, server receives.
+    TcpSend,
+    /// Server sends via TCP, client receives.
+    TcpReceive,
+    /// Client sends via UDP, server receives.
+    UdpSend,
+}
+impl TestKind {
+    fn to_byte(self) -> u8 {
+        match self {
+            TestKind::TcpSend => 0,
+            TestKind::TcpReceive => 1,
+            TestKind::UdpSend => 2,
+        }
+    }
+    fn from_byte(value: u8) -> Result<Self, ThroughputError> {
+        match value {
+            0 => Ok(TestKind::TcpSend),
+            1 => Ok(TestKind::TcpReceive),
+            2 => Ok(TestKind::UdpSend),
+            other => Err(ThroughputError::Protocol { message: format!("Unknown test kind <{other}>.") }),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct TestRequest {
+    kind: TestKind,
+    duration: Duration,
+    bandwidth_kbit_per_second: u64,
+    test_id: u64,
+}
+impl TestRequest {
+    async fn write_to(&self, writer: &mut (impl AsyncWrite + Unpin)) -> Result<(), ThroughputError> {
+        writer.write_u8(PROTOCOL_VERSION).await?;
+        writer.write_u8(self.kind.to_byte()).await?;
+        writer.write_u64(self.duration.as_millis() as u64).await?;
+        writer.write_u64(self.bandwidth_kbit_per_second).await?;
+        writer.write_u64(self.test_id).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+
+    async fn read_from(reader: &mut (impl AsyncRead + Unpin)) -> Result<Self, ThroughputError> {
+        let version = reader.read_u8().await?;
+        if version != PROTOCOL_VERSION {
+            return Err(ThroughputError::Protocol { message: format!("Unsupported protocol version <{version}>, expected <{PROTOCOL_VERSION}>.") });
+        }
+        let kind = TestKind::from_byte(reader.read_u8().await?)?;
+        let duration = Duration::from_millis(reader.read_u64().await?);
+        let bandwidth_kbit_per_second = reader.read_u64().await?;
+        let test_id = reader.read_u64().await?;
+        Ok(Self { kind, duration, bandwidth_kbit_per_second, test_id })
+    }
+}
+
+/// Amount of data received within a time span.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransferReport {
+    pub bytes: u64,
+    pub elapsed: Duration,
+}
+impl TransferReport {
+    pub fn megabits_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.bytes as f64 * 8.0 / seconds / 1_000_000.0
+        } else {
+            0.0
+        }
+    }
+
+    async fn write_to(&self, writer: &mut (impl AsyncWrite + Unpin)) -> Result<(), ThroughputError> {
+        writer.write_u64(self.bytes).await?;
+        writer.write_u64(self.elapsed.as_micros() as u64).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+
+    async fn read_from(reader: &mut (impl AsyncRead + Unpin)) -> Result<Self, ThroughputError> {
+        let bytes = reader.read_u64().await?;
+        let elapsed = Duration::from_micros(reader.read_u64().await?);
+        Ok(Self { bytes, elapsed })
+    }
+}
+
+/// Statistics of the datagrams received during a UDP test.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct UdpReport {
+    pub transfer: TransferReport,
+    pub received_datagrams: u64,
+    pub jitter: Duration,
+}
+impl UdpReport {
+    pub fn packet_loss_percent(&self, sent_datagrams: u64) -> f64 {
+        if sent_datagrams == 0 {
+            return 0.0;
+        }
+        let lost_datagrams = sent_datagrams.saturating_sub(self.received_datagrams);
+        lost_datagrams as f64 / sent_datagrams as f64 * 100.0
+    }
+
+    async fn write_to(&self, writer: &mut (impl AsyncWrite + Unpin)) -> Result<(), ThroughputError> {
+        writer.write_u64(self.received_datagrams).await?;
+        writer.write_u64(self.jitter.as_micros() as u64).await?;
+        self.transfer.write_to(writer).await
+    }
+
+    async fn read_from(reader: &mut (impl AsyncRead + Unpin)) -> Result<Self, ThroughputError> {
+        let received_datagrams = reader.read_u64().await?;
+        let jitter = Duration::from_micros(reader.read_u64().await?);
+        let transfer = TransferReport::read_from(reader).await?;
+        Ok(Self { transfer, received_datagrams, jitter })
+    }
+}
+
+/// Header of each UDP datagram, followed by padding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct UdpDatagramHeader {
+    test_id: u64,
+    sequence_number: u64,
+    sent_after: Duration,
+}
+impl UdpDatagramHeader {
+    const SIZE: usize = 3 * std::mem::size_of::<u64>();
+
+    fn encode_into(&self, buffer: &mut [u8]) {
+        buffer[0..8].copy_from_slice(&self.test_id.to_be_bytes());
+        buffer[8..16].copy_from_slice(&self.sequence_number.to_be_bytes());
+        buffer[16..24].copy_from_slice(&(self.sent_after.as_micros() as u64).to_be_bytes());
+    }
+
+    fn decode(buffer: &[u8]) -> Option<Self> {
+        if buffer.len() < Self::SIZE {
+            return None;
+        }
+        let read_u64 = |range: std::ops::Range<usize>| u64::from_be_bytes(buffer[range].try_into().expect("Slice should have a length of 8 bytes."));
+        Some(Self {
+            test_id: read_u64(0..8),
+            sequence_number: read_u64(8..16),
+            sent_after: Duration::from_micros(read_u64(16..24)),
+        })
+    }
+}
+
+/// Accumulates the statistics of received UDP datagrams.
+/// The jitter is calculated as the smoothed mean deviation of the transit time, as specified in RFC 3550.
+#[derive(Debug)]
+struct UdpReceiverStatistics {
+    first_received_at: Option<Instant>,
+    last_received_at: Option<Instant>,
+    last_transit: Option<f64>,
+    jitter_micros: f64,
+    received_datagrams: u64,
+    received_bytes: u64,
+}
+impl UdpReceiverStatistics {
+    fn new() -> Self {
+        Self {
+            first_received_at: None,
+            last_received_at: None,
+            last_transit: None,
+            jitter_micros: 0.0,
+            received_datagrams: 0,
+            received_bytes: 0,
+        }
+    }
+
+    fn record(&mut self, header: &UdpDatagramHeader, size: usize, received_at: Instant) {
+        let first_received_at = *self.first_received_at.get_or_insert(received_at);
+        self.last_received_at = Some(received_at);
+
+        let received_after = received_at.duration_since(first_received_at).as_micros() as f64;
+        let transit = received_after - header.sent_after.as_micros() as f64;
+        if let Some(last_transit) = self.last_transit {
+            let deviation = (transit - last_transit).abs();
+            self.jitter_micros += (deviation - self.jitter_micros) / 16.0;
+        }
+        self.last_transit = Some(transit);
+
+        self.received_datagrams += 1;
+        self.received_bytes += size as u64;
+    }
+
+    fn report(&self) -> UdpReport {
+        let elapsed = match (self.first_received_at, self.last_received_at) {
+            (Some(first), Some(last)) => last.duration_since(first),
+            _ => Duration::ZERO,
+        };
+        UdpReport {
+            transfer: TransferReport { bytes: self.received_bytes, elapsed },
+            received_datagrams: self.received_datagrams,
+            jitter: Duration::from_micros(self.jitter_micros.round() as u64),
+        }
+    }
+}
+
+/// Delays the sender, so that data is transmitted at the target bandwidth.
+struct Pacer {
+    started_at: Instant,
+    bits_per_second: f64,
+}
+impl Pacer {
+    fn new(bandwidth_kbit_per_second: u64) -> Self {
+        Self {
+            started_at: Instant::now(),
+            bits_per_second: bandwidth_kbit_per_second as f64 * 1000.0,
+        }
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    async fn wait_for(&self, bytes_sent: u64) {
+        if self.bits_per_second <= 0.0 {
+            return;
+        }
+        let target_elapsed = Duration::from_secs_f64(bytes_sent as f64 * 8.0 / self.bits_per_second);
+        let elapsed = self.elapsed();
+        if target_elapsed > elapsed {
+            tokio::time::sleep(target_elapsed - elapsed).await;
+        }
+    }
+}
+
+/// Writes paced test data to the stream for the given duration and returns the amount of bytes written.
+async fn send_tcp(writer: &mut (impl AsyncWrite + Unpin), duration: Duration, bandwidth_kbit_per_second: u64) -> Result<u64, ThroughputError> {
+    let chunk = vec![0u8; TCP_CHUNK_SIZE];
+    let pacer = Pacer::new(bandwidth_kbit_per_second);
+    let mut bytes_sent = 0u64;
+
+    while pacer.elapsed() < duration {
+        writer.write_all(&chunk).await?;
+        bytes_sent += chunk.len() as u64;
+        pacer.wait_for(bytes_sent).await;
+    }
+    writer.flush().await?;
+    Ok(bytes_sent)
+}
+
+/// Reads test data from the stream until the sender closes its side of the connection.
+async fn receive_tcp(reader: &mut (impl AsyncRead + Unpin)) -> Result<TransferReport, ThroughputError> {
+    let mut buffer = vec![0u8; TCP_CHUNK_SIZE];
+    let mut bytes = 0u64;
+    let mut started_at = None;
+
+    loop {
+        let read = reader.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        started_at.get_or_insert_with(Instant::now);
+        bytes += read as u64;
+    }
+    let elapsed = started_at.map(|started_at| started_at.elapsed()).unwrap_or_default();
+    Ok(TransferReport { bytes, elapsed })
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ThroughputError {
+    #[error("I/O error during throughput measurement: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Protocol error during throughput measurement: {message}")]
+    Protocol { message: String },
+    #[error("Throughput measurement timed out.")]
+    Timeout,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_encode_and_decode_test_request() -> anyhow::Result<()> {
+        let request = TestRequest {
+            kind: TestKind::UdpSend,
+            duration: Duration::from_secs(5),
+            bandwidth_kbit_per_second: 100_000,
+            test_id: 42,
+        };
+
+        let mut buffer = Vec::new();
+        request.write_to(&mut buffer).await?;
+        let decoded = TestRequest::read_from(&mut buffer.as_slice()).await?;
+
+        assert_that!(decoded, eq(request));
+        Ok(())
+    }
+
+    #[test]
+    fn should_encode_and_decode_udp_datagram_header() {
+        let header = UdpDatagramHeader { test_id: 7, sequence_number: 3, sent_after: Duration::from_micros(1500) };
+
+        let mut buffer = [0u8; UDP_DATAGRAM_SIZE];
+        header.encode_into(&mut buffer);
+
+        assert_that!(UdpDatagramHeader::decode(&buffer), some(eq(header)));
+        assert_that!(UdpDatagramHeader::decode(&buffer[..10]), none());
+    }
+
+    #[test]
+    fn should_calculate_jitter_and_packet_loss() {
+        let mut statistics = UdpReceiverStatistics::new();
+        let start = Instant::now();
+
+        for (sequence_number, (sent_after_ms, received_after_ms)) in [(0, 10), (10, 20), (20, 40), (30, 40)].into_iter().enumerate() {
+            let header = UdpDatagramHeader { test_id: 1, sequence_number: sequence_number as u64, sent_after: Duration::from_millis(sent_after_ms) };
+            statistics.record(&header, UDP_DATAGRAM_SIZE, start + Duration::from_millis(received_after_ms));
+        }
+        let report = statistics.report();
+
+        assert_that!(report.received_datagrams, eq(4));
+        assert_that!(report.transfer, eq(TransferReport { bytes: 4 * UDP_DATAGRAM_SIZE as u64, elapsed: Duration::from_millis(30) }));
+        // deviations of the transit time: 0ms, 10ms, 10ms
+        assert_that!(report.jitter, eq(Duration::from_micros(1211)));
+        assert_that!(report.packet_loss_percent(5), eq(20.0));
+    }
+
+    #[test]
+    fn should_calculate_megabits_per_second() {
+        let report = TransferReport { bytes: 12_500_000, elapsed: Duration::from_secs(2) };
+        assert_that!(report.megabits_per_second(), eq(50.0));
+    }
+
+    #[tokio::test]
+    async fn should_not_overflow_when_pacing_at_maximum_bandwidth() {
+        let pacer = Pacer::new(u64::MAX);
+        tokio::time::timeout(Duration::from_secs(1), pacer.wait_for(u64::MAX)).await
+            .expect("Pacing at maximum bandwidth should not wait.");
+    }
+
+    #[tokio::test]
+    async fn should_measure_throughput_via_loopback() -> anyhow::Result<()> {
+        let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let server = server::ThroughputServer::bind(SocketAddr::new(localhost, 0)).await?;
+        let port = server.port();
+        tokio::spawn(server.serve());
+
+        let options = ThroughputOptions {
+            interval: Duration::from_secs(60),
+            duration: Duration::from_millis(200),
+            port,
+            target_bandwidth_kbit_per_second: 10_000,
+            tcp_enabled: true,
+            udp_enabled: true,
+        };
+
+        let measurement = client::measure(localhost, &options).await?;
+
+        assert_that!(measurement.tcp_send.map(|report| report.megabits_per_second()), some(gt(0.0)));
+        assert_that!(measurement.tcp_receive.map(|report| report.megabits_per_second()), some(gt(0.0)));
+        let udp = measurement.udp.expect("UDP should have been measured.");
+        assert_that!(udp.report.received_datagrams, gt(0));
+        assert_that!(udp.report.packet_loss_percent(udp.sent_datagrams), lt(50.0));
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::ops::Not;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{debug, error, info, trace};
+
+use crate::service::network_metrics::throughput::{receive_tcp, send_tcp, TestKind, TestRequest, ThroughputError, UdpDatagramHeader, UdpReceiverStatistics};
+
+/// Longest test duration a client may request, to prevent the server from being occupied indefinitely.
+const MAX_TEST_DURATION: Duration = Duration::from_secs(60);
+
+/// Additional time granted to a client beyond the requested test duration, before the connection is closed.
+const CONNECTION_TIMEOUT_MARGIN: Duration = Duration::from_secs(10);
+
+/// Delay before binding to the VPN address again, which may not be available yet.
+const BIND_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+type UdpStatisticsRef = Arc<Mutex<HashMap<u64, UdpReceiverStatistics>>>;
+
+pub struct ThroughputServer {
+    tcp_listener: TcpListener,
+    udp_socket: UdpSocket,
+}
+
+impl ThroughputServer {
+    /// Binds a TCP listener and a UDP socket on the same port.
+    pub async fn bind(address: SocketAddr) -> io::Result<Self> {
+        let tcp_listener = TcpListener::bind(address).await?;
+        let udp_socket = UdpSocket::bind(tcp_listener.local_addr()?).await?;
+        Ok(Self { tcp_listener, udp_socket })
+    }
+
+    #[cfg(test)]
+    pub fn port(&self) -> u16 {
+        self.udp_socket.local_addr()
+            .map(|address| address.port())
+            .unwrap_or_default()
+    }
+
+    /// Serves requests until the returned future is dropped, which closes the sockets.
+    pub async fn serve(self) {
+        let Self { tcp_listener, udp_socket } = self;
+        let udp_statistics: UdpStatisticsRef = Default::default();
+
+        let accept_connections = async {
+            loop {
+                match tcp_listener.accept().await {
+                    Ok((stream, remote_address)) => {
+                        let udp_statistics = Arc::clone(&udp_statistics);
+                        tokio::spawn(async move {
+                            let _ = handle_connection(stream, udp_statistics).await
+                                .inspect_err(|cause| error!("Error while measuring throughput with {remote_address}: {cause}"));
+                        });
+                    }
+                    Err(cause) => error!("Failed to accept connection for throughput measurement: {cause}"),
+                }
+            }
+        };
+
+        tokio::join!(
+            receive_udp(udp_socket, Arc::clone(&udp_statistics)),
+            accept_connections,
+        );
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, udp_statistics: UdpStatisticsRef) -> Result<(), ThroughputError> {
+    let request = TestRequest::read_from(&mut stream).await?;
+    trace!("Received throughput test request: {request:?}");
+
+    if request.duration > MAX_TEST_DURATION {
+        return Err(ThroughputError::Protocol { message: format!("Requested test duration of {} ms exceeds the maximum of {} ms.", request.duration.as_millis(), MAX_TEST_DURATION.as_millis()) });
+    }
+
+    let test = async {
+        match request.kind {
+            TestKind::TcpSend => {
+                let report = receive_tcp(&mut stream).await?;
+                report.write_to(&mut stream).await?;
+            }
+            TestKind::TcpReceive => {
+                send_tcp(&mut stream, request.duration, request.bandwidth_kbit_per_second).await?;
+                stream.shutdown().await?;
+            }
+            TestKind::UdpSend => {
+                udp_statistics.lock().await.insert(request.test_id, UdpReceiverStatistics::new());
+
+                let result = async {
+                    stream.write_u8(1).await?; //ready to receive
+                    stream.read_u8().await?; //client finished sending
+                    Ok::<_, io::Error>(())
+                }.await;
+
+                let statistics = udp_statistics.lock().await.remove(&request.test_id);
+                result?;
+
+                let report = statistics.map(|statistics| statistics.report()).unwrap_or_default();
+                report.write_to(&mut stream).await?;
+            }
+        }
+        Ok::<_, ThroughputError>(())
+    };
+
+    tokio::time::timeout(request.duration + CONNECTION_TIMEOUT_MARGIN, test).await
+        .map_err(|_| ThroughputError::Timeout)??;
+
+    debug!("Completed throughput test of kind {:?}.", request.kind);
+    Ok(())
+}
+
+async fn receive_udp(socket: UdpSocket, udp_statistics: UdpStatisticsRef) {
+    let mut buffer = vec![0u8; u16::MAX as usize];
+    loop {
+        match socket.recv_from(&mut buffer).await {
+            Ok((size, _)) => {
+                let received_at = Instant::now();
+                if let Some(header) = UdpDatagramHeader::decode(&buffer[..size]) {
+                    if let Some(statistics) = udp_statistics.lock().await.get_mut(&header.test_id) {
+                        statistics.record(&header, size, received_at);
+                    }
+                }
+            }
+            Err(cause) => error!("Failed to receive UDP datagram for throughput measurement: {cause}"),
+        }
+    }
+}
+
+pub type ThroughputServerManagerRef = Arc<ThroughputServerManager>;
+
+/// Runs the server on the VPN address of this peer, so that it is only reachable from within the cluster.
+#[derive(Debug)]
+pub struct ThroughputServerManager {
+    port: u16,
+    running: std::sync::Mutex<Option<(IpAddr, JoinHandle<()>)>>,
+}
+
+impl ThroughputServerManager {
+    pub fn create(port: u16) -> ThroughputServerManagerRef {
+        Arc::new(Self { port, running: Default::default() })
+    }
+
+    /// Starts the server in the background on the given address, stopping it on a previous address.
+    /// Errors are only logged, as a failing throughput measurement should not prevent EDGAR from running.
+    pub fn serve_on(&self, address: IpAddr) {
+        let mut running = self.running.lock().expect("Lock of throughput server should not be poisoned");
+
+        if let Some((running_address, handle)) = running.as_ref() {
+            if *running_address == address && handle.is_finished().not() {
+                return;
+            }
+        }
+        if let Some((previous_address, handle)) = running.take() {
+            debug!("Stopping throughput measurement server on {previous_address}.");
+            handle.abort();
+        }
+
+        let address = SocketAddr::new(address, self.port);
+        let handle = tokio::spawn(async move {
+            //the VPN address may not be assigned to an interface yet, so binding is retried
+            let server = loop {
+                match ThroughputServer::bind(address).await {
+                    Ok(server) => break server,
+                    Err(cause) => {
+                        debug!("Failed to start throughput measurement server on {address}. Retrying in {} ms.\n  {cause}", BIND_RETRY_DELAY.as_millis());
+                        tokio::time::sleep(BIND_RETRY_DELAY).await;
+                    }
+                }
+            };
+            info!("Listening for throughput measurements on {address}.");
+            server.serve().await
+        });
+        *running = Some((address.ip(), handle));
+    }
+}
@@ -1,19 +1,20 @@
 use std::fmt::Formatter;
 use opendut_types::cluster::{ClusterAssignment, PeerClusterAssignment};
 use opendut_types::util::net::NetworkInterfaceName;
-use tracing::{debug, error, info, trace, warn};
+use tracing::{debug, error, info, trace, warn, Instrument, Span};
 use std::sync::Arc;
 use opendut_types::peer::configuration::{OldPeerConfiguration, ParameterTarget, PeerConfiguration};
-use opendut_util::project;
 use opendut_types::peer::PeerId;
 use std::time::Duration;
-use std::ops::Not;
 use tokio::sync::mpsc;
 use crate::common::task::{runner, Task};
 use crate::service::{cluster_assignment, network_metrics, tasks};
 use crate::service::can_manager::CanManagerRef;
+use crate::service::network_metrics::throughput::ThroughputOptions;
+use crate::service::network_metrics::throughput::server::ThroughputServerManagerRef;
 use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
 use crate::service::test_execution::executor_manager::ExecutorManagerRef;
+use crate::cli::OutputFormat;
 use crate::setup::RunMode;
 
 #[derive(Debug)]
@@ -24,6 +25,9 @@ pub struct ApplyPeerConfigurationParams {
     pub network_interface_management: NetworkInterfaceManagement,
     pub executor_manager: ExecutorManagerRef,
     pub cluster_metrics_options: ClusterMetricsOptions,
+    /// Span of the message from CARL, which carries the remote tracing context.
+    /// Applying the configuration is traced as a child of this span.
+    pub parent_span: Span,
 }
 #[derive(Clone)]
 pub enum NetworkInterfaceManagement {
@@ -42,15 +46,35 @@ impl std::fmt::Debug for NetworkInterfaceManagement {
 #[derive(Clone, Debug)]
 pub struct ClusterMetricsOptions {
     pub ping_interval: Duration,
-    pub target_bandwidth_kbit_per_second: u64,
-    pub rperf_backoff_max_elapsed_time: Duration,
+    pub ping_timeout: Duration,
+    pub throughput: ThroughputOptions,
+    pub throughput_server: ThroughputServerManagerRef,
 }
 
-pub async fn spawn_peer_configurations_handler(mut rx_peer_configuration: mpsc::Receiver<ApplyPeerConfigurationParams>) -> anyhow::Result<()> {
+#[cfg_attr(target_arch = "arm", allow(unused_variables))]
+pub async fn spawn_peer_configurations_handler(mut rx_peer_configuration: mpsc::Receiver<ApplyPeerConfigurationParams>, settings: &config::Config) -> anyhow::Result<()> {
+    #[cfg(not(target_arch = "arm"))]
+    let mut service_plugins = crate::service::plugin::ServicePlugins::load(settings);
+
     tokio::spawn(async move {
         while let Some(apply_peer_configuration_params) = rx_peer_configuration.recv().await {
-            apply_peer_configuration(apply_peer_configuration_params).await
+            let parent_span = Clone::clone(&apply_peer_configuration_params.parent_span);
+
+            #[cfg(not(target_arch = "arm"))]
+            let (self_id, cluster_assignment) = (
+                apply_peer_configuration_params.self_id,
+                Clone::clone(&apply_peer_configuration_params.old_peer_configuration.cluster_assignment),
+            );
+
+            apply_peer_configuration(apply_peer_configuration_params)
+                .instrument(Clone::clone(&parent_span))
+                .await
                 .expect("Error while applying peer configuration.");
+
+            #[cfg(not(target_arch = "arm"))]
+            service_plugins.apply(&cluster_assignment, self_id)
+                .instrument(parent_span)
+                .await;
         }
     });
     Ok(())
@@ -58,7 +82,7 @@ pub async fn spawn_peer_configurations_handler(mut rx_peer_configuration: mpsc::
 
 #[tracing::instrument(skip_all)]
 async fn apply_peer_configuration(params: ApplyPeerConfigurationParams) -> anyhow::Result<()> {
-    let ApplyPeerConfigurationParams { self_id, peer_configuration, old_peer_configuration, network_interface_management, executor_manager, cluster_metrics_options } = params;
+    let ApplyPeerConfigurationParams { self_id, peer_configuration, old_peer_configuration, network_interface_management, executor_manager, cluster_metrics_options, parent_span: _ } = params;
 
     {
         let mut tasks: Vec<Box<dyn Task>> = vec![];
@@ -70,9 +94,15 @@ async fn apply_peer_configuration(params: ApplyPeerConfigurationParams) -> anyho
                     network_interface_manager: Arc::clone(network_interface_manager),
                 }));
             }
+
+            for parameter in peer_configuration.wireguard_peers.iter().cloned() {
+                tasks.push(Box::new(tasks::configure_wireguard_peer::ConfigureWireGuardPeer {
+                    parameter,
+                }));
+            }
         }
 
-        runner::run(RunMode::Service, &tasks).await?;
+        runner::run(RunMode::Service, OutputFormat::Text, &tasks).await?;
     }
 
     {
@@ -95,8 +125,7 @@ async fn apply_peer_configuration(params: ApplyPeerConfigurationParams) -> anyho
     }
 
     let mut executor_manager = executor_manager.lock().unwrap();
-    executor_manager.terminate_executors();
-    executor_manager.create_new_executors(peer_configuration.executors);
+    executor_manager.update_executors(peer_configuration.executors);
 
     setup_cluster_metrics(
         &old_peer_configuration.cluster_assignment,
@@ -177,15 +206,17 @@ fn setup_cluster_metrics( //TODO make idempotent
                 .filter(|peer_cluster_assignment | peer_cluster_assignment.vpn_address != local_ip)
                 .cloned().collect();
 
-            let ClusterMetricsOptions { ping_interval, target_bandwidth_kbit_per_second, rperf_backoff_max_elapsed_time } = cluster_metrics_options;
+            let ClusterMetricsOptions { ping_interval, ping_timeout, throughput, throughput_server } = cluster_metrics_options;
+
+            if throughput.tcp_enabled || throughput.udp_enabled {
+                throughput_server.serve_on(local_ip);
+            }
 
             tokio::spawn(async move {
-                network_metrics::ping::cluster_ping(peers.clone(), ping_interval).await;
+                network_metrics::ping::cluster_ping(peers.clone(), ping_interval, ping_timeout).await;
 
-                if project::is_running_in_development().not() {
-                    let _ = network_metrics::rperf::server::exponential_backoff_launch_rperf_server(rperf_backoff_max_elapsed_time).await //ignore errors during startup of rperf server, as we do not want to crash EDGAR for this
-                        .inspect_err(|cause| error!("Failed to start rperf server:\n  {cause}"));
-                    network_metrics::rperf::client::launch_rperf_clients(peers, target_bandwidth_kbit_per_second, rperf_backoff_max_elapsed_time).await;
+                if throughput.tcp_enabled || throughput.udp_enabled {
+                    network_metrics::throughput::client::launch_throughput_measurements(peers, throughput).await;
                 }
             });
         }
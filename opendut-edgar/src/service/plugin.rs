@@ -0,0 +1,331 @@
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use config::Config;
+use tracing::{debug, error, info, warn};
+use wasmtime::component::{Component, Linker};
+use wasmtime::{Engine, Store, Trap};
+
+use opendut_edgar_plugin_api::host::service::{ClusterAssignment as PluginClusterAssignment, PeerAssignment, ServicePlugin, Success};
+use opendut_types::cluster::ClusterAssignment;
+use opendut_types::peer::PeerId;
+use opendut_types::plugin::PluginKind;
+use opendut_util::project;
+
+use crate::common::constants::edgar_install_directory;
+use crate::fs;
+use crate::setup::plugin::init::discover_plugins_in_path;
+use crate::setup::plugin::registry;
+use crate::setup::plugin::plugin_runtime::{check_api_version, create_interruptible_engine, create_store, PluginContext, PluginState};
+
+/// Interval in which the epoch of the engine is incremented, which is the granularity of the call deadline.
+const EPOCH_TICK: Duration = Duration::from_millis(100);
+/// Maximum time a plugin may run for one call, before it is interrupted.
+const CALL_DEADLINE: Duration = Duration::from_secs(60);
+
+fn path_in_edgar_installation() -> anyhow::Result<PathBuf> {
+    let path = PathBuf::from("service-plugins");
+    project::make_path_absolute(&path)
+        .map_err(|cause| anyhow!("Failed to determine absolute path of the service plugin folder, which is supposed to be at '{path:?}': {cause}"))
+}
+
+/// File in which the cluster assignment is stored, which the plugins were last notified about.
+fn default_deployed_file() -> PathBuf {
+    edgar_install_directory().join("service-plugins-deployed-cluster.json")
+}
+
+/// Plugins loaded by the EDGAR service, which react to this peer joining or leaving a cluster.
+/// They are sandboxed like setup plugins, according to their manifest.
+pub struct ServicePlugins {
+    plugins: Vec<Arc<ServicePluginStore>>,
+    /// Cluster assignment, which the plugins were last notified about.
+    /// Persisted, so that the plugins are notified about an undeployment, which happened while EDGAR was not running.
+    deployed: Option<ClusterAssignment>,
+    deployed_file: PathBuf,
+}
+
+impl ServicePlugins {
+    /// Loads the plugins from the EDGAR installation and the plugin registry.
+    /// Plugins which fail to load are skipped, so that they do not prevent the EDGAR service from starting.
+    pub fn load(settings: &Config) -> Self {
+        let mut plugin_paths = path_in_edgar_installation()
+            .and_then(|plugin_dir| discover_plugins_in_path(&plugin_dir))
+            .unwrap_or_else(|cause| {
+                error!("Failed to discover service plugins in the EDGAR installation. Skipping them.\n  {cause:#}");
+                vec![]
+            });
+        plugin_paths.extend(
+            registry::discover_verified_plugins(PluginKind::Service, settings)
+                .unwrap_or_else(|cause| {
+                    error!("Failed to discover service plugins from the plugin registry. Skipping them.\n  {cause:#}");
+                    vec![]
+                })
+        );
+
+        Self::load_from(&plugin_paths, default_deployed_file())
+    }
+
+    fn load_from(plugin_paths: &[PathBuf], deployed_file: PathBuf) -> Self {
+        let engine = create_interruptible_engine();
+        let mut linker = Linker::new(&engine);
+        wasmtime_wasi::add_to_linker_sync(&mut linker).expect("Could not add wasi to linker");
+        ServicePlugin::add_to_linker(&mut linker, |state: &mut PluginState| state)
+            .expect("Could not add PluginState to linker");
+
+        let plugins = plugin_paths.iter()
+            .filter_map(|path| {
+                ServicePluginStore::create(&engine, &linker, path)
+                    .inspect_err(|cause| error!("Failed to load service plugin at '{}'. Skipping it.\n  {cause:#}", path.display()))
+                    .ok()
+                    .map(Arc::new)
+            })
+            .collect::<Vec<_>>();
+
+        if plugins.is_empty().not() {
+            info!("Loaded {} service plugin(s).", plugins.len());
+            spawn_epoch_ticker(&engine);
+        }
+
+        let deployed = load_deployed(&deployed_file)
+            .unwrap_or_else(|cause| {
+                warn!("Failed to load the cluster assignment, which the service plugins were last notified about, from '{}'. Assuming none.\n  {cause:#}", deployed_file.display());
+                None
+            });
+
+        Self { plugins, deployed, deployed_file }
+    }
+
+    /// Notifies the plugins, when this peer left its previous cluster or joined a new cluster.
+    pub async fn apply(&mut self, cluster_assignment: &Option<ClusterAssignment>, self_id: PeerId) {
+        if self.plugins.is_empty() || &self.deployed == cluster_assignment {
+            return;
+        }
+
+        if let Some(previous) = self.deployed.take() {
+            let assignment = to_plugin_cluster_assignment(&previous, self_id);
+            for plugin in self.plugins.iter().rev() { //undo in reverse order
+                let result = Arc::clone(plugin).call(assignment.clone(), ServicePluginStore::cluster_undeployed).await;
+                log_result(plugin, "cluster-undeployed", result);
+            }
+        }
+
+        if let Some(cluster_assignment) = cluster_assignment {
+            let assignment = to_plugin_cluster_assignment(cluster_assignment, self_id);
+            for plugin in &self.plugins {
+                let result = Arc::clone(plugin).call(assignment.clone(), ServicePluginStore::cluster_deployed).await;
+                log_result(plugin, "cluster-deployed", result);
+            }
+            self.deployed = Some(Clone::clone(cluster_assignment));
+        }
+
+        if let Err(cause) = store_deployed(&self.deployed_file, &self.deployed) {
+            error!("Failed to store the cluster assignment, which the service plugins were notified about, at '{}'.\n  {cause:#}", self.deployed_file.display());
+        }
+    }
+}
+
+/// Increments the epoch of the engine in the background, until the engine is dropped.
+fn spawn_epoch_ticker(engine: &Engine) {
+    let engine = engine.weak();
+    std::thread::spawn(move || {
+        while let Some(engine) = engine.upgrade() {
+            engine.increment_epoch();
+            drop(engine);
+            std::thread::sleep(EPOCH_TICK);
+        }
+    });
+}
+
+fn load_deployed(deployed_file: &Path) -> anyhow::Result<Option<ClusterAssignment>> {
+    if deployed_file.exists().not() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(deployed_file)?;
+    let deployed = serde_json::from_str(&content)?;
+    Ok(deployed)
+}
+
+fn store_deployed(deployed_file: &Path, deployed: &Option<ClusterAssignment>) -> anyhow::Result<()> {
+    match deployed {
+        Some(deployed) => fs::write(deployed_file, serde_json::to_string(deployed)?)?,
+        None => if deployed_file.exists() {
+            fs::remove_file(deployed_file)?
+        },
+    }
+    Ok(())
+}
+
+struct ServicePluginStore {
+    path: PathBuf,
+    store: Mutex<Store<PluginState>>,
+    instance: ServicePlugin,
+}
+
+impl ServicePluginStore {
+    fn create(engine: &Engine, linker: &Linker<PluginState>, plugin_path: &Path) -> anyhow::Result<Self> {
+        let mut store = create_store(engine, plugin_path, PluginContext::default())?;
+        store.set_epoch_deadline(deadline_ticks());
+
+        let component = Component::from_file(engine, plugin_path)
+            .context(format!("Could not load service plugin at '{}'.", plugin_path.display()))?;
+        check_api_version(engine, &component)
+            .context(format!("Could not load service plugin at '{}'.", plugin_path.display()))?;
+
+        let instance = ServicePlugin::instantiate(&mut store, &component, linker)
+            .context(format!("Could not instantiate service plugin at '{}'.", plugin_path.display()))?;
+
+        debug!("Loaded service plugin at: {}", plugin_path.display());
+
+        Ok(Self {
+            path: plugin_path.to_path_buf(),
+            store: Mutex::new(store),
+            instance,
+        })
+    }
+
+    fn cluster_deployed(&self, assignment: PluginClusterAssignment) -> anyhow::Result<Result<Success, String>> {
+        let mut store = self.store.lock().expect("Unable to lock mutex");
+        store.set_epoch_deadline(deadline_ticks());
+        self.instance.edgar_setup_cluster_handler().call_cluster_deployed(&mut *store, &assignment)
+    }
+
+    fn cluster_undeployed(&self, assignment: PluginClusterAssignment) -> anyhow::Result<Result<Success, String>> {
+        let mut store = self.store.lock().expect("Unable to lock mutex");
+        store.set_epoch_deadline(deadline_ticks());
+        self.instance.edgar_setup_cluster_handler().call_cluster_undeployed(&mut *store, &assignment)
+    }
+
+    /// Calls into the plugin on a blocking thread, as plugins are executed synchronously.
+    /// The call is interrupted, if it takes longer than [`CALL_DEADLINE`].
+    async fn call(
+        self: Arc<Self>,
+        assignment: PluginClusterAssignment,
+        function: fn(&Self, PluginClusterAssignment) -> anyhow::Result<Result<Success, String>>,
+    ) -> anyhow::Result<Success> {
+        tokio::task::spawn_blocking(move || function(&self, assignment)).await?
+            .map_err(|cause| match cause.downcast_ref::<Trap>() {
+                Some(Trap::Interrupt) => anyhow!("Plugin did not complete within {} seconds and was interrupted.", CALL_DEADLINE.as_secs()),
+                _ => cause,
+            })?
+            .map_err(|message| anyhow!(message))
+    }
+}
+
+fn deadline_ticks() -> u64 {
+    (CALL_DEADLINE.as_millis() / EPOCH_TICK.as_millis()) as u64
+}
+
+fn log_result(plugin: &ServicePluginStore, function: &str, result: anyhow::Result<Success>) {
+    let path = plugin.path.display();
+    match result {
+        Ok(Success { message: Some(message) }) => info!("Service plugin at '{path}' completed {function}: {message}"),
+        Ok(Success { message: None }) => info!("Service plugin at '{path}' completed {function}."),
+        Err(cause) => error!("Service plugin at '{path}' failed during {function}: {cause:#}"),
+    }
+}
+
+fn to_plugin_cluster_assignment(cluster_assignment: &ClusterAssignment, self_id: PeerId) -> PluginClusterAssignment {
+    PluginClusterAssignment {
+        id: cluster_assignment.id.to_string(),
+        leader: cluster_assignment.leader.to_string(),
+        self_id: self_id.to_string(),
+        peers: cluster_assignment.assignments.iter()
+            .map(|assignment| PeerAssignment {
+                peer_id: assignment.peer_id.to_string(),
+                vpn_address: assignment.vpn_address.to_string(),
+                can_server_port: assignment.can_server_port.0,
+                device_interfaces: assignment.device_interfaces.iter()
+                    .map(|interface| interface.name.name())
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::{ClusterId, PeerClusterAssignment};
+    use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceId, NetworkInterfaceName};
+    use opendut_types::util::Port;
+
+    use super::*;
+
+    fn cluster_assignment(peer_id: PeerId) -> ClusterAssignment {
+        ClusterAssignment {
+            id: ClusterId::random(),
+            leader: peer_id,
+            assignments: vec![
+                PeerClusterAssignment {
+                    peer_id,
+                    vpn_address: IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+                    can_server_port: Port(10000),
+                    device_interfaces: vec![
+                        NetworkInterfaceDescriptor {
+                            id: NetworkInterfaceId::random(),
+                            name: NetworkInterfaceName::try_from("eth0").unwrap(),
+                            configuration: NetworkInterfaceConfiguration::Ethernet,
+                        },
+                    ],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn should_skip_plugins_which_fail_to_load() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let broken_plugin = temp.child("broken.wasm");
+        broken_plugin.write_str("not a WebAssembly component")?;
+        temp.child("broken.toml").write_str("")?;
+
+        let service_plugins = ServicePlugins::load_from(&[broken_plugin.to_path_buf()], temp.child("deployed.json").to_path_buf());
+
+        assert_that!(service_plugins.plugins.len(), eq(0));
+        Ok(())
+    }
+
+    #[test]
+    fn should_restore_the_deployed_cluster_assignment_after_a_restart() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let deployed_file = temp.child("deployed.json");
+        let deployed = Some(cluster_assignment(PeerId::random()));
+
+        store_deployed(&deployed_file, &deployed)?;
+        let service_plugins = ServicePlugins::load_from(&[], deployed_file.to_path_buf());
+        assert_that!(service_plugins.deployed, eq(&deployed));
+
+        store_deployed(&deployed_file, &None)?;
+        deployed_file.assert(predicates::path::missing());
+        assert_that!(load_deployed(&deployed_file)?, none());
+        Ok(())
+    }
+
+    #[test]
+    fn should_convert_the_cluster_assignment_for_plugins() {
+        let self_id = PeerId::random();
+        let assignment = cluster_assignment(self_id);
+
+        let result = to_plugin_cluster_assignment(&assignment, self_id);
+
+        assert_that!(result.id, eq(&assignment.id.to_string()));
+        assert_that!(result.self_id, eq(&self_id.to_string()));
+        assert_that!(result.peers.len(), eq(1));
+        assert_that!(result.peers[0].vpn_address, eq("10.0.0.1"));
+        assert_that!(result.peers[0].can_server_port, eq(10000));
+        assert_that!(result.peers[0].device_interfaces, elements_are![eq("eth0")]);
+    }
+
+    #[test]
+    fn should_allow_calls_to_run_for_the_call_deadline() {
+        assert_that!(EPOCH_TICK * deadline_ticks() as u32, eq(CALL_DEADLINE));
+    }
+}
@@ -0,0 +1,47 @@
+use std::net::SocketAddr;
+
+use anyhow::Context;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use http::{header, StatusCode};
+use prometheus::Registry;
+use tracing::{error, info};
+
+use opendut_util::telemetry::metrics;
+
+/// Serves the meters of EDGAR in the Prometheus text format at `http://<address>/metrics`.
+pub fn spawn(address: SocketAddr, registry: &'static Registry) {
+    let router = Router::new()
+        .route("/metrics", get(move || prometheus_metrics(registry)));
+
+    tokio::spawn(async move {
+        info!("Serving metrics for Prometheus at http://{address}/metrics");
+
+        let result = axum::Server::try_bind(&address)
+            .context(format!("Failed to bind Prometheus metrics endpoint to {address}."))
+            .map(|server| server.serve(router.into_make_service()));
+
+        match result {
+            Ok(server) => {
+                if let Err(cause) = server.await {
+                    error!("Prometheus metrics endpoint terminated with an error: {cause}");
+                }
+            }
+            Err(cause) => error!("{cause:#}"),
+        }
+    });
+}
+
+async fn prometheus_metrics(registry: &Registry) -> impl IntoResponse {
+    match metrics::encode_prometheus_metrics(registry) {
+        Ok(body) => {
+            let headers = [(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)];
+            (headers, body).into_response()
+        }
+        Err(cause) => {
+            error!("Failed to encode metrics for Prometheus: {cause}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
@@ -1,14 +1,21 @@
 use std::any::Any;
 use std::fmt::Debug;
+use std::net::SocketAddr;
 use std::ops::Not;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Context;
 use opendut_carl_api::proto::services::peer_messaging_broker;
 use opendut_carl_api::proto::services::peer_messaging_broker::downstream::Message;
-use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerConfiguration, TracingContext};
+use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerConfiguration, NetworkDiagnosticsReport, RunNetworkDiagnostics, StartExecutorRun, StopExecutorRun, TracingContext};
+use opendut_types::cluster::diagnostics::NetworkDiagnosticsOptions;
+use opendut_types::cluster::PeerClusterAssignment;
 use opendut_types::peer::configuration::{OldPeerConfiguration, PeerConfiguration};
+use opendut_types::peer::executor::ExecutorId;
+use opendut_types::peer::executor::log::ExecutorRunId;
+use opendut_types::peer::executor::test_run::ExecutorRunOverrides;
 use opendut_types::peer::PeerId;
 use opendut_util::settings::LoadedConfig;
 use opendut_util::telemetry;
@@ -19,14 +26,19 @@ use opentelemetry_sdk::propagation::TraceContextPropagator;
 use tokio::sync::mpsc;
 use tokio::time::sleep;
 use tonic::Code;
-use tracing::{debug, error, info, trace, warn, Span};
+use tracing::{debug, error, info, trace, warn, Instrument, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use crate::common::{carl, settings};
 use crate::service::can_manager::{CanManager, CanManagerRef};
 use crate::service::network_interface::manager::{NetworkInterfaceManager, NetworkInterfaceManagerRef};
+use crate::service::network_metrics;
+use crate::service::network_metrics::throughput::ThroughputOptions;
+use crate::service::network_metrics::throughput::server::ThroughputServerManager;
 use crate::service::peer_configuration::{ApplyPeerConfigurationParams, ClusterMetricsOptions, NetworkInterfaceManagement};
 use crate::service::test_execution::executor_manager::{ExecutorManager, ExecutorManagerRef};
+use crate::service::test_execution::log_shipper::{self, ExecutorLogShipper};
+use crate::service::test_execution::results::ResultsUploadOptions;
 use crate::service::vpn;
 
 const BANNER: &str = r"
@@ -71,8 +83,26 @@ pub async fn create_with_telemetry(settings_override: config::Config) -> anyhow:
         telemetry::metrics::initialize_metrics_collection(cpu_collection_interval_ms, meter_providers);
     }
 
+    if let Some(registry) = telemetry::metrics::prometheus_registry() {
+        let address: SocketAddr = {
+            let host = settings.config.get_string("opentelemetry.metrics.prometheus.bind.host")?;
+            let port = settings.config.get_int("opentelemetry.metrics.prometheus.bind.port")?;
+            SocketAddr::from_str(&format!("{host}:{port}"))
+                .context("Failed to parse bind address of Prometheus metrics endpoint.")?
+        };
+        crate::service::prometheus_endpoint::spawn(address, registry);
+    }
+
+    if settings.config.get::<bool>("opentelemetry.metrics.can.enabled")? {
+        let interval = Duration::from_millis(settings.config.get::<u64>("opentelemetry.metrics.can.interval.ms")?);
+        network_metrics::can::launch_can_metrics_collection(interval);
+    }
+
+    #[cfg(not(target_arch = "arm"))]
+    crate::setup::plugin::registry::fetch_service_plugins(self_id, &settings.config).await;
+
     let (tx_peer_configuration, rx_peer_configuration) = mpsc::channel(100);
-    crate::service::peer_configuration::spawn_peer_configurations_handler(rx_peer_configuration).await?;
+    crate::service::peer_configuration::spawn_peer_configurations_handler(rx_peer_configuration, &settings.config).await?;
 
     run_stream_receiver(self_id, settings, tx_peer_configuration).await?;
 
@@ -89,8 +119,11 @@ pub async fn run_stream_receiver(
 
     info!("Started with ID <{self_id}> and configuration: {settings:?}");
 
+    let executor_log_shipper = ExecutorLogShipper::spawn(log_shipper::default_spool_file());
+
     let handle_stream_info = {
-        let executor_manager: ExecutorManagerRef = ExecutorManager::create();
+        let results_upload_options = ResultsUploadOptions::load(&settings.config).await?;
+        let executor_manager: ExecutorManagerRef = ExecutorManager::create(executor_log_shipper.sender(), executor_log_shipper.test_run_update_sender(), results_upload_options);
 
         let network_interface_management = {
             let network_interface_management_enabled = settings.config.get::<bool>("network.interface.management.enabled")?;
@@ -105,8 +138,17 @@ pub async fn run_stream_receiver(
         };
 
         let ping_interval = Duration::from_millis(settings.config.get::<u64>("opentelemetry.metrics.cluster.ping.interval.ms")?);
+        let ping_timeout = Duration::from_millis(settings.config.get::<u64>("opentelemetry.metrics.cluster.ping.timeout.ms")?);
         let target_bandwidth_kbit_per_second = settings.config.get::<u64>("opentelemetry.metrics.cluster.target.bandwidth.kilobit.per.second")?;
-        let rperf_backoff_max_elapsed_time = Duration::from_millis(settings.config.get::<u64>("opentelemetry.metrics.cluster.rperf.backoff.max.elapsed.time.ms")?);
+        let throughput = ThroughputOptions {
+            interval: Duration::from_millis(settings.config.get::<u64>("opentelemetry.metrics.cluster.throughput.interval.ms")?),
+            duration: Duration::from_millis(settings.config.get::<u64>("opentelemetry.metrics.cluster.throughput.duration.ms")?),
+            port: settings.config.get::<u16>("opentelemetry.metrics.cluster.throughput.port")?,
+            target_bandwidth_kbit_per_second,
+            tcp_enabled: settings.config.get::<bool>("opentelemetry.metrics.cluster.throughput.tcp.enabled")?,
+            udp_enabled: settings.config.get::<bool>("opentelemetry.metrics.cluster.throughput.udp.enabled")?,
+        };
+        let throughput_server = ThroughputServerManager::create(throughput.port);
 
         HandleStreamInfo {
             self_id,
@@ -114,12 +156,14 @@ pub async fn run_stream_receiver(
             executor_manager,
             cluster_metrics_options: ClusterMetricsOptions {
                 ping_interval,
-                target_bandwidth_kbit_per_second,
-                rperf_backoff_max_elapsed_time,
+                ping_timeout,
+                throughput,
+                throughput_server,
             },
         }
     };
 
+    vpn::setup(&settings, &handle_stream_info.network_interface_management).await?;
     let remote_address = vpn::retrieve_remote_host(&settings).await?;
     
     let timeout_duration = Duration::from_millis(settings.config.get::<u64>("carl.disconnect.timeout.ms")?);
@@ -128,6 +172,8 @@ pub async fn run_stream_receiver(
 
     let (mut rx_inbound, tx_outbound) = carl::open_stream(self_id, &remote_address, &mut carl).await?;
 
+    executor_log_shipper.connect(Clone::clone(&tx_outbound));
+
     loop {
         let received = tokio::time::timeout(timeout_duration, rx_inbound.message()).await;
 
@@ -215,6 +261,9 @@ async fn handle_stream_message(
                         .inspect_err(|cause| debug!("Failed to send ping to CARL: {cause}"));
             }
             Message::ApplyPeerConfiguration(message) => apply_peer_configuration_raw(message, context, handle_stream_info, peer_configuration_sender).await?,
+            Message::RunNetworkDiagnostics(message) => run_network_diagnostics_raw(message, context, handle_stream_info, tx_outbound),
+            Message::StartExecutorRun(message) => start_executor_run_raw(message, context, handle_stream_info),
+            Message::StopExecutorRun(message) => stop_executor_run_raw(message, context, handle_stream_info),
         }
     } else {
         ignore(message)
@@ -236,38 +285,131 @@ async fn apply_peer_configuration_raw(
             configuration: Some(peer_configuration),
         } => {
 
-            let span = Span::current();
+            let span = tracing::info_span!("apply_peer_configuration_message");
             set_parent_context(&span, context);
-            let _span = span.enter();
-
-            match OldPeerConfiguration::try_from(old_peer_configuration) {
-                Ok(old_peer_configuration) => {
-                    match PeerConfiguration::try_from(peer_configuration) {
-                        Ok(peer_configuration) => {
-                            info!("Received OldPeerConfiguration: {old_peer_configuration:?}");
-                            info!("Received PeerConfiguration: {peer_configuration:?}");
+
+            async {
+                match OldPeerConfiguration::try_from(old_peer_configuration) {
+                    Ok(old_peer_configuration) => {
+                        match PeerConfiguration::try_from(peer_configuration) {
+                            Ok(peer_configuration) => {
+                                info!("Received OldPeerConfiguration: {old_peer_configuration:?}");
+                                info!("Received PeerConfiguration: {peer_configuration:?}");
                             
-                            let apply_config_params = ApplyPeerConfigurationParams {
-                                self_id: handle_stream_info.self_id,
-                                peer_configuration,
-                                old_peer_configuration,
-                                network_interface_management: handle_stream_info.network_interface_management.clone(),
-                                executor_manager: Arc::clone(&handle_stream_info.executor_manager),
-                                cluster_metrics_options: handle_stream_info.cluster_metrics_options.clone(),
-                            };
-                            peer_configuration_sender.send(apply_config_params).await?
+                                let apply_config_params = ApplyPeerConfigurationParams {
+                                    self_id: handle_stream_info.self_id,
+                                    peer_configuration,
+                                    old_peer_configuration,
+                                    network_interface_management: handle_stream_info.network_interface_management.clone(),
+                                    executor_manager: Arc::clone(&handle_stream_info.executor_manager),
+                                    cluster_metrics_options: handle_stream_info.cluster_metrics_options.clone(),
+                                    parent_span: Clone::clone(&span),
+                                };
+                                peer_configuration_sender.send(apply_config_params).await?
+                            }
+                            Err(error) => error!("Illegal PeerConfiguration: {error}"),
                         }
-                        Err(error) => error!("Illegal PeerConfiguration: {error}"),
                     }
-                }
-                Err(error) => error!("Illegal OldPeerConfiguration: {error}"),
-            };
+                    Err(error) => error!("Illegal OldPeerConfiguration: {error}"),
+                };
+                Ok::<_, anyhow::Error>(())
+            }.instrument(Clone::clone(&span)).await?;
         }
         _ => ignore(message),
     }
     Ok(())
 }
 
+fn start_executor_run_raw(
+    message: StartExecutorRun,
+    context: Option<TracingContext>,
+    handle_stream_info: &HandleStreamInfo,
+) {
+    let span = tracing::info_span!("start_executor_run");
+    set_parent_context(&span, context);
+    let _span = span.enter();
+
+    let StartExecutorRun { executor_id, run_id, overrides } = message;
+
+    let executor_id = executor_id.map(ExecutorId::try_from);
+    let run_id = run_id.map(ExecutorRunId::try_from);
+    let overrides = overrides.map(ExecutorRunOverrides::try_from).transpose();
+
+    match (executor_id, run_id, overrides) {
+        (Some(Ok(executor_id)), Some(Ok(run_id)), Ok(overrides)) => {
+            info!("Starting run <{run_id}> of executor <{executor_id}>.");
+            let mut executor_manager = handle_stream_info.executor_manager.lock().unwrap();
+            executor_manager.start_executor(executor_id, run_id, overrides.unwrap_or_default());
+        }
+        _ => error!("Received invalid request to start an executor from CARL. Ignoring it."),
+    }
+}
+
+fn stop_executor_run_raw(
+    message: StopExecutorRun,
+    context: Option<TracingContext>,
+    handle_stream_info: &HandleStreamInfo,
+) {
+    let span = tracing::info_span!("stop_executor_run");
+    set_parent_context(&span, context);
+    let _span = span.enter();
+
+    match message.executor_id.map(ExecutorId::try_from) {
+        Some(Ok(executor_id)) => {
+            let mut executor_manager = handle_stream_info.executor_manager.lock().unwrap();
+            executor_manager.stop_executor(executor_id);
+        }
+        _ => error!("Received invalid request to stop an executor from CARL. Ignoring it."),
+    }
+}
+
+fn run_network_diagnostics_raw(
+    message: RunNetworkDiagnostics,
+    context: Option<TracingContext>,
+    handle_stream_info: &HandleStreamInfo,
+    tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>,
+) {
+    let RunNetworkDiagnostics { id, options, targets } = message;
+
+    let (Some(id), Some(options)) = (id, options) else {
+        error!("Received RunNetworkDiagnostics without ID or options.");
+        return;
+    };
+    let options = NetworkDiagnosticsOptions::from(options);
+    let targets = match targets.into_iter().map(PeerClusterAssignment::try_from).collect::<Result<Vec<_>, _>>() {
+        Ok(targets) => targets,
+        Err(error) => {
+            error!("Illegal target for network diagnostics: {error}");
+            return;
+        }
+    };
+
+    let span = tracing::info_span!("run_network_diagnostics");
+    set_parent_context(&span, context);
+
+    let self_id = handle_stream_info.self_id;
+    let throughput_port = handle_stream_info.cluster_metrics_options.throughput.port;
+    let tx_outbound = Clone::clone(tx_outbound);
+
+    //run in background, so that the stream keeps being processed while the tests are running
+    tokio::spawn(async move {
+        info!("Running network diagnostics to {} peer(s) with options: {options:?}", targets.len());
+
+        let links = network_metrics::diagnostics::diagnose_links(self_id, targets, &options, throughput_port).await;
+
+        let message = peer_messaging_broker::Upstream {
+            message: Some(peer_messaging_broker::upstream::Message::NetworkDiagnosticsReport(NetworkDiagnosticsReport {
+                id: Some(id),
+                links: links.into_iter().map(Into::into).collect(),
+            })),
+            context: None,
+        };
+        let _ignore_error =
+            tx_outbound.send(message).await
+                .inspect_err(|cause| error!("Failed to send network diagnostics report to CARL: {cause}"));
+    }.instrument(span));
+}
+
 fn set_parent_context(span: &Span, context: Option<TracingContext>) {
     if let Some(context) = context {
         let propagator = TraceContextPropagator::new();
@@ -279,3 +421,59 @@ fn set_parent_context(span: &Span, context: Option<TracingContext>) {
 fn ignore(message: impl Any + Debug) {
     warn!("Ignoring illegal message: {message:?}");
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use opentelemetry::trace::{TraceContextExt, TraceId, TracerProvider as _};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    const REMOTE_TRACE_ID: &str = "0af7651916cd43dd8448eb211c80319c";
+
+    fn remote_context() -> TracingContext {
+        TracingContext {
+            values: HashMap::from([
+                (String::from("traceparent"), format!("00-{REMOTE_TRACE_ID}-b7ad6b7169203331-01")),
+            ]),
+        }
+    }
+
+    fn trace_id(span: &Span) -> TraceId {
+        span.context().span().span_context().trace_id()
+    }
+
+    #[test]
+    fn should_continue_the_trace_of_the_remote_context() {
+        let tracer_provider = opentelemetry_sdk::trace::TracerProvider::builder().build(); //the tracer only holds a weak reference to its provider
+        let subscriber = tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("apply_peer_configuration_message");
+            set_parent_context(&span, Some(remote_context()));
+
+            let remote_trace_id = TraceId::from_hex(REMOTE_TRACE_ID).unwrap();
+            assert_eq!(trace_id(&span), remote_trace_id);
+
+            let child_span = span.in_scope(|| tracing::info_span!("apply_peer_configuration"));
+            assert_eq!(trace_id(&child_span), remote_trace_id);
+        });
+    }
+
+    #[test]
+    fn should_start_a_new_trace_without_a_remote_context() {
+        let tracer_provider = opentelemetry_sdk::trace::TracerProvider::builder().build(); //the tracer only holds a weak reference to its provider
+        let subscriber = tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("apply_peer_configuration_message");
+            set_parent_context(&span, None);
+
+            let trace_id = trace_id(&span);
+            assert_ne!(trace_id, TraceId::INVALID);
+            assert_ne!(trace_id, TraceId::from_hex(REMOTE_TRACE_ID).unwrap());
+        });
+    }
+}
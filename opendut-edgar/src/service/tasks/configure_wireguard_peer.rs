@@ -0,0 +1,34 @@
+use crate::common::task::{Success, Task, TaskFulfilled};
+use crate::service::vpn::wireguard;
+use opendut_types::peer::configuration::{Parameter, ParameterTarget};
+use opendut_types::vpn::wireguard::WireGuardPeer;
+use async_trait::async_trait;
+
+pub struct ConfigureWireGuardPeer {
+    pub parameter: Parameter<WireGuardPeer>,
+}
+#[async_trait]
+impl Task for ConfigureWireGuardPeer {
+    fn description(&self) -> String {
+        match self.parameter.target {
+            ParameterTarget::Present => format!("Configure WireGuard peer '{}'", self.parameter.value.public_key),
+            ParameterTarget::Absent => format!("Remove WireGuard peer '{}'", self.parameter.value.public_key),
+        }
+    }
+
+    async fn check_fulfilled(&self) -> anyhow::Result<TaskFulfilled> {
+        Ok(TaskFulfilled::Unchecked) //setting and removing peers is idempotent, so we always run it
+    }
+
+    async fn execute(&self) -> anyhow::Result<Success> {
+        match self.parameter.target {
+            ParameterTarget::Present => {
+                wireguard::set_peer(&self.parameter.value).await?;
+            }
+            ParameterTarget::Absent => {
+                wireguard::remove_peer(&self.parameter.value.public_key).await?;
+            }
+        }
+        Ok(Success::default())
+    }
+}
@@ -1 +1,2 @@
+pub mod configure_wireguard_peer;
 pub mod create_ethernet_bridge;
@@ -1,16 +1,16 @@
-use std::{env, io::{Cursor, ErrorKind, Write}, path::PathBuf, process::Stdio};
+use std::{process::Stdio, sync::{atomic::{AtomicU64, Ordering}, Arc}, time::{Duration, SystemTime}};
 
 use anyhow::Result;
-use tokio::{fs::{self, File}, io::{AsyncBufReadExt, AsyncReadExt, BufReader}, process::{Child, Command}, sync::{mpsc, watch}};
-use tracing::{error, info, warn};
+use tokio::{io::{AsyncBufReadExt, AsyncRead, BufReader}, process::{Child, Command}, sync::{mpsc, watch}, task::JoinHandle};
+use tracing::{error, info, warn, Instrument, Span};
 use url::Url;
-use uuid::Uuid;
-use walkdir::WalkDir;
-use zip::{CompressionMethod, write::{FileOptionExtension, FileOptions, SimpleFileOptions}, ZipWriter};
 
-use opendut_types::peer::executor::{container::{CommandName, ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ResultsUrl};
+use opendut_types::peer::executor::log::{ExecutorLogLine, ExecutorLogStream, ExecutorRunId};
+use opendut_types::peer::executor::test_run::{TestRunStatus, TestRunUpdate};
+use opendut_types::peer::executor::{ExecutorId, container::{CommandName, ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ResultsUrl};
 
-use crate::service::test_execution::webdav_client::{self, WebdavClient};
+use crate::service::test_execution::log_shipper::ExecutorLogEntry;
+use crate::service::test_execution::results::{self, ResultsDirectory, ResultsUploadOptions};
 
 #[derive(Debug)]
 enum ContainerState {
@@ -37,55 +37,66 @@ pub struct ContainerConfiguration {
 
 pub struct ContainerManager{
     config: ContainerConfiguration,
-    results_dir: PathBuf,
-    webdav_client: WebdavClient,
+    results_dir: ResultsDirectory,
     termination_channel_rx: watch::Receiver<bool>,
+    log_context: ContainerLogContext,
 }
 
 const MONITOR_INTERVAL_MS: u64 = 1000;
-const RESULTS_READY_FILE: &str = ".results_ready";
 const CONTAINER_RESULTS_DIRECTORY: &str = "/results";
+/// Maximum time to wait for the remaining output of an exited executor, before reporting the run as finished.
+pub(super) const LOG_FORWARDING_TIMEOUT: Duration = Duration::from_secs(5);
 
 impl ContainerManager {
 
-    pub fn new(container_configuration: ContainerConfiguration, termination_channel_rx: watch::Receiver<bool>) -> Self {
+    pub fn new(container_configuration: ContainerConfiguration, termination_channel_rx: watch::Receiver<bool>, log_context: ContainerLogContext, results_upload_options: ResultsUploadOptions) -> Self {
+        let results_dir = ResultsDirectory::new(Clone::clone(&container_configuration.results_url), results_upload_options);
         Self { 
             config: container_configuration,
-            results_dir: env::temp_dir().join(format!("opendut-edgar-results_{}", Uuid::new_v4())),
-            webdav_client: WebdavClient::new("some_dummy_token".to_string()), // TODO: Authenticate with actual token
-            termination_channel_rx
+            results_dir,
+            termination_channel_rx,
+            log_context,
         }
     }
 
-    pub async fn start(&mut self) {
-        match self.run().await {
+    /// Starts the container within the given span and then monitors it until it exits or is terminated.
+    pub async fn start(&mut self, start_span: Span) {
+        match self.run(start_span).await {
             Ok(_) => (),
-            Err(cause) => error!("{}", cause.to_string()),
+            Err(cause) => {
+                error!("{}", cause.to_string());
+                self.report_status(TestRunStatus::Failed, None, None).await;
+            }
         }
     }
 
-    async fn run(&mut self) -> Result<(), Error> {
+    async fn run(&mut self, start_span: Span) -> Result<(), Error> {
+        let mut results_location = None;
         let mut results_uploaded = false;
+        let mut terminated = false;
 
-        self.create_results_dir().await?;
-        let container_name = self.start_container().await?;
-        let mut log_reader = 
+        let container_name = async {
+            self.results_dir.create().await?;
+            self.start_container().await
+        }.instrument(start_span).await?;
+        let log_reader =
             ContainerLogReader::create(
-                self.config.engine.command_name(), 
-                container_name.clone()
+                self.config.engine.command_name(),
+                container_name.clone(),
+                Clone::clone(&self.log_context),
             )?;
+        self.report_status(TestRunStatus::Running, None, None).await;
 
         loop {
-            log_reader.read().await;
-
             // If the value in the channel has changed or the channel has been closed, we terminate
             if self.termination_channel_rx.has_changed().unwrap_or(true) {
                 self.stop_container(&container_name).await?;
+                terminated = true;
             }
 
-            if self.are_results_ready().await? {
-                self.remove_result_ready_indicator().await?;
-                self.upload_results().await?;
+            if self.results_dir.are_ready().await {
+                self.results_dir.remove_ready_indicator().await?;
+                results_location = self.upload_results().await?;
                 results_uploaded = true;
             }
 
@@ -93,9 +104,20 @@ impl ContainerManager {
                 ContainerState::Running => (),
                 ContainerState::Exited => {
                     if ! results_uploaded {
-                        self.remove_result_ready_indicator().await?;
-                        self.upload_results().await?;
+                        self.results_dir.remove_ready_indicator().await?;
+                        results_location = self.upload_results().await?;
                     }
+                    let exit_code = self.get_container_exit_code(&container_name).await?;
+                    log_reader.finish().await;
+
+                    let status = if terminated {
+                        TestRunStatus::Aborted
+                    } else if exit_code == 0 {
+                        TestRunStatus::Succeeded
+                    } else {
+                        TestRunStatus::Failed
+                    };
+                    self.report_status(status, Some(exit_code), results_location.take()).await;
                     break
                 },
                 state => {
@@ -106,7 +128,7 @@ impl ContainerManager {
             tokio::time::sleep(std::time::Duration::from_millis(MONITOR_INTERVAL_MS)).await;
         }
 
-        self.cleanup_results_dir().await?;
+        self.results_dir.cleanup().await?;
 
         Ok(())
     }
@@ -130,6 +152,35 @@ impl ContainerManager {
         
     }
 
+    async fn get_container_exit_code(&self, container_name: &String) -> Result<i32, Error> {
+        let output = Command::new(self.config.engine.command_name())
+            .args(["inspect", "-f", "'{{.State.ExitCode}}'", container_name])
+            .output()
+            .await
+            .map_err(|cause| Error::CommandLineProgramExecution { command: format!("{} inspect", &self.config.engine.command_name()), cause })?;
+
+        let exit_code = String::from_utf8_lossy(&output.stdout).into_owned().replace('\'', "");
+        exit_code.trim().parse::<i32>()
+            .map_err(|cause| Error::Other { message: format!("Invalid exit code returned by {} inspect: '{}': {}", &self.config.engine.command_name(), exit_code.trim(), cause) })
+    }
+
+    /// Reports the progress of the run to CARL.
+    async fn report_status(&self, status: TestRunStatus, exit_code: Option<i32>, results_location: Option<Url>) {
+        let ContainerLogContext { executor_id, run_id, tx_test_run_updates, .. } = &self.log_context;
+
+        let update = TestRunUpdate {
+            run_id: *run_id,
+            executor_id: *executor_id,
+            status,
+            timestamp: SystemTime::now(),
+            exit_code,
+            results_location,
+        };
+        if tx_test_run_updates.send(update).await.is_err() {
+            warn!("Log shipper stopped. Not reporting status '{status}' of run <{run_id}> of executor <{executor_id}>.");
+        }
+    }
+
     async fn start_container(&mut self) -> Result<String, Error>{
 
         let mut cmd = Command::new(self.config.engine.command_name());
@@ -147,7 +198,7 @@ impl ContainerManager {
         }
         cmd.args(["--name", container_name.as_str()]);
 
-        cmd.args(["--mount", format!("type=bind,source={},target={}", self.results_dir.to_string_lossy(), CONTAINER_RESULTS_DIRECTORY).as_str()]);
+        cmd.args(["--mount", format!("type=bind,source={},target={}", self.results_dir.path().to_string_lossy(), CONTAINER_RESULTS_DIRECTORY).as_str()]);
         
         for env in &self.config.envs {
             cmd.args(["--env", &format!("{}={}", env.name(), env.value())]);
@@ -208,126 +259,31 @@ impl ContainerManager {
 
     }
 
-    async fn remove_result_ready_indicator(&self) -> Result<(), Error>{ 
-        let indicator_file = self.results_dir.join(RESULTS_READY_FILE);
-        match fs::remove_file(&indicator_file).await {
-            Ok(_) => Ok(()),
-            Err(err) => match err.kind() {
-                ErrorKind::NotFound => Ok(()),
-                _ => Err(Error::Other { message: format!("Failed to remove result indicator file '{}': {}", indicator_file.to_string_lossy(), err) }),
-            },
-        }
-
-    }
-
-    async fn upload_results(&self) -> Result<(), Error>{
-        info!("Starting upload for results of {}", self.config.name);
-        let results_url = match &self.config.results_url {
-            Some(results_url) => results_url.value(),
-            None => {
-                info!("Container {} has no results URL, won't upload results.", self.config.name);
-                return Ok(());
-            },
-        };
-        
-        let mut zipped_data = Vec::new();
-        // https://github.com/zip-rs/zip2/issues/195 large_file(true) produces invalid zip file with crate version 2.1.3
-        let zip_options = SimpleFileOptions::default().compression_method(CompressionMethod::BZIP2).large_file(false);
-        create_zip_from_directory(&mut zipped_data, &self.results_dir, zip_options).await.map_err(|cause| Error::ResultZipping { path: self.results_dir.clone(), cause })?;
-
-        self.webdav_client.create_collection_path(results_url.clone())
-            .await
-            .map_err(|cause| Error::ResultUploadingInternal { url: results_url.clone(), cause })?;
-
-        let results_file_url = results_url.join(
-            format!("{}_{}.zip", chrono::offset::Local::now().format("%Y-%m-%d_%H-%M-%S"), self.config.name).as_str()
-        ).map_err(|cause| Error::Other { message: format!("Failed to construct URL for results directory: {}", cause) })?;
-
-        let response = self.webdav_client.put(zipped_data, results_file_url.clone())
-            .await
-            .map_err(|cause| Error::ResultUploadingInternal { url: results_file_url.clone(), cause })?;
-
-        match response.status().is_success() {
-            true => {
-                info!("Successfully uploaded results of {}", self.config.name);
-                Ok(())
-            },
-            false => Err(Error::ResultUploadingServer { container_name: self.config.name.clone(), url: results_file_url.clone(), status: response.status() }),
-        }
-
-    }
-
-    async fn create_results_dir(&mut self) -> Result<(), Error>{
-        fs::create_dir_all(&self.results_dir)
-            .await
-            .map_err(|cause| Error::Other { message: format!("Failed to create results directory '{}': {}", self.results_dir.to_string_lossy(), cause) })?;
-        Ok(())
-    }
-
-    async fn cleanup_results_dir(&self) -> Result<(), Error> {
-        fs::remove_dir_all(&self.results_dir)
-            .await
-            .map_err(|cause| Error::Other { message: format!("Failed to remove results directory '{}': {}", self.results_dir.to_string_lossy(), cause) })?;
-        Ok(())
-    }
-
-    async fn are_results_ready(&self) -> Result<bool, Error> {
-        let indicator_file = self.results_dir.join(RESULTS_READY_FILE);
-        Ok(indicator_file.is_file())
+    async fn upload_results(&self) -> Result<Option<Url>, Error> {
+        let results_location = self.results_dir.upload(&self.config.name.to_string()).await?;
+        Ok(results_location)
     }
 
 }
 
-async fn create_zip_from_directory<T>(data: &mut Vec<u8>, directory: &PathBuf, file_options: FileOptions<'_, T>) -> Result<()> 
-    where
-        T: FileOptionExtension + std::marker::Copy,
-    {
-        let mut file_buffer = Vec::new();
-        let zip_buffer = Cursor::new(data);
-        let mut zip = ZipWriter::new(zip_buffer);
-
-        for entry_res in WalkDir::new(directory) {
-            let entry = entry_res?;
-            let entry_path = entry.path();
-            let entry_metadata = entry.metadata()?;
-
-            if entry_metadata.is_file() {
-                let mut f = File::open(&entry_path).await?;
-                f.read_to_end(&mut file_buffer).await?;
-                let relative_path = entry_path.strip_prefix(directory)?;
-                zip.start_file(relative_path.to_string_lossy(), file_options)?;
-                zip.write_all(file_buffer.as_ref())?;
-                file_buffer.clear();
-            } else if entry_metadata.is_dir() {
-                let relative_path = entry_path.strip_prefix(directory)?;
-                zip.add_directory(relative_path.to_string_lossy(), file_options)?;
-            }
-        }
-
-        Ok(())
-    }
-
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Failure while invoking command line program '{command}': {cause}")]
     CommandLineProgramExecution { command: String, cause: std::io::Error },
-    #[error("Failure while creating a ZIP archive of the test results at '{path}' : {cause}")]
-    ResultZipping { path: PathBuf, cause: anyhow::Error },
-    #[error("Failure while uploading test results to '{url}': {cause}")]
-    ResultUploadingInternal { url: Url, cause: webdav_client::Error },
-    #[error("Failure while uploading test results for '{container_name}' to '{url}' (HTTP status {status})")]
-    ResultUploadingServer { container_name: ContainerName, url: Url, status: reqwest::StatusCode },
+    #[error(transparent)]
+    Results(#[from] results::Error),
     #[error("{message}")]
     Other { message: String },
 }
 
+/// Reads the output of a container and hands it to the [`ExecutorLogShipper`](crate::service::test_execution::log_shipper::ExecutorLogShipper) line by line.
 struct ContainerLogReader {
     _log_proc: Child,
-    receiver: mpsc::Receiver<Vec<u8>>,
+    forwarders: Vec<JoinHandle<()>>,
 }
 
 impl ContainerLogReader {
-    pub fn create(engine: &str, container_name: String) -> Result<Self, Error> {
+    pub fn create(engine: &str, container_name: String, log_context: ContainerLogContext) -> Result<Self, Error> {
         let mut cmd = Command::new(engine);
         cmd.args(["logs", "--timestamps", "--follow"]);
         cmd.arg(container_name);
@@ -339,46 +295,112 @@ impl ContainerLogReader {
             .map_err(|cause| Error::CommandLineProgramExecution { command: format!("{engine} logs"), cause })?;
 
         let stdout = child.stdout.take().ok_or(Error::Other { message: format!("Failed to get stdout of '{engine} logs' process")})?;
+        let stderr = child.stderr.take().ok_or(Error::Other { message: format!("Failed to get stderr of '{engine} logs' process")})?;
 
-        let mut stdout_reader = BufReader::new(stdout);
-
-        let (tx, rx) = tokio::sync::mpsc::channel::<Vec<u8>>(100);
+        let sequence_number = Arc::new(AtomicU64::new(0));
 
-        tokio::spawn(async move {
-            let mut buffer = Vec::new();
-    
-            loop {
-                match stdout_reader.read_until(b'\n', &mut buffer).await {
-                    Ok(0) => {
-                        // EOF reached
-                        break;
-                    }
-                    Ok(_) => {
-                        let _ = tx.send(buffer.clone()).await;
-                        buffer.clear();
-                    }
-                    Err(e) => {
-                        error!("Error reading from logs stdout stream: {}", e);
-                        break;
-                    }
-                }
-            }
-        });
-        
+        let forwarders = vec![
+            tokio::spawn(forward_lines(BufReader::new(stdout), ExecutorLogStream::Stdout, Clone::clone(&log_context), Arc::clone(&sequence_number), parse_log_line)),
+            tokio::spawn(forward_lines(BufReader::new(stderr), ExecutorLogStream::Stderr, log_context, sequence_number, parse_log_line)),
+        ];
 
         Ok(
             Self {
                 _log_proc: child,
-                receiver: rx,
+                forwarders,
             }
         )
     }
 
+    /// Waits until the remaining output of the exited container has been forwarded.
+    pub async fn finish(self) {
+        let forwarders = futures::future::join_all(self.forwarders);
+        if tokio::time::timeout(LOG_FORWARDING_TIMEOUT, forwarders).await.is_err() {
+            warn!("Output of container was not completely forwarded within {} seconds.", LOG_FORWARDING_TIMEOUT.as_secs());
+        }
+    }
+}
+
+/// Identifies the run which the output and the progress of an executor belong to.
+#[derive(Clone)]
+pub struct ContainerLogContext {
+    pub executor_id: ExecutorId,
+    pub run_id: ExecutorRunId,
+    pub tx_log_entries: mpsc::Sender<ExecutorLogEntry>,
+    pub tx_test_run_updates: mpsc::Sender<TestRunUpdate>,
+}
 
-    async fn read(&mut self) {
-        while let Ok(line) = self.receiver.try_recv() {
-            info!("Received line: {:?}", String::from_utf8_lossy(&line))
+/// Hands the lines read from `reader` to the log shipper. `parse_line` determines the timestamp and text of each line.
+pub(super) async fn forward_lines<R: AsyncRead + Unpin>(
+    mut reader: BufReader<R>,
+    stream: ExecutorLogStream,
+    log_context: ContainerLogContext,
+    sequence_number: Arc<AtomicU64>,
+    parse_line: fn(&str) -> (SystemTime, String),
+) {
+    let ContainerLogContext { executor_id, run_id, tx_log_entries, .. } = log_context;
+    let mut buffer = Vec::new();
+
+    loop {
+        match reader.read_until(b'\n', &mut buffer).await {
+            Ok(0) => {
+                // EOF reached
+                break;
+            }
+            Ok(_) => {
+                let (timestamp, text) = parse_line(&String::from_utf8_lossy(&buffer));
+                buffer.clear();
+
+                let line = ExecutorLogLine {
+                    sequence_number: sequence_number.fetch_add(1, Ordering::SeqCst),
+                    timestamp,
+                    stream,
+                    text,
+                };
+                if tx_log_entries.send(ExecutorLogEntry { executor_id, run_id, line }).await.is_err() {
+                    warn!("Log shipper stopped. Not forwarding further logs of run <{run_id}> of executor <{executor_id}>.");
+                    break;
+                }
+            }
+            Err(e) => {
+                error!("Error reading from logs {} stream: {}", stream, e);
+                break;
+            }
         }
     }
-    
+}
+
+/// Splits a line, as output by `logs --timestamps`, into its timestamp and text.
+/// Falls back to the current time, if the line does not start with a timestamp.
+fn parse_log_line(line: &str) -> (SystemTime, String) {
+    let line = line.trim_end_matches(['\n', '\r']);
+
+    line.split_once(' ')
+        .and_then(|(timestamp, text)| {
+            chrono::DateTime::parse_from_rfc3339(timestamp).ok()
+                .map(|timestamp| (SystemTime::from(timestamp), text.to_owned()))
+        })
+        .unwrap_or_else(|| (SystemTime::now(), line.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_a_log_line_with_timestamp() {
+        let (timestamp, text) = parse_log_line("2024-09-16T08:15:23.500000000Z Running test 1 of 3\n");
+
+        assert_that!(timestamp, eq(SystemTime::UNIX_EPOCH + Duration::from_millis(1_726_474_523_500)));
+        assert_that!(text, eq("Running test 1 of 3"));
+    }
+
+    #[test]
+    fn should_keep_a_log_line_without_timestamp() {
+        let (_, text) = parse_log_line("Running test 1 of 3\n");
+
+        assert_that!(text, eq("Running test 1 of 3"));
+    }
 }
@@ -0,0 +1,363 @@
+use std::{process::{ExitStatus, Stdio}, sync::{atomic::AtomicU64, Arc}, time::{Duration, SystemTime}};
+
+use nix::errno::Errno;
+use nix::sys::signal::{killpg, Signal};
+use nix::unistd::Pid;
+use tokio::{io::BufReader, process::{Child, Command}, sync::watch};
+use tracing::{error, info, warn, Instrument, Span};
+use url::Url;
+
+use opendut_types::peer::executor::container::{ContainerCommandArgument, ContainerEnvironmentVariable};
+use opendut_types::peer::executor::executable::{ExecutablePath, ExecutableTimeout, ExecutableWorkingDirectory};
+use opendut_types::peer::executor::log::ExecutorLogStream;
+use opendut_types::peer::executor::test_run::{TestRunStatus, TestRunUpdate};
+use opendut_types::peer::executor::ResultsUrl;
+
+use crate::service::test_execution::container_manager::{forward_lines, ContainerLogContext, LOG_FORWARDING_TIMEOUT};
+use crate::service::test_execution::results::{self, ResultsDirectory, ResultsUploadOptions};
+
+/// Environment variable, which tells the executable where to write its results.
+/// Like in containers, creating the file `.results_ready` in this directory triggers the upload.
+pub const RESULTS_DIRECTORY_VARIABLE: &str = "OPENDUT_RESULTS_DIR";
+
+const MONITOR_INTERVAL_MS: u64 = 1000;
+
+pub struct ExecutableConfiguration {
+    pub path: ExecutablePath,
+    pub args: Vec<ContainerCommandArgument>,
+    pub envs: Vec<ContainerEnvironmentVariable>,
+    pub working_dir: Option<ExecutableWorkingDirectory>,
+    pub timeout: Option<ExecutableTimeout>,
+    pub results_url: Option<ResultsUrl>,
+}
+
+/// Runs a program natively on the host, analogous to the [`ContainerManager`](crate::service::test_execution::container_manager::ContainerManager).
+pub struct ExecutableManager {
+    config: ExecutableConfiguration,
+    results_dir: ResultsDirectory,
+    termination_channel_rx: watch::Receiver<bool>,
+    log_context: ContainerLogContext,
+}
+
+enum Outcome {
+    Exited(ExitStatus),
+    Terminated,
+    TimedOut,
+}
+
+impl ExecutableManager {
+
+    pub fn new(executable_configuration: ExecutableConfiguration, termination_channel_rx: watch::Receiver<bool>, log_context: ContainerLogContext, results_upload_options: ResultsUploadOptions) -> Self {
+        let results_dir = ResultsDirectory::new(Clone::clone(&executable_configuration.results_url), results_upload_options);
+        Self {
+            config: executable_configuration,
+            results_dir,
+            termination_channel_rx,
+            log_context,
+        }
+    }
+
+    /// Starts the executable within the given span and then monitors it until it exits, times out or is terminated.
+    pub async fn start(&mut self, start_span: Span) {
+        if let Err(cause) = self.run(start_span).await {
+            error!("{cause}");
+            self.report_status(TestRunStatus::Failed, None, None).await;
+        }
+    }
+
+    async fn run(&mut self, start_span: Span) -> Result<(), Error> {
+        let mut results_location = None;
+        let mut results_uploaded = false;
+
+        let mut child = async {
+            self.results_dir.create().await?;
+            self.start_executable()
+        }.instrument(start_span).await?;
+
+        let forwarders = self.forward_output(&mut child)?;
+        self.report_status(TestRunStatus::Running, None, None).await;
+
+        let deadline = self.config.timeout
+            .map(|timeout| tokio::time::Instant::now() + timeout.value());
+
+        let outcome = loop {
+            // If the value in the channel has changed or the channel has been closed, we terminate
+            if self.termination_channel_rx.has_changed().unwrap_or(true) {
+                self.kill(&mut child).await?;
+                break Outcome::Terminated;
+            }
+
+            if deadline.is_some_and(|deadline| tokio::time::Instant::now() >= deadline) {
+                warn!("Executable '{}' did not exit within its timeout of {}. Killing it.", self.config.path, self.config.timeout.map(|timeout| timeout.to_string()).unwrap_or_default());
+                self.kill(&mut child).await?;
+                break Outcome::TimedOut;
+            }
+
+            if self.results_dir.are_ready().await {
+                self.results_dir.remove_ready_indicator().await?;
+                results_location = self.upload_results().await?;
+                results_uploaded = true;
+            }
+
+            let exit_status = child.try_wait()
+                .map_err(|cause| Error::ExecutableExecution { path: self.config.path.clone(), cause })?;
+            if let Some(exit_status) = exit_status {
+                break Outcome::Exited(exit_status);
+            }
+
+            tokio::time::sleep(Duration::from_millis(MONITOR_INTERVAL_MS)).await;
+        };
+
+        if ! results_uploaded {
+            self.results_dir.remove_ready_indicator().await?;
+            results_location = self.upload_results().await?;
+        }
+
+        let forwarders = futures::future::join_all(forwarders);
+        if tokio::time::timeout(LOG_FORWARDING_TIMEOUT, forwarders).await.is_err() {
+            warn!("Output of executable was not completely forwarded within {} seconds.", LOG_FORWARDING_TIMEOUT.as_secs());
+        }
+
+        let (status, exit_code) = match outcome {
+            Outcome::Exited(exit_status) => {
+                let status = if exit_status.success() { TestRunStatus::Succeeded } else { TestRunStatus::Failed };
+                (status, exit_status.code())
+            }
+            Outcome::Terminated => (TestRunStatus::Aborted, None),
+            Outcome::TimedOut => (TestRunStatus::Failed, None),
+        };
+        self.report_status(status, exit_code, results_location).await;
+
+        self.results_dir.cleanup().await?;
+
+        Ok(())
+    }
+
+    fn start_executable(&self) -> Result<Child, Error> {
+        let mut cmd = Command::new(self.config.path.value());
+        cmd.args(self.config.args.iter().map(|arg| arg.value()));
+        for env in &self.config.envs {
+            cmd.env(env.name(), env.value());
+        }
+        cmd.env(RESULTS_DIRECTORY_VARIABLE, self.results_dir.path());
+        if let Some(working_dir) = &self.config.working_dir {
+            cmd.current_dir(working_dir.value());
+        }
+        cmd.stdin(Stdio::null());
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+        cmd.process_group(0); //own process group, so that processes started by the executable can be killed along with it
+        cmd.kill_on_drop(true);
+
+        let child = cmd.spawn()
+            .map_err(|cause| Error::ExecutableExecution { path: self.config.path.clone(), cause })?;
+
+        info!("Started executable {}", self.config.path);
+        Ok(child)
+    }
+
+    fn forward_output(&self, child: &mut Child) -> Result<Vec<tokio::task::JoinHandle<()>>, Error> {
+        let stdout = child.stdout.take().ok_or(Error::Other { message: format!("Failed to get stdout of executable '{}'", self.config.path) })?;
+        let stderr = child.stderr.take().ok_or(Error::Other { message: format!("Failed to get stderr of executable '{}'", self.config.path) })?;
+
+        let sequence_number = Arc::new(AtomicU64::new(0));
+
+        Ok(vec![
+            tokio::spawn(forward_lines(BufReader::new(stdout), ExecutorLogStream::Stdout, Clone::clone(&self.log_context), Arc::clone(&sequence_number), parse_log_line)),
+            tokio::spawn(forward_lines(BufReader::new(stderr), ExecutorLogStream::Stderr, Clone::clone(&self.log_context), sequence_number, parse_log_line)),
+        ])
+    }
+
+    /// Kills the process group of the executable, including the processes it started, and then waits for the executable to exit.
+    async fn kill(&self, child: &mut Child) -> Result<(), Error> {
+        if let Some(process_group_id) = child.id() {
+            let process_group_id = i32::try_from(process_group_id)
+                .map_err(|cause| Error::Other { message: format!("Invalid process ID of executable '{}': {cause}", self.config.path) })?;
+
+            match killpg(Pid::from_raw(process_group_id), Signal::SIGKILL) {
+                Ok(()) | Err(Errno::ESRCH) => {}
+                Err(cause) => return Err(Error::ExecutableExecution { path: self.config.path.clone(), cause: cause.into() }),
+            }
+        }
+        child.kill().await
+            .map_err(|cause| Error::ExecutableExecution { path: self.config.path.clone(), cause })
+    }
+
+    async fn upload_results(&self) -> Result<Option<Url>, Error> {
+        let executable_name = std::path::Path::new(self.config.path.value())
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.config.path.to_string());
+        let results_location = self.results_dir.upload(&executable_name).await?;
+        Ok(results_location)
+    }
+
+    /// Reports the progress of the run to CARL.
+    async fn report_status(&self, status: TestRunStatus, exit_code: Option<i32>, results_location: Option<Url>) {
+        let ContainerLogContext { executor_id, run_id, tx_test_run_updates, .. } = &self.log_context;
+
+        let update = TestRunUpdate {
+            run_id: *run_id,
+            executor_id: *executor_id,
+            status,
+            timestamp: SystemTime::now(),
+            exit_code,
+            results_location,
+        };
+        if tx_test_run_updates.send(update).await.is_err() {
+            warn!("Log shipper stopped. Not reporting status '{status}' of run <{run_id}> of executor <{executor_id}>.");
+        }
+    }
+}
+
+/// Unlike the output of containers, the output of executables carries no timestamps, so the time of reading is used.
+fn parse_log_line(line: &str) -> (SystemTime, String) {
+    (SystemTime::now(), line.trim_end_matches(['\n', '\r']).to_owned())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failure while executing '{path}': {cause}")]
+    ExecutableExecution { path: ExecutablePath, cause: std::io::Error },
+    #[error(transparent)]
+    Results(#[from] results::Error),
+    #[error("{message}")]
+    Other { message: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use tokio::sync::mpsc;
+
+    use std::ops::Not;
+
+    use opendut_types::peer::executor::ExecutorId;
+    use opendut_types::peer::executor::log::ExecutorRunId;
+
+    use crate::service::test_execution::log_shipper::ExecutorLogEntry;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_report_the_exit_code_of_an_executable() -> anyhow::Result<()> {
+        let mut fixture = Fixture::new();
+        let config = ExecutableConfiguration {
+            envs: vec![ContainerEnvironmentVariable::new("GREETING", "Hello")?],
+            ..shell_configuration("echo $GREETING; exit 3")?
+        };
+
+        fixture.manager(config).start(Span::none()).await;
+
+        let log_entry = fixture.rx_log_entries.recv().await.unwrap();
+        assert_that!(log_entry.line.text, eq("Hello"));
+
+        let running = fixture.rx_test_run_updates.recv().await.unwrap();
+        assert_that!(running.status, eq(TestRunStatus::Running));
+        let finished = fixture.rx_test_run_updates.recv().await.unwrap();
+        assert_that!(finished.status, eq(TestRunStatus::Failed));
+        assert_that!(finished.exit_code, some(eq(3)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_kill_an_executable_which_exceeds_its_timeout() -> anyhow::Result<()> {
+        let mut fixture = Fixture::new();
+        let config = ExecutableConfiguration {
+            timeout: Some(ExecutableTimeout::try_from(1)?),
+            ..shell_configuration("sleep 30")?
+        };
+
+        tokio::time::timeout(Duration::from_secs(20), fixture.manager(config).start(Span::none())).await?;
+
+        let running = fixture.rx_test_run_updates.recv().await.unwrap();
+        assert_that!(running.status, eq(TestRunStatus::Running));
+        let finished = fixture.rx_test_run_updates.recv().await.unwrap();
+        assert_that!(finished.status, eq(TestRunStatus::Failed));
+        assert_that!(finished.exit_code, none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_kill_the_processes_started_by_an_executable_when_terminating() -> anyhow::Result<()> {
+        let mut fixture = Fixture::new();
+        let config = shell_configuration("sleep 30 & echo $!; wait")?;
+
+        let mut manager = fixture.manager(config);
+        let run = tokio::spawn(async move { manager.start(Span::none()).await });
+
+        let log_entry = fixture.rx_log_entries.recv().await.unwrap();
+        let grandchild_pid = log_entry.line.text.parse::<u32>()?;
+        assert!(is_alive(grandchild_pid));
+
+        fixture.tx_termination.send(true)?;
+        tokio::time::timeout(Duration::from_secs(20), run).await??;
+
+        let running = fixture.rx_test_run_updates.recv().await.unwrap();
+        assert_that!(running.status, eq(TestRunStatus::Running));
+        let finished = fixture.rx_test_run_updates.recv().await.unwrap();
+        assert_that!(finished.status, eq(TestRunStatus::Aborted));
+
+        let mut attempts = 0;
+        while is_alive(grandchild_pid) && attempts < 50 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            attempts += 1;
+        }
+        assert!(!is_alive(grandchild_pid), "Process <{grandchild_pid}> started by the executable is still running.");
+        Ok(())
+    }
+
+    struct Fixture {
+        log_context: ContainerLogContext,
+        rx_log_entries: mpsc::Receiver<ExecutorLogEntry>,
+        rx_test_run_updates: mpsc::Receiver<TestRunUpdate>,
+        tx_termination: watch::Sender<bool>,
+        rx_termination: watch::Receiver<bool>,
+    }
+    impl Fixture {
+        fn new() -> Self {
+            let (tx_log_entries, rx_log_entries) = mpsc::channel(10);
+            let (tx_test_run_updates, rx_test_run_updates) = mpsc::channel(10);
+            let (tx_termination, rx_termination) = watch::channel(false);
+
+            let log_context = ContainerLogContext {
+                executor_id: ExecutorId::random(),
+                run_id: ExecutorRunId::random(),
+                tx_log_entries,
+                tx_test_run_updates,
+            };
+            Self { log_context, rx_log_entries, rx_test_run_updates, tx_termination, rx_termination }
+        }
+
+        fn manager(&self, config: ExecutableConfiguration) -> ExecutableManager {
+            let results_upload_options = ResultsUploadOptions {
+                confidential_client: None,
+                results_storage_url: None,
+                retries: 0,
+                retry_interval: Duration::ZERO,
+            };
+            ExecutableManager::new(config, Clone::clone(&self.rx_termination), Clone::clone(&self.log_context), results_upload_options)
+        }
+    }
+
+    fn shell_configuration(script: &str) -> anyhow::Result<ExecutableConfiguration> {
+        Ok(ExecutableConfiguration {
+            path: ExecutablePath::try_from("sh")?,
+            args: vec![
+                ContainerCommandArgument::try_from("-c")?,
+                ContainerCommandArgument::try_from(script)?,
+            ],
+            envs: vec![],
+            working_dir: None,
+            timeout: None,
+            results_url: None,
+        })
+    }
+
+    /// Processes, which have been killed but not yet reaped, are shown as zombies.
+    fn is_alive(pid: u32) -> bool {
+        std::fs::read_to_string(format!("/proc/{pid}/stat"))
+            .map(|stat| stat.rsplit_once(')').is_some_and(|(_, fields)| fields.trim_start().starts_with('Z').not()))
+            .unwrap_or(false)
+    }
+}
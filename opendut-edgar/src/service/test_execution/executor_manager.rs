@@ -1,84 +1,188 @@
+use std::collections::HashMap;
+use std::ops::Not;
 use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
-use opendut_types::peer::{self, executor::{ExecutorDescriptor, ExecutorKind}};
+use opendut_types::peer::{self, executor::{ExecutorDescriptor, ExecutorId, ExecutorKind, log::ExecutorRunId, test_run::{ExecutorRunOverrides, TestRunStatus, TestRunUpdate}}};
+use tokio::sync::mpsc;
 use tokio::sync::watch::{self, Sender};
-use tracing::{debug, warn};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
 
-use crate::service::test_execution::container_manager::{ContainerManager, ContainerConfiguration};
+use crate::service::test_execution::container_manager::{ContainerManager, ContainerConfiguration, ContainerLogContext};
+use crate::service::test_execution::executable_manager::{ExecutableConfiguration, ExecutableManager};
+use crate::service::test_execution::log_shipper::ExecutorLogEntry;
+use crate::service::test_execution::results::ResultsUploadOptions;
 
 pub type ExecutorManagerRef = Arc<Mutex<ExecutorManager>>;
 
 #[derive(Debug)]
 pub struct ExecutorManager {
-    tx_termination_channels: Vec<Sender<bool>>,
+    executors: HashMap<ExecutorId, ExecutorDescriptor>,
+    runs: HashMap<ExecutorId, ExecutorRun>,
+    tx_log_entries: mpsc::Sender<ExecutorLogEntry>,
+    tx_test_run_updates: mpsc::Sender<TestRunUpdate>,
+    results_upload_options: ResultsUploadOptions,
+}
+
+#[derive(Debug)]
+struct ExecutorRun {
+    run_id: ExecutorRunId,
+    tx_termination: Sender<bool>,
+    handle: JoinHandle<()>,
 }
 
 impl ExecutorManager {
-    pub fn create() -> ExecutorManagerRef {
+    pub fn create(tx_log_entries: mpsc::Sender<ExecutorLogEntry>, tx_test_run_updates: mpsc::Sender<TestRunUpdate>, results_upload_options: ResultsUploadOptions) -> ExecutorManagerRef {
         Arc::new(Mutex::new(Self {
-            tx_termination_channels: Vec::new(),
+            executors: HashMap::new(),
+            runs: HashMap::new(),
+            tx_log_entries,
+            tx_test_run_updates,
+            results_upload_options,
         }))
     }
 
-    pub fn create_new_executors(&mut self, executors: Vec<peer::configuration::Parameter<ExecutorDescriptor>>) {
-        debug!("Creating executors.");
+    /// Remembers the given executors, so that they can be started on request of CARL.
+    /// Runs of executors, which are not configured anymore, are stopped.
+    pub fn update_executors(&mut self, executors: Vec<peer::configuration::Parameter<ExecutorDescriptor>>) {
+        debug!("Updating executors.");
 
-        let executors = executors.into_iter()
+        self.executors = executors.into_iter()
             .filter_map(|executor| { //TODO properly handle Present vs. Absent
                 if matches!(executor.target, peer::configuration::ParameterTarget::Present) {
-                    Some(executor.value)
+                    Some((executor.value.id, executor.value))
                 } else {
                     None
                 }
+            })
+            .collect();
+
+        let removed_executors = self.runs.keys()
+            .filter(|executor_id| self.executors.contains_key(executor_id).not())
+            .copied()
+            .collect::<Vec<_>>();
+
+        for executor_id in removed_executors {
+            self.stop_executor(executor_id);
+        }
+    }
+
+    /// Starts a run of the given executor with the ID assigned by CARL.
+    /// A run of the same executor, which is still in progress, is stopped beforehand.
+    pub fn start_executor(&mut self, executor_id: ExecutorId, run_id: ExecutorRunId, overrides: ExecutorRunOverrides) {
+
+        let Some(ExecutorDescriptor { id, kind, results_url }) = self.executors.get(&executor_id).cloned() else {
+            warn!("Received request to start unknown executor <{executor_id}>. Reporting run <{run_id}> as failed.");
+            self.report_failed_run(executor_id, run_id);
+            return;
+        };
+
+        let previous_run = self.runs.remove(&executor_id)
+            .map(|previous_run| {
+                info!("Stopping run <{}> of executor <{executor_id}>, before starting run <{run_id}>.", previous_run.run_id);
+                previous_run.terminate();
+                previous_run.handle
             });
 
-        for executor in executors {
+        //the span only covers starting the executor, so that the trace of the request does not last as long as the executor runs
+        let start_span = tracing::info_span!("start_executor", executor_id = %id.uuid, run_id = %run_id);
 
-            let (tx, rx) = watch::channel(false);
+        let (tx_termination, rx_termination) = watch::channel(false);
 
-            let ExecutorDescriptor { id: _, kind, results_url } = executor;
+        let log_context = ContainerLogContext {
+            executor_id: id,
+            run_id,
+            tx_log_entries: Clone::clone(&self.tx_log_entries),
+            tx_test_run_updates: Clone::clone(&self.tx_test_run_updates),
+        };
+        let results_upload_options = Clone::clone(&self.results_upload_options);
 
-            match kind {
-                ExecutorKind::Executable => warn!("Executing Executable not yet implemented."),
-                ExecutorKind::Container {
-                    engine,
+        let handle = match overrides.apply(kind) {
+            ExecutorKind::Executable { path, args, envs, working_dir, timeout } => {
+                let executable_config = ExecutableConfiguration {
+                    path,
+                    args,
+                    envs,
+                    working_dir,
+                    timeout,
+                    results_url,
+                };
+                tokio::spawn(async move {
+                    if let Some(previous_run) = previous_run {
+                        let _ = previous_run.await; //wait for the previous process to exit, so both do not run concurrently
+                    }
+                    ExecutableManager::new(executable_config, rx_termination, log_context, results_upload_options).start(start_span).await;
+                })
+            }
+            ExecutorKind::Container {
+                engine,
+                name,
+                image,
+                volumes,
+                devices,
+                envs,
+                ports,
+                command,
+                args,
+            } => {
+                let container_config = ContainerConfiguration{
                     name,
+                    engine,
                     image,
-                    volumes,
-                    devices,
-                    envs,
-                    ports,
                     command,
                     args,
-                } => {
-                    let container_config = ContainerConfiguration{
-                        name,
-                        engine,
-                        image,
-                        command,
-                        args,
-                        envs,
-                        results_url,
-                        ports,
-                        devices,
-                        volumes,
-                    };
-                    tokio::spawn(async move {
-                        ContainerManager::new(container_config, rx).start().await;
-                    });
-                }
+                    envs,
+                    results_url,
+                    ports,
+                    devices,
+                    volumes,
+                };
+                tokio::spawn(async move {
+                    if let Some(previous_run) = previous_run {
+                        let _ = previous_run.await; //wait for the previous container to be removed, so both do not run concurrently
+                    }
+                    ContainerManager::new(container_config, rx_termination, log_context, results_upload_options).start(start_span).await;
+                })
             }
-            self.tx_termination_channels.push(tx);
-        }
+        };
+
+        self.runs.insert(executor_id, ExecutorRun { run_id, tx_termination, handle });
     }
 
-    pub fn terminate_executors(&mut self) {
-        debug!("Terminating executors.");
-        for tx_termination_channel in &self.tx_termination_channels {
-            if let Err(cause) = tx_termination_channel.send(true) {
-                warn!("Failed to send termination signal to executor, perhaps it already terminated? Cause: {cause}");
+    /// Stops the run of the given executor, if one is in progress.
+    pub fn stop_executor(&mut self, executor_id: ExecutorId) {
+        match self.runs.remove(&executor_id) {
+            Some(run) => {
+                info!("Stopping run <{}> of executor <{executor_id}>.", run.run_id);
+                run.terminate();
             }
+            None => debug!("Received request to stop executor <{executor_id}>, but no run is in progress."),
+        }
+    }
+
+    fn report_failed_run(&self, executor_id: ExecutorId, run_id: ExecutorRunId) {
+        let update = TestRunUpdate {
+            run_id,
+            executor_id,
+            status: TestRunStatus::Failed,
+            timestamp: SystemTime::now(),
+            exit_code: None,
+            results_location: None,
+        };
+        if let Err(cause) = self.tx_test_run_updates.try_send(update) {
+            warn!("Failed to report run <{run_id}> of executor <{executor_id}> as failed: {cause}");
+        }
+    }
+}
+
+impl ExecutorRun {
+    fn terminate(&self) {
+        if self.handle.is_finished() {
+            return;
+        }
+        if let Err(cause) = self.tx_termination.send(true) {
+            warn!("Failed to send termination signal to run <{}>, perhaps it already terminated? Cause: {cause}", self.run_id);
         }
-        self.tx_termination_channels.clear();
     }
 }
@@ -0,0 +1,370 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::ops::Not;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, watch};
+use tracing::{debug, info, warn};
+
+use opendut_carl_api::proto::services::peer_messaging_broker;
+use opendut_carl_api::proto::services::peer_messaging_broker::{upstream, ExecutorLogs};
+use opendut_types::peer::executor::log::{ExecutorLogLine, ExecutorRunId};
+use opendut_types::peer::executor::test_run::TestRunUpdate;
+use opendut_types::peer::executor::ExecutorId;
+
+use crate::common::constants::edgar_install_directory;
+
+/// Number of lines which may be queued for the [`ExecutorLogShipper`], before reading further output of the executors is paused.
+const BUFFERED_LINES_CAPACITY: usize = 10_000;
+/// Number of lines which may be spooled while they cannot be sent to CARL. Further lines are dropped.
+const MAX_SPOOLED_LINES: usize = 100_000;
+/// Number of progress updates of test runs, which may be buffered.
+const BUFFERED_TEST_RUN_UPDATES_CAPACITY: usize = 100;
+/// Maximum number of lines sent to CARL in one message.
+const MAX_BATCH_SIZE: usize = 500;
+/// Maximum time a line is held back, before it is sent to CARL. Also the delay before sending again, after sending failed.
+const BATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+pub type ExecutorLogShipperRef = Arc<ExecutorLogShipper>;
+
+/// File in which the output of the executors is spooled, until it has been sent to CARL.
+pub fn default_spool_file() -> PathBuf {
+    edgar_install_directory().join("executor-logs-spool.jsonl")
+}
+
+/// A line of output of a run of an executor, as handed to the [`ExecutorLogShipper`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExecutorLogEntry {
+    pub executor_id: ExecutorId,
+    pub run_id: ExecutorRunId,
+    pub line: ExecutorLogLine,
+}
+
+/// Collects the output of all executors and sends it to CARL in batches.
+/// Also reports the progress of the test runs, after the output which was produced before.
+///
+/// Lines are spooled to a file until they have been sent, so they are sent after a restart of EDGAR, if no connection to CARL was available before.
+/// CARL ignores lines it already received, so lines may be sent twice.
+/// When [`MAX_SPOOLED_LINES`] are spooled, further lines are dropped and the number of dropped lines is logged.
+/// Progress updates are only kept in memory.
+pub struct ExecutorLogShipper {
+    tx_entries: mpsc::Sender<ExecutorLogEntry>,
+    tx_test_run_updates: mpsc::Sender<TestRunUpdate>,
+    tx_upstream: watch::Sender<Option<mpsc::Sender<peer_messaging_broker::Upstream>>>,
+}
+
+impl ExecutorLogShipper {
+    /// Starts shipping logs, beginning with the lines left in the spool file by a previous run of EDGAR.
+    pub fn spawn(spool_file: PathBuf) -> ExecutorLogShipperRef {
+        Self::spawn_with(spool_file, MAX_SPOOLED_LINES)
+    }
+
+    fn spawn_with(spool_file: PathBuf, max_spooled_lines: usize) -> ExecutorLogShipperRef {
+        let (tx_entries, rx_entries) = mpsc::channel(BUFFERED_LINES_CAPACITY);
+        let (tx_test_run_updates, rx_test_run_updates) = mpsc::channel(BUFFERED_TEST_RUN_UPDATES_CAPACITY);
+        let (tx_upstream, rx_upstream) = watch::channel(None);
+
+        let spool = Spool::load(spool_file, max_spooled_lines);
+        tokio::spawn(ship_logs(spool, rx_entries, rx_test_run_updates, rx_upstream));
+
+        Arc::new(Self { tx_entries, tx_test_run_updates, tx_upstream })
+    }
+
+    /// Channel into which the output of the executors should be sent.
+    pub fn sender(&self) -> mpsc::Sender<ExecutorLogEntry> {
+        Clone::clone(&self.tx_entries)
+    }
+
+    /// Channel into which the progress of the test runs should be sent.
+    pub fn test_run_update_sender(&self) -> mpsc::Sender<TestRunUpdate> {
+        Clone::clone(&self.tx_test_run_updates)
+    }
+
+    /// Sets the stream to CARL, via which the logs are sent from now on.
+    pub fn connect(&self, tx_upstream: mpsc::Sender<peer_messaging_broker::Upstream>) {
+        self.tx_upstream.send_replace(Some(tx_upstream));
+    }
+}
+
+async fn ship_logs(
+    mut spool: Spool,
+    mut rx_entries: mpsc::Receiver<ExecutorLogEntry>,
+    mut rx_test_run_updates: mpsc::Receiver<TestRunUpdate>,
+    rx_upstream: watch::Receiver<Option<mpsc::Sender<peer_messaging_broker::Upstream>>>,
+) {
+    let mut interval = tokio::time::interval(BATCH_INTERVAL);
+    let mut pending_updates = VecDeque::new();
+
+    loop {
+        let flush = tokio::select! {
+            entry = rx_entries.recv() => match entry {
+                Some(entry) => {
+                    spool.push(entry);
+                    spool.lines >= MAX_BATCH_SIZE
+                }
+                None => {
+                    debug!("All senders of executor logs have been dropped. Sending remaining logs and stopping. Logs, which cannot be sent, remain spooled.");
+                    send_pending(&mut spool, &mut pending_updates, &rx_upstream).await;
+                    break;
+                }
+            },
+            Some(update) = rx_test_run_updates.recv() => {
+                //the output of a run is sent before its update, so CARL has the complete output, once the run is reported as finished
+                while let Ok(entry) = rx_entries.try_recv() {
+                    spool.push(entry);
+                }
+                pending_updates.push_back(update);
+                true
+            },
+            _ = interval.tick() => true,
+        };
+
+        if flush {
+            send_pending(&mut spool, &mut pending_updates, &rx_upstream).await;
+        }
+    }
+}
+
+/// Sends the spooled lines and afterwards the pending progress updates to CARL, if a stream to CARL is available.
+/// Whatever could not be sent is kept and sent again with the next flush.
+async fn send_pending(
+    spool: &mut Spool,
+    pending_updates: &mut VecDeque<TestRunUpdate>,
+    rx_upstream: &watch::Receiver<Option<mpsc::Sender<peer_messaging_broker::Upstream>>>,
+) {
+    if spool.batches.is_empty() && pending_updates.is_empty() {
+        return;
+    }
+    let Some(tx_upstream) = Clone::clone(&*rx_upstream.borrow()) else {
+        return;
+    };
+
+    let runs = spool.batches.keys().copied().collect::<Vec<_>>();
+    for (executor_id, run_id) in runs {
+        let lines = spool.batches.get(&(executor_id, run_id)).map(Vec::as_slice).unwrap_or_default();
+
+        for batch in lines.chunks(MAX_BATCH_SIZE) {
+            let message = peer_messaging_broker::Upstream {
+                context: None,
+                message: Some(upstream::Message::ExecutorLogs(ExecutorLogs {
+                    executor_id: Some(executor_id.into()),
+                    run_id: Some(run_id.into()),
+                    lines: batch.iter().cloned().map(From::from).collect(),
+                })),
+            };
+
+            if let Err(cause) = tx_upstream.send(message).await {
+                warn!("Failed to send logs of run <{run_id}> of executor <{executor_id}> to CARL. Keeping them spooled and retrying in {} ms.\n  {cause}", BATCH_INTERVAL.as_millis());
+                return;
+            }
+        }
+        spool.remove(executor_id, run_id);
+    }
+    spool.clear();
+
+    while let Some(update) = pending_updates.front() {
+        let TestRunUpdate { run_id, executor_id, status, .. } = *update;
+
+        let message = peer_messaging_broker::Upstream {
+            context: None,
+            message: Some(upstream::Message::TestRunUpdate(Clone::clone(update).into())),
+        };
+
+        if let Err(cause) = tx_upstream.send(message).await {
+            warn!("Failed to send status '{status}' of run <{run_id}> of executor <{executor_id}> to CARL. Retrying in {} ms.\n  {cause}", BATCH_INTERVAL.as_millis());
+            return;
+        }
+        pending_updates.pop_front();
+    }
+}
+
+/// Lines which have not been sent to CARL yet. They are kept in memory and appended to a file,
+/// from which they are loaded again after a restart of EDGAR.
+struct Spool {
+    path: PathBuf,
+    file: Option<File>,
+    /// Set after writing to the file failed, to not retry and warn for every line until the spool is cleared.
+    file_failed: bool,
+    batches: HashMap<(ExecutorId, ExecutorRunId), Vec<ExecutorLogLine>>,
+    lines: usize,
+    max_lines: usize,
+    dropped_lines: u64,
+}
+
+impl Spool {
+    /// Loads the lines, which were spooled but not sent before EDGAR was stopped.
+    fn load(path: PathBuf, max_lines: usize) -> Self {
+        let mut spool = Self {
+            path,
+            file: None,
+            file_failed: false,
+            batches: HashMap::new(),
+            lines: 0,
+            max_lines,
+            dropped_lines: 0,
+        };
+
+        match std::fs::read_to_string(&spool.path) {
+            Ok(content) => {
+                for line in content.lines() {
+                    match serde_json::from_str::<ExecutorLogEntry>(line) {
+                        Ok(entry) => spool.insert(entry),
+                        Err(cause) => warn!("Skipping malformed line in spool file '{}' of executor logs: {cause}", spool.path.display()),
+                    }
+                }
+                if spool.lines > 0 {
+                    info!("Replaying {} line(s) of executor logs, which were not sent to CARL before EDGAR was stopped.", spool.lines);
+                }
+            }
+            Err(cause) if cause.kind() == io::ErrorKind::NotFound => {}
+            Err(cause) => warn!("Failed to read spool file '{}' of executor logs. Logs, which were not sent to CARL before EDGAR was stopped, are lost.\n  {cause}", spool.path.display()),
+        }
+        spool
+    }
+
+    /// Spools a line, unless the spool is full, in which case the line is dropped.
+    fn push(&mut self, entry: ExecutorLogEntry) {
+        if self.lines >= self.max_lines {
+            if self.dropped_lines == 0 {
+                warn!("Spool of executor logs is full with {} lines, which have not been sent to CARL yet. Dropping further lines until they have been sent.", self.max_lines);
+            }
+            self.dropped_lines += 1;
+            return;
+        }
+
+        if self.file_failed.not() {
+            if let Err(cause) = self.append_to_file(&entry) {
+                warn!("Failed to write executor logs to spool file '{}'. Logs, which are not sent to CARL before EDGAR is stopped, are lost.\n  {cause}", self.path.display());
+                self.file_failed = true;
+            }
+        }
+        self.insert(entry);
+    }
+
+    fn insert(&mut self, entry: ExecutorLogEntry) {
+        if self.lines >= self.max_lines {
+            self.dropped_lines += 1;
+            return;
+        }
+        let ExecutorLogEntry { executor_id, run_id, line } = entry;
+        self.batches.entry((executor_id, run_id)).or_default().push(line);
+        self.lines += 1;
+    }
+
+    fn append_to_file(&mut self, entry: &ExecutorLogEntry) -> io::Result<()> {
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => self.file.insert(OpenOptions::new().create(true).append(true).open(&self.path)?),
+        };
+        let json = serde_json::to_string(entry)?;
+        writeln!(file, "{json}")
+    }
+
+    /// Removes the lines of a run from memory, after they have been sent. They remain in the file until the spool is cleared.
+    fn remove(&mut self, executor_id: ExecutorId, run_id: ExecutorRunId) {
+        if let Some(lines) = self.batches.remove(&(executor_id, run_id)) {
+            self.lines -= lines.len();
+        }
+    }
+
+    /// Removes all lines and the spool file, after the lines have been sent.
+    fn clear(&mut self) {
+        self.batches.clear();
+        self.lines = 0;
+
+        if self.dropped_lines > 0 {
+            warn!("Dropped {} line(s) of executor logs, because the spool was full.", self.dropped_lines);
+            self.dropped_lines = 0;
+        }
+
+        self.file = None;
+        self.file_failed = false;
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => {}
+            Err(cause) if cause.kind() == io::ErrorKind::NotFound => {}
+            Err(cause) => warn!("Failed to remove spool file '{}' of executor logs. Its logs are sent again after a restart of EDGAR.\n  {cause}", self.path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+
+    use opendut_types::peer::executor::log::ExecutorLogStream;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_replay_spooled_lines_after_a_restart() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let spool_file = temp.join("executor-logs-spool.jsonl");
+        let executor_id = ExecutorId::random();
+        let run_id = ExecutorRunId::random();
+
+        let mut spool_before_restart = Spool::load(Clone::clone(&spool_file), 10);
+        spool_before_restart.push(entry(executor_id, run_id, 0, "first"));
+        spool_before_restart.push(entry(executor_id, run_id, 1, "second"));
+        drop(spool_before_restart);
+
+        let shipper = ExecutorLogShipper::spawn_with(Clone::clone(&spool_file), 10);
+        let (tx_upstream, mut rx_upstream) = mpsc::channel(10);
+        shipper.connect(tx_upstream);
+
+        let message = tokio::time::timeout(Duration::from_secs(5), rx_upstream.recv()).await?.unwrap();
+        let Some(upstream::Message::ExecutorLogs(logs)) = message.message else {
+            panic!("Expected executor logs to be sent to CARL, but got: {message:?}");
+        };
+        assert_that!(logs.lines.iter().map(|line| line.text.as_str()).collect::<Vec<_>>(), elements_are![eq(&"first"), eq(&"second")]);
+
+        let mut attempts = 0;
+        while spool_file.exists() && attempts < 50 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            attempts += 1;
+        }
+        assert!(spool_file.exists().not(), "Spool file should be removed after its lines have been sent.");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_drop_lines_when_the_spool_is_full() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let spool_file = temp.join("executor-logs-spool.jsonl");
+        let executor_id = ExecutorId::random();
+        let run_id = ExecutorRunId::random();
+
+        let mut spool = Spool::load(Clone::clone(&spool_file), 2);
+        for sequence_number in 0..3 {
+            spool.push(entry(executor_id, run_id, sequence_number, "line"));
+        }
+
+        assert_that!(spool.lines, eq(2));
+        assert_that!(spool.dropped_lines, eq(1));
+        assert_that!(std::fs::read_to_string(&spool_file)?.lines().count(), eq(2));
+
+        spool.clear();
+        assert_that!(spool.dropped_lines, eq(0));
+        assert!(spool_file.exists().not());
+        Ok(())
+    }
+
+    fn entry(executor_id: ExecutorId, run_id: ExecutorRunId, sequence_number: u64, text: &str) -> ExecutorLogEntry {
+        ExecutorLogEntry {
+            executor_id,
+            run_id,
+            line: ExecutorLogLine {
+                sequence_number,
+                timestamp: SystemTime::now(),
+                stream: ExecutorLogStream::Stdout,
+                text: text.to_owned(),
+            },
+        }
+    }
+}
@@ -1,3 +1,6 @@
 pub mod container_manager;
+pub mod executable_manager;
+pub mod results;
 mod webdav_client;
-pub mod executor_manager;
\ No newline at end of file
+pub mod executor_manager;
+pub mod log_shipper;
\ No newline at end of file
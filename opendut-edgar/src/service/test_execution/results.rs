@@ -0,0 +1,455 @@
+use std::{env, io::{Cursor, ErrorKind, Write}, path::{Path, PathBuf}, time::Duration};
+
+use anyhow::{Context, Result};
+use reqwest::StatusCode;
+use tokio::{fs::{self, File}, io::AsyncReadExt};
+use tracing::{info, warn};
+use url::Url;
+use uuid::Uuid;
+use walkdir::WalkDir;
+use zip::{CompressionMethod, write::{FileOptionExtension, FileOptions, SimpleFileOptions}, ZipWriter};
+
+use opendut_auth::confidential::client::{ConfidentialClient, ConfidentialClientRef};
+use opendut_auth::confidential::config::{ConfidentialClientConfig, ConfidentialClientConfigData};
+use opendut_auth::confidential::reqwest_client::OidcReqwestClient;
+use opendut_types::peer::executor::ResultsUrl;
+
+use crate::service::test_execution::webdav_client::{self, WebdavClient};
+
+/// File, which an executor creates in its results directory, when the results are ready for upload.
+pub const RESULTS_READY_FILE: &str = ".results_ready";
+
+/// Upper bound for the delay between two upload attempts.
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Settings for uploading results, shared by all executors of this EDGAR.
+#[derive(Clone, Debug)]
+pub struct ResultsUploadOptions {
+    /// OIDC client for authenticating against the result storage. `None`, if OIDC is disabled.
+    pub confidential_client: Option<ConfidentialClientRef>,
+    /// URL of the result storage. The token of the OIDC client is only sent to its origin.
+    pub results_storage_url: Option<Url>,
+    pub retries: u32,
+    /// Delay before the first retry, which is doubled for each further retry.
+    pub retry_interval: Duration,
+}
+
+impl ResultsUploadOptions {
+    /// Creates a separate OIDC client for the result storage, so that its token can be requested with dedicated scopes.
+    /// If no scopes are configured in `results.upload.oidc.scopes`, the scopes of `network.oidc.client.scopes` are used.
+    /// The token is only sent to the result storage configured in `results.upload.oidc.url`.
+    pub async fn load(settings: &config::Config) -> Result<Self> {
+        let raw_results_storage_url = settings.get_string("results.upload.oidc.url")?;
+        let results_storage_url = match raw_results_storage_url.trim() {
+            "" => None,
+            url => Some(Url::parse(url).context(format!("Failed to parse URL of the result storage in 'results.upload.oidc.url': {url}"))?),
+        };
+
+        let confidential_client = match ConfidentialClientConfig::from_settings(settings)? {
+            ConfidentialClientConfig::Confidential(mut client_config) => {
+                let raw_scopes = settings.get_string("results.upload.oidc.scopes")?;
+                let scopes = ConfidentialClientConfigData::parse_scopes(client_config.client_id.as_str(), raw_scopes);
+                if ! scopes.is_empty() {
+                    client_config.scopes = scopes;
+                }
+                let reqwest_client = OidcReqwestClient::from_config(settings).await
+                    .context("Failed to create HTTP client for authenticating result uploads.")?;
+                Some(ConfidentialClient::from_client_config(client_config, reqwest_client).await?)
+            }
+            ConfidentialClientConfig::AuthenticationDisabled => None,
+        };
+
+        if confidential_client.is_some() && results_storage_url.is_none() {
+            warn!("No result storage configured in 'results.upload.oidc.url'. Results will be uploaded without token.");
+        }
+
+        Ok(Self {
+            confidential_client,
+            results_storage_url,
+            retries: settings.get::<u32>("results.upload.retries")?,
+            retry_interval: Duration::from_millis(settings.get::<u64>("results.upload.retry.interval.ms")?),
+        })
+    }
+}
+
+/// Temporary directory, into which an executor writes its results, and which is uploaded as ZIP file to the results URL.
+pub struct ResultsDirectory {
+    path: PathBuf,
+    results_url: Option<ResultsUrl>,
+    webdav_client: WebdavClient,
+    retries: u32,
+    retry_interval: Duration,
+}
+
+impl ResultsDirectory {
+    pub fn new(results_url: Option<ResultsUrl>, upload_options: ResultsUploadOptions) -> Self {
+        Self {
+            path: env::temp_dir().join(format!("opendut-edgar-results_{}", Uuid::new_v4())),
+            results_url,
+            webdav_client: WebdavClient::new(upload_options.confidential_client, upload_options.results_storage_url),
+            retries: upload_options.retries,
+            retry_interval: upload_options.retry_interval,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub async fn create(&self) -> Result<(), Error> {
+        fs::create_dir_all(&self.path)
+            .await
+            .map_err(|cause| Error::Other { message: format!("Failed to create results directory '{}': {}", self.path.to_string_lossy(), cause) })
+    }
+
+    pub async fn cleanup(&self) -> Result<(), Error> {
+        fs::remove_dir_all(&self.path)
+            .await
+            .map_err(|cause| Error::Other { message: format!("Failed to remove results directory '{}': {}", self.path.to_string_lossy(), cause) })
+    }
+
+    pub async fn are_ready(&self) -> bool {
+        self.path.join(RESULTS_READY_FILE).is_file()
+    }
+
+    pub async fn remove_ready_indicator(&self) -> Result<(), Error> {
+        let indicator_file = self.path.join(RESULTS_READY_FILE);
+        match fs::remove_file(&indicator_file).await {
+            Ok(_) => Ok(()),
+            Err(err) => match err.kind() {
+                ErrorKind::NotFound => Ok(()),
+                _ => Err(Error::Other { message: format!("Failed to remove result indicator file '{}': {}", indicator_file.to_string_lossy(), err) }),
+            },
+        }
+    }
+
+    /// Uploads the results and returns the URL of the uploaded file, if a results URL is configured.
+    /// The `executor_name` is used in the name of the uploaded file.
+    pub async fn upload(&self, executor_name: &str) -> Result<Option<Url>, Error> {
+        info!("Starting upload for results of {executor_name}");
+        let results_url = match &self.results_url {
+            Some(results_url) => results_url.value(),
+            None => {
+                info!("Executor {executor_name} has no results URL, won't upload results.");
+                return Ok(None);
+            },
+        };
+
+        let mut zipped_data = Vec::new();
+        // https://github.com/zip-rs/zip2/issues/195 large_file(true) produces invalid zip file with crate version 2.1.3
+        let zip_options = SimpleFileOptions::default().compression_method(CompressionMethod::BZIP2).large_file(false);
+        create_zip_from_directory(&mut zipped_data, &self.path, zip_options).await.map_err(|cause| Error::ResultZipping { path: self.path.clone(), cause })?;
+
+        let results_file_url = results_url.join(
+            format!("{}_{}.zip", chrono::offset::Local::now().format("%Y-%m-%d_%H-%M-%S"), executor_name).as_str()
+        ).map_err(|cause| Error::Other { message: format!("Failed to construct URL for results directory: {}", cause) })?;
+
+        let mut attempt = 0;
+        loop {
+            match self.try_upload(&zipped_data, results_url, &results_file_url, executor_name).await {
+                Ok(()) => {
+                    info!("Successfully uploaded results of {executor_name}");
+                    return Ok(Some(results_file_url));
+                }
+                Err(cause) if attempt < self.retries && cause.is_retryable() => {
+                    let delay = retry_delay(self.retry_interval, attempt);
+                    attempt += 1;
+                    warn!("Uploading results of {executor_name} failed. Retrying in {} ms ({attempt} of {} retries). Cause: {cause}", delay.as_millis(), self.retries);
+
+                    if cause.is_unauthorized() {
+                        if let Err(cause) = self.webdav_client.refresh_token().await {
+                            warn!("Failed to refresh token for uploading results: {cause}");
+                        }
+                    }
+                    tokio::time::sleep(delay).await;
+                }
+                Err(cause) => return Err(cause),
+            }
+        }
+    }
+
+    async fn try_upload(&self, zipped_data: &[u8], results_url: &Url, results_file_url: &Url, executor_name: &str) -> Result<(), Error> {
+        self.webdav_client.create_collection_path(results_url.clone())
+            .await
+            .map_err(|cause| Error::ResultUploadingInternal { url: results_url.clone(), cause })?;
+
+        let response = self.webdav_client.put(zipped_data.to_vec(), results_file_url.clone())
+            .await
+            .map_err(|cause| Error::ResultUploadingInternal { url: results_file_url.clone(), cause })?;
+
+        match response.status().is_success() {
+            true => Ok(()),
+            false => Err(Error::ResultUploadingServer { executor_name: executor_name.to_owned(), url: results_file_url.clone(), status: response.status() }),
+        }
+    }
+}
+
+/// Exponential backoff, starting with the configured interval.
+fn retry_delay(retry_interval: Duration, attempt: u32) -> Duration {
+    retry_interval
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(MAX_RETRY_INTERVAL)
+}
+
+async fn create_zip_from_directory<T>(data: &mut Vec<u8>, directory: &PathBuf, file_options: FileOptions<'_, T>) -> Result<()>
+    where
+        T: FileOptionExtension + std::marker::Copy,
+    {
+        let mut file_buffer = Vec::new();
+        let zip_buffer = Cursor::new(data);
+        let mut zip = ZipWriter::new(zip_buffer);
+
+        for entry_res in WalkDir::new(directory) {
+            let entry = entry_res?;
+            let entry_path = entry.path();
+            let entry_metadata = entry.metadata()?;
+
+            if entry_metadata.is_file() {
+                let mut f = File::open(&entry_path).await?;
+                f.read_to_end(&mut file_buffer).await?;
+                let relative_path = entry_path.strip_prefix(directory)?;
+                zip.start_file(relative_path.to_string_lossy(), file_options)?;
+                zip.write_all(file_buffer.as_ref())?;
+                file_buffer.clear();
+            } else if entry_metadata.is_dir() {
+                let relative_path = entry_path.strip_prefix(directory)?;
+                zip.add_directory(relative_path.to_string_lossy(), file_options)?;
+            }
+        }
+
+        Ok(())
+    }
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failure while creating a ZIP archive of the test results at '{path}' : {cause}")]
+    ResultZipping { path: PathBuf, cause: anyhow::Error },
+    #[error("Failure while uploading test results to '{url}': {cause}")]
+    ResultUploadingInternal { url: Url, cause: webdav_client::Error },
+    #[error("Failure while uploading test results for '{executor_name}' to '{url}' (HTTP status {status})")]
+    ResultUploadingServer { executor_name: String, url: Url, status: reqwest::StatusCode },
+    #[error("{message}")]
+    Other { message: String },
+}
+
+impl Error {
+    /// Whether the upload might succeed when attempted again, e.g. after a connection problem or an expired token.
+    fn is_retryable(&self) -> bool {
+        match self {
+            Error::ResultUploadingInternal { cause, .. } => matches!(cause, webdav_client::Error::Request { .. } | webdav_client::Error::Authentication { .. }),
+            Error::ResultUploadingServer { status, .. } => {
+                status.is_server_error()
+                    || matches!(*status, StatusCode::UNAUTHORIZED | StatusCode::REQUEST_TIMEOUT | StatusCode::TOO_MANY_REQUESTS)
+            }
+            Error::ResultZipping { .. } | Error::Other { .. } => false,
+        }
+    }
+
+    fn is_unauthorized(&self) -> bool {
+        matches!(self, Error::ResultUploadingServer { status: StatusCode::UNAUTHORIZED, .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use googletest::prelude::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    #[derive(Clone, Debug)]
+    struct RecordedRequest {
+        method: String,
+        path: String,
+        authorization: Option<String>,
+    }
+
+    /// Minimal HTTP server, which serves as OIDC token endpoint and as result storage.
+    /// The result storage accepts MKCOL requests and answers PUT requests with the given status codes in turn, followed by `201 Created`.
+    struct MockServer {
+        url: Url,
+        requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    }
+
+    impl MockServer {
+        async fn start(put_statuses: Vec<u16>) -> anyhow::Result<Self> {
+            let listener = TcpListener::bind("127.0.0.1:0").await?;
+            let url = Url::parse(&format!("http://{}/", listener.local_addr()?))?;
+            let requests = Arc::new(Mutex::new(Vec::new()));
+
+            let recorded_requests = Arc::clone(&requests);
+            tokio::spawn(async move {
+                let mut put_statuses = put_statuses.into_iter();
+                let mut issued_tokens = 0;
+
+                while let Ok((mut stream, _)) = listener.accept().await {
+                    let Ok(request) = read_request(&mut stream).await else { continue };
+                    recorded_requests.lock().unwrap().push(request.clone());
+
+                    let (status, content_type, body) = match request.method.as_str() {
+                        "POST" if request.path.ends_with("/protocol/openid-connect/token") => {
+                            issued_tokens += 1;
+                            (200, "application/json", format!(r#"{{"access_token":"token-{issued_tokens}","token_type":"bearer","expires_in":300}}"#))
+                        }
+                        "PUT" => (put_statuses.next().unwrap_or(201), "text/plain", String::new()),
+                        _ => (201, "text/plain", String::new()),
+                    };
+                    let response = format!("HTTP/1.1 {status} Mock\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len());
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    let _ = stream.shutdown().await;
+                }
+            });
+
+            Ok(Self { url, requests })
+        }
+
+        fn requests(&self, method: &str) -> Vec<RecordedRequest> {
+            self.requests.lock().unwrap().iter()
+                .filter(|request| request.method == method)
+                .cloned()
+                .collect()
+        }
+    }
+
+    async fn read_request(stream: &mut tokio::net::TcpStream) -> anyhow::Result<RecordedRequest> {
+        let mut reader = BufReader::new(stream);
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).await?;
+        let mut request_line = request_line.split_whitespace();
+        let method = request_line.next().unwrap_or_default().to_owned();
+        let path = request_line.next().unwrap_or_default().to_owned();
+
+        let mut authorization = None;
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).await?;
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                match name.to_lowercase().as_str() {
+                    "authorization" => authorization = Some(value.trim().to_owned()),
+                    "content-length" => content_length = value.trim().parse()?,
+                    _ => {}
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).await?;
+
+        Ok(RecordedRequest { method, path, authorization })
+    }
+
+    async fn upload_options(server: &MockServer, results_storage_url: &Url) -> anyhow::Result<ResultsUploadOptions> {
+        let settings = config::Config::builder()
+            .set_override("network.oidc.enabled", true)?
+            .set_override("network.oidc.client.id", "opendut-edgar-client")?
+            .set_override("network.oidc.client.secret", "secret")?
+            .set_override("network.oidc.client.issuer.url", server.url.join("realms/opendut/")?.to_string())?
+            .set_override("network.oidc.client.scopes", "")?
+            .set_override("network.tls.ca", "resources/development/tls/insecure-development-ca.pem")?
+            .set_override("results.upload.oidc.scopes", "")?
+            .set_override("results.upload.oidc.url", results_storage_url.to_string())?
+            .set_override("results.upload.retries", 2)?
+            .set_override("results.upload.retry.interval.ms", 0)?
+            .build()?;
+
+        ResultsUploadOptions::load(&settings).await
+    }
+
+    async fn upload(server: &MockServer, upload_options: ResultsUploadOptions) -> std::result::Result<Option<Url>, Error> {
+        let results_url = ResultsUrl::try_from(server.url.join("results/").unwrap().to_string()).unwrap();
+        let results_directory = ResultsDirectory::new(Some(results_url), upload_options);
+        results_directory.create().await?;
+
+        let result = results_directory.upload("executor").await;
+        results_directory.cleanup().await?;
+        result
+    }
+
+    fn authorizations(requests: Vec<RecordedRequest>) -> Vec<Option<String>> {
+        requests.into_iter().map(|request| request.authorization).collect()
+    }
+
+    #[tokio::test]
+    async fn should_retry_the_upload_after_a_server_error() -> anyhow::Result<()> {
+        let server = MockServer::start(vec![503]).await?;
+        let upload_options = upload_options(&server, &server.url).await?;
+
+        let result = upload(&server, upload_options).await;
+
+        assert_that!(result, ok(some(anything())));
+        assert_that!(server.requests("PUT").len(), eq(2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_refresh_the_token_and_retry_when_the_token_is_rejected() -> anyhow::Result<()> {
+        let server = MockServer::start(vec![401]).await?;
+        let upload_options = upload_options(&server, &server.url).await?;
+
+        let result = upload(&server, upload_options).await;
+
+        assert_that!(result, ok(some(anything())));
+        assert_that!(server.requests("POST").len(), eq(2));
+        assert_that!(authorizations(server.requests("PUT")), elements_are![
+            some(eq("Bearer token-1")),
+            some(eq("Bearer token-2")),
+        ]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_the_retries_are_exhausted() -> anyhow::Result<()> {
+        let server = MockServer::start(vec![401, 401, 401]).await?;
+        let upload_options = upload_options(&server, &server.url).await?;
+
+        let result = upload(&server, upload_options).await;
+
+        assert!(matches!(result, Err(Error::ResultUploadingServer { status: StatusCode::UNAUTHORIZED, .. })));
+        assert_that!(server.requests("PUT").len(), eq(3));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_retry_the_upload_when_it_is_forbidden() -> anyhow::Result<()> {
+        let server = MockServer::start(vec![403]).await?;
+        let upload_options = upload_options(&server, &server.url).await?;
+
+        let result = upload(&server, upload_options).await;
+
+        assert!(matches!(result, Err(Error::ResultUploadingServer { status: StatusCode::FORBIDDEN, .. })));
+        assert_that!(server.requests("PUT").len(), eq(1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_send_the_token_to_another_host_than_the_results_storage() -> anyhow::Result<()> {
+        let server = MockServer::start(vec![]).await?;
+        let upload_options = upload_options(&server, &Url::parse("https://results.example.com/")?).await?;
+
+        let result = upload(&server, upload_options).await;
+
+        assert_that!(result, ok(some(anything())));
+        assert_that!(server.requests("POST"), empty());
+        assert_that!(authorizations(server.requests("MKCOL")), each(none()));
+        assert_that!(authorizations(server.requests("PUT")), elements_are![none()]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_double_the_retry_delay_up_to_a_maximum() {
+        let interval = Duration::from_millis(500);
+
+        assert_that!(retry_delay(interval, 0), eq(Duration::from_millis(500)));
+        assert_that!(retry_delay(interval, 1), eq(Duration::from_millis(1000)));
+        assert_that!(retry_delay(interval, 3), eq(Duration::from_millis(4000)));
+        assert_that!(retry_delay(interval, 40), eq(MAX_RETRY_INTERVAL));
+    }
+}
@@ -1,24 +1,58 @@
 use reqwest::{Method, RequestBuilder, Url, Body, Response, header};
-use tracing::error;
+use tracing::{debug, error};
+use url::Origin;
+
+use opendut_auth::confidential::client::{AuthError, ConfidentialClientRef};
 
 pub struct WebdavClient {
-    bearer_token: String,
+    confidential_client: Option<ConfidentialClientRef>,
+    authenticated_origin: Option<Origin>,
     client: reqwest::Client,
 }
 
 impl WebdavClient {
 
-    pub fn new(bearer_token: String) -> Self {
-        Self { 
-            bearer_token,
+    /// Requests to the origin of the `authenticated_url` are authenticated with a bearer token from the given OIDC client.
+    /// Requests to other origins, or all requests without a client, e.g. when OIDC is disabled, are sent unauthenticated.
+    pub fn new(confidential_client: Option<ConfidentialClientRef>, authenticated_url: Option<Url>) -> Self {
+        Self {
+            confidential_client,
+            authenticated_origin: authenticated_url.map(|url| url.origin()),
             client: reqwest::Client::new()
         }
     }
 
-    fn start_request(&self, method: Method, path: Url) -> RequestBuilder {
-        self.client
-            .request(method, path)
-            .bearer_auth(self.bearer_token.clone())
+    fn is_authenticated(&self, url: &Url) -> bool {
+        self.authenticated_origin.as_ref()
+            .is_some_and(|origin| origin.is_tuple() && *origin == url.origin())
+    }
+
+    /// The token is requested anew for each request, so that it gets refreshed when it expires during a long run.
+    async fn start_request(&self, method: Method, path: Url) -> Result<RequestBuilder, Error> {
+        let authenticate = self.is_authenticated(&path);
+        let request = self.client.request(method, path.clone());
+
+        match &self.confidential_client {
+            Some(confidential_client) if authenticate => {
+                let token = confidential_client.get_token().await
+                    .map_err(|cause| Error::Authentication { cause })?;
+                Ok(request.bearer_auth(token.value))
+            }
+            Some(_) => {
+                debug!("Sending request to <{path}> without token, since it does not belong to the configured results storage.");
+                Ok(request)
+            }
+            None => Ok(request),
+        }
+    }
+
+    /// Fetches a new token, e.g. after the server rejected the previous one.
+    pub async fn refresh_token(&self) -> Result<(), Error> {
+        if let Some(confidential_client) = &self.confidential_client {
+            confidential_client.refresh_token().await
+                .map_err(|cause| Error::Authentication { cause })?;
+        }
+        Ok(())
     }
 
     fn custom_header(&self, name: &str, value: &str) -> header::HeaderMap {
@@ -34,7 +68,7 @@ impl WebdavClient {
     ///
     /// Use absolute path to the webdav server folder location
     pub async fn put<B: Into<Body>>(&self, body: B, path: Url) -> Result<Response, Error> {
-        self.start_request(Method::PUT, path)
+        self.start_request(Method::PUT, path).await?
             .headers(self.custom_header("content-type", "application/octet-stream"))
             .body(body)
             .send()
@@ -43,7 +77,7 @@ impl WebdavClient {
     }
 
     pub async fn mkcol(&self, path: Url) -> Result<Response, Error> {
-        self.start_request(Method::from_bytes(b"MKCOL").unwrap(), path)
+        self.start_request(Method::from_bytes(b"MKCOL").unwrap(), path).await?
             .send()
             .await
             .map_err(|cause| Error::Request { method: String::from("MKCOL"), cause } )
@@ -81,6 +115,35 @@ impl WebdavClient {
 pub enum Error {
     #[error("Failure while sending WebDAV '{method}' request: {cause}")]
     Request { method: String, cause: reqwest::Error },
+    #[error("Failure while authenticating WebDAV request: {cause}")]
+    Authentication { cause: AuthError },
     #[error("{message}")]
     Other { message: String },
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_only_authenticate_requests_to_the_origin_of_the_results_storage() -> anyhow::Result<()> {
+        let client = WebdavClient::new(None, Some(Url::parse("https://webdav.example.com/results/")?));
+
+        assert_that!(client.is_authenticated(&Url::parse("https://webdav.example.com/results/run.zip")?), eq(true));
+        assert_that!(client.is_authenticated(&Url::parse("https://webdav.example.com:443/other/")?), eq(true));
+        assert_that!(client.is_authenticated(&Url::parse("http://webdav.example.com/results/")?), eq(false));
+        assert_that!(client.is_authenticated(&Url::parse("https://webdav.example.com:8443/results/")?), eq(false));
+        assert_that!(client.is_authenticated(&Url::parse("https://attacker.example.com/results/")?), eq(false));
+        Ok(())
+    }
+
+    #[test]
+    fn should_not_authenticate_requests_without_a_results_storage() -> anyhow::Result<()> {
+        let client = WebdavClient::new(None, None);
+
+        assert_that!(client.is_authenticated(&Url::parse("https://webdav.example.com/results/")?), eq(false));
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::ops::Not;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use tokio::net::UdpSocket;
+use tracing::debug;
+
+use opendut_types::util::net::IpNetwork;
+use opendut_util::settings::LoadedConfig;
+
+use crate::common::settings;
+
+/// Determines the address, under which the other peers reach this peer on the shared network.
+///
+/// In order of precedence, this is the address configured via `vpn.direct.address`,
+/// the first address of a local interface within the network assigned by CARL,
+/// or the local address used for reaching CARL.
+pub async fn determine_address(settings: &LoadedConfig) -> anyhow::Result<IpAddr> {
+    let network = {
+        let network = settings.config.get_string(settings::key::vpn::direct::network)?;
+        if network.trim().is_empty() {
+            None
+        } else {
+            let network = IpNetwork::from_str(&network)
+                .map_err(|cause| anyhow!("Configuration value '{field}' must be a network in CIDR notation: {cause}", field=settings::key::vpn::direct::network))?;
+            Some(network)
+        }
+    };
+
+    let configured_address = settings.config.get_string(settings::key::vpn::direct::address)?;
+
+    let address = if configured_address.trim().is_empty().not() {
+        IpAddr::from_str(configured_address.trim())
+            .map_err(|cause| anyhow!("Configuration value '{field}' must be a valid IP address: {cause}", field=settings::key::vpn::direct::address))?
+    } else if let Some(network) = network {
+        debug!("Determining address of a local interface in network {network}.");
+        local_addresses()?
+            .into_iter()
+            .find(|address| network.contains(address))
+            .ok_or(anyhow!("No local interface has an address in network {network}. Configure the address via '{field}' or check the network configured in CARL.", field=settings::key::vpn::direct::address))?
+    } else {
+        debug!("Determining local address used for reaching CARL.");
+        address_towards_carl(settings).await?
+    };
+
+    if let Some(network) = network {
+        if network.contains(&address).not() {
+            return Err(anyhow!("Address {address} is not within network {network}, which is configured for the direct network in CARL."));
+        }
+    }
+
+    Ok(address)
+}
+
+fn local_addresses() -> anyhow::Result<Vec<IpAddr>> {
+    let addresses = nix::ifaddrs::getifaddrs()
+        .context("Failed to list addresses of local interfaces.")?
+        .filter_map(|interface| interface.address)
+        .filter_map(|address| {
+            if let Some(address) = address.as_sockaddr_in() {
+                Some(IpAddr::V4(address.ip()))
+            } else {
+                address.as_sockaddr_in6()
+                    .map(|address| IpAddr::V6(address.ip()))
+            }
+        })
+        .collect();
+    Ok(addresses)
+}
+
+/// Determines the local address, which the operating system chooses for the route to CARL.
+/// Connecting a UDP socket sends no packets, it only selects the route.
+async fn address_towards_carl(settings: &LoadedConfig) -> anyhow::Result<IpAddr> {
+    let host = settings.config.get_string("network.carl.host")?;
+    let port = u16::try_from(settings.config.get_int("network.carl.port")?)?;
+
+    let carl_address = tokio::net::lookup_host((host.as_str(), port)).await
+        .context(format!("Failed to resolve address of CARL at '{host}'."))?
+        .next()
+        .ok_or(anyhow!("No address found for CARL at '{host}'."))?;
+
+    let unspecified = match carl_address {
+        SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    };
+    let socket = UdpSocket::bind(SocketAddr::new(unspecified, 0)).await?;
+    socket.connect(carl_address).await
+        .context(format!("No route to CARL at {carl_address}."))?;
+
+    Ok(socket.local_addr()?.ip())
+}
+
+#[cfg(test)]
+mod tests {
+    use config::Config;
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn settings(network: &str, address: &str) -> anyhow::Result<LoadedConfig> {
+        let config = Config::builder()
+            .set_override(settings::key::vpn::direct::network, network)?
+            .set_override(settings::key::vpn::direct::address, address)?
+            .set_override("network.carl.host", "127.0.0.1")?
+            .set_override("network.carl.port", 443)?
+            .build()?;
+
+        Ok(LoadedConfig {
+            config: Clone::clone(&config),
+            redacted_config: config,
+            config_files_used: vec![],
+            config_files_declared: vec![],
+        })
+    }
+
+    #[tokio::test]
+    async fn should_use_the_configured_address() -> anyhow::Result<()> {
+        let address = determine_address(&settings("192.168.1.0/24", "192.168.1.23")?).await?;
+
+        assert_that!(address, eq(IpAddr::from([192, 168, 1, 23])));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_a_configured_address_outside_of_the_network() -> anyhow::Result<()> {
+        let result = determine_address(&settings("192.168.1.0/24", "10.0.0.23")?).await;
+
+        assert_that!(result, err(anything()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_an_invalid_configured_address_or_network() -> anyhow::Result<()> {
+        assert_that!(determine_address(&settings("", "not-an-address")?).await, err(anything()));
+        assert_that!(determine_address(&settings("192.168.1.0", "")?).await, err(anything()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_determine_the_address_of_a_local_interface_in_the_network() -> anyhow::Result<()> {
+        let address = determine_address(&settings("127.0.0.0/8", "")?).await?;
+
+        assert!(address.is_loopback());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_if_no_local_interface_is_in_the_network() -> anyhow::Result<()> {
+        let result = determine_address(&settings("198.51.100.0/24", "")?).await; //documentation range, not assigned to any interface
+
+        assert_that!(result, err(anything()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_determine_the_address_used_for_reaching_carl_without_a_network() -> anyhow::Result<()> {
+        let address = determine_address(&settings("", "")?).await?;
+
+        assert_that!(address, eq(IpAddr::from([127, 0, 0, 1])));
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use std::net::IpAddr;
+use std::sync::Arc;
 
 use anyhow::anyhow;
 use opendut_netbird_client_api::extension::LocalPeerStateExtension;
@@ -7,11 +8,39 @@ use serde::Deserialize;
 use tracing::debug;
 
 use crate::common::settings;
+use crate::service::peer_configuration::NetworkInterfaceManagement;
+
+mod direct;
+pub mod wireguard;
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all="kebab-case")]
 pub struct VpnConfig {
     pub enabled: bool,
+    pub kind: VpnKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all="lowercase")]
+pub enum VpnKind {
+    Netbird,
+    Wireguard,
+    Direct,
+}
+
+/// Prepares the VPN, before connecting to CARL.
+/// For NetBird, this is done by the NetBird Client, so only WireGuard requires any work.
+pub async fn setup(settings: &LoadedConfig, network_interface_management: &NetworkInterfaceManagement) -> anyhow::Result<()> {
+    let vpn_config = settings.config.get::<VpnConfig>(settings::key::vpn::table)?;
+
+    if vpn_config.enabled && vpn_config.kind == VpnKind::Wireguard {
+        let NetworkInterfaceManagement::Enabled { network_interface_manager, .. } = network_interface_management else {
+            return Err(anyhow!("The WireGuard VPN requires network interface management to be enabled via 'network.interface.management.enabled'."));
+        };
+        let wireguard_config = wireguard::WireGuardConfig::load(settings)?;
+        wireguard::setup_interface(&wireguard_config, Arc::clone(network_interface_manager)).await?;
+    }
+    Ok(())
 }
 
 
@@ -19,16 +48,22 @@ pub async fn retrieve_remote_host(settings: &LoadedConfig) -> anyhow::Result<IpA
     let vpn_config = settings.config.get::<VpnConfig>(settings::key::vpn::table)?;
 
     let address = if vpn_config.enabled {
-        debug!("Determining remote IP address of host in VPN network.");
-        let mut client = opendut_netbird_client_api::client::Client::connect().await?;
+        match vpn_config.kind {
+            VpnKind::Netbird => {
+                debug!("Determining remote IP address of host in VPN network.");
+                let mut client = opendut_netbird_client_api::client::Client::connect().await?;
 
-        let status = client.full_status().await?;
+                let status = client.full_status().await?;
 
-        let host = status.local_peer_state
-            .ok_or(anyhow!("NetBird Client did not return a local peer state. May not be logged in. Re-run `edgar setup` to fix this."))?
-            .local_ip()?;
+                let host = status.local_peer_state
+                    .ok_or(anyhow!("NetBird Client did not return a local peer state. May not be logged in. Re-run `edgar setup` to fix this."))?
+                    .local_ip()?;
 
-        IpAddr::from(host)
+                IpAddr::from(host)
+            }
+            VpnKind::Wireguard => wireguard::WireGuardConfig::load(settings)?.address,
+            VpnKind::Direct => direct::determine_address(settings).await?,
+        }
     } else {
         settings.config.get::<IpAddr>(settings::key::vpn::disabled::remote::host)
             .map_err(|cause| anyhow!("Configuration value '{field}' must be a valid IP address: {cause}", field=settings::key::vpn::disabled::remote::host))?
@@ -0,0 +1,155 @@
+use std::net::IpAddr;
+
+use anyhow::anyhow;
+use futures::StreamExt;
+use netlink_packet_core::{NetlinkMessage, NetlinkPayload, NLM_F_ACK, NLM_F_REQUEST};
+use netlink_packet_generic::GenlMessage;
+use netlink_packet_wireguard::{Wireguard, WireguardCmd};
+use netlink_packet_wireguard::constants::{AF_INET, AF_INET6, WGPEER_F_REMOVE_ME, WGPEER_F_REPLACE_ALLOWEDIPS};
+use netlink_packet_wireguard::nlas::{WgAllowedIp, WgAllowedIpAttrs, WgDeviceAttrs, WgPeer, WgPeerAttrs};
+use tracing::debug;
+
+use opendut_types::util::net::NetworkInterfaceName;
+use opendut_types::vpn::wireguard::{WireGuardPeer, WireGuardPrivateKey, WireGuardPublicKey};
+use opendut_util::settings::LoadedConfig;
+
+use crate::common::settings;
+use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
+
+/// Name of the WireGuard interface, which EDGAR creates when the VPN kind is "wireguard".
+pub const INTERFACE_NAME: &str = "wg-opendut";
+
+/// Interval in seconds, in which packets are sent to keep NAT mappings alive,
+/// so that peers behind a NAT remain reachable for the other peers.
+const PERSISTENT_KEEPALIVE_INTERVAL: u16 = 25;
+
+pub struct WireGuardConfig {
+    pub private_key: WireGuardPrivateKey,
+    pub address: IpAddr,
+    pub prefix_length: u8,
+    pub listen_port: u16,
+}
+impl WireGuardConfig {
+    pub fn load(settings: &LoadedConfig) -> anyhow::Result<Self> {
+        let private_key = settings.config.get_string(settings::key::vpn::wireguard::private::key)?;
+        let private_key = WireGuardPrivateKey::try_from(private_key)
+            .map_err(|cause| anyhow!("Configuration value '{field}' must be a valid WireGuard key: {cause}. Re-run `edgar setup` to fix this.", field=settings::key::vpn::wireguard::private::key))?;
+
+        let address = settings.config.get::<IpAddr>(settings::key::vpn::wireguard::address)
+            .map_err(|cause| anyhow!("Configuration value '{field}' must be a valid IP address: {cause}. Re-run `edgar setup` to fix this.", field=settings::key::vpn::wireguard::address))?;
+
+        Ok(Self {
+            private_key,
+            address,
+            prefix_length: settings.config.get::<u8>(settings::key::vpn::wireguard::prefix::length)?,
+            listen_port: settings.config.get::<u16>(settings::key::vpn::wireguard::listen::port)?,
+        })
+    }
+}
+
+pub fn interface_name() -> NetworkInterfaceName {
+    NetworkInterfaceName::try_from(INTERFACE_NAME)
+        .expect("WireGuard interface name should be valid") //if not, this is a bug in the constant
+}
+
+/// (Re-)creates the WireGuard interface with the key and address assigned by CARL.
+/// Peers are configured separately, when CARL sends the peer configuration.
+pub async fn setup_interface(config: &WireGuardConfig, network_interface_manager: NetworkInterfaceManagerRef) -> anyhow::Result<()> {
+    let interface_name = interface_name();
+
+    if let Some(existing_interface) = network_interface_manager.find_interface(&interface_name).await? {
+        debug!("Deleting existing WireGuard interface '{interface_name}' before recreating it, to clear any previous peers.");
+        network_interface_manager.delete_interface(&existing_interface).await?;
+    }
+
+    let interface = network_interface_manager.create_wireguard_interface(&interface_name).await?;
+    network_interface_manager.add_address(&interface, config.address, config.prefix_length).await?;
+
+    set_device(vec![
+        WgDeviceAttrs::IfName(interface_name.name()),
+        WgDeviceAttrs::PrivateKey(*config.private_key.as_bytes()),
+        WgDeviceAttrs::ListenPort(config.listen_port),
+    ]).await?;
+
+    network_interface_manager.set_interface_up(&interface).await?;
+
+    debug!("Configured WireGuard interface '{interface_name}' with address {}/{}.", config.address, config.prefix_length);
+    Ok(())
+}
+
+/// Adds the given peer to the WireGuard interface or updates it, if it already exists.
+pub async fn set_peer(peer: &WireGuardPeer) -> Result<(), Error> {
+    let WireGuardPeer { public_key, allowed_ip, endpoint } = peer;
+
+    let (family, cidr) = match allowed_ip {
+        IpAddr::V4(_) => (AF_INET, 32),
+        IpAddr::V6(_) => (AF_INET6, 128),
+    };
+
+    let mut attributes = vec![
+        WgPeerAttrs::PublicKey(*public_key.as_bytes()),
+        WgPeerAttrs::Flags(WGPEER_F_REPLACE_ALLOWEDIPS),
+        WgPeerAttrs::PersistentKeepalive(PERSISTENT_KEEPALIVE_INTERVAL),
+        WgPeerAttrs::AllowedIps(vec![
+            WgAllowedIp(vec![
+                WgAllowedIpAttrs::Family(family),
+                WgAllowedIpAttrs::IpAddr(*allowed_ip),
+                WgAllowedIpAttrs::Cidr(cidr),
+            ]),
+        ]),
+    ];
+    if let Some(endpoint) = endpoint {
+        attributes.push(WgPeerAttrs::Endpoint(*endpoint));
+    }
+
+    set_device(vec![
+        WgDeviceAttrs::IfName(String::from(INTERFACE_NAME)),
+        WgDeviceAttrs::Peers(vec![WgPeer(attributes)]),
+    ]).await
+}
+
+/// Removes the peer with the given public key from the WireGuard interface.
+pub async fn remove_peer(public_key: &WireGuardPublicKey) -> Result<(), Error> {
+    set_device(vec![
+        WgDeviceAttrs::IfName(String::from(INTERFACE_NAME)),
+        WgDeviceAttrs::Peers(vec![WgPeer(vec![
+            WgPeerAttrs::PublicKey(*public_key.as_bytes()),
+            WgPeerAttrs::Flags(WGPEER_F_REMOVE_ME),
+        ])]),
+    ]).await
+}
+
+async fn set_device(attributes: Vec<WgDeviceAttrs>) -> Result<(), Error> {
+    let (connection, mut handle, _) = genetlink::new_connection()
+        .map_err(|cause| Error::Connecting { cause })?;
+    tokio::spawn(connection);
+
+    let mut message = NetlinkMessage::from(GenlMessage::from_payload(Wireguard {
+        cmd: WireguardCmd::SetDevice,
+        nlas: attributes,
+    }));
+    message.header.flags = NLM_F_REQUEST | NLM_F_ACK;
+
+    let mut responses = handle.request(message).await
+        .map_err(|cause| Error::SetDevice { cause: cause.to_string() })?;
+
+    while let Some(response) = responses.next().await {
+        let response = response
+            .map_err(|cause| Error::SetDevice { cause: cause.to_string() })?;
+
+        if let NetlinkPayload::Error(error) = response.payload {
+            if error.code.is_some() { //an error message without code is an acknowledgement
+                return Err(Error::SetDevice { cause: error.to_string() });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to establish connection to generic netlink: {cause}")]
+    Connecting { cause: std::io::Error },
+    #[error("Failure while configuring WireGuard interface '{INTERFACE_NAME}': {cause}")]
+    SetDevice { cause: String },
+}
@@ -67,16 +67,4 @@ pub mod netbird {
     }
 }
 
-pub mod rperf {
-    use std::path::PathBuf;
-    use anyhow::anyhow;
-    use opendut_util::project;
-
-    pub fn path_in_edgar_distribution() -> anyhow::Result<PathBuf> {
-        let path = PathBuf::from("install/rperf");
-        project::make_path_absolute(&path)
-            .map_err(|cause| anyhow!("Failed to determine absolute path of rperf in the unpacked EDGAR distribution, which is supposed to be at '{path:?}': {cause}"))
-    }
-}
-
 pub const REQUIRED_COMMAND_LINE_PROGRAMS: [(&str, &str); 3] = [("systemctl", "--version"), ("cannelloni", "-h"), ("cangw", "-s")];
@@ -7,7 +7,7 @@ mod constants;
 pub mod start;
 
 #[cfg(not(target_arch = "arm"))]
-mod plugin;
+pub(crate) mod plugin;
 
 #[allow(non_camel_case_types)]
 mod tasks;
@@ -1,22 +1,30 @@
 use std::path::{Path, PathBuf};
 use tracing::{debug, error, warn};
 use std::ffi::OsStr;
+use opendut_types::plugin::PluginKind;
+use crate::common::settings;
 use crate::common::task::Task;
 use crate::fs;
-use crate::setup::plugin::plugin_runtime::PluginRuntime;
+use crate::setup::plugin::plugin_runtime::{PluginContext, PluginRuntime};
+use crate::setup::plugin::registry;
 
-pub fn create_plugin_runtime(tasks: &mut Vec<Box<dyn Task>>) -> PluginRuntime {
+/// Creates the tasks of the plugins from the EDGAR distribution, followed by the plugins fetched from the plugin registry of CARL.
+/// The latter are only instantiated, if their signature, checksum and manifest could be verified.
+pub fn create_plugin_runtime(tasks: &mut Vec<Box<dyn Task>>, context: PluginContext) -> anyhow::Result<PluginRuntime> {
 
-    let plugin_runtime = PluginRuntime::new();
-    let plugin_paths = discover_plugins().unwrap();
+    let plugin_runtime = PluginRuntime::new(context);
+    let mut plugin_paths = discover_plugins()?;
+
+    let settings = settings::load_with_overrides(config::Config::default())?;
+    plugin_paths.append(&mut registry::discover_verified_plugins(PluginKind::Setup, &settings.config)?);
 
     let mut plugins: Vec<Box<dyn Task>> = plugin_paths.iter()
-        .map(|path| Box::new(plugin_runtime.create_plugin_from_wasm(path)) as Box<dyn Task>)
-        .collect();
+        .map(|path| plugin_runtime.create_plugin_from_wasm(path).map(|plugin| Box::new(plugin) as Box<dyn Task>))
+        .collect::<anyhow::Result<_>>()?;
 
     tasks.append(&mut plugins);
 
-    plugin_runtime
+    Ok(plugin_runtime)
 }
 
 fn discover_plugins() -> anyhow::Result<Vec<PathBuf>> {
@@ -25,7 +33,7 @@ fn discover_plugins() -> anyhow::Result<Vec<PathBuf>> {
     discover_plugins_in_path(&path)
 }
 
-fn discover_plugins_in_path(plugin_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+pub fn discover_plugins_in_path(plugin_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
     if !plugin_dir.exists() {
         warn!("File or folder '{}' does not exist.", plugin_dir.display());
         return anyhow::Ok(vec![]);
@@ -58,7 +66,7 @@ fn read_plugin_order(plugin_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
     let config = fs::read_to_string(&config_path)?;
     let potential_plugin_paths = config.lines()
         .map(|line| line.trim())
-        .filter(|line| line.is_empty())
+        .filter(|line| !line.is_empty())
         .map(PathBuf::from);
 
     let mut paths: Vec<PathBuf> = vec![];
@@ -82,3 +90,28 @@ fn read_plugin_order(plugin_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
 
     anyhow::Ok(paths)
 }
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_discover_the_plugins_listed_in_the_plugin_order() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        temp.child("plugin_a.wasm").touch()?;
+        temp.child("plugin_b.wasm").touch()?;
+        temp.child("plugins.txt").write_str("plugin_b.wasm\n\n  plugin_a.wasm  \n")?;
+
+        let result = discover_plugins_in_path(temp.path())?;
+
+        assert_that!(result, elements_are![
+            eq(&temp.child("plugin_b.wasm").to_path_buf()),
+            eq(&temp.child("plugin_a.wasm").to_path_buf()),
+        ]);
+        Ok(())
+    }
+}
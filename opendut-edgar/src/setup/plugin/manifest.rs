@@ -0,0 +1,157 @@
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Context;
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use crate::fs;
+
+/// Declares which capabilities of the host a plugin may use.
+/// Placed next to the plugin with the same file name, but the extension `.toml`, e.g. `my_plugin.toml` for `my_plugin.wasm`.
+/// Plugins without a manifest may only log.
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct PluginManifest {
+    #[serde(default)]
+    pub capabilities: Capabilities,
+}
+
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Capabilities {
+    /// Names of the commands, which may be executed via `call-command`.
+    #[serde(default)]
+    pub commands: Vec<String>,
+    #[serde(default)]
+    pub filesystem: FilesystemCapabilities,
+    /// Whether the plugin may open network sockets.
+    #[serde(default)]
+    pub network: bool,
+    /// Whether the plugin may query the network interfaces of the host.
+    #[serde(default)]
+    pub network_interfaces: bool,
+    #[serde(default)]
+    pub peer_setup: bool,
+    #[serde(default)]
+    pub edgar_config: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct FilesystemCapabilities {
+    /// Absolute paths of files or directories, which may be read.
+    #[serde(default)]
+    pub read: Vec<PathBuf>,
+    /// Absolute paths of files or directories, which may be read and written.
+    #[serde(default)]
+    pub write: Vec<PathBuf>,
+}
+
+impl PluginManifest {
+    pub fn manifest_path(plugin_path: &Path) -> PathBuf {
+        plugin_path.with_extension("toml")
+    }
+
+    pub fn load_for_plugin(plugin_path: &Path) -> anyhow::Result<Self> {
+        let manifest_path = Self::manifest_path(plugin_path);
+
+        if !manifest_path.exists() {
+            warn!("No manifest found for plugin at '{}'. The plugin is not granted any capabilities.", plugin_path.display());
+            return Ok(Self::default());
+        }
+
+        let manifest = fs::read_to_string(&manifest_path)?;
+        let manifest = Self::parse(&manifest)
+            .context(format!("Failed to parse plugin manifest at '{}'.", manifest_path.display()))?;
+        debug!("Loaded manifest for plugin at '{}': {manifest:?}", plugin_path.display());
+
+        Ok(manifest)
+    }
+
+    fn parse(manifest: &str) -> anyhow::Result<Self> {
+        let manifest: Self = toml_edit::de::from_str(manifest)?;
+
+        let filesystem = &manifest.capabilities.filesystem;
+        for path in filesystem.read.iter().chain(&filesystem.write) {
+            if !path.is_absolute() || path.components().any(|component| component == Component::ParentDir) {
+                anyhow::bail!("Filesystem paths must be absolute and must not contain '..', but got: {}", path.display());
+            }
+        }
+        Ok(manifest)
+    }
+}
+
+impl Capabilities {
+    pub fn may_call_command(&self, command: &str) -> bool {
+        self.commands.iter().any(|allowed| allowed == command)
+    }
+
+    pub fn may_read(&self, path: &Path) -> bool {
+        let filesystem = &self.filesystem;
+        filesystem.read.iter().chain(&filesystem.write)
+            .any(|allowed| is_within(path, allowed))
+    }
+
+    pub fn may_write(&self, path: &Path) -> bool {
+        self.filesystem.write.iter()
+            .any(|allowed| is_within(path, allowed))
+    }
+}
+
+fn is_within(path: &Path, allowed: &Path) -> bool {
+    path.is_absolute()
+        && path.components().all(|component| component != Component::ParentDir)
+        && path.starts_with(allowed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_a_manifest() -> anyhow::Result<()> {
+        let manifest = PluginManifest::parse(r#"
+            [capabilities]
+            commands = ["ip"]
+            network-interfaces = true
+
+            [capabilities.filesystem]
+            read = ["/etc/opendut"]
+            write = ["/opt/vendor"]
+        "#)?;
+
+        let capabilities = &manifest.capabilities;
+        assert!(capabilities.may_call_command("ip"));
+        assert!(!capabilities.may_call_command("rm"));
+        assert!(capabilities.network_interfaces);
+        assert!(!capabilities.network);
+        assert!(!capabilities.peer_setup);
+
+        assert!(capabilities.may_read(Path::new("/etc/opendut/edgar.toml")));
+        assert!(!capabilities.may_write(Path::new("/etc/opendut/edgar.toml")));
+        assert!(capabilities.may_read(Path::new("/opt/vendor/bin")));
+        assert!(capabilities.may_write(Path::new("/opt/vendor/bin")));
+        assert!(!capabilities.may_read(Path::new("/opt/vendor/../../etc/shadow")));
+        assert!(!capabilities.may_read(Path::new("/etc/opendut-other")));
+        Ok(())
+    }
+
+    #[test]
+    fn should_grant_no_capabilities_by_default() -> anyhow::Result<()> {
+        let manifest = PluginManifest::parse("")?;
+
+        assert_eq!(manifest, PluginManifest::default());
+        assert!(!manifest.capabilities.may_read(Path::new("/")));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_relative_paths() {
+        let result = PluginManifest::parse(r#"
+            [capabilities.filesystem]
+            read = ["etc/opendut"]
+        "#);
+
+        assert!(result.is_err());
+    }
+}
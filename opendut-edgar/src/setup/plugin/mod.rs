@@ -1,5 +1,7 @@
 pub mod init;
+pub mod manifest;
 pub mod plugin_runtime;
+pub mod registry;
 mod setup_plugin;
 
 mod constants {
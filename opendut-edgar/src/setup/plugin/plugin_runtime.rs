@@ -1,25 +1,53 @@
+use crate::common::settings;
+use crate::fs;
+use crate::setup::plugin::manifest::{Capabilities, PluginManifest};
 use crate::setup::plugin::setup_plugin::SetupPluginStore;
-use opendut_edgar_plugin_api::host::{Host, LogLevel, SetupPlugin, Success, TaskFulfilled};
+use anyhow::Context;
+use opendut_edgar_plugin_api::host::{API_VERSION, FilesystemHost, Host, HostError, LogLevel, NetworkHost, NetworkInterface, PeerHost, PeerSetup, SetupPlugin, Success, TaskFulfilled, TypesHost, VpnKind};
+use opendut_types::vpn::VpnPeerConfiguration;
+use std::io;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use wasmtime::component::__internal;
 use wasmtime::component::{Component, Linker, ResourceTable};
 use wasmtime::{Config, Engine, Store};
 use wasmtime_wasi::{DirPerms, FilePerms, WasiCtx, WasiCtxBuilder, WasiView};
-use tracing::{event, trace, Level};
+use tracing::{event, trace, warn, Level};
 
 pub struct PluginRuntime {
     engine: Engine,
     linker: Linker<PluginState>,
+    context: PluginContext,
 }
 
-impl PluginRuntime {
-    pub fn new() -> Self {
-        let mut config = Config::new();
-        config.wasm_component_model(true);
-        config.debug_info(true);
+/// Information about the setup, which plugins may query, if their manifest grants the respective capability.
+#[derive(Clone, Default)]
+pub struct PluginContext {
+    pub peer_setup: Option<PeerSetup>,
+}
 
-        let engine = Engine::new(&config).unwrap();
+impl PluginContext {
+    pub fn for_peer_setup(peer_setup: &opendut_types::peer::PeerSetup) -> Self {
+        let vpn = match peer_setup.vpn {
+            VpnPeerConfiguration::Disabled => VpnKind::Disabled,
+            VpnPeerConfiguration::Netbird { .. } => VpnKind::Netbird,
+            VpnPeerConfiguration::WireGuard { .. } => VpnKind::Wireguard,
+            VpnPeerConfiguration::Direct { .. } => VpnKind::Direct,
+        };
+
+        Self {
+            peer_setup: Some(PeerSetup {
+                id: peer_setup.id.to_string(),
+                carl_url: peer_setup.carl.to_string(),
+                vpn,
+            }),
+        }
+    }
+}
+
+impl PluginRuntime {
+    pub fn new(context: PluginContext) -> Self {
+        let engine = create_engine();
 
         let mut linker = Linker::new(&engine);
 
@@ -29,42 +57,173 @@ impl PluginRuntime {
         SetupPlugin::add_to_linker(&mut linker, |state: &mut PluginState| state)
             .expect("Could not add PluginState to linker");
 
-        Self { engine, linker }
+        Self { engine, linker, context }
     }
 
-    pub fn create_plugin_from_wasm(&self, plugin_path: &Path) -> SetupPluginStore {
-        let mut store = Store::new(&self.engine, PluginState::new(plugin_path));
+    pub fn create_plugin_from_wasm(&self, plugin_path: &Path) -> anyhow::Result<SetupPluginStore> {
+        let mut store = create_store(&self.engine, plugin_path, self.context.clone())?;
 
-        let component = Component::from_file(&self.engine, plugin_path).unwrap();
+        let component = Component::from_file(&self.engine, plugin_path)
+            .context(format!("Could not load plugin at '{}'.", plugin_path.display()))?;
+        check_api_version(&self.engine, &component)
+            .context(format!("Could not load plugin at '{}'.", plugin_path.display()))?;
 
         let instance = SetupPlugin::instantiate(&mut store, &component, &self.linker)
-            .expect("Could not instantiate plugin");
+            .context(format!("Could not instantiate plugin at '{}'.", plugin_path.display()))?;
 
-        SetupPluginStore::new(store, instance)
+        Ok(SetupPluginStore::new(store, instance))
     }
 }
 
+/// Checks that the plugin was built against the version of the WIT package `edgar:setup`, which this EDGAR provides.
+/// Otherwise, instantiating the plugin would fail with an error about missing imports.
+pub fn check_api_version(engine: &Engine, component: &Component) -> anyhow::Result<()> {
+    let component_type = component.component_type();
+    let imports = component_type.imports(engine).map(|(name, _)| name);
+    let exports = component_type.exports(engine).map(|(name, _)| name);
+
+    for name in imports.chain(exports) {
+        let Some(interface) = name.strip_prefix(API_PACKAGE_PREFIX) else {
+            continue;
+        };
+        match interface.split_once('@') {
+            Some((_, version)) if is_compatible_api_version(version, API_VERSION) => {}
+            Some((_, version)) => anyhow::bail!(
+                "The plugin was built against the WIT package `edgar:setup@{version}`, which is not compatible with `edgar:setup@{API_VERSION}` provided by this EDGAR. Rebuild the plugin against `edgar:setup@{API_VERSION}`."
+            ),
+            None => anyhow::bail!(
+                "The plugin was built against an unversioned WIT package `edgar:setup`, but this EDGAR provides `edgar:setup@{API_VERSION}`. Rebuild the plugin against `edgar:setup@{API_VERSION}`."
+            ),
+        }
+    }
+    Ok(())
+}
+
+const API_PACKAGE_PREFIX: &str = "edgar:setup/";
+
+/// Plugins built against an older minor or patch version of the API are compatible, as these versions only extend the API.
+/// Below version 1.0.0, the minor version is treated like the major version.
+fn is_compatible_api_version(plugin_version: &str, host_version: &str) -> bool {
+    let (Some(plugin_version), Some(host_version)) = (parse_api_version(plugin_version), parse_api_version(host_version)) else {
+        return false;
+    };
+    let (plugin_major, plugin_minor, _) = plugin_version;
+    let (host_major, host_minor, _) = host_version;
+
+    let same_major = if host_major == 0 {
+        plugin_major == 0 && plugin_minor == host_minor
+    } else {
+        plugin_major == host_major
+    };
+    same_major && plugin_version <= host_version
+}
+
+fn parse_api_version(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.splitn(3, '.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = parts.next()?.parse().ok()?;
+    Some((major, minor, patch))
+}
+
+pub fn create_engine() -> Engine {
+    Engine::new(&engine_config()).unwrap()
+}
+
+/// Like [`create_engine`], but calls into plugins are interrupted, when the engine's epoch passes the deadline set on their store.
+pub fn create_interruptible_engine() -> Engine {
+    let mut config = engine_config();
+    config.epoch_interruption(true);
+
+    Engine::new(&config).unwrap()
+}
+
+fn engine_config() -> Config {
+    let mut config = Config::new();
+    config.wasm_component_model(true);
+    config.debug_info(true);
+    config
+}
+
+/// Creates the store for a plugin, sandboxed according to the manifest next to the plugin.
+pub fn create_store(engine: &Engine, plugin_path: &Path, context: PluginContext) -> anyhow::Result<Store<PluginState>> {
+    let manifest = PluginManifest::load_for_plugin(plugin_path)?;
+
+    Ok(Store::new(engine, PluginState::new(plugin_path, manifest.capabilities, context)))
+}
+
 pub struct PluginState {
     path: PathBuf,
+    capabilities: Capabilities,
+    context: PluginContext,
     ctx: WasiCtx,
     table: ResourceTable,
 }
 
 impl PluginState {
-    pub fn new(path: &Path) -> Self {
+    pub fn new(path: &Path, capabilities: Capabilities, context: PluginContext) -> Self {
         let mut ctx_builder = WasiCtxBuilder::new();
         ctx_builder.inherit_stdio();
-        ctx_builder
-            .preopened_dir("/", "/", DirPerms::all(), FilePerms::all())
-            .expect("Could not preopen folder for plugin");
-        ctx_builder.inherit_network();
+
+        //only directories can be preopened, files are only accessible via the filesystem interface of the host
+        let filesystem = &capabilities.filesystem;
+        for dir in filesystem.read.iter().filter(|path| path.is_dir()) {
+            ctx_builder
+                .preopened_dir(dir, dir.to_string_lossy(), DirPerms::READ, FilePerms::READ)
+                .expect("Could not preopen folder for plugin");
+        }
+        for dir in filesystem.write.iter().filter(|path| path.is_dir()) {
+            ctx_builder
+                .preopened_dir(dir, dir.to_string_lossy(), DirPerms::all(), FilePerms::all())
+                .expect("Could not preopen folder for plugin");
+        }
+
+        if capabilities.network {
+            ctx_builder.inherit_network();
+        }
 
         Self {
             ctx: ctx_builder.build(),
             table: ResourceTable::new(),
             path: path.to_path_buf(),
+            capabilities,
+            context,
         }
     }
+
+    fn permission_denied(&self, capability: &str) -> HostError {
+        warn!("Plugin at '{}' requested capability '{capability}', which is not granted in its manifest.", self.path.display());
+        HostError::PermissionDenied(format!("The capability '{capability}' is not granted in the plugin manifest."))
+    }
+
+    /// Checks the path against the capabilities, also after resolving symlinks, so that plugins cannot link out of their permitted paths.
+    fn permitted_path(&self, path: &str, write: bool) -> Result<PathBuf, HostError> {
+        let is_permitted = |path: &Path| if write {
+            self.capabilities.may_write(path)
+        } else {
+            self.capabilities.may_read(path)
+        };
+        let capability = if write { "filesystem.write" } else { "filesystem.read" };
+
+        let path = PathBuf::from(path);
+        if !is_permitted(&path) {
+            return Err(self.permission_denied(&format!("{capability} for {}", path.display())));
+        }
+
+        let resolved_path = if path.exists() {
+            fs::canonicalize(&path)
+        } else {
+            match (path.parent(), path.file_name()) {
+                (Some(parent), Some(file_name)) => fs::canonicalize(parent).map(|parent| parent.join(file_name)),
+                _ => Ok(path.clone()),
+            }
+        }.map_err(to_host_error)?;
+
+        if !is_permitted(&resolved_path) {
+            return Err(self.permission_denied(&format!("{capability} for {}", resolved_path.display())));
+        }
+        Ok(resolved_path)
+    }
 }
 
 impl WasiView for PluginState {
@@ -83,6 +242,10 @@ impl Host for PluginState {
         command: __internal::String,
         args: __internal::Vec<__internal::String>,
     ) -> Result<__internal::String, __internal::String> {
+        if !self.capabilities.may_call_command(&command) {
+            warn!("Plugin at '{}' tried to execute command '{command}', which is not listed in its manifest.", self.path.display());
+            return Err(format!("The command '{command}' is not listed in `capabilities.commands` of the plugin manifest."));
+        }
         trace!("Plugin executing command {command} with args {args:?}");
 
         let mut command = Command::new(command);
@@ -115,6 +278,94 @@ impl Host for PluginState {
     }
 }
 
+impl TypesHost for PluginState {}
+
+impl FilesystemHost for PluginState {
+    fn read_file(&mut self, path: __internal::String) -> Result<__internal::Vec<u8>, HostError> {
+        let path = self.permitted_path(&path, false)?;
+        fs::read(path).map_err(to_host_error)
+    }
+
+    fn write_file(&mut self, path: __internal::String, contents: __internal::Vec<u8>) -> Result<(), HostError> {
+        let path = self.permitted_path(&path, true)?;
+        trace!("Plugin writing file: {}", path.display());
+        fs::write(path, contents).map_err(to_host_error)
+    }
+
+    fn exists(&mut self, path: __internal::String) -> Result<bool, HostError> {
+        let path = self.permitted_path(&path, false)?;
+        Ok(path.exists())
+    }
+}
+
+impl NetworkHost for PluginState {
+    fn list_interfaces(&mut self) -> Result<__internal::Vec<NetworkInterface>, HostError> {
+        if !self.capabilities.network_interfaces {
+            return Err(self.permission_denied("network-interfaces"));
+        }
+
+        let mut interfaces = Vec::new();
+        for entry in fs::read_dir("/sys/class/net").map_err(to_host_error)? {
+            let entry = entry.map_err(to_host_error)?;
+            let dir = entry.path();
+            let read = |file: &str| fs::read_to_string(dir.join(file))
+                .map(|value| value.trim().to_owned())
+                .ok();
+
+            const IFF_UP: u32 = 0x1;
+            let flags = read("flags")
+                .and_then(|flags| u32::from_str_radix(flags.trim_start_matches("0x"), 16).ok())
+                .unwrap_or_default();
+
+            interfaces.push(NetworkInterface {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                index: read("ifindex").and_then(|index| index.parse().ok()).unwrap_or_default(),
+                mac_address: read("address").filter(|address| !address.is_empty()),
+                mtu: read("mtu").and_then(|mtu| mtu.parse().ok()).unwrap_or_default(),
+                up: flags & IFF_UP != 0,
+            });
+        }
+        interfaces.sort_by_key(|interface| interface.index);
+
+        Ok(interfaces)
+    }
+}
+
+impl PeerHost for PluginState {
+    fn get_peer_setup(&mut self) -> Result<Option<PeerSetup>, HostError> {
+        if !self.capabilities.peer_setup {
+            return Err(self.permission_denied("peer-setup"));
+        }
+        Ok(self.context.peer_setup.clone())
+    }
+
+    fn get_edgar_config(&mut self, key: __internal::String) -> Result<Option<__internal::String>, HostError> {
+        if !self.capabilities.edgar_config {
+            return Err(self.permission_denied("edgar-config"));
+        }
+        if key.split('.').any(|segment| segment == "secret" || segment == "password") {
+            return Err(HostError::PermissionDenied(format!("The configuration value '{key}' is secret.")));
+        }
+
+        let settings = settings::load_with_overrides(config::Config::default())
+            .map_err(|cause| HostError::Failed(cause.to_string()))?;
+
+        match settings.redacted_config.get_string(&key) {
+            Ok(value) => Ok(Some(value)),
+            Err(config::ConfigError::NotFound(_)) => Ok(None),
+            Err(cause) => Err(HostError::Failed(cause.to_string())),
+        }
+    }
+}
+
+fn to_host_error(error: io::Error) -> HostError {
+    match error.kind() {
+        io::ErrorKind::NotFound => HostError::NotFound(error.to_string()),
+        io::ErrorKind::PermissionDenied => HostError::PermissionDenied(error.to_string()),
+        _ => HostError::Failed(error.to_string()),
+    }
+}
+
 impl From<TaskFulfilled> for crate::common::task::TaskFulfilled {
     fn from(value: TaskFulfilled) -> Self {
         match value {
@@ -133,3 +384,146 @@ impl From<Success> for crate::common::task::Success {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Not;
+    use std::os::unix::fs::symlink;
+
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    use crate::setup::plugin::manifest::FilesystemCapabilities;
+
+    use super::*;
+
+    fn plugin_state(capabilities: Capabilities) -> PluginState {
+        PluginState::new(Path::new("/opt/opendut/edgar/plugins/test_plugin.wasm"), capabilities, PluginContext::default())
+    }
+
+    fn component(engine: &Engine, interface: &str) -> Component {
+        Component::new(engine, format!(r#"(component (import "{interface}" (instance)))"#))
+            .expect("Test component should be valid")
+    }
+
+    #[test]
+    fn should_permit_paths_according_to_the_filesystem_capabilities() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let root = fs::canonicalize(temp.path())?;
+        let readable = root.join("readable");
+        let writable = root.join("writable");
+        fs::create_dir(&readable)?;
+        fs::create_dir(&writable)?;
+
+        let state = plugin_state(Capabilities {
+            filesystem: FilesystemCapabilities {
+                read: vec![readable.clone()],
+                write: vec![writable.clone()],
+            },
+            ..Default::default()
+        });
+
+        let readable_file = readable.join("file").to_string_lossy().into_owned();
+        let writable_file = writable.join("file").to_string_lossy().into_owned();
+
+        assert!(matches!(state.permitted_path(&readable_file, false), Ok(path) if path == readable.join("file")));
+        assert!(matches!(state.permitted_path(&readable_file, true), Err(HostError::PermissionDenied(_))));
+        assert!(matches!(state.permitted_path(&writable_file, true), Ok(path) if path == writable.join("file")));
+        assert!(matches!(state.permitted_path(&root.join("other").to_string_lossy(), false), Err(HostError::PermissionDenied(_))));
+        assert!(matches!(state.permitted_path(&format!("{}/../other", readable.display()), false), Err(HostError::PermissionDenied(_))));
+        assert!(matches!(state.permitted_path("relative/file", false), Err(HostError::PermissionDenied(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn should_deny_symlinks_pointing_out_of_the_permitted_paths() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let root = fs::canonicalize(temp.path())?;
+        let readable = root.join("readable");
+        fs::create_dir(&readable)?;
+        temp.child("secret").write_str("secret")?;
+        symlink(root.join("secret"), readable.join("link"))?;
+
+        let state = plugin_state(Capabilities {
+            filesystem: FilesystemCapabilities {
+                read: vec![readable.clone()],
+                write: vec![],
+            },
+            ..Default::default()
+        });
+
+        let result = state.permitted_path(&readable.join("link").to_string_lossy(), false);
+
+        assert!(matches!(result, Err(HostError::PermissionDenied(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_call_commands_listed_in_the_manifest() {
+        let mut state = plugin_state(Capabilities {
+            commands: vec![String::from("true")],
+            ..Default::default()
+        });
+
+        assert_eq!(state.call_command(String::from("true"), vec![]), Ok(String::new()));
+
+        let result = state.call_command(String::from("false"), vec![]);
+        assert!(result.is_err_and(|message| message.contains("not listed")));
+    }
+
+    #[test]
+    fn should_deny_host_functions_without_capability() {
+        let mut state = plugin_state(Capabilities::default());
+
+        assert!(matches!(state.read_file(String::from("/etc/hostname")), Err(HostError::PermissionDenied(_))));
+        assert!(matches!(state.write_file(String::from("/tmp/file"), vec![]), Err(HostError::PermissionDenied(_))));
+        assert!(matches!(state.exists(String::from("/etc/hostname")), Err(HostError::PermissionDenied(_))));
+        assert!(matches!(state.list_interfaces(), Err(HostError::PermissionDenied(_))));
+        assert!(matches!(state.get_peer_setup(), Err(HostError::PermissionDenied(_))));
+        assert!(matches!(state.get_edgar_config(String::from("peer.id")), Err(HostError::PermissionDenied(_))));
+    }
+
+    #[test]
+    fn should_deny_secret_configuration_values() {
+        let mut state = plugin_state(Capabilities {
+            edgar_config: true,
+            ..Default::default()
+        });
+
+        assert!(matches!(state.get_edgar_config(String::from("network.oidc.client.secret")), Err(HostError::PermissionDenied(_))));
+    }
+
+    #[test]
+    fn should_check_the_api_version_of_a_plugin() {
+        let engine = Engine::new(Config::new().wasm_component_model(true)).unwrap(); //without debug info, which Wasmtime fails to generate for components without core modules
+
+        assert!(check_api_version(&engine, &component(&engine, &format!("edgar:setup/host@{API_VERSION}"))).is_ok());
+        assert!(check_api_version(&engine, &component(&engine, "wasi:cli/environment@0.2.0")).is_ok());
+
+        let result = check_api_version(&engine, &component(&engine, "edgar:setup/host@0.9.0"));
+        assert!(result.is_err_and(|cause| cause.to_string().contains("edgar:setup@0.9.0")));
+
+        let result = check_api_version(&engine, &component(&engine, "edgar:setup/host"));
+        assert!(result.is_err_and(|cause| cause.to_string().contains("unversioned")));
+    }
+
+    #[test]
+    fn should_accept_plugins_built_against_a_compatible_api_version() {
+        assert!(is_compatible_api_version("1.1.0", "1.1.0"));
+        assert!(is_compatible_api_version("1.0.0", "1.1.0"));
+        assert!(is_compatible_api_version("1.1.0", "1.1.2"));
+        assert!(is_compatible_api_version("0.2.0", "0.2.1"));
+
+        assert!(is_compatible_api_version("1.2.0", "1.1.0").not());
+        assert!(is_compatible_api_version("2.0.0", "1.1.0").not());
+        assert!(is_compatible_api_version("0.9.0", "1.1.0").not());
+        assert!(is_compatible_api_version("0.1.0", "0.2.0").not());
+        assert!(is_compatible_api_version("1.1.0-rc.1", "1.1.0").not());
+    }
+
+    #[test]
+    fn should_declare_the_api_version_of_the_wit_package() {
+        let wit = include_str!("../../../plugin-api/wit/world.wit");
+        assert!(wit.contains(&format!("package edgar:setup@{API_VERSION};")));
+    }
+}
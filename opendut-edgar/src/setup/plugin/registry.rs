@@ -0,0 +1,319 @@
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context};
+use config::Config;
+use ed25519_dalek::{Signature, VerifyingKey};
+use tracing::{debug, error, info, warn};
+
+use opendut_carl_api::carl::{CaCertInfo, CarlClient};
+use opendut_types::peer::{PeerId, PeerSetup};
+use opendut_types::plugin::{PluginDescriptor, PluginKind, PluginVerifyingKey};
+
+use crate::common::constants::edgar_install_directory;
+use crate::common::settings;
+use crate::fs;
+use crate::setup::plugin::init::discover_plugins_in_path;
+use crate::setup::plugin::manifest::PluginManifest;
+use crate::setup::util::checksum;
+
+const PLUGIN_ORDER_FILE: &str = "plugins.txt";
+
+/// Folder, into which the plugins assigned to this peer in the plugin registry of CARL are downloaded.
+/// Laid out like the plugin folder in the EDGAR distribution, with a signed descriptor next to each plugin.
+pub fn directory(kind: PluginKind) -> PathBuf {
+    let kind = match kind {
+        PluginKind::Setup => "setup",
+        PluginKind::Service => "service",
+    };
+    edgar_install_directory().join("registry-plugins").join(kind)
+}
+
+fn descriptor_path(plugin_path: &Path) -> PathBuf {
+    plugin_path.with_extension("descriptor.json")
+}
+
+/// Downloads the plugins of the given kind, which are assigned to this peer, replacing previously downloaded plugins.
+/// The previously downloaded plugins are kept, if downloading fails.
+pub async fn fetch_assigned_plugins(carl: &mut CarlClient, peer_id: PeerId, kind: PluginKind, target_dir: &Path) -> anyhow::Result<()> {
+    let plugins = carl.plugins.list_plugins(Some(peer_id)).await
+        .context("Failed to list the plugins assigned to this peer in the plugin registry of CARL.")?
+        .into_iter()
+        .filter(|plugin| plugin.kind == kind)
+        .collect::<Vec<_>>();
+
+    let download_dir = target_dir.with_extension("download");
+    if download_dir.exists() {
+        fs::remove_dir_all(&download_dir)?;
+    }
+    fs::create_dir_all(&download_dir)?;
+
+    let mut plugin_order = String::new();
+
+    for plugin in &plugins {
+        let (descriptor, artifact) = carl.plugins.download_plugin(plugin.id).await
+            .context(format!("Failed to download plugin '{}' in version '{}' <{}>.", plugin.name, plugin.version, plugin.id))?;
+
+        if descriptor.id != plugin.id || descriptor.kind != kind {
+            bail!("Received a different plugin from CARL than requested with <{}>.", plugin.id);
+        }
+
+        let file_name = format!("{}-{}.wasm", descriptor.name, descriptor.version);
+        if file_name.starts_with('.') || file_name.contains(std::path::is_separator) {
+            bail!("Plugin '{}' in version '{}' <{}> cannot be stored, because its name is not a valid file name.", descriptor.name, descriptor.version, descriptor.id);
+        }
+
+        let plugin_path = download_dir.join(&file_name);
+        fs::write(&plugin_path, &artifact.binary)?;
+        if let Some(manifest) = &descriptor.manifest {
+            fs::write(PluginManifest::manifest_path(&plugin_path), manifest)?;
+        }
+        fs::write(descriptor_path(&plugin_path), serde_json::to_string_pretty(&descriptor)?)?;
+
+        plugin_order.push_str(&file_name);
+        plugin_order.push('\n');
+
+        debug!("Downloaded plugin '{}' in version '{}' <{}> to: {}", descriptor.name, descriptor.version, descriptor.id, plugin_path.display());
+    }
+
+    fs::write(download_dir.join(PLUGIN_ORDER_FILE), plugin_order)?;
+
+    if target_dir.exists() {
+        fs::remove_dir_all(target_dir)?;
+    }
+    fs::rename(&download_dir, target_dir)?;
+
+    info!("Fetched {} {kind} plugin(s) from the plugin registry of CARL into: {}", plugins.len(), target_dir.display());
+    Ok(())
+}
+
+/// Fetches the setup plugins assigned to the peer during `edgar setup`, after the configuration was written.
+/// The CA certificate is taken from the PeerSetup, as it is only written to disk by a later setup task.
+pub async fn fetch_setup_plugins(peer_setup: &PeerSetup) -> anyhow::Result<()> {
+    let settings = settings::load_with_overrides(Config::default())?;
+
+    let host = peer_setup.carl.host_str()
+        .ok_or_else(|| anyhow!("Host name should be defined in CARL URL."))?;
+    let port = peer_setup.carl.port().unwrap_or(443);
+    let ca_cert = CaCertInfo::Content(peer_setup.ca.encode_as_string());
+    let domain_name_override = settings.config.get_string("network.tls.domain.name.override")?;
+    let domain_name_override = domain_name_override.is_empty().not().then_some(domain_name_override);
+
+    let mut carl = CarlClient::create(host, port, &ca_cert, &domain_name_override, &settings.config).await
+        .context("Failed to connect to CARL for fetching the plugins assigned to this peer.")?;
+
+    fetch_assigned_plugins(&mut carl, peer_setup.id, PluginKind::Setup, &directory(PluginKind::Setup)).await
+}
+
+/// Fetches the service plugins assigned to the peer when the EDGAR service starts.
+/// Failures are only logged, so the service starts with the previously downloaded plugins, e.g. while CARL is not reachable.
+pub async fn fetch_service_plugins(peer_id: PeerId, settings: &Config) {
+    let result: anyhow::Result<()> = async {
+        if verifying_key(settings)?.is_none() {
+            debug!("No plugin verifying key configured. Not fetching service plugins from the plugin registry of CARL.");
+            return Ok(());
+        }
+        let mut carl = crate::common::carl::connect(settings).await
+            .context("Failed to connect to CARL for fetching the plugins assigned to this peer.")?;
+
+        fetch_assigned_plugins(&mut carl, peer_id, PluginKind::Service, &directory(PluginKind::Service)).await
+    }.await;
+
+    if let Err(cause) = result {
+        warn!("Failed to fetch service plugins from the plugin registry of CARL. Continuing with previously downloaded plugins, if any: {cause:#}");
+    }
+}
+
+/// Discovers the plugins downloaded from the plugin registry of CARL and verifies each of them before it may be instantiated.
+/// Plugins, which fail the verification, are skipped and reported in the log, so they do not block the remaining plugins.
+pub fn discover_verified_plugins(kind: PluginKind, settings: &Config) -> anyhow::Result<Vec<PathBuf>> {
+    let plugin_dir = directory(kind);
+    discover_verified_plugins_in_path(&plugin_dir, settings)
+}
+
+fn discover_verified_plugins_in_path(plugin_dir: &Path, settings: &Config) -> anyhow::Result<Vec<PathBuf>> {
+    if plugin_dir.join(PLUGIN_ORDER_FILE).exists().not() {
+        return Ok(vec![]);
+    }
+
+    let plugin_paths = discover_plugins_in_path(plugin_dir)?;
+    if plugin_paths.is_empty() {
+        return Ok(plugin_paths);
+    }
+
+    let verifying_key = verifying_key(settings)?
+        .ok_or_else(|| anyhow!(
+            "Found plugins from the plugin registry of CARL at '{}', but no key to verify them is configured in '{}'. Run `edgar setup` again to configure it.",
+            plugin_dir.display(), settings::key::plugins::registry::verifying::key,
+        ))?;
+
+    let verified_plugin_paths = plugin_paths.into_iter()
+        .filter(|plugin_path| {
+            match verify_plugin(plugin_path, &verifying_key) {
+                Ok(descriptor) => {
+                    info!("Verified plugin '{}' in version '{}' <{}> from the plugin registry of CARL.", descriptor.name, descriptor.version, descriptor.id);
+                    true
+                }
+                Err(cause) => {
+                    error!("Verification of plugin at '{}' failed. Refusing to load it and continuing with the remaining plugins.\n  {cause:#}", plugin_path.display());
+                    false
+                }
+            }
+        })
+        .collect();
+
+    Ok(verified_plugin_paths)
+}
+
+fn verifying_key(settings: &Config) -> anyhow::Result<Option<PluginVerifyingKey>> {
+    let key = settings.get_string(settings::key::plugins::registry::verifying::key)?;
+    if key.trim().is_empty() {
+        return Ok(None);
+    }
+    let key = PluginVerifyingKey::from_str(&key)
+        .context(format!("Invalid configuration parameter for '{}'.", settings::key::plugins::registry::verifying::key))?;
+    Ok(Some(key))
+}
+
+/// Checks that the descriptor next to the plugin is signed by CARL and matches the WebAssembly component and its manifest.
+fn verify_plugin(plugin_path: &Path, verifying_key: &PluginVerifyingKey) -> anyhow::Result<PluginDescriptor> {
+    let descriptor_path = descriptor_path(plugin_path);
+    let descriptor: PluginDescriptor = serde_json::from_str(&fs::read_to_string(&descriptor_path)?)
+        .context(format!("Failed to parse plugin descriptor at '{}'.", descriptor_path.display()))?;
+
+    let key = VerifyingKey::from_bytes(&verifying_key.0)
+        .context("The configured plugin verifying key is not a valid Ed25519 public key.")?;
+    let signature = Signature::from_slice(&descriptor.signature)
+        .context("The signature in the plugin descriptor is malformed.")?;
+    key.verify_strict(&descriptor.signed_content(), &signature)
+        .map_err(|_| anyhow!("The signature of the plugin descriptor is not valid for the configured plugin verifying key."))?;
+
+    let checksum = checksum::file(plugin_path)?;
+    if checksum != descriptor.checksum {
+        bail!("The checksum of the WebAssembly component does not match the signed checksum.");
+    }
+
+    let manifest_path = PluginManifest::manifest_path(plugin_path);
+    let manifest = if manifest_path.exists() {
+        Some(fs::read_to_string(&manifest_path)?)
+    } else {
+        None
+    };
+    if manifest != descriptor.manifest {
+        bail!("The capability manifest at '{}' does not match the signed manifest.", manifest_path.display());
+    }
+
+    Ok(descriptor)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use ed25519_dalek::{Signer, SigningKey};
+    use googletest::prelude::*;
+
+    use opendut_types::plugin::PluginId;
+
+    use super::*;
+
+    const BINARY: &[u8] = b"not actually a WebAssembly component";
+    const MANIFEST: &str = "[capabilities]\nnetwork = true\n";
+
+    struct Fixture {
+        temp_dir: TempDir,
+        plugin_path: PathBuf,
+        settings: Config,
+    }
+
+    fn fixture() -> anyhow::Result<Fixture> {
+        let temp_dir = TempDir::new()?;
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+
+        let mut descriptor = PluginDescriptor {
+            id: PluginId::random(),
+            name: String::from("can-setup"),
+            version: String::from("1.0.0"),
+            kind: PluginKind::Setup,
+            checksum: checksum::string(std::str::from_utf8(BINARY)?)?,
+            manifest: Some(String::from(MANIFEST)),
+            signature: vec![],
+            uploaded_by: None,
+            uploaded_at: SystemTime::now(),
+        };
+        descriptor.signature = signing_key.sign(&descriptor.signed_content()).to_vec();
+
+        temp_dir.child(PLUGIN_ORDER_FILE).write_str("can-setup-1.0.0.wasm\n")?;
+        let plugin_path = temp_dir.child("can-setup-1.0.0.wasm");
+        plugin_path.write_binary(BINARY)?;
+        temp_dir.child("can-setup-1.0.0.toml").write_str(MANIFEST)?;
+        temp_dir.child("can-setup-1.0.0.descriptor.json").write_str(&serde_json::to_string(&descriptor)?)?;
+
+        let verifying_key = PluginVerifyingKey(signing_key.verifying_key().to_bytes());
+        let settings = Config::builder()
+            .set_override(settings::key::plugins::registry::verifying::key, verifying_key.to_string())?
+            .build()?;
+
+        Ok(Fixture { plugin_path: plugin_path.path().to_path_buf(), temp_dir, settings })
+    }
+
+    #[test]
+    fn should_accept_a_plugin_signed_by_carl() -> anyhow::Result<()> {
+        let fixture = fixture()?;
+
+        let plugins = discover_verified_plugins_in_path(fixture.temp_dir.path(), &fixture.settings)?;
+
+        assert_that!(plugins, elements_are![eq(&fixture.plugin_path)]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_a_plugin_with_a_modified_webassembly_component() -> anyhow::Result<()> {
+        let fixture = fixture()?;
+        fs::write(&fixture.plugin_path, b"modified")?;
+
+        assert_that!(discover_verified_plugins_in_path(fixture.temp_dir.path(), &fixture.settings), ok(empty()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_a_plugin_with_a_modified_or_added_manifest() -> anyhow::Result<()> {
+        let fixture = fixture()?;
+        fs::write(PluginManifest::manifest_path(&fixture.plugin_path), "[capabilities]\ncommands = [\"rm\"]\n")?;
+
+        assert_that!(discover_verified_plugins_in_path(fixture.temp_dir.path(), &fixture.settings), ok(empty()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_a_plugin_signed_with_another_key_or_without_a_configured_key() -> anyhow::Result<()> {
+        let fixture = fixture()?;
+
+        let other_key = PluginVerifyingKey(SigningKey::from_bytes(&[8; 32]).verifying_key().to_bytes());
+        let other_settings = Config::builder()
+            .set_override(settings::key::plugins::registry::verifying::key, other_key.to_string())?
+            .build()?;
+        assert_that!(discover_verified_plugins_in_path(fixture.temp_dir.path(), &other_settings), ok(empty()));
+
+        let no_key_settings = Config::builder()
+            .set_override(settings::key::plugins::registry::verifying::key, "")?
+            .build()?;
+        assert_that!(discover_verified_plugins_in_path(fixture.temp_dir.path(), &no_key_settings), err(anything()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_skip_an_unverifiable_plugin_and_load_the_remaining_plugins() -> anyhow::Result<()> {
+        let fixture = fixture()?;
+        fixture.temp_dir.child(PLUGIN_ORDER_FILE).write_str("unsigned-1.0.0.wasm\ncan-setup-1.0.0.wasm\n")?;
+        fixture.temp_dir.child("unsigned-1.0.0.wasm").write_binary(BINARY)?;
+
+        let plugins = discover_verified_plugins_in_path(fixture.temp_dir.path(), &fixture.settings)?;
+
+        assert_that!(plugins, elements_are![eq(&fixture.plugin_path)]);
+        Ok(())
+    }
+}
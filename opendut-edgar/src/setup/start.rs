@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 
-use anyhow::Context;
+use anyhow::{bail, Context};
 use tracing::info;
 use url::Url;
 
@@ -19,10 +19,10 @@ use opendut_util::telemetry;
 use opendut_util::telemetry::opentelemetry_types::Opentelemetry;
 use std::env;
 use std::ops::Not;
-use crate::cli::DryRun;
+use crate::cli::{DryRun, OutputFormat};
 
 #[allow(clippy::box_default)]
-pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mtu: u16) -> anyhow::Result<()> {
+pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mtu: u16, reenroll: bool, output: OutputFormat) -> anyhow::Result<()> {
 
     let peer_setup = PeerSetup::decode(&setup_string)
         .context("Failed to decode Setup-String.")?;
@@ -30,10 +30,27 @@ pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mt
     let service_user = determine_service_user_name();
     info!("Using service user '{}'.", service_user.name);
 
-    println!("Using PeerId: {}", peer_setup.id);
-    println!("Will connect to CARL at: {}", peer_setup.carl);
+    info!("Using PeerId <{}> and connecting to CARL at: {}", peer_setup.id, peer_setup.carl);
+    if reenroll {
+        info!("Re-enrolling peer <{}>.", peer_setup.id);
+    }
 
-    let should_run = no_confirm || user_confirmation(&dry_run)?;
+    let should_run = match output {
+        OutputFormat::Text => {
+            println!("Using PeerId: {}", peer_setup.id);
+            println!("Will connect to CARL at: {}", peer_setup.carl);
+            if reenroll {
+                println!("Re-enrolling this peer. The credentials of its previous setup will be replaced.");
+            }
+            no_confirm || user_confirmation(&dry_run, "setup")?
+        }
+        OutputFormat::Json => {
+            if dry_run.not() && no_confirm.not() {
+                bail!("Cannot ask for confirmation when printing JSON. Run with '--no-confirm' to continue without confirmation.");
+            }
+            true
+        }
+    };
     if should_run.not() {
         return Ok(());
     }
@@ -42,30 +59,42 @@ pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mt
         write_configuration::WriteConfiguration::with_override(
             write_configuration::ConfigOverride {
                 peer_id: peer_setup.id,
-                carl_url: peer_setup.carl,
-                auth_config: peer_setup.auth_config,
+                carl_url: Clone::clone(&peer_setup.carl),
+                auth_config: Clone::clone(&peer_setup.auth_config),
+                vpn: Clone::clone(&peer_setup.vpn),
+                plugin_verifying_key: peer_setup.plugin_verifying_key,
             },
             no_confirm,
+            reenroll,
         ).execute().await?;
+
+        #[cfg(not(target_arch = "arm"))]
+        {
+            if peer_setup.plugin_verifying_key.is_some() {
+                crate::setup::plugin::registry::fetch_setup_plugins(&peer_setup).await
+                    .context("Failed to fetch the setup plugins assigned to this peer from the plugin registry of CARL.")?;
+            }
+        }
     }
 
     let mut tasks: Vec<Box<dyn Task>> = vec![];
 
     #[cfg(target_arch = "arm")]
     {
-        println!("Running on ARMv7 / ARM32. Plugins cannot be used on this architecture.");
+        if output == OutputFormat::Text {
+            println!("Running on ARMv7 / ARM32. Plugins cannot be used on this architecture.");
+        }
         info!("Running on ARMv7 / ARM32. Plugins cannot be used on this architecture. For more information, see: https://github.com/bytecodealliance/wasmtime/issues/1173")
     }
     #[cfg(not(target_arch = "arm"))]
-    let _ = crate::setup::plugin::init::create_plugin_runtime(&mut tasks);
+    crate::setup::plugin::init::create_plugin_runtime(&mut tasks, crate::setup::plugin::plugin_runtime::PluginContext::for_peer_setup(&peer_setup))?;
 
 
     tasks.append(&mut vec![
         Box::new(tasks::WriteCaCertificate::with_certificate(peer_setup.ca)),
         Box::new(tasks::CheckCommandLinePrograms),
         Box::new(tasks::CheckCarlReachable),
-        Box::new(tasks::CopyExecutable),
-        Box::new(tasks::copy_rperf::CopyRperf),
+        Box::new(tasks::CopyExecutable::new()?),
 
         Box::new(tasks::LoadKernelModules::default()),
     ]);
@@ -82,10 +111,19 @@ pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mt
             info!("VPN is configured for NetBird in PeerSetup. Running NetBird-related tasks.");
             tasks.append(&mut vec![
                 Box::new(tasks::netbird::Unpack::default()),
-                Box::new(tasks::netbird::InstallService),
+                Box::new(tasks::netbird::InstallService::new()?),
                 Box::new(tasks::netbird::RestartService),
-                Box::new(tasks::netbird::Connect { management_url, setup_key, mtu }),
             ]);
+            if reenroll {
+                tasks.push(Box::new(tasks::netbird::Disconnect));
+            }
+            tasks.push(Box::new(tasks::netbird::Connect { management_url, setup_key, mtu }));
+        }
+        VpnPeerConfiguration::WireGuard { .. } => {
+            info!("VPN is configured for WireGuard in PeerSetup. The WireGuard interface is created when the service starts.");
+        }
+        VpnPeerConfiguration::Direct { .. } => {
+            info!("VPN is configured as direct network in PeerSetup. No VPN software needs to be installed.");
         }
     };
 
@@ -94,7 +132,7 @@ pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mt
     } else {
         info!("Setting up custom service user '{}'.", service_user.name);
         tasks.append(&mut vec![
-            Box::new(tasks::CreateUser { service_user: service_user.clone() }),
+            Box::new(tasks::CreateUser::with_service_user(service_user.clone())),
             Box::new(tasks::ClaimFileOwnership { service_user: service_user.clone() }),
             Box::new(tasks::RequestLinuxNetworkCapability),
         ]);
@@ -109,7 +147,16 @@ pub async fn managed(dry_run: DryRun, no_confirm: bool, setup_string: String, mt
         DryRun::Yes => RunMode::SetupDryRun,
         DryRun::No => RunMode::Setup,
     };
-    runner::run(run_mode, &tasks).await
+    let report = runner::run(run_mode, output, &tasks).await?;
+
+    if output == OutputFormat::Json {
+        println!("{}", serde_json::to_string(&report)?);
+
+        if report.success.not() {
+            bail!("Setup failed. See the report for details.");
+        }
+    }
+    Ok(())
 }
 
 #[allow(clippy::box_default, clippy::too_many_arguments)]
@@ -123,7 +170,7 @@ pub async fn unmanaged(
     leader: Leader,
     mtu: u16,
 ) -> anyhow::Result<()> {
-    let should_run = no_confirm || user_confirmation(&dry_run)?;
+    let should_run = no_confirm || user_confirmation(&dry_run, "setup")?;
     if should_run.not() {
         return Ok(());
     }
@@ -138,29 +185,61 @@ pub async fn unmanaged(
         info!("Running on ARMv7 / ARM32. Plugins cannot be used on this architecture. For more information, see: https://github.com/bytecodealliance/wasmtime/issues/1173")
     }
     #[cfg(not(target_arch = "arm"))]
-    let _ = crate::setup::plugin::init::create_plugin_runtime(&mut tasks);
+    crate::setup::plugin::init::create_plugin_runtime(&mut tasks, crate::setup::plugin::plugin_runtime::PluginContext::default())?;
 
     tasks.append(&mut vec![
         Box::new(tasks::CheckCommandLinePrograms),
         Box::new(tasks::netbird::Unpack::default()),
-        Box::new(tasks::netbird::InstallService),
+        Box::new(tasks::netbird::InstallService::new()?),
         Box::new(tasks::netbird::RestartService),
         Box::new(tasks::netbird::Connect { management_url, setup_key, mtu }),
 
         Box::new(tasks::network_interface::CreateBridge { network_interface_manager: Arc::clone(&network_interface_manager), bridge_name: bridge_name.clone() }),
         Box::new(tasks::network_interface::CreateGreInterfaces { network_interface_manager: Arc::clone(&network_interface_manager), bridge_name: bridge_name.clone(), leader }),
         Box::new(tasks::network_interface::ConnectDeviceInterfaces { network_interface_manager, bridge_name, device_interfaces }),
-
-        Box::new(tasks::copy_rperf::CopyRperf),
     ]);
 
     let run_mode = match dry_run {
         DryRun::Yes => RunMode::SetupDryRun,
         DryRun::No => RunMode::Setup,
     };
-    runner::run(run_mode, &tasks).await
+    runner::run(run_mode, OutputFormat::Text, &tasks).await?;
+    Ok(())
 }
 
+/// Undoes the tasks of a managed setup in reverse order.
+/// The configuration file is kept, so that EDGAR can be set up again with the same PeerId.
+#[allow(clippy::box_default)]
+pub async fn uninstall(dry_run: DryRun, no_confirm: bool) -> anyhow::Result<()> {
+    let should_run = no_confirm || user_confirmation(&dry_run, "uninstall")?;
+    if should_run.not() {
+        return Ok(());
+    }
+
+    let service_user = determine_service_user_name();
+
+    //listed in the order of the setup, the runner undoes them in reverse order
+    let mut tasks: Vec<Box<dyn Task>> = vec![
+        Box::new(tasks::WriteCaCertificate::without_certificate()),
+        Box::new(tasks::CopyExecutable::new()?),
+        Box::new(tasks::CreateKernelModuleLoadRule),
+        Box::new(tasks::netbird::Unpack::default()),
+        Box::new(tasks::netbird::InstallService::new()?),
+    ];
+
+    if service_user.is_root().not() {
+        tasks.push(Box::new(tasks::CreateUser::with_service_user(service_user.clone())));
+    }
+
+    tasks.push(Box::new(tasks::CreateServiceFile::with_service_user(service_user)));
+
+    let run_mode = match dry_run {
+        DryRun::Yes => RunMode::UninstallDryRun,
+        DryRun::No => RunMode::Uninstall,
+    };
+    runner::run(run_mode, OutputFormat::Text, &tasks).await?;
+    Ok(())
+}
 
 pub async fn init_logging() -> anyhow::Result<()> {
 
@@ -188,11 +267,11 @@ fn determine_service_user_name() -> User {
     User { name }
 }
 
-fn user_confirmation(dry_run: &DryRun) -> anyhow::Result<bool> {
+fn user_confirmation(dry_run: &DryRun, action: &str) -> anyhow::Result<bool> {
     let crate_version = crate::app_info::CRATE_VERSION;
     match dry_run {
         DryRun::No => {
-            println!("This will setup EDGAR {crate_version} on your system.");
+            println!("This will {action} EDGAR {crate_version} on your system.");
 
             let user_confirmed = crate::setup::user_confirmation_prompt("Do you want to continue?")?;
 
@@ -203,7 +282,7 @@ fn user_confirmation(dry_run: &DryRun) -> anyhow::Result<bool> {
             Ok(user_confirmed)
         }
         DryRun::Yes => {
-            println!("Pretending to setup EDGAR {crate_version} on your system.");
+            println!("Pretending to {action} EDGAR {crate_version} on your system.");
             Ok(true)
         }
     }
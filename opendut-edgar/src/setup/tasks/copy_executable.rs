@@ -1,5 +1,6 @@
 use crate::fs;
 use std::os::unix;
+use std::path::PathBuf;
 use std::process::Command;
 
 use anyhow::{Context, Result};
@@ -7,20 +8,24 @@ use async_trait::async_trait;
 use crate::setup::constants::{executable_install_path, PATH_dir, SYSTEMD_SERVICE_FILE_NAME};
 use crate::common::task::{Success, Task, TaskFulfilled};
 use crate::setup::util;
+use crate::setup::util::{CommandRunner, DefaultCommandRunner};
 
-pub struct CopyExecutable;
+pub struct CopyExecutable {
+    pub executable_path: PathBuf,
+    pub install_path: PathBuf,
+    pub link_path: PathBuf,
+    pub command_runner: Box<dyn CommandRunner>,
+}
 
 #[async_trait]
 impl Task for CopyExecutable {
     fn description(&self) -> String {
-        format!("Copy executable to \"{}\"", executable_install_path().unwrap().display())
+        format!("Copy executable to \"{}\"", self.install_path.display())
     }
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
-        let installed_path = executable_install_path()?;
-        if installed_path.exists() {
-            let installed_digest = util::checksum::file(installed_path)?;
-            let unpacked_path = std::env::current_exe()?;
-            let unpacked_digest = util::checksum::file(unpacked_path)?;
+        if self.install_path.exists() {
+            let installed_digest = util::checksum::file(&self.install_path)?;
+            let unpacked_digest = util::checksum::file(&self.executable_path)?;
 
             if installed_digest == unpacked_digest {
                 return Ok(TaskFulfilled::Yes);
@@ -29,28 +34,154 @@ impl Task for CopyExecutable {
         Ok(TaskFulfilled::No)
     }
     async fn execute(&self) -> Result<Success> {
-        let target_path = executable_install_path()?;
+        let target_path = &self.install_path;
         fs::create_dir_all(target_path.parent().unwrap())?;
 
-        let executable_path = std::env::current_exe()?;
-        let executable_name = executable_path.file_name()
-            .context("Failed to retrieve file name of executable.")?;
+        let executable_path = &self.executable_path;
 
         // Stop service to allow replacing executable. This also ensures the new executable is used in the service immediately.
-        let mut command = Command::new("systemctl");
-        let command = command.arg("stop").arg(SYSTEMD_SERVICE_FILE_NAME);
-        let _ = command.output(); //ignore errors, e.g. when service is not running.
+        let _ = self.command_runner.run(
+            Command::new("systemctl").arg("stop").arg(SYSTEMD_SERVICE_FILE_NAME)
+        ); //ignore errors, e.g. when service is not running.
 
-        fs::copy(&executable_path, &target_path)
+        fs::copy(executable_path, target_path)
             .context(format!("Error while copying file from '{}' to '{}'", executable_path.display(), target_path.display()))?;
 
-        let link_path = PATH_dir().join(executable_name);
-        if link_path.exists() {
-            fs::remove_file(&link_path)?;
+        let link_path = &self.link_path;
+        if link_path.symlink_metadata().is_ok() {
+            fs::remove_file(link_path)?;
         }
-        unix::fs::symlink(&target_path, &link_path)
+        unix::fs::symlink(target_path, link_path)
             .context(format!("Error while linking executable from '{}' to PATH directory '{}'", target_path.display(), link_path.display()))?;
 
         Ok(Success::default())
     }
+    async fn check_undone(&self) -> Result<TaskFulfilled> {
+        if self.install_path.exists() || self.link_path.symlink_metadata().is_ok() {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn undo(&self) -> Result<Success> {
+        if self.link_path.symlink_metadata().is_ok() {
+            fs::remove_file(&self.link_path)?;
+        }
+
+        if self.install_path.exists() {
+            fs::remove_file(&self.install_path)
+                .context(format!("Error while removing executable at '{}'", self.install_path.display()))?;
+        }
+
+        Ok(Success::default())
+    }
+}
+
+impl CopyExecutable {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            executable_path: std::env::current_exe()?,
+            install_path: executable_install_path()?,
+            link_path: link_path()?,
+            command_runner: Box::new(DefaultCommandRunner),
+        })
+    }
+}
+
+fn link_path() -> Result<PathBuf> {
+    let executable_path = std::env::current_exe()?;
+    let executable_name = executable_path.file_name()
+        .context("Failed to retrieve file name of executable.")?;
+    Ok(PATH_dir().join(executable_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::fixture::ChildPath;
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use rstest::{fixture, rstest};
+
+    use crate::common::task::{Task, TaskFulfilled};
+    use crate::setup::tasks::CopyExecutable;
+    use crate::setup::util::NoopCommandRunner;
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_check_task_is_fulfilled(fixture: Fixture) -> anyhow::Result<()> {
+        let task = fixture.task();
+
+        assert_eq!(task.check_fulfilled().await?, TaskFulfilled::No);
+        task.execute().await?;
+        assert_eq!(task.check_fulfilled().await?, TaskFulfilled::Yes);
+
+        fixture.link_path.assert(predicates::path::is_symlink());
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_remove_the_executable_and_its_link_when_undone(fixture: Fixture) -> anyhow::Result<()> {
+        let task = fixture.task();
+
+        assert_eq!(task.check_undone().await?, TaskFulfilled::Yes);
+        task.execute().await?;
+        assert_eq!(task.check_undone().await?, TaskFulfilled::No);
+
+        task.undo().await?;
+        assert_eq!(task.check_undone().await?, TaskFulfilled::Yes);
+        fixture.install_path.assert(predicates::path::missing());
+        fixture.link_path.assert(predicates::path::missing());
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_remove_a_dangling_link_when_undone(fixture: Fixture) -> anyhow::Result<()> {
+        let task = fixture.task();
+        std::os::unix::fs::symlink(fixture.install_path.path(), fixture.link_path.path())?;
+
+        assert_eq!(task.check_undone().await?, TaskFulfilled::No);
+
+        task.undo().await?;
+        assert_eq!(task.check_undone().await?, TaskFulfilled::Yes);
+        assert!(fixture.link_path.path().symlink_metadata().is_err());
+
+        Ok(())
+    }
+
+    struct Fixture {
+        _temp: TempDir,
+        executable_path: ChildPath,
+        install_path: ChildPath,
+        link_path: ChildPath,
+    }
+    impl Fixture {
+        fn task(&self) -> CopyExecutable {
+            CopyExecutable {
+                executable_path: self.executable_path.to_path_buf(),
+                install_path: self.install_path.to_path_buf(),
+                link_path: self.link_path.to_path_buf(),
+                command_runner: Box::new(NoopCommandRunner),
+            }
+        }
+    }
+    #[fixture]
+    fn fixture() -> Fixture {
+        let temp = TempDir::new().unwrap();
+
+        let executable_path = temp.child("distribution/opendut-edgar");
+        executable_path.write_binary(b"executable").unwrap();
+        let install_path = temp.child("install/opendut-edgar");
+        let link_path = temp.child("opendut-edgar");
+
+        Fixture {
+            _temp: temp,
+            executable_path,
+            install_path,
+            link_path,
+        }
+    }
 }
@@ -72,6 +72,28 @@ impl Task for CreateKernelModuleLoadRule {
             }
         }
 
+        Ok(Success::default())
+    }
+    async fn check_undone(&self) -> Result<TaskFulfilled> {
+        let any_rule_exists = required_kernel_modules().iter()
+            .any(|kernel_module| load_rule_file_path(kernel_module).exists() || options_rule_file_path(kernel_module).exists());
+
+        if any_rule_exists {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn undo(&self) -> Result<Success> {
+        for kernel_module in required_kernel_modules() {
+            for path in [load_rule_file_path(&kernel_module), options_rule_file_path(&kernel_module)] {
+                if path.exists() {
+                    fs::remove_file(&path)
+                        .context(format!("Error while removing kernel module rule file '{}'", path.display()))?;
+                }
+            }
+        }
+
         Ok(Success::default())
     }
 }
@@ -92,6 +92,30 @@ impl Task for CreateServiceFile {
         fs::write(checksum_systemd_file_unpack_file, checksum_systemd_file)
             .context(format!("Writing checksum for opendut-edgar.service to '{}'.", checksum_systemd_file_unpack_file.display()))?;
 
+        Ok(Success::default())
+    }
+    async fn check_undone(&self) -> Result<TaskFulfilled> {
+        if self.systemd_file_path.exists() || self.checksum_systemd_file.exists() {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn undo(&self) -> Result<Success> {
+        let _ = self.command_runner.run(
+            Command::new("systemctl").args(["disable", "--now"]).arg(SYSTEMD_SERVICE_FILE_NAME)
+        ); //ignore errors, e.g. when the service is not loaded anymore
+
+        for file in [&self.systemd_file_path, &self.checksum_systemd_file] {
+            if file.exists() {
+                fs::remove_file(file)?;
+            }
+        }
+
+        self.command_runner.run(
+            Command::new("systemctl").arg("daemon-reload")
+        ).context("systemctl daemon-reload could not be executed successfully!")?;
+
         Ok(Success::default())
     }
 }
@@ -139,4 +163,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn should_remove_the_service_file_when_undone() -> anyhow::Result<()> {
+        let temp = TempDir::new().unwrap();
+
+        let systemd_file_path = temp.child(SYSTEMD_SERVICE_FILE_NAME);
+        let checksum_systemd_file_path = temp.child(format!("{SYSTEMD_SERVICE_FILE_NAME}.checksum"));
+
+        let task = CreateServiceFile {
+            service_user: User { name: "testUser".to_string() },
+            systemd_file_path: systemd_file_path.to_path_buf(),
+            checksum_systemd_file: checksum_systemd_file_path.to_path_buf(),
+            command_runner: Box::new(NoopCommandRunner),
+        };
+
+        assert_eq!(task.check_undone().await?, TaskFulfilled::Yes);
+        task.execute().await?;
+        assert_eq!(task.check_undone().await?, TaskFulfilled::No);
+
+        task.undo().await?;
+        assert_eq!(task.check_undone().await?, TaskFulfilled::Yes);
+        systemd_file_path.assert(predicates::path::missing());
+        checksum_systemd_file_path.assert(predicates::path::missing());
+
+        Ok(())
+    }
 }
@@ -7,12 +7,14 @@ use async_trait::async_trait;
 use crate::setup::User;
 
 use crate::common::task::{Success, Task, TaskFulfilled};
-use crate::setup::util::EvaluateRequiringSuccess;
+use crate::setup::util::{CommandRunner, DefaultCommandRunner};
 
-fn passwd_file() -> PathBuf { PathBuf::from("/etc/passwd") }
+fn default_passwd_file() -> PathBuf { PathBuf::from("/etc/passwd") }
 
 pub struct CreateUser {
     pub service_user: User,
+    pub passwd_file: PathBuf,
+    pub command_runner: Box<dyn CommandRunner>,
 }
 
 #[async_trait]
@@ -22,28 +24,119 @@ impl Task for CreateUser {
     }
 
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
-        let passwd = fs::read_to_string(passwd_file())?;
+        if self.user_exists()? {
+            Ok(TaskFulfilled::Yes)
+        } else {
+            Ok(TaskFulfilled::No)
+        }
+    }
+
+    async fn execute(&self) -> Result<Success> {
+        self.command_runner.run(
+            Command::new("useradd").arg("--no-create-home").arg(&self.service_user.name)
+        ).context(format!("Error when trying to create user '{}'.", self.service_user.name))?;
+
+        Ok(Success::default())
+    }
+
+    async fn check_undone(&self) -> Result<TaskFulfilled> {
+        if self.user_exists()? {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+
+    async fn undo(&self) -> Result<Success> {
+        self.command_runner.run(
+            Command::new("userdel").arg(&self.service_user.name)
+        ).context(format!("Error when trying to delete user '{}'.", self.service_user.name))?;
+
+        Ok(Success::default())
+    }
+}
+
+impl CreateUser {
+    pub fn with_service_user(service_user: User) -> Self {
+        Self {
+            service_user,
+            passwd_file: default_passwd_file(),
+            command_runner: Box::new(DefaultCommandRunner),
+        }
+    }
+
+    fn user_exists(&self) -> Result<bool> {
+        let passwd = fs::read_to_string(&self.passwd_file)?;
 
         let user_exists = passwd.lines()
             .any(|line| match line.split(':').next() {
                 Some(user) => user == self.service_user.name,
                 None => false,
             });
+        Ok(user_exists)
+    }
+}
 
-        if user_exists {
-            Ok(TaskFulfilled::Yes)
-        } else {
-            Ok(TaskFulfilled::No)
-        }
+#[cfg(test)]
+mod tests {
+    use assert_fs::fixture::ChildPath;
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+
+    use crate::common::task::{Task, TaskFulfilled};
+    use crate::setup::tasks::CreateUser;
+    use crate::setup::User;
+    use crate::setup::util::RecordingCommandRunner;
+
+    #[tokio::test]
+    async fn should_delete_the_user_when_undone() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let passwd_file = passwd_file(&temp, "testUser")?;
+
+        let command_runner = RecordingCommandRunner::default();
+        let task = create_user(&passwd_file, &command_runner);
+
+        assert_eq!(task.check_undone().await?, TaskFulfilled::No);
+
+        task.undo().await?;
+
+        assert_that!(command_runner.recorded_commands(), elements_are![eq("userdel testUser")]);
+        Ok(())
     }
 
-    async fn execute(&self) -> Result<Success> {
-        Command::new("useradd")
-            .arg("--no-create-home")
-            .arg(&self.service_user.name)
-            .evaluate_requiring_success()
-            .context(format!("Error when trying to create user '{}'.", self.service_user.name))?;
+    #[tokio::test]
+    async fn should_check_the_user_is_undone_if_it_does_not_exist() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let passwd_file = passwd_file(&temp, "testUserWithSuffix")?;
 
-        Ok(Success::default())
+        let task = create_user(&passwd_file, &RecordingCommandRunner::default());
+
+        assert_eq!(task.check_undone().await?, TaskFulfilled::Yes);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_to_undo_if_the_user_cannot_be_deleted() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+
+        let task = create_user(&temp.child("passwd"), &RecordingCommandRunner::failing_on("userdel testUser"));
+
+        assert!(task.undo().await.is_err());
+        Ok(())
+    }
+
+    fn passwd_file(temp: &TempDir, user_name: &str) -> anyhow::Result<ChildPath> {
+        let passwd_file = temp.child("passwd");
+        passwd_file.write_str(&format!("root:x:0:0:root:/root:/bin/bash\n{user_name}:x:1000:1000::/home/{user_name}:/bin/sh\n"))?;
+        Ok(passwd_file)
+    }
+
+    fn create_user(passwd_file: &ChildPath, command_runner: &RecordingCommandRunner) -> CreateUser {
+        CreateUser {
+            service_user: User { name: String::from("testUser") },
+            passwd_file: passwd_file.to_path_buf(),
+            command_runner: Box::new(Clone::clone(command_runner)),
+        }
     }
 }
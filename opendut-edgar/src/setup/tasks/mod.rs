@@ -34,5 +34,3 @@ pub use create_kernel_module_load_rule::CreateKernelModuleLoadRule;
 
 pub mod write_ca_certificate;
 pub use write_ca_certificate::WriteCaCertificate;
-
-pub mod copy_rperf;
@@ -0,0 +1,38 @@
+use std::ops::Not;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::common::task::{Success, Task, TaskFulfilled};
+
+/// Logs the NetBird Client out of its previous peer, so that it can be logged in with the setup key of a re-enrollment.
+pub struct Disconnect;
+
+#[async_trait]
+impl Task for Disconnect {
+    fn description(&self) -> String {
+        String::from("NetBird - Disconnect Previous Enrollment")
+    }
+    async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
+        if opendut_netbird_client_api::client::socket_path().exists().not() {
+            debug!("NetBird Client is not running. Nothing to disconnect.");
+            return Ok(TaskFulfilled::Yes);
+        }
+        let mut client = opendut_netbird_client_api::client::Client::connect().await?;
+        let is_up = client.check_is_up().await?;
+        if is_up {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn execute(&self) -> Result<Success> {
+        let mut client = opendut_netbird_client_api::client::Client::connect().await?;
+
+        client.down().await
+            .context("Error during NetBird-Down")?;
+
+        Ok(Success::default())
+    }
+}
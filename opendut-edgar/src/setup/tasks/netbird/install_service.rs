@@ -1,12 +1,16 @@
+use std::path::PathBuf;
 use std::process::Command;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use crate::setup::constants::netbird;
 use crate::common::task::{Success, Task, TaskFulfilled};
-use crate::setup::util::EvaluateRequiringSuccess;
+use crate::setup::util::{CommandRunner, DefaultCommandRunner};
 
-pub struct InstallService;
+pub struct InstallService {
+    pub netbird_executable: PathBuf,
+    pub command_runner: Box<dyn CommandRunner>,
+}
 
 #[async_trait]
 impl Task for InstallService {
@@ -14,25 +18,114 @@ impl Task for InstallService {
         String::from("NetBird - Install Service")
     }
     async fn check_fulfilled(&self) -> Result<TaskFulfilled> {
-        let output = Command::new("systemctl")
-            .arg("cat")
-            .arg("netbird")
-            .output()?;
-
-        if output.status.success() {
+        if self.service_installed() {
             Ok(TaskFulfilled::Yes)
         } else {
             Ok(TaskFulfilled::No)
         }
     }
     async fn execute(&self) -> Result<Success> {
-        let netbird = netbird::unpacked_executable()?;
+        self.command_runner.run(
+            Command::new(&self.netbird_executable).arg("service").arg("install")
+        )?;
+
+        Ok(Success::default())
+    }
+    async fn check_undone(&self) -> Result<TaskFulfilled> {
+        if self.service_installed() {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn undo(&self) -> Result<Success> {
+        let _ = self.command_runner.run(
+            Command::new(&self.netbird_executable).arg("service").arg("stop")
+        ); //ignore errors, e.g. when service is not running.
 
-        let _ = Command::new(netbird)
-            .arg("service")
-            .arg("install")
-            .evaluate_requiring_success()?;
+        self.command_runner.run(
+            Command::new(&self.netbird_executable).arg("service").arg("uninstall")
+        )?;
 
         Ok(Success::default())
     }
 }
+
+impl InstallService {
+    fn service_installed(&self) -> bool {
+        self.command_runner.run(
+            Command::new("systemctl").arg("cat").arg("netbird")
+        ).is_ok()
+    }
+}
+
+impl InstallService {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            netbird_executable: netbird::unpacked_executable()?,
+            command_runner: Box::new(DefaultCommandRunner),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use googletest::prelude::*;
+
+    use crate::common::task::{Task, TaskFulfilled};
+    use crate::setup::tasks::netbird::InstallService;
+    use crate::setup::util::RecordingCommandRunner;
+
+    fn install_service(command_runner: &RecordingCommandRunner) -> InstallService {
+        InstallService {
+            netbird_executable: PathBuf::from("netbird"),
+            command_runner: Box::new(Clone::clone(command_runner)),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_stop_and_uninstall_the_service_when_undone() -> anyhow::Result<()> {
+        let command_runner = RecordingCommandRunner::default();
+        let task = install_service(&command_runner);
+
+        task.undo().await?;
+
+        assert_that!(command_runner.recorded_commands(), elements_are![
+            eq("netbird service stop"),
+            eq("netbird service uninstall"),
+        ]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_uninstall_the_service_when_undone_even_if_it_is_not_running() -> anyhow::Result<()> {
+        let command_runner = RecordingCommandRunner::failing_on("netbird service stop");
+        let task = install_service(&command_runner);
+
+        task.undo().await?;
+
+        assert_that!(command_runner.recorded_commands(), contains(eq("netbird service uninstall")));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fail_to_undo_if_the_service_cannot_be_uninstalled() -> anyhow::Result<()> {
+        let command_runner = RecordingCommandRunner::failing_on("netbird service uninstall");
+        let task = install_service(&command_runner);
+
+        assert!(task.undo().await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_check_the_service_is_undone_if_it_is_not_known_to_systemd() -> anyhow::Result<()> {
+        let installed = install_service(&RecordingCommandRunner::default());
+        assert_eq!(installed.check_undone().await?, TaskFulfilled::No);
+
+        let uninstalled = install_service(&RecordingCommandRunner::failing_on("systemctl cat netbird"));
+        assert_eq!(uninstalled.check_undone().await?, TaskFulfilled::Yes);
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 pub mod connect;
 pub use connect::Connect;
 
+pub mod disconnect;
+pub use disconnect::Disconnect;
+
 pub mod restart_service;
 pub use restart_service::RestartService;
 
@@ -49,6 +49,24 @@ impl Task for Unpack {
         fs::write(checksum_unpack_file, checksum)
             .context(format!("Writing checksum for netbird.tar.gz to '{}'.", checksum_unpack_file.display()))?;
 
+        Ok(Success::default())
+    }
+    async fn check_undone(&self) -> Result<TaskFulfilled> {
+        if self.to_dir.exists() || self.checksum_unpack_file.exists() {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+    async fn undo(&self) -> Result<Success> {
+        if self.to_dir.exists() {
+            fs::remove_dir_all(&self.to_dir)
+                .context(format!("Failed to remove unpacked NetBird distribution at '{}'.", self.to_dir.display()))?;
+        }
+        if self.checksum_unpack_file.exists() {
+            fs::remove_file(&self.checksum_unpack_file)?;
+        }
+
         Ok(Success::default())
     }
 }
@@ -102,4 +120,33 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn should_remove_the_unpacked_files_when_undone() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+
+        let from = temp.child("netbird.tar.gz");
+        from.write_binary(b"hello")?;
+
+        let to_dir = temp.child("netbird");
+        to_dir.child("netbird").write_binary(b"executable")?;
+
+        let checksum_file_path = temp.child("netbird.tar.gz.checksum");
+        checksum_file_path.write_binary(&util::checksum::file(from.path())?)?;
+
+        let task = Unpack {
+            from: from.to_path_buf(),
+            to_dir: to_dir.to_path_buf(),
+            checksum_unpack_file: checksum_file_path.to_path_buf(),
+        };
+
+        assert_eq!(task.check_undone().await?, TaskFulfilled::No);
+        task.undo().await?;
+        assert_eq!(task.check_undone().await?, TaskFulfilled::Yes);
+
+        to_dir.assert(predicates::path::missing());
+        from.assert(predicates::path::exists());
+
+        Ok(())
+    }
 }
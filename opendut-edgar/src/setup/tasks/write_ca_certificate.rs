@@ -13,7 +13,8 @@ use crate::common::task::{Success, Task, TaskFulfilled};
 use crate::setup::util::{CommandRunner, DefaultCommandRunner};
 
 pub struct WriteCaCertificate {
-    pub certificate: Certificate,
+    /// Certificate to write. Not needed for undoing the task.
+    pub certificate: Option<Certificate>,
     pub carl_ca_certificate_path: PathBuf,
     pub os_cert_store_ca_certificate_path: PathBuf,
     pub checksum_carl_ca_certificate_file: PathBuf,
@@ -29,6 +30,9 @@ impl Task for WriteCaCertificate {
     }
 
     async fn check_fulfilled(&self) -> anyhow::Result<TaskFulfilled> {
+        let certificate = self.certificate.as_ref()
+            .context("No CA certificate provided for writing.")?;
+
         let installed_carl_checksum_file = &self.checksum_carl_ca_certificate_file;
         let installed_os_cert_store_checksum_file = &self.checksum_os_cert_store_ca_certificate_file;
 
@@ -54,7 +58,7 @@ impl Task for WriteCaCertificate {
         };
 
         let provided_certificate_checksum = util::checksum::string(
-            self.certificate.encode_as_string()
+            certificate.encode_as_string()
         )?;
 
         if installed_carl_checksum == provided_certificate_checksum
@@ -67,20 +71,54 @@ impl Task for WriteCaCertificate {
     }
 
     async fn execute(&self) -> anyhow::Result<Success> {
+        let certificate = self.certificate.as_ref()
+            .context("No CA certificate provided for writing.")?;
         let carl_ca_certificate_path = &self.carl_ca_certificate_path;
 
-        write_carl_certificate(&self.certificate, carl_ca_certificate_path, &self.checksum_carl_ca_certificate_file)?;
+        write_carl_certificate(certificate, carl_ca_certificate_path, &self.checksum_carl_ca_certificate_file)?;
 
         write_os_cert_store_certificate(carl_ca_certificate_path, &self.os_cert_store_ca_certificate_path, &self.checksum_os_cert_store_ca_certificate_file, self.command_runner.as_ref())?; //TODO this certificate doesn't have to be the same as for CARL and should instead be retrieved from CARL after the initial connection
 
         Ok(Success::default())
     }
+
+    async fn check_undone(&self) -> anyhow::Result<TaskFulfilled> {
+        if self.files().iter().any(|file| file.exists()) {
+            Ok(TaskFulfilled::No)
+        } else {
+            Ok(TaskFulfilled::Yes)
+        }
+    }
+
+    async fn undo(&self) -> anyhow::Result<Success> {
+        for file in self.files() {
+            if file.exists() {
+                fs::remove_file(file)?;
+            }
+        }
+
+        if let Ok(update_ca_certificates) = which::which("update-ca-certificates") {
+            self.command_runner.run(
+                Command::new(update_ca_certificates).arg("--fresh") //remove the dangling link to our certificate from the OS certificate store
+            ).context("update-ca-certificates could not be executed successfully!")?;
+        }
+
+        Ok(Success::default())
+    }
 }
 
 impl WriteCaCertificate {
     pub fn with_certificate(certificate: Certificate) -> Self {
         Self {
-            certificate,
+            certificate: Some(certificate),
+            ..Self::without_certificate()
+        }
+    }
+
+    /// For undoing the task, when uninstalling EDGAR.
+    pub fn without_certificate() -> Self {
+        Self {
+            certificate: None,
             carl_ca_certificate_path: constants::default_carl_ca_certificate_path(),
             os_cert_store_ca_certificate_path: constants::default_os_cert_store_ca_certificate_path(),
             checksum_carl_ca_certificate_file: constants::default_checksum_carl_ca_certificate_file(),
@@ -88,6 +126,15 @@ impl WriteCaCertificate {
             command_runner: Box::new(DefaultCommandRunner),
         }
     }
+
+    fn files(&self) -> [&PathBuf; 4] {
+        [
+            &self.carl_ca_certificate_path,
+            &self.os_cert_store_ca_certificate_path,
+            &self.checksum_carl_ca_certificate_file,
+            &self.checksum_os_cert_store_ca_certificate_file,
+        ]
+    }
 }
 
 fn write_carl_certificate(new_certificate: &Certificate, carl_ca_certificate_path: &Path, checksum_carl_ca_certificate_file: &Path) -> anyhow::Result<()> {
@@ -173,7 +220,7 @@ mod tests {
         let pem_string = PEM_STRING_1;
 
         let task = WriteCaCertificate {
-            certificate: Certificate::from_str(pem_string)?,
+            certificate: Some(Certificate::from_str(pem_string)?),
             carl_ca_certificate_path: carl_ca_certificate_path.to_path_buf(),
             os_cert_store_ca_certificate_path: os_cert_store_ca_certificate_path.to_path_buf(),
             checksum_carl_ca_certificate_file: checksum_carl_ca_certificate_file.to_path_buf(),
@@ -188,6 +235,38 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_remove_the_certificate_files_when_undone() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+
+        let carl_ca_certificate_path = temp.child("ca.pem");
+        let os_cert_store_ca_certificate_path = temp.child("opendut-ca.crt");
+
+        let checksum_carl_ca_certificate_file = temp.child("ca.pem.checksum");
+        let checksum_os_cert_store_ca_certificate_file = temp.child("opendut-ca.crt.checksum");
+
+        carl_ca_certificate_path.write_str(PEM_STRING_1)?;
+        os_cert_store_ca_certificate_path.write_str(PEM_STRING_1)?;
+
+        let task = WriteCaCertificate {
+            certificate: None,
+            carl_ca_certificate_path: carl_ca_certificate_path.to_path_buf(),
+            os_cert_store_ca_certificate_path: os_cert_store_ca_certificate_path.to_path_buf(),
+            checksum_carl_ca_certificate_file: checksum_carl_ca_certificate_file.to_path_buf(),
+            checksum_os_cert_store_ca_certificate_file: checksum_os_cert_store_ca_certificate_file.to_path_buf(),
+            command_runner: Box::new(NoopCommandRunner),
+        };
+
+        assert_eq!(task.check_undone().await?, TaskFulfilled::No);
+        task.undo().await?;
+        assert_eq!(task.check_undone().await?, TaskFulfilled::Yes);
+
+        carl_ca_certificate_path.assert(predicates::path::missing());
+        os_cert_store_ca_certificate_path.assert(predicates::path::missing());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn should_report_task_as_unfulfilled_when_checksums_dont_match() -> anyhow::Result<()> {
         let temp = TempDir::new()?;
@@ -210,7 +289,7 @@ mod tests {
 
 
         let task = WriteCaCertificate {
-            certificate: Certificate::from_str(new_pem)?,
+            certificate: Some(Certificate::from_str(new_pem)?),
             carl_ca_certificate_path: carl_ca_certificate_path.to_path_buf(),
             os_cert_store_ca_certificate_path: os_cert_store_ca_certificate_path.to_path_buf(),
             checksum_carl_ca_certificate_file: checksum_carl_ca_certificate_file.to_path_buf(),
@@ -245,7 +324,7 @@ mod tests {
 
 
         let task = WriteCaCertificate {
-            certificate: Certificate::from_str(pem_string)?,
+            certificate: Some(Certificate::from_str(pem_string)?),
             carl_ca_certificate_path: carl_ca_certificate_path.to_path_buf(),
             os_cert_store_ca_certificate_path: os_cert_store_ca_certificate_path.to_path_buf(),
             checksum_carl_ca_certificate_file: checksum_carl_ca_certificate_file.to_path_buf(),
@@ -275,7 +354,7 @@ mod tests {
         os_cert_store_ca_certificate_path.write_str(pem_string)?;
 
         let task = WriteCaCertificate {
-            certificate: Certificate::from_str(pem_string)?,
+            certificate: Some(Certificate::from_str(pem_string)?),
             carl_ca_certificate_path: carl_ca_certificate_path.to_path_buf(),
             os_cert_store_ca_certificate_path: os_cert_store_ca_certificate_path.to_path_buf(),
             checksum_carl_ca_certificate_file: checksum_carl_ca_certificate_file.to_path_buf(),
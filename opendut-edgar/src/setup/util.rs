@@ -31,6 +31,36 @@ cfg_if! {
                 })
             }
         }
+
+        /// Records the command lines instead of running them. The given command line fails, to test error handling.
+        #[derive(Clone, Default)]
+        pub struct RecordingCommandRunner {
+            pub commands: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+            pub failing_command: Option<String>,
+        }
+        impl RecordingCommandRunner {
+            pub fn failing_on(command: impl Into<String>) -> Self {
+                Self { failing_command: Some(command.into()), ..Default::default() }
+            }
+            pub fn recorded_commands(&self) -> Vec<String> {
+                self.commands.lock().unwrap().clone()
+            }
+        }
+        impl CommandRunner for RecordingCommandRunner {
+            fn run(&self, command: &mut Command) -> anyhow::Result<Output> {
+                let command_line = std::iter::once(command.get_program())
+                    .chain(command.get_args())
+                    .map(|part| part.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                self.commands.lock().unwrap().push(command_line.clone());
+
+                if self.failing_command.as_ref() == Some(&command_line) {
+                    bail!("Error while running `{command_line}`.");
+                }
+                NoopCommandRunner.run(command)
+            }
+        }
      }
 }
 
@@ -8,7 +8,9 @@ use tracing::{debug, error, info};
 use url::Url;
 
 use opendut_types::peer::PeerId;
+use opendut_types::plugin::PluginVerifyingKey;
 use opendut_types::util::net::AuthConfig;
+use opendut_types::vpn::VpnPeerConfiguration;
 
 use crate::common::settings;
 use crate::setup::constants;
@@ -18,11 +20,16 @@ pub struct WriteConfiguration {
     config_merge_suggestion_file: PathBuf,
     config_override: ConfigOverride,
     require_confirmation: bool,
+    /// Overwrite mismatched configurations without asking, as the previous credentials were revoked when re-enrolling.
+    overwrite_mismatched: bool,
 }
 pub struct ConfigOverride {
     pub peer_id: PeerId,
     pub carl_url: Url,
     pub auth_config: AuthConfig,
+    pub vpn: VpnPeerConfiguration,
+    /// Written, if CARL signs the plugins of its plugin registry.
+    pub plugin_verifying_key: Option<PluginVerifyingKey>,
 }
 
 impl WriteConfiguration {
@@ -87,6 +94,64 @@ impl WriteConfiguration {
                 }
             };
 
+            match &self.config_override.vpn {
+                VpnPeerConfiguration::Disabled => {}
+                VpnPeerConfiguration::Netbird { .. } => {
+                    //only written, if the kind was set before, to not modify configurations from before there were multiple kinds
+                    if new_settings.get("vpn").and_then(|vpn| vpn.get("kind")).is_some() {
+                        new_settings["vpn"]["kind"] = toml_edit::value("netbird");
+                    }
+                }
+                VpnPeerConfiguration::WireGuard { private_key, address, prefix_length, listen_port } => {
+                    if new_settings.get("vpn").is_none() {
+                        new_settings["vpn"] = toml_edit::table();
+                    }
+                    new_settings["vpn"]["kind"] = toml_edit::value("wireguard");
+
+                    if new_settings.get("vpn").and_then(|vpn| vpn.get("wireguard")).is_none() {
+                        new_settings["vpn"]["wireguard"] = toml_edit::table();
+                        for dotted_key in ["private", "prefix", "listen"] {
+                            new_settings["vpn"]["wireguard"][dotted_key] = toml_edit::table();
+                            new_settings["vpn"]["wireguard"][dotted_key].as_table_mut().unwrap().set_dotted(true);
+                        }
+                    }
+                    new_settings["vpn"]["wireguard"]["private"]["key"] = toml_edit::value(String::from(Clone::clone(private_key)));
+                    new_settings["vpn"]["wireguard"]["address"] = toml_edit::value(address.to_string());
+                    new_settings["vpn"]["wireguard"]["prefix"]["length"] = toml_edit::value(i64::from(*prefix_length));
+                    new_settings["vpn"]["wireguard"]["listen"]["port"] = toml_edit::value(i64::from(*listen_port));
+                }
+                VpnPeerConfiguration::Direct { network } => {
+                    if new_settings.get("vpn").is_none() {
+                        new_settings["vpn"] = toml_edit::table();
+                    }
+                    new_settings["vpn"]["kind"] = toml_edit::value("direct");
+
+                    if new_settings.get("vpn").and_then(|vpn| vpn.get("direct")).is_none() {
+                        new_settings["vpn"]["direct"] = toml_edit::table();
+                    }
+                    let network = network.map(|network| network.to_string()).unwrap_or_default();
+                    new_settings["vpn"]["direct"]["network"] = toml_edit::value(network);
+                }
+            }
+
+            if let Some(plugin_verifying_key) = &self.config_override.plugin_verifying_key {
+                if new_settings.get("plugins").is_none() {
+                    new_settings["plugins"] = toml_edit::table();
+                }
+                if new_settings.get("plugins").and_then(|plugins| plugins.get("registry")).is_none() {
+                    new_settings["plugins"]["registry"] = toml_edit::table();
+                }
+                if new_settings.get("plugins")
+                    .and_then(|plugins| plugins.get("registry"))
+                    .and_then(|registry| registry.get("verifying"))
+                    .is_none() {
+
+                    new_settings["plugins"]["registry"]["verifying"] = toml_edit::table();
+                    new_settings["plugins"]["registry"]["verifying"].as_table_mut().unwrap().set_dotted(true);
+                }
+                new_settings["plugins"]["registry"]["verifying"]["key"] = toml_edit::value(plugin_verifying_key.to_string());
+            }
+
             new_settings.to_string()
         };
 
@@ -99,7 +164,7 @@ impl WriteConfiguration {
             self.config_file_to_write_to.exists().not()
             || self.config_file_to_write_to.metadata()?.len() == 0;
 
-        let should_overwrite = if target_file_empty {
+        let should_overwrite = if target_file_empty || self.overwrite_mismatched {
             true
         } else if self.require_confirmation {
             crate::setup::user_confirmation_prompt("Settings file already exists, but contains mismatched configurations! Do you want to overwrite it?")?
@@ -126,12 +191,13 @@ impl WriteConfiguration {
     }
 }
 impl WriteConfiguration {
-    pub fn with_override(config_override: ConfigOverride, no_confirm: bool) -> Self {
+    pub fn with_override(config_override: ConfigOverride, no_confirm: bool, reenroll: bool) -> Self {
         Self {
             config_file_to_write_to: settings::default_config_file_path(),
             config_merge_suggestion_file: constants::default_config_merge_suggestion_file_path(),
             config_override,
             require_confirmation: if no_confirm { false } else { console::user_attended() },
+            overwrite_mismatched: reenroll,
         }
     }
 
@@ -181,7 +247,9 @@ mod tests {
     use predicates::prelude::predicate;
     use uuid::uuid;
     use googletest::prelude::*;
+    use std::net::IpAddr;
     use opendut_types::util::net::{ClientId, ClientSecret, OAuthScope};
+    use opendut_types::vpn::wireguard::WireGuardPrivateKey;
 
     use super::*;
 
@@ -363,6 +431,109 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_overwrite_a_mismatched_configuration_when_reenrolling_but_should_not_delete_existing_unknown_keys() -> anyhow::Result<()> {
+        let fixture = Fixture::new();
+        let mut write_configuration = create_write_configuration(&fixture, AuthEnabled::Yes);
+        write_configuration.overwrite_mismatched = true;
+
+        let config_file = ChildPath::new(write_configuration.config_file_to_write_to.clone());
+        let config_merge_suggestion_file = ChildPath::new(write_configuration.config_merge_suggestion_file.clone());
+
+        config_file.write_str(&format!(indoc!(r#"
+            [peer]
+            id = "{}"
+            [peer.unknown]
+            key = "value"
+
+            [network.oidc.client]
+            id = "{}"
+            secret = "PreviousClientSecret"
+        "#), fixture.peer_id, CLIENT_ID))?;
+
+        write_configuration.execute().await?;
+
+        assert!(predicate::path::missing().eval(&config_merge_suggestion_file));
+        let file_content = fs::read_to_string(&config_file)?;
+        assert!(predicate::str::contains("secret = \"ClientSecret\"").eval(&file_content));
+        assert!(predicate::str::contains("PreviousClientSecret").not().eval(&file_content));
+        assert!(predicate::str::contains("[peer.unknown]").eval(&file_content));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_write_the_wireguard_configuration() -> anyhow::Result<()> {
+        let fixture = Fixture::new();
+        let mut write_configuration = create_write_configuration(&fixture, AuthEnabled::No);
+        write_configuration.config_override.vpn = VpnPeerConfiguration::WireGuard {
+            private_key: WireGuardPrivateKey::from_bytes([7; 32]),
+            address: IpAddr::from([100, 64, 0, 12]),
+            prefix_length: 10,
+            listen_port: 51820,
+        };
+
+        let path = write_configuration.config_file_to_write_to.clone();
+
+        write_configuration.execute().await?;
+
+        let file_content = fs::read_to_string(&path)?;
+        let settings = toml_edit::DocumentMut::from_str(&file_content)?;
+
+        assert_that!(settings["vpn"]["kind"].as_str(), some(eq("wireguard")));
+        assert_that!(settings["vpn"]["wireguard"]["private"]["key"].as_str(), some(eq("BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc=")));
+        assert_that!(settings["vpn"]["wireguard"]["address"].as_str(), some(eq("100.64.0.12")));
+        assert_that!(settings["vpn"]["wireguard"]["prefix"]["length"].as_integer(), some(eq(10)));
+        assert_that!(settings["vpn"]["wireguard"]["listen"]["port"].as_integer(), some(eq(51820)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_write_the_plugin_verifying_key() -> anyhow::Result<()> {
+        let fixture = Fixture::new();
+        let mut write_configuration = create_write_configuration(&fixture, AuthEnabled::No);
+        write_configuration.config_override.plugin_verifying_key = Some(PluginVerifyingKey([7; PluginVerifyingKey::LENGTH]));
+
+        let path = write_configuration.config_file_to_write_to.clone();
+
+        write_configuration.execute().await?;
+
+        let file_content = fs::read_to_string(&path)?;
+        let settings = toml_edit::DocumentMut::from_str(&file_content)?;
+
+        assert_that!(settings["plugins"]["registry"]["verifying"]["key"].as_str(), some(eq("BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc=")));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_merge_the_plugin_verifying_key_into_an_existing_plugins_table() -> anyhow::Result<()> {
+        let fixture = Fixture::new();
+        let mut write_configuration = create_write_configuration(&fixture, AuthEnabled::No);
+        write_configuration.config_override.plugin_verifying_key = Some(PluginVerifyingKey([7; PluginVerifyingKey::LENGTH]));
+        write_configuration.overwrite_mismatched = true;
+
+        let config_file = ChildPath::new(write_configuration.config_file_to_write_to.clone());
+        config_file.write_str(&format!(indoc!(r#"
+            [peer]
+            id = "{}"
+
+            [plugins]
+            unknown = "value"
+        "#), fixture.peer_id))?;
+
+        write_configuration.execute().await?;
+
+        let file_content = fs::read_to_string(&config_file)?;
+        let settings = toml_edit::DocumentMut::from_str(&file_content)?;
+
+        assert_that!(settings["plugins"]["unknown"].as_str(), some(eq("value")));
+        assert_that!(settings["plugins"]["registry"]["verifying"]["key"].as_str(), some(eq("BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc=")));
+
+        Ok(())
+    }
+
     fn create_write_configuration(
         fixture: &Fixture,
         auth_enabled: AuthEnabled,
@@ -378,11 +549,15 @@ mod tests {
                     client_id: ClientId::from(CLIENT_ID),
                     scopes: vec![OAuthScope("test".to_string())],
                 },
+                vpn: VpnPeerConfiguration::Disabled,
+                plugin_verifying_key: None,
             },
             AuthEnabled::No => ConfigOverride {
                 peer_id: fixture.peer_id,
                 carl_url: Url::parse("https://example.com:1234").unwrap(),
                 auth_config: AuthConfig::Disabled,
+                vpn: VpnPeerConfiguration::Disabled,
+                plugin_verifying_key: None,
             },
         };
 
@@ -391,6 +566,7 @@ mod tests {
             config_merge_suggestion_file: fixture.config_merge_suggestion_file.to_path_buf(),
             config_override,
             require_confirmation: false, //always disabled in unit tests
+            overwrite_mismatched: false,
         }
     }
     enum AuthEnabled { Yes, No }
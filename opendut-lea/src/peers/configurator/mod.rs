@@ -2,7 +2,7 @@ use crate::app::{use_app_globals, ExpectGlobals};
 use crate::components::use_active_tab;
 use crate::components::{BasePageContainer, Breadcrumb, Initialized, UserInputError, UserInputValue};
 use crate::peers::configurator::components::Controls;
-use crate::peers::configurator::tabs::{DevicesTab, ExecutorTab, GeneralTab, NetworkTab, SetupTab, TabIdentifier};
+use crate::peers::configurator::tabs::{DevicesTab, ExecutorTab, GeneralTab, HistoryTab, NetworkTab, SetupTab, TabIdentifier};
 use crate::peers::configurator::types::{UserContainerEnv, UserDeviceConfiguration, UserNetworkInterface, UserPeerConfiguration, UserPeerExecutor, UserPeerExecutorKind, UserPeerNetwork};
 use crate::routing::{navigate_to, WellKnownRoutes};
 use crate::util;
@@ -58,6 +58,7 @@ pub fn PeerConfigurator() -> impl IntoView {
                 },
                 is_new: true,
                 executors: Vec::new(),
+                executables: Vec::new(),
             });
 
             let peer_configuration_resource = create_local_resource(|| {}, move |_| {
@@ -99,10 +100,14 @@ pub fn PeerConfigurator() -> impl IntoView {
                                 })
                                 .collect();
                             for executor in configuration.executors.executors {
+                                if matches!(executor.kind, ExecutorKind::Executable { .. }) {
+                                    user_configuration.executables.push(executor);
+                                    continue;
+                                }
                                 let ExecutorDescriptor { id, kind, results_url } = executor;
 
                                 let kind = match kind {
-                                    ExecutorKind::Executable => todo!(),
+                                    ExecutorKind::Executable { .. } => unreachable!("Executable executors are retained separately."),
                                     ExecutorKind::Container {
                                         engine,
                                         name,
@@ -281,6 +286,17 @@ pub fn PeerConfigurator() -> impl IntoView {
             classes.join(" ")
         };
 
+        let history_tab_classes = move || {
+            let mut classes = Vec::<&'static str>::new();
+            if TabIdentifier::History == active_tab.get() {
+                classes.push("is-active");
+            }
+            if setup_disabled.get() {
+                classes.push("is-hidden");
+            }
+            classes.join(" ")
+        };
+
         let breadcrumbs = {
             let active_tab = Clone::clone(&active_tab);
             MaybeSignal::derive(move || {
@@ -326,6 +342,9 @@ pub fn PeerConfigurator() -> impl IntoView {
                             <li class=setup_tab_classes>
                                 <a href={ TabIdentifier::Setup.as_str() }>Setup</a>
                             </li>
+                            <li class=history_tab_classes>
+                                <a href={ TabIdentifier::History.as_str() }>History</a>
+                            </li>
                         </ul>
                     </div>
                     <div class="container">
@@ -344,6 +363,9 @@ pub fn PeerConfigurator() -> impl IntoView {
                         <div class=("is-hidden", move || TabIdentifier::Setup != active_tab.get())>
                             <SetupTab peer_configuration=peer_configuration.read_only() />
                         </div>
+                        <div class=("is-hidden", move || TabIdentifier::History != active_tab.get())>
+                            <HistoryTab peer_configuration=peer_configuration.read_only() />
+                        </div>
                     </div>
                 </Show>
             </BasePageContainer>
@@ -0,0 +1,90 @@
+use chrono::{DateTime, Local};
+use leptos::{component, create_local_resource, create_rw_signal, view, CollectView, IntoView, RwSignal, SignalGet, SignalGetUntracked, SignalUpdate, Transition};
+
+use opendut_types::peer::executor::log::{ExecutorLogLine, ExecutorLogStream};
+
+use crate::app::{use_app_globals, ExpectGlobals};
+use crate::components::{ButtonColor, ButtonSize, ButtonState, FontAwesomeIcon, IconButton};
+use crate::peers::configurator::types::UserPeerExecutor;
+
+#[component]
+pub fn ExecutorLogs(executor: RwSignal<UserPeerExecutor>) -> impl IntoView {
+
+    let globals = use_app_globals();
+
+    let refresh_counter = create_rw_signal(0_u64);
+
+    let log = create_local_resource(move || refresh_counter.get(), move |_| {
+        let mut carl = globals.expect_client();
+        let executor_id = executor.get_untracked().id;
+        async move {
+            let mut log = carl.peers.get_executor_logs(executor_id, None, 0).await
+                .ok()?;
+
+            //CARL returns long logs in pages, so fetch until no further lines are returned
+            loop {
+                let page = carl.peers.get_executor_logs(executor_id, Some(log.id), log.next_sequence_number()).await
+                    .ok()?;
+                if page.lines.is_empty() {
+                    break Some(log);
+                }
+                log.append(page.lines);
+            }
+        }
+    });
+
+    let log_content = move || {
+        match log.get().flatten() {
+            Some(log) => {
+                let run = format!("Run {}", log.id);
+                let lines = log.lines.into_iter()
+                    .map(|line| view! { <LogLine line /> })
+                    .collect_view();
+                view! {
+                    <p class="is-size-7 has-text-grey pb-1">{ run }</p>
+                    <pre class="is-size-7" style="max-height: 30em; overflow-y: auto;">{ lines }</pre>
+                }.into_view()
+            }
+            None => view! {
+                <p class="has-text-grey">"No output has been received for this executor."</p>
+            }.into_view(),
+        }
+    };
+
+    view! {
+        <div class="field">
+            <div class="is-flex is-justify-content-space-between is-align-items-center">
+                <label class="label">"Output of latest run"</label>
+                <IconButton
+                    icon=FontAwesomeIcon::ArrowsRotate
+                    color=ButtonColor::Light
+                    size=ButtonSize::Small
+                    state=ButtonState::Enabled
+                    label="Refresh Output"
+                    on_action=move || refresh_counter.update(|counter| *counter += 1)
+                />
+            </div>
+            <Transition
+                fallback=move || view! { <p>"Loading..."</p> }
+            >
+                { log_content }
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn LogLine(line: ExecutorLogLine) -> impl IntoView {
+    let timestamp = DateTime::<Local>::from(line.timestamp).format("%Y-%m-%d %H:%M:%S%.3f").to_string();
+    let text_class = match line.stream {
+        ExecutorLogStream::Stdout => "",
+        ExecutorLogStream::Stderr => "has-text-danger",
+    };
+
+    view! {
+        <div>
+            <span class="has-text-grey pr-2">{ timestamp }</span>
+            <span class=text_class>{ line.text }</span>
+        </div>
+    }
+}
@@ -3,6 +3,8 @@ use opendut_types::peer::executor::{container::{ContainerCommand, ContainerComma
 use strum::IntoEnumIterator;
 
 use crate::components::{ButtonColor, ButtonSize, ButtonState, ConfirmationButton, FontAwesomeIcon, IconButton, Toggled, UserInput, UserInputValue, VectorUserInput};
+use crate::peers::configurator::tabs::executor::executor_logs::ExecutorLogs;
+use crate::peers::configurator::tabs::executor::executor_test_runs::ExecutorTestRuns;
 use crate::peers::configurator::types::{EMPTY_CONTAINER_IMAGE_ERROR_MESSAGE, UserContainerEnv, UserPeerExecutor, UserPeerExecutorKind};
 use crate::util::NON_BREAKING_SPACE;
 
@@ -33,6 +35,8 @@ where
                     <ExecutorContainerCommandInput executor />
                     <ExecutorContainerArgsInput executor />
                     <ExecutorContainerResultsUrlInput executor />
+                    <ExecutorTestRuns executor />
+                    <ExecutorLogs executor />
                 </div>
             </div>
         </div>
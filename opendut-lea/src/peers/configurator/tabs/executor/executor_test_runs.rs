@@ -0,0 +1,111 @@
+use chrono::{DateTime, Local};
+use leptos::{component, create_local_resource, create_rw_signal, view, CollectView, IntoView, RwSignal, SignalGet, SignalGetUntracked, SignalUpdate, Transition};
+
+use opendut_types::peer::executor::test_run::{TestRun, TestRunStatus};
+
+use crate::app::{use_app_globals, ExpectGlobals};
+use crate::components::{ButtonColor, ButtonSize, ButtonState, FontAwesomeIcon, IconButton};
+use crate::peers::configurator::types::UserPeerExecutor;
+
+#[component]
+pub fn ExecutorTestRuns(executor: RwSignal<UserPeerExecutor>) -> impl IntoView {
+
+    let globals = use_app_globals();
+
+    let refresh_counter = create_rw_signal(0_u64);
+
+    let test_runs = create_local_resource(move || refresh_counter.get(), move |_| {
+        let mut carl = globals.expect_client();
+        let executor_id = executor.get_untracked().id;
+        async move {
+            carl.peers.list_test_runs(None, Some(executor_id)).await
+                .unwrap_or_default()
+        }
+    });
+
+    let test_run_rows = move || {
+        test_runs.get().unwrap_or_default()
+            .into_iter()
+            .map(|test_run| view! { <TestRunRow test_run /> })
+            .collect_view()
+    };
+
+    view! {
+        <div class="field">
+            <div class="is-flex is-justify-content-space-between is-align-items-center">
+                <label class="label">"Runs"</label>
+                <IconButton
+                    icon=FontAwesomeIcon::ArrowsRotate
+                    color=ButtonColor::Light
+                    size=ButtonSize::Small
+                    state=ButtonState::Enabled
+                    label="Refresh Runs"
+                    on_action=move || refresh_counter.update(|counter| *counter += 1)
+                />
+            </div>
+            <Transition
+                fallback=move || view! { <p>"Loading..."</p> }
+            >
+                <table class="table is-hoverable is-fullwidth is-narrow">
+                    <thead>
+                        <tr>
+                            <th>"Status"</th>
+                            <th>"Started"</th>
+                            <th>"Finished"</th>
+                            <th>"Exit Code"</th>
+                            <th>"Triggered By"</th>
+                            <th>"Results"</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { test_run_rows }
+                    </tbody>
+                </table>
+            </Transition>
+        </div>
+    }
+}
+
+#[component]
+fn TestRunRow(test_run: TestRun) -> impl IntoView {
+
+    fn format_time(time: std::time::SystemTime) -> String {
+        DateTime::<Local>::from(time).format("%Y-%m-%d %H:%M:%S").to_string()
+    }
+
+    let status_class = match test_run.status {
+        TestRunStatus::Pending | TestRunStatus::Aborted => "has-text-grey",
+        TestRunStatus::Running => "has-text-info",
+        TestRunStatus::Succeeded => "has-text-success",
+        TestRunStatus::Failed => "has-text-danger",
+    };
+
+    let started_at = test_run.started_at
+        .map(format_time)
+        .unwrap_or_else(|| String::from("-"));
+    let finished_at = test_run.finished_at
+        .map(format_time)
+        .unwrap_or_else(|| String::from("-"));
+    let exit_code = test_run.exit_code
+        .map(|exit_code| exit_code.to_string())
+        .unwrap_or_else(|| String::from("-"));
+    let triggered_by = test_run.triggered_by
+        .unwrap_or_else(|| String::from("-"));
+    let results = match test_run.results_location {
+        Some(results_location) => view! {
+            <a href=results_location.to_string() target="_blank">"Download"</a>
+        }.into_view(),
+        None => view! { "-" }.into_view(),
+    };
+
+    view! {
+        <tr title=format!("Run {}", test_run.id)>
+            <td class=status_class>{ test_run.status.to_string() }</td>
+            <td>{ started_at }</td>
+            <td>{ finished_at }</td>
+            <td>{ exit_code }</td>
+            <td>{ triggered_by }</td>
+            <td>{ results }</td>
+        </tr>
+    }
+}
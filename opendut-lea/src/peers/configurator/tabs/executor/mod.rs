@@ -5,7 +5,9 @@ use crate::components::UserInputValue;
 use crate::peers::configurator::tabs::executor::executor_panel::ExecutorPanel;
 use crate::peers::configurator::types::{EMPTY_CONTAINER_IMAGE_ERROR_MESSAGE, UserPeerConfiguration, UserPeerExecutor, UserPeerExecutorKind};
 
+mod executor_logs;
 mod executor_panel;
+mod executor_test_runs;
 
 #[component]
 pub fn ExecutorTab(peer_configuration: RwSignal<UserPeerConfiguration>) -> impl IntoView {
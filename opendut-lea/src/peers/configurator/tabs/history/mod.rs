@@ -0,0 +1,143 @@
+use chrono::{DateTime, Local};
+use leptos::{component, create_local_resource, view, For, IntoView, ReadSignal, SignalGet, SignalGetUntracked, Transition};
+
+use opendut_types::peer::history::{PeerConnectionSession, PeerEnrollment, PeerHistory};
+
+use crate::app::{use_app_globals, ExpectGlobals};
+use crate::peers::configurator::types::UserPeerConfiguration;
+
+#[component]
+pub fn HistoryTab(peer_configuration: ReadSignal<UserPeerConfiguration>) -> impl IntoView {
+
+    let globals = use_app_globals();
+
+    let history = create_local_resource(move || peer_configuration.get().is_new, move |is_new| {
+        let mut carl = globals.expect_client();
+        let peer_id = peer_configuration.get_untracked().id;
+        async move {
+            if is_new {
+                Ok(PeerHistory::default())
+            } else {
+                carl.peers.get_peer_history(peer_id).await
+                    .map(|mut history| {
+                        history.sessions.reverse(); //show newest session first
+                        history.enrollments.reverse();
+                        history
+                    })
+                    .map_err(|cause| format!("Could not load the history of the peer: {cause}"))
+            }
+        }
+    });
+
+    let loaded_history = move || {
+        history.get()
+            .and_then(Result::ok)
+            .unwrap_or_default()
+    };
+
+    let error_notification = move || {
+        history.get()
+            .and_then(Result::err)
+            .map(|error| view! { <div class="notification is-danger">{ error }</div> })
+    };
+
+    view! {
+        <Transition
+            fallback=move || view! { <p>"Loading..."</p> }
+        >
+            { error_notification }
+            <table class="table is-hoverable is-fullwidth">
+                <thead>
+                    <tr>
+                        <th class="is-narrow">"State"</th>
+                        <th>"Connected"</th>
+                        <th>"Disconnected"</th>
+                        <th>"Duration"</th>
+                        <th>"Remote Host"</th>
+                        <th>"EDGAR Version"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    <For
+                        each=move || loaded_history().sessions
+                        key=|session| (session.connected_at, session.disconnected_at)
+                        children=|session| view! { <SessionRow session /> }
+                    />
+                </tbody>
+            </table>
+            <h5 class="title is-5">"Enrollments"</h5>
+            <table class="table is-hoverable is-fullwidth">
+                <thead>
+                    <tr>
+                        <th>"Issued"</th>
+                        <th>"Kind"</th>
+                        <th>"Issued By"</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    <For
+                        each=move || loaded_history().enrollments
+                        key=|enrollment| enrollment.issued_at
+                        children=|enrollment| view! { <EnrollmentRow enrollment /> }
+                    />
+                </tbody>
+            </table>
+        </Transition>
+    }
+}
+
+fn format_time(time: std::time::SystemTime) -> String {
+    DateTime::<Local>::from(time).format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+#[component]
+fn SessionRow(session: PeerConnectionSession) -> impl IntoView {
+
+    let (state_icon, state_text) = if session.is_connected() {
+        ("fa-solid fa-plug-circle-check has-text-success", "Connected")
+    } else {
+        ("fa-solid fa-plug-circle-xmark has-text-grey", "Disconnected")
+    };
+
+    let connected_at = format_time(session.connected_at);
+    let disconnected_at = session.disconnected_at
+        .map(format_time)
+        .unwrap_or_else(|| String::from("-"));
+    let duration = session.duration()
+        .map(|duration| {
+            let seconds = duration.as_secs();
+            format!("{}h {}m {}s", seconds / 3600, seconds % 3600 / 60, seconds % 60)
+        })
+        .unwrap_or_else(|| String::from("-"));
+    let edgar_version = session.edgar_version
+        .unwrap_or_else(|| String::from("unknown"));
+
+    view! {
+        <tr>
+            <td class="is-vcentered" title=state_text>
+                <span class="icon"><i class=state_icon></i></span>
+            </td>
+            <td class="is-vcentered">{ connected_at }</td>
+            <td class="is-vcentered">{ disconnected_at }</td>
+            <td class="is-vcentered">{ duration }</td>
+            <td class="is-vcentered">{ session.remote_host.to_string() }</td>
+            <td class="is-vcentered">{ edgar_version }</td>
+        </tr>
+    }
+}
+
+#[component]
+fn EnrollmentRow(enrollment: PeerEnrollment) -> impl IntoView {
+
+    let kind = if enrollment.reenrollment { "Re-enrollment" } else { "Enrollment" };
+    let issued_by = enrollment.issued_by
+        .unwrap_or_else(|| String::from("-"));
+
+    view! {
+        <tr>
+            <td class="is-vcentered">{ format_time(enrollment.issued_at) }</td>
+            <td class="is-vcentered">{ kind }</td>
+            <td class="is-vcentered">{ issued_by }</td>
+        </tr>
+    }
+}
@@ -2,12 +2,14 @@ pub use devices::DevicesTab;
 pub use general::GeneralTab;
 pub use network::NetworkTab;
 pub use executor::ExecutorTab;
+pub use history::HistoryTab;
 pub use setup::SetupTab;
 
 mod devices;
 mod general;
 mod network;
 mod executor;
+mod history;
 mod setup;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -18,6 +20,7 @@ pub enum TabIdentifier {
     Network,
     Executor,
     Setup,
+    History,
 }
 
 impl TabIdentifier {
@@ -26,6 +29,7 @@ impl TabIdentifier {
     const NETWORK_STR: &'static str = "network";
     const EXECUTOR_STR: &'static str = "executor";
     const SETUP_STR: &'static str = "setup";
+    const HISTORY_STR: &'static str = "history";
 
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -34,6 +38,7 @@ impl TabIdentifier {
             TabIdentifier::Network => TabIdentifier::NETWORK_STR,
             TabIdentifier::Executor => TabIdentifier::EXECUTOR_STR,
             TabIdentifier::Setup => TabIdentifier::SETUP_STR,
+            TabIdentifier::History => TabIdentifier::HISTORY_STR,
         }
     }
 }
@@ -48,6 +53,7 @@ impl TryFrom<&str> for TabIdentifier {
             TabIdentifier::NETWORK_STR => Ok(TabIdentifier::Network),
             TabIdentifier::EXECUTOR_STR => Ok(TabIdentifier::Executor),
             TabIdentifier::SETUP_STR => Ok(TabIdentifier::Setup),
+            TabIdentifier::HISTORY_STR => Ok(TabIdentifier::History),
             _ => Err(InvalidTabIdentifier {
                 value: String::from(value),
             }),
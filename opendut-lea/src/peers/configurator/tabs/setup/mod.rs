@@ -26,7 +26,7 @@ pub fn SetupTab(peer_configuration: ReadSignal<UserPeerConfiguration>) -> impl I
             };
             if let Some(peer_id) = peer_id {
                 let mut carl = globals.expect_client();
-                let setup = carl.peers.create_peer_setup(peer_id, user_id).await
+                let setup = carl.peers.create_peer_setup(peer_id, user_id, false).await
                     .expect("Failed to request the setup string.");
                 let setup_string = setup.encode()
                     .expect("PeerSetup should be encodable into a setup-string");
@@ -46,6 +46,8 @@ pub struct UserPeerConfiguration {
     pub devices: Vec<RwSignal<UserDeviceConfiguration>>,
     pub network: UserPeerNetwork,
     pub executors: Vec<RwSignal<UserPeerExecutor>>,
+    /// Executable executors cannot be edited in LEA yet. They are retained unchanged, when the peer is stored.
+    pub executables: Vec<ExecutorDescriptor>,
     pub is_new: bool,
 }
 
@@ -157,6 +159,7 @@ impl TryFrom<UserPeerConfiguration> for PeerDescriptor {
                 ExecutorDescriptor::try_from(executor)
                     .map_err(|_|  PeerMisconfigurationError::InvalidPeerExecutor)
             })
+            .chain(configuration.executables.into_iter().map(Ok))
             .collect::<Result<Vec<_>, _>>()?;
         Ok(PeerDescriptor {
             id: configuration.id,
@@ -1,18 +1,21 @@
 use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
 use crate::cluster::ClusterId;
 use crate::peer::PeerId;
 use crate::util::net::NetworkInterfaceDescriptor;
 use crate::util::Port;
 
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ClusterAssignment {
     pub id: ClusterId,
     pub leader: PeerId,
     pub assignments: Vec<PeerClusterAssignment>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PeerClusterAssignment {
     pub peer_id: PeerId,
     pub vpn_address: IpAddr,
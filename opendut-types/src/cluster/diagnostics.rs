@@ -0,0 +1,200 @@
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::cluster::ClusterId;
+use crate::peer::PeerId;
+
+/// Identifies a request for network diagnostics sent to a peer, so that its report can be associated with the request.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NetworkDiagnosticsId(pub Uuid);
+
+impl NetworkDiagnosticsId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for NetworkDiagnosticsId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for NetworkDiagnosticsId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Determines which tests are run between each pair of peers in a cluster.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NetworkDiagnosticsOptions {
+    /// Number of ICMP echo requests sent to measure the latency. Latency is not measured, if zero.
+    pub ping_count: u32,
+    pub ping_timeout: Duration,
+    /// Duration of each throughput test. Throughput is not measured, if zero.
+    pub throughput_duration: Duration,
+    pub target_bandwidth_kbit_per_second: u64,
+    pub path_mtu_discovery: bool,
+}
+
+impl NetworkDiagnosticsOptions {
+    pub const MAX_PING_COUNT: u32 = 100;
+    pub const MAX_PING_TIMEOUT: Duration = Duration::from_secs(10);
+    pub const MAX_THROUGHPUT_DURATION: Duration = Duration::from_secs(60);
+    pub const MAX_TARGET_BANDWIDTH_KBIT_PER_SECOND: u64 = 10_000_000;
+
+    /// Checks that the options are within limits, so that a request cannot keep peers busy for an unbounded time.
+    pub fn validate(&self) -> Result<(), IllegalNetworkDiagnosticsOptions> {
+        if self.ping_count > Self::MAX_PING_COUNT {
+            return Err(IllegalNetworkDiagnosticsOptions { details: format!("Ping count must be at most {}, but was {}.", Self::MAX_PING_COUNT, self.ping_count) });
+        }
+        if self.ping_timeout > Self::MAX_PING_TIMEOUT {
+            return Err(IllegalNetworkDiagnosticsOptions { details: format!("Ping timeout must be at most {:?}, but was {:?}.", Self::MAX_PING_TIMEOUT, self.ping_timeout) });
+        }
+        if self.throughput_duration > Self::MAX_THROUGHPUT_DURATION {
+            return Err(IllegalNetworkDiagnosticsOptions { details: format!("Throughput duration must be at most {:?}, but was {:?}.", Self::MAX_THROUGHPUT_DURATION, self.throughput_duration) });
+        }
+        if self.target_bandwidth_kbit_per_second > Self::MAX_TARGET_BANDWIDTH_KBIT_PER_SECOND {
+            return Err(IllegalNetworkDiagnosticsOptions { details: format!("Target bandwidth must be at most {} kbit/s, but was {} kbit/s.", Self::MAX_TARGET_BANDWIDTH_KBIT_PER_SECOND, self.target_bandwidth_kbit_per_second) });
+        }
+        Ok(())
+    }
+
+    /// Upper bound for the time a peer needs to test its connection to one other peer.
+    pub fn max_duration_per_target(&self) -> Duration {
+        const THROUGHPUT_TESTS: u32 = 3;
+        const THROUGHPUT_OVERHEAD: Duration = Duration::from_secs(2);
+        const PATH_MTU_PROBES: u32 = 12;
+
+        let latency = self.ping_timeout.saturating_mul(self.ping_count);
+        let throughput = if self.throughput_duration.is_zero() {
+            Duration::ZERO
+        } else {
+            self.throughput_duration.saturating_add(THROUGHPUT_OVERHEAD).saturating_mul(THROUGHPUT_TESTS)
+        };
+        let path_mtu = if self.path_mtu_discovery {
+            self.ping_timeout.saturating_mul(PATH_MTU_PROBES)
+        } else {
+            Duration::ZERO
+        };
+        latency.saturating_add(throughput).saturating_add(path_mtu)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug, Eq, PartialEq)]
+#[error("Illegal network diagnostics options. {details}")]
+pub struct IllegalNetworkDiagnosticsOptions {
+    details: String,
+}
+
+impl Default for NetworkDiagnosticsOptions {
+    fn default() -> Self {
+        Self {
+            ping_count: 5,
+            ping_timeout: Duration::from_secs(1),
+            throughput_duration: Duration::from_secs(5),
+            target_bandwidth_kbit_per_second: 100_000,
+            path_mtu_discovery: true,
+        }
+    }
+}
+
+/// Results of the tests from one peer of a cluster to another.
+/// Tests which were not requested or which failed have no result. The failures are listed in `errors`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeerLinkDiagnostics {
+    pub source: PeerId,
+    pub target: PeerId,
+    pub latency: Option<LatencyDiagnostics>,
+    pub throughput: Option<ThroughputDiagnostics>,
+    pub path_mtu: Option<u16>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LatencyDiagnostics {
+    pub sent: u32,
+    pub received: u32,
+    pub min: Duration,
+    pub avg: Duration,
+    pub max: Duration,
+}
+
+impl LatencyDiagnostics {
+    pub fn packet_loss_percent(&self) -> f64 {
+        if self.sent == 0 {
+            return 0.0;
+        }
+        f64::from(self.sent.saturating_sub(self.received)) / f64::from(self.sent) * 100.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ThroughputDiagnostics {
+    pub tcp_send_megabits_per_second: f64,
+    pub tcp_receive_megabits_per_second: f64,
+    pub udp_send_megabits_per_second: f64,
+    pub udp_jitter: Duration,
+    pub udp_packet_loss_percent: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClusterNetworkDiagnostics {
+    pub cluster_id: ClusterId,
+    pub links: Vec<PeerLinkDiagnostics>,
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_estimate_the_max_duration_per_target() {
+        let options = NetworkDiagnosticsOptions {
+            ping_count: 5,
+            ping_timeout: Duration::from_secs(1),
+            throughput_duration: Duration::from_secs(5),
+            target_bandwidth_kbit_per_second: 100_000,
+            path_mtu_discovery: true,
+        };
+        assert_that!(options.max_duration_per_target(), eq(Duration::from_secs(5 + 3 * 7 + 12)));
+
+        let options = NetworkDiagnosticsOptions {
+            throughput_duration: Duration::ZERO,
+            path_mtu_discovery: false,
+            ..options
+        };
+        assert_that!(options.max_duration_per_target(), eq(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn should_reject_options_exceeding_the_limits() {
+        assert_that!(NetworkDiagnosticsOptions::default().validate(), ok(anything()));
+
+        let options = NetworkDiagnosticsOptions { ping_count: u32::MAX, ping_timeout: Duration::MAX, ..NetworkDiagnosticsOptions::default() };
+        assert_that!(options.validate(), err(anything()));
+        assert_that!(options.max_duration_per_target(), eq(Duration::MAX));
+
+        let options = NetworkDiagnosticsOptions { target_bandwidth_kbit_per_second: u64::MAX, ..NetworkDiagnosticsOptions::default() };
+        assert_that!(options.validate(), err(anything()));
+    }
+
+    #[test]
+    fn should_calculate_packet_loss_of_latency_test() {
+        let latency = LatencyDiagnostics {
+            sent: 4,
+            received: 3,
+            min: Duration::from_millis(1),
+            avg: Duration::from_millis(2),
+            max: Duration::from_millis(3),
+        };
+        assert_that!(latency.packet_loss_percent(), eq(25.0));
+    }
+}
@@ -11,6 +11,7 @@ use crate::peer::PeerId;
 use crate::topology::DeviceId;
 
 mod assignment;
+pub mod diagnostics;
 pub mod state;
 
 
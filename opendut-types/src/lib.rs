@@ -2,6 +2,7 @@ use uuid::{Uuid, uuid};
 
 pub mod cluster;
 pub mod peer;
+pub mod plugin;
 pub mod proto;
 pub mod topology;
 pub mod vpn;
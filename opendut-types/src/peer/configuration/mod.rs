@@ -1,6 +1,7 @@
 use crate::cluster::ClusterAssignment;
 use crate::peer::ethernet::EthernetBridge;
 use crate::peer::executor::ExecutorDescriptor;
+use crate::vpn::wireguard::WireGuardPeer;
 
 mod parameter;
 pub use parameter::*;
@@ -15,6 +16,7 @@ pub struct OldPeerConfiguration {
 pub struct PeerConfiguration {
     pub executors: Vec<Parameter<ExecutorDescriptor>>,
     pub ethernet_bridges: Vec<Parameter<EthernetBridge>>,
+    pub wireguard_peers: Vec<Parameter<WireGuardPeer>>,
     //TODO migrate more parameters
 }
 impl PeerConfiguration {
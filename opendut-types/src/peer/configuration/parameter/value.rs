@@ -2,6 +2,7 @@ use crate::peer::configuration::parameter::{Parameter, ParameterId};
 use crate::peer::configuration::PeerConfiguration;
 use crate::peer::ethernet::EthernetBridge;
 use crate::peer::executor::{ExecutorDescriptor, ExecutorKind};
+use crate::vpn::wireguard::WireGuardPeer;
 use crate::OPENDUT_UUID_NAMESPACE;
 use std::any::Any;
 use std::hash::{DefaultHasher, Hash, Hasher};
@@ -42,7 +43,7 @@ impl ParameterValue for ExecutorDescriptor {
     fn parameter_identifier(&self) -> ParameterId {
         let mut hasher = DefaultHasher::new(); //ID not stable across Rust releases
         match &self.kind {
-            ExecutorKind::Executable => self.kind.hash(&mut hasher),
+            ExecutorKind::Executable { path, .. } => path.hash(&mut hasher),
             ExecutorKind::Container { name, .. } => name.hash(&mut hasher),
         }
         self.results_url.hash(&mut hasher);
@@ -70,23 +71,45 @@ impl ParameterValue for EthernetBridge {
     }
 }
 
+impl ParameterValue for WireGuardPeer {
+    fn parameter_identifier(&self) -> ParameterId {
+        let mut hasher = DefaultHasher::new(); //ID not stable across Rust releases
+        self.public_key.hash(&mut hasher);
+        let id = hasher.finish();
+
+        let id = Uuid::new_v5(&OPENDUT_UUID_NAMESPACE, &id.to_le_bytes());
+        ParameterId(id)
+    }
+    fn peer_configuration_field(peer_configuration: &mut PeerConfiguration) -> &mut Vec<Parameter<Self>> {
+        &mut peer_configuration.wireguard_peers
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::peer::configuration::ParameterTarget;
     use crate::peer::executor::ExecutorId;
+    use crate::peer::executor::executable::ExecutablePath;
 
     #[test]
     fn insert_value_in_peer_configuration() {
         let mut peer_configuration = PeerConfiguration {
             executors: vec![],
             ethernet_bridges: vec![],
+            wireguard_peers: vec![],
         };
 
         let value = ExecutorDescriptor {
             id: ExecutorId::random(),
-            kind: ExecutorKind::Executable,
+            kind: ExecutorKind::Executable {
+                path: ExecutablePath::try_from("/opt/tests/run.sh").unwrap(),
+                args: vec![],
+                envs: vec![],
+                working_dir: None,
+                timeout: None,
+            },
             results_url: None
         };
         let target = ParameterTarget::Present;
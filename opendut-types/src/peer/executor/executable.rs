@@ -0,0 +1,168 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+
+/// Path of the program to run. Relative paths are looked up in the `PATH` of EDGAR.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ExecutablePath(String);
+
+impl ExecutablePath {
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalExecutablePath {
+    #[error("Executable path must not be empty.")]
+    Empty,
+}
+
+impl TryFrom<String> for ExecutablePath {
+    type Error = IllegalExecutablePath;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            Err(IllegalExecutablePath::Empty)
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for ExecutablePath {
+    type Error = IllegalExecutablePath;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ExecutablePath::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ExecutablePath {
+    type Err = IllegalExecutablePath;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ExecutablePath::try_from(value)
+    }
+}
+
+impl From<ExecutablePath> for String {
+    fn from(value: ExecutablePath) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ExecutablePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Directory, in which the program is run. Must be an absolute path.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ExecutableWorkingDirectory(String);
+
+impl ExecutableWorkingDirectory {
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalExecutableWorkingDirectory {
+    #[error("Working directory '{value}' is not an absolute path.")]
+    NotAbsolute { value: String },
+}
+
+impl TryFrom<String> for ExecutableWorkingDirectory {
+    type Error = IllegalExecutableWorkingDirectory;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.starts_with('/') {
+            Ok(Self(value))
+        } else {
+            Err(IllegalExecutableWorkingDirectory::NotAbsolute { value })
+        }
+    }
+}
+
+impl TryFrom<&str> for ExecutableWorkingDirectory {
+    type Error = IllegalExecutableWorkingDirectory;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ExecutableWorkingDirectory::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ExecutableWorkingDirectory {
+    type Err = IllegalExecutableWorkingDirectory;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ExecutableWorkingDirectory::try_from(value)
+    }
+}
+
+impl From<ExecutableWorkingDirectory> for String {
+    fn from(value: ExecutableWorkingDirectory) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ExecutableWorkingDirectory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Maximum duration of a run, after which the program is killed. Specified in seconds.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u64", into = "u64")]
+pub struct ExecutableTimeout(Duration);
+
+impl ExecutableTimeout {
+    pub fn value(&self) -> Duration {
+        self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalExecutableTimeout {
+    #[error("Executable timeout must be at least one second.")]
+    Zero,
+}
+
+impl TryFrom<u64> for ExecutableTimeout {
+    type Error = IllegalExecutableTimeout;
+
+    fn try_from(seconds: u64) -> Result<Self, Self::Error> {
+        if seconds == 0 {
+            Err(IllegalExecutableTimeout::Zero)
+        } else {
+            Ok(Self(Duration::from_secs(seconds)))
+        }
+    }
+}
+
+impl From<ExecutableTimeout> for u64 {
+    fn from(value: ExecutableTimeout) -> Self {
+        value.0.as_secs()
+    }
+}
+
+impl FromStr for ExecutableTimeout {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let seconds = u64::from_str(value)
+            .map_err(|cause| format!("Executable timeout '{value}' is not a number of seconds: {cause}"))?;
+        ExecutableTimeout::try_from(seconds)
+            .map_err(|cause| cause.to_string())
+    }
+}
+
+impl fmt::Display for ExecutableTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}s", self.0.as_secs())
+    }
+}
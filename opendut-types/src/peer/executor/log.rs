@@ -0,0 +1,231 @@
+use std::fmt;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::peer::executor::ExecutorId;
+use crate::peer::PeerId;
+
+/// Identifies a single run of an executor, i.e. from starting its container until it exits.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ExecutorRunId(pub Uuid);
+
+impl ExecutorRunId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for ExecutorRunId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for ExecutorRunId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum ExecutorLogStream {
+    Stdout,
+    Stderr,
+}
+
+impl fmt::Display for ExecutorLogStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutorLogStream::Stdout => write!(f, "stdout"),
+            ExecutorLogStream::Stderr => write!(f, "stderr"),
+        }
+    }
+}
+
+/// A line of output of an executor.
+/// The sequence number is assigned by EDGAR, counting up from zero for each run.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExecutorLogLine {
+    pub sequence_number: u64,
+    pub timestamp: SystemTime,
+    pub stream: ExecutorLogStream,
+    pub text: String,
+}
+
+/// Output of one run of an executor, as received by CARL.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExecutorRunLog {
+    pub id: ExecutorRunId,
+    pub executor_id: ExecutorId,
+    pub peer_id: PeerId,
+    /// Ordered by sequence number, without duplicates.
+    pub lines: Vec<ExecutorLogLine>,
+}
+
+impl ExecutorRunLog {
+    pub fn new(id: ExecutorRunId, executor_id: ExecutorId, peer_id: PeerId) -> Self {
+        Self { id, executor_id, peer_id, lines: Vec::new() }
+    }
+
+    /// Time of the first line of output.
+    pub fn started_at(&self) -> Option<SystemTime> {
+        self.lines.first().map(|line| line.timestamp)
+    }
+
+    /// Sequence number of the line expected next.
+    pub fn next_sequence_number(&self) -> u64 {
+        self.lines.last()
+            .map(|line| line.sequence_number + 1)
+            .unwrap_or_default()
+    }
+
+    /// Appends the given lines, skipping lines which have been appended before,
+    /// e.g. because EDGAR sent them again after a lost connection.
+    pub fn append(&mut self, lines: impl IntoIterator<Item=ExecutorLogLine>) {
+        let mut lines = lines.into_iter().collect::<Vec<_>>();
+        lines.sort_by_key(|line| line.sequence_number);
+
+        for line in lines {
+            if line.sequence_number >= self.next_sequence_number() {
+                self.lines.push(line);
+            }
+        }
+    }
+
+    /// Lines with a sequence number greater than or equal to the given one.
+    pub fn lines_from(&self, sequence_number: u64) -> &[ExecutorLogLine] {
+        let start = self.lines.partition_point(|line| line.sequence_number < sequence_number);
+        &self.lines[start..]
+    }
+}
+
+/// State of the output of one run of an executor, as stored by CARL.
+/// The lines themselves are stored in [`ExecutorLogChunk`]s, so they can be appended and queried without loading the whole output.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExecutorRunLogSummary {
+    pub id: ExecutorRunId,
+    pub executor_id: ExecutorId,
+    pub peer_id: PeerId,
+    /// Time of the first line of output.
+    pub started_at: Option<SystemTime>,
+    /// Sequence number of the line expected next.
+    pub next_sequence_number: u64,
+    pub line_count: u64,
+}
+
+impl ExecutorRunLogSummary {
+    pub fn new(id: ExecutorRunId, executor_id: ExecutorId, peer_id: PeerId) -> Self {
+        Self { id, executor_id, peer_id, started_at: None, next_sequence_number: 0, line_count: 0 }
+    }
+}
+
+/// Identifies the chunk of the output of a run, which contains the lines with a sequence number
+/// from `index * LINES_PER_CHUNK` up to, but excluding, `(index + 1) * LINES_PER_CHUNK`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ExecutorLogChunkId {
+    pub run_id: ExecutorRunId,
+    pub index: u64,
+}
+
+impl ExecutorLogChunkId {
+    pub const LINES_PER_CHUNK: u64 = 1000;
+
+    pub fn containing(run_id: ExecutorRunId, sequence_number: u64) -> Self {
+        Self { run_id, index: sequence_number / Self::LINES_PER_CHUNK }
+    }
+
+    pub fn first_sequence_number(&self) -> u64 {
+        self.index.saturating_mul(Self::LINES_PER_CHUNK)
+    }
+
+    /// Deterministic UUID of the chunk, derived from the run and the index.
+    pub fn uuid(&self) -> Uuid {
+        Uuid::new_v5(&self.run_id.0, &self.index.to_be_bytes())
+    }
+}
+
+impl fmt::Display for ExecutorLogChunkId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.run_id, self.index)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExecutorLogChunk {
+    pub id: ExecutorLogChunkId,
+    /// Ordered by sequence number, without duplicates.
+    pub lines: Vec<ExecutorLogLine>,
+}
+
+impl ExecutorLogChunk {
+    pub fn new(id: ExecutorLogChunkId) -> Self {
+        Self { id, lines: Vec::new() }
+    }
+
+    /// Inserts the given lines at their position, skipping lines which have been inserted before
+    /// or which do not belong into this chunk.
+    pub fn insert(&mut self, lines: impl IntoIterator<Item=ExecutorLogLine>) {
+        for line in lines {
+            if ExecutorLogChunkId::containing(self.id.run_id, line.sequence_number) != self.id {
+                continue;
+            }
+            if let Err(position) = self.lines.binary_search_by_key(&line.sequence_number, |line| line.sequence_number) {
+                self.lines.insert(position, line);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn line(sequence_number: u64) -> ExecutorLogLine {
+        ExecutorLogLine {
+            sequence_number,
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(sequence_number),
+            stream: ExecutorLogStream::Stdout,
+            text: format!("line {sequence_number}"),
+        }
+    }
+
+    #[test]
+    fn should_append_lines_in_order_and_skip_duplicates() {
+        let mut log = ExecutorRunLog::new(ExecutorRunId::random(), ExecutorId::random(), PeerId::random());
+
+        log.append(vec![line(1), line(0)]);
+        log.append(vec![line(1), line(2), line(3)]);
+
+        assert_that!(log.lines.iter().map(|line| line.sequence_number).collect::<Vec<_>>(), eq(&vec![0, 1, 2, 3]));
+        assert_that!(log.next_sequence_number(), eq(4));
+        assert_that!(log.started_at(), some(eq(SystemTime::UNIX_EPOCH)));
+    }
+
+    #[test]
+    fn should_insert_only_lines_belonging_to_the_chunk() {
+        let run_id = ExecutorRunId::random();
+        let mut chunk = ExecutorLogChunk::new(ExecutorLogChunkId::containing(run_id, 1));
+
+        chunk.insert(vec![line(2), line(0), line(ExecutorLogChunkId::LINES_PER_CHUNK)]);
+        chunk.insert(vec![line(1), line(2)]);
+
+        assert_that!(chunk.lines, elements_are![eq(&line(0)), eq(&line(1)), eq(&line(2))]);
+        assert_that!(ExecutorLogChunkId::containing(run_id, ExecutorLogChunkId::LINES_PER_CHUNK).uuid(), not(eq(chunk.id.uuid())));
+    }
+
+    #[test]
+    fn should_return_lines_from_a_sequence_number() {
+        let mut log = ExecutorRunLog::new(ExecutorRunId::random(), ExecutorId::random(), PeerId::random());
+        log.append(vec![line(0), line(1), line(2)]);
+
+        assert_that!(log.lines_from(1), elements_are![eq(&line(1)), eq(&line(2))]);
+        assert_that!(log.lines_from(3), empty());
+    }
+}
@@ -4,9 +4,13 @@ use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 use url::Url;
 use uuid::Uuid;
+use crate::peer::executor::executable::{ExecutablePath, ExecutableTimeout, ExecutableWorkingDirectory};
 use crate::peer::executor::container::{Engine, ContainerName, ContainerImage, ContainerVolume, ContainerDevice, ContainerEnvironmentVariable, ContainerPortSpec, ContainerCommand, ContainerCommandArgument, deserialize_container_environment_variable_vec};
 
 pub mod container;
+pub mod executable;
+pub mod log;
+pub mod test_run;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExecutorDescriptors {
@@ -35,11 +39,28 @@ impl From<Uuid> for ExecutorId {
         Self { uuid }
     }
 }
+impl fmt::Display for ExecutorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.uuid)
+    }
+}
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 pub enum ExecutorKind {
-    Executable,
+    /// A program, which EDGAR runs natively on the host.
+    #[serde(rename_all = "kebab-case")]
+    Executable {
+        path: ExecutablePath,
+        #[serde(default)]
+        args: Vec<ContainerCommandArgument>,
+        #[serde(default, deserialize_with = "deserialize_container_environment_variable_vec")]
+        envs: Vec<ContainerEnvironmentVariable>,
+        #[serde(default)]
+        working_dir: Option<ExecutableWorkingDirectory>,
+        #[serde(default)]
+        timeout: Option<ExecutableTimeout>,
+    },
     #[serde(rename_all = "kebab-case")]
     Container {
         engine: Engine,
@@ -0,0 +1,239 @@
+use std::fmt;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::cluster::ClusterId;
+use crate::peer::executor::container::{ContainerCommandArgument, ContainerEnvironmentVariable};
+use crate::peer::executor::log::ExecutorRunId;
+use crate::peer::executor::{ExecutorId, ExecutorKind};
+use crate::peer::PeerId;
+
+/// A run of an executor.
+/// Created by CARL when the executor is started and updated as EDGAR reports the progress of the executor.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TestRun {
+    /// Also identifies the logs of the run.
+    pub id: ExecutorRunId,
+    pub executor_id: ExecutorId,
+    pub peer_id: PeerId,
+    /// Cluster the peer was assigned to, when the run was started.
+    pub cluster_id: Option<ClusterId>,
+    /// Name of the user who started the run, if known.
+    pub triggered_by: Option<String>,
+    pub created_at: SystemTime,
+    pub started_at: Option<SystemTime>,
+    pub finished_at: Option<SystemTime>,
+    pub status: TestRunStatus,
+    pub exit_code: Option<i32>,
+    /// Location the results of the run were uploaded to.
+    pub results_location: Option<Url>,
+}
+
+impl TestRun {
+    pub fn new(id: ExecutorRunId, executor_id: ExecutorId, peer_id: PeerId, cluster_id: Option<ClusterId>, triggered_by: Option<String>) -> Self {
+        Self {
+            id,
+            executor_id,
+            peer_id,
+            cluster_id,
+            triggered_by,
+            created_at: SystemTime::now(),
+            started_at: None,
+            finished_at: None,
+            status: TestRunStatus::Pending,
+            exit_code: None,
+            results_location: None,
+        }
+    }
+
+    /// Applies an update reported by EDGAR.
+    /// Updates of a run, which is already finished, are ignored, as they may arrive out of order.
+    pub fn apply(&mut self, update: TestRunUpdate) {
+        if self.status.is_finished() {
+            return;
+        }
+
+        match update.status {
+            TestRunStatus::Pending => {}
+            TestRunStatus::Running => {
+                self.started_at = Some(update.timestamp);
+            }
+            TestRunStatus::Succeeded | TestRunStatus::Failed | TestRunStatus::Aborted => {
+                self.finished_at = Some(update.timestamp);
+            }
+        }
+        self.status = update.status;
+
+        if update.exit_code.is_some() {
+            self.exit_code = update.exit_code;
+        }
+        if update.results_location.is_some() {
+            self.results_location = update.results_location;
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum TestRunStatus {
+    /// Created by CARL, but not yet started by EDGAR.
+    Pending,
+    Running,
+    /// The executor exited with exit code 0.
+    Succeeded,
+    /// The executor exited with a non-zero exit code or could not be run.
+    Failed,
+    /// The executor was stopped before it exited, e.g. because the cluster was undeployed.
+    Aborted,
+}
+
+impl TestRunStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, TestRunStatus::Succeeded | TestRunStatus::Failed | TestRunStatus::Aborted)
+    }
+}
+
+impl fmt::Display for TestRunStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestRunStatus::Pending => write!(f, "Pending"),
+            TestRunStatus::Running => write!(f, "Running"),
+            TestRunStatus::Succeeded => write!(f, "Succeeded"),
+            TestRunStatus::Failed => write!(f, "Failed"),
+            TestRunStatus::Aborted => write!(f, "Aborted"),
+        }
+    }
+}
+
+/// Progress of a run of an executor, as reported by EDGAR.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TestRunUpdate {
+    pub run_id: ExecutorRunId,
+    pub executor_id: ExecutorId,
+    pub status: TestRunStatus,
+    pub timestamp: SystemTime,
+    pub exit_code: Option<i32>,
+    pub results_location: Option<Url>,
+}
+
+/// Changes to the configuration of an executor, which only apply to a single run.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExecutorRunOverrides {
+    /// Added to the configured environment variables, replacing those with the same name.
+    pub envs: Vec<ContainerEnvironmentVariable>,
+    /// Replace the configured arguments, if not empty.
+    pub args: Vec<ContainerCommandArgument>,
+}
+
+impl ExecutorRunOverrides {
+    pub fn apply(&self, kind: ExecutorKind) -> ExecutorKind {
+        match kind {
+            ExecutorKind::Executable { path, args, envs, working_dir, timeout } => {
+                let envs = self.apply_envs(envs);
+                let args = self.apply_args(args);
+
+                ExecutorKind::Executable { path, args, envs, working_dir, timeout }
+            }
+            ExecutorKind::Container { engine, name, image, volumes, devices, envs, ports, command, args } => {
+                let envs = self.apply_envs(envs);
+                let args = self.apply_args(args);
+
+                ExecutorKind::Container { engine, name, image, volumes, devices, envs, ports, command, args }
+            }
+        }
+    }
+
+    fn apply_envs(&self, mut envs: Vec<ContainerEnvironmentVariable>) -> Vec<ContainerEnvironmentVariable> {
+        envs.retain(|env| self.envs.iter().all(|override_env| override_env.name() != env.name()));
+        envs.extend(self.envs.iter().cloned());
+        envs
+    }
+
+    fn apply_args(&self, args: Vec<ContainerCommandArgument>) -> Vec<ContainerCommandArgument> {
+        if self.args.is_empty() { args } else { Clone::clone(&self.args) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn update(test_run: &TestRun, status: TestRunStatus, secs: u64) -> TestRunUpdate {
+        TestRunUpdate {
+            run_id: test_run.id,
+            executor_id: test_run.executor_id,
+            status,
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+            exit_code: None,
+            results_location: None,
+        }
+    }
+
+    #[test]
+    fn should_record_the_lifecycle_of_a_run() -> Result<()> {
+        let mut test_run = TestRun::new(ExecutorRunId::random(), ExecutorId::random(), PeerId::random(), Some(ClusterId::random()), None);
+
+        test_run.apply(update(&test_run, TestRunStatus::Running, 10));
+        test_run.apply(TestRunUpdate {
+            exit_code: Some(1),
+            results_location: Some(Url::parse("https://example.com/results/run.zip")?),
+            ..update(&test_run, TestRunStatus::Failed, 20)
+        });
+
+        assert_that!(test_run.status, eq(TestRunStatus::Failed));
+        assert_that!(test_run.started_at, some(eq(SystemTime::UNIX_EPOCH + Duration::from_secs(10))));
+        assert_that!(test_run.finished_at, some(eq(SystemTime::UNIX_EPOCH + Duration::from_secs(20))));
+        assert_that!(test_run.exit_code, some(eq(1)));
+        assert_that!(test_run.results_location, some(anything()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_ignore_updates_after_the_run_finished() {
+        let mut test_run = TestRun::new(ExecutorRunId::random(), ExecutorId::random(), PeerId::random(), None, None);
+
+        test_run.apply(update(&test_run, TestRunStatus::Aborted, 20));
+        test_run.apply(update(&test_run, TestRunStatus::Running, 10));
+
+        assert_that!(test_run.status, eq(TestRunStatus::Aborted));
+        assert_that!(test_run.started_at, none());
+    }
+
+    #[test]
+    fn should_apply_overrides_to_a_container_executor() -> Result<()> {
+        use crate::peer::executor::container::{ContainerCommand, ContainerImage, ContainerName, Engine};
+
+        let kind = ExecutorKind::Container {
+            engine: Engine::Docker,
+            name: ContainerName::Empty,
+            image: ContainerImage::try_from("testenv-executor")?,
+            volumes: vec![],
+            devices: vec![],
+            envs: vec![
+                ContainerEnvironmentVariable::new("TEST_SUITE", "all")?,
+                ContainerEnvironmentVariable::new("VERBOSE", "false")?,
+            ],
+            ports: vec![],
+            command: ContainerCommand::Default,
+            args: vec![ContainerCommandArgument::try_from("--fast")?],
+        };
+        let overrides = ExecutorRunOverrides {
+            envs: vec![ContainerEnvironmentVariable::new("VERBOSE", "true")?],
+            args: vec![],
+        };
+
+        let ExecutorKind::Container { envs, args, .. } = overrides.apply(kind) else { panic!("Expected container executor.") };
+
+        assert_that!(envs, unordered_elements_are![
+            eq(&ContainerEnvironmentVariable::new("TEST_SUITE", "all")?),
+            eq(&ContainerEnvironmentVariable::new("VERBOSE", "true")?),
+        ]);
+        assert_that!(args, elements_are![eq(&ContainerCommandArgument::try_from("--fast")?)]);
+        Ok(())
+    }
+}
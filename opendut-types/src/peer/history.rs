@@ -0,0 +1,140 @@
+use std::fmt;
+use std::net::IpAddr;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::peer::PeerId;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PeerConnectionSessionId(pub Uuid);
+
+impl PeerConnectionSessionId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for PeerConnectionSessionId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for PeerConnectionSessionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A single connection of an EDGAR to CARL, from opening the peer messaging stream until it ended.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PeerConnectionSession {
+    pub id: PeerConnectionSessionId,
+    pub peer_id: PeerId,
+    pub remote_host: IpAddr,
+    /// Version of EDGAR running on the peer. Not reported by older EDGAR versions.
+    pub edgar_version: Option<String>,
+    pub connected_at: SystemTime,
+    /// `None`, while the session is still ongoing.
+    pub disconnected_at: Option<SystemTime>,
+}
+
+impl PeerConnectionSession {
+    pub fn is_connected(&self) -> bool {
+        self.disconnected_at.is_none()
+    }
+
+    /// Duration of the session, if it has ended already.
+    pub fn duration(&self) -> Option<Duration> {
+        self.disconnected_at
+            .and_then(|disconnected_at| disconnected_at.duration_since(self.connected_at).ok())
+    }
+}
+
+/// The retained connection sessions of a peer, ordered from oldest to newest.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PeerConnectionSessions {
+    pub peer_id: PeerId,
+    pub sessions: Vec<PeerConnectionSession>,
+}
+
+impl PeerConnectionSessions {
+    pub fn new(peer_id: PeerId) -> Self {
+        Self { peer_id, sessions: Vec::new() }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PeerEnrollmentId(pub Uuid);
+
+impl PeerEnrollmentId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for PeerEnrollmentId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for PeerEnrollmentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Issuing of a PeerSetup for a peer, which enrolls a host as this peer.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PeerEnrollment {
+    pub id: PeerEnrollmentId,
+    pub peer_id: PeerId,
+    /// Whether the credentials of previous enrollments were revoked, e.g. because the host of the peer was replaced.
+    pub reenrollment: bool,
+    /// User, who requested the PeerSetup. Not known, if authentication is disabled.
+    pub issued_by: Option<String>,
+    pub issued_at: SystemTime,
+}
+
+/// Connection sessions and enrollments of a peer, each ordered from oldest to newest.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PeerHistory {
+    pub sessions: Vec<PeerConnectionSession>,
+    pub enrollments: Vec<PeerEnrollment>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_calculate_duration_of_ended_session() -> Result<()> {
+        let connected_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+
+        let mut session = PeerConnectionSession {
+            id: PeerConnectionSessionId::random(),
+            peer_id: PeerId::random(),
+            remote_host: IpAddr::from_str("192.168.56.10")?,
+            edgar_version: Some(String::from("0.3.0")),
+            connected_at,
+            disconnected_at: None,
+        };
+        assert_that!(session.is_connected(), eq(true));
+        assert_that!(session.duration(), none());
+
+        session.disconnected_at = Some(connected_at + Duration::from_secs(42));
+        assert_that!(session.is_connected(), eq(false));
+        assert_that!(session.duration(), some(eq(Duration::from_secs(42))));
+
+        Ok(())
+    }
+}
@@ -8,11 +8,13 @@ use url::Url;
 use uuid::Uuid;
 
 use crate::peer::executor::ExecutorDescriptors;
+use crate::plugin::PluginVerifyingKey;
 use crate::topology::{DeviceDescriptor, Topology};
 use crate::util::net::{AuthConfig, Certificate, NetworkInterfaceDescriptor, NetworkInterfaceName};
 use crate::vpn::VpnPeerConfiguration;
 
 pub mod state;
+pub mod history;
 pub mod executor;
 pub mod configuration;
 pub mod ethernet;
@@ -258,6 +260,10 @@ pub struct PeerSetup {
     pub ca: Certificate,
     pub auth_config: AuthConfig,
     pub vpn: VpnPeerConfiguration,
+    /// Public key, with which EDGAR verifies the plugins fetched from the plugin registry of CARL.
+    /// Not set, if plugin signing is not configured in CARL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin_verifying_key: Option<PluginVerifyingKey>,
 }
 
 impl PeerSetup {
@@ -344,6 +350,7 @@ mod tests {
                 management_url: Url::parse("https://netbird.opendut.local/api")?,
                 setup_key: SetupKey::from(Uuid::parse_str("d79c202f-bbbf-4997-844e-678f27606e1c")?),
             },
+            plugin_verifying_key: None,
         };
 
         let encoded = setup.encode()?;
@@ -355,6 +362,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn A_PeerSetup_with_a_plugin_verifying_key_should_be_decodable() -> Result<()> {
+        let setup = PeerSetup {
+            id: PeerId::random(),
+            carl: Url::parse("https://carl.opendut.local")?,
+            ca: Certificate(Pem::new("Test Tag".to_string(), vec![])),
+            auth_config: AuthConfig::Disabled,
+            vpn: VpnPeerConfiguration::Disabled,
+            plugin_verifying_key: Some(PluginVerifyingKey([42; PluginVerifyingKey::LENGTH])),
+        };
+
+        let decoded = PeerSetup::decode(&setup.encode()?)?;
+        assert_that!(decoded, eq(&setup));
+
+        Ok(())
+    }
+
     #[test]
     fn A_PeerName_should_contain_valid_characters() -> Result<()> {
         let peer_name =
@@ -0,0 +1,202 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::peer::PeerId;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PluginId(pub Uuid);
+
+impl PluginId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for PluginId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for PluginId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum PluginKind {
+    /// Contributes tasks to `edgar setup`.
+    Setup,
+    /// Loaded by the EDGAR service and notified about cluster deployments.
+    Service,
+}
+
+impl fmt::Display for PluginKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginKind::Setup => write!(f, "Setup"),
+            PluginKind::Service => write!(f, "Service"),
+        }
+    }
+}
+
+/// A plugin stored in the plugin registry of CARL.
+/// The WebAssembly component itself is stored separately as [`PluginArtifact`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PluginDescriptor {
+    pub id: PluginId,
+    pub name: String,
+    /// The same name may be uploaded in multiple versions, which are distinct plugins.
+    pub version: String,
+    pub kind: PluginKind,
+    /// SHA-256 checksum of the WebAssembly component.
+    pub checksum: Vec<u8>,
+    /// Capability manifest in TOML, as placed next to plugins in the EDGAR distribution. No capabilities are granted, if not set.
+    pub manifest: Option<String>,
+    /// Ed25519 signature of CARL over the [`PluginDescriptor::signed_content`].
+    pub signature: Vec<u8>,
+    /// User, who uploaded the plugin. Not known, if authentication is disabled.
+    pub uploaded_by: Option<String>,
+    pub uploaded_at: SystemTime,
+}
+
+impl PluginDescriptor {
+    /// The content covered by the signature, which includes the checksum of the WebAssembly component and the manifest,
+    /// so neither the plugin nor its capabilities can be changed without invalidating the signature.
+    pub fn signed_content(&self) -> Vec<u8> {
+        #[derive(Serialize)]
+        struct SignedContent<'a> {
+            id: &'a PluginId,
+            name: &'a str,
+            version: &'a str,
+            kind: &'a PluginKind,
+            checksum: &'a [u8],
+            manifest: &'a Option<String>,
+        }
+
+        let content = SignedContent {
+            id: &self.id,
+            name: &self.name,
+            version: &self.version,
+            kind: &self.kind,
+            checksum: &self.checksum,
+            manifest: &self.manifest,
+        };
+        serde_json::to_vec(&content)
+            .expect("Signed content of a plugin should always be serializable to JSON.")
+    }
+}
+
+/// The WebAssembly component of a plugin.
+#[derive(Clone, Eq, PartialEq)]
+pub struct PluginArtifact {
+    pub id: PluginId,
+    pub binary: Vec<u8>,
+}
+
+impl fmt::Debug for PluginArtifact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PluginArtifact")
+            .field("id", &self.id)
+            .field("binary", &format_args!("<{} bytes>", self.binary.len()))
+            .finish()
+    }
+}
+
+/// Plugins, which EDGAR fetches from CARL for a peer, in the order in which they are run.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PeerPluginAssignment {
+    pub peer_id: PeerId,
+    pub plugins: Vec<PluginId>,
+}
+
+/// Public key of CARL, with which EDGAR verifies the signatures of plugins.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct PluginVerifyingKey(pub [u8; Self::LENGTH]);
+
+impl PluginVerifyingKey {
+    pub const LENGTH: usize = 32;
+}
+
+impl fmt::Display for PluginVerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", BASE64_STANDARD.encode(self.0))
+    }
+}
+
+impl FromStr for PluginVerifyingKey {
+    type Err = IllegalPluginVerifyingKey;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let bytes = BASE64_STANDARD.decode(value.trim())
+            .map_err(|cause| IllegalPluginVerifyingKey { details: format!("Not valid Base64: {cause}") })?;
+        let bytes = <[u8; Self::LENGTH]>::try_from(bytes)
+            .map_err(|bytes| IllegalPluginVerifyingKey { details: format!("Expected {} bytes, but got {}.", Self::LENGTH, bytes.len()) })?;
+        Ok(Self(bytes))
+    }
+}
+
+impl TryFrom<&[u8]> for PluginVerifyingKey {
+    type Error = IllegalPluginVerifyingKey;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        <[u8; Self::LENGTH]>::try_from(value)
+            .map(Self)
+            .map_err(|_| IllegalPluginVerifyingKey { details: format!("Expected {} bytes, but got {}.", Self::LENGTH, value.len()) })
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug, Eq, PartialEq)]
+#[error("Illegal plugin verifying key. {details}")]
+pub struct IllegalPluginVerifyingKey {
+    details: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn the_signed_content_should_cover_the_checksum_and_the_manifest() {
+        let descriptor = PluginDescriptor {
+            id: PluginId::random(),
+            name: String::from("can-setup"),
+            version: String::from("1.0.0"),
+            kind: PluginKind::Setup,
+            checksum: vec![1, 2, 3],
+            manifest: None,
+            signature: vec![],
+            uploaded_by: None,
+            uploaded_at: SystemTime::UNIX_EPOCH + Duration::from_secs(1_728_000_000),
+        };
+
+        let with_other_checksum = PluginDescriptor { checksum: vec![3, 2, 1], ..Clone::clone(&descriptor) };
+        let with_manifest = PluginDescriptor { manifest: Some(String::from("[capabilities]\nnetwork = true")), ..Clone::clone(&descriptor) };
+        let with_signature = PluginDescriptor { signature: vec![4, 5, 6], ..Clone::clone(&descriptor) };
+
+        assert_that!(with_other_checksum.signed_content(), not(eq(&descriptor.signed_content())));
+        assert_that!(with_manifest.signed_content(), not(eq(&descriptor.signed_content())));
+        assert_that!(with_signature.signed_content(), eq(&descriptor.signed_content()));
+    }
+
+    #[test]
+    fn a_verifying_key_should_be_parseable_from_its_string_representation() -> Result<()> {
+        let key = PluginVerifyingKey([7; PluginVerifyingKey::LENGTH]);
+
+        assert_that!(PluginVerifyingKey::from_str(&key.to_string()), ok(eq(&key)));
+        assert_that!(PluginVerifyingKey::from_str("AAEC"), err(anything()));
+        Ok(())
+    }
+}
@@ -262,6 +262,173 @@ impl TryFrom<PeerClusterAssignment> for crate::cluster::PeerClusterAssignment {
     }
 }
 
+impl From<crate::cluster::diagnostics::NetworkDiagnosticsId> for NetworkDiagnosticsId {
+    fn from(value: crate::cluster::diagnostics::NetworkDiagnosticsId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<NetworkDiagnosticsId> for crate::cluster::diagnostics::NetworkDiagnosticsId {
+    type Error = ConversionError;
+
+    fn try_from(value: NetworkDiagnosticsId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<NetworkDiagnosticsId, crate::cluster::diagnostics::NetworkDiagnosticsId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::cluster::diagnostics::NetworkDiagnosticsOptions> for NetworkDiagnosticsOptions {
+    fn from(value: crate::cluster::diagnostics::NetworkDiagnosticsOptions) -> Self {
+        Self {
+            ping_count: value.ping_count,
+            ping_timeout_millis: value.ping_timeout.as_millis() as u64,
+            throughput_duration_millis: value.throughput_duration.as_millis() as u64,
+            target_bandwidth_kbit_per_second: value.target_bandwidth_kbit_per_second,
+            path_mtu_discovery: value.path_mtu_discovery,
+        }
+    }
+}
+
+impl From<NetworkDiagnosticsOptions> for crate::cluster::diagnostics::NetworkDiagnosticsOptions {
+    fn from(value: NetworkDiagnosticsOptions) -> Self {
+        Self {
+            ping_count: value.ping_count,
+            ping_timeout: std::time::Duration::from_millis(value.ping_timeout_millis),
+            throughput_duration: std::time::Duration::from_millis(value.throughput_duration_millis),
+            target_bandwidth_kbit_per_second: value.target_bandwidth_kbit_per_second,
+            path_mtu_discovery: value.path_mtu_discovery,
+        }
+    }
+}
+
+impl From<crate::cluster::diagnostics::PeerLinkDiagnostics> for PeerLinkDiagnostics {
+    fn from(value: crate::cluster::diagnostics::PeerLinkDiagnostics) -> Self {
+        Self {
+            source: Some(value.source.into()),
+            target: Some(value.target.into()),
+            latency: value.latency.map(Into::into),
+            throughput: value.throughput.map(Into::into),
+            path_mtu: value.path_mtu.map(u32::from),
+            errors: value.errors,
+        }
+    }
+}
+
+impl TryFrom<PeerLinkDiagnostics> for crate::cluster::diagnostics::PeerLinkDiagnostics {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerLinkDiagnostics) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerLinkDiagnostics, crate::cluster::diagnostics::PeerLinkDiagnostics>;
+
+        let source: crate::peer::PeerId = value.source
+            .ok_or(ErrorBuilder::field_not_set("source"))?
+            .try_into()?;
+
+        let target: crate::peer::PeerId = value.target
+            .ok_or(ErrorBuilder::field_not_set("target"))?
+            .try_into()?;
+
+        let path_mtu = value.path_mtu
+            .map(|path_mtu| u16::try_from(path_mtu)
+                .map_err(|_| ErrorBuilder::message(format!("Path MTU <{path_mtu}> is out of range.")))
+            )
+            .transpose()?;
+
+        Ok(Self {
+            source,
+            target,
+            latency: value.latency.map(Into::into),
+            throughput: value.throughput.map(Into::into),
+            path_mtu,
+            errors: value.errors,
+        })
+    }
+}
+
+impl From<crate::cluster::diagnostics::LatencyDiagnostics> for LatencyDiagnostics {
+    fn from(value: crate::cluster::diagnostics::LatencyDiagnostics) -> Self {
+        Self {
+            sent: value.sent,
+            received: value.received,
+            min_micros: value.min.as_micros() as u64,
+            avg_micros: value.avg.as_micros() as u64,
+            max_micros: value.max.as_micros() as u64,
+        }
+    }
+}
+
+impl From<LatencyDiagnostics> for crate::cluster::diagnostics::LatencyDiagnostics {
+    fn from(value: LatencyDiagnostics) -> Self {
+        Self {
+            sent: value.sent,
+            received: value.received,
+            min: std::time::Duration::from_micros(value.min_micros),
+            avg: std::time::Duration::from_micros(value.avg_micros),
+            max: std::time::Duration::from_micros(value.max_micros),
+        }
+    }
+}
+
+impl From<crate::cluster::diagnostics::ThroughputDiagnostics> for ThroughputDiagnostics {
+    fn from(value: crate::cluster::diagnostics::ThroughputDiagnostics) -> Self {
+        Self {
+            tcp_send_megabits_per_second: value.tcp_send_megabits_per_second,
+            tcp_receive_megabits_per_second: value.tcp_receive_megabits_per_second,
+            udp_send_megabits_per_second: value.udp_send_megabits_per_second,
+            udp_jitter_micros: value.udp_jitter.as_micros() as u64,
+            udp_packet_loss_percent: value.udp_packet_loss_percent,
+        }
+    }
+}
+
+impl From<ThroughputDiagnostics> for crate::cluster::diagnostics::ThroughputDiagnostics {
+    fn from(value: ThroughputDiagnostics) -> Self {
+        Self {
+            tcp_send_megabits_per_second: value.tcp_send_megabits_per_second,
+            tcp_receive_megabits_per_second: value.tcp_receive_megabits_per_second,
+            udp_send_megabits_per_second: value.udp_send_megabits_per_second,
+            udp_jitter: std::time::Duration::from_micros(value.udp_jitter_micros),
+            udp_packet_loss_percent: value.udp_packet_loss_percent,
+        }
+    }
+}
+
+impl From<crate::cluster::diagnostics::ClusterNetworkDiagnostics> for ClusterNetworkDiagnostics {
+    fn from(value: crate::cluster::diagnostics::ClusterNetworkDiagnostics) -> Self {
+        Self {
+            cluster_id: Some(value.cluster_id.into()),
+            links: value.links.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<ClusterNetworkDiagnostics> for crate::cluster::diagnostics::ClusterNetworkDiagnostics {
+    type Error = ConversionError;
+
+    fn try_from(value: ClusterNetworkDiagnostics) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ClusterNetworkDiagnostics, crate::cluster::diagnostics::ClusterNetworkDiagnostics>;
+
+        let cluster_id: crate::cluster::ClusterId = value.cluster_id
+            .ok_or(ErrorBuilder::field_not_set("cluster_id"))?
+            .try_into()?;
+
+        let links = value.links
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            cluster_id,
+            links,
+        })
+    }
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod test {
@@ -318,4 +485,52 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn A_ClusterNetworkDiagnostics_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+        use crate::cluster::diagnostics::{ClusterNetworkDiagnostics as Native, LatencyDiagnostics, PeerLinkDiagnostics, ThroughputDiagnostics};
+        use std::time::Duration;
+
+        let native = Native {
+            cluster_id: crate::cluster::ClusterId::random(),
+            links: vec![
+                PeerLinkDiagnostics {
+                    source: crate::peer::PeerId::random(),
+                    target: crate::peer::PeerId::random(),
+                    latency: Some(LatencyDiagnostics {
+                        sent: 5,
+                        received: 4,
+                        min: Duration::from_micros(800),
+                        avg: Duration::from_micros(1200),
+                        max: Duration::from_micros(2500),
+                    }),
+                    throughput: Some(ThroughputDiagnostics {
+                        tcp_send_megabits_per_second: 94.5,
+                        tcp_receive_megabits_per_second: 93.0,
+                        udp_send_megabits_per_second: 99.8,
+                        udp_jitter: Duration::from_micros(150),
+                        udp_packet_loss_percent: 0.5,
+                    }),
+                    path_mtu: Some(1380),
+                    errors: vec![],
+                },
+                PeerLinkDiagnostics {
+                    source: crate::peer::PeerId::random(),
+                    target: crate::peer::PeerId::random(),
+                    latency: None,
+                    throughput: None,
+                    path_mtu: None,
+                    errors: vec![String::from("Throughput measurement timed out.")],
+                },
+            ],
+        };
+        let proto: ClusterNetworkDiagnostics = Clone::clone(&native).into();
+
+        assert_that!(
+            Native::try_from(proto),
+            ok(eq(&native))
+        );
+
+        Ok(())
+    }
 }
@@ -29,6 +29,7 @@ impl From<crate::peer::configuration::PeerConfiguration> for PeerConfiguration {
         Self {
             executors: value.executors.into_iter().map(From::from).collect(),
             ethernet_bridges: value.ethernet_bridges.into_iter().map(From::from).collect(),
+            wireguard_peers: value.wireguard_peers.into_iter().map(From::from).collect(),
         }
     }
 }
@@ -39,6 +40,7 @@ impl TryFrom<PeerConfiguration> for crate::peer::configuration::PeerConfiguratio
         Ok(crate::peer::configuration::PeerConfiguration {
             executors: value.executors.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
             ethernet_bridges: value.ethernet_bridges.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            wireguard_peers: value.wireguard_peers.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
         })
     }
 }
@@ -122,6 +124,46 @@ mod ethernet_bridge {
     }
 }
 
+mod wireguard_peer {
+    use super::*;
+    type Model = crate::peer::configuration::Parameter<crate::vpn::wireguard::WireGuardPeer>;
+    type Proto = PeerConfigurationParameterWireGuardPeer;
+
+    impl From<Model> for Proto {
+        fn from(value: Model) -> Self {
+
+            let wireguard_peer: crate::proto::vpn::WireGuardPeer = value.value.clone().into();
+            let parameter = PeerConfigurationParameter::from(value);
+
+            Self {
+                parameter: Some(parameter),
+                value: Some(wireguard_peer),
+            }
+        }
+    }
+    impl TryFrom<Proto> for Model {
+        type Error = ConversionError;
+
+        fn try_from(value: Proto) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<Proto, Model>;
+
+            let parameter = value.parameter
+                .ok_or(ErrorBuilder::field_not_set("parameter"))?;
+
+            let wireguard_peer: crate::vpn::wireguard::WireGuardPeer = value.value
+                .ok_or(ErrorBuilder::field_not_set("wireguard_peer"))?
+                .try_into()?;
+
+            Ok(Self {
+                id: parameter.id.ok_or(ErrorBuilder::field_not_set("id"))?.try_into()?,
+                dependencies: parameter.dependencies.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+                target: parameter.target.ok_or(ErrorBuilder::field_not_set("target"))?.into(),
+                value: wireguard_peer,
+            })
+        }
+    }
+}
+
 impl<V: crate::peer::configuration::ParameterValue> From<crate::peer::configuration::Parameter<V>> for PeerConfigurationParameter {
     fn from(value: crate::peer::configuration::Parameter<V>) -> Self {
         Self {
@@ -32,11 +32,22 @@ impl From<crate::peer::executor::ExecutorDescriptor> for ExecutorDescriptor {
         let id = Some(value.id.into());
 
         let executor_kind = match value.kind {
-            crate::peer::executor::ExecutorKind::Executable => {
+            crate::peer::executor::ExecutorKind::Executable {
+                path,
+                args,
+                envs,
+                working_dir,
+                timeout,
+            } => {
                 Some(executor_descriptor::Kind::Executable(
-                       Executable {}
-                    )
-                )
+                    Executable {
+                        path: Some(path.into()),
+                        args: args.into_iter().map(|arg| arg.into()).collect(),
+                        envs: envs.into_iter().map(|env| env.into()).collect(),
+                        working_dir: working_dir.map(|working_dir| working_dir.into()),
+                        timeout_seconds: timeout.map(u64::from),
+                    }
+                ))
             }
             crate::peer::executor::ExecutorKind::Container { 
                 engine,
@@ -87,8 +98,40 @@ impl TryFrom<ExecutorDescriptor> for crate::peer::executor::ExecutorDescriptor {
             .ok_or(ErrorBuilder::field_not_set("kind"))?;
 
         let result_kind = match kind {
-            executor_descriptor::Kind::Executable(_) => {
-                crate::peer::executor::ExecutorKind::Executable
+            executor_descriptor::Kind::Executable(descriptor) => {
+                let Executable {
+                    path,
+                    args,
+                    envs,
+                    working_dir,
+                    timeout_seconds,
+                } = descriptor;
+                let path = path
+                    .ok_or(ErrorBuilder::field_not_set("path"))?
+                    .try_into()?;
+                let args = args
+                    .into_iter()
+                    .map(TryFrom::try_from)
+                    .collect::<Result<_, _>>()?;
+                let envs = envs
+                    .into_iter()
+                    .map(TryFrom::try_from)
+                    .collect::<Result<_, _>>()?;
+                let working_dir = working_dir
+                    .map(TryFrom::try_from)
+                    .transpose()?;
+                let timeout = timeout_seconds
+                    .map(crate::peer::executor::executable::ExecutableTimeout::try_from)
+                    .transpose()
+                    .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+
+                crate::peer::executor::ExecutorKind::Executable {
+                    path,
+                    args,
+                    envs,
+                    working_dir,
+                    timeout,
+                }
             },
             executor_descriptor::Kind::Container(descriptor) => {
                 let Container {
@@ -379,6 +422,44 @@ impl TryFrom<ContainerCommandArgument> for crate::peer::executor::container::Con
     }
 }
 
+impl From<crate::peer::executor::executable::ExecutablePath> for ExecutablePath {
+    fn from(value: crate::peer::executor::executable::ExecutablePath) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ExecutablePath> for crate::peer::executor::executable::ExecutablePath {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutablePath) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutablePath, crate::peer::executor::executable::ExecutablePath>;
+
+        crate::peer::executor::executable::ExecutablePath::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::executable::ExecutableWorkingDirectory> for ExecutableWorkingDirectory {
+    fn from(value: crate::peer::executor::executable::ExecutableWorkingDirectory) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ExecutableWorkingDirectory> for crate::peer::executor::executable::ExecutableWorkingDirectory {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutableWorkingDirectory) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutableWorkingDirectory, crate::peer::executor::executable::ExecutableWorkingDirectory>;
+
+        crate::peer::executor::executable::ExecutableWorkingDirectory::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
 impl From<crate::peer::executor::ResultsUrl> for ResultsUrl {
     fn from(value: crate::peer::executor::ResultsUrl) -> Self {
         Self {
@@ -397,3 +478,357 @@ impl TryFrom<ResultsUrl> for crate::peer::executor::ResultsUrl{
             .map_err(|cause| ErrorBuilder::message(cause.to_string()))
     }
 }
+
+impl From<crate::peer::executor::log::ExecutorRunId> for ExecutorRunId {
+    fn from(value: crate::peer::executor::log::ExecutorRunId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<ExecutorRunId> for crate::peer::executor::log::ExecutorRunId {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutorRunId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutorRunId, crate::peer::executor::log::ExecutorRunId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::peer::executor::log::ExecutorLogStream> for ExecutorLogStream {
+    fn from(value: crate::peer::executor::log::ExecutorLogStream) -> Self {
+        let inner = match value {
+            crate::peer::executor::log::ExecutorLogStream::Stdout => executor_log_stream::Inner::Stdout(ExecutorLogStreamStdout {}),
+            crate::peer::executor::log::ExecutorLogStream::Stderr => executor_log_stream::Inner::Stderr(ExecutorLogStreamStderr {}),
+        };
+        Self { inner: Some(inner) }
+    }
+}
+
+impl TryFrom<ExecutorLogStream> for crate::peer::executor::log::ExecutorLogStream {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutorLogStream) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutorLogStream, crate::peer::executor::log::ExecutorLogStream>;
+
+        match value.inner.ok_or(ErrorBuilder::field_not_set("inner"))? {
+            executor_log_stream::Inner::Stdout(_) => Ok(Self::Stdout),
+            executor_log_stream::Inner::Stderr(_) => Ok(Self::Stderr),
+        }
+    }
+}
+
+impl From<crate::peer::executor::log::ExecutorLogLine> for ExecutorLogLine {
+    fn from(value: crate::peer::executor::log::ExecutorLogLine) -> Self {
+        Self {
+            sequence_number: value.sequence_number,
+            timestamp: Some(value.timestamp.into()),
+            stream: Some(value.stream.into()),
+            text: value.text,
+        }
+    }
+}
+
+impl TryFrom<ExecutorLogLine> for crate::peer::executor::log::ExecutorLogLine {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutorLogLine) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutorLogLine, crate::peer::executor::log::ExecutorLogLine>;
+
+        let timestamp = value.timestamp
+            .ok_or(ErrorBuilder::field_not_set("timestamp"))?
+            .into();
+        let stream = value.stream
+            .ok_or(ErrorBuilder::field_not_set("stream"))?
+            .try_into()?;
+
+        Ok(Self {
+            sequence_number: value.sequence_number,
+            timestamp,
+            stream,
+            text: value.text,
+        })
+    }
+}
+
+impl From<crate::peer::executor::log::ExecutorRunLog> for ExecutorRunLog {
+    fn from(value: crate::peer::executor::log::ExecutorRunLog) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            executor_id: Some(value.executor_id.into()),
+            peer_id: Some(value.peer_id.into()),
+            lines: value.lines.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<ExecutorRunLog> for crate::peer::executor::log::ExecutorRunLog {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutorRunLog) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutorRunLog, crate::peer::executor::log::ExecutorRunLog>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+        let executor_id = value.executor_id
+            .ok_or(ErrorBuilder::field_not_set("executor_id"))?
+            .try_into()?;
+        let peer_id = value.peer_id
+            .ok_or(ErrorBuilder::field_not_set("peer_id"))?
+            .try_into()?;
+        let lines = value.lines.into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { id, executor_id, peer_id, lines })
+    }
+}
+
+impl From<crate::peer::executor::test_run::TestRunStatus> for TestRunStatus {
+    fn from(value: crate::peer::executor::test_run::TestRunStatus) -> Self {
+        let inner = match value {
+            crate::peer::executor::test_run::TestRunStatus::Pending => test_run_status::Inner::Pending(TestRunStatusPending {}),
+            crate::peer::executor::test_run::TestRunStatus::Running => test_run_status::Inner::Running(TestRunStatusRunning {}),
+            crate::peer::executor::test_run::TestRunStatus::Succeeded => test_run_status::Inner::Succeeded(TestRunStatusSucceeded {}),
+            crate::peer::executor::test_run::TestRunStatus::Failed => test_run_status::Inner::Failed(TestRunStatusFailed {}),
+            crate::peer::executor::test_run::TestRunStatus::Aborted => test_run_status::Inner::Aborted(TestRunStatusAborted {}),
+        };
+        Self { inner: Some(inner) }
+    }
+}
+
+impl TryFrom<TestRunStatus> for crate::peer::executor::test_run::TestRunStatus {
+    type Error = ConversionError;
+
+    fn try_from(value: TestRunStatus) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<TestRunStatus, crate::peer::executor::test_run::TestRunStatus>;
+
+        match value.inner.ok_or(ErrorBuilder::field_not_set("inner"))? {
+            test_run_status::Inner::Pending(_) => Ok(Self::Pending),
+            test_run_status::Inner::Running(_) => Ok(Self::Running),
+            test_run_status::Inner::Succeeded(_) => Ok(Self::Succeeded),
+            test_run_status::Inner::Failed(_) => Ok(Self::Failed),
+            test_run_status::Inner::Aborted(_) => Ok(Self::Aborted),
+        }
+    }
+}
+
+impl From<crate::peer::executor::test_run::TestRun> for TestRun {
+    fn from(value: crate::peer::executor::test_run::TestRun) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            executor_id: Some(value.executor_id.into()),
+            peer_id: Some(value.peer_id.into()),
+            cluster_id: value.cluster_id.map(Into::into),
+            triggered_by: value.triggered_by,
+            created_at: Some(value.created_at.into()),
+            started_at: value.started_at.map(Into::into),
+            finished_at: value.finished_at.map(Into::into),
+            status: Some(value.status.into()),
+            exit_code: value.exit_code,
+            results_location: value.results_location.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<TestRun> for crate::peer::executor::test_run::TestRun {
+    type Error = ConversionError;
+
+    fn try_from(value: TestRun) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<TestRun, crate::peer::executor::test_run::TestRun>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+        let executor_id = value.executor_id
+            .ok_or(ErrorBuilder::field_not_set("executor_id"))?
+            .try_into()?;
+        let peer_id = value.peer_id
+            .ok_or(ErrorBuilder::field_not_set("peer_id"))?
+            .try_into()?;
+        let cluster_id = value.cluster_id
+            .map(TryInto::try_into)
+            .transpose()?;
+        let created_at = value.created_at
+            .ok_or(ErrorBuilder::field_not_set("created_at"))?
+            .into();
+        let status = value.status
+            .ok_or(ErrorBuilder::field_not_set("status"))?
+            .try_into()?;
+        let results_location = value.results_location
+            .map(TryInto::try_into)
+            .transpose()?;
+
+        Ok(Self {
+            id,
+            executor_id,
+            peer_id,
+            cluster_id,
+            triggered_by: value.triggered_by,
+            created_at,
+            started_at: value.started_at.map(Into::into),
+            finished_at: value.finished_at.map(Into::into),
+            status,
+            exit_code: value.exit_code,
+            results_location,
+        })
+    }
+}
+
+impl From<crate::peer::executor::test_run::TestRunUpdate> for TestRunUpdate {
+    fn from(value: crate::peer::executor::test_run::TestRunUpdate) -> Self {
+        Self {
+            run_id: Some(value.run_id.into()),
+            executor_id: Some(value.executor_id.into()),
+            status: Some(value.status.into()),
+            timestamp: Some(value.timestamp.into()),
+            exit_code: value.exit_code,
+            results_location: value.results_location.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<TestRunUpdate> for crate::peer::executor::test_run::TestRunUpdate {
+    type Error = ConversionError;
+
+    fn try_from(value: TestRunUpdate) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<TestRunUpdate, crate::peer::executor::test_run::TestRunUpdate>;
+
+        let run_id = value.run_id
+            .ok_or(ErrorBuilder::field_not_set("run_id"))?
+            .try_into()?;
+        let executor_id = value.executor_id
+            .ok_or(ErrorBuilder::field_not_set("executor_id"))?
+            .try_into()?;
+        let status = value.status
+            .ok_or(ErrorBuilder::field_not_set("status"))?
+            .try_into()?;
+        let timestamp = value.timestamp
+            .ok_or(ErrorBuilder::field_not_set("timestamp"))?
+            .into();
+        let results_location = value.results_location
+            .map(TryInto::try_into)
+            .transpose()?;
+
+        Ok(Self {
+            run_id,
+            executor_id,
+            status,
+            timestamp,
+            exit_code: value.exit_code,
+            results_location,
+        })
+    }
+}
+
+impl From<crate::peer::executor::test_run::ExecutorRunOverrides> for ExecutorRunOverrides {
+    fn from(value: crate::peer::executor::test_run::ExecutorRunOverrides) -> Self {
+        Self {
+            envs: value.envs.into_iter().map(From::from).collect(),
+            args: value.args.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+impl TryFrom<ExecutorRunOverrides> for crate::peer::executor::test_run::ExecutorRunOverrides {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutorRunOverrides) -> Result<Self, Self::Error> {
+        let envs = value.envs.into_iter()
+            .map(TryFrom::try_from)
+            .collect::<Result<_, _>>()?;
+        let args = value.args.into_iter()
+            .map(TryFrom::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { envs, args })
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn An_ExecutorRunLog_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+        let native = crate::peer::executor::log::ExecutorRunLog {
+            id: crate::peer::executor::log::ExecutorRunId::random(),
+            executor_id: crate::peer::executor::ExecutorId::random(),
+            peer_id: crate::peer::PeerId::random(),
+            lines: vec![
+                crate::peer::executor::log::ExecutorLogLine {
+                    sequence_number: 0,
+                    timestamp: SystemTime::UNIX_EPOCH + Duration::from_millis(1_725_000_000_123),
+                    stream: crate::peer::executor::log::ExecutorLogStream::Stderr,
+                    text: String::from("Starting test."),
+                },
+            ],
+        };
+        let proto: ExecutorRunLog = Clone::clone(&native).into();
+
+        assert_that!(
+            crate::peer::executor::log::ExecutorRunLog::try_from(proto),
+            ok(eq(&native))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn A_TestRun_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+        let native = crate::peer::executor::test_run::TestRun {
+            id: crate::peer::executor::log::ExecutorRunId::random(),
+            executor_id: crate::peer::executor::ExecutorId::random(),
+            peer_id: crate::peer::PeerId::random(),
+            cluster_id: Some(crate::cluster::ClusterId::random()),
+            triggered_by: Some(String::from("tester")),
+            created_at: SystemTime::UNIX_EPOCH + Duration::from_millis(1_725_000_000_000),
+            started_at: Some(SystemTime::UNIX_EPOCH + Duration::from_millis(1_725_000_001_000)),
+            finished_at: None,
+            status: crate::peer::executor::test_run::TestRunStatus::Running,
+            exit_code: None,
+            results_location: Some(url::Url::parse("https://example.com/results/")?),
+        };
+        let proto: TestRun = Clone::clone(&native).into();
+
+        assert_that!(
+            crate::peer::executor::test_run::TestRun::try_from(proto),
+            ok(eq(&native))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn An_Executable_ExecutorDescriptor_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+        use crate::peer::executor::container::{ContainerCommandArgument, ContainerEnvironmentVariable};
+        use crate::peer::executor::executable::{ExecutablePath, ExecutableTimeout, ExecutableWorkingDirectory};
+
+        let native = crate::peer::executor::ExecutorDescriptor {
+            id: crate::peer::executor::ExecutorId::random(),
+            kind: crate::peer::executor::ExecutorKind::Executable {
+                path: ExecutablePath::try_from("/opt/tests/run.sh")?,
+                args: vec![ContainerCommandArgument::try_from("--verbose")?],
+                envs: vec![ContainerEnvironmentVariable::new("TARGET", "ecu-1")?],
+                working_dir: Some(ExecutableWorkingDirectory::try_from("/opt/tests")?),
+                timeout: Some(ExecutableTimeout::try_from(600)?),
+            },
+            results_url: None,
+        };
+        let proto: ExecutorDescriptor = Clone::clone(&native).into();
+
+        assert_that!(
+            crate::peer::executor::ExecutorDescriptor::try_from(proto),
+            ok(eq(&native))
+        );
+        Ok(())
+    }
+}
@@ -176,6 +176,7 @@ impl From<crate::peer::PeerSetup> for PeerSetup {
             ca: Some(value.ca.into()),
             vpn: Some(value.vpn.into()),
             auth_config: Some(value.auth_config.into()),
+            plugin_verifying_key: value.plugin_verifying_key.map(|key| key.0.to_vec()),
         }
     }
 }
@@ -207,12 +208,18 @@ impl TryFrom<PeerSetup> for crate::peer::PeerSetup {
             .ok_or(ErrorBuilder::field_not_set("auth_config"))?
             .try_into()?;
 
+        let plugin_verifying_key = value.plugin_verifying_key
+            .map(|key| crate::plugin::PluginVerifyingKey::try_from(key.as_slice()))
+            .transpose()
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+
         Ok(Self {
             id,
             carl,
             ca,
             auth_config,
             vpn,
+            plugin_verifying_key,
         })
     }
 }
@@ -348,6 +355,127 @@ impl TryFrom<PeerState> for crate::peer::state::PeerState {
     }
 }
 
+impl From<crate::peer::history::PeerConnectionSessionId> for PeerConnectionSessionId {
+    fn from(value: crate::peer::history::PeerConnectionSessionId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<PeerConnectionSessionId> for crate::peer::history::PeerConnectionSessionId {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerConnectionSessionId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerConnectionSessionId, crate::peer::history::PeerConnectionSessionId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::peer::history::PeerConnectionSession> for PeerConnectionSession {
+    fn from(value: crate::peer::history::PeerConnectionSession) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            peer_id: Some(value.peer_id.into()),
+            remote_host: Some(value.remote_host.into()),
+            edgar_version: value.edgar_version,
+            connected_at: Some(value.connected_at.into()),
+            disconnected_at: value.disconnected_at.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<PeerConnectionSession> for crate::peer::history::PeerConnectionSession {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerConnectionSession) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerConnectionSession, crate::peer::history::PeerConnectionSession>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+        let peer_id = value.peer_id
+            .ok_or(ErrorBuilder::field_not_set("peer_id"))?
+            .try_into()?;
+        let remote_host = value.remote_host
+            .ok_or(ErrorBuilder::field_not_set("remote_host"))?
+            .try_into()?;
+        let connected_at = value.connected_at
+            .ok_or(ErrorBuilder::field_not_set("connected_at"))?
+            .into();
+
+        Ok(Self {
+            id,
+            peer_id,
+            remote_host,
+            edgar_version: value.edgar_version,
+            connected_at,
+            disconnected_at: value.disconnected_at.map(Into::into),
+        })
+    }
+}
+
+impl From<crate::peer::history::PeerEnrollmentId> for PeerEnrollmentId {
+    fn from(value: crate::peer::history::PeerEnrollmentId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<PeerEnrollmentId> for crate::peer::history::PeerEnrollmentId {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerEnrollmentId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerEnrollmentId, crate::peer::history::PeerEnrollmentId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::peer::history::PeerEnrollment> for PeerEnrollment {
+    fn from(value: crate::peer::history::PeerEnrollment) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            peer_id: Some(value.peer_id.into()),
+            reenrollment: value.reenrollment,
+            issued_by: value.issued_by,
+            issued_at: Some(value.issued_at.into()),
+        }
+    }
+}
+
+impl TryFrom<PeerEnrollment> for crate::peer::history::PeerEnrollment {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerEnrollment) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerEnrollment, crate::peer::history::PeerEnrollment>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+        let peer_id = value.peer_id
+            .ok_or(ErrorBuilder::field_not_set("peer_id"))?
+            .try_into()?;
+        let issued_at = value.issued_at
+            .ok_or(ErrorBuilder::field_not_set("issued_at"))?
+            .into();
+
+        Ok(Self {
+            id,
+            peer_id,
+            reenrollment: value.reenrollment,
+            issued_by: value.issued_by,
+            issued_at,
+        })
+    }
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod tests {
@@ -484,4 +612,49 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn A_PeerConnectionSession_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+        use std::time::{Duration, SystemTime};
+
+        let connected_at = SystemTime::UNIX_EPOCH + Duration::from_millis(1_725_000_000_123);
+
+        let native = crate::peer::history::PeerConnectionSession {
+            id: crate::peer::history::PeerConnectionSessionId::random(),
+            peer_id: crate::peer::PeerId::random(),
+            remote_host: IpAddr::from_str("1.2.3.4")?,
+            edgar_version: Some(String::from("0.3.0")),
+            connected_at,
+            disconnected_at: Some(connected_at + Duration::from_secs(60)),
+        };
+        let proto: PeerConnectionSession = Clone::clone(&native).into();
+
+        assert_that!(
+            crate::peer::history::PeerConnectionSession::try_from(proto),
+            ok(eq(&native))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn A_PeerEnrollment_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+        use std::time::{Duration, SystemTime};
+
+        let native = crate::peer::history::PeerEnrollment {
+            id: crate::peer::history::PeerEnrollmentId::random(),
+            peer_id: crate::peer::PeerId::random(),
+            reenrollment: true,
+            issued_by: Some(String::from("testUser")),
+            issued_at: SystemTime::UNIX_EPOCH + Duration::from_millis(1_728_000_000_456),
+        };
+        let proto: PeerEnrollment = Clone::clone(&native).into();
+
+        assert_that!(
+            crate::peer::history::PeerEnrollment::try_from(proto),
+            ok(eq(&native))
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,125 @@
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.plugin.rs"));
+
+impl From<crate::plugin::PluginId> for PluginId {
+    fn from(value: crate::plugin::PluginId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<PluginId> for crate::plugin::PluginId {
+    type Error = ConversionError;
+
+    fn try_from(value: PluginId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PluginId, crate::plugin::PluginId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::plugin::PluginKind> for PluginKind {
+    fn from(value: crate::plugin::PluginKind) -> Self {
+        let inner = match value {
+            crate::plugin::PluginKind::Setup => plugin_kind::Inner::Setup(PluginKindSetup {}),
+            crate::plugin::PluginKind::Service => plugin_kind::Inner::Service(PluginKindService {}),
+        };
+        Self { inner: Some(inner) }
+    }
+}
+
+impl TryFrom<PluginKind> for crate::plugin::PluginKind {
+    type Error = ConversionError;
+
+    fn try_from(value: PluginKind) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PluginKind, crate::plugin::PluginKind>;
+
+        match value.inner.ok_or(ErrorBuilder::field_not_set("inner"))? {
+            plugin_kind::Inner::Setup(_) => Ok(Self::Setup),
+            plugin_kind::Inner::Service(_) => Ok(Self::Service),
+        }
+    }
+}
+
+impl From<crate::plugin::PluginDescriptor> for PluginDescriptor {
+    fn from(value: crate::plugin::PluginDescriptor) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            name: value.name,
+            version: value.version,
+            kind: Some(value.kind.into()),
+            checksum: value.checksum,
+            manifest: value.manifest,
+            signature: value.signature,
+            uploaded_by: value.uploaded_by,
+            uploaded_at: Some(value.uploaded_at.into()),
+        }
+    }
+}
+
+impl TryFrom<PluginDescriptor> for crate::plugin::PluginDescriptor {
+    type Error = ConversionError;
+
+    fn try_from(value: PluginDescriptor) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PluginDescriptor, crate::plugin::PluginDescriptor>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+        let kind = value.kind
+            .ok_or(ErrorBuilder::field_not_set("kind"))?
+            .try_into()?;
+        let uploaded_at = value.uploaded_at
+            .ok_or(ErrorBuilder::field_not_set("uploaded_at"))?
+            .into();
+
+        Ok(Self {
+            id,
+            name: value.name,
+            version: value.version,
+            kind,
+            checksum: value.checksum,
+            manifest: value.manifest,
+            signature: value.signature,
+            uploaded_by: value.uploaded_by,
+            uploaded_at,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn A_PluginDescriptor_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+        let native = crate::plugin::PluginDescriptor {
+            id: crate::plugin::PluginId::random(),
+            name: String::from("can-setup"),
+            version: String::from("1.0.0"),
+            kind: crate::plugin::PluginKind::Service,
+            checksum: vec![1, 2, 3],
+            manifest: Some(String::from("[capabilities]\nnetwork = true")),
+            signature: vec![4, 5, 6],
+            uploaded_by: Some(String::from("testUser")),
+            uploaded_at: SystemTime::UNIX_EPOCH + Duration::from_millis(1_728_000_000_456),
+        };
+        let proto: PluginDescriptor = Clone::clone(&native).into();
+
+        assert_that!(
+            crate::plugin::PluginDescriptor::try_from(proto),
+            ok(eq(&native))
+        );
+
+        Ok(())
+    }
+}
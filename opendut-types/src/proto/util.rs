@@ -20,6 +20,21 @@ impl From<Uuid> for uuid::Uuid {
     }
 }
 
+impl From<std::time::SystemTime> for Timestamp {
+    fn from(value: std::time::SystemTime) -> Self {
+        let unix_millis = value.duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX))
+            .unwrap_or_default(); //timestamps before the epoch are not expected
+        Self { unix_millis }
+    }
+}
+
+impl From<Timestamp> for std::time::SystemTime {
+    fn from(value: Timestamp) -> Self {
+        std::time::UNIX_EPOCH + std::time::Duration::from_millis(value.unix_millis)
+    }
+}
+
 impl From<String> for Hostname {
     fn from(value: String) -> Self {
         Self { value }
@@ -134,6 +149,31 @@ impl TryFrom<IpAddress> for std::net::IpAddr {
     }
 }
 
+impl From<crate::util::net::IpNetwork> for IpNetwork {
+    fn from(value: crate::util::net::IpNetwork) -> Self {
+        Self {
+            address: Some(value.address().into()),
+            prefix_length: u32::from(value.prefix_length()),
+        }
+    }
+}
+impl TryFrom<IpNetwork> for crate::util::net::IpNetwork {
+    type Error = ConversionError;
+
+    fn try_from(value: IpNetwork) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<IpNetwork, crate::util::net::IpNetwork>;
+
+        let address = value.address
+            .ok_or(ErrorBuilder::field_not_set("address"))?
+            .try_into()?;
+        let prefix_length = u8::try_from(value.prefix_length)
+            .map_err(|cause| ErrorBuilder::message(format!("Prefix length '{}' is out of range: {cause}", value.prefix_length)))?;
+
+        crate::util::net::IpNetwork::new(address, prefix_length)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
 impl From<std::net::Ipv4Addr> for IpV4Address {
     fn from(value: std::net::Ipv4Addr) -> Self {
         Self {
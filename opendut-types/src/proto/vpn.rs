@@ -22,6 +22,27 @@ impl From<crate::vpn::VpnPeerConfiguration> for VpnPeerConfig {
                     ))
                 }
             }
+            crate::vpn::VpnPeerConfiguration::WireGuard { private_key, address, prefix_length, listen_port } => {
+                VpnPeerConfig {
+                    config: Some(vpn_peer_config::Config::WireGuard(
+                        VpnPeerConfigWireGuard {
+                            private_key: Some(private_key.into()),
+                            address: Some(address.into()),
+                            prefix_length: u32::from(prefix_length),
+                            listen_port: u32::from(listen_port),
+                        }
+                    ))
+                }
+            }
+            crate::vpn::VpnPeerConfiguration::Direct { network } => {
+                VpnPeerConfig {
+                    config: Some(vpn_peer_config::Config::Direct(
+                        VpnPeerConfigDirect {
+                            network: network.map(Into::into),
+                        }
+                    ))
+                }
+            }
         }
     }
 }
@@ -52,6 +73,34 @@ impl TryFrom<VpnPeerConfig> for crate::vpn::VpnPeerConfiguration {
                     setup_key,
                 }
             },
+            vpn_peer_config::Config::WireGuard(config) => {
+                let VpnPeerConfigWireGuard { private_key, address, prefix_length, listen_port } = config;
+                let private_key = private_key
+                    .ok_or(ErrorBuilder::field_not_set("private_key"))?
+                    .try_into()?;
+                let address = address
+                    .ok_or(ErrorBuilder::field_not_set("address"))?
+                    .try_into()?;
+                let prefix_length = u8::try_from(prefix_length)
+                    .map_err(|cause| ErrorBuilder::message(format!("Prefix length '{prefix_length}' is out of range: {cause}")))?;
+                let listen_port = u16::try_from(listen_port)
+                    .map_err(|cause| ErrorBuilder::message(format!("Listen port '{listen_port}' is out of range: {cause}")))?;
+                crate::vpn::VpnPeerConfiguration::WireGuard {
+                    private_key,
+                    address,
+                    prefix_length,
+                    listen_port,
+                }
+            },
+            vpn_peer_config::Config::Direct(config) => {
+                let VpnPeerConfigDirect { network } = config;
+                let network = network
+                    .map(TryInto::try_into)
+                    .transpose()?;
+                crate::vpn::VpnPeerConfiguration::Direct {
+                    network,
+                }
+            },
         };
 
         Ok(result)
@@ -77,3 +126,282 @@ impl TryFrom<SetupKey> for crate::vpn::netbird::SetupKey {
         Ok(result)
     }
 }
+
+impl From<crate::vpn::wireguard::WireGuardPrivateKey> for WireGuardPrivateKey {
+    fn from(value: crate::vpn::wireguard::WireGuardPrivateKey) -> Self {
+        Self { value: value.as_bytes().to_vec() }
+    }
+}
+
+impl TryFrom<WireGuardPrivateKey> for crate::vpn::wireguard::WireGuardPrivateKey {
+    type Error = ConversionError;
+
+    fn try_from(value: WireGuardPrivateKey) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<WireGuardPrivateKey, crate::vpn::wireguard::WireGuardPrivateKey>;
+
+        let bytes = <[u8; crate::vpn::wireguard::KEY_LENGTH]>::try_from(value.value.as_slice())
+            .map_err(|_| ErrorBuilder::message(format!("WireGuard key must be {} bytes long, but was {} bytes long.", crate::vpn::wireguard::KEY_LENGTH, value.value.len())))?;
+        Ok(crate::vpn::wireguard::WireGuardPrivateKey::from_bytes(bytes))
+    }
+}
+
+impl From<crate::vpn::wireguard::WireGuardPublicKey> for WireGuardPublicKey {
+    fn from(value: crate::vpn::wireguard::WireGuardPublicKey) -> Self {
+        Self { value: value.as_bytes().to_vec() }
+    }
+}
+
+impl TryFrom<WireGuardPublicKey> for crate::vpn::wireguard::WireGuardPublicKey {
+    type Error = ConversionError;
+
+    fn try_from(value: WireGuardPublicKey) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<WireGuardPublicKey, crate::vpn::wireguard::WireGuardPublicKey>;
+
+        let bytes = <[u8; crate::vpn::wireguard::KEY_LENGTH]>::try_from(value.value.as_slice())
+            .map_err(|_| ErrorBuilder::message(format!("WireGuard key must be {} bytes long, but was {} bytes long.", crate::vpn::wireguard::KEY_LENGTH, value.value.len())))?;
+        Ok(crate::vpn::wireguard::WireGuardPublicKey::from_bytes(bytes))
+    }
+}
+
+impl From<crate::vpn::wireguard::WireGuardPeer> for WireGuardPeer {
+    fn from(value: crate::vpn::wireguard::WireGuardPeer) -> Self {
+        Self {
+            public_key: Some(value.public_key.into()),
+            allowed_ip: Some(value.allowed_ip.into()),
+            endpoint: value.endpoint.map(|endpoint| endpoint.to_string()),
+        }
+    }
+}
+
+impl TryFrom<WireGuardPeer> for crate::vpn::wireguard::WireGuardPeer {
+    type Error = ConversionError;
+
+    fn try_from(value: WireGuardPeer) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<WireGuardPeer, crate::vpn::wireguard::WireGuardPeer>;
+
+        let public_key = value.public_key
+            .ok_or(ErrorBuilder::field_not_set("public_key"))?
+            .try_into()?;
+        let allowed_ip = value.allowed_ip
+            .ok_or(ErrorBuilder::field_not_set("allowed_ip"))?
+            .try_into()?;
+        let endpoint = value.endpoint
+            .map(|endpoint| endpoint.parse::<std::net::SocketAddr>()
+                .map_err(|cause| ErrorBuilder::message(format!("Endpoint '{endpoint}' is not a valid socket address: {cause}")))
+            )
+            .transpose()?;
+
+        Ok(crate::vpn::wireguard::WireGuardPeer {
+            public_key,
+            allowed_ip,
+            endpoint,
+        })
+    }
+}
+
+impl From<crate::vpn::reconciliation::VpnReconciliationReport> for VpnReconciliationReport {
+    fn from(value: crate::vpn::reconciliation::VpnReconciliationReport) -> Self {
+        Self {
+            dry_run: value.dry_run,
+            drifts: value.drifts.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<VpnReconciliationReport> for crate::vpn::reconciliation::VpnReconciliationReport {
+    type Error = ConversionError;
+
+    fn try_from(value: VpnReconciliationReport) -> Result<Self, Self::Error> {
+        let drifts = value.drifts.into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            dry_run: value.dry_run,
+            drifts,
+        })
+    }
+}
+
+impl From<crate::vpn::reconciliation::VpnDriftEntry> for VpnDriftEntry {
+    fn from(value: crate::vpn::reconciliation::VpnDriftEntry) -> Self {
+        Self {
+            drift: Some(value.drift.into()),
+            resolution: Some(value.resolution.into()),
+        }
+    }
+}
+
+impl TryFrom<VpnDriftEntry> for crate::vpn::reconciliation::VpnDriftEntry {
+    type Error = ConversionError;
+
+    fn try_from(value: VpnDriftEntry) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<VpnDriftEntry, crate::vpn::reconciliation::VpnDriftEntry>;
+
+        let drift = value.drift
+            .ok_or(ErrorBuilder::field_not_set("drift"))?
+            .try_into()?;
+        let resolution = value.resolution
+            .ok_or(ErrorBuilder::field_not_set("resolution"))?
+            .try_into()?;
+
+        Ok(Self { drift, resolution })
+    }
+}
+
+impl From<crate::vpn::reconciliation::VpnDrift> for VpnDrift {
+    fn from(value: crate::vpn::reconciliation::VpnDrift) -> Self {
+        let kind = match value {
+            crate::vpn::reconciliation::VpnDrift::OrphanedCluster { cluster_id } => {
+                vpn_drift::Kind::OrphanedCluster(VpnDriftOrphanedCluster { cluster_id: Some(cluster_id.into()) })
+            }
+            crate::vpn::reconciliation::VpnDrift::MissingCluster { cluster_id } => {
+                vpn_drift::Kind::MissingCluster(VpnDriftMissingCluster { cluster_id: Some(cluster_id.into()) })
+            }
+            crate::vpn::reconciliation::VpnDrift::OrphanedPeer { peer_id } => {
+                vpn_drift::Kind::OrphanedPeer(VpnDriftOrphanedPeer { peer_id: Some(peer_id.into()) })
+            }
+            crate::vpn::reconciliation::VpnDrift::MissingPeer { peer_id } => {
+                vpn_drift::Kind::MissingPeer(VpnDriftMissingPeer { peer_id: Some(peer_id.into()) })
+            }
+        };
+        Self { kind: Some(kind) }
+    }
+}
+
+impl TryFrom<VpnDrift> for crate::vpn::reconciliation::VpnDrift {
+    type Error = ConversionError;
+
+    fn try_from(value: VpnDrift) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<VpnDrift, crate::vpn::reconciliation::VpnDrift>;
+
+        let kind = value.kind
+            .ok_or(ErrorBuilder::field_not_set("kind"))?;
+
+        let result = match kind {
+            vpn_drift::Kind::OrphanedCluster(VpnDriftOrphanedCluster { cluster_id }) => {
+                let cluster_id = cluster_id.ok_or(ErrorBuilder::field_not_set("cluster_id"))?.try_into()?;
+                crate::vpn::reconciliation::VpnDrift::OrphanedCluster { cluster_id }
+            }
+            vpn_drift::Kind::MissingCluster(VpnDriftMissingCluster { cluster_id }) => {
+                let cluster_id = cluster_id.ok_or(ErrorBuilder::field_not_set("cluster_id"))?.try_into()?;
+                crate::vpn::reconciliation::VpnDrift::MissingCluster { cluster_id }
+            }
+            vpn_drift::Kind::OrphanedPeer(VpnDriftOrphanedPeer { peer_id }) => {
+                let peer_id = peer_id.ok_or(ErrorBuilder::field_not_set("peer_id"))?.try_into()?;
+                crate::vpn::reconciliation::VpnDrift::OrphanedPeer { peer_id }
+            }
+            vpn_drift::Kind::MissingPeer(VpnDriftMissingPeer { peer_id }) => {
+                let peer_id = peer_id.ok_or(ErrorBuilder::field_not_set("peer_id"))?.try_into()?;
+                crate::vpn::reconciliation::VpnDrift::MissingPeer { peer_id }
+            }
+        };
+
+        Ok(result)
+    }
+}
+
+impl From<crate::vpn::reconciliation::VpnDriftResolution> for VpnDriftResolution {
+    fn from(value: crate::vpn::reconciliation::VpnDriftResolution) -> Self {
+        let kind = match value {
+            crate::vpn::reconciliation::VpnDriftResolution::Reported => {
+                vpn_drift_resolution::Kind::Reported(VpnDriftResolutionReported {})
+            }
+            crate::vpn::reconciliation::VpnDriftResolution::Removed => {
+                vpn_drift_resolution::Kind::Removed(VpnDriftResolutionRemoved {})
+            }
+            crate::vpn::reconciliation::VpnDriftResolution::RemovalFailed { cause } => {
+                vpn_drift_resolution::Kind::RemovalFailed(VpnDriftResolutionRemovalFailed { cause })
+            }
+        };
+        Self { kind: Some(kind) }
+    }
+}
+
+impl TryFrom<VpnDriftResolution> for crate::vpn::reconciliation::VpnDriftResolution {
+    type Error = ConversionError;
+
+    fn try_from(value: VpnDriftResolution) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<VpnDriftResolution, crate::vpn::reconciliation::VpnDriftResolution>;
+
+        let kind = value.kind
+            .ok_or(ErrorBuilder::field_not_set("kind"))?;
+
+        let result = match kind {
+            vpn_drift_resolution::Kind::Reported(_) => crate::vpn::reconciliation::VpnDriftResolution::Reported,
+            vpn_drift_resolution::Kind::Removed(_) => crate::vpn::reconciliation::VpnDriftResolution::Removed,
+            vpn_drift_resolution::Kind::RemovalFailed(VpnDriftResolutionRemovalFailed { cause }) => {
+                crate::vpn::reconciliation::VpnDriftResolution::RemovalFailed { cause }
+            }
+        };
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use std::net::IpAddr;
+    use std::str::FromStr;
+
+    use googletest::prelude::*;
+
+    use crate::vpn::wireguard::{WireGuardPrivateKey, KEY_LENGTH};
+
+    use super::*;
+
+    #[test]
+    fn A_WireGuardVpnPeerConfiguration_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+        let native = crate::vpn::VpnPeerConfiguration::WireGuard {
+            private_key: WireGuardPrivateKey::from_bytes([42; KEY_LENGTH]),
+            address: IpAddr::from_str("100.64.0.12")?,
+            prefix_length: 10,
+            listen_port: 51820,
+        };
+
+        let proto = VpnPeerConfig::from(Clone::clone(&native));
+        let result = crate::vpn::VpnPeerConfiguration::try_from(proto)?;
+
+        assert_that!(result, eq(&native));
+        Ok(())
+    }
+
+    #[test]
+    fn A_DirectVpnPeerConfiguration_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+        let native = crate::vpn::VpnPeerConfiguration::Direct {
+            network: Some(crate::util::net::IpNetwork::from_str("192.168.56.0/24")?),
+        };
+
+        let proto = VpnPeerConfig::from(Clone::clone(&native));
+        let result = crate::vpn::VpnPeerConfiguration::try_from(proto)?;
+
+        assert_that!(result, eq(&native));
+        Ok(())
+    }
+
+    #[test]
+    fn A_VpnReconciliationReport_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+        use crate::vpn::reconciliation::{VpnDrift, VpnDriftEntry, VpnDriftResolution};
+
+        let native = crate::vpn::reconciliation::VpnReconciliationReport {
+            dry_run: false,
+            drifts: vec![
+                VpnDriftEntry {
+                    drift: VpnDrift::OrphanedCluster { cluster_id: crate::cluster::ClusterId::random() },
+                    resolution: VpnDriftResolution::RemovalFailed { cause: String::from("Connection refused") },
+                },
+                VpnDriftEntry {
+                    drift: VpnDrift::MissingPeer { peer_id: crate::peer::PeerId::random() },
+                    resolution: VpnDriftResolution::Reported,
+                },
+            ],
+        };
+
+        let proto = VpnReconciliationReport::from(Clone::clone(&native));
+        let result = crate::vpn::reconciliation::VpnReconciliationReport::try_from(proto)?;
+
+        assert_that!(result, eq(&native));
+        Ok(())
+    }
+}
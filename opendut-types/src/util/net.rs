@@ -1,5 +1,6 @@
 use std::fmt;
 use std::fmt::{Debug, Formatter};
+use std::net::IpAddr;
 use std::ops::Not;
 use std::str::FromStr;
 use serde::{Deserialize, Serialize};
@@ -440,13 +441,100 @@ impl AuthConfig {
     }
 }
 
+/// Network in CIDR notation, e.g. `192.168.56.0/24`.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IpNetwork {
+    address: IpAddr,
+    prefix_length: u8,
+}
+impl IpNetwork {
+    pub fn new(address: IpAddr, prefix_length: u8) -> Result<Self, IllegalIpNetwork> {
+        let max_prefix_length = match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if prefix_length > max_prefix_length {
+            Err(IllegalIpNetwork::PrefixLengthTooLong { value: prefix_length, max: max_prefix_length })
+        } else {
+            Ok(Self { address, prefix_length })
+        }
+    }
+
+    pub fn address(&self) -> IpAddr {
+        self.address
+    }
+
+    pub fn prefix_length(&self) -> u8 {
+        self.prefix_length
+    }
+
+    pub fn contains(&self, address: &IpAddr) -> bool {
+        match (self.address, address) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(self.prefix_length)).unwrap_or(0);
+                u32::from(network) & mask == u32::from(*address) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(self.prefix_length)).unwrap_or(0);
+                u128::from(network) & mask == u128::from(*address) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix_length)
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = IllegalIpNetwork;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (address, prefix_length) = value.trim().split_once('/')
+            .ok_or_else(|| IllegalIpNetwork::InvalidFormat { value: value.to_owned() })?;
+
+        let address = IpAddr::from_str(address)
+            .map_err(|_| IllegalIpNetwork::InvalidFormat { value: value.to_owned() })?;
+        let prefix_length = u8::from_str(prefix_length)
+            .map_err(|_| IllegalIpNetwork::InvalidFormat { value: value.to_owned() })?;
+
+        Self::new(address, prefix_length)
+    }
+}
+
+impl TryFrom<String> for IpNetwork {
+    type Error = IllegalIpNetwork;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
+impl From<IpNetwork> for String {
+    fn from(value: IpNetwork) -> Self {
+        value.to_string()
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalIpNetwork {
+    #[error("Network '{value}' is not in CIDR notation, e.g. '192.168.56.0/24'.")]
+    InvalidFormat { value: String },
+    #[error("Prefix length {value} of network is longer than {max}.")]
+    PrefixLengthTooLong { value: u8, max: u8 },
+}
+
 #[cfg(test)]
 mod tests {
     use googletest::assert_that;
     use googletest::matchers::eq;
     use url::Url;
 
-    use crate::util::net::{AuthConfig, ClientCredentials, ClientId, ClientSecret, OAuthScope};
+    use crate::util::net::{AuthConfig, ClientCredentials, ClientId, ClientSecret, IpNetwork, OAuthScope};
 
     #[test]
     pub fn test_create_auth_config() {
@@ -464,4 +552,15 @@ mod tests {
             scopes: expected_scopes,
         }));
     }
+
+    #[test]
+    pub fn test_ip_network_contains_address() -> googletest::Result<()> {
+        let network: IpNetwork = "192.168.56.0/24".parse()?;
+
+        assert_that!(network.contains(&"192.168.56.12".parse()?), eq(true));
+        assert_that!(network.contains(&"192.168.57.12".parse()?), eq(false));
+        assert_that!(network.contains(&"::1".parse()?), eq(false));
+        assert_that!(network.to_string(), eq("192.168.56.0/24"));
+        Ok(())
+    }
 }
@@ -1,6 +1,13 @@
+use std::net::IpAddr;
+
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use crate::util::net::IpNetwork;
+
+pub mod reconciliation;
+pub mod wireguard;
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum VpnPeerConfiguration {
@@ -9,7 +16,22 @@ pub enum VpnPeerConfiguration {
     Netbird {
         management_url: Url,
         setup_key: netbird::SetupKey,
-    }
+    },
+    /// Peer keys and addresses are assigned by CARL. EDGAR configures the WireGuard interface itself.
+    #[serde(rename_all = "kebab-case")]
+    WireGuard {
+        private_key: wireguard::WireGuardPrivateKey,
+        address: IpAddr,
+        /// Prefix length of the VPN network, which is routed via the WireGuard interface.
+        prefix_length: u8,
+        listen_port: u16,
+    },
+    /// The peers reach each other directly via a shared network, e.g. in an isolated lab, so no VPN software is used.
+    #[serde(rename_all = "kebab-case")]
+    Direct {
+        /// Network, in which EDGAR looks for the address it reports to CARL. If not set, the address used for reaching CARL is reported.
+        network: Option<IpNetwork>,
+    },
 }
 
 pub mod netbird {
@@ -0,0 +1,73 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cluster::ClusterId;
+use crate::peer::PeerId;
+
+/// Difference between the resources known to CARL and the resources in the VPN service.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", tag = "kind")]
+pub enum VpnDrift {
+    /// A cluster exists in the VPN service, but is not deployed in CARL, e.g. because CARL crashed while deleting its deployment.
+    #[serde(rename_all = "kebab-case")]
+    OrphanedCluster { cluster_id: ClusterId },
+    /// A cluster is deployed in CARL, but does not exist in the VPN service. Deploying the cluster again recreates it.
+    #[serde(rename_all = "kebab-case")]
+    MissingCluster { cluster_id: ClusterId },
+    /// A peer exists in the VPN service, but not in CARL, e.g. because CARL crashed while deleting it.
+    #[serde(rename_all = "kebab-case")]
+    OrphanedPeer { peer_id: PeerId },
+    /// A peer exists in CARL, but not in the VPN service. Generating a new PeerSetup for the peer recreates it.
+    #[serde(rename_all = "kebab-case")]
+    MissingPeer { peer_id: PeerId },
+}
+
+impl VpnDrift {
+    /// Orphaned resources are not needed anymore and can be removed from the VPN service.
+    pub fn is_orphan(&self) -> bool {
+        matches!(self, VpnDrift::OrphanedCluster { .. } | VpnDrift::OrphanedPeer { .. })
+    }
+}
+
+impl fmt::Display for VpnDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VpnDrift::OrphanedCluster { cluster_id } => write!(f, "Cluster <{cluster_id}> exists in the VPN service, but is not deployed."),
+            VpnDrift::MissingCluster { cluster_id } => write!(f, "Cluster <{cluster_id}> is deployed, but does not exist in the VPN service."),
+            VpnDrift::OrphanedPeer { peer_id } => write!(f, "Peer <{peer_id}> exists in the VPN service, but not in CARL."),
+            VpnDrift::MissingPeer { peer_id } => write!(f, "Peer <{peer_id}> exists in CARL, but not in the VPN service."),
+        }
+    }
+}
+
+/// What was done about a drift during a reconciliation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", tag = "kind")]
+pub enum VpnDriftResolution {
+    /// The drift was only reported, because it cannot be resolved automatically or because it was a dry run.
+    Reported,
+    /// The orphaned resource was removed from the VPN service.
+    Removed,
+    RemovalFailed { cause: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VpnDriftEntry {
+    pub drift: VpnDrift,
+    pub resolution: VpnDriftResolution,
+}
+
+/// Result of comparing the clusters and peers in CARL with the resources in the VPN service.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VpnReconciliationReport {
+    /// Whether orphaned resources were only reported, instead of being removed.
+    pub dry_run: bool,
+    pub drifts: Vec<VpnDriftEntry>,
+}
+
+impl VpnReconciliationReport {
+    pub fn is_in_sync(&self) -> bool {
+        self.drifts.is_empty()
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::str::FromStr;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::peer::PeerId;
+
+/// Length of WireGuard keys in bytes.
+pub const KEY_LENGTH: usize = 32;
+
+/// Private key of the WireGuard interface of a peer.
+/// Represented as Base64 string, like in the configuration files of WireGuard.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct WireGuardPrivateKey([u8; KEY_LENGTH]);
+
+impl WireGuardPrivateKey {
+    pub fn from_bytes(bytes: [u8; KEY_LENGTH]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; KEY_LENGTH] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for WireGuardPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WireGuardPrivateKey(<redacted>)")
+    }
+}
+
+impl TryFrom<String> for WireGuardPrivateKey {
+    type Error = IllegalWireGuardKey;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        decode_key(&value).map(Self)
+    }
+}
+
+impl TryFrom<&str> for WireGuardPrivateKey {
+    type Error = IllegalWireGuardKey;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        decode_key(value).map(Self)
+    }
+}
+
+impl FromStr for WireGuardPrivateKey {
+    type Err = IllegalWireGuardKey;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        WireGuardPrivateKey::try_from(value)
+    }
+}
+
+impl From<WireGuardPrivateKey> for String {
+    fn from(value: WireGuardPrivateKey) -> Self {
+        BASE64_STANDARD.encode(value.0)
+    }
+}
+
+/// Public key of the WireGuard interface of a peer.
+/// Represented as Base64 string, like in the configuration files of WireGuard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct WireGuardPublicKey([u8; KEY_LENGTH]);
+
+impl WireGuardPublicKey {
+    pub fn from_bytes(bytes: [u8; KEY_LENGTH]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; KEY_LENGTH] {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for WireGuardPublicKey {
+    type Error = IllegalWireGuardKey;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        decode_key(&value).map(Self)
+    }
+}
+
+impl TryFrom<&str> for WireGuardPublicKey {
+    type Error = IllegalWireGuardKey;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        decode_key(value).map(Self)
+    }
+}
+
+impl FromStr for WireGuardPublicKey {
+    type Err = IllegalWireGuardKey;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        WireGuardPublicKey::try_from(value)
+    }
+}
+
+impl From<WireGuardPublicKey> for String {
+    fn from(value: WireGuardPublicKey) -> Self {
+        BASE64_STANDARD.encode(value.0)
+    }
+}
+
+impl fmt::Display for WireGuardPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", BASE64_STANDARD.encode(self.0))
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalWireGuardKey {
+    #[error("WireGuard key is not valid Base64: {cause}")]
+    InvalidEncoding { cause: String },
+    #[error("WireGuard key must be {expected} bytes long, but was {actual} bytes long.", expected = KEY_LENGTH)]
+    InvalidLength { actual: usize },
+}
+
+fn decode_key(value: &str) -> Result<[u8; KEY_LENGTH], IllegalWireGuardKey> {
+    let bytes = BASE64_STANDARD.decode(value.trim())
+        .map_err(|cause| IllegalWireGuardKey::InvalidEncoding { cause: cause.to_string() })?;
+
+    <[u8; KEY_LENGTH]>::try_from(bytes.as_slice())
+        .map_err(|_| IllegalWireGuardKey::InvalidLength { actual: bytes.len() })
+}
+
+/// Another peer of the same cluster, to which a WireGuard tunnel is established.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WireGuardPeer {
+    pub public_key: WireGuardPublicKey,
+    /// Address of the peer within the VPN. Traffic to this address is sent through the tunnel.
+    pub allowed_ip: IpAddr,
+    /// Address, under which the peer is reachable from outside the VPN.
+    /// Without an endpoint, the tunnel is only established once the other peer connects.
+    pub endpoint: Option<SocketAddr>,
+}
+
+/// Address and key nonce of a peer within a WireGuard VPN coordinated by CARL.
+/// Both are allocated by CARL when a peer is created, so that no two peers share an address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WireGuardPeerAllocation {
+    pub peer_id: PeerId,
+    pub address: Ipv4Addr,
+    /// Random value, which is included when deriving the keys of the peer.
+    /// A new value is chosen when the peer is re-enrolled, so that its previous keys become invalid.
+    pub key_nonce: Uuid,
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_roundtrip_a_key_via_base64() -> Result<()> {
+        let key = WireGuardPublicKey::from_bytes([7; KEY_LENGTH]);
+
+        let encoded = String::from(key);
+        let decoded = WireGuardPublicKey::try_from(encoded)?;
+
+        assert_that!(decoded, eq(key));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_a_key_with_invalid_length() -> Result<()> {
+        let encoded = BASE64_STANDARD.encode([7; 16]);
+
+        let result = WireGuardPrivateKey::try_from(encoded);
+
+        assert_that!(result, err(matches_pattern!(IllegalWireGuardKey::InvalidLength { actual: eq(&16) })));
+        Ok(())
+    }
+}
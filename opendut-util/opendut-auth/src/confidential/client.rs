@@ -139,6 +139,11 @@ impl ConfidentialClient {
         Ok(access_token)
     }
 
+    /// Discards the cached token and fetches a new one, e.g. after the token was rejected by a server.
+    pub async fn refresh_token(&self) -> Result<Token, AuthError> {
+        self.fetch_token().await
+    }
+
     pub async fn check_login(&self) -> Result<bool, AuthError> {
         let token = self.get_token().await?;
         Ok(!token.value.is_empty())
@@ -1,10 +1,12 @@
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use opentelemetry::KeyValue;
 use opentelemetry::metrics::{MeterProvider, MetricsError};
-use opentelemetry_otlp::WithExportConfig;
-use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_otlp::{MetricsExporterBuilder, WithExportConfig};
+use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+use opentelemetry_sdk::metrics::reader::{DefaultAggregationSelector, DefaultTemporalitySelector};
 use opentelemetry_sdk::{Resource, runtime};
+use prometheus::{Registry, TextEncoder};
 use simple_moving_average::{SMA, SumTreeSMA};
 use sysinfo::{Pid, System};
 use tokio::sync::Mutex;
@@ -13,15 +15,31 @@ use opendut_auth::confidential::blocking::client::{ConfClientArcMutex, Confident
 use crate::telemetry::DEFAULT_METER_NAME;
 use crate::telemetry::opentelemetry_types::Endpoint;
 
-pub fn init_metrics(telemetry_interceptor: ConfClientArcMutex<Option<ConfidentialClientRef>>, endpoint: &Endpoint, service_name: impl Into<String>, service_instance_id: impl Into<String>, metrics_interval: Duration) -> Result<SdkMeterProvider, MetricsError> {
-    opentelemetry_otlp::new_pipeline()
-        .metrics(runtime::Tokio)
-        .with_exporter(
-            opentelemetry_otlp::new_exporter()
-                .tonic()
-                .with_interceptor(telemetry_interceptor)
-                .with_endpoint(Clone::clone(&endpoint.url))
-        )
+static PROMETHEUS_REGISTRY: OnceLock<Registry> = OnceLock::new();
+
+/// Returns the registry, which the meters are exported into, if the Prometheus exporter is enabled.
+pub fn prometheus_registry() -> Option<&'static Registry> {
+    PROMETHEUS_REGISTRY.get()
+}
+
+pub(crate) fn init_prometheus_registry() -> Registry {
+    Clone::clone(PROMETHEUS_REGISTRY.get_or_init(Registry::new))
+}
+
+/// Encodes the current values of all meters in the Prometheus text exposition format.
+pub fn encode_prometheus_metrics(registry: &Registry) -> Result<String, prometheus::Error> {
+    TextEncoder::new().encode_to_string(&registry.gather())
+}
+
+pub fn init_metrics(
+    telemetry_interceptor: ConfClientArcMutex<Option<ConfidentialClientRef>>,
+    endpoint: Option<&Endpoint>,
+    prometheus_exporter: Option<opentelemetry_prometheus::ExporterBuilder>,
+    service_name: impl Into<String>,
+    service_instance_id: impl Into<String>,
+    metrics_interval: Duration,
+) -> Result<SdkMeterProvider, MetricsError> {
+    let mut meter_provider = SdkMeterProvider::builder()
         .with_resource(Resource::new(vec![
             KeyValue::new(
                 opentelemetry_semantic_conventions::resource::SERVICE_NAME,
@@ -31,9 +49,29 @@ pub fn init_metrics(telemetry_interceptor: ConfClientArcMutex<Option<Confidentia
                 opentelemetry_semantic_conventions::resource::SERVICE_INSTANCE_ID,
                 service_instance_id.into()
             ),
-        ]))
-        .with_period(metrics_interval)
-        .build()
+        ]));
+
+    if let Some(endpoint) = endpoint {
+        let exporter = MetricsExporterBuilder::from(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_interceptor(telemetry_interceptor)
+                .with_endpoint(Clone::clone(&endpoint.url))
+        ).build_metrics_exporter(
+            Box::new(DefaultTemporalitySelector::new()),
+            Box::new(DefaultAggregationSelector::new()),
+        )?;
+        let reader = PeriodicReader::builder(exporter, runtime::Tokio)
+            .with_interval(metrics_interval)
+            .build();
+        meter_provider = meter_provider.with_reader(reader);
+    }
+
+    if let Some(prometheus_exporter) = prometheus_exporter {
+        meter_provider = meter_provider.with_reader(prometheus_exporter.build()?);
+    }
+
+    Ok(meter_provider.build())
 }
 
 pub fn initialize_metrics_collection(
@@ -96,3 +134,63 @@ pub struct NamedMeterProviderKindDefault;
 impl NamedMeterProviderKind for NamedMeterProviderKindDefault {}
 pub struct NamedMeterProviderKindCpu;
 impl NamedMeterProviderKind for NamedMeterProviderKindCpu {}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use url::Url;
+
+    use super::*;
+
+    fn no_confidential_client() -> ConfClientArcMutex<Option<ConfidentialClientRef>> {
+        ConfClientArcMutex(Arc::new(Mutex::new(None)))
+    }
+
+    fn record_test_counter(meter_provider: &SdkMeterProvider) {
+        let counter = meter_provider.meter("test").u64_counter("test_counter").init();
+        counter.add(3, &[]);
+    }
+
+    #[test]
+    fn should_export_metrics_via_prometheus_without_a_collector_endpoint() -> anyhow::Result<()> {
+        let registry = Registry::new();
+
+        let meter_provider = init_metrics(
+            no_confidential_client(),
+            None,
+            Some(opentelemetry_prometheus::exporter().with_registry(Clone::clone(&registry))),
+            "opendut-test",
+            "test-instance",
+            Duration::from_secs(60),
+        )?;
+        record_test_counter(&meter_provider);
+
+        let metrics = encode_prometheus_metrics(&registry)?;
+
+        assert_that!(metrics, contains_substring("test_counter_total{otel_scope_name=\"test\"} 3"));
+        assert_that!(metrics, contains_substring("service_instance_id=\"test-instance\""));
+        assert_that!(metrics, contains_substring("service_name=\"opendut-test\""));
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_export_metrics_via_prometheus_alongside_a_collector_endpoint() -> anyhow::Result<()> {
+        let registry = Registry::new();
+        let endpoint = Endpoint { url: Url::parse("http://localhost:4317")? };
+
+        let meter_provider = init_metrics(
+            no_confidential_client(),
+            Some(&endpoint),
+            Some(opentelemetry_prometheus::exporter().with_registry(Clone::clone(&registry))),
+            "opendut-test",
+            "test-instance",
+            Duration::from_secs(60),
+        )?;
+        record_test_counter(&meter_provider);
+
+        let metrics = encode_prometheus_metrics(&registry)?;
+
+        assert_that!(metrics, contains_substring("test_counter_total{otel_scope_name=\"test\"} 3"));
+        Ok(())
+    }
+}
@@ -89,6 +89,7 @@ pub async fn initialize_with_config(
     let (tracer, logger, logger_layer, meter_providers) =
         if let Opentelemetry::Enabled {
             collector_endpoint,
+            prometheus_enabled,
             service_name, 
             service_instance_id, 
             metrics_interval_ms, 
@@ -97,18 +98,30 @@ pub async fn initialize_with_config(
 
             let confidential_client = ConfClientArcMutex(Arc::new(Mutex::new(confidential_client)));
 
-            let tracer = traces::init_tracer(confidential_client.clone(), &collector_endpoint, service_name.clone(), service_instance_id.clone()).expect("Failed to initialize tracer.");
-            
-            let logger = logging::init_logger(confidential_client.clone(), &collector_endpoint, service_name.clone(), service_instance_id.clone()).expect("Failed to initialize logs.");
+            let tracer = collector_endpoint.as_ref().map(|collector_endpoint|
+                traces::init_tracer(confidential_client.clone(), collector_endpoint, service_name.clone(), service_instance_id.clone()).expect("Failed to initialize tracer.")
+            );
 
-            let logger_provider: GlobalLoggerProvider = logger_provider();
-            let logger_layer = OpenTelemetryTracingBridge::new(&logger_provider);
+            let logger = collector_endpoint.as_ref().map(|collector_endpoint|
+                logging::init_logger(confidential_client.clone(), collector_endpoint, service_name.clone(), service_instance_id.clone()).expect("Failed to initialize logs.")
+            );
+
+            let logger_layer = logger.as_ref().map(|_| {
+                let logger_provider: GlobalLoggerProvider = logger_provider();
+                OpenTelemetryTracingBridge::new(&logger_provider)
+            });
+
+            let prometheus_registry = prometheus_enabled.then(metrics::init_prometheus_registry);
 
             let default_meter_provider = NamedMeterProvider {
                 kind: NamedMeterProviderKindDefault,
                 meter_provider: metrics::init_metrics(
                     confidential_client.clone(),
-                    &collector_endpoint,
+                    collector_endpoint.as_ref(),
+                    prometheus_registry.as_ref().map(|registry|
+                        opentelemetry_prometheus::exporter()
+                            .with_registry(Clone::clone(registry))
+                    ),
                     service_name.clone(),
                     service_instance_id.clone(),
                     metrics_interval_ms
@@ -119,7 +132,13 @@ pub async fn initialize_with_config(
                 kind: NamedMeterProviderKindCpu,
                 meter_provider: metrics::init_metrics(
                     confidential_client,
-                    &collector_endpoint,
+                    collector_endpoint.as_ref(),
+                    prometheus_registry.as_ref().map(|registry|
+                        opentelemetry_prometheus::exporter()
+                            .with_registry(Clone::clone(registry))
+                            .without_target_info() //already exported by the default meter provider
+                            .without_scope_info()
+                    ),
                     service_name,
                     service_instance_id,
                     cpu_collection_interval_ms
@@ -129,7 +148,7 @@ pub async fn initialize_with_config(
             global::set_meter_provider(default_meter_provider.meter_provider.clone());
             let meter_providers: NamedMeterProviders = (default_meter_provider, cpu_meter_provider);
 
-            (Some(tracer), Some(logger), Some(logger_layer), Some(meter_providers))
+            (tracer, logger, logger_layer, Some(meter_providers))
     } else {
         (None, None, None, None)
     };
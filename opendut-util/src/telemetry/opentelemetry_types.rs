@@ -7,7 +7,9 @@ use opendut_auth::confidential::error::ConfidentialClientError;
 pub enum Opentelemetry {
     Enabled {
         confidential_client: Option<ConfidentialClientRef>,
-        collector_endpoint: Endpoint,
+        /// Endpoint of the OpenTelemetry collector, which traces, logs and metrics are pushed to. Optional, when metrics are scraped via Prometheus.
+        collector_endpoint: Option<Endpoint>,
+        prometheus_enabled: bool,
         service_name: String,
         service_instance_id: String,
         metrics_interval_ms: Duration,
@@ -34,13 +36,31 @@ impl Opentelemetry {
                         field: field.clone(),
                         cause: format!("{:?}", cause)
                     })?;
-                let url = Url::parse(&url)
+                if url.is_empty() {
+                    None
+                } else {
+                    let url = Url::parse(&url)
+                        .map_err(|cause| OpentelemetryConfigError::ValueParseError {
+                            field,
+                            cause: format!("{:?}", cause)
+                        })?;
+                    Some(Endpoint { url })
+                }
+            };
+            let prometheus_enabled = {
+                let field = String::from("opentelemetry.metrics.prometheus.enabled");
+                config.get_bool(&field)
                     .map_err(|cause| OpentelemetryConfigError::ValueParseError {
-                        field,
+                        field: field.clone(),
                         cause: format!("{:?}", cause)
-                    })?;
-                Endpoint { url }
+                    })?
             };
+            if collector_endpoint.is_none() && !prometheus_enabled {
+                return Err(OpentelemetryConfigError::InvalidValueError {
+                    field: String::from("opentelemetry.collector.endpoint"),
+                    message: String::from("Provided configuration value must not be empty, unless 'opentelemetry.metrics.prometheus.enabled' is set"),
+                });
+            }
             let service_name = {
                 let field = String::from("opentelemetry.service.name");
                 config.get_string(&field)
@@ -104,6 +124,7 @@ impl Opentelemetry {
             Ok(Opentelemetry::Enabled {
                 confidential_client,
                 collector_endpoint,
+                prometheus_enabled,
                 service_name,
                 service_instance_id,
                 metrics_interval_ms,
@@ -138,3 +159,63 @@ pub enum OpentelemetryConfigError {
 pub struct Endpoint {
     pub url: Url,
 }
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn config(collector_endpoint: &str, prometheus_enabled: bool) -> anyhow::Result<config::Config> {
+        let config = config::Config::builder()
+            .set_override("opentelemetry.enabled", true)?
+            .set_override("opentelemetry.collector.endpoint", collector_endpoint)?
+            .set_override("opentelemetry.metrics.prometheus.enabled", prometheus_enabled)?
+            .set_override("opentelemetry.service.name", "opendut-test")?
+            .set_override("opentelemetry.metrics.interval.ms", 60000)?
+            .set_override("opentelemetry.metrics.cpu.collection.interval.ms", 5000)?
+            .set_override("network.oidc.enabled", false)?
+            .build()?;
+        Ok(config)
+    }
+
+    #[tokio::test]
+    async fn should_allow_prometheus_without_a_collector_endpoint() -> anyhow::Result<()> {
+        let opentelemetry = Opentelemetry::load(&config("", true)?, String::from("test-instance")).await?;
+
+        let Opentelemetry::Enabled { collector_endpoint, prometheus_enabled, .. } = opentelemetry else {
+            panic!("Expected OpenTelemetry to be enabled.");
+        };
+        assert!(collector_endpoint.is_none());
+        assert_that!(prometheus_enabled, eq(true));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_load_the_collector_endpoint_alongside_prometheus() -> anyhow::Result<()> {
+        let opentelemetry = Opentelemetry::load(&config("http://localhost:4317", true)?, String::from("test-instance")).await?;
+
+        let Opentelemetry::Enabled { collector_endpoint, prometheus_enabled, .. } = opentelemetry else {
+            panic!("Expected OpenTelemetry to be enabled.");
+        };
+        assert_that!(collector_endpoint.map(|endpoint| endpoint.url.to_string()), some(eq("http://localhost:4317/")));
+        assert_that!(prometheus_enabled, eq(true));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_require_a_collector_endpoint_if_prometheus_is_disabled() -> anyhow::Result<()> {
+        let result = Opentelemetry::load(&config("", false)?, String::from("test-instance")).await;
+
+        assert!(matches!(result, Err(OpentelemetryConfigError::InvalidValueError { field, .. }) if field == "opentelemetry.collector.endpoint"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_an_invalid_collector_endpoint() -> anyhow::Result<()> {
+        let result = Opentelemetry::load(&config("not a url", true)?, String::from("test-instance")).await;
+
+        assert!(matches!(result, Err(OpentelemetryConfigError::ValueParseError { field, .. }) if field == "opentelemetry.collector.endpoint"));
+        Ok(())
+    }
+}
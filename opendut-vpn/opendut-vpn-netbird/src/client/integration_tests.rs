@@ -11,7 +11,7 @@ use opendut_types::peer::PeerId;
 use opendut_util::project;
 use opendut_vpn::VpnManagementClient;
 
-use crate::{netbird, NetbirdManagementClient, NetbirdManagementClientConfiguration, NetbirdToken};
+use crate::{netbird, NetbirdManagementClient, NetbirdManagementClientConfiguration, NetbirdPolicyMode, NetbirdToken};
 use crate::client::{Client, DefaultClient};
 
 #[test_with::env(NETBIRD_INTEGRATION_API_TOKEN)]
@@ -34,6 +34,7 @@ async fn test_netbird_management_client() {
             timeout,
             retries,
             setup_key_expiration,
+            policy_mode: NetbirdPolicyMode::AllowAll,
         }
     ).expect("Netbird management client could not be created!");
 
@@ -74,7 +75,7 @@ async fn test_netbird_vpn_client() {
     let _setup_key = client.generate_netbird_setup_key(peer_id).await
         .expect("Could not generate setup key!");
 
-    client.create_netbird_self_policy(netbird_group.clone(), cluster_id.into()).await
+    client.create_netbird_policy(netbird_group.clone(), cluster_id.into(), vec![netbird::PolicyRule::allow_all()]).await
         .expect("Could not create self access control policy");
     println!("Netbird group {:?} contains peers: {:?}", netbird_group.id, netbird_group.peers);
 
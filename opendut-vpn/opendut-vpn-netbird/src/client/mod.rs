@@ -22,14 +22,18 @@ mod integration_tests;
 pub trait Client {
     async fn create_netbird_group(&self, name: netbird::GroupName, peers: Vec<netbird::PeerId>) -> Result<netbird::Group, RequestError>;
     async fn get_netbird_group(&self, group_name: &netbird::GroupName) -> Result<netbird::Group, GetGroupError>;
+    async fn list_netbird_groups(&self) -> Result<Vec<netbird::Group>, RequestError>;
     async fn delete_netbird_group(&self, group_id: &netbird::GroupId) -> Result<(), RequestError>;
     #[allow(unused)] //Currently unused, but expected to be needed again
     async fn get_netbird_peer(&self, peer_id: &netbird::PeerId) -> Result<netbird::Peer, RequestError>;
     async fn delete_netbird_peer(&self, peer_id: &netbird::PeerId) -> Result<(), RequestError>;
-    async fn create_netbird_self_policy(&self, group: netbird::Group, policy_name: netbird::PolicyName) -> Result<(), RequestError>;
+    async fn create_netbird_policy(&self, group: netbird::Group, policy_name: netbird::PolicyName, rules: Vec<netbird::PolicyRule>) -> Result<(), RequestError>;
     async fn get_netbird_policy(&self, policy_name: &netbird::PolicyName) -> Result<netbird::Policy, GetPoliciesError>;
+    async fn list_netbird_policies(&self) -> Result<Vec<netbird::Policy>, RequestError>;
     async fn delete_netbird_policy(&self, policy_id: &netbird::PolicyId) -> Result<(), RequestError>;
     async fn generate_netbird_setup_key(&self, peer_id: PeerId) -> Result<netbird::SetupKey, CreateSetupKeyError>;
+    async fn list_netbird_setup_keys(&self) -> Result<Vec<netbird::SetupKey>, RequestError>;
+    async fn delete_netbird_setup_key(&self, setup_key_id: &str) -> Result<(), RequestError>;
 }
 
 pub struct DefaultClient {
@@ -147,6 +151,18 @@ impl Client for DefaultClient {
         }
     }
 
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn list_netbird_groups(&self) -> Result<Vec<netbird::Group>, RequestError> {
+        let url = routes::groups(self.netbird_url.clone());
+        let request = Request::new(Method::GET, url);
+
+        let response = self.requester.handle(request).await?
+            .error_for_status().map_err(RequestError::IllegalStatus)?;
+
+        response.json::<Vec<netbird::Group>>().await
+            .map_err(RequestError::JsonDeserialization)
+    }
+
     #[tracing::instrument(skip(self), level="trace")]
     async fn delete_netbird_group(&self, group_id: &netbird::GroupId) -> Result<(), RequestError> {
         let url = routes::group(Clone::clone(&self.netbird_url), group_id);
@@ -188,33 +204,39 @@ impl Client for DefaultClient {
     }
 
     #[tracing::instrument(skip(self), level="trace")]
-    async fn create_netbird_self_policy(&self, group: netbird::Group, policy_name: netbird::PolicyName) -> Result<(), RequestError> {
+    async fn create_netbird_policy(&self, group: netbird::Group, policy_name: netbird::PolicyName, rules: Vec<netbird::PolicyRule>) -> Result<(), RequestError> {
         let url = routes::policies(self.netbird_url.clone());
 
         let body = {
             #[derive(Serialize, Debug)]
             struct CreateAccessControlRule {
-                name: netbird::PolicyName,
+                name: String,
                 description: String,
                 enabled: bool,
                 action: netbird::RuleAction,
                 bidirectional: bool,
                 protocol: netbird::RuleProtocol,
+                #[serde(skip_serializing_if = "Vec::is_empty")]
+                ports: Vec<String>,
                 sources: Vec<netbird::GroupId>,
                 destinations: Vec<netbird::GroupId>,
             }
 
             let description = policy_name.description();
-            let rule = CreateAccessControlRule {
-                name: policy_name.clone(),
-                description: description.clone(),
-                enabled: true,
-                action: netbird::RuleAction::Accept,
-                bidirectional: true,
-                protocol: netbird::RuleProtocol::All,
-                sources: vec![group.id.clone()],
-                destinations: vec![group.id],
-            };
+            let rules = rules.into_iter()
+                .map(|rule| CreateAccessControlRule {
+                    name: rule.name(&policy_name),
+                    description: description.clone(),
+                    enabled: true,
+                    action: netbird::RuleAction::Accept,
+                    bidirectional: true,
+                    protocol: rule.protocol,
+                    ports: rule.ports.iter().map(ToString::to_string).collect(),
+                    sources: vec![group.id.clone()],
+                    destinations: vec![group.id.clone()],
+                })
+                .collect::<Vec<_>>();
+
             #[derive(Serialize, Debug)]
             struct CreatePolicy {
                 name: netbird::PolicyName,
@@ -226,7 +248,7 @@ impl Client for DefaultClient {
                 name: policy_name,
                 description,
                 enabled: true,
-                rules: vec![rule]
+                rules,
             }
         };
         let request = post_json_request(url, body)?;
@@ -258,6 +280,18 @@ impl Client for DefaultClient {
         }
     }
 
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn list_netbird_policies(&self) -> Result<Vec<netbird::Policy>, RequestError> {
+        let url = routes::policies(self.netbird_url.clone());
+        let request = Request::new(Method::GET, url);
+
+        let response = self.requester.handle(request).await?
+            .error_for_status().map_err(RequestError::IllegalStatus)?;
+
+        response.json::<Vec<netbird::Policy>>().await
+            .map_err(RequestError::JsonDeserialization)
+    }
+
     #[tracing::instrument(skip(self), level="trace")]
     async fn delete_netbird_policy(&self, policy_id: &netbird::PolicyId) -> Result<(), RequestError> {
         let url = routes::policy(Clone::clone(&self.netbird_url), policy_id);
@@ -312,6 +346,29 @@ impl Client for DefaultClient {
 
         Ok(result)
     }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn list_netbird_setup_keys(&self) -> Result<Vec<netbird::SetupKey>, RequestError> {
+        let url = routes::setup_keys(self.netbird_url.clone());
+        let request = Request::new(Method::GET, url);
+
+        let response = self.requester.handle(request).await?
+            .error_for_status().map_err(RequestError::IllegalStatus)?;
+
+        response.json::<Vec<netbird::SetupKey>>().await
+            .map_err(RequestError::JsonDeserialization)
+    }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn delete_netbird_setup_key(&self, setup_key_id: &str) -> Result<(), RequestError> {
+        let url = routes::setup_key(Clone::clone(&self.netbird_url), setup_key_id);
+
+        let request = Request::new(Method::DELETE, url);
+
+        let response = self.requester.handle(request).await?;
+
+        parse_response_status(response, format!("NetBird setup key with ID <{setup_key_id}>")).await
+    }
 }
 
 fn post_json_request(url: Url, body: impl Serialize) -> Result<Request, RequestError> {
@@ -40,6 +40,28 @@ async fn delete_peer(fixture: Fixture) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[rstest]
+#[tokio::test]
+async fn delete_setup_key(fixture: Fixture) -> anyhow::Result<()> {
+
+    let requester = fixture.requester(|_, request| {
+        assert_that!(request.method(), eq(&Method::DELETE));
+        assert_that!(request.url().path(), eq("/api/setup-keys/2531583362"));
+        assert_that!(request.body(), none());
+        Ok(Response::from(http::Response::builder()
+            .body("")
+            .unwrap()))
+    });
+
+    let client = DefaultClient::create(fixture.base_url(), None, None, Some(Box::new(requester)), TIMEOUT, RETRIES, SETUP_KEY_EXPIRATION)?;
+
+    let result = client.delete_netbird_setup_key("2531583362").await;
+
+    assert_that!(result, ok(anything()));
+
+    Ok(())
+}
+
 #[rstest]
 #[tokio::test]
 async fn create_group(fixture: Fixture) -> anyhow::Result<()> {
@@ -325,9 +347,57 @@ async fn create_access_policy(fixture: Fixture) -> anyhow::Result<()> {
         peers: vec![],
     };
 
-    client.create_netbird_self_policy(
+    client.create_netbird_policy(
+        group,
+        fixture.cluster_id().into(),
+        vec![netbird::PolicyRule::allow_all()],
+    ).await?;
+
+    Ok(())
+}
+
+#[rstest]
+#[tokio::test]
+async fn create_restricted_access_policy(fixture: Fixture) -> anyhow::Result<()> {
+
+    let requester = fixture.requester(|fixture, request| {
+        let request = request.body().unwrap().as_bytes().unwrap();
+        let request: serde_json::Value = serde_json::from_slice(request).unwrap();
+
+        let expectation = json!([{
+            "name": format!("{}-can", fixture.netbird_cluster_policy_name()),
+            "description": fixture.netbird_cluster_policy_name().description(),
+            "enabled": true,
+            "action": "accept",
+            "bidirectional": true,
+            "protocol": "udp",
+            "ports": ["10000", "10001"],
+            "sources": [fixture.netbird_group_id()],
+            "destinations": [fixture.netbird_group_id()],
+        }]);
+
+        assert_that!(request["rules"], eq(&expectation));
+
+        let response = http::Response::builder()
+            .body(json!({}).to_string())
+            .unwrap();
+
+        Ok(Response::from(response))
+    });
+
+    let client = DefaultClient::create(fixture.base_url(), None, None, Some(Box::new(requester)), TIMEOUT, RETRIES, SETUP_KEY_EXPIRATION)?;
+
+    let group = netbird::Group {
+        id: fixture.netbird_group_id(),
+        name: fixture.cluster_id().into(),
+        peers_count: 0,
+        peers: vec![],
+    };
+
+    client.create_netbird_policy(
         group,
         fixture.cluster_id().into(),
+        vec![netbird::PolicyRule { name_suffix: Some("can"), protocol: netbird::RuleProtocol::Udp, ports: vec![10000, 10001] }],
     ).await?;
 
     Ok(())
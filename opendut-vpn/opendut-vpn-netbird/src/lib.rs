@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::Read;
 use std::path::PathBuf;
@@ -12,7 +13,7 @@ pub use netbird::Token as NetbirdToken;
 use opendut_types::cluster::ClusterId;
 use opendut_types::peer::PeerId;
 use opendut_types::vpn::VpnPeerConfiguration;
-use opendut_vpn::{CreateClusterError, CreatePeerError, CreateVpnPeerConfigurationError, DeleteClusterError, DeletePeerError, VpnManagementClient};
+use opendut_vpn::{ClusterTraffic, CreateClusterError, CreatePeerError, CreateVpnPeerConfigurationError, DeleteClusterError, DeletePeerError, ListResourcesError, VpnManagementClient, VpnResources};
 
 use crate::client::{Client, DefaultClient};
 use crate::netbird::error::{CreateClientError, CreateSetupKeyError, GetGroupError, GetPoliciesError, RequestError};
@@ -31,10 +32,24 @@ pub struct NetbirdManagementClientConfiguration {
     pub timeout: Duration,
     pub retries: u32,
     pub setup_key_expiration: Duration,
+    pub policy_mode: NetbirdPolicyMode,
+}
+
+/// Determines which traffic the NetBird policy of a cluster allows between its peers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetbirdPolicyMode {
+    /// Allows all traffic between the peers of a cluster.
+    AllowAll,
+    /// Only allows the traffic, which the cluster uses, i.e. the CAN server ports
+    /// as well as pings and throughput measurements on the given port for the cluster metrics.
+    /// Clusters with Ethernet interfaces are rejected, unless `allow_all_protocols_for_ethernet` is set,
+    /// since their GRE tunnels can only be allowed by allowing all protocols between the peers of the cluster.
+    Restricted { throughput_port: u16, allow_all_protocols_for_ethernet: bool },
 }
 
 pub struct NetbirdManagementClient {
     management_url: Url,
+    policy_mode: NetbirdPolicyMode,
     inner: Inner,
 }
 
@@ -63,6 +78,7 @@ impl NetbirdManagementClient {
         )?);
         Ok(Self {
             management_url,
+            policy_mode: configuration.policy_mode,
             inner,
         })
     }
@@ -72,7 +88,14 @@ impl NetbirdManagementClient {
 impl VpnManagementClient for NetbirdManagementClient {
 
     #[tracing::instrument(skip(self), level="trace")]
-    async fn create_cluster(&self, cluster_id: ClusterId, peers: &[PeerId]) -> Result<(), CreateClusterError> {
+    async fn create_cluster(&self, cluster_id: ClusterId, peers: &[PeerId], traffic: &ClusterTraffic) -> Result<(), CreateClusterError> {
+
+        let rules = match self.policy_mode {
+            NetbirdPolicyMode::AllowAll => vec![netbird::PolicyRule::allow_all()],
+            NetbirdPolicyMode::Restricted { throughput_port, allow_all_protocols_for_ethernet } =>
+                netbird::PolicyRule::for_cluster_traffic(traffic, throughput_port, allow_all_protocols_for_ethernet)
+                    .map_err(|error| CreateClusterError::AccessPolicyCreationFailure { cluster_id, error: error.into() })?,
+        };
 
         match self.delete_cluster(cluster_id).await {
             Ok(_) => debug!("Deleted a previous cluster with ID <{cluster_id}> before creating the new cluster."),
@@ -99,7 +122,8 @@ impl VpnManagementClient for NetbirdManagementClient {
         let group = self.inner.create_netbird_group(cluster_id.into(), netbird_peers).await
             .map_err(|error| CreateClusterError::CreationFailure { cluster_id, error: error.into() })?;
 
-        self.inner.create_netbird_self_policy(group, cluster_id.into()).await
+        //a policy of a previous deployment was deleted above, so the rules always match the current traffic of the cluster
+        self.inner.create_netbird_policy(group, cluster_id.into(), rules).await
             .map_err(|error| CreateClusterError::AccessPolicyCreationFailure { cluster_id, error: error.into() })?;
 
         Ok(())
@@ -178,22 +202,69 @@ impl VpnManagementClient for NetbirdManagementClient {
 
     #[tracing::instrument(skip(self), level="trace")]
     async fn delete_peer(&self, peer_id: PeerId) -> Result<(), DeletePeerError> {
-        let self_group = self.inner.get_netbird_group(&netbird::GroupName::from(peer_id)).await
+        match self.inner.get_netbird_group(&netbird::GroupName::from(peer_id)).await {
+            Ok(self_group) => {
+                if let Some(peer_info) = self_group.peers.first() {
+                    self.inner.delete_netbird_peer(&peer_info.id)
+                        .await
+                        .map_err(|error| DeletePeerError::DeletionFailure { peer_id, error: error.into() })?;
+                }
+
+                self.inner.delete_netbird_group(&self_group.id)
+                    .await
+                    .map_err(|error| DeletePeerError::DeletionFailure { peer_id, error: error.into() })?;
+            }
+            Err(GetGroupError::GroupNotFound { .. }) => {
+                debug!("There is no self group for peer <{peer_id}> to delete. Only deleting its setup keys.");
+            }
+            Err(error) => return Err(DeletePeerError::ResolutionFailure { peer_id, error: error.into() }),
+        }
+
+        let setup_keys = self.inner.list_netbird_setup_keys().await
             .map_err(|error| DeletePeerError::ResolutionFailure { peer_id, error: error.into() })?;
 
-        if let Some(peer_info) = self_group.peers.first() {
-            self.inner.delete_netbird_peer(&peer_info.id)
-                .await
+        for setup_key in setup_keys.into_iter().filter(|setup_key| netbird::parse_setup_key_name(&setup_key.name) == Some(peer_id)) {
+            self.inner.delete_netbird_setup_key(&setup_key.id).await
                 .map_err(|error| DeletePeerError::DeletionFailure { peer_id, error: error.into() })?;
+            debug!("Deleted NetBird setup key with name '{}' and NetBird setup key ID '{}'.", setup_key.name, setup_key.id);
         }
 
-        self.inner.delete_netbird_group(&self_group.id)
-            .await
-            .map_err(|error| DeletePeerError::DeletionFailure { peer_id, error: error.into() })?;
-
         Ok(())
     }
 
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn list_resources(&self) -> Result<Option<VpnResources>, ListResourcesError> {
+        let groups = self.inner.list_netbird_groups().await
+            .map_err(|error| ListResourcesError::ListingFailure { error: error.into() })?;
+        let policies = self.inner.list_netbird_policies().await
+            .map_err(|error| ListResourcesError::ListingFailure { error: error.into() })?;
+        let setup_keys = self.inner.list_netbird_setup_keys().await
+            .map_err(|error| ListResourcesError::ListingFailure { error: error.into() })?;
+
+        let mut clusters = HashSet::new();
+        let mut peers = HashSet::new();
+
+        for group in groups {
+            match group.name {
+                GroupName::Cluster(cluster_id) => { clusters.insert(cluster_id); }
+                GroupName::Peer(peer_id) => { peers.insert(peer_id); }
+                GroupName::Other(_) => {}
+            }
+        }
+        for policy in policies {
+            if let netbird::PolicyName::Cluster(cluster_id) = policy.name {
+                clusters.insert(cluster_id);
+            }
+        }
+        peers.extend(setup_keys.iter()
+            .filter_map(|setup_key| netbird::parse_setup_key_name(&setup_key.name)));
+
+        Ok(Some(VpnResources {
+            clusters: clusters.into_iter().collect(),
+            peers: peers.into_iter().collect(),
+        }))
+    }
+
     #[tracing::instrument(skip(self), level="trace")]
     async fn generate_vpn_peer_configuration(&self, peer_id: PeerId) -> Result<VpnPeerConfiguration, CreateVpnPeerConfigurationError> {
 
@@ -270,9 +341,9 @@ mod test {
     use opendut_types::peer::PeerId;
     use opendut_types::vpn;
     use opendut_types::vpn::VpnPeerConfiguration;
-    use opendut_vpn::VpnManagementClient;
+    use opendut_vpn::{ClusterTraffic, VpnManagementClient};
 
-    use crate::{netbird, NetbirdManagementClient};
+    use crate::{netbird, NetbirdManagementClient, NetbirdPolicyMode};
     use crate::client::Client;
     use crate::netbird::error::{CreateSetupKeyError, GetGroupError, GetPoliciesError, RequestError};
     use crate::netbird::GroupPeerInfo;
@@ -341,13 +412,32 @@ mod test {
                     let cluster_group = Clone::clone(&cluster_group);
                     move |_, _| Ok(Clone::clone(&cluster_group))
                 });
-            mock_client.expect_create_netbird_self_policy()
+            mock_client.expect_create_netbird_policy()
                 .times(1)
-                .withf(move |actual_group, _policy_name| actual_group == &cluster_group)
-                .returning(|_, _| Ok(()));
+                .withf(move |actual_group, _policy_name, actual_rules| actual_group == &cluster_group && actual_rules == &[netbird::PolicyRule::allow_all()])
+                .returning(|_, _, _| Ok(()));
+        });
+
+        assert_that!(fixture.testee.create_cluster(cluster_id, &[peer_a_id, peer_b_id], &ClusterTraffic::default()).await, ok(anything()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn A_NetbirdManagementClient_with_restricted_policies_should_reject_a_cluster_with_ethernet_without_changing_netbird() -> Result<()> {
+
+        let cluster_id = ClusterId::random();
+        let peer_id = PeerId::random();
+
+        let mut fixture = Fixture::setup(|_mock_client| {
+            // no NetBird requests are expected
         });
+        fixture.testee.policy_mode = NetbirdPolicyMode::Restricted { throughput_port: 5201, allow_all_protocols_for_ethernet: false };
+
+        let traffic = ClusterTraffic { ethernet: true, can_server_ports: vec![] };
+        let result = fixture.testee.create_cluster(cluster_id, &[peer_id], &traffic).await;
 
-        assert_that!(fixture.testee.create_cluster(cluster_id, &[peer_a_id, peer_b_id]).await, ok(anything()));
+        assert!(matches!(result, Err(opendut_vpn::CreateClusterError::AccessPolicyCreationFailure { .. })));
 
         Ok(())
     }
@@ -424,6 +514,54 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn A_NetbirdManagementClient_should_list_the_clusters_and_peers_of_its_groups_policies_and_setup_keys() -> Result<()> {
+
+        let cluster_a_id = ClusterId::from(uuid!("6a6510a9-031b-4834-a4f7-454cc401fe13"));
+        let cluster_b_id = ClusterId::from(uuid!("0e7d1e73-8a3c-4a0e-9d3a-0c4b6c1f1a55"));
+        let peer_a_id = PeerId::from(uuid!("d61bed7b-2fec-4a5b-a937-d6a791cb5ff9"));
+        let peer_b_id = PeerId::from(uuid!("267ca2c0-942e-4780-857e-c153ddcf2328"));
+
+        let fixture = Fixture::setup(|mock_client| {
+            mock_client.expect_list_netbird_groups()
+                .returning(move || Ok(vec![
+                    netbird::Group { id: netbird::GroupId::from("cluster-a-group"), name: netbird::GroupName::from(cluster_a_id), peers_count: 0, peers: vec![] },
+                    netbird::Group { id: netbird::GroupId::from("peer-a-group"), name: netbird::GroupName::from(peer_a_id), peers_count: 0, peers: vec![] },
+                    netbird::Group { id: netbird::GroupId::from("all"), name: netbird::GroupName::Other(String::from("All")), peers_count: 0, peers: vec![] },
+                ]));
+            mock_client.expect_list_netbird_policies()
+                .returning(move || Ok(vec![
+                    netbird::Policy { id: netbird::PolicyId::from("cluster-a-policy"), name: netbird::PolicyName::from(cluster_a_id) },
+                    netbird::Policy { id: netbird::PolicyId::from("cluster-b-policy"), name: netbird::PolicyName::from(cluster_b_id) },
+                ]));
+            mock_client.expect_list_netbird_setup_keys()
+                .returning(move || Ok(vec![
+                    netbird::SetupKey {
+                        id: String::from("2531583362"),
+                        key: uuid!("a616097e-fcf0-48fa-9354-ca4a61142761"),
+                        name: netbird::setup_key_name_format(peer_b_id),
+                        expires: netbird::SetupKeyTimeStamp { inner: OffsetDateTime::now_utc() },
+                        r#type: netbird::SetupKeyType::Reusable,
+                        valid: true,
+                        revoked: false,
+                        used_times: 0,
+                        last_used: netbird::SetupKeyTimeStamp { inner: OffsetDateTime::now_utc() },
+                        state: netbird::SetupKeyState::Valid,
+                        auto_groups: vec![],
+                        updated_at: netbird::SetupKeyTimeStamp { inner: OffsetDateTime::now_utc() },
+                        usage_limit: 0,
+                    },
+                ]));
+        });
+
+        let resources = fixture.testee.list_resources().await.unwrap().unwrap();
+
+        assert_that!(resources.clusters, unordered_elements_are![eq(&cluster_a_id), eq(&cluster_b_id)]);
+        assert_that!(resources.peers, unordered_elements_are![eq(&peer_a_id), eq(&peer_b_id)]);
+
+        Ok(())
+    }
+
     struct Fixture {
         testee: NetbirdManagementClient,
     }
@@ -437,6 +575,7 @@ mod test {
             setup(&mut mock_client);
             let testee = NetbirdManagementClient {
                 management_url: Url::parse("https://localhost/api/").unwrap(),
+                policy_mode: NetbirdPolicyMode::AllowAll,
                 inner: Box::new(mock_client),
             };
 
@@ -452,13 +591,17 @@ mod test {
         impl Client for MockClient {
             async fn create_netbird_group(&self, name: netbird::GroupName, peers: Vec<netbird::PeerId>) -> std::result::Result<netbird::Group, RequestError>;
             async fn get_netbird_group(&self, group_name: &netbird::GroupName) -> std::result::Result<netbird::Group, GetGroupError>;
+            async fn list_netbird_groups(&self) -> std::result::Result<Vec<netbird::Group>, RequestError>;
             async fn delete_netbird_group(&self, group_id: &netbird::GroupId) -> std::result::Result<(), RequestError>;
             async fn get_netbird_peer(&self, peer_id: &netbird::PeerId) -> std::result::Result<netbird::Peer, RequestError>;
             async fn delete_netbird_peer(&self, peer_id: &netbird::PeerId) -> std::result::Result<(), RequestError>;
-            async fn create_netbird_self_policy(&self, group: netbird::Group, policy_name: netbird::PolicyName) -> std::result::Result<(), RequestError>;
+            async fn create_netbird_policy(&self, group: netbird::Group, policy_name: netbird::PolicyName, rules: Vec<netbird::PolicyRule>) -> std::result::Result<(), RequestError>;
             async fn get_netbird_policy(&self, policy_name: &netbird::PolicyName) -> std::result::Result<netbird::Policy, GetPoliciesError>;
+            async fn list_netbird_policies(&self) -> std::result::Result<Vec<netbird::Policy>, RequestError>;
             async fn delete_netbird_policy(&self, policy_id: &netbird::PolicyId) -> std::result::Result<(), RequestError>;
             async fn generate_netbird_setup_key(&self, peer_id: PeerId) -> std::result::Result<netbird::SetupKey, CreateSetupKeyError>;
+            async fn list_netbird_setup_keys(&self) -> std::result::Result<Vec<netbird::SetupKey>, RequestError>;
+            async fn delete_netbird_setup_key(&self, setup_key_id: &str) -> std::result::Result<(), RequestError>;
         }
     }
 }
@@ -2,8 +2,8 @@
 
 pub(crate) use group::{Group, GroupId, GroupName, GroupPeerInfo};
 pub(crate) use peer::{Peer, PeerId};
-pub(crate) use policies::{Policy, RuleAction, RuleProtocol, PolicyId, PolicyName};
-pub(crate) use setup_key::{name_format as setup_key_name_format, SetupKey, State as SetupKeyState, Timestamp as SetupKeyTimeStamp, Type as SetupKeyType};
+pub(crate) use policies::{Policy, PolicyRule, RuleAction, RuleProtocol, PolicyId, PolicyName};
+pub(crate) use setup_key::{name_format as setup_key_name_format, parse_name as parse_setup_key_name, SetupKey, State as SetupKeyState, Timestamp as SetupKeyTimeStamp, Type as SetupKeyType};
 pub use token::Token;
 
 pub mod error;
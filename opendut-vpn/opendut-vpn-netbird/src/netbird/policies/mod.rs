@@ -1,9 +1,11 @@
 use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::ops::Not;
 
 use serde::{Deserialize, Serialize};
 
 use opendut_types::cluster::ClusterId;
+use opendut_vpn::ClusterTraffic;
 
 use crate::netbird::group::GroupId;
 
@@ -112,7 +114,7 @@ pub(crate) enum RuleAction {
     Accept,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all="lowercase")]
 pub(crate) enum RuleProtocol {
     Tcp,
@@ -120,3 +122,90 @@ pub(crate) enum RuleProtocol {
     All,
     Icmp,
 }
+
+/// Rule of a policy, which allows traffic between the peers of a group.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct PolicyRule {
+    /// Appended to the name of the policy to name the rule. If not set, the rule is named like the policy.
+    pub name_suffix: Option<&'static str>,
+    pub protocol: RuleProtocol,
+    /// Ports to allow for TCP and UDP. All ports are allowed, if empty.
+    pub ports: Vec<u16>,
+}
+
+impl PolicyRule {
+    pub fn allow_all() -> Self {
+        Self { name_suffix: None, protocol: RuleProtocol::All, ports: vec![] }
+    }
+
+    pub fn name(&self, policy_name: &PolicyName) -> String {
+        match self.name_suffix {
+            Some(suffix) => format!("{policy_name}-{suffix}"),
+            None => String::from(policy_name),
+        }
+    }
+
+    /// Only allows the traffic, which the peers of a cluster exchange, and the traffic of the cluster metrics.
+    /// Like all rules, these only apply between the peers in the group of the cluster.
+    ///
+    /// NetBird cannot filter GRE (IP protocol 47) specifically, so tunneling Ethernet requires a rule allowing all protocols.
+    /// Such a rule is only created, if `allow_all_protocols_for_ethernet` is set. Otherwise, clusters with Ethernet are rejected.
+    pub fn for_cluster_traffic(traffic: &ClusterTraffic, throughput_port: u16, allow_all_protocols_for_ethernet: bool) -> Result<Vec<Self>, UnrestrictableTrafficError> {
+        let mut rules = vec![
+            Self { name_suffix: Some("ping"), protocol: RuleProtocol::Icmp, ports: vec![] },
+            Self { name_suffix: Some("throughput-tcp"), protocol: RuleProtocol::Tcp, ports: vec![throughput_port] },
+            Self { name_suffix: Some("throughput-udp"), protocol: RuleProtocol::Udp, ports: vec![throughput_port] },
+        ];
+        if traffic.can_server_ports.is_empty().not() {
+            rules.push(Self { name_suffix: Some("can"), protocol: RuleProtocol::Udp, ports: traffic.can_server_ports.clone() });
+        }
+        if traffic.ethernet {
+            if allow_all_protocols_for_ethernet.not() {
+                return Err(UnrestrictableTrafficError::Ethernet);
+            }
+            rules.push(Self { name_suffix: Some("ethernet"), protocol: RuleProtocol::All, ports: vec![] });
+        }
+        Ok(rules)
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum UnrestrictableTrafficError {
+    #[error("The cluster contains Ethernet interfaces, whose GRE tunnels can only be allowed by allowing all protocols between the peers of the cluster, which is not permitted.")]
+    Ethernet,
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_only_allow_the_traffic_of_a_cluster_without_ethernet() -> Result<()> {
+        let traffic = ClusterTraffic {
+            ethernet: false,
+            can_server_ports: vec![10000, 10001],
+        };
+
+        let rules = PolicyRule::for_cluster_traffic(&traffic, 5201, false)?;
+
+        assert_that!(rules.iter().any(|rule| rule.protocol == RuleProtocol::All), eq(false));
+        assert_that!(rules, contains(eq(&PolicyRule { name_suffix: Some("can"), protocol: RuleProtocol::Udp, ports: vec![10000, 10001] })));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_ethernet_unless_all_protocols_are_allowed_for_it() -> Result<()> {
+        let traffic = ClusterTraffic {
+            ethernet: true,
+            can_server_ports: vec![],
+        };
+
+        assert_that!(PolicyRule::for_cluster_traffic(&traffic, 5201, false), err(eq(&UnrestrictableTrafficError::Ethernet)));
+
+        let rules = PolicyRule::for_cluster_traffic(&traffic, 5201, true)?;
+        assert_that!(rules, contains(eq(&PolicyRule { name_suffix: Some("ethernet"), protocol: RuleProtocol::All, ports: vec![] })));
+        Ok(())
+    }
+}
@@ -10,6 +10,12 @@ pub fn name_format(peer_id: PeerId) -> String {
     format!("opendut-peer-{peer_id}")
 }
 
+/// Determines the peer, for which a setup key was created, from the name of the setup key.
+pub fn parse_name(name: &str) -> Option<PeerId> {
+    name.strip_prefix("opendut-peer-")
+        .and_then(|peer_id| PeerId::try_from(peer_id).ok())
+}
+
 #[allow(unused)]
 #[derive(Clone, Debug, Deserialize)]
 pub struct SetupKey {
@@ -6,6 +6,10 @@ pub fn setup_keys(base_url: Url) -> Url {
     join(base_url, "setup-keys")
 }
 
+pub fn setup_key(base_url: Url, setup_key_id: &str) -> Url {
+    join(setup_keys(base_url), setup_key_id)
+}
+
 pub fn groups(base_url: Url) -> Url {
     join(base_url, "groups")
 }
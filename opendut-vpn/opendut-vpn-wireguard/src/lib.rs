@@ -0,0 +1,404 @@
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::ops::Not;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use tokio::sync::{Mutex, RwLock};
+use tracing::debug;
+use uuid::Uuid;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::PeerId;
+use opendut_types::vpn::VpnPeerConfiguration;
+use opendut_types::vpn::wireguard::{WireGuardPeer, WireGuardPeerAllocation, WireGuardPrivateKey, WireGuardPublicKey};
+use opendut_vpn::{ClusterTraffic, CreateClusterError, CreatePeerError, CreateVpnPeerConfigurationError, DeleteClusterError, DeletePeerError, VpnManagementClient};
+
+const MIN_PREFIX_LENGTH: u8 = 8;
+const MAX_PREFIX_LENGTH: u8 = 30;
+
+pub struct WireGuardManagementClientConfiguration {
+    /// Secret, from which the keys of the peers are derived. Anyone knowing it can derive the private keys of all peers.
+    pub key_secret: String,
+    /// Network, from which the VPN addresses of the peers are assigned.
+    pub network_address: Ipv4Addr,
+    pub network_prefix_length: u8,
+    pub listen_port: u16,
+}
+
+/// Persists the VPN addresses and key nonces allocated to the peers, e.g. as resources of CARL.
+#[async_trait]
+pub trait WireGuardPeerStore: Send + Sync {
+    async fn list(&self) -> Result<Vec<WireGuardPeerAllocation>, StoreError>;
+
+    async fn insert(&self, allocation: WireGuardPeerAllocation) -> Result<(), StoreError>;
+
+    async fn remove(&self, peer_id: PeerId) -> Result<(), StoreError>;
+}
+
+pub type StoreError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Coordinates a plain WireGuard VPN, without a separate management server.
+///
+/// Keys are derived from the ID of a peer and a nonce. The nonce and the VPN address are allocated when a peer is created
+/// and kept in the [`WireGuardPeerStore`], so both remain the same when CARL restarts.
+/// Deleting and re-creating a peer, as done when re-enrolling it, chooses a new nonce and therefore new keys.
+/// The peers of a cluster are passed by CARL when generating the tunnels, so no clusters are kept.
+/// Endpoints are only kept in memory. They are restored when the peers reconnect to CARL,
+/// which they need to do before a cluster can be deployed to them.
+pub struct WireGuardManagementClient {
+    configuration: WireGuardManagementClientConfiguration,
+    peer_store: Arc<dyn WireGuardPeerStore>,
+    /// Serializes the allocation of addresses, so that no address is allocated twice.
+    allocation_lock: Mutex<()>,
+    endpoints: RwLock<HashMap<PeerId, IpAddr>>,
+}
+
+impl WireGuardManagementClient {
+
+    pub fn create(configuration: WireGuardManagementClientConfiguration, peer_store: Arc<dyn WireGuardPeerStore>) -> Result<Self, Error> {
+        let WireGuardManagementClientConfiguration { key_secret, network_address, network_prefix_length, .. } = &configuration;
+
+        if key_secret.is_empty() {
+            return Err(Error::Configuration { message: String::from("The secret for deriving the keys of peers must not be empty.") });
+        }
+        if !(MIN_PREFIX_LENGTH..=MAX_PREFIX_LENGTH).contains(network_prefix_length) {
+            return Err(Error::Configuration { message: format!("The prefix length of the VPN network must be between {MIN_PREFIX_LENGTH} and {MAX_PREFIX_LENGTH}, but was {network_prefix_length}.") });
+        }
+        if u32::from(*network_address) & !network_mask(*network_prefix_length) != 0 {
+            return Err(Error::Configuration { message: format!("The VPN network address {network_address}/{network_prefix_length} has host bits set.") });
+        }
+
+        Ok(Self {
+            configuration,
+            peer_store,
+            allocation_lock: Default::default(),
+            endpoints: Default::default(),
+        })
+    }
+
+    fn private_key(&self, allocation: &WireGuardPeerAllocation) -> StaticSecret {
+        let mut hasher = Sha256::new();
+        hasher.update(b"opendut-wireguard-private-key");
+        hasher.update(self.configuration.key_secret.as_bytes());
+        hasher.update(allocation.peer_id.uuid.as_bytes());
+        hasher.update(allocation.key_nonce.as_bytes());
+        let bytes: [u8; 32] = hasher.finalize().into();
+        StaticSecret::from(bytes)
+    }
+
+    fn public_key(&self, allocation: &WireGuardPeerAllocation) -> WireGuardPublicKey {
+        let public_key = PublicKey::from(&self.private_key(allocation));
+        WireGuardPublicKey::from_bytes(public_key.to_bytes())
+    }
+
+    /// Returns the allocation of the peer, allocating the lowest free address of the VPN network and a new key nonce if there is none yet.
+    async fn allocation(&self, peer_id: PeerId) -> Result<WireGuardPeerAllocation, Error> {
+        let _allocation_lock = self.allocation_lock.lock().await;
+
+        let allocations = self.peer_store.list().await
+            .map_err(|cause| Error::Store { cause })?;
+
+        if let Some(allocation) = allocations.iter().find(|allocation| allocation.peer_id == peer_id) {
+            return Ok(allocation.clone());
+        }
+
+        let address = self.free_address(&allocations)?;
+        let allocation = WireGuardPeerAllocation { peer_id, address, key_nonce: Uuid::new_v4() };
+        self.peer_store.insert(allocation.clone()).await
+            .map_err(|cause| Error::Store { cause })?;
+
+        debug!("Allocated VPN address {address} to peer <{peer_id}>.");
+        Ok(allocation)
+    }
+
+    /// Finds the lowest address within the VPN network, which is not yet allocated, omitting the network and broadcast address.
+    fn free_address(&self, allocations: &[WireGuardPeerAllocation]) -> Result<Ipv4Addr, Error> {
+        let WireGuardManagementClientConfiguration { network_address, network_prefix_length, .. } = self.configuration;
+
+        let allocated_addresses = allocations.iter()
+            .map(|allocation| allocation.address)
+            .collect::<HashSet<_>>();
+
+        let host_bits = 32 - u32::from(network_prefix_length);
+        let host_count = (1u32 << host_bits) - 2;
+
+        (1..=host_count)
+            .map(|host| Ipv4Addr::from(u32::from(network_address) | host))
+            .find(|address| allocated_addresses.contains(address).not())
+            .ok_or(Error::NetworkExhausted { network_address, network_prefix_length })
+    }
+}
+
+fn network_mask(prefix_length: u8) -> u32 {
+    u32::MAX.checked_shl(32 - u32::from(prefix_length)).unwrap_or(0)
+}
+
+#[async_trait]
+impl VpnManagementClient for WireGuardManagementClient {
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn create_cluster(&self, cluster_id: ClusterId, peers: &[PeerId], _traffic: &ClusterTraffic) -> Result<(), CreateClusterError> {
+        for peer_id in peers {
+            self.allocation(*peer_id).await
+                .map_err(|cause| CreateClusterError::PeerResolutionFailure { peer_id: *peer_id, cluster_id, error: Box::new(cause) })?;
+        }
+
+        debug!("Checked VPN addresses of the peers of WireGuard cluster <{cluster_id}>. Tunnels are generated when the cluster is deployed.");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn delete_cluster(&self, cluster_id: ClusterId) -> Result<(), DeleteClusterError> {
+        debug!("WireGuard clusters are not kept. Nothing to delete for cluster <{cluster_id}>.");
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn create_peer(&self, peer_id: PeerId) -> Result<(), CreatePeerError> {
+        self.allocation(peer_id).await
+            .map_err(|cause| CreatePeerError::CreationFailure { peer_id, error: Box::new(cause) })?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn delete_peer(&self, peer_id: PeerId) -> Result<(), DeletePeerError> {
+        self.endpoints.write().await
+            .remove(&peer_id);
+
+        let _allocation_lock = self.allocation_lock.lock().await;
+        self.peer_store.remove(peer_id).await
+            .map_err(|cause| DeletePeerError::DeletionFailure { peer_id, error: Box::new(Error::Store { cause }) })?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn generate_vpn_peer_configuration(&self, peer_id: PeerId) -> Result<VpnPeerConfiguration, CreateVpnPeerConfigurationError> {
+        let allocation = self.allocation(peer_id).await
+            .map_err(|cause| CreateVpnPeerConfigurationError::CreationFailure { peer_id, error: Box::new(cause) })?;
+        let private_key = WireGuardPrivateKey::from_bytes(self.private_key(&allocation).to_bytes());
+
+        Ok(VpnPeerConfiguration::WireGuard {
+            private_key,
+            address: IpAddr::V4(allocation.address),
+            prefix_length: self.configuration.network_prefix_length,
+            listen_port: self.configuration.listen_port,
+        })
+    }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn update_peer_endpoint(&self, peer_id: PeerId, endpoint: IpAddr) {
+        self.endpoints.write().await
+            .insert(peer_id, endpoint);
+    }
+
+    #[tracing::instrument(skip(self), level="trace")]
+    async fn generate_wireguard_peers(&self, cluster_id: ClusterId, peer_id: PeerId, cluster_peers: &[PeerId]) -> Result<Vec<WireGuardPeer>, CreateVpnPeerConfigurationError> {
+        let mut wireguard_peers = Vec::new();
+
+        for other_peer_id in cluster_peers.iter().copied().filter(|other_peer_id| *other_peer_id != peer_id) {
+            let allocation = self.allocation(other_peer_id).await
+                .map_err(|cause| CreateVpnPeerConfigurationError::CreationFailure { peer_id, error: Box::new(cause) })?;
+            let endpoint = self.endpoints.read().await
+                .get(&other_peer_id)
+                .map(|host| SocketAddr::new(*host, self.configuration.listen_port));
+
+            wireguard_peers.push(WireGuardPeer {
+                public_key: self.public_key(&allocation),
+                allowed_ip: IpAddr::V4(allocation.address),
+                endpoint,
+            });
+        }
+        debug!("Generated {} WireGuard peers of peer <{peer_id}> in cluster <{cluster_id}>.", wireguard_peers.len());
+
+        Ok(wireguard_peers)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("Invalid configuration for WireGuard VPN: {message}")]
+    Configuration { message: String },
+    #[error("All addresses of the VPN network {network_address}/{network_prefix_length} are allocated. Choose a larger VPN network or delete unused peers.")]
+    NetworkExhausted { network_address: Ipv4Addr, network_prefix_length: u8 },
+    #[error("Error while accessing the stored VPN addresses and key nonces of the peers:\n  {cause}")]
+    Store { cause: StoreError },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct InMemoryPeerStore {
+        allocations: std::sync::Mutex<Vec<WireGuardPeerAllocation>>,
+    }
+
+    #[async_trait]
+    impl WireGuardPeerStore for InMemoryPeerStore {
+        async fn list(&self) -> std::result::Result<Vec<WireGuardPeerAllocation>, StoreError> {
+            Ok(self.allocations.lock().unwrap().clone())
+        }
+
+        async fn insert(&self, allocation: WireGuardPeerAllocation) -> std::result::Result<(), StoreError> {
+            self.allocations.lock().unwrap().push(allocation);
+            Ok(())
+        }
+
+        async fn remove(&self, peer_id: PeerId) -> std::result::Result<(), StoreError> {
+            self.allocations.lock().unwrap().retain(|allocation| allocation.peer_id != peer_id);
+            Ok(())
+        }
+    }
+
+    fn client(peer_store: Arc<InMemoryPeerStore>) -> WireGuardManagementClient {
+        client_in_network(peer_store, 10)
+    }
+
+    fn client_in_network(peer_store: Arc<InMemoryPeerStore>, network_prefix_length: u8) -> WireGuardManagementClient {
+        WireGuardManagementClient::create(WireGuardManagementClientConfiguration {
+            key_secret: String::from("secret"),
+            network_address: Ipv4Addr::new(100, 64, 0, 0),
+            network_prefix_length,
+            listen_port: 51820,
+        }, peer_store).unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_derive_the_same_configuration_for_a_peer() -> Result<()> {
+        let peer_store = Arc::new(InMemoryPeerStore::default());
+        let peer_id = PeerId::random();
+
+        let first = client(Arc::clone(&peer_store)).generate_vpn_peer_configuration(peer_id).await.unwrap();
+        let second = client(Arc::clone(&peer_store)).generate_vpn_peer_configuration(peer_id).await.unwrap();
+
+        assert_that!(first, eq(&second));
+        let VpnPeerConfiguration::WireGuard { address: IpAddr::V4(address), prefix_length, .. } = first else { panic!("Expected WireGuard configuration with IPv4 address.") };
+        assert_that!(prefix_length, eq(10));
+        assert_that!(u32::from(address) & network_mask(10), eq(u32::from(Ipv4Addr::new(100, 64, 0, 0))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_allocate_distinct_addresses_to_peers() -> Result<()> {
+        let client = client(Arc::new(InMemoryPeerStore::default()));
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+
+        client.create_peer(peer_a).await.unwrap();
+        client.create_peer(peer_b).await.unwrap();
+
+        assert_that!(client.allocation(peer_a).await.unwrap().address, eq(Ipv4Addr::new(100, 64, 0, 1)));
+        assert_that!(client.allocation(peer_b).await.unwrap().address, eq(Ipv4Addr::new(100, 64, 0, 2)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reuse_the_address_of_a_deleted_peer() -> Result<()> {
+        let peer_store = Arc::new(InMemoryPeerStore::default());
+        let client = client_in_network(Arc::clone(&peer_store), 30);
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+        let peer_c = PeerId::random();
+
+        client.create_peer(peer_a).await.unwrap();
+        client.create_peer(peer_b).await.unwrap();
+
+        let result = client.create_peer(peer_c).await;
+        assert!(matches!(result, Err(CreatePeerError::CreationFailure { .. })));
+
+        client.delete_peer(peer_a).await.unwrap();
+        client.create_peer(peer_c).await.unwrap();
+
+        assert_that!(client.allocation(peer_c).await.unwrap().address, eq(Ipv4Addr::new(100, 64, 0, 1)));
+        assert_that!(peer_store.list().await.unwrap().len(), eq(2));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_rotate_the_keys_of_a_peer_when_it_is_recreated() -> Result<()> {
+        let client = client(Arc::new(InMemoryPeerStore::default()));
+        let peer_id = PeerId::random();
+
+        client.create_peer(peer_id).await.unwrap();
+        let VpnPeerConfiguration::WireGuard { private_key: previous_private_key, .. } = client.generate_vpn_peer_configuration(peer_id).await.unwrap() else { panic!("Expected WireGuard configuration.") };
+        let previous_public_key = client.public_key(&client.allocation(peer_id).await.unwrap());
+
+        client.delete_peer(peer_id).await.unwrap();
+        client.create_peer(peer_id).await.unwrap();
+        let VpnPeerConfiguration::WireGuard { private_key, .. } = client.generate_vpn_peer_configuration(peer_id).await.unwrap() else { panic!("Expected WireGuard configuration.") };
+        let public_key = client.public_key(&client.allocation(peer_id).await.unwrap());
+
+        assert_that!(private_key, not(eq(&previous_private_key)));
+        assert_that!(public_key, not(eq(previous_public_key)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_generate_the_other_peers_of_a_cluster() -> Result<()> {
+        let client = client(Arc::new(InMemoryPeerStore::default()));
+        let cluster_id = ClusterId::random();
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+        let peer_b_host = IpAddr::from_str("192.168.56.12")?;
+
+        client.create_cluster(cluster_id, &[peer_a, peer_b], &ClusterTraffic::default()).await.unwrap();
+        client.update_peer_endpoint(peer_b, peer_b_host).await;
+        let peer_b_allocation = client.allocation(peer_b).await.unwrap();
+
+        let wireguard_peers = client.generate_wireguard_peers(cluster_id, peer_a, &[peer_a, peer_b]).await.unwrap();
+
+        assert_that!(wireguard_peers, elements_are![eq(&WireGuardPeer {
+            public_key: client.public_key(&peer_b_allocation),
+            allowed_ip: IpAddr::V4(peer_b_allocation.address),
+            endpoint: Some(SocketAddr::new(peer_b_host, 51820)),
+        })]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_generate_the_other_peers_of_a_cluster_after_a_restart() -> Result<()> {
+        let peer_store = Arc::new(InMemoryPeerStore::default());
+        let cluster_id = ClusterId::random();
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+        let peer_b_host = IpAddr::from_str("192.168.56.12")?;
+
+        let client_before_restart = client(Arc::clone(&peer_store));
+        client_before_restart.create_peer(peer_b).await.unwrap();
+        client_before_restart.create_peer(peer_a).await.unwrap();
+        client_before_restart.create_cluster(cluster_id, &[peer_a, peer_b], &ClusterTraffic::default()).await.unwrap();
+        let peer_b_allocation = client_before_restart.allocation(peer_b).await.unwrap();
+
+        let client = client(Arc::clone(&peer_store));
+        client.update_peer_endpoint(peer_b, peer_b_host).await;
+        client.delete_cluster(cluster_id).await.unwrap();
+
+        let wireguard_peers = client.generate_wireguard_peers(cluster_id, peer_a, &[peer_a, peer_b]).await.unwrap();
+
+        assert_that!(wireguard_peers, elements_are![eq(&WireGuardPeer {
+            public_key: client_before_restart.public_key(&peer_b_allocation),
+            allowed_ip: IpAddr::V4(peer_b_allocation.address),
+            endpoint: Some(SocketAddr::new(peer_b_host, 51820)),
+        })]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_a_network_address_with_host_bits() {
+        let result = WireGuardManagementClient::create(WireGuardManagementClientConfiguration {
+            key_secret: String::from("secret"),
+            network_address: Ipv4Addr::new(100, 64, 0, 1),
+            network_prefix_length: 10,
+            listen_port: 51820,
+        }, Arc::new(InMemoryPeerStore::default()));
+
+        assert!(matches!(result, Err(Error::Configuration { .. })));
+    }
+}
@@ -1,13 +1,32 @@
+use std::net::IpAddr;
+
 use async_trait::async_trait;
 
 use opendut_types::cluster::ClusterId;
 use opendut_types::peer::PeerId;
 use opendut_types::vpn::VpnPeerConfiguration;
+use opendut_types::vpn::wireguard::WireGuardPeer;
+
+/// Traffic between the peers of a cluster, which VPNs with access control need to allow.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClusterTraffic {
+    /// Whether Ethernet frames are tunneled between the peers via GRE.
+    pub ethernet: bool,
+    /// UDP ports, on which the peers serve CAN tunnels to the other peers.
+    pub can_server_ports: Vec<u16>,
+}
+
+/// Resources of openDuT, which exist in the VPN service.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VpnResources {
+    pub clusters: Vec<ClusterId>,
+    pub peers: Vec<PeerId>,
+}
 
 #[async_trait]
 pub trait VpnManagementClient {
 
-    async fn create_cluster(&self, cluster_id: ClusterId, peers: &[PeerId]) -> Result<(), CreateClusterError>;
+    async fn create_cluster(&self, cluster_id: ClusterId, peers: &[PeerId], traffic: &ClusterTraffic) -> Result<(), CreateClusterError>;
 
     async fn delete_cluster(&self, cluster_id: ClusterId) -> Result<(), DeleteClusterError>;
 
@@ -16,6 +35,23 @@ pub trait VpnManagementClient {
     async fn delete_peer(&self, peer_id: PeerId) -> Result<(), DeletePeerError>;
 
     async fn generate_vpn_peer_configuration(&self, peer_id: PeerId) -> Result<VpnPeerConfiguration, CreateVpnPeerConfigurationError>;
+
+    /// Remembers the address, under which the given peer is reachable by other peers.
+    /// Only needed by VPNs, which do not discover this themselves. Does nothing by default.
+    async fn update_peer_endpoint(&self, _peer_id: PeerId, _endpoint: IpAddr) {}
+
+    /// Generates the WireGuard peers of a cluster, to which the given peer establishes tunnels.
+    /// The peers of the cluster are passed by CARL, so no state of previous deployments is needed.
+    /// Only needed by VPNs, whose tunnels are configured by EDGAR itself. Returns no peers by default.
+    async fn generate_wireguard_peers(&self, _cluster_id: ClusterId, _peer_id: PeerId, _cluster_peers: &[PeerId]) -> Result<Vec<WireGuardPeer>, CreateVpnPeerConfigurationError> {
+        Ok(Vec::new())
+    }
+
+    /// Lists the clusters and peers, which exist in the VPN service, to detect differences to the state of CARL.
+    /// Returns `None` for VPNs, which keep no state of their own that could drift. Returns `None` by default.
+    async fn list_resources(&self) -> Result<Option<VpnResources>, ListResourcesError> {
+        Ok(None)
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -83,3 +119,11 @@ pub enum CreateVpnPeerConfigurationError {
         error: Box<dyn std::error::Error>
     },
 }
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListResourcesError {
+    #[error("An error occurred while listing the resources of the VPN service:\n  {error}")]
+    ListingFailure {
+        error: Box<dyn std::error::Error>
+    },
+}
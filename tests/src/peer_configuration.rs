@@ -65,6 +65,7 @@ async fn carl_should_send_peer_configurations_in_happy_flow() -> anyhow::Result<
                         }),
                     })
                 ),
+                wireguard_peers: empty(),
             }));
             Ok::<_, anyhow::Error>(())
         };
@@ -159,6 +160,7 @@ async fn carl_should_send_cluster_related_peer_configuration_if_a_peer_comes_onl
                         }),
                     })
                 ),
+                wireguard_peers: empty(),
             }));
             Ok::<_, anyhow::Error>(())
         };
@@ -209,6 +211,7 @@ impl Fixture {
         let empty_peer_configuration = PeerConfiguration {
             executors: vec![],
             ethernet_bridges: vec![],
+            wireguard_peers: vec![],
         };
         let empty_old_peer_configuration = OldPeerConfiguration { cluster_assignment: None };
 
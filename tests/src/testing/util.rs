@@ -45,8 +45,9 @@ pub fn spawn_carl() -> anyhow::Result<Port> {
 
 pub async fn spawn_edgar_with_default_behavior(peer_id: PeerId, carl_port: Port) -> anyhow::Result<()> {
     let receiver = spawn_edgar_with_peer_configuration_receiver(peer_id, carl_port).await?;
+    let edgar_config = load_edgar_config(carl_port, peer_id)?;
 
-    opendut_edgar::testing::service::peer_configuration::spawn_peer_configurations_handler(receiver.inner).await.unwrap();
+    opendut_edgar::testing::service::peer_configuration::spawn_peer_configurations_handler(receiver.inner, &edgar_config.config).await.unwrap();
     Ok(())
 }
 